@@ -0,0 +1,112 @@
+//! Benchmarks running SWFs in a headless Ruffle instance.
+//!
+//! The SWFs are shared with the regression tests, which check their output.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::input::NullInputBackend;
+use ruffle_core::backend::locale::NullLocaleBackend;
+use ruffle_core::backend::log::NullLogBackend;
+use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::NullRenderer;
+use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::NullUiBackend;
+use ruffle_core::backend::video::NullVideoBackend;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::{GotoSnapshotSettings, Player};
+use std::sync::{Arc, Mutex};
+
+/// Creates a player with null backends, and loads an SWF into it.
+fn load_swf(swf_path: &str) -> Arc<Mutex<Player>> {
+    let movie = SwfMovie::from_path(swf_path).unwrap();
+    let player = Player::new(
+        Box::new(NullRenderer),
+        Box::new(NullAudioBackend::new()),
+        Box::new(NullNavigatorBackend::new()),
+        Box::new(NullInputBackend::new()),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(NullLogBackend::new()),
+        Box::new(NullUiBackend::new()),
+        Box::new(NullVideoBackend::new()),
+    )
+    .unwrap();
+    player.lock().unwrap().set_root_movie(Arc::new(movie));
+    player
+}
+
+/// Scrubs backwards through a 1000 frame timeline, with and without
+/// snapshots.
+fn goto_rewind_snapshots(c: &mut Criterion) {
+    let mut group = c.benchmark_group("goto_rewind_snapshots");
+    for &(label, interval) in &[("no snapshots", 0), ("default", 50), ("every frame", 1)] {
+        let settings = GotoSnapshotSettings {
+            interval,
+            ..Default::default()
+        };
+        group.bench_function(label, |b| {
+            b.iter_batched(
+                || {
+                    let player = load_swf("tests/swfs/avm1/goto_rewind_snapshots/test.swf");
+                    player.lock().unwrap().set_goto_snapshots(settings);
+                    player
+                },
+                |player| player.lock().unwrap().run_frame(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Loops a clip with fifty children through its ten frames twenty times.
+fn timeline_loop(c: &mut Criterion) {
+    c.bench_function("timeline_loop", |b| {
+        b.iter_batched(
+            || load_swf("tests/swfs/avm1/timeline_loop/test.swf"),
+            |player| {
+                for _ in 0..200 {
+                    player.lock().unwrap().run_frame();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Hit tests a star with five hundred edges at a thousand points on each of
+/// ten frames.
+fn hittest_shapeflag(c: &mut Criterion) {
+    c.bench_function("hittest_shapeflag", |b| {
+        b.iter_batched(
+            || load_swf("tests/swfs/avm1/hittest_shapeflag_benchmark/test.swf"),
+            |player| {
+                for _ in 0..15 {
+                    player.lock().unwrap().run_frame();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Renders two hundred text fields that have already been laid out.
+fn edittext_layout_cache(c: &mut Criterion) {
+    let player = load_swf("tests/swfs/avm1/edittext_layout_cache_benchmark/test.swf");
+    for _ in 0..12 {
+        player.lock().unwrap().run_frame();
+    }
+
+    c.bench_function("edittext_layout_cache", |b| {
+        b.iter(|| player.lock().unwrap().render())
+    });
+}
+
+criterion_group!(
+    benches,
+    goto_rewind_snapshots,
+    timeline_loop,
+    hittest_shapeflag,
+    edittext_layout_cache
+);
+criterion_main!(benches);
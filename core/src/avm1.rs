@@ -114,6 +114,11 @@ pub struct Avm1<'gc> {
     /// Used to prevent scrolling on web.
     has_mouse_listener: bool,
 
+    /// `Sound` objects whose last started sound is still playing.
+    ///
+    /// These are sent `onSoundComplete` once their sound finishes.
+    playing_sounds: Vec<SoundObject<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -127,6 +132,7 @@ unsafe impl<'gc> gc_arena::Collect for Avm1<'gc> {
         self.prototypes.trace(cc);
         self.display_properties.trace(cc);
         self.stack.trace(cc);
+        self.playing_sounds.trace(cc);
 
         for register in &self.registers {
             register.trace(cc);
@@ -155,6 +161,7 @@ impl<'gc> Avm1<'gc> {
             halted: false,
             max_recursion_depth: 255,
             has_mouse_listener: false,
+            playing_sounds: Vec::new(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -379,6 +386,61 @@ impl<'gc> Avm1<'gc> {
         self.has_mouse_listener
     }
 
+    /// Keep track of a `Sound` object that has started playing a sound, so
+    /// that it can be sent `onSoundComplete` once the sound finishes.
+    pub fn add_playing_sound(&mut self, sound: SoundObject<'gc>) {
+        if !self
+            .playing_sounds
+            .iter()
+            .any(|s| Object::ptr_eq((*s).into(), sound.into()))
+        {
+            self.playing_sounds.push(sound);
+        }
+    }
+
+    /// Stop keeping track of the `Sound` objects that match a predicate.
+    ///
+    /// This is used when their sounds are stopped by a script, which doesn't
+    /// count as the sound completing.
+    pub fn remove_playing_sounds(&mut self, mut predicate: impl FnMut(SoundObject<'gc>) -> bool) {
+        self.playing_sounds.retain(|s| !predicate(*s));
+    }
+
+    /// Send `onSoundComplete` to every `Sound` object whose sound has
+    /// finished playing since the last time this was called.
+    pub fn update_playing_sounds(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let audio = &*context.audio;
+        let mut finished = vec![];
+        context.avm1.playing_sounds.retain(|sound| {
+            let is_playing = sound
+                .sound_instance()
+                .map(|instance| audio.is_sound_playing(instance))
+                .unwrap_or(false);
+            if !is_playing {
+                finished.push(*sound);
+            }
+            is_playing
+        });
+
+        for sound in finished {
+            // The position of a finished sound stays at its end.
+            sound.set_position(context.gc_context, sound.duration());
+            sound.set_sound_instance(context.gc_context, None);
+
+            let active_clip = sound.owner().or_else(|| context.levels.get(&0).copied());
+            if let Some(active_clip) = active_clip {
+                Avm1::run_stack_frame_for_method(
+                    active_clip,
+                    sound.into(),
+                    active_clip.swf_version(),
+                    context,
+                    "onSoundComplete",
+                    &[],
+                );
+            }
+        }
+    }
+
     /// Halts the AVM, preventing execution of any further actions.
     ///
     /// If the AVM is currently evaluating an action, it will continue until it realizes that it has
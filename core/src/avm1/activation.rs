@@ -10,7 +10,7 @@ use crate::avm1::{
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
-use crate::ecma_conversions::f64_to_wrapping_u32;
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use crate::tag_utils::SwfSlice;
 use crate::vminterface::Instantiator;
 use crate::{avm_error, avm_warn};
@@ -1206,13 +1206,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             let url = url.to_string();
             match target[6..].parse::<u32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = self
+                        .context
+                        .navigator
+                        .fetch_body(&url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     if url.is_empty() {
                         //Blank URL on movie loads = unload!
                         if let Some(mut mc) = level.as_movie_clip() {
-                            mc.replace_with_movie(self.context.gc_context, None)
+                            mc.replace_with_movie(&mut self.context, None)
                         }
                     } else {
                         let process = self.context.load_manager.load_movie_into_clip(
@@ -1307,10 +1310,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 if url.is_empty() {
                     //Blank URL on movie loads = unload!
                     if let Some(mut mc) = clip_target.as_movie_clip() {
-                        mc.replace_with_movie(self.context.gc_context, None)
+                        mc.replace_with_movie(&mut self.context, None)
                     }
                 } else {
-                    let fetch = self.context.navigator.fetch(&url, opts);
+                    let fetch = self.context.navigator.fetch_body(&url, opts);
                     let process = self.context.load_manager.load_movie_into_clip(
                         self.context.player.clone().unwrap(),
                         clip_target,
@@ -1326,7 +1329,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // target of `_level#` indicates a `loadMovieNum` call.
             match window_target[6..].parse::<u32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = self
+                        .context
+                        .navigator
+                        .fetch_body(&url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     let process = self.context.load_manager.load_movie_into_clip(
@@ -1996,6 +2002,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_stop_sounds(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.audio.stop_all_sounds();
+        self.context.avm1.remove_playing_sounds(|_| true);
         Ok(FrameControl::Continue)
     }
 
@@ -2168,12 +2175,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_wait_for_frame(
         &mut self,
-        _frame: u16,
+        frame: u16,
         num_actions_to_skip: u8,
         r: &mut Reader<'_>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let loaded = true;
+        // The frame index is 0-based.
+        let loaded = self.is_frame_loaded(i32::from(frame) + 1);
         if !loaded {
             // Note that the offset is given in # of actions, NOT in bytes.
             // Read the actions and toss them away.
@@ -2187,9 +2194,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         num_actions_to_skip: u8,
         r: &mut Reader<'_>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let _frame_num = self.context.avm1.pop().coerce_to_f64(self)? as u16;
-        let loaded = true;
+        // Param can either be a 1-based frame number or a frame label.
+        let frame = self.context.avm1.pop();
+        let frame = if let Value::Number(frame) = frame {
+            f64_to_wrapping_i32(frame)
+        } else {
+            let label = frame.coerce_to_string(self)?;
+            self.target_clip()
+                .and_then(|clip| clip.as_movie_clip())
+                .map_or(0, |clip| {
+                    // A missing label waits for the whole clip.
+                    let frame = clip.frame_label_to_number(&label);
+                    i32::from(frame.unwrap_or_else(|| clip.total_frames()))
+                })
+        };
+        let loaded = self.is_frame_loaded(frame);
         if !loaded {
             // Note that the offset is given in # of actions, NOT in bytes.
             // Read the actions and toss them away.
@@ -2198,6 +2217,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Returns whether the given 1-based frame of the target clip has finished loading.
+    ///
+    /// Frames past the end of the clip are treated as its last frame, and
+    /// targets that are not movie clips are always considered loaded.
+    fn is_frame_loaded(&self, frame: i32) -> bool {
+        if let Some(clip) = self.target_clip().and_then(|clip| clip.as_movie_clip()) {
+            let frame = frame.min(i32::from(clip.total_frames()));
+            frame <= i32::from(clip.frames_loaded())
+        } else {
+            true
+        }
+    }
+
     #[allow(unused_variables)]
     fn action_throw(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm1.pop();
@@ -12,6 +12,7 @@ use std::f64;
 mod array;
 pub(crate) mod as_broadcaster;
 mod bevel_filter;
+mod bitmap_data;
 mod bitmap_filter;
 mod blur_filter;
 pub(crate) mod boolean;
@@ -20,13 +21,16 @@ mod color;
 mod color_transform;
 pub(crate) mod context_menu;
 pub(crate) mod context_menu_item;
+mod convolution_filter;
 mod date;
+mod displacement_map_filter;
 pub(crate) mod display_object;
 pub(crate) mod error;
 mod external_interface;
 mod function;
 mod key;
 mod load_vars;
+pub(crate) mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -48,6 +52,7 @@ pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
 mod transform;
+mod video;
 mod xml;
 
 pub fn random<'gc>(
@@ -345,7 +350,14 @@ pub struct SystemPrototypes<'gc> {
     pub blur_filter_constructor: Object<'gc>,
     pub bevel_filter: Object<'gc>,
     pub bevel_filter_constructor: Object<'gc>,
+    pub convolution_filter: Object<'gc>,
+    pub convolution_filter_constructor: Object<'gc>,
+    pub displacement_map_filter: Object<'gc>,
+    pub displacement_map_filter_constructor: Object<'gc>,
+    pub bitmap_data: Object<'gc>,
+    pub bitmap_data_constructor: Object<'gc>,
     pub date: Object<'gc>,
+    pub video: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -373,6 +385,8 @@ pub fn create_globals<'gc>(
     let text_format_proto: Object<'gc> =
         text_format::create_proto(gc_context, object_proto, function_proto);
 
+    let video_proto: Object<'gc> = video::create_proto(gc_context, object_proto, function_proto);
+
     let array_proto: Object<'gc> = array::create_proto(gc_context, object_proto, function_proto);
 
     let color_proto: Object<'gc> = color::create_proto(gc_context, object_proto, function_proto);
@@ -390,6 +404,8 @@ pub fn create_globals<'gc>(
         boolean::create_proto(gc_context, object_proto, function_proto);
     let load_vars_proto: Object<'gc> =
         load_vars::create_proto(gc_context, object_proto, function_proto);
+    let local_connection_proto: Object<'gc> =
+        local_connection::create_proto(gc_context, object_proto, function_proto);
     let matrix_proto: Object<'gc> = matrix::create_proto(gc_context, object_proto, function_proto);
     let point_proto: Object<'gc> = point::create_proto(gc_context, object_proto, function_proto);
     let rectangle_proto: Object<'gc> =
@@ -459,6 +475,12 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         load_vars_proto,
     );
+    let local_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(local_connection::constructor),
+        Some(function_proto),
+        local_connection_proto,
+    );
     let movie_clip = FunctionObject::constructor(
         gc_context,
         Executable::Native(movie_clip::constructor),
@@ -484,6 +506,12 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         text_format_proto,
     );
+    let video = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(video::constructor),
+        Some(function_proto),
+        video_proto,
+    );
     let array = array::create_array_object(gc_context, array_proto, Some(function_proto));
     let xmlnode = FunctionObject::constructor(
         gc_context,
@@ -564,6 +592,24 @@ pub fn create_globals<'gc>(
         bevel_filter_proto,
     );
 
+    let convolution_filter_proto =
+        convolution_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let convolution_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(convolution_filter::constructor),
+        Some(function_proto),
+        convolution_filter_proto,
+    );
+
+    let displacement_map_filter_proto =
+        displacement_map_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let displacement_map_filter = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(displacement_map_filter::constructor),
+        Some(function_proto),
+        displacement_map_filter_proto,
+    );
+
     filters.define_value(
         gc_context,
         "BitmapFilter",
@@ -582,6 +628,31 @@ pub fn create_globals<'gc>(
         bevel_filter.into(),
         EnumSet::empty(),
     );
+    filters.define_value(
+        gc_context,
+        "ConvolutionFilter",
+        convolution_filter.into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "DisplacementMapFilter",
+        displacement_map_filter.into(),
+        EnumSet::empty(),
+    );
+
+    let display = ScriptObject::object(gc_context, Some(object_proto));
+    let bitmap_data_proto = bitmap_data::create_proto(gc_context, object_proto, function_proto);
+    let bitmap_data =
+        bitmap_data::create_bitmap_data_object(gc_context, bitmap_data_proto, Some(function_proto));
+
+    flash.define_value(gc_context, "display", display.into(), EnumSet::empty());
+    display.define_value(
+        gc_context,
+        "BitmapData",
+        bitmap_data.into(),
+        EnumSet::empty(),
+    );
 
     let external = ScriptObject::object(gc_context, Some(object_proto));
     let external_interface = external_interface::create_external_interface_object(
@@ -613,6 +684,12 @@ pub fn create_globals<'gc>(
     globals.define_value(gc_context, "Object", object.into(), DontEnum.into());
     globals.define_value(gc_context, "Function", function.into(), DontEnum.into());
     globals.define_value(gc_context, "LoadVars", load_vars.into(), DontEnum.into());
+    globals.define_value(
+        gc_context,
+        "LocalConnection",
+        local_connection.into(),
+        DontEnum.into(),
+    );
     globals.define_value(gc_context, "MovieClip", movie_clip.into(), DontEnum.into());
     globals.define_value(
         gc_context,
@@ -628,6 +705,7 @@ pub fn create_globals<'gc>(
         text_format.into(),
         DontEnum.into(),
     );
+    globals.define_value(gc_context, "Video", video.into(), DontEnum.into());
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), DontEnum.into());
     globals.define_value(gc_context, "XML", xml.into(), DontEnum.into());
     globals.define_value(gc_context, "String", string.into(), DontEnum.into());
@@ -866,7 +944,14 @@ pub fn create_globals<'gc>(
             blur_filter_constructor: blur_filter,
             bevel_filter: bevel_filter_proto,
             bevel_filter_constructor: bevel_filter,
+            convolution_filter: convolution_filter_proto,
+            convolution_filter_constructor: convolution_filter,
+            displacement_map_filter: displacement_map_filter_proto,
+            displacement_map_filter_constructor: displacement_map_filter,
+            bitmap_data: bitmap_data_proto,
+            bitmap_data_constructor: bitmap_data,
             date: date_proto,
+            video: video_proto,
         },
         globals.into(),
         broadcaster_functions,
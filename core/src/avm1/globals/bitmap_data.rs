@@ -0,0 +1,396 @@
+//! flash.display.BitmapData object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::point::value_to_point;
+use crate::avm1::object::bitmap_data::BitmapDataObject;
+use crate::avm1::{Object, TObject, Value};
+use crate::avm_warn;
+use crate::bitmap_data::BitmapDataStorage;
+use crate::character::Character;
+use crate::display_object::TDisplayObject;
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+/// The largest width or height a `BitmapData` may have.
+const MAX_DIMENSION: i32 = 2880;
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let width = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let height = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let transparent = args
+        .get(2)
+        .unwrap_or(&true.into())
+        .as_bool(activation.current_swf_version());
+    let fill_color = args
+        .get(3)
+        .unwrap_or(&0xFFFF_FFFFu32.into())
+        .coerce_to_u32(activation)?;
+
+    if width <= 0 || height <= 0 || width > MAX_DIMENSION || height > MAX_DIMENSION {
+        avm_warn!(activation, "BitmapData: Invalid size {}x{}", width, height);
+        return Ok(Value::Undefined);
+    }
+
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        *bitmap_data.storage_mut(activation.context.gc_context) =
+            BitmapDataStorage::new(width as u32, height as u32, transparent, fill_color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_bitmap_data_object()
+        .unwrap()
+        .storage()
+        .width()
+        .into())
+}
+
+pub fn get_height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_bitmap_data_object()
+        .unwrap()
+        .storage()
+        .height()
+        .into())
+}
+
+pub fn get_transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_bitmap_data_object()
+        .unwrap()
+        .storage()
+        .transparent()
+        .into())
+}
+
+/// Coerce the `x` and `y` arguments of a pixel method.
+fn pixel_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(i32, i32), Error<'gc>> {
+    let x = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let y = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    Ok((x, y))
+}
+
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x, y) = pixel_position(activation, args)?;
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        return Ok((bitmap_data.storage().get_pixel32(x, y) & 0x00FF_FFFF).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x, y) = pixel_position(activation, args)?;
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        // AVM1 numbers are signed, so opaque pixels come out negative.
+        return Ok((bitmap_data.storage().get_pixel32(x, y) as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x, y) = pixel_position(activation, args)?;
+    let color = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        bitmap_data
+            .storage_mut(activation.context.gc_context)
+            .set_pixel(x, y, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x, y) = pixel_position(activation, args)?;
+    let color = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        bitmap_data
+            .storage_mut(activation.context.gc_context)
+            .set_pixel32(x, y, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.applyFilter`, which returns 0 on success and a
+/// negative number when the filter could not be applied.
+pub fn apply_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let source = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation);
+    let source_rect = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation);
+    let dest_point = args.get(2).cloned().unwrap_or(Value::Undefined);
+    let filter = args
+        .get(3)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation);
+
+    let source = match source.as_bitmap_data_object() {
+        Some(source) => source,
+        None => return Ok((-1).into()),
+    };
+    let mut rect = [0; 4];
+    for (value, name) in rect.iter_mut().zip(&["x", "y", "width", "height"]) {
+        *value = source_rect
+            .get(name, activation)?
+            .coerce_to_i32(activation)?;
+    }
+    let (dest_x, dest_y) = value_to_point(dest_point, activation)?;
+
+    let filter = if let Some(blur) = filter.as_blur_filter_object() {
+        swf::Filter::BlurFilter(Box::new(blur.filter()))
+    } else if let Some(bevel) = filter.as_bevel_filter_object() {
+        swf::Filter::BevelFilter(Box::new(bevel.filter()))
+    } else if let Some(convolution) = filter.as_convolution_filter_object() {
+        swf::Filter::ConvolutionFilter(Box::new(convolution.filter()))
+    } else {
+        avm_warn!(activation, "BitmapData.applyFilter: Unsupported filter");
+        return Ok((-1).into());
+    };
+
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        // The source may be this same image, so read it before writing.
+        let source = source.storage().clone();
+        bitmap_data
+            .storage_mut(activation.context.gc_context)
+            .apply_filter(
+                &source,
+                (rect[0], rect[1], rect[2], rect[3]),
+                (dest_x as i32, dest_y as i32),
+                &filter,
+            );
+        return Ok(0.into());
+    }
+
+    Ok((-1).into())
+}
+
+/// Implements `BitmapData.loadBitmap`, which creates a `BitmapData` from a
+/// bitmap exported from the library.
+pub fn load_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let bitmap = activation
+        .base_clip()
+        .movie()
+        .and_then(|movie| activation.context.library.library_for_movie(movie))
+        .and_then(|library| library.get_character_by_export_name(&name))
+        .and_then(|character| match character {
+            Character::Bitmap(bitmap) => Some(*bitmap),
+            _ => None,
+        });
+    let bitmap = match bitmap {
+        Some(bitmap) => bitmap,
+        None => {
+            avm_warn!(
+                activation,
+                "BitmapData.loadBitmap: Bitmap '{}' not found",
+                name
+            );
+            return Ok(Value::Undefined);
+        }
+    };
+
+    let storage = match bitmap.decode_pixels() {
+        Ok(storage) => storage,
+        Err(e) => {
+            log::error!("BitmapData.loadBitmap: Could not decode '{}': {}", name, e);
+            return Ok(Value::Undefined);
+        }
+    };
+
+    let bitmap_data = BitmapDataObject::empty_object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.bitmap_data),
+    );
+    *bitmap_data.storage_mut(activation.context.gc_context) = storage;
+
+    Ok(bitmap_data.into())
+}
+
+pub fn create_bitmap_data_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    bitmap_data_proto: Object<'gc>,
+    fn_proto: Option<Object<'gc>>,
+) -> Object<'gc> {
+    let bitmap_data = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(constructor),
+        fn_proto,
+        bitmap_data_proto,
+    );
+    let mut object = bitmap_data.as_script_object().unwrap();
+
+    object.force_set_function(
+        "loadBitmap",
+        load_bitmap,
+        gc_context,
+        EnumSet::empty(),
+        fn_proto,
+    );
+
+    bitmap_data
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let bitmap_data = BitmapDataObject::empty_object(gc_context, Some(proto));
+    let mut object = bitmap_data.as_script_object().unwrap();
+
+    object.add_property(
+        gc_context,
+        "width",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_width),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        EnumSet::empty(),
+    );
+
+    object.add_property(
+        gc_context,
+        "height",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_height),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        EnumSet::empty(),
+    );
+
+    object.add_property(
+        gc_context,
+        "transparent",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_transparent),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        EnumSet::empty(),
+    );
+
+    object.force_set_function(
+        "applyFilter",
+        apply_filter,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getPixel",
+        get_pixel,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getPixel32",
+        get_pixel32,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setPixel",
+        set_pixel,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setPixel32",
+        set_pixel32,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    bitmap_data.into()
+}
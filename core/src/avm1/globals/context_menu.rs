@@ -4,6 +4,9 @@ use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::Object;
 use crate::avm1::{ScriptObject, Value};
+use crate::backend::ui::ContextMenuItem;
+use crate::context_menu::{BuiltInItemFlags, ContextMenuCallback, ContextMenuState};
+use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
 pub fn constructor<'gc>(
@@ -130,6 +133,74 @@ pub fn hide_builtin_items<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Build the menu that the host shows for a right click on `target`.
+///
+/// The visible custom items of `menu` come first, followed by the built-in
+/// items that it has not hidden. Without a menu, only the built-in items are
+/// shown.
+pub fn make_context_menu_state<'gc>(
+    menu: Option<Object<'gc>>,
+    target: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ContextMenuState<'gc>, Error<'gc>> {
+    let swf_version = activation.swf_version();
+    let mut result = ContextMenuState::new();
+    let mut builtin_items = BuiltInItemFlags::default();
+
+    if let Some(menu) = menu {
+        if let Value::Object(built_in) = menu.get("builtInItems", activation)? {
+            builtin_items = BuiltInItemFlags {
+                play: built_in.get("play", activation)?.as_bool(swf_version),
+                rewind: built_in.get("rewind", activation)?.as_bool(swf_version),
+                forward_and_back: built_in
+                    .get("forward_back", activation)?
+                    .as_bool(swf_version),
+            };
+        }
+
+        if let Value::Object(custom_items) = menu.get("customItems", activation)? {
+            for i in 0..custom_items.length() {
+                let item = match custom_items.array_element(i) {
+                    Value::Object(item) => item,
+                    _ => continue,
+                };
+                if !item.get("visible", activation)?.as_bool(swf_version) {
+                    continue;
+                }
+
+                let caption = item
+                    .get("caption", activation)?
+                    .coerce_to_string(activation)?
+                    .to_string();
+                let enabled = item.get("enabled", activation)?.as_bool(swf_version);
+                let separator_before = item
+                    .get("separatorBefore", activation)?
+                    .as_bool(swf_version);
+                result.push(
+                    ContextMenuItem {
+                        caption,
+                        enabled,
+                        separator_before,
+                        checked: false,
+                    },
+                    ContextMenuCallback::Avm1 { item, target },
+                );
+            }
+        }
+    }
+
+    let root = activation
+        .context
+        .levels
+        .get(&0)
+        .and_then(|root| root.as_movie_clip());
+    if let Some(root) = root {
+        result.build_builtin_items(builtin_items, root);
+    }
+
+    Ok(result)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
@@ -0,0 +1,465 @@
+//! flash.filter.ConvolutionFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_matrix_x(activation, this, args.get(0..1).unwrap_or(&[]))?;
+    set_matrix_y(activation, this, args.get(1..2).unwrap_or(&[]))?;
+    set_matrix(activation, this, args.get(2..3).unwrap_or(&[]))?;
+    set_divisor(activation, this, args.get(3..4).unwrap_or(&[]))?;
+    set_bias(activation, this, args.get(4..5).unwrap_or(&[]))?;
+    set_preserve_alpha(activation, this, args.get(5..6).unwrap_or(&[]))?;
+    set_clamp(activation, this, args.get(6..7).unwrap_or(&[]))?;
+    set_color(activation, this, args.get(7..8).unwrap_or(&[]))?;
+    set_alpha(activation, this, args.get(8..9).unwrap_or(&[]))?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_matrix_x()
+        .into())
+}
+
+pub fn set_matrix_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix_x = args
+        .get(0)
+        .unwrap_or(&0.into())
+        .coerce_to_i32(activation)
+        .map(|x| x.max(0).min(15) as u8)?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_matrix_x(activation.context.gc_context, matrix_x);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_matrix_y()
+        .into())
+}
+
+pub fn set_matrix_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix_y = args
+        .get(0)
+        .unwrap_or(&0.into())
+        .coerce_to_i32(activation)
+        .map(|x| x.max(0).min(15) as u8)?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_matrix_y(activation.context.gc_context, matrix_y);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = this.as_convolution_filter_object().unwrap().get_matrix();
+
+    let array = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+    array.set_length(activation.context.gc_context, matrix.len());
+    for (i, value) in matrix.into_iter().enumerate() {
+        array.set_array_element(i, value.into(), activation.context.gc_context);
+    }
+
+    Ok(array.into())
+}
+
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = match args.get(0) {
+        Some(Value::Object(array)) => (0..array.length())
+            .map(|i| array.array_element(i).coerce_to_f64(activation))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => Vec::new(),
+    };
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_matrix(activation.context.gc_context, matrix);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_divisor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_divisor()
+        .into())
+}
+
+pub fn set_divisor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let divisor = args
+        .get(0)
+        .unwrap_or(&1.0.into())
+        .coerce_to_f64(activation)?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_divisor(activation.context.gc_context, divisor);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_bias<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_bias()
+        .into())
+}
+
+pub fn set_bias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bias = args
+        .get(0)
+        .unwrap_or(&0.0.into())
+        .coerce_to_f64(activation)?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_bias(activation.context.gc_context, bias);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_preserve_alpha<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_preserve_alpha()
+        .into())
+}
+
+pub fn set_preserve_alpha<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let preserve_alpha = args
+        .get(0)
+        .unwrap_or(&true.into())
+        .as_bool(activation.current_swf_version());
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_preserve_alpha(activation.context.gc_context, preserve_alpha);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_clamp<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_clamp()
+        .into())
+}
+
+pub fn set_clamp<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let clamp = args
+        .get(0)
+        .unwrap_or(&true.into())
+        .as_bool(activation.current_swf_version());
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_clamp(activation.context.gc_context, clamp);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_color<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_color()
+        .into())
+}
+
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = args
+        .get(0)
+        .unwrap_or(&0x000000.into())
+        .coerce_to_u32(activation)?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_color(activation.context.gc_context, color & 0xFFFFFF);
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_alpha<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_convolution_filter_object()
+        .unwrap()
+        .get_alpha()
+        .into())
+}
+
+pub fn set_alpha<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let alpha = args
+        .get(0)
+        .unwrap_or(&0.into())
+        .coerce_to_f64(activation)
+        .map(|x| x.max(0.0).min(1.0))?;
+
+    this.as_convolution_filter_object()
+        .unwrap()
+        .set_alpha(activation.context.gc_context, alpha);
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ConvolutionFilterObject::empty_object(gc_context, Some(proto));
+    let script_object = object.as_script_object().unwrap();
+
+    script_object.add_property(
+        gc_context,
+        "matrixX",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_matrix_x),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_matrix_x),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "matrixY",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_matrix_y),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_matrix_y),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "matrix",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_matrix),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_matrix),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "divisor",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_divisor),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_divisor),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "bias",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_bias),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_bias),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "preserveAlpha",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_preserve_alpha),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_preserve_alpha),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "clamp",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_clamp),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_clamp),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "color",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_color),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_color),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+    script_object.add_property(
+        gc_context,
+        "alpha",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_alpha),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_alpha),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        EnumSet::empty(),
+    );
+
+    object.into()
+}
@@ -0,0 +1,239 @@
+//! AVM1 LocalConnection object
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use crate::external::Value as ExternalValue;
+use crate::local_connection::{
+    movie_domain, qualified_name, LocalConnectionMessage, LocalConnectionObject,
+};
+use gc_arena::MutationContext;
+
+/// Implements `LocalConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// The domain of the movie running the current code.
+fn own_domain(activation: &mut Activation<'_, '_, '_>) -> String {
+    let movie = activation.base_clip().movie();
+    movie_domain(movie.as_ref().and_then(|movie| movie.url()))
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => *name,
+        _ => return Ok(false.into()),
+    };
+
+    let domain = own_domain(activation);
+    let name = qualified_name(&domain, &name);
+
+    Ok(activation
+        .context
+        .local_connections
+        .connect(name, LocalConnectionObject::Avm1(this), domain)
+        .into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .local_connections
+        .close(LocalConnectionObject::Avm1(this));
+
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (connection_name, method_name) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(connection_name)), Some(Value::String(method_name))) => {
+            (*connection_name, *method_name)
+        }
+        _ => return Ok(false.into()),
+    };
+
+    let mut arguments = Vec::new();
+    for arg in args.get(2..).unwrap_or(&[]) {
+        arguments.push(ExternalValue::from_avm1(activation, arg.clone())?);
+    }
+
+    let sender_domain = own_domain(activation);
+    activation
+        .context
+        .local_connections
+        .send(LocalConnectionMessage {
+            sender: LocalConnectionObject::Avm1(this),
+            connection_name: qualified_name(&sender_domain, &connection_name),
+            sender_domain,
+            method_name: method_name.to_string(),
+            arguments,
+        });
+
+    Ok(true.into())
+}
+
+pub fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let domain = own_domain(activation);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
+
+/// Run code for a `LocalConnection` from outside of any movie's actions.
+fn run_with_activation<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    name: &'static str,
+    f: impl FnOnce(&mut Activation<'_, 'gc, '_>) -> Result<(), Error<'gc>>,
+) {
+    let base_clip = *context.levels.get(&0).unwrap();
+    let swf_version = context.swf.version();
+    let globals = context.avm1.global_object_cell();
+    let mut activation = Activation::from_nothing(
+        context.reborrow(),
+        ActivationIdentifier::root(name),
+        swf_version,
+        globals,
+        base_clip,
+    );
+
+    if let Err(e) = f(&mut activation) {
+        log::error!("Unhandled AVM1 error in LocalConnection: {}", e);
+    }
+}
+
+/// Deliver a message to a connected `LocalConnection`, by calling the named
+/// method on it.
+///
+/// Messages from other domains are only delivered if the object's
+/// `allowDomain` method returns `true` for the sender's domain. Returns
+/// whether or not the message was delivered.
+pub fn receive_message<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    same_domain: bool,
+    sender_domain: &str,
+    method_name: &str,
+    arguments: Vec<ExternalValue>,
+) -> bool {
+    let mut delivered = false;
+
+    run_with_activation(context, "[LocalConnection]", |activation| {
+        if !same_domain {
+            let domain = AvmString::new(activation.context.gc_context, sender_domain.to_string());
+            let allowed = match this.get("allowDomain", activation)? {
+                Value::Object(_) => this
+                    .call_method("allowDomain", &[domain.into()], activation)?
+                    .as_bool(activation.current_swf_version()),
+                _ => false,
+            };
+
+            if !allowed {
+                return Ok(());
+            }
+        }
+
+        delivered = true;
+
+        let args: Vec<Value<'gc>> = arguments
+            .into_iter()
+            .map(|arg| arg.into_avm1(activation))
+            .collect();
+        this.call_method(method_name, &args, activation)?;
+
+        Ok(())
+    });
+
+    delivered
+}
+
+/// Call the `onStatus` handler of a `LocalConnection` to report whether or
+/// not a message it sent was delivered.
+pub fn notify_status<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    delivered: bool,
+) {
+    run_with_activation(context, "[LocalConnection onStatus]", |activation| {
+        if !matches!(this.get("onStatus", activation)?, Value::Object(_)) {
+            return Ok(());
+        }
+
+        let info = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        let level = if delivered { "status" } else { "error" };
+        info.set("level", level.into(), activation)?;
+
+        this.call_method("onStatus", &[info.into()], activation)?;
+
+        Ok(())
+    });
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "domain",
+        domain,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
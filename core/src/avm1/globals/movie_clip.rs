@@ -7,13 +7,17 @@ use crate::avm1::globals::display_object::{
     self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH, AVM_MAX_REMOVE_DEPTH,
 };
 use crate::avm1::globals::matrix::gradient_object_to_matrix;
+use crate::avm1::object::bevel_filter::BevelFilterObject;
+use crate::avm1::object::blur_filter::BlurFilterObject;
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_error;
 use crate::avm_warn;
 use crate::backend::navigator::NavigationMethod;
 use crate::display_object::{
-    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    blend_mode_from_name, blend_mode_name, DisplayObject, EditText, MovieClip, TDisplayObject,
+    TDisplayObjectContainer,
 };
 use crate::ecma_conversions::f64_to_wrapping_i32;
 use crate::prelude::*;
@@ -212,6 +216,11 @@ pub fn create_proto<'gc>(
         "transform" => [transform, set_transform],
         "enabled" => [enabled, set_enabled],
         "focusEnabled" => [focus_enabled, set_focus_enabled],
+        "scale9Grid" => [scale_9_grid, set_scale_9_grid],
+        "blendMode" => [blend_mode, set_blend_mode],
+        "filters" => [filters, set_filters],
+        "cacheAsBitmap" => [cache_as_bitmap, set_cache_as_bitmap],
+        "opaqueBackground" => [opaque_background, set_opaque_background],
     );
 
     object.into()
@@ -734,18 +743,45 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
 
 fn get_bytes_loaded<'gc>(
     movie_clip: MovieClip<'gc>,
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((movie_clip.movie().unwrap().data().len() + 20).into())
+    let (bytes_loaded, _) = load_progress(movie_clip, activation);
+    Ok(bytes_loaded)
 }
 
 fn get_bytes_total<'gc>(
     movie_clip: MovieClip<'gc>,
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((movie_clip.movie().unwrap().data().len() + 20).into())
+    let (_, bytes_total) = load_progress(movie_clip, activation);
+    Ok(bytes_total)
+}
+
+/// Get how many bytes of the movie in a clip have been loaded, and how many
+/// bytes it has in total.
+///
+/// A movie that is still being downloaded into the clip reports how much of
+/// it has arrived, and an undefined total if its length isn't known yet.
+pub fn load_progress<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> (Value<'gc>, Value<'gc>) {
+    let progress = activation
+        .context
+        .load_manager
+        .movie_load_progress(movie_clip.into());
+    match progress {
+        Some((bytes_loaded, bytes_total)) => (
+            (bytes_loaded as f64).into(),
+            bytes_total.map_or(Value::Undefined, |total| (total as f64).into()),
+        ),
+        None => match movie_clip.movie() {
+            Some(movie) => (movie.bytes_loaded().into(), movie.bytes_total().into()),
+            None => (Value::Undefined, Value::Undefined),
+        },
+    }
 }
 
 fn get_next_highest_depth<'gc>(
@@ -1055,7 +1091,7 @@ fn get_rect<'gc>(
 
 #[allow(unused_must_use)] //can't use errors yet
 pub fn get_url<'gc>(
-    _movie_clip: MovieClip<'gc>,
+    movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
 
     args: &[Value<'gc>],
@@ -1070,17 +1106,24 @@ pub fn get_url<'gc>(
             return Ok(Value::Undefined);
         }
 
-        let window = if let Some(window) = args.get(1) {
-            Some(window.coerce_to_string(activation)?.to_string())
-        } else {
-            None
+        let window = match args.get(1) {
+            Some(Value::Undefined) | Some(Value::Null) | None => None,
+            Some(window) => Some(window.coerce_to_string(activation)?.to_string()),
         };
         let method = match args.get(2) {
-            Some(Value::String(s)) if *s == "GET" => Some(NavigationMethod::GET),
-            Some(Value::String(s)) if *s == "POST" => Some(NavigationMethod::POST),
-            _ => None,
+            Some(method) => {
+                NavigationMethod::from_method_str(&method.coerce_to_string(activation)?)
+            }
+            None => None,
+        };
+        // The clip's variables are sent along if a method is given.
+        let vars_method = match method {
+            Some(method) => {
+                let this = movie_clip.object().coerce_to_object(activation);
+                Some((method, activation.object_into_form_values(this)))
+            }
+            None => None,
         };
-        let vars_method = method.map(|m| (m, activation.locals_into_form_values()));
 
         activation
             .context
@@ -1133,8 +1176,9 @@ fn load_movie<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let this = target.object().coerce_to_object(activation);
+    let (url, opts) = activation.object_into_request_options(this, Cow::Borrowed(&url), method);
+    let fetch = activation.context.navigator.fetch_body(&url, opts);
     let process = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone().unwrap(),
         DisplayObject::MovieClip(target),
@@ -1157,9 +1201,9 @@ fn load_variables<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
     let target = target.object().coerce_to_object(activation);
+    let (url, opts) = activation.object_into_request_options(target, Cow::Borrowed(&url), method);
+    let fetch = activation.context.navigator.fetch(&url, opts);
     let process = activation.context.load_manager.load_form_into_object(
         activation.context.player.clone().unwrap(),
         target,
@@ -1177,7 +1221,7 @@ fn unload_movie<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     target.unload(&mut activation.context);
-    target.replace_with_movie(activation.context.gc_context, None);
+    target.replace_with_movie(&mut activation.context, None);
 
     Ok(Value::Undefined)
 }
@@ -1236,3 +1280,182 @@ fn set_focus_enabled<'gc>(
     );
     Ok(())
 }
+
+fn scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(rect) = this.scaling_grid() {
+        let args = [
+            Value::Number(rect.x_min.to_pixels()),
+            Value::Number(rect.y_min.to_pixels()),
+            Value::Number((rect.x_max - rect.x_min).to_pixels()),
+            Value::Number((rect.y_max - rect.y_min).to_pixels()),
+        ];
+        let constructor = activation.context.avm1.prototypes.rectangle_constructor;
+        let result = constructor.construct(activation, &args)?;
+        Ok(result.into())
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // The rectangle is copied, so later changes to it have no effect.
+    let scaling_grid = match value {
+        Value::Undefined | Value::Null => None,
+        value => {
+            let rect = value.coerce_to_object(activation);
+            let x = rect.get("x", activation)?.coerce_to_f64(activation)?;
+            let y = rect.get("y", activation)?.coerce_to_f64(activation)?;
+            let width = rect.get("width", activation)?.coerce_to_f64(activation)?;
+            let height = rect.get("height", activation)?.coerce_to_f64(activation)?;
+            Some(swf::Rectangle {
+                x_min: Twips::from_pixels(x),
+                y_min: Twips::from_pixels(y),
+                x_max: Twips::from_pixels(x + width),
+                y_max: Twips::from_pixels(y + height),
+            })
+        }
+    };
+    this.set_scaling_grid(activation.context.gc_context, scaling_grid);
+    Ok(())
+}
+
+fn blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = blend_mode_name(this.blend_mode());
+    Ok(AvmString::new(activation.context.gc_context, name.to_string()).into())
+}
+
+fn set_blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // Unknown blend modes are ignored.
+    let name = value.coerce_to_string(activation)?;
+    if let Some(blend_mode) = blend_mode_from_name(&name) {
+        this.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+    Ok(())
+}
+
+fn filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Filters without a script class yet are left out.
+    let gc_context = activation.context.gc_context;
+    let prototypes = &activation.context.avm1.prototypes;
+    let filters: Vec<Object<'gc>> = this
+        .filters()
+        .iter()
+        .filter_map(|filter| match filter {
+            swf::Filter::BlurFilter(blur) => Some(
+                BlurFilterObject::from_filter(gc_context, Some(prototypes.blur_filter), blur)
+                    .into(),
+            ),
+            swf::Filter::BevelFilter(bevel) => Some(
+                BevelFilterObject::from_filter(gc_context, Some(prototypes.bevel_filter), bevel)
+                    .into(),
+            ),
+            swf::Filter::ConvolutionFilter(convolution) => Some(
+                ConvolutionFilterObject::from_filter(
+                    gc_context,
+                    Some(prototypes.convolution_filter),
+                    convolution,
+                )
+                .into(),
+            ),
+            _ => None,
+        })
+        .collect();
+
+    let array = ScriptObject::array(gc_context, Some(prototypes.array));
+    array.set_length(gc_context, filters.len());
+    for (i, filter) in filters.into_iter().enumerate() {
+        array.set_array_element(i, filter.into(), gc_context);
+    }
+    Ok(array.into())
+}
+
+fn set_filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // The filters are copied, so later changes to them have no effect.
+    let array = value.coerce_to_object(activation);
+    let filters = (0..array.length())
+        .filter_map(|i| {
+            let filter = array.array_element(i);
+            let filter = match filter {
+                Value::Object(filter) => filter,
+                _ => return None,
+            };
+            if let Some(blur) = filter.as_blur_filter_object() {
+                Some(swf::Filter::BlurFilter(Box::new(blur.filter())))
+            } else if let Some(bevel) = filter.as_bevel_filter_object() {
+                Some(swf::Filter::BevelFilter(Box::new(bevel.filter())))
+            } else if let Some(convolution) = filter.as_convolution_filter_object() {
+                Some(swf::Filter::ConvolutionFilter(Box::new(
+                    convolution.filter(),
+                )))
+            } else {
+                None
+            }
+        })
+        .collect();
+    this.set_filters(activation.context.gc_context, filters);
+    Ok(())
+}
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Objects with filters are always cached.
+    Ok((this.is_bitmap_cached() || !this.filters().is_empty()).into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let is_bitmap_cached = value.as_bool(activation.current_swf_version());
+    this.set_is_bitmap_cached(activation.context.gc_context, is_bitmap_cached);
+    Ok(())
+}
+
+fn opaque_background<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(color) = this.opaque_background() {
+        let rgb = (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b);
+        Ok(rgb.into())
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_opaque_background<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let color = match value {
+        Value::Undefined | Value::Null => None,
+        value => Some(Color::from_rgb(value.coerce_to_u32(activation)?, 0xFF)),
+    };
+    this.set_opaque_background(activation.context.gc_context, color);
+    Ok(())
+}
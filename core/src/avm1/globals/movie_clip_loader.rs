@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::globals::movie_clip;
 use crate::avm1::object::script_object::ScriptObject;
 use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
@@ -49,7 +50,7 @@ pub fn load_clip<'gc>(
             let fetch = activation
                 .context
                 .navigator
-                .fetch(&url, RequestOptions::get());
+                .fetch_body(&url, RequestOptions::get());
             let process = activation.context.load_manager.load_movie_into_clip(
                 activation.context.player.clone().unwrap(),
                 DisplayObject::MovieClip(movieclip),
@@ -79,8 +80,26 @@ pub fn unload_clip<'gc>(
             .as_display_object()
             .and_then(|dobj| dobj.as_movie_clip())
         {
+            // A load that is still in progress fails with error code 2.
+            let broadcaster = activation
+                .context
+                .load_manager
+                .cancel_movie_load(DisplayObject::MovieClip(movieclip));
+            if let Some(broadcaster) = broadcaster {
+                broadcaster.call_method(
+                    "broadcastMessage",
+                    &[
+                        "onLoadError".into(),
+                        movieclip.object(),
+                        2.into(),
+                        "Load canceled".into(),
+                    ],
+                    activation,
+                )?;
+            }
+
             movieclip.unload(&mut activation.context);
-            movieclip.replace_with_movie(activation.context.gc_context, None);
+            movieclip.replace_with_movie(&mut activation.context, None);
 
             return Ok(true.into());
         }
@@ -101,23 +120,19 @@ pub fn get_progress<'gc>(
             .as_display_object()
             .and_then(|dobj| dobj.as_movie_clip())
         {
+            let (bytes_loaded, bytes_total) = movie_clip::load_progress(movieclip, activation);
+
             let ret_obj = ScriptObject::object(activation.context.gc_context, None);
             ret_obj.define_value(
                 activation.context.gc_context,
                 "bytesLoaded",
-                movieclip
-                    .movie()
-                    .map(|mv| (mv.data().len() + 21).into())
-                    .unwrap_or(Value::Undefined),
+                bytes_loaded,
                 EnumSet::empty(),
             );
             ret_obj.define_value(
                 activation.context.gc_context,
                 "bytesTotal",
-                movieclip
-                    .movie()
-                    .map(|mv| (mv.data().len() + 21).into())
-                    .unwrap_or(Value::Undefined),
+                bytes_total,
                 EnumSet::empty(),
             );
 
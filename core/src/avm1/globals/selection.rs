@@ -2,7 +2,7 @@ use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::property::Attribute;
-use crate::avm1::{Object, ScriptObject, TDisplayObject, TObject, Value};
+use crate::avm1::{AvmString, Object, ScriptObject, TDisplayObject, TObject, Value};
 use crate::display_object::{EditText, TextSelection};
 use gc_arena::MutationContext;
 
@@ -93,6 +93,8 @@ pub fn set_selection<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Selection.getFocus`, which returns the target path of the
+/// focused object.
 pub fn get_focus<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -100,36 +102,44 @@ pub fn get_focus<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let focus = activation.context.focus_tracker.get();
     match focus {
-        Some(focus) => Ok(focus.object()),
+        Some(focus) => Ok(AvmString::new(activation.context.gc_context, focus.path()).into()),
         None => Ok(Value::Null),
     }
 }
 
+/// Implements `Selection.setFocus`, which focuses an object given either as
+/// itself or as a target path.
 pub fn set_focus<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let tracker = activation.context.focus_tracker;
-    match args.get(0) {
+    let display_object = match args.get(0) {
         Some(Value::Null) | Some(Value::Undefined) => {
             tracker.set(None, &mut activation.context);
-            Ok(true.into())
+            return Ok(true.into());
         }
-        Some(Value::Object(obj)) => {
-            if let Some(display_object) = obj.as_display_object() {
-                if display_object.is_focusable() {
-                    tracker.set(Some(display_object), &mut activation.context);
-                }
-                // [NA] Note: The documentation says true is success and false is failure,
-                // but from testing this seems to be opposite.
-                Ok(false.into())
-            } else {
-                Ok(true.into())
+        Some(Value::Object(obj)) => match obj.as_display_object() {
+            Some(display_object) => display_object,
+            None => return Ok(true.into()),
+        },
+        Some(path @ Value::String(_)) => {
+            let start = activation.target_clip_or_root();
+            match activation.resolve_target_display_object(start, path.clone(), false)? {
+                Some(display_object) => display_object,
+                None => return Ok(false.into()),
             }
         }
-        _ => Ok(false.into()),
+        _ => return Ok(false.into()),
+    };
+
+    if display_object.is_focusable() {
+        tracker.set(Some(display_object), &mut activation.context);
     }
+    // [NA] Note: The documentation says true is success and false is failure,
+    // but from testing this seems to be opposite.
+    Ok(false.into())
 }
 
 pub fn create_selection_object<'gc>(
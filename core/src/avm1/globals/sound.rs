@@ -1,5 +1,5 @@
 //! AVM1 Sound object
-//! TODO: Sound position, transform, loadSound
+//! TODO: Sound transform, loadSound
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
@@ -207,6 +207,7 @@ fn attach_sound<'gc>(
                         .unwrap_or(0),
                 );
                 sound_object.set_position(activation.context.gc_context, 0);
+                sound_object.set_id3(activation.context.gc_context, None);
             } else {
                 avm_warn!(activation, "Sound.attachSound: Sound '{}' not found", name);
             }
@@ -292,13 +293,23 @@ fn get_volume<'gc>(
     Ok(100.into())
 }
 
+/// Implements `Sound.id3`.
+///
+/// Only sounds loaded from MP3 files have ID3 tags; sounds attached from the
+/// library never do.
 fn id3<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
-        avm_warn!(activation, "Sound.id3: Unimplemented");
+        if let Some(sound_object) = this.as_sound_object() {
+            if let Some(id3) = sound_object.id3() {
+                return Ok(id3.into());
+            }
+        } else {
+            avm_warn!(activation, "Sound.id3: this is not a Sound");
+        }
     }
     Ok(Value::Undefined)
 }
@@ -314,6 +325,10 @@ fn load_sound<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Sound.position`.
+///
+/// The position is "sticky"; once the sound is no longer playing, the last
+/// position it played at is returned.
 fn position<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -321,13 +336,8 @@ fn position<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
         if let Some(sound_object) = this.as_sound_object() {
-            // TODO: The position is "sticky"; even if the sound is no longer playing, it should return
-            // the previous valid position.
-            // Needs some audio backend work for this.
             if sound_object.sound().is_some() {
-                if let Some(_sound_instance) = sound_object.sound_instance() {
-                    avm_warn!(activation, "Sound.position: Unimplemented");
-                }
+                update_position(activation, sound_object);
                 return Ok(sound_object.position().into());
             }
         } else {
@@ -337,6 +347,27 @@ fn position<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Update the position of a `Sound` object from the sound playing on it, if
+/// it is still playing.
+fn update_position<'gc>(activation: &mut Activation<'_, 'gc, '_>, sound_object: SoundObject<'gc>) {
+    let played = sound_object
+        .sound_instance()
+        .and_then(|instance| activation.context.audio.get_sound_position(instance));
+
+    if let Some(mut played) = played {
+        // Each loop of a sound starts over from where the sound was started.
+        let start_offset = f64::from(sound_object.start_offset());
+        let loop_length = f64::from(sound_object.duration()) - start_offset;
+        if loop_length > 0.0 {
+            played %= loop_length;
+        }
+        sound_object.set_position(
+            activation.context.gc_context,
+            (start_offset + played) as u32,
+        );
+    }
+}
+
 fn set_pan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -402,8 +433,12 @@ fn start<'gc>(
                 },
             );
             if let Ok(sound_instance) = sound_instance {
+                let start_offset = (start_offset.max(0.0) * 1000.0) as u32;
                 sound_object
                     .set_sound_instance(activation.context.gc_context, Some(sound_instance));
+                sound_object.set_start_offset(activation.context.gc_context, start_offset);
+                sound_object.set_position(activation.context.gc_context, start_offset);
+                activation.context.avm1.add_playing_sound(sound_object);
             }
         } else {
             avm_warn!(activation, "Sound.start: No sound is attached");
@@ -436,7 +471,12 @@ fn stop<'gc>(
                     .get_character_by_export_name(&name)
                 {
                     // Stop all sounds with the given name.
-                    activation.context.audio.stop_sounds_with_handle(*sound);
+                    let sound = *sound;
+                    activation.context.audio.stop_sounds_with_handle(sound);
+                    activation
+                        .context
+                        .avm1
+                        .remove_playing_sounds(|s| s.sound() == Some(sound));
                 } else {
                     avm_warn!(activation, "Sound.stop: Sound '{}' not found", name);
                 }
@@ -451,11 +491,17 @@ fn stop<'gc>(
             // Usage 2: Stop all sound running within a given clip.
             // TODO: We just stop the last played sound for now.
             if let Some(sound_instance) = sound.sound_instance() {
+                update_position(activation, sound);
                 activation.context.audio.stop_sound(sound_instance);
+                activation
+                    .context
+                    .avm1
+                    .remove_playing_sounds(|s| Object::ptr_eq(s.into(), sound.into()));
             }
         } else {
             // Usage 3: If there is no owner and no name, this call acts like `stopAllSounds()`.
             activation.context.audio.stop_all_sounds();
+            activation.context.avm1.remove_playing_sounds(|_| true);
         }
     } else {
         avm_warn!(activation, "Sound.stop: this is not a Sound");
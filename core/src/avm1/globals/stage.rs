@@ -39,6 +39,24 @@ pub fn create_stage_object<'gc>(
         Attribute::DontEnum | Attribute::DontDelete,
     );
 
+    stage.add_property(
+        gc_context,
+        "displayState",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(display_state),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_display_state),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        Attribute::DontEnum | Attribute::DontDelete,
+    );
+
     stage.add_property(
         gc_context,
         "height",
@@ -122,6 +140,60 @@ fn set_align<'gc>(
     Ok(Value::Undefined)
 }
 
+fn display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.context.ui.is_fullscreen() {
+        Ok("fullScreen".into())
+    } else {
+        Ok("normal".into())
+    }
+}
+
+fn set_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let display_state = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let is_full = if display_state.eq_ignore_ascii_case("fullScreen") {
+        true
+    } else if display_state.eq_ignore_ascii_case("normal") {
+        false
+    } else {
+        return Ok(Value::Undefined);
+    };
+
+    // Fullscreen can only be entered in response to a click or a key press.
+    if is_full && !activation.context.is_user_gesture {
+        avm_warn!(
+            activation,
+            "Stage.displayState: fullscreen can only be entered from user input"
+        );
+        return Ok(Value::Undefined);
+    }
+    if is_full == activation.context.ui.is_fullscreen() {
+        return Ok(Value::Undefined);
+    }
+
+    if let Err(e) = activation.context.ui.set_fullscreen(is_full) {
+        avm_warn!(activation, "Stage.displayState: {}", e);
+        return Ok(Value::Undefined);
+    }
+    this.call_method(
+        "broadcastMessage",
+        &["onFullScreen".into(), is_full.into()],
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
 fn height<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
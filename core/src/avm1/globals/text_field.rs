@@ -5,7 +5,9 @@ use crate::avm1::globals::display_object::{self, AVM_DEPTH_BIAS, AVM_MAX_REMOVE_
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_error;
-use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{
+    AntiAliasType, AutoSizeMode, EditText, TDisplayObject, TDisplayObjectContainer,
+};
 use crate::html::TextFormat;
 use enumset::EnumSet;
 use gc_arena::MutationContext;
@@ -515,6 +517,12 @@ pub fn create_proto<'gc>(
     with_text_field_props!(
         object, gc_context, fn_proto,
         "type" => [get_type, set_type],
+        "scroll" => [scroll, set_scroll],
+        "maxscroll" => [max_scroll],
+        "mouseWheelEnabled" => [mouse_wheel_enabled, set_mouse_wheel_enabled],
+        "antiAliasType" => [anti_alias_type, set_anti_alias_type],
+        "sharpness" => [sharpness, set_sharpness],
+        "thickness" => [thickness, set_thickness],
     );
 
     object.into()
@@ -864,6 +872,107 @@ pub fn set_type<'gc>(
     Ok(())
 }
 
+pub fn scroll<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.scroll().into())
+}
+
+pub fn set_scroll<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let scroll = value.coerce_to_f64(activation)?;
+    this.set_scroll(scroll, activation.context.gc_context);
+    Ok(())
+}
+
+pub fn max_scroll<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.max_scroll().into())
+}
+
+pub fn mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_mouse_wheel_enabled().into())
+}
+
+pub fn set_mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let is_enabled = value.as_bool(activation.current_swf_version());
+    this.set_mouse_wheel_enabled(is_enabled, activation.context.gc_context);
+    Ok(())
+}
+
+pub fn anti_alias_type<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(AvmString::new(
+        activation.context.gc_context,
+        this.anti_alias_type().as_str(),
+    )
+    .into())
+}
+
+pub fn set_anti_alias_type<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let name = value.coerce_to_string(activation)?.to_ascii_lowercase();
+    match AntiAliasType::from_name(&name) {
+        Some(anti_alias_type) => {
+            this.set_anti_alias_type(anti_alias_type, activation.context.gc_context)
+        }
+        None => log::warn!("Invalid TextField.antiAliasType: {}", name),
+    }
+    Ok(())
+}
+
+pub fn sharpness<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.sharpness().into())
+}
+
+pub fn set_sharpness<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let sharpness = value.coerce_to_f64(activation)?;
+    this.set_sharpness(sharpness, activation.context.gc_context);
+    Ok(())
+}
+
+pub fn thickness<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.thickness().into())
+}
+
+pub fn set_thickness<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let thickness = value.coerce_to_f64(activation)?;
+    this.set_thickness(thickness, activation.context.gc_context);
+    Ok(())
+}
+
 fn remove_text_field<'gc>(
     text_field: EditText<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -0,0 +1,124 @@
+//! Video prototype
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::property::Attribute::*;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::display_object::{TDisplayObject, Video};
+use gc_arena::MutationContext;
+
+macro_rules! with_video_props {
+    ($obj:ident, $gc:ident, $fn_proto:ident, $($name:literal => [$get:ident $(, $set:ident)*],)*) => {
+        $(
+            $obj.add_property(
+                $gc,
+                $name,
+                with_video_props!(getter $gc, $fn_proto, $get),
+                with_video_props!(setter $gc, $fn_proto, $($set),*),
+                DontDelete | DontEnum,
+            );
+        )*
+    };
+
+    (getter $gc:ident, $fn_proto:ident, $get:ident) => {
+        FunctionObject::function(
+            $gc,
+            Executable::Native(
+                |activation: &mut Activation<'_, 'gc, '_>, this, _args| -> Result<Value<'gc>, Error<'gc>> {
+                    if let Some(display_object) = this.as_display_object() {
+                        if let Some(video) = display_object.as_video() {
+                            return $get(video, activation);
+                        }
+                    }
+                    Ok(Value::Undefined)
+                } as crate::avm1::function::NativeFunction<'gc>
+            ),
+            Some($fn_proto),
+            $fn_proto
+        )
+    };
+
+    (setter $gc:ident, $fn_proto:ident, $set:ident) => {
+        Some(FunctionObject::function(
+            $gc,
+            Executable::Native(
+                |activation: &mut Activation<'_, 'gc, '_>, this, args| -> Result<Value<'gc>, Error<'gc>> {
+                    if let Some(display_object) = this.as_display_object() {
+                        if let Some(video) = display_object.as_video() {
+                            let value = args
+                                .get(0)
+                                .unwrap_or(&Value::Undefined)
+                                .clone();
+                            $set(video, activation, value)?;
+                        }
+                    }
+                    Ok(Value::Undefined)
+                } as crate::avm1::function::NativeFunction<'gc>
+            ),
+            Some($fn_proto),
+            $fn_proto)
+        )
+    };
+
+    (setter $gc:ident, $fn_proto:ident,) => {
+        None
+    };
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::object(gc_context, Some(proto));
+
+    with_video_props!(
+        object, gc_context, fn_proto,
+        "height" => [height],
+        "smoothing" => [smoothing, set_smoothing],
+        "width" => [width],
+    );
+
+    object.into()
+}
+
+/// Implements `Video` constructor.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+fn width<'gc>(
+    this: Video<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.decoded_width().into())
+}
+
+fn height<'gc>(
+    this: Video<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.decoded_height().into())
+}
+
+fn smoothing<'gc>(
+    this: Video<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.smoothing().into())
+}
+
+fn set_smoothing<'gc>(
+    this: Video<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let smoothing = value.as_bool(activation.current_swf_version());
+    this.set_smoothing(activation.context.gc_context, smoothing);
+    Ok(())
+}
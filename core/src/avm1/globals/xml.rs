@@ -922,28 +922,75 @@ pub fn xml_load<'gc>(
     if let Some(node) = this.as_xml_node() {
         let url = url.coerce_to_string(activation)?;
 
-        this.set("loaded", false.into(), activation)?;
+        spawn_xml_fetch(activation, this, node, &url, RequestOptions::get())
+    } else {
+        Ok(false.into())
+    }
+}
 
-        let fetch = activation
-            .context
-            .navigator
-            .fetch(&url, RequestOptions::get());
-        let target_clip = activation.target_clip_or_root();
-        let process = activation.context.load_manager.load_xml_into_node(
-            activation.context.player.clone().unwrap(),
-            node,
-            target_clip,
-            fetch,
-        );
+/// Implements `XML.sendAndLoad`.
+///
+/// The document is sent to the URL as the body of a POST request, and the
+/// response is loaded into the target XML object.
+pub fn xml_send_and_load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get(0).cloned().unwrap_or(Value::Undefined);
 
-        activation.context.navigator.spawn_future(process);
+    if let Value::Null = url {
+        return Ok(false.into());
+    }
+
+    let target = match args.get(1) {
+        Some(&Value::Object(target)) => target,
+        _ => return Ok(false.into()),
+    };
 
-        Ok(true.into())
+    if let (Some(_), Some(target_node)) = (this.as_xml_node(), target.as_xml_node()) {
+        let url = url.coerce_to_string(activation)?;
+        let body = this
+            .call_method("toString", &[], activation)?
+            .coerce_to_string(activation)?;
+        let content_type = this
+            .get("contentType", activation)?
+            .coerce_to_string(activation)?;
+        let request_options =
+            RequestOptions::post(Some((body.as_bytes().to_vec(), content_type.to_string())));
+
+        spawn_xml_fetch(activation, target, target_node, &url, request_options)
     } else {
         Ok(false.into())
     }
 }
 
+/// Start loading the document at a URL into an XML object.
+///
+/// The object's `onData` handler is called with the response once it arrives.
+fn spawn_xml_fetch<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_object: Object<'gc>,
+    loader_node: XMLNode<'gc>,
+    url: &str,
+    request_options: RequestOptions,
+) -> Result<Value<'gc>, Error<'gc>> {
+    loader_object.set("loaded", false.into(), activation)?;
+
+    let fetch = activation.context.navigator.fetch(url, request_options);
+    let target_clip = activation.target_clip_or_root();
+    let process = activation.context.load_manager.load_xml_into_node(
+        activation.context.player.clone().unwrap(),
+        loader_node,
+        target_clip,
+        fetch,
+    );
+
+    activation.context.navigator.spawn_future(process);
+
+    Ok(true.into())
+}
+
 pub fn xml_on_data<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -952,6 +999,8 @@ pub fn xml_on_data<'gc>(
     let src = args.get(0).cloned().unwrap_or(Value::Undefined);
 
     if let Value::Undefined = src {
+        this.set("loaded", false.into(), activation)?;
+
         this.call_method("onLoad", &[false.into()], activation)?;
     } else {
         let src = src.coerce_to_string(activation)?;
@@ -1141,6 +1190,13 @@ pub fn create_xml_proto<'gc>(
         EnumSet::empty(),
         Some(fn_proto),
     );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "sendAndLoad",
+        xml_send_and_load,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
     xml_proto.as_script_object().unwrap().force_set_function(
         "onData",
         xml_on_data,
@@ -1148,6 +1204,12 @@ pub fn create_xml_proto<'gc>(
         EnumSet::empty(),
         Some(fn_proto),
     );
+    xml_proto.define_value(
+        gc_context,
+        "contentType",
+        "application/x-www-form-urlencoded".into(),
+        EnumSet::empty(),
+    );
 
     xml_proto
 }
@@ -9,9 +9,12 @@ use crate::avm1::property::Attribute;
 
 use crate::avm1::activation::Activation;
 use crate::avm1::object::bevel_filter::BevelFilterObject;
+use crate::avm1::object::bitmap_data::BitmapDataObject;
 use crate::avm1::object::blur_filter::BlurFilterObject;
 use crate::avm1::object::color_transform_object::ColorTransformObject;
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
 use crate::avm1::object::date_object::DateObject;
+use crate::avm1::object::displacement_map_filter::DisplacementMapFilterObject;
 use crate::avm1::object::transform_object::TransformObject;
 use crate::avm1::object::xml_attributes_object::XMLAttributesObject;
 use crate::avm1::object::xml_idmap_object::XMLIDMapObject;
@@ -27,10 +30,13 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 
 pub mod bevel_filter;
+pub mod bitmap_data;
 pub mod blur_filter;
 pub mod color_transform_object;
+pub mod convolution_filter;
 mod custom_object;
 pub mod date_object;
+pub mod displacement_map_filter;
 pub mod script_object;
 pub mod shared_object;
 pub mod sound_object;
@@ -62,6 +68,9 @@ pub mod xml_object;
         TransformObject(TransformObject<'gc>),
         BlurFilterObject(BlurFilterObject<'gc>),
         BevelFilterObject(BevelFilterObject<'gc>),
+        ConvolutionFilterObject(ConvolutionFilterObject<'gc>),
+        DisplacementMapFilterObject(DisplacementMapFilterObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
         DateObject(DateObject<'gc>),
     }
 )]
@@ -449,6 +458,21 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get the underlying `ConvolutionFilterObject`, if it exists
+    fn as_convolution_filter_object(&self) -> Option<ConvolutionFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `DisplacementMapFilterObject`, if it exists
+    fn as_displacement_map_filter_object(&self) -> Option<DisplacementMapFilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `BitmapDataObject`, if it exists
+    fn as_bitmap_data_object(&self) -> Option<BitmapDataObject<'gc>> {
+        None
+    }
+
     fn as_ptr(&self) -> *const ObjectPtr;
 
     /// Check if this object is in the prototype chain of the specified test object.
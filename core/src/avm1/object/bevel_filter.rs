@@ -126,6 +126,62 @@ impl<'gc> BevelFilterObject<'gc> {
             },
         ))
     }
+
+    /// Construct a `BevelFilter` with the values of a SWF filter.
+    pub fn from_filter(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        filter: &swf::BevelFilter,
+    ) -> Self {
+        let rgb = |color: &swf::Color| {
+            (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+        };
+        let type_ = if filter.is_on_top {
+            BevelFilterType::Full
+        } else if filter.is_inner {
+            BevelFilterType::Inner
+        } else {
+            BevelFilterType::Outer
+        };
+
+        BevelFilterObject(GcCell::allocate(
+            gc_context,
+            BevelFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                angle: filter.angle.to_degrees(),
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                distance: filter.distance,
+                highlight_alpha: f64::from(filter.highlight_color.a) / 255.0,
+                highlight_color: rgb(&filter.highlight_color),
+                knockout: filter.is_knockout,
+                quality: filter.num_passes.into(),
+                shadow_alpha: f64::from(filter.shadow_color.a) / 255.0,
+                shadow_color: rgb(&filter.shadow_color),
+                strength: filter.strength.into(),
+                type_,
+            },
+        ))
+    }
+
+    /// The SWF filter described by this object.
+    pub fn filter(&self) -> swf::BevelFilter {
+        let this = self.0.read();
+        let color = |rgb: u32, alpha: f64| swf::Color::from_rgb(rgb, (alpha * 255.0) as u8);
+        swf::BevelFilter {
+            shadow_color: color(this.shadow_color, this.shadow_alpha),
+            highlight_color: color(this.highlight_color, this.highlight_alpha),
+            blur_x: this.blur_x,
+            blur_y: this.blur_y,
+            angle: this.angle.to_radians(),
+            distance: this.distance,
+            strength: this.strength as f32,
+            is_inner: matches!(this.type_, BevelFilterType::Inner),
+            is_knockout: this.knockout,
+            is_on_top: matches!(this.type_, BevelFilterType::Full),
+            num_passes: this.quality as u8,
+        }
+    }
 }
 
 impl<'gc> TObject<'gc> for BevelFilterObject<'gc> {
@@ -0,0 +1,91 @@
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::bitmap_data::BitmapDataStorage;
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::cell::{Ref, RefMut};
+use std::fmt;
+
+/// A BitmapData
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct BitmapDataData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The pixels of this bitmap.
+    storage: BitmapDataStorage,
+}
+
+impl fmt::Debug for BitmapDataObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("BitmapData")
+            .field("width", &this.storage.width())
+            .field("height", &this.storage.height())
+            .field("transparent", &this.storage.transparent())
+            .finish()
+    }
+}
+
+impl<'gc> BitmapDataObject<'gc> {
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        BitmapDataObject(GcCell::allocate(
+            gc_context,
+            BitmapDataData {
+                base: ScriptObject::object(gc_context, proto),
+                storage: Default::default(),
+            },
+        ))
+    }
+
+    pub fn storage(&self) -> Ref<BitmapDataStorage> {
+        Ref::map(self.0.read(), |data| &data.storage)
+    }
+
+    pub fn storage_mut(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<BitmapDataStorage> {
+        RefMut::map(self.0.write(gc_context), |data| &mut data.storage)
+    }
+}
+
+impl<'gc> TObject<'gc> for BitmapDataObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            (*self).into(),
+            Some(activation.context.avm1.prototypes.bitmap_data),
+        )
+    }
+
+    fn as_bitmap_data_object(&self) -> Option<BitmapDataObject<'gc>> {
+        Some(*self)
+    }
+
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(BitmapDataObject::empty_object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.bitmap_data),
+        )
+        .into())
+    }
+}
@@ -52,6 +52,33 @@ impl<'gc> BlurFilterObject<'gc> {
             },
         ))
     }
+
+    /// Construct a `BlurFilter` with the values of a SWF filter.
+    pub fn from_filter(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        filter: &swf::BlurFilter,
+    ) -> Self {
+        BlurFilterObject(GcCell::allocate(
+            gc_context,
+            BlurFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                quality: filter.num_passes.into(),
+            },
+        ))
+    }
+
+    /// The SWF filter described by this object.
+    pub fn filter(&self) -> swf::BlurFilter {
+        let this = self.0.read();
+        swf::BlurFilter {
+            blur_x: this.blur_x,
+            blur_y: this.blur_y,
+            num_passes: this.quality as u8,
+        }
+    }
 }
 
 impl<'gc> TObject<'gc> for BlurFilterObject<'gc> {
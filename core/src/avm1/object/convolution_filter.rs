@@ -0,0 +1,189 @@
+use crate::add_field_accessors;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::fmt;
+
+/// A ConvolutionFilter
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct ConvolutionFilterObject<'gc>(GcCell<'gc, ConvolutionFilterData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ConvolutionFilterData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    alpha: f64,
+    bias: f64,
+    clamp: bool,
+    color: u32,
+    divisor: f64,
+    matrix: Vec<f64>,
+    matrix_x: u8,
+    matrix_y: u8,
+    preserve_alpha: bool,
+}
+
+impl fmt::Debug for ConvolutionFilterObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("ConvolutionFilter")
+            .field("alpha", &this.alpha)
+            .field("bias", &this.bias)
+            .field("clamp", &this.clamp)
+            .field("color", &this.color)
+            .field("divisor", &this.divisor)
+            .field("matrix", &this.matrix)
+            .field("matrixX", &this.matrix_x)
+            .field("matrixY", &this.matrix_y)
+            .field("preserveAlpha", &this.preserve_alpha)
+            .finish()
+    }
+}
+
+impl<'gc> ConvolutionFilterObject<'gc> {
+    add_field_accessors!(
+        [set_alpha, get_alpha, alpha, f64],
+        [set_bias, get_bias, bias, f64],
+        [set_clamp, get_clamp, clamp, bool],
+        [set_color, get_color, color, u32],
+        [set_divisor, get_divisor, divisor, f64],
+        [set_preserve_alpha, get_preserve_alpha, preserve_alpha, bool],
+    );
+
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        ConvolutionFilterObject(GcCell::allocate(
+            gc_context,
+            ConvolutionFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                alpha: 0.0,
+                bias: 0.0,
+                clamp: true,
+                color: 0,
+                divisor: 1.0,
+                matrix: Vec::new(),
+                matrix_x: 0,
+                matrix_y: 0,
+                preserve_alpha: true,
+            },
+        ))
+    }
+
+    /// Construct a `ConvolutionFilter` with the values of a SWF filter.
+    pub fn from_filter(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        filter: &swf::ConvolutionFilter,
+    ) -> Self {
+        let color = &filter.default_color;
+        ConvolutionFilterObject(GcCell::allocate(
+            gc_context,
+            ConvolutionFilterData {
+                base: ScriptObject::object(gc_context, proto),
+                alpha: f64::from(color.a) / 255.0,
+                bias: filter.bias,
+                clamp: filter.is_clamped,
+                color: (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b),
+                divisor: filter.divisor,
+                matrix: filter.matrix.clone(),
+                matrix_x: filter.num_matrix_cols,
+                matrix_y: filter.num_matrix_rows,
+                preserve_alpha: filter.is_preserve_alpha,
+            },
+        ))
+    }
+
+    /// The SWF filter described by this object.
+    pub fn filter(&self) -> swf::ConvolutionFilter {
+        let this = self.0.read();
+        swf::ConvolutionFilter {
+            num_matrix_rows: this.matrix_y,
+            num_matrix_cols: this.matrix_x,
+            matrix: this.matrix.clone(),
+            divisor: this.divisor,
+            bias: this.bias,
+            default_color: swf::Color::from_rgb(this.color, (this.alpha * 255.0) as u8),
+            is_clamped: this.clamp,
+            is_preserve_alpha: this.preserve_alpha,
+        }
+    }
+
+    pub fn get_matrix(&self) -> Vec<f64> {
+        self.0.read().matrix.clone()
+    }
+
+    /// Set the matrix values, padding them with zeroes or truncating them to
+    /// fit the matrix size.
+    pub fn set_matrix(&self, gc_context: MutationContext<'gc, '_>, mut matrix: Vec<f64>) {
+        let mut this = self.0.write(gc_context);
+        matrix.resize(usize::from(this.matrix_x) * usize::from(this.matrix_y), 0.0);
+        this.matrix = matrix;
+    }
+
+    pub fn get_matrix_x(&self) -> u8 {
+        self.0.read().matrix_x
+    }
+
+    /// Set the number of columns of the matrix, keeping as many of its values
+    /// in order as fit.
+    pub fn set_matrix_x(&self, gc_context: MutationContext<'gc, '_>, matrix_x: u8) {
+        let mut this = self.0.write(gc_context);
+        this.matrix_x = matrix_x;
+        let len = usize::from(this.matrix_x) * usize::from(this.matrix_y);
+        this.matrix.resize(len, 0.0);
+    }
+
+    pub fn get_matrix_y(&self) -> u8 {
+        self.0.read().matrix_y
+    }
+
+    /// Set the number of rows of the matrix, keeping as many of its values
+    /// in order as fit.
+    pub fn set_matrix_y(&self, gc_context: MutationContext<'gc, '_>, matrix_y: u8) {
+        let mut this = self.0.write(gc_context);
+        this.matrix_y = matrix_y;
+        let len = usize::from(this.matrix_x) * usize::from(this.matrix_y);
+        this.matrix.resize(len, 0.0);
+    }
+}
+
+impl<'gc> TObject<'gc> for ConvolutionFilterObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            (*self).into(),
+            Some(activation.context.avm1.prototypes.convolution_filter),
+        )
+    }
+
+    fn as_convolution_filter_object(&self) -> Option<ConvolutionFilterObject<'gc>> {
+        Some(*self)
+    }
+
+    fn create_bare_object(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(ConvolutionFilterObject::empty_object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.convolution_filter),
+        )
+        .into())
+    }
+}
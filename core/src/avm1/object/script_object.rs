@@ -849,8 +849,10 @@ mod tests {
     use crate::backend::locale::NullLocaleBackend;
     use crate::backend::log::NullLogBackend;
     use crate::backend::navigator::NullNavigatorBackend;
-    use crate::backend::render::NullRenderer;
+    use crate::backend::render::{NullRenderer, StageQuality};
     use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::ui::NullUiBackend;
+    use crate::backend::video::NullVideoBackend;
     use crate::context::UpdateContext;
     use crate::display_object::MovieClip;
     use crate::focus_tracker::FocusTracker;
@@ -903,11 +905,15 @@ mod tests {
                 renderer: &mut NullRenderer::new(),
                 locale: &mut NullLocaleBackend::new(),
                 log: &mut NullLogBackend::new(),
+                ui: &mut NullUiBackend::new(),
+                video: &mut NullVideoBackend::new(),
                 system_prototypes: avm1.prototypes().clone(),
                 mouse_hovered_object: None,
+                mouse_pressed_object: None,
                 mouse_position: &(Twips::new(0), Twips::new(0)),
                 drag_object: &mut None,
                 stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
+                quality: &mut StageQuality::default(),
                 player: None,
                 load_manager: &mut LoadManager::new(),
                 system: &mut SystemProperties::default(),
@@ -917,12 +923,21 @@ mod tests {
                 unbound_text_fields: &mut Vec::new(),
                 timers: &mut Timers::new(),
                 needs_render: &mut false,
+                needs_redraw: &mut false,
+                needs_collect: &mut false,
+                gc_stats: &Default::default(),
                 avm1: &mut avm1,
                 avm2: &mut avm2,
                 external_interface: &mut Default::default(),
+                local_connections: &mut Default::default(),
+                current_context_menu: &mut None,
                 update_start: Instant::now(),
                 max_execution_duration: Duration::from_secs(15),
+                goto_snapshots: Default::default(),
+                rewind_frames_replayed: &mut 0,
+                text_relayouts: &mut 0,
                 focus_tracker: FocusTracker::new(gc_context),
+                is_user_gesture: false,
             };
 
             root.post_instantiation(&mut context, root, None, Instantiator::Movie, false);
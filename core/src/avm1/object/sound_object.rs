@@ -33,14 +33,22 @@ pub struct SoundObjectData<'gc> {
     /// Position of the last playing sound in milliseconds.
     position: u32,
 
+    /// Where in the attached sound the last played sound started, in
+    /// milliseconds.
+    start_offset: u32,
+
     /// Duration of the currently attached sound in milliseconds.
     duration: u32,
+
+    /// The ID3 tags of the attached sound, if it has any.
+    id3: Option<Object<'gc>>,
 }
 
 unsafe impl<'gc> Collect for SoundObjectData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.owner.trace(cc);
+        self.id3.trace(cc);
     }
 }
 
@@ -68,7 +76,9 @@ impl<'gc> SoundObject<'gc> {
                 sound_instance: None,
                 owner: None,
                 position: 0,
+                start_offset: 0,
                 duration: 0,
+                id3: None,
             },
         ))
     }
@@ -120,6 +130,22 @@ impl<'gc> SoundObject<'gc> {
     pub fn set_position(self, gc_context: MutationContext<'gc, '_>, position: u32) {
         self.0.write(gc_context).position = position;
     }
+
+    pub fn start_offset(self) -> u32 {
+        self.0.read().start_offset
+    }
+
+    pub fn set_start_offset(self, gc_context: MutationContext<'gc, '_>, start_offset: u32) {
+        self.0.write(gc_context).start_offset = start_offset;
+    }
+
+    pub fn id3(self) -> Option<Object<'gc>> {
+        self.0.read().id3
+    }
+
+    pub fn set_id3(self, gc_context: MutationContext<'gc, '_>, id3: Option<Object<'gc>>) {
+        self.0.write(gc_context).id3 = id3;
+    }
 }
 
 impl<'gc> TObject<'gc> for SoundObject<'gc> {
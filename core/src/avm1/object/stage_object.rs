@@ -7,6 +7,7 @@ use crate::avm1::object::search_prototype;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ObjectPtr, ScriptObject, TDisplayObject, TObject, Value};
 use crate::avm_warn;
+use crate::backend::render::StageQuality;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, EditText, MovieClip, TDisplayObjectContainer};
 use crate::property_map::PropertyMap;
@@ -703,6 +704,9 @@ fn set_alpha<'gc>(
     val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
     if let Some(val) = property_coerce_to_number(activation, val)? {
+        // The alpha of nested clips is multiplied together when rendering, so
+        // each one is kept within 0-100%.
+        let val = val.max(0.0).min(100.0);
         this.set_alpha(activation.context.gc_context, val / 100.0);
     }
     Ok(())
@@ -850,16 +854,25 @@ fn high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
-    Ok(1.into())
+    let quality = match *activation.context.quality {
+        StageQuality::Low => 0,
+        StageQuality::Medium | StageQuality::High => 1,
+        StageQuality::Best => 2,
+    };
+    Ok(quality.into())
 }
 
 fn set_high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
+    let quality = match val.coerce_to_i32(activation)? {
+        0 => StageQuality::Low,
+        1 => StageQuality::High,
+        _ => StageQuality::Best,
+    };
+    set_stage_quality(activation, quality);
     Ok(())
 }
 
@@ -901,19 +914,28 @@ fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
-    Ok("HIGH".into())
+    Ok(activation.context.quality.as_str().into())
 }
 
 fn set_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
+    // Unknown quality names are ignored.
+    let name = val.coerce_to_string(activation)?;
+    if let Some(quality) = StageQuality::from_name(&name) {
+        set_stage_quality(activation, quality);
+    }
     Ok(())
 }
 
+fn set_stage_quality(activation: &mut Activation<'_, '_, '_>, quality: StageQuality) {
+    *activation.context.quality = quality;
+    activation.context.renderer.set_quality(quality);
+    *activation.context.needs_render = true;
+}
+
 fn x_mouse<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: DisplayObject<'gc>,
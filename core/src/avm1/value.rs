@@ -233,6 +233,11 @@ impl<'gc> Value<'gc> {
     }
 
     /// ECMA-262 2nd edition s. 11.8.5 Abstract relational comparison algorithm
+    ///
+    /// In SWF6 and later, if `valueOf` leaves either side as an object other
+    /// than a display object (as `Object.prototype.valueOf` does), the
+    /// comparison is `false` without calling `toString`. SWF5 and earlier
+    /// treat such objects as `NaN`, which makes the comparison `undefined`.
     #[allow(clippy::float_cmp)]
     pub fn abstract_lt(
         &self,
@@ -242,6 +247,12 @@ impl<'gc> Value<'gc> {
         let prim_self = self.to_primitive_num(activation)?;
         let prim_other = other.to_primitive_num(activation)?;
 
+        if activation.current_swf_version() >= 6
+            && (prim_self.is_non_display_object() || prim_other.is_non_display_object())
+        {
+            return Ok(false.into());
+        }
+
         if let (Value::String(a), Value::String(b)) = (&prim_self, &prim_other) {
             return Ok(a.to_string().bytes().lt(b.to_string().bytes()).into());
         }
@@ -419,7 +430,19 @@ impl<'gc> Value<'gc> {
         self.coerce_to_f64(activation).map(f64_to_wrapping_u32)
     }
 
+    /// Whether this value is an object that isn't a display object.
+    fn is_non_display_object(&self) -> bool {
+        matches!(self, Value::Object(object) if object.as_display_object().is_none())
+    }
+
     /// Coerce a value to a string.
+    ///
+    /// Before SWF7, `undefined` coerces to an empty string; `null` is always
+    /// `"null"`.
+    ///
+    /// Objects are coerced by calling `toString`. In SWF6 and later, a result
+    /// that isn't a string becomes `"[type Object]"`; SWF5 and earlier coerce
+    /// a primitive result to a string instead.
     pub fn coerce_to_string(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
@@ -427,6 +450,10 @@ impl<'gc> Value<'gc> {
         Ok(match self {
             Value::Object(object) => match object.call_method("toString", &[], activation)? {
                 Value::String(s) => s,
+                Value::Object(_) => "[type Object]".into(),
+                value if activation.current_swf_version() < 6 => {
+                    value.coerce_to_string(activation)?
+                }
                 _ => "[type Object]".into(),
             },
             Value::Undefined => {
@@ -686,6 +713,30 @@ mod test {
         })
     }
 
+    #[test]
+    fn abstract_lt_object() {
+        // Before SWF6, the object is treated as `NaN`, which is `undefined`.
+        for &(version, expected) in &[(5, None), (6, Some(false))] {
+            with_avm(version, |activation, _this| -> Result<(), Error> {
+                let o = ScriptObject::object_cell(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes.object),
+                );
+                let a = Value::String(AvmString::new(
+                    activation.context.gc_context,
+                    "a".to_owned(),
+                ));
+
+                assert_eq!(
+                    a.abstract_lt(o.into(), activation).unwrap(),
+                    expected.map_or(Value::Undefined, Value::Bool)
+                );
+
+                Ok(())
+            })
+        }
+    }
+
     #[test]
     fn abstract_gt_str() {
         with_avm(8, |activation, _this| -> Result<(), Error> {
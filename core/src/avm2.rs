@@ -1,11 +1,27 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
+use crate::avm2::events::Event;
+use crate::avm2::globals::flash::display::{loaderinfo, stage};
+use crate::avm2::globals::flash::events::{contextmenuevent, mouseevent};
+use crate::avm2::globals::flash::net::{localconnection, sharedobject, url_loader};
+use crate::avm2::globals::flash::system::worker;
+use crate::avm2::globals::flash::ui::context_menu;
 use crate::avm2::globals::SystemPrototypes;
 use crate::avm2::method::Method;
+use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::avm2::sound::GeneratedSound;
+use crate::backend::audio::SoundHandle;
+use crate::backend::navigator::Response;
+use crate::collect::CollectWrapper;
 use crate::context::UpdateContext;
+use crate::context_menu::ContextMenuState;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::external::Value as ExternalValue;
 use crate::tag_utils::SwfSlice;
+use crate::worker::{ChannelHandle, WorkerHandle, Workers};
 use gc_arena::{Collect, MutationContext};
+use std::collections::HashMap;
 use std::rc::Rc;
 use swf::avm2::read::Reader;
 
@@ -19,9 +35,13 @@ macro_rules! avm_debug {
 }
 
 mod activation;
+mod amf;
 mod array;
+mod bytearray;
 mod class;
 mod domain;
+mod error;
+mod events;
 mod function;
 mod globals;
 mod method;
@@ -29,18 +49,23 @@ mod names;
 mod object;
 mod property;
 mod property_map;
+mod regexp;
 mod return_value;
 mod scope;
 mod script;
 mod slot;
+mod sound;
 mod string;
 mod traits;
 mod value;
 
 pub use crate::avm2::activation::Activation;
+pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::domain::Domain;
+pub use crate::avm2::error::{NativeError, ThrownValue};
 pub use crate::avm2::names::{Namespace, QName};
-pub use crate::avm2::object::{Object, StageObject, TObject};
+pub use crate::avm2::object::{ArrayObject, Object, ScriptObject, StageObject, TObject};
+pub use crate::avm2::string::AvmString;
 pub use crate::avm2::value::Value;
 
 /// Boxed error alias.
@@ -56,12 +81,32 @@ pub struct Avm2<'gc> {
     /// Values currently present on the operand stack.
     stack: Vec<Value<'gc>>,
 
+    /// The value most recently thrown by a script, until it is caught.
+    thrown_value: Option<Value<'gc>>,
+
     /// Global scope object.
     globals: Domain<'gc>,
 
     /// System prototypes.
     system_prototypes: Option<SystemPrototypes<'gc>>,
 
+    /// Sounds whose samples are generated by `sampleData` event handlers.
+    generated_sounds: Vec<GeneratedSound<'gc>>,
+
+    /// Library sounds linked to `Sound` subclasses by `SymbolClass` tags,
+    /// along with the prototype of their class.
+    sound_symbols: Vec<(Object<'gc>, CollectWrapper<SoundHandle>)>,
+
+    /// Local shared objects, by name.
+    shared_objects: HashMap<String, Object<'gc>>,
+
+    /// The object for the stage, once something has asked for it.
+    stage: Option<Object<'gc>>,
+
+    /// The worker this player runs, along with every other worker and
+    /// message channel it knows about.
+    workers: Workers<'gc>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -73,8 +118,14 @@ impl<'gc> Avm2<'gc> {
 
         Self {
             stack: Vec::new(),
+            thrown_value: None,
             globals,
             system_prototypes: None,
+            generated_sounds: Vec::new(),
+            sound_symbols: Vec::new(),
+            shared_objects: HashMap::new(),
+            stage: None,
+            workers: Workers::new(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -131,9 +182,371 @@ impl<'gc> Avm2<'gc> {
         Ok(())
     }
 
+    /// Request more samples for all playing sounds that are generated by
+    /// `sampleData` event handlers.
+    pub fn generate_sound_samples(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
+        if context.avm2.generated_sounds.is_empty() {
+            return Ok(());
+        }
+
+        let mut activation = Activation::from_nothing(context.reborrow());
+        sound::generate_sound_samples(&mut activation)
+    }
+
+    /// Dispatch a `MouseEvent` of a given type on a display object.
+    ///
+    /// The event's local coordinates are taken from the current mouse
+    /// position. Display objects without an AVM2 object are ignored.
+    pub fn dispatch_mouse_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+        event_type: &'static str,
+        delta: i32,
+    ) -> Result<bool, Error> {
+        let target_object = match target.object2() {
+            Value::Object(o) => o,
+            _ => return Ok(false),
+        };
+        let (local_x, local_y) = target.global_to_local(*context.mouse_position);
+
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let mouseevent_proto = activation.context.avm2.prototypes().mouseevent;
+        let event = EventObject::from_event(
+            activation.context.gc_context,
+            Some(mouseevent_proto),
+            Event::new(event_type),
+        );
+        mouseevent::instance_init(
+            &mut activation,
+            Some(event),
+            &[
+                event_type.into(),
+                true.into(),
+                false.into(),
+                local_x.to_pixels().into(),
+                local_y.to_pixels().into(),
+                Value::Null,
+                false.into(),
+                false.into(),
+                false.into(),
+                false.into(),
+                delta.into(),
+            ],
+        )?;
+
+        events::dispatch_event(&mut activation, target_object, event)
+    }
+
+    /// List all sounds that are generated by `sampleData` event handlers.
+    fn generated_sounds(&self) -> &[GeneratedSound<'gc>] {
+        &self.generated_sounds
+    }
+
+    /// Register a newly-started generated sound.
+    fn add_generated_sound(&mut self, sound: GeneratedSound<'gc>) {
+        self.generated_sounds.push(sound);
+    }
+
+    /// Replace the state of a generated sound with an updated copy.
+    fn update_generated_sound(&mut self, sound: GeneratedSound<'gc>) {
+        if let Some(existing) = self
+            .generated_sounds
+            .iter_mut()
+            .find(|s| Object::ptr_eq(s.channel(), sound.channel()))
+        {
+            *existing = sound;
+        }
+    }
+
+    /// Unregister the generated sound playing on a given channel.
+    fn remove_generated_sound(&mut self, channel: Object<'gc>) -> Option<GeneratedSound<'gc>> {
+        let index = self
+            .generated_sounds
+            .iter()
+            .position(|s| Object::ptr_eq(s.channel(), channel))?;
+
+        Some(self.generated_sounds.remove(index))
+    }
+
+    /// Unregister every generated sound.
+    fn remove_all_generated_sounds(&mut self) {
+        self.generated_sounds.clear();
+    }
+
+    /// Link a library sound to the `Sound` subclass with the given prototype.
+    pub fn register_sound_symbol(&mut self, proto: Object<'gc>, sound: SoundHandle) {
+        self.sound_symbols.push((proto, CollectWrapper(sound)));
+    }
+
+    /// Find the library sound that a `Sound` object was constructed from, if
+    /// its class was linked to one.
+    fn sound_for_object(&self, object: Object<'gc>) -> Option<SoundHandle> {
+        let mut proto = object.proto();
+        while let Some(p) = proto {
+            if let Some((_, sound)) = self
+                .sound_symbols
+                .iter()
+                .find(|(symbol_proto, _)| Object::ptr_eq(*symbol_proto, p))
+            {
+                return Some(sound.0);
+            }
+
+            proto = p.proto();
+        }
+
+        None
+    }
+
+    /// The object for the stage, if it has been created.
+    fn stage(&self) -> Option<Object<'gc>> {
+        self.stage
+    }
+
+    fn set_stage(&mut self, stage: Object<'gc>) {
+        self.stage = Some(stage);
+    }
+
+    /// Fire the stage's `render` event, after a call to `Stage.invalidate`.
+    pub fn dispatch_render_event(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = stage::dispatch_render(&mut activation) {
+            log::error!("Unhandled AVM2 exception in render handler: {}", e);
+        }
+    }
+
+    /// Look up a local shared object that has already been opened.
+    fn shared_object(&self, name: &str) -> Option<Object<'gc>> {
+        self.shared_objects.get(name).copied()
+    }
+
+    /// Register a newly-opened local shared object.
+    fn add_shared_object(&mut self, name: String, shared_object: Object<'gc>) {
+        self.shared_objects.insert(name, shared_object);
+    }
+
+    /// Write the data of every open local shared object to storage.
+    pub fn flush_shared_objects(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let shared_objects: Vec<Object<'gc>> =
+            context.avm2.shared_objects.values().copied().collect();
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        for shared_object in shared_objects {
+            if let Err(e) = sharedobject::flush_data(&mut activation, shared_object, 0) {
+                log::error!("Could not flush shared object: {}", e);
+            }
+        }
+    }
+
+    /// Access the workers and message channels known to this player.
+    fn workers(&mut self) -> &mut Workers<'gc> {
+        &mut self.workers
+    }
+
+    /// Make this player run as a given background worker.
+    pub fn set_current_worker(&mut self, worker: WorkerHandle) {
+        self.workers.set_current(worker);
+    }
+
+    /// The message channels that deliver messages to the worker this player
+    /// runs.
+    pub fn receiving_worker_channels(&self) -> Vec<ChannelHandle> {
+        self.workers.receiving_channels()
+    }
+
+    /// Share a `ByteArray` holding the given bytes with other workers, as a
+    /// shared property of the worker this player runs.
+    pub fn set_worker_shared_byte_array(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        key: &str,
+        bytes: Vec<u8>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = worker::set_shared_byte_array(&mut activation, key, bytes) {
+            log::error!("Could not share ByteArray with workers: {}", e);
+        }
+    }
+
+    /// Fire events for any changes that other workers have made to the
+    /// workers and message channels this player knows about.
+    pub fn poll_workers(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let pending_events = context.avm2.workers.poll();
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        for (target, event_type) in pending_events {
+            let event_proto = activation.context.avm2.prototypes().event;
+            let event = EventObject::from_event(
+                activation.context.gc_context,
+                Some(event_proto),
+                Event::new(event_type),
+            );
+
+            if let Err(e) = events::dispatch_event(&mut activation, target, event) {
+                log::error!("Unhandled AVM2 exception in {} handler: {}", event_type, e);
+            }
+        }
+    }
+
+    /// Deliver a message sent over a `LocalConnection` to an AVM2 receiver.
+    ///
+    /// Returns `false` if the receiver does not accept messages from the
+    /// sender's domain.
+    pub fn receive_local_connection_message(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        receiver: Object<'gc>,
+        same_domain: bool,
+        sender_domain: &str,
+        method_name: &str,
+        arguments: Vec<ExternalValue>,
+    ) -> bool {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        match localconnection::receive_message(
+            &mut activation,
+            receiver,
+            same_domain,
+            sender_domain,
+            method_name,
+            arguments,
+        ) {
+            Ok(delivered) => delivered,
+            Err(e) => {
+                log::error!("Unhandled AVM2 exception in LocalConnection: {}", e);
+                true
+            }
+        }
+    }
+
+    /// Tell an AVM2 `LocalConnection` whether or not a message it sent was
+    /// delivered.
+    pub fn dispatch_local_connection_status(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        sender: Object<'gc>,
+        delivered: bool,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = localconnection::dispatch_status(&mut activation, sender, delivered) {
+            log::error!("Unhandled AVM2 exception in status handler: {}", e);
+        }
+    }
+
+    /// Build the context menu for a right click on an AVM2 display object.
+    ///
+    /// Returns `None` if a script error stopped the menu from being built.
+    pub fn make_context_menu_state(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+    ) -> Option<ContextMenuState<'gc>> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        match context_menu::open(&mut activation, target) {
+            Ok(menu) => Some(menu),
+            Err(e) => {
+                log::error!("Unhandled AVM2 exception in menuSelect handler: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Tell an AVM2 `ContextMenuItem` that it was chosen from the context
+    /// menu.
+    pub fn dispatch_context_menu_item_select(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        item: Object<'gc>,
+        mouse_target: Object<'gc>,
+        owner: Object<'gc>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) =
+            contextmenuevent::dispatch(&mut activation, item, "menuItemSelect", mouse_target, owner)
+        {
+            log::error!("Unhandled AVM2 exception in menuItemSelect handler: {}", e);
+        }
+    }
+
+    /// Tell an AVM2 `LoaderInfo` that the movie it was loading has been
+    /// loaded into a given display object.
+    pub fn loader_content_loaded(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        content: DisplayObject<'gc>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = loaderinfo::content_loaded(&mut activation, loader_info, content) {
+            log::error!("Unhandled AVM2 exception in Loader event handler: {}", e);
+        }
+    }
+
+    /// Tell an AVM2 `LoaderInfo` how much of the movie it is loading has been
+    /// downloaded.
+    pub fn loader_content_progress(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        bytes_loaded: u64,
+        bytes_total: Option<u64>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = loaderinfo::content_load_progress(
+            &mut activation,
+            loader_info,
+            bytes_loaded,
+            bytes_total,
+        ) {
+            log::error!("Unhandled AVM2 exception in Loader event handler: {}", e);
+        }
+    }
+
+    /// Fire the error events of a `LoaderInfo` whose movie failed to load.
+    pub fn loader_content_failed(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        url: &str,
+        status: u16,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = loaderinfo::content_load_failed(&mut activation, loader_info, url, status) {
+            log::error!("Unhandled AVM2 exception in Loader event handler: {}", e);
+        }
+    }
+
+    /// Tell an AVM2 `URLLoader` that its request has been made.
+    pub fn url_loader_started(context: &mut UpdateContext<'_, 'gc, '_>, url_loader: Object<'gc>) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = url_loader::load_started(&mut activation, url_loader) {
+            log::error!("Unhandled AVM2 exception in URLLoader event handler: {}", e);
+        }
+    }
+
+    /// Give an AVM2 `URLLoader` the response to its request.
+    ///
+    /// The response is `None` if the request failed without an HTTP response.
+    pub fn url_loader_response(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        url_loader: Object<'gc>,
+        url: &str,
+        response: Option<Response>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        if let Err(e) = url_loader::load_response(&mut activation, url_loader, url, response) {
+            log::error!("Unhandled AVM2 exception in URLLoader event handler: {}", e);
+        }
+    }
+
     /// Load an ABC file embedded in a `SwfSlice`.
     ///
     /// The `SwfSlice` must resolve to the contents of an ABC file.
+    ///
+    /// Scripts only run once one of their definitions is first looked up.
+    /// The exception is the last script in the file, which is its entry
+    /// point: unless `lazy_init` is set, it runs right away.
     pub fn load_abc(
         abc: SwfSlice,
         _abc_name: &str,
@@ -146,12 +559,17 @@ impl<'gc> Avm2<'gc> {
         let abc_file = Rc::new(read.read()?);
         let tunit = TranslationUnit::from_abc(abc_file.clone(), domain, context.gc_context);
 
-        for i in (0..abc_file.scripts.len()).rev() {
-            let mut script = tunit.load_script(i as u32, context.avm2, context.gc_context)?;
+        // Every script is loaded before any of them runs, so that the entry
+        // point can use definitions from the scripts before it.
+        let num_scripts = abc_file.scripts.len() as u32;
+        for i in (0..num_scripts).rev() {
+            tunit.load_script(i, context.avm2, context.gc_context)?;
+        }
 
-            if !lazy_init {
-                script.globals(context)?;
-            }
+        if !lazy_init && num_scripts > 0 {
+            let mut entrypoint =
+                tunit.load_script(num_scripts - 1, context.avm2, context.gc_context)?;
+            entrypoint.globals(context)?;
         }
 
         Ok(())
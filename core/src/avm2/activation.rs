@@ -1,25 +1,27 @@
 //! Activation frames
 
 use crate::avm2::array::ArrayStorage;
-use crate::avm2::class::Class;
+use crate::avm2::class::{Class, ClassState};
+use crate::avm2::globals::xml;
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, Namespace, QName};
-use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject};
+use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject, XmlObject};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
+use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::Value;
-use crate::avm2::{value, Avm2, Error};
+use crate::avm2::{value, Avm2, Error, NativeError, ThrownValue};
 use crate::context::UpdateContext;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
 use std::io::Cursor;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
+    Class as AbcClass, Exception as AbcException, Index, Method as AbcMethod,
+    Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
 };
 
 /// Represents a particular register set.
@@ -97,11 +99,23 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// A `scope` of `None` indicates that the scope stack is empty.
     scope: Option<GcCell<'gc, Scope<'gc>>>,
 
+    /// The scope stack this activation was entered with.
+    ///
+    /// Scopes pushed on top of this one belong to the activation itself, and
+    /// are the ones that `getscopeobject` indexes into.
+    outer_scope: Option<GcCell<'gc, Scope<'gc>>>,
+
     /// The base prototype of `this`.
     ///
     /// This will not be available if this is not a method call.
     base_proto: Option<Object<'gc>>,
 
+    /// The default XML namespace set by `dxns` or `dxnslate`.
+    ///
+    /// This is scoped to the activation, so that functions setting different
+    /// default namespaces do not interfere with each other.
+    default_xml_namespace: Option<AvmString<'gc>>,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -125,7 +139,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
+            outer_scope: None,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         }
     }
@@ -164,7 +180,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
+            outer_scope: scope,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -209,7 +227,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
+            outer_scope: scope,
             base_proto,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -294,6 +314,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.base_proto
     }
 
+    /// Get the default XML namespace of this activation, if one was set.
+    pub fn default_xml_namespace(&self) -> Option<AvmString<'gc>> {
+        self.default_xml_namespace
+    }
+
     /// Retrieve a int from the current constant pool.
     fn pool_int(
         &self,
@@ -391,15 +416,136 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let mut read = Reader::new(Cursor::new(body?.code.as_ref()));
+        let stack_depth = self.context.avm2.stack.len();
+        let entry_scope = self.scope();
 
         loop {
+            let offset = read.seek(0)?;
             let result = self.do_next_opcode(method, &mut read);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+                Err(e) => {
+                    if let Err(e) =
+                        self.catch_error(method, &mut read, offset, e, stack_depth, entry_scope)
+                    {
+                        break Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Jump to the first exception handler of a method that covers the
+    /// instruction at `offset` and catches the given error.
+    ///
+    /// Values thrown by scripts and errors raised by native code can be
+    /// caught; any other error is returned as-is. Before jumping to a handler,
+    /// the operand and scope stacks are reset to how they were when the
+    /// method was entered, and the caught value is pushed for the handler to
+    /// pick up.
+    fn catch_error(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        reader: &mut Reader<Cursor<&[u8]>>,
+        offset: u64,
+        error: Error,
+        stack_depth: usize,
+        entry_scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<(), Error> {
+        if !error.is::<NativeError>() && !error.is::<ThrownValue>() {
+            return Err(error);
+        }
+
+        let handlers: Vec<&AbcException> = match method.body() {
+            Some(body) => body
+                .exceptions
+                .iter()
+                .filter(|handler| {
+                    u64::from(handler.from_offset) <= offset
+                        && offset < u64::from(handler.to_offset)
+                })
+                .collect(),
+            None => return Err(error),
+        };
+        if handlers.is_empty() {
+            return Err(error);
+        }
+
+        self.context.avm2.stack.truncate(stack_depth);
+        self.set_scope(entry_scope);
+        let caught_value = match error.downcast_ref::<NativeError>() {
+            Some(native_error) => self.construct_native_error(native_error)?.into(),
+            None => self
+                .context
+                .avm2
+                .thrown_value
+                .clone()
+                .unwrap_or(Value::Undefined),
+        };
+
+        for handler in handlers {
+            if handler.type_name.0 != 0 {
+                let type_name = self.pool_multiname_static(
+                    method,
+                    handler.type_name.clone(),
+                    self.context.gc_context,
+                )?;
+                let type_object = if let Some(scope) = self.scope() {
+                    scope
+                        .write(self.context.gc_context)
+                        .resolve(&type_name, self)?
+                } else {
+                    None
+                };
+
+                let is_caught = match (type_object, &caught_value) {
+                    (_, Value::Undefined) | (_, Value::Null) => false,
+                    (Some(Value::Object(type_object)), caught_value) => caught_value
+                        .coerce_to_object(self)?
+                        .is_instance_of(self, type_object, true)?,
+                    _ => false,
+                };
+                if !is_caught {
+                    continue;
+                }
             }
+
+            self.context.avm2.thrown_value = None;
+            self.context.avm2.push(caught_value);
+            let position = reader.seek(0)?;
+            reader.seek(i64::from(handler.target_offset) - position as i64)?;
+
+            return Ok(());
         }
+
+        Err(error)
+    }
+
+    /// Construct the object that scripts catch for an error raised by native
+    /// code.
+    fn construct_native_error(&mut self, error: &NativeError) -> Result<Object<'gc>, Error> {
+        let class_name = QName::new(Namespace::public_namespace(), error.class_name());
+        let mut constr = self
+            .context
+            .avm2
+            .global_domain()
+            .get_defined_value(self, class_name)?
+            .coerce_to_object(self)?;
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                self,
+            )?
+            .coerce_to_object(self)?;
+
+        let message = AvmString::new(self.context.gc_context, error.message());
+        let args: [Value<'gc>; 2] = [message.into(), error.id().into()];
+        let object = proto.construct(self, &args)?;
+        constr.call(Some(object), &args, self, object.proto())?;
+
+        Ok(object)
     }
 
     /// Run a single action from a given action reader.
@@ -434,6 +580,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::PushUndefined => self.op_push_undefined(),
                 Op::Pop => self.op_pop(),
                 Op::Dup => self.op_dup(),
+                Op::Swap => self.op_swap(),
+                Op::Throw => self.op_throw(),
                 Op::GetLocal { index } => self.op_get_local(index),
                 Op::SetLocal { index } => self.op_set_local(index),
                 Op::Kill { index } => self.op_kill(index),
@@ -459,6 +607,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::SetProperty { index } => self.op_set_property(method, index),
                 Op::InitProperty { index } => self.op_init_property(method, index),
                 Op::DeleteProperty { index } => self.op_delete_property(method, index),
+                Op::GetDescendants { index } => self.op_get_descendants(method, index),
                 Op::GetSuper { index } => self.op_get_super(method, index),
                 Op::SetSuper { index } => self.op_set_super(method, index),
                 Op::In => self.op_in(),
@@ -479,11 +628,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                     self.op_construct_prop(method, index, num_args)
                 }
                 Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
-                Op::NewActivation => self.op_new_activation(),
+                Op::NewActivation => self.op_new_activation(method),
+                Op::NewCatch { index } => self.op_new_catch(method, index),
                 Op::NewObject { num_args } => self.op_new_object(num_args),
                 Op::NewFunction { index } => self.op_new_function(method, index),
                 Op::NewClass { index } => self.op_new_class(method, index),
                 Op::NewArray { num_args } => self.op_new_array(num_args),
+                Op::Coerce { index } => self.op_coerce(method, index),
                 Op::CoerceA => self.op_coerce_a(),
                 Op::CoerceS => self.op_coerce_s(),
                 Op::ConvertB => self.op_convert_b(),
@@ -557,6 +708,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 } => self.op_debug(method, is_local_register, register_name, register),
                 Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
                 Op::DebugLine { line_num } => self.op_debug_line(line_num),
+                Op::Dxns { index } => self.op_dxns(method, index),
+                Op::DxnsLate => self.op_dxns_late(),
                 _ => self.unknown_op(op),
             };
 
@@ -690,6 +843,23 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_throw(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        self.context.avm2.thrown_value = Some(value);
+
+        Err(ThrownValue.into())
+    }
+
+    fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let a = self.context.avm2.pop();
+        let b = self.context.avm2.pop();
+
+        self.context.avm2.push(a);
+        self.context.avm2.push(b);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
         self.context.avm2.push(self.local_register(register_index)?);
         Ok(FrameControl::Continue)
@@ -824,6 +994,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             scope,
             self.context.avm2.prototypes().function,
             None,
+            None,
         );
         let value = function.call(Some(receiver), &args, self, receiver.proto())?;
 
@@ -912,23 +1083,35 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        let name = match object.resolve_multiname(&multiname)? {
+            Some(name) => name,
+            None => {
+                // Names that are not visible from here (e.g. `internal` names of
+                // another package) fail to resolve, and sealed objects must not
+                // pretend that they exist.
+                if let Some(class) = object.as_proto_class() {
+                    let class = class.read();
+                    if class.is_sealed() {
+                        return Err(NativeError::reference_error(
+                            1069,
+                            format!(
+                                "Property {} not found on {} and there is no default value.",
+                                multiname.local_name().unwrap_or_else(|| "*".into()),
+                                class.name().to_qualified_name()
+                            ),
+                        )
+                        .into());
+                    }
+                }
 
-        // Special case for dynamic properties as scripts may attempt to get
-        // dynamic properties not yet set
-        if name.is_err()
-            && !object
-                .as_proto_class()
-                .map(|c| c.read().is_sealed())
-                .unwrap_or(false)
-        {
-            self.context.avm2.push(Value::Undefined);
-            return Ok(FrameControl::Continue);
-        }
+                // Special case for dynamic properties as scripts may attempt to get
+                // dynamic properties not yet set
+                self.context.avm2.push(Value::Undefined);
+                return Ok(FrameControl::Continue);
+            }
+        };
 
-        let value = object.get_property(object, &name?, self)?;
+        let value = object.get_property(object, &name, self)?;
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
@@ -946,12 +1129,28 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
         } else {
-            //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
             let local_name: Result<AvmString<'gc>, Error> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
-            let name = QName::dynamic_name(local_name?);
+            let local_name = local_name?;
+
+            if let Some(class) = object.as_proto_class() {
+                let class = class.read();
+                if class.is_sealed() {
+                    return Err(NativeError::reference_error(
+                        1056,
+                        format!(
+                            "Cannot create property {} on {}.",
+                            local_name,
+                            class.name().to_qualified_name()
+                        ),
+                    )
+                    .into());
+                }
+            }
+
+            let name = QName::dynamic_name(local_name);
             object.set_property(object, &name, value, self)?;
         }
 
@@ -1007,6 +1206,44 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
+
+        let nodes: Result<Vec<_>, Error> = object
+            .as_xml_nodes()
+            .map(|nodes| nodes.clone())
+            .ok_or_else(|| {
+                "TypeError: Error #1016: Descendants operator (..) is not supported on this type."
+                    .into()
+            });
+
+        let default_namespace = self.default_xml_namespace();
+        let mut descendants = Vec::new();
+        for node in nodes? {
+            xml::descendants(
+                self.context.gc_context,
+                node,
+                &multiname,
+                default_namespace.as_deref(),
+                &mut descendants,
+            );
+        }
+
+        let xml_list_proto = self.context.avm2.prototypes().xml_list;
+        self.context.avm2.push(XmlObject::from_nodes(
+            descendants,
+            xml_list_proto,
+            self.context.gc_context,
+        ));
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1105,19 +1342,27 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_scope_object(&mut self, index: u8) -> Result<FrameControl<'gc>, Error> {
+        // Scope objects are indexed from the bottom of this activation's own
+        // scope stack, not counting the scopes it was entered with.
+        let mut local_scopes = Vec::new();
         let mut scope = self.scope();
-
-        while index > 0 {
-            if let Some(child_scope) = scope {
-                scope = child_scope.read().parent_cell();
+        while let Some(child_scope) = scope {
+            if let Some(outer_scope) = self.outer_scope {
+                if GcCell::ptr_eq(child_scope, outer_scope) {
+                    break;
+                }
             }
 
-            index -= 1;
+            local_scopes.push(child_scope);
+            scope = child_scope.read().parent_cell();
         }
 
         self.context.avm2.push(
-            scope
+            local_scopes
+                .iter()
+                .rev()
+                .nth(index as usize)
                 .map(|s| s.read().locals().clone().into())
                 .unwrap_or(Value::Undefined),
         );
@@ -1210,6 +1455,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
+        self.check_slot_access(object, index)?;
         let value = object.get_slot(index)?;
 
         self.context.avm2.push(value);
@@ -1221,11 +1467,69 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop();
 
+        self.check_slot_access(object, index)?;
         object.set_slot(index, value, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
+    /// Check that the current method is allowed to access a slot of an object.
+    ///
+    /// Slots declared in a class's `protected` namespace may only be accessed
+    /// from methods of that class or one of its subclasses.
+    fn check_slot_access(&self, object: Object<'gc>, index: u32) -> Result<(), Error> {
+        let mut proto = object.proto();
+        while let Some(p) = proto {
+            if let Some(class) = p.as_class() {
+                let class = class.read();
+                let slot_trait = class.instance_traits().iter().find(|t| match t.kind() {
+                    TraitKind::Slot { slot_id, .. } | TraitKind::Const { slot_id, .. } => {
+                        *slot_id == index
+                    }
+                    _ => false,
+                });
+
+                if let Some(slot_trait) = slot_trait {
+                    let ns = slot_trait.name().namespace();
+                    if matches!(ns, Namespace::Protected(_)) && !self.is_protected_visible(ns) {
+                        return Err(NativeError::reference_error(
+                            1069,
+                            format!(
+                                "Property {} not found on {} and there is no default value.",
+                                slot_trait.name().local_name(),
+                                class.name().to_qualified_name()
+                            ),
+                        )
+                        .into());
+                    }
+
+                    return Ok(());
+                }
+            }
+
+            proto = p.proto();
+        }
+
+        Ok(())
+    }
+
+    /// Determine if the given `protected` namespace belongs to the class of the
+    /// current method or one of its superclasses.
+    fn is_protected_visible(&self, ns: &Namespace<'gc>) -> bool {
+        let mut proto = self.base_proto;
+        while let Some(p) = proto {
+            if let Some(class) = p.as_class() {
+                if class.read().protected_namespace() == Some(ns) {
+                    return true;
+                }
+            }
+
+            proto = p.proto();
+        }
+
+        false
+    }
+
     fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.scope.unwrap().read().globals().get_slot(index)?;
 
@@ -1320,10 +1624,64 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error> {
-        self.context
-            .avm2
-            .push(ScriptObject::bare_object(self.context.gc_context));
+    fn op_new_activation(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let mut activation_object = ScriptObject::bare_object(self.context.gc_context);
+
+        // The activation holds the method's captured locals as slots, so that
+        // closures created by `newfunction` share them through the scope chain.
+        if let Some(body) = method.body() {
+            let scope = self.scope();
+            for abc_trait in body.traits.iter() {
+                let trait_entry = Trait::from_abc_trait(
+                    method.translation_unit(),
+                    abc_trait,
+                    self.context.avm2,
+                    self.context.gc_context,
+                )?;
+
+                activation_object.install_foreign_trait(
+                    self,
+                    trait_entry,
+                    scope,
+                    activation_object,
+                )?;
+            }
+        }
+
+        self.context.avm2.push(activation_object);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_new_catch(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcException>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let variable_name: Result<_, Error> = method
+            .body()
+            .and_then(|body| body.exceptions.get(index.0 as usize))
+            .map(|exception| exception.variable_name.0)
+            .ok_or_else(|| format!("Exception index {} does not exist", index.0).into());
+        let variable_name = variable_name?;
+
+        // The catch scope holds the caught error in its first slot, named
+        // after the variable of the `catch` block. Despite its type, the
+        // variable name is a multiname index.
+        let mut catch_object = ScriptObject::bare_object(self.context.gc_context);
+        if variable_name != 0 {
+            let name = QName::from_abc_multiname(
+                method.translation_unit(),
+                Index::new(variable_name),
+                self.context.gc_context,
+            )?;
+            catch_object.install_slot(self.context.gc_context, name, 1, Value::Undefined);
+        }
+
+        self.context.avm2.push(catch_object);
 
         Ok(FrameControl::Continue)
     }
@@ -1365,6 +1723,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             scope,
             self.context.avm2.prototypes().function,
             None,
+            None,
         );
         let es3_proto = ScriptObject::object(
             self.context.gc_context,
@@ -1396,12 +1755,33 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         };
 
         let class_entry = self.table_class(method, index)?;
+        if class_entry.read().state() == ClassState::Initializing {
+            // The class initializer asked for its own class again. Flash
+            // Player doesn't initialize a class twice; the class doesn't
+            // exist yet as far as the initializer can tell, so it's null.
+            avm_debug!(
+                self.context.avm2,
+                "Class {:?} requested during its own initialization",
+                class_entry.read().name().local_name()
+            );
+            self.context.avm2.push(Value::Null);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let scope = self.scope();
 
         let (new_class, class_init) =
             FunctionObject::from_class(self, class_entry, base_class, scope)?;
 
-        class_init.call(Some(new_class), &[], self, None)?;
+        class_entry
+            .write(self.context.gc_context)
+            .set_state(ClassState::Initializing);
+        let result = class_init.call(Some(new_class), &[], self, None);
+        class_entry
+            .write(self.context.gc_context)
+            .set_state(ClassState::Initialized);
+        result?;
 
         self.context.avm2.push(new_class);
 
@@ -1422,6 +1802,74 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_coerce(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        type_name_index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        // Index zero is the any type, which every value already is.
+        if type_name_index.0 == 0 {
+            return Ok(FrameControl::Continue);
+        }
+
+        let type_name =
+            self.pool_multiname_static(method, type_name_index, self.context.gc_context)?;
+        let primitive_name = type_name
+            .local_name()
+            .filter(|_| type_name.namespace_set().any(|ns| ns.is_public()));
+        let value = self.context.avm2.pop();
+
+        let coerced = match primitive_name.as_ref().map(|name| &name[..]) {
+            Some("String") => match value {
+                Value::Undefined | Value::Null => Value::Null,
+                _ => value.coerce_to_string(self)?.into(),
+            },
+            Some("Boolean") => value.coerce_to_boolean().into(),
+            Some("Number") => value.coerce_to_number(self)?.into(),
+            Some("int") => value.coerce_to_i32(self)?.into(),
+            Some("uint") => value.coerce_to_u32(self)?.into(),
+            _ => match value {
+                Value::Undefined | Value::Null => Value::Null,
+                _ => {
+                    let type_object = if let Some(scope) = self.scope() {
+                        scope.read().find(&type_name, self)?
+                    } else {
+                        None
+                    };
+                    let type_object = match type_object {
+                        Some(type_object) => type_object,
+                        None => {
+                            return Err(format!(
+                                "Attempted to coerce to nonexistent type {:?}",
+                                type_name
+                            )
+                            .into())
+                        }
+                    };
+
+                    let object = value.coerce_to_object(self)?;
+                    if !object.is_instance_of(self, type_object, true)? {
+                        return Err(NativeError::type_error(
+                            1034,
+                            format!(
+                                "Type Coercion failed: cannot convert {} to {}.",
+                                value.coerce_to_string(self)?,
+                                type_name.local_name().unwrap_or_else(|| "*".into())
+                            ),
+                        )
+                        .into());
+                    }
+
+                    value
+                }
+            },
+        };
+
+        self.context.avm2.push(coerced);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error> {
         Ok(FrameControl::Continue)
     }
@@ -2162,6 +2610,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let namespace = self.pool_string(&method, index, self.context.gc_context)?;
+
+        self.default_xml_namespace = Some(namespace);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let namespace = self.context.avm2.pop().coerce_to_string(self)?;
+
+        self.default_xml_namespace = Some(namespace);
+
+        Ok(FrameControl::Continue)
+    }
+
     #[allow(unused_variables)]
     #[cfg(avm_debug)]
     fn op_debug(
@@ -0,0 +1,751 @@
+//! AMF3 serialization of AVM2 values
+//!
+//! AMF3 is the binary format used by AVM2 to persist values, such as the data
+//! of local `SharedObject`s. Only the subset of the format that can be
+//! produced from the values we support is implemented: objects, arrays, byte
+//! arrays, and primitives.
+//!
+//! Instances of classes registered with `registerClassAlias` are written with
+//! their alias, and read back as instances of the same class. Classes that
+//! implement `IExternalizable` serialize themselves with `writeExternal` and
+//! `readExternal`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, ByteArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::TraitKind;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use std::collections::HashMap;
+
+const MARKER_UNDEFINED: u8 = 0x00;
+const MARKER_NULL: u8 = 0x01;
+const MARKER_FALSE: u8 = 0x02;
+const MARKER_TRUE: u8 = 0x03;
+const MARKER_INTEGER: u8 = 0x04;
+const MARKER_DOUBLE: u8 = 0x05;
+const MARKER_STRING: u8 = 0x06;
+const MARKER_ARRAY: u8 = 0x09;
+const MARKER_OBJECT: u8 = 0x0A;
+const MARKER_BYTE_ARRAY: u8 = 0x0C;
+
+/// The smallest value that can be stored as an AMF3 integer.
+const MIN_INTEGER: i32 = -(1 << 28);
+
+/// The largest value that can be stored as an AMF3 integer.
+const MAX_INTEGER: i32 = (1 << 28) - 1;
+
+/// Encodes AVM2 values as AMF3.
+///
+/// Strings, objects and object traits that occur more than once are written
+/// as references to their first occurrence, which also allows cyclic object
+/// graphs to be written.
+pub struct Amf3Writer<'gc> {
+    bytes: Vec<u8>,
+    strings: HashMap<String, usize>,
+    objects: Vec<Object<'gc>>,
+
+    /// The prototypes of every class whose traits have been written.
+    traits: Vec<Object<'gc>>,
+}
+
+impl<'gc> Amf3Writer<'gc> {
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            strings: HashMap::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// Retrieve everything that has been written so far.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    /// Write a variable-length 29-bit unsigned integer.
+    fn write_u29(&mut self, value: u32) {
+        let value = value & 0x1FFF_FFFF;
+
+        if value < 0x80 {
+            self.bytes.push(value as u8);
+        } else if value < 0x4000 {
+            self.bytes.push(((value >> 7) | 0x80) as u8);
+            self.bytes.push((value & 0x7F) as u8);
+        } else if value < 0x20_0000 {
+            self.bytes.push(((value >> 14) | 0x80) as u8);
+            self.bytes.push(((value >> 7) | 0x80) as u8);
+            self.bytes.push((value & 0x7F) as u8);
+        } else {
+            self.bytes.push(((value >> 22) | 0x80) as u8);
+            self.bytes.push(((value >> 15) | 0x80) as u8);
+            self.bytes.push(((value >> 8) | 0x80) as u8);
+            self.bytes.push(value as u8);
+        }
+    }
+
+    /// Write a string without a type marker, such as an object member name.
+    pub fn write_string(&mut self, value: &str) {
+        if value.is_empty() {
+            // The empty string is never sent by reference.
+            self.write_u29(1);
+            return;
+        }
+
+        if let Some(index) = self.strings.get(value) {
+            self.write_u29((*index as u32) << 1);
+            return;
+        }
+
+        self.strings.insert(value.to_string(), self.strings.len());
+        self.write_u29(((value.len() as u32) << 1) | 1);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    /// Write a reference to an object that was already written, if it was.
+    ///
+    /// Otherwise, the object is registered so that later occurrences may
+    /// refer to it, and `false` is returned.
+    fn write_object_reference(&mut self, object: Object<'gc>) -> bool {
+        if let Some(index) = self.objects.iter().position(|o| Object::ptr_eq(*o, object)) {
+            self.write_u29((index as u32) << 1);
+            return true;
+        }
+
+        self.objects.push(object);
+        false
+    }
+
+    /// List the public, enumerable properties of an object.
+    fn public_enumerants(object: Object<'gc>) -> Vec<QName<'gc>> {
+        let mut names = Vec::new();
+        let mut index = 1;
+
+        while let Some(name) = object.get_enumerant_name(index) {
+            if name.namespace().is_public() {
+                names.push(name);
+            }
+            index += 1;
+        }
+
+        names
+    }
+
+    /// Write the members of an object that are not functions.
+    fn write_dynamic_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        for name in Self::public_enumerants(object) {
+            let value = object.get_property(object, &name, activation)?;
+            if let Value::Object(o) = value {
+                if o.as_executable().is_some() {
+                    continue;
+                }
+            }
+
+            self.write_string(&name.local_name());
+            self.write_value(activation, value)?;
+        }
+
+        self.write_string("");
+
+        Ok(())
+    }
+
+    fn write_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<(), Error> {
+        let values: Vec<Value<'gc>> = object
+            .as_array_storage()
+            .map(|a| a.iter().map(|v| v.unwrap_or(Value::Undefined)).collect())
+            .unwrap_or_default();
+
+        self.write_u29(((values.len() as u32) << 1) | 1);
+        self.write_dynamic_members(activation, object)?;
+        for value in values {
+            self.write_value(activation, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Determine how an object, whose class has the given prototype, is
+    /// written.
+    fn traits_for(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        mut proto: Object<'gc>,
+    ) -> Result<Amf3Traits, Error> {
+        let object_proto = activation.context.avm2.prototypes().object;
+        if Object::ptr_eq(proto, object_proto) {
+            return Ok(Amf3Traits::anonymous());
+        }
+
+        let class = proto
+            .get_property(proto, &QName::dynamic_name("constructor"), activation)?
+            .coerce_to_object(activation)?;
+        let class_name = activation
+            .context
+            .avm2
+            .global_domain()
+            .get_alias_for_class(class)
+            .unwrap_or_default();
+
+        let iexternalizable_proto = activation.context.avm2.prototypes().iexternalizable;
+        if object.has_prototype_in_chain(iexternalizable_proto, true)? {
+            return Ok(Amf3Traits {
+                class_name,
+                is_dynamic: false,
+                is_externalizable: true,
+                sealed_members: Vec::new(),
+            });
+        }
+
+        // Sealed members are the public variables and read-write accessors
+        // of the class and its superclasses, superclasses first.
+        let mut sealed_members = Vec::new();
+        let mut is_dynamic = None;
+        let mut next_proto = Some(proto);
+        while let Some(proto) = next_proto {
+            if Object::ptr_eq(proto, object_proto) {
+                break;
+            }
+
+            if let Some(class) = proto.as_class() {
+                let class = class.read();
+                is_dynamic.get_or_insert(!class.is_sealed());
+
+                let traits = class.instance_traits();
+                let mut class_members = Vec::new();
+                for instance_trait in traits {
+                    if !instance_trait.name().namespace().is_public() {
+                        continue;
+                    }
+
+                    let name = instance_trait.name().local_name().to_string();
+                    let is_member = match instance_trait.kind() {
+                        TraitKind::Slot { .. } => true,
+                        TraitKind::Getter { .. } => traits.iter().any(|t| {
+                            matches!(t.kind(), TraitKind::Setter { .. })
+                                && t.name() == instance_trait.name()
+                        }),
+                        _ => false,
+                    };
+
+                    if is_member && !class_members.contains(&name) {
+                        class_members.push(name);
+                    }
+                }
+
+                class_members.append(&mut sealed_members);
+                sealed_members = class_members;
+            }
+
+            next_proto = proto.proto();
+        }
+
+        Ok(Amf3Traits {
+            class_name,
+            is_dynamic: is_dynamic.unwrap_or(true),
+            is_externalizable: false,
+            sealed_members,
+        })
+    }
+
+    fn write_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        let object_proto = activation.context.avm2.prototypes().object;
+        let proto = object.proto().unwrap_or(object_proto);
+        let traits = Self::traits_for(activation, object, proto)?;
+
+        if let Some(index) = self.traits.iter().position(|p| Object::ptr_eq(*p, proto)) {
+            self.write_u29(((index as u32) << 2) | 0b01);
+        } else {
+            self.traits.push(proto);
+            self.write_u29(
+                ((traits.sealed_members.len() as u32) << 4)
+                    | (traits.is_dynamic as u32) << 3
+                    | (traits.is_externalizable as u32) << 2
+                    | 0b11,
+            );
+            self.write_string(&traits.class_name);
+            for name in traits.sealed_members.iter() {
+                self.write_string(name);
+            }
+        }
+
+        if traits.is_externalizable {
+            // The object writes itself into a scratch `ByteArray`, which is
+            // then copied into our output.
+            // TODO: Objects written by `writeExternal` do not share our
+            // reference tables.
+            let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+            let output = ByteArrayObject::from_storage(
+                ByteArrayStorage::new(),
+                bytearray_proto,
+                activation.context.gc_context,
+            );
+            let write_external = object
+                .get_property(object, &QName::dynamic_name("writeExternal"), activation)?
+                .coerce_to_object(activation)?;
+            write_external.call(Some(object), &[output.into()], activation, object.proto())?;
+
+            let bytes = output.as_bytearray().unwrap().bytes().to_vec();
+            self.bytes.extend_from_slice(&bytes);
+
+            return Ok(());
+        }
+
+        for name in traits.sealed_members.iter() {
+            let name = AvmString::new(activation.context.gc_context, name.clone());
+            let value = object.get_property(
+                object,
+                &QName::new(Namespace::public_namespace(), name),
+                activation,
+            )?;
+            self.write_value(activation, value)?;
+        }
+
+        if traits.is_dynamic {
+            self.write_dynamic_members(activation, object)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a value, along with its type marker.
+    pub fn write_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.write_u8(MARKER_UNDEFINED),
+            Value::Null => self.write_u8(MARKER_NULL),
+            Value::Bool(false) => self.write_u8(MARKER_FALSE),
+            Value::Bool(true) => self.write_u8(MARKER_TRUE),
+            Value::Integer(i) if (MIN_INTEGER..=MAX_INTEGER).contains(&i) => {
+                self.write_u8(MARKER_INTEGER);
+                self.write_u29(i as u32);
+            }
+            Value::Unsigned(u) if u <= MAX_INTEGER as u32 => {
+                self.write_u8(MARKER_INTEGER);
+                self.write_u29(u);
+            }
+            Value::Integer(_) | Value::Unsigned(_) | Value::Number(_) => {
+                let number = value.coerce_to_number(activation)?;
+                self.write_u8(MARKER_DOUBLE);
+                self.bytes.extend_from_slice(&number.to_be_bytes());
+            }
+            Value::String(s) => {
+                self.write_u8(MARKER_STRING);
+                self.write_string(&s);
+            }
+            Value::Object(o) if o.as_executable().is_some() => {
+                // Functions cannot be serialized.
+                self.write_u8(MARKER_UNDEFINED);
+            }
+            Value::Object(o) => {
+                let marker = if o.as_bytearray().is_some() {
+                    MARKER_BYTE_ARRAY
+                } else if o.as_array_storage().is_some() {
+                    MARKER_ARRAY
+                } else {
+                    MARKER_OBJECT
+                };
+
+                self.write_u8(marker);
+                if self.write_object_reference(o) {
+                    return Ok(());
+                }
+
+                match marker {
+                    MARKER_BYTE_ARRAY => {
+                        let bytes = o.as_bytearray().unwrap().bytes().to_vec();
+                        self.write_u29(((bytes.len() as u32) << 1) | 1);
+                        self.bytes.extend_from_slice(&bytes);
+                    }
+                    MARKER_ARRAY => self.write_array(activation, o)?,
+                    _ => self.write_object(activation, o)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'gc> Default for Amf3Writer<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The traits of an object written to or read from AMF3.
+#[derive(Clone)]
+struct Amf3Traits {
+    /// The alias of the object's class, or the empty string for anonymous
+    /// objects.
+    class_name: String,
+    is_dynamic: bool,
+    is_externalizable: bool,
+    sealed_members: Vec<String>,
+}
+
+impl Amf3Traits {
+    /// The traits of a plain `Object`.
+    fn anonymous() -> Self {
+        Self {
+            class_name: String::new(),
+            is_dynamic: true,
+            is_externalizable: false,
+            sealed_members: Vec::new(),
+        }
+    }
+}
+
+/// Decodes AMF3 into AVM2 values.
+pub struct Amf3Reader<'a, 'gc> {
+    bytes: &'a [u8],
+    position: usize,
+    strings: Vec<String>,
+    objects: Vec<Object<'gc>>,
+    traits: Vec<Amf3Traits>,
+}
+
+impl<'a, 'gc> Amf3Reader<'a, 'gc> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// The number of bytes read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Whether or not every byte has been read.
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("Unexpected end of AMF3 data")?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Read a variable-length 29-bit unsigned integer.
+    fn read_u29(&mut self) -> Result<u32, Error> {
+        let mut value = 0;
+
+        for _ in 0..3 {
+            let byte = self.read_u8()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Ok((value << 8) | self.read_u8()? as u32)
+    }
+
+    /// Read a string without a type marker, such as an object member name.
+    pub fn read_string(&mut self) -> Result<String, Error> {
+        let header = self.read_u29()?;
+        if header & 1 == 0 {
+            return self
+                .strings
+                .get((header >> 1) as usize)
+                .cloned()
+                .ok_or_else(|| "Invalid AMF3 string reference".into());
+        }
+
+        let bytes = self.read_bytes((header >> 1) as usize)?;
+        let string = String::from_utf8_lossy(bytes).into_owned();
+        if !string.is_empty() {
+            self.strings.push(string.clone());
+        }
+
+        Ok(string)
+    }
+
+    /// Read the header of an object, returning either the previously read
+    /// object it refers to, or the header's remaining bits.
+    fn read_object_header(&mut self) -> Result<Result<Object<'gc>, u32>, Error> {
+        let header = self.read_u29()?;
+        if header & 1 == 0 {
+            return self
+                .objects
+                .get((header >> 1) as usize)
+                .copied()
+                .map(Ok)
+                .ok_or_else(|| "Invalid AMF3 object reference".into());
+        }
+
+        Ok(Err(header >> 1))
+    }
+
+    /// Set a public property of an object that is being read.
+    fn set_member(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+        name: String,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        let name = AvmString::new(activation.context.gc_context, name);
+        object.set_property(
+            object,
+            &QName::new(Namespace::public_namespace(), name),
+            value,
+            activation,
+        )
+    }
+
+    /// Read name-value pairs into an object until an empty name is found.
+    fn read_dynamic_members(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<(), Error> {
+        loop {
+            let name = self.read_string()?;
+            if name.is_empty() {
+                return Ok(());
+            }
+
+            let value = self.read_value(activation)?;
+            Self::set_member(activation, object, name, value)?;
+        }
+    }
+
+    fn read_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let length = match self.read_object_header()? {
+            Ok(array) => return Ok(array.into()),
+            Err(length) => length as usize,
+        };
+
+        let array_proto = activation.context.avm2.prototypes().array;
+        let array = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            array_proto,
+            activation.context.gc_context,
+        );
+        self.objects.push(array);
+
+        self.read_dynamic_members(activation, array)?;
+        for _ in 0..length {
+            let value = self.read_value(activation)?;
+            if let Some(mut storage) = array.as_array_storage_mut(activation.context.gc_context) {
+                storage.push(value);
+            }
+        }
+
+        Ok(array.into())
+    }
+
+    /// Construct an object to read values into.
+    ///
+    /// Objects of registered classes are constructed by calling their
+    /// constructor with no arguments. Objects of unknown classes become plain
+    /// objects.
+    fn construct_object(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &str,
+    ) -> Result<Object<'gc>, Error> {
+        let class = if class_name.is_empty() {
+            None
+        } else {
+            activation
+                .context
+                .avm2
+                .global_domain()
+                .get_class_by_alias(class_name)
+        };
+
+        if let Some(mut class) = class {
+            let proto = class
+                .get_property(class, &QName::dynamic_name("prototype"), activation)?
+                .coerce_to_object(activation)?;
+            let object = proto.construct(activation, &[])?;
+            class.call(Some(object), &[], activation, object.proto())?;
+
+            return Ok(object);
+        }
+
+        let object_proto = activation.context.avm2.prototypes().object;
+        Ok(ScriptObject::object(
+            activation.context.gc_context,
+            object_proto,
+        ))
+    }
+
+    /// Let an externalizable object read itself from the remaining input.
+    fn read_external(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+        class_name: &str,
+    ) -> Result<(), Error> {
+        let iexternalizable_proto = activation.context.avm2.prototypes().iexternalizable;
+        if !object.has_prototype_in_chain(iexternalizable_proto, true)? {
+            return Err(format!("ArgumentError: Error #2173: Unable to read object in stream. The class {} does not implement flash.utils.IExternalizable but is aliased to an externalizable class.", class_name).into());
+        }
+
+        // The object reads itself from a `ByteArray` holding the rest of our
+        // input; afterwards, we skip over whatever it read.
+        let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+        let input = ByteArrayObject::from_storage(
+            ByteArrayStorage::from_vec(self.bytes[self.position..].to_vec()),
+            bytearray_proto,
+            activation.context.gc_context,
+        );
+        let read_external = object
+            .get_property(object, &QName::dynamic_name("readExternal"), activation)?
+            .coerce_to_object(activation)?;
+        read_external.call(Some(object), &[input.into()], activation, object.proto())?;
+
+        let read_len = input.as_bytearray().unwrap().position();
+        self.read_bytes(read_len)?;
+
+        Ok(())
+    }
+
+    fn read_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let header = match self.read_object_header()? {
+            Ok(object) => return Ok(object.into()),
+            Err(header) => header,
+        };
+
+        let traits = if header & 1 == 0 {
+            self.traits
+                .get((header >> 1) as usize)
+                .cloned()
+                .ok_or("Invalid AMF3 traits reference")?
+        } else {
+            let class_name = self.read_string()?;
+            let sealed_count = header >> 3;
+            let mut sealed_members = Vec::new();
+            for _ in 0..sealed_count {
+                sealed_members.push(self.read_string()?);
+            }
+
+            let traits = Amf3Traits {
+                class_name,
+                is_dynamic: header & 0b100 != 0,
+                is_externalizable: header & 0b10 != 0,
+                sealed_members,
+            };
+            self.traits.push(traits.clone());
+            traits
+        };
+
+        let object = Self::construct_object(activation, &traits.class_name)?;
+        self.objects.push(object);
+
+        if traits.is_externalizable {
+            self.read_external(activation, object, &traits.class_name)?;
+
+            return Ok(object.into());
+        }
+
+        for name in traits.sealed_members {
+            let value = self.read_value(activation)?;
+            Self::set_member(activation, object, name, value)?;
+        }
+
+        if traits.is_dynamic {
+            self.read_dynamic_members(activation, object)?;
+        }
+
+        Ok(object.into())
+    }
+
+    fn read_byte_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let length = match self.read_object_header()? {
+            Ok(bytearray) => return Ok(bytearray.into()),
+            Err(length) => length as usize,
+        };
+
+        let bytes = self.read_bytes(length)?.to_vec();
+        let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+        let bytearray = ByteArrayObject::from_storage(
+            ByteArrayStorage::from_vec(bytes),
+            bytearray_proto,
+            activation.context.gc_context,
+        );
+        self.objects.push(bytearray);
+
+        Ok(bytearray.into())
+    }
+
+    /// Read a value, along with its type marker.
+    pub fn read_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        match self.read_u8()? {
+            MARKER_UNDEFINED => Ok(Value::Undefined),
+            MARKER_NULL => Ok(Value::Null),
+            MARKER_FALSE => Ok(false.into()),
+            MARKER_TRUE => Ok(true.into()),
+            MARKER_INTEGER => {
+                // Sign-extend the 29-bit value.
+                let value = ((self.read_u29()? << 3) as i32) >> 3;
+                Ok(value.into())
+            }
+            MARKER_DOUBLE => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.read_bytes(8)?);
+                Ok(f64::from_be_bytes(bytes).into())
+            }
+            MARKER_STRING => {
+                let string = self.read_string()?;
+                Ok(AvmString::new(activation.context.gc_context, string).into())
+            }
+            MARKER_ARRAY => self.read_array(activation),
+            MARKER_OBJECT => self.read_object(activation),
+            MARKER_BYTE_ARRAY => self.read_byte_array(activation),
+            marker => Err(format!("Unsupported AMF3 type marker {:#04x}", marker).into()),
+        }
+    }
+}
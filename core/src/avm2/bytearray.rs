@@ -0,0 +1,203 @@
+//! ByteArray support types
+
+use gc_arena::Collect;
+use std::convert::TryInto;
+
+/// The byte order used to read and write multi-byte values.
+#[derive(Clone, Copy, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+impl Endian {
+    /// The name of this byte order, as used by `flash.utils.Endian`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Endian::Big => "bigEndian",
+            Endian::Little => "littleEndian",
+        }
+    }
+}
+
+/// The storage portion of a `ByteArray`.
+///
+/// Reads and writes happen at the current position, which is advanced past
+/// the bytes that were read or written. Writing past the end of the storage
+/// extends it.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct ByteArrayStorage {
+    /// The bytes held by the array.
+    bytes: Vec<u8>,
+
+    /// The current read/write position.
+    position: usize,
+
+    /// The byte order of multi-byte values.
+    endian: Endian,
+}
+
+impl Default for ByteArrayStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ByteArrayStorage {
+    /// Construct new, empty storage.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            position: 0,
+            endian: Endian::Big,
+        }
+    }
+
+    /// Wrap an existing byte buffer.
+    pub fn from_vec(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            endian: Endian::Big,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Resize the storage, filling any new space with zeroes.
+    ///
+    /// The position is clamped to the new length.
+    pub fn set_len(&mut self, len: usize) {
+        self.bytes.resize(len, 0);
+        self.position = self.position.min(len);
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// The number of bytes between the current position and the end.
+    pub fn bytes_available(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    /// Remove all bytes and reset the position.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.position = 0;
+    }
+
+    /// Write bytes at the current position, growing the storage as needed.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        let end = self.position + bytes.len();
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+
+        self.bytes[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+    }
+
+    /// Read bytes from the current position.
+    ///
+    /// Yields `None` if there are not enough bytes available, in which case
+    /// the position is not changed.
+    pub fn read_bytes(&mut self, len: usize) -> Option<&[u8]> {
+        if self.bytes_available() < len {
+            return None;
+        }
+
+        let start = self.position;
+        self.position += len;
+
+        Some(&self.bytes[start..self.position])
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn read_u8(&mut self) -> Option<u8> {
+        self.read_bytes(1).map(|b| b[0])
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Option<u16> {
+        let bytes = self.read_bytes(2)?.try_into().ok()?;
+
+        Some(match self.endian {
+            Endian::Big => u16::from_be_bytes(bytes),
+            Endian::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn read_u32(&mut self) -> Option<u32> {
+        let bytes = self.read_bytes(4)?.try_into().ok()?;
+
+        Some(match self.endian {
+            Endian::Big => u32::from_be_bytes(bytes),
+            Endian::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32(value.to_bits());
+    }
+
+    pub fn read_f32(&mut self) -> Option<f32> {
+        self.read_u32().map(f32::from_bits)
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        match self.endian {
+            Endian::Big => self.write_bytes(&value.to_be_bytes()),
+            Endian::Little => self.write_bytes(&value.to_le_bytes()),
+        }
+    }
+
+    pub fn read_f64(&mut self) -> Option<f64> {
+        let bytes = self.read_bytes(8)?.try_into().ok()?;
+
+        Some(match self.endian {
+            Endian::Big => f64::from_be_bytes(bytes),
+            Endian::Little => f64::from_le_bytes(bytes),
+        })
+    }
+}
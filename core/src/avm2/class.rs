@@ -5,7 +5,7 @@ use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
-use crate::avm2::{Avm2, Error};
+use crate::avm2::{Avm2, Error, NativeError};
 use crate::collect::CollectWrapper;
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::{Collect, GcCell, MutationContext};
@@ -26,6 +26,23 @@ pub enum ClassAttributes {
     Interface,
 }
 
+/// How far a class has gotten in running its class initializer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum ClassState {
+    /// The class initializer has not been run yet.
+    Uninitialized,
+
+    /// The class initializer is currently running.
+    ///
+    /// Requests for the class made from within its own initializer see this
+    /// state and must not try to initialize it again.
+    Initializing,
+
+    /// The class initializer has finished running.
+    Initialized,
+}
+
 /// A loaded ABC Class which can be used to construct objects with.
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
@@ -69,6 +86,9 @@ pub struct Class<'gc> {
 
     /// Whether or not this `Class` has loaded its traits or not.
     traits_loaded: bool,
+
+    /// Whether or not the class initializer has been run.
+    state: ClassState,
 }
 
 /// Find traits in a list of traits matching a name.
@@ -136,6 +156,7 @@ impl<'gc> Class<'gc> {
                 class_init,
                 class_traits: Vec::new(),
                 traits_loaded: true,
+                state: ClassState::Uninitialized,
             },
         )
     }
@@ -150,6 +171,11 @@ impl<'gc> Class<'gc> {
         self.protected_namespace = Some(ns)
     }
 
+    /// Get the namespace that this class's protected traits are declared in.
+    pub fn protected_namespace(&self) -> Option<&Namespace<'gc>> {
+        self.protected_namespace.as_ref()
+    }
+
     /// Construct a class from a `TranslationUnit` and its class index.
     ///
     /// The returned class will be allocated, but no traits will be loaded. The
@@ -228,6 +254,7 @@ impl<'gc> Class<'gc> {
                 class_init,
                 class_traits: Vec::new(),
                 traits_loaded: false,
+                state: ClassState::Uninitialized,
             },
         ))
     }
@@ -390,6 +417,13 @@ impl<'gc> Class<'gc> {
         None
     }
 
+    /// Enumerate the traits this class provides on its instances.
+    ///
+    /// Traits inherited from superclasses are not included.
+    pub fn instance_traits(&self) -> &[Trait<'gc>] {
+        &self.instance_traits
+    }
+
     /// Get this class's instance initializer.
     pub fn instance_init(&self) -> Method<'gc> {
         self.instance_init.clone()
@@ -400,6 +434,16 @@ impl<'gc> Class<'gc> {
         self.class_init.clone()
     }
 
+    /// Get how far this class has gotten in running its class initializer.
+    pub fn state(&self) -> ClassState {
+        self.state
+    }
+
+    /// Record progress in running this class's class initializer.
+    pub fn set_state(&mut self, state: ClassState) {
+        self.state = state;
+    }
+
     pub fn interfaces(&self) -> &[Multiname<'gc>] {
         &self.interfaces
     }
@@ -412,4 +456,44 @@ impl<'gc> Class<'gc> {
     pub fn is_sealed(&self) -> bool {
         self.attributes.0.contains(ClassAttributes::Sealed)
     }
+
+    /// Determine if this class is final (cannot be subclassed)
+    pub fn is_final(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::Final)
+    }
+
+    /// Check that none of this class's instance traits override a final
+    /// instance trait of the given superclass.
+    ///
+    /// This should be run against every class in the superclass chain when
+    /// the class is defined.
+    pub fn validate_final_overrides(&self, superclass: &Class<'gc>) -> Result<(), Error> {
+        for trait_entry in self.instance_traits.iter() {
+            for super_trait in superclass.instance_traits.iter() {
+                if trait_entry.name() != super_trait.name() {
+                    continue;
+                }
+
+                match (&trait_entry.kind(), &super_trait.kind()) {
+                    (TraitKind::Getter { .. }, TraitKind::Setter { .. }) => continue,
+                    (TraitKind::Setter { .. }, TraitKind::Getter { .. }) => continue,
+                    _ => {}
+                };
+
+                if super_trait.is_final() {
+                    return Err(NativeError::verify_error(
+                        1053,
+                        format!(
+                            "Illegal override of {} in {}.",
+                            trait_entry.name().local_name(),
+                            self.name.local_name()
+                        ),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
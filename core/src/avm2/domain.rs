@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::names::{Multiname, QName};
-use crate::avm2::object::TObject;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -23,6 +23,9 @@ struct DomainData<'gc> {
 
     /// The parent domain.
     parent: Option<Domain<'gc>>,
+
+    /// Classes registered with `registerClassAlias`, by alias name.
+    class_aliases: HashMap<String, Object<'gc>>,
 }
 
 impl<'gc> Domain<'gc> {
@@ -36,6 +39,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: None,
+                class_aliases: HashMap::new(),
             },
         ))
     }
@@ -47,6 +51,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: Some(parent),
+                class_aliases: HashMap::new(),
             },
         ))
     }
@@ -146,4 +151,45 @@ impl<'gc> Domain<'gc> {
 
         Ok(())
     }
+
+    /// Register a class under an alias name, so that serialized instances of
+    /// it may be identified.
+    ///
+    /// Registering a new class under an existing alias replaces the old one.
+    pub fn register_class_alias(
+        self,
+        alias: String,
+        class: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        self.0.write(mc).class_aliases.insert(alias, class);
+    }
+
+    /// Retrieve the class registered under an alias name, in this or any
+    /// parent domain.
+    pub fn get_class_by_alias(self, alias: &str) -> Option<Object<'gc>> {
+        let read = self.0.read();
+
+        if let Some(class) = read.class_aliases.get(alias) {
+            return Some(*class);
+        }
+
+        read.parent
+            .and_then(|parent| parent.get_class_by_alias(alias))
+    }
+
+    /// Retrieve the alias name a class was registered under, in this or any
+    /// parent domain.
+    pub fn get_alias_for_class(self, class: Object<'gc>) -> Option<String> {
+        let read = self.0.read();
+
+        for (alias, registered) in read.class_aliases.iter() {
+            if Object::ptr_eq(*registered, class) {
+                return Some(alias.clone());
+            }
+        }
+
+        read.parent
+            .and_then(|parent| parent.get_alias_for_class(class))
+    }
 }
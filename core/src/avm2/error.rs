@@ -0,0 +1,78 @@
+//! Errors that scripts can catch
+
+use thiserror::Error;
+
+/// An error raised by a native method.
+///
+/// Scripts catch these as an instance of the builtin error class of the same
+/// name, constructed with the error's message and ID.
+#[derive(Error, Debug)]
+#[error("{class_name}: Error #{id}: {message}")]
+pub struct NativeError {
+    /// The name of the error class, in the public namespace.
+    class_name: &'static str,
+
+    id: u32,
+
+    /// The message, without the `Error #id:` prefix.
+    message: String,
+}
+
+impl NativeError {
+    /// Construct a `SecurityError`.
+    pub fn security_error(id: u32, message: impl Into<String>) -> Self {
+        Self {
+            class_name: "SecurityError",
+            id,
+            message: message.into(),
+        }
+    }
+
+    /// Construct a `ReferenceError`.
+    pub fn reference_error(id: u32, message: impl Into<String>) -> Self {
+        Self {
+            class_name: "ReferenceError",
+            id,
+            message: message.into(),
+        }
+    }
+
+    /// Construct a `TypeError`.
+    pub fn type_error(id: u32, message: impl Into<String>) -> Self {
+        Self {
+            class_name: "TypeError",
+            id,
+            message: message.into(),
+        }
+    }
+
+    /// Construct a `VerifyError`.
+    pub fn verify_error(id: u32, message: impl Into<String>) -> Self {
+        Self {
+            class_name: "VerifyError",
+            id,
+            message: message.into(),
+        }
+    }
+
+    pub fn class_name(&self) -> &'static str {
+        self.class_name
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The message as seen by scripts, which includes the error ID.
+    pub fn message(&self) -> String {
+        format!("Error #{}: {}", self.id, self.message)
+    }
+}
+
+/// A value thrown by a script with the `throw` opcode.
+///
+/// Errors can't hold garbage-collected values, so the thrown value itself is
+/// kept on the AVM until a `catch` block picks it up.
+#[derive(Error, Debug)]
+#[error("A script has thrown an error.")]
+pub struct ThrownValue;
@@ -0,0 +1,427 @@
+//! Core event structure
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{DispatchObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// Which phase of event dispatch is currently occurring.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum EventPhase {
+    /// The event has yet to be fired on the target and is descending the
+    /// ancestors of the event target.
+    Capturing,
+
+    /// The event is currently firing on the target.
+    AtTarget,
+
+    /// The event has already fired on the target and is ascending the
+    /// ancestors of the event target.
+    Bubbling,
+}
+
+impl From<EventPhase> for u32 {
+    fn from(event: EventPhase) -> u32 {
+        match event {
+            EventPhase::Capturing => 1,
+            EventPhase::AtTarget => 2,
+            EventPhase::Bubbling => 3,
+        }
+    }
+}
+
+/// How this event is allowed to propagate.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum PropagationMode {
+    /// Propagate events normally.
+    Allow,
+
+    /// Stop capturing or bubbling events.
+    Stop,
+
+    /// Stop running event handlers altogether.
+    StopImmediate,
+}
+
+/// Represents data fields of an event that can be fired on an object that
+/// implements `IEventDispatcher`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct Event<'gc> {
+    /// Whether or not the event "bubbles" - fires on it's parents after it
+    /// fires on the child.
+    bubbles: bool,
+
+    /// Whether or not the event has a default response that an event handler
+    /// can request to not occur.
+    cancelable: bool,
+
+    /// Whether or not the event's default response has been cancelled.
+    cancelled: bool,
+
+    /// Whether or not event propagation has stopped.
+    propagation: PropagationMode,
+
+    /// The object currently having it's event handlers invoked.
+    current_target: Option<Object<'gc>>,
+
+    /// The current event phase.
+    event_phase: EventPhase,
+
+    /// The object this event was dispatched on.
+    target: Option<Object<'gc>>,
+
+    /// The name of the event being triggered.
+    event_type: AvmString<'gc>,
+}
+
+impl<'gc> Event<'gc> {
+    /// Construct a new event of a given type.
+    pub fn new<S>(event_type: S) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        Event {
+            bubbles: false,
+            cancelable: false,
+            cancelled: false,
+            propagation: PropagationMode::Allow,
+            current_target: None,
+            event_phase: EventPhase::AtTarget,
+            target: None,
+            event_type: event_type.into(),
+        }
+    }
+
+    pub fn event_type(&self) -> AvmString<'gc> {
+        self.event_type
+    }
+
+    pub fn set_event_type<S>(&mut self, event_type: S)
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        self.event_type = event_type.into();
+    }
+
+    pub fn is_bubbling(&self) -> bool {
+        self.bubbles
+    }
+
+    pub fn set_bubbles(&mut self, bubbling: bool) {
+        self.bubbles = bubbling;
+    }
+
+    pub fn is_cancelable(&self) -> bool {
+        self.cancelable
+    }
+
+    pub fn set_cancelable(&mut self, cancelable: bool) {
+        self.cancelable = cancelable;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Cancel the event's default response, if it can be cancelled.
+    pub fn cancel(&mut self) {
+        if self.cancelable {
+            self.cancelled = true;
+        }
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation != PropagationMode::Allow
+    }
+
+    pub fn stop_propagation(&mut self) {
+        if self.propagation != PropagationMode::StopImmediate {
+            self.propagation = PropagationMode::Stop;
+        }
+    }
+
+    pub fn is_propagation_stopped_immediately(&self) -> bool {
+        self.propagation == PropagationMode::StopImmediate
+    }
+
+    pub fn stop_immediate_propagation(&mut self) {
+        self.propagation = PropagationMode::StopImmediate;
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.event_phase
+    }
+
+    pub fn set_phase(&mut self, phase: EventPhase) {
+        self.event_phase = phase;
+    }
+
+    pub fn target(&self) -> Option<Object<'gc>> {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: Object<'gc>) {
+        self.target = Some(target)
+    }
+
+    pub fn current_target(&self) -> Option<Object<'gc>> {
+        self.current_target
+    }
+
+    pub fn set_current_target(&mut self, current_target: Object<'gc>) {
+        self.current_target = Some(current_target)
+    }
+}
+
+/// A set of handlers organized by event type, priority, and order added.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct DispatchList<'gc>(BTreeMap<String, Vec<EventHandler<'gc>>>);
+
+impl<'gc> DispatchList<'gc> {
+    /// Construct a new dispatch list.
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Insert an event handler into the dispatch list.
+    ///
+    /// Handlers are kept sorted by priority, with handlers of equal priority
+    /// running in the order they were added. A handler that is already
+    /// registered for the same event type and phase will not be added
+    /// twice.
+    pub fn add_event_listener(
+        &mut self,
+        event: impl Into<String>,
+        priority: i32,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let handlers = self.0.entry(event.into()).or_insert_with(Vec::new);
+        let new_handler = EventHandler::new(handler, priority, use_capture);
+
+        if handlers.iter().any(|h| h.is_same_handler(&new_handler)) {
+            return;
+        }
+
+        let position = handlers
+            .iter()
+            .position(|h| h.priority < priority)
+            .unwrap_or_else(|| handlers.len());
+        handlers.insert(position, new_handler);
+    }
+
+    /// Remove an event handler from the dispatch list.
+    pub fn remove_event_listener(&mut self, event: &str, handler: Object<'gc>, use_capture: bool) {
+        if let Some(handlers) = self.0.get_mut(event) {
+            let old_handler = EventHandler::new(handler, 0, use_capture);
+
+            handlers.retain(|h| !h.is_same_handler(&old_handler));
+        }
+    }
+
+    /// Determine if there are any event listeners for a given event type.
+    pub fn has_event_listener(&self, event: &str) -> bool {
+        self.0
+            .get(event)
+            .map(|handlers| !handlers.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// List all of the handlers that should run for a given event type and
+    /// phase, in the order they should run.
+    pub fn iter_event_handlers<'a>(
+        &'a self,
+        event: &str,
+        use_capture: bool,
+    ) -> impl 'a + Iterator<Item = Object<'gc>> {
+        self.0
+            .get(event)
+            .into_iter()
+            .flatten()
+            .filter(move |h| h.use_capture == use_capture)
+            .map(|h| h.handler)
+    }
+}
+
+impl<'gc> Default for DispatchList<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single instance of an event handler.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct EventHandler<'gc> {
+    /// The event handler to call.
+    handler: Object<'gc>,
+
+    /// The priority of the event handler.
+    priority: i32,
+
+    /// Indicates if this handler should only be called for capturing events
+    /// (when `true`), or if it should only be called for bubbling and
+    /// at-target events (when `false`).
+    use_capture: bool,
+}
+
+impl<'gc> EventHandler<'gc> {
+    fn new(handler: Object<'gc>, priority: i32, use_capture: bool) -> Self {
+        Self {
+            handler,
+            priority,
+            use_capture,
+        }
+    }
+
+    /// Determine if two handlers refer to the same registration.
+    ///
+    /// Priority is not considered, as Flash does not allow the same handler
+    /// to be registered twice with differing priorities.
+    fn is_same_handler(&self, other: &Self) -> bool {
+        Object::ptr_eq(self.handler, other.handler) && self.use_capture == other.use_capture
+    }
+}
+
+/// Retrieve the dispatch list object of an event dispatcher, if it has one.
+///
+/// If `create` is set, a new dispatch list will be attached to dispatchers
+/// that do not yet have one.
+pub fn dispatch_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    create: bool,
+) -> Result<Option<Object<'gc>>, Error> {
+    let name = QName::new(Namespace::Private("ruffle".into()), "dispatch_list");
+
+    match this.get_property(this, &name, activation)? {
+        Value::Object(o) if o.as_dispatch().is_some() => Ok(Some(o)),
+        _ if create => {
+            let dispatch_list = DispatchObject::empty_list(activation.context.gc_context);
+            this.set_property(this, &name, dispatch_list.into(), activation)?;
+
+            Ok(Some(dispatch_list))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Call all of the event handlers on a given target for the current phase.
+fn dispatch_event_to_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+    use_capture: bool,
+) -> Result<(), Error> {
+    let dispatch_list = match dispatch_list(activation, target, false)? {
+        Some(dispatch_list) => dispatch_list,
+        None => return Ok(()),
+    };
+
+    let event_type = event
+        .as_event()
+        .ok_or("Attempted to dispatch a non-event object")?
+        .event_type();
+
+    // Handlers added or removed during dispatch do not affect the current
+    // target, so we take a copy of the list up front.
+    let handlers: Vec<Object<'gc>> = dispatch_list
+        .as_dispatch()
+        .unwrap()
+        .iter_event_handlers(&event_type, use_capture)
+        .collect();
+
+    if let Some(mut evtwrite) = event.as_event_mut(activation.context.gc_context) {
+        evtwrite.set_current_target(target);
+    }
+
+    for handler in handlers {
+        if event
+            .as_event()
+            .map(|e| e.is_propagation_stopped_immediately())
+            .unwrap_or(false)
+        {
+            break;
+        }
+
+        handler.call(None, &[event.into()], activation, None)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch an event on an object, including its capture and bubble phases.
+///
+/// Display objects propagate events through their ancestors; all other
+/// dispatchers only fire events on themselves. The return value indicates if
+/// the event's default action was not cancelled.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<bool, Error> {
+    let mut ancestor_list = Vec::new();
+    let mut parent = this.as_display_object().and_then(|dobj| dobj.parent());
+    while let Some(parent_dobj) = parent {
+        if let Value::Object(parent_obj) = parent_dobj.object2() {
+            ancestor_list.push(parent_obj);
+        }
+
+        parent = parent_dobj.parent();
+    }
+
+    let bubbles = {
+        let mut evtwrite = event
+            .as_event_mut(activation.context.gc_context)
+            .ok_or("Attempted to dispatch a non-event object")?;
+
+        evtwrite.set_target(this);
+        evtwrite.set_phase(EventPhase::Capturing);
+
+        evtwrite.is_bubbling()
+    };
+
+    for ancestor in ancestor_list.iter().rev() {
+        if event.as_event().unwrap().is_propagation_stopped() {
+            break;
+        }
+
+        dispatch_event_to_target(activation, *ancestor, event, true)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::AtTarget);
+
+    if !event.as_event().unwrap().is_propagation_stopped() {
+        dispatch_event_to_target(activation, this, event, false)?;
+    }
+
+    if bubbles {
+        event
+            .as_event_mut(activation.context.gc_context)
+            .unwrap()
+            .set_phase(EventPhase::Bubbling);
+
+        for ancestor in ancestor_list.iter() {
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            dispatch_event_to_target(activation, *ancestor, event, false)?;
+        }
+    }
+
+    let cancelled = event.as_event().unwrap().is_cancelled();
+
+    Ok(!cancelled)
+}
@@ -25,6 +25,12 @@ pub struct BytecodeExecutable<'gc> {
     /// If `None`, then the receiver provided by the caller is used. A
     /// `Some` value indicates a bound executable.
     receiver: Option<Object<'gc>>,
+
+    /// The prototype of the class that this function was defined in, which
+    /// `super` accesses are resolved against.
+    ///
+    /// If `None`, then the base prototype provided by the caller is used.
+    base_proto: Option<Object<'gc>>,
 }
 
 /// Represents code that can be executed by some means.
@@ -54,6 +60,7 @@ impl<'gc> Executable<'gc> {
         method: Method<'gc>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         receiver: Option<Object<'gc>>,
+        base_proto: Option<Object<'gc>>,
         mc: MutationContext<'gc, '_>,
     ) -> Self {
         match method {
@@ -64,6 +71,7 @@ impl<'gc> Executable<'gc> {
                     method,
                     scope,
                     receiver,
+                    base_proto,
                 },
             )),
         }
@@ -90,6 +98,7 @@ impl<'gc> Executable<'gc> {
             }
             Executable::Action(bm) => {
                 let receiver = bm.receiver.or(unbound_reciever);
+                let base_proto = bm.base_proto.or(base_proto);
                 let mut activation = Activation::from_method(
                     activation.context.reborrow(),
                     bm.method,
@@ -113,6 +122,7 @@ impl<'gc> fmt::Debug for Executable<'gc> {
                 .field("method", &be.method)
                 .field("scope", &be.scope)
                 .field("receiver", &be.receiver)
+                .field("base_proto", &be.base_proto)
                 .finish(),
             Self::Native(nf, receiver) => fmt
                 .debug_tuple("Executable::Native")
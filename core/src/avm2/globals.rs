@@ -6,8 +6,9 @@ use crate::avm2::domain::Domain;
 use crate::avm2::method::NativeMethod;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{
-    implicit_deriver, ArrayObject, DomainObject, FunctionObject, NamespaceObject, Object,
-    PrimitiveObject, ScriptObject, StageObject, TObject,
+    implicit_deriver, ArrayObject, BitmapDataObject, ByteArrayObject, DomainObject, EventObject,
+    FunctionObject, NamespaceObject, Object, PrimitiveObject, RegExpObject, ScriptObject,
+    StageObject, TObject, XmlObject,
 };
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
@@ -20,7 +21,8 @@ use std::f64::NAN;
 mod array;
 mod boolean;
 mod class;
-mod flash;
+mod error;
+pub mod flash;
 mod function;
 mod global_scope;
 mod int;
@@ -28,8 +30,15 @@ mod math;
 mod namespace;
 mod number;
 mod object;
+mod reference_error;
+mod regexp;
+mod security_error;
 mod string;
+mod type_error;
 mod r#uint;
+mod verify_error;
+pub mod xml;
+mod xml_list;
 
 fn trace<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -65,10 +74,38 @@ pub struct SystemPrototypes<'gc> {
     pub uint: Object<'gc>,
     pub namespace: Object<'gc>,
     pub array: Object<'gc>,
+    pub xml: Object<'gc>,
+    pub xml_list: Object<'gc>,
     pub movieclip: Object<'gc>,
     pub framelabel: Object<'gc>,
     pub scene: Object<'gc>,
+    pub graphics: Object<'gc>,
     pub application_domain: Object<'gc>,
+    pub event: Object<'gc>,
+    pub sampledataevent: Object<'gc>,
+    pub bytearray: Object<'gc>,
+    pub soundchannel: Object<'gc>,
+    pub soundtransform: Object<'gc>,
+    pub mouseevent: Object<'gc>,
+    pub ioerrorevent: Object<'gc>,
+    pub httpstatusevent: Object<'gc>,
+    pub progressevent: Object<'gc>,
+    pub netstatusevent: Object<'gc>,
+    pub statusevent: Object<'gc>,
+    pub asyncerrorevent: Object<'gc>,
+    pub contextmenuevent: Object<'gc>,
+    pub sharedobject: Object<'gc>,
+    pub iexternalizable: Object<'gc>,
+    pub loaderinfo: Object<'gc>,
+    pub urlvariables: Object<'gc>,
+    pub avm1movie: Object<'gc>,
+    pub worker: Object<'gc>,
+    pub workerdomain: Object<'gc>,
+    pub messagechannel: Object<'gc>,
+    pub rectangle: Object<'gc>,
+    pub colormatrixfilter: Object<'gc>,
+    pub textsnapshot: Object<'gc>,
+    pub stage: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -97,10 +134,38 @@ impl<'gc> SystemPrototypes<'gc> {
             uint: empty,
             namespace: empty,
             array: empty,
+            xml: empty,
+            xml_list: empty,
             movieclip: empty,
             framelabel: empty,
             scene: empty,
+            graphics: empty,
             application_domain: empty,
+            event: empty,
+            sampledataevent: empty,
+            bytearray: empty,
+            soundchannel: empty,
+            soundtransform: empty,
+            mouseevent: empty,
+            ioerrorevent: empty,
+            httpstatusevent: empty,
+            progressevent: empty,
+            netstatusevent: empty,
+            statusevent: empty,
+            asyncerrorevent: empty,
+            contextmenuevent: empty,
+            sharedobject: empty,
+            iexternalizable: empty,
+            loaderinfo: empty,
+            urlvariables: empty,
+            avm1movie: empty,
+            worker: empty,
+            workerdomain: empty,
+            messagechannel: empty,
+            rectangle: empty,
+            colormatrixfilter: empty,
+            textsnapshot: empty,
+            stage: empty,
         }
     }
 }
@@ -245,6 +310,24 @@ fn array_deriver<'gc>(
     ArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
+fn xml_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    XmlObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn regexp_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    RegExpObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 fn stage_deriver<'gc>(
     base_proto: Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -276,6 +359,33 @@ fn appdomain_deriver<'gc>(
     )
 }
 
+fn event_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    EventObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn bitmapdata_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    BitmapDataObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn bytearray_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    ByteArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 /// Add a builtin constant to the global scope.
 fn constant<'gc>(
     mc: MutationContext<'gc, '_>,
@@ -391,6 +501,20 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    sp.xml = class(
+        activation,
+        xml::create_class(mc),
+        xml_deriver,
+        domain,
+        script,
+    )?;
+    sp.xml_list = class(
+        activation,
+        xml_list::create_class(mc),
+        xml_deriver,
+        domain,
+        script,
+    )?;
 
     // At this point we have to hide the fact that we had to create the player
     // globals scope *before* the `Object` class
@@ -411,6 +535,48 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        regexp::create_class(mc),
+        regexp_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        security_error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        reference_error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        type_error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        verify_error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.system`
     activation
@@ -426,8 +592,28 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::system::system::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.events`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .event = class(
+        activation,
+        flash::events::event::create_class(mc),
+        event_deriver,
+        domain,
+        script,
+    )?;
     class(
         activation,
         flash::events::ieventdispatcher::create_interface(mc),
@@ -442,32 +628,169 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
-
-    // package `flash.display`
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sampledataevent = class(
         activation,
-        flash::display::displayobject::create_class(mc),
-        stage_deriver,
+        flash::events::sampledataevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .mouseevent = class(
+        activation,
+        flash::events::mouseevent::create_class(mc),
+        implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::display::interactiveobject::create_class(mc),
+        flash::events::textevent::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::display::displayobjectcontainer::create_class(mc),
+        flash::events::errorevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .ioerrorevent = class(
+        activation,
+        flash::events::ioerrorevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .httpstatusevent = class(
+        activation,
+        flash::events::httpstatusevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .progressevent = class(
+        activation,
+        flash::events::progressevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .netstatusevent = class(
+        activation,
+        flash::events::netstatusevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .statusevent = class(
+        activation,
+        flash::events::statusevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .asyncerrorevent = class(
+        activation,
+        flash::events::asyncerrorevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .contextmenuevent = class(
+        activation,
+        flash::events::contextmenuevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.system`, continued with classes that extend
+    // `EventDispatcher`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .worker = class(
+        activation,
+        flash::system::worker::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .workerdomain = class(
+        activation,
+        flash::system::workerdomain::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::display::sprite::create_class(mc),
+        flash::system::workerstate::create_class(mc),
         implicit_deriver,
         domain,
         script,
@@ -478,9 +801,32 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .movieclip = class(
+        .messagechannel = class(
         activation,
-        flash::display::movieclip::create_class(mc),
+        flash::system::messagechannel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::system::messagechannelstate::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.utils`
+    class(
+        activation,
+        flash::utils::idatainput::create_interface(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::idataoutput::create_interface(mc),
         implicit_deriver,
         domain,
         script,
@@ -491,9 +837,9 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .framelabel = class(
+        .iexternalizable = class(
         activation,
-        flash::display::framelabel::create_class(mc),
+        flash::utils::iexternalizable::create_interface(mc),
         implicit_deriver,
         domain,
         script,
@@ -504,9 +850,388 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .scene = class(
+        .bytearray = class(
         activation,
-        flash::display::scene::create_class(mc),
+        flash::utils::bytearray::create_class(mc),
+        bytearray_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.net`
+    class(
+        activation,
+        flash::net::filefilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::filereference::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::localconnection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sharedobject = class(
+        activation,
+        flash::net::sharedobject::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::url_loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::urlrequest::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .urlvariables = class(
+        activation,
+        flash::net::urlvariables::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.net",
+        "registerClassAlias",
+        flash::net::register_class_alias,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.net",
+        "getClassByAlias",
+        flash::net::get_class_by_alias,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.net",
+        "navigateToURL",
+        flash::net::navigate_to_url,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.net",
+        "sendToURL",
+        flash::net::send_to_url,
+        fn_proto,
+        domain,
+        script,
+    )?;
+
+    // package `flash.geom`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .rectangle = class(
+        activation,
+        flash::geom::rectangle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.filters`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .colormatrixfilter = class(
+        activation,
+        flash::filters::color_matrix_filter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.media`
+    class(
+        activation,
+        flash::media::sound::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .soundchannel = class(
+        activation,
+        flash::media::soundchannel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::media::soundmixer::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .soundtransform = class(
+        activation,
+        flash::media::soundtransform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.display`
+    class(
+        activation,
+        flash::display::displayobject::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::interactiveobject::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::displayobjectcontainer::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::shape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::sprite::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .stage = class(
+        activation,
+        flash::display::stage::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::bitmap::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::bitmapdata::create_class(mc),
+        bitmapdata_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .loaderinfo = class(
+        activation,
+        flash::display::loaderinfo::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .avm1movie = class(
+        activation,
+        flash::display::avm1movie::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::actionscriptversion::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics = class(
+        activation,
+        flash::display::graphics::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .movieclip = class(
+        activation,
+        flash::display::movieclip::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .framelabel = class(
+        activation,
+        flash::display::framelabel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .scene = class(
+        activation,
+        flash::display::scene::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::nativemenu::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::nativemenuitem::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.ui`
+    class(
+        activation,
+        flash::ui::context_menu::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::context_menu_item::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.text`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .textsnapshot = class(
+        activation,
+        flash::text::textsnapshot::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textfield::create_class(mc),
         implicit_deriver,
         domain,
         script,
@@ -0,0 +1,225 @@
+//! `Error` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `Error`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let message = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined => "".into(),
+            message => message.coerce_to_string(activation)?,
+        };
+        let id = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "message"),
+            message.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            id.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.message`'s getter.
+pub fn message<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "message"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.message`'s setter.
+pub fn set_message<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let message = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "message"),
+            message.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.name`'s getter.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "name"),
+            activation,
+        )?;
+        if name == Value::Undefined {
+            return Ok("Error".into());
+        }
+
+        return Ok(name);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.name`'s setter.
+pub fn set_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "name"),
+            name.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.errorID`.
+pub fn error_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.toString`.
+///
+/// This is the error's name, followed by its message if it has one.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if this.is_some() {
+        let name = name(activation, this, &[])?.coerce_to_string(activation)?;
+        let message = message(activation, this, &[])?.coerce_to_string(activation)?;
+
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}: {}", name, message),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Error`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "Error"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "message"),
+        Method::from_builtin(message),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "message"),
+        Method::from_builtin(set_message),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "name"),
+        Method::from_builtin(set_name),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "errorID"),
+        Method::from_builtin(error_id),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
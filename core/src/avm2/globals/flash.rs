@@ -2,4 +2,11 @@
 
 pub mod display;
 pub mod events;
+pub mod filters;
+pub mod geom;
+pub mod media;
+pub mod net;
 pub mod system;
+pub mod text;
+pub mod ui;
+pub mod utils;
@@ -0,0 +1,50 @@
+//! `flash.display.AVM1Movie` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.AVM1Movie`'s instance constructor.
+///
+/// `AVM1Movie`s only exist to represent AVM1 content loaded into an AVM2
+/// movie, and cannot be constructed by user code.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            return Err(
+                "ArgumentError: Error #2012: AVM1Movie class cannot be instantiated.".into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.AVM1Movie`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `AVM1Movie`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::package("flash.display"), "AVM1Movie"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
@@ -0,0 +1,170 @@
+//! `flash.display.Bitmap` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{Bitmap, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Coerce a value to a `BitmapData` object, or `None` for `null`.
+fn coerce_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    match value {
+        Value::Undefined | Value::Null => Ok(None),
+        value => {
+            let object = value.coerce_to_object(activation)?;
+            if object.as_bitmap_data().is_none() {
+                return Err("TypeError: Error #1034: Type Coercion failed: cannot convert to flash.display.BitmapData.".into());
+            }
+
+            Ok(Some(object))
+        }
+    }
+}
+
+/// Implements `flash.display.Bitmap`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let bitmap_data =
+                coerce_to_bitmap_data(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+            let smoothing = args
+                .get(2)
+                .cloned()
+                .unwrap_or(Value::Bool(false))
+                .coerce_to_boolean();
+
+            let new_do = Bitmap::new_with_avm2(activation.context.gc_context, this, bitmap_data);
+            new_do.set_smoothing(activation.context.gc_context, smoothing);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Bitmap`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.bitmapData`'s getter.
+pub fn bitmap_data<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        return Ok(bitmap.bitmap_data().map(Value::from).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.bitmapData`'s setter.
+pub fn set_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let bitmap_data =
+            coerce_to_bitmap_data(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+
+        bitmap.set_bitmap_data(activation.context.gc_context, bitmap_data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.smoothing`'s getter.
+pub fn smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        return Ok(bitmap.smoothing().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.smoothing`'s setter.
+pub fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let smoothing = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        bitmap.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Bitmap`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Bitmap"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bitmapData"),
+        Method::from_builtin(bitmap_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "bitmapData"),
+        Method::from_builtin(set_bitmap_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "smoothing"),
+        Method::from_builtin(smoothing),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "smoothing"),
+        Method::from_builtin(set_smoothing),
+    ));
+
+    class
+}
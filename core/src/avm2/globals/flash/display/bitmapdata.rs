@@ -0,0 +1,412 @@
+//! `flash.display.BitmapData` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::filters;
+use crate::avm2::globals::flash::geom::rectangle;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap_data::BitmapDataStorage;
+use crate::color_transform::ColorTransform;
+use crate::transform::Transform;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// The largest width or height a `BitmapData` may have.
+const MAX_DIMENSION: i32 = 8191;
+
+/// Implements `flash.display.BitmapData`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let width = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let height = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let transparent = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(true))
+            .coerce_to_boolean();
+        let fill_color = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0xFFFF_FFFFu32.into())
+            .coerce_to_u32(activation)?;
+
+        if width <= 0 || height <= 0 || width > MAX_DIMENSION || height > MAX_DIMENSION {
+            return Err("ArgumentError: Error #2015: Invalid BitmapData.".into());
+        }
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            *storage = BitmapDataStorage::new(width as u32, height as u32, transparent, fill_color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.BitmapData`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.transparent`'s getter.
+pub fn transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.transparent().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Coerce the `x` and `y` arguments of a pixel method.
+fn pixel_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(i32, i32), Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    Ok((x, y))
+}
+
+/// Implements `BitmapData.getPixel`.
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = pixel_position(activation, args)?;
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok((storage.get_pixel32(x, y) & 0x00FF_FFFF).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel32`.
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = pixel_position(activation, args)?;
+        if let Some(storage) = this.as_bitmap_data() {
+            return Ok(storage.get_pixel32(x, y).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel`.
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = pixel_position(activation, args)?;
+        let color = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.set_pixel(x, y, color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel32`.
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = pixel_position(activation, args)?;
+        let color = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.set_pixel32(x, y, color);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read a number property of a script object, or a default if unset.
+fn number_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+    default: f64,
+) -> Result<f64, Error> {
+    match object.get_property(
+        object,
+        &QName::new(Namespace::public_namespace(), name),
+        activation,
+    )? {
+        Value::Undefined => Ok(default),
+        value => value.coerce_to_number(activation),
+    }
+}
+
+/// Read the properties of a `flash.geom.Matrix` into a matrix.
+fn object_to_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Matrix, Error> {
+    Ok(Matrix {
+        a: number_property(activation, object, "a", 1.0)? as f32,
+        b: number_property(activation, object, "b", 0.0)? as f32,
+        c: number_property(activation, object, "c", 0.0)? as f32,
+        d: number_property(activation, object, "d", 1.0)? as f32,
+        tx: Twips::from_pixels(number_property(activation, object, "tx", 0.0)?),
+        ty: Twips::from_pixels(number_property(activation, object, "ty", 0.0)?),
+    })
+}
+
+/// Read the properties of a `flash.geom.ColorTransform` into a color
+/// transform.
+fn object_to_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<ColorTransform, Error> {
+    Ok(ColorTransform {
+        r_mult: number_property(activation, object, "redMultiplier", 1.0)? as f32,
+        g_mult: number_property(activation, object, "greenMultiplier", 1.0)? as f32,
+        b_mult: number_property(activation, object, "blueMultiplier", 1.0)? as f32,
+        a_mult: number_property(activation, object, "alphaMultiplier", 1.0)? as f32,
+        r_add: number_property(activation, object, "redOffset", 0.0)? as f32 / 255.0,
+        g_add: number_property(activation, object, "greenOffset", 0.0)? as f32 / 255.0,
+        b_add: number_property(activation, object, "blueOffset", 0.0)? as f32 / 255.0,
+        a_add: number_property(activation, object, "alphaOffset", 0.0)? as f32 / 255.0,
+    })
+}
+
+/// Implements `BitmapData.draw`.
+///
+/// The source is drawn with the given matrix instead of its own transform.
+/// Blend modes are not supported yet.
+pub fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #2007: Parameter source must be non-null.".into())
+            }
+            source => source.coerce_to_object(activation)?,
+        };
+
+        let mut transform = Transform::default();
+        match args.get(1).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {}
+            matrix => {
+                let matrix = matrix.coerce_to_object(activation)?;
+                transform.matrix = object_to_matrix(activation, matrix)?;
+            }
+        }
+        match args.get(2).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {}
+            color_transform => {
+                let color_transform = color_transform.coerce_to_object(activation)?;
+                transform.color_transform = object_to_color_transform(activation, color_transform)?;
+            }
+        }
+
+        if let Some(source) = source.as_display_object() {
+            if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+                storage.draw(source, &transform);
+            }
+        } else {
+            log::warn!("BitmapData.draw: Only display objects can be drawn");
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.applyFilter`.
+pub fn apply_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut objects = Vec::with_capacity(4);
+        for (i, name) in ["sourceBitmapData", "sourceRect", "destPoint", "filter"]
+            .iter()
+            .enumerate()
+        {
+            match args.get(i).cloned().unwrap_or(Value::Undefined) {
+                Value::Undefined | Value::Null => {
+                    return Err(format!(
+                        "TypeError: Error #2007: Parameter {} must be non-null.",
+                        name
+                    )
+                    .into())
+                }
+                value => objects.push(value.coerce_to_object(activation)?),
+            }
+        }
+
+        let source_rect = rectangle::to_rectangle(activation, objects[1])?;
+        let dest_x = number_property(activation, objects[2], "x", 0.0)?;
+        let dest_y = number_property(activation, objects[2], "y", 0.0)?;
+        let filter = match filters::object_to_filter(activation, objects[3])? {
+            Some(filter) => filter,
+            None => {
+                log::warn!("BitmapData.applyFilter: Unsupported filter");
+                return Ok(Value::Undefined);
+            }
+        };
+
+        // The source may be this same bitmap, so read it before writing.
+        let source = match objects[0].as_bitmap_data() {
+            Some(source) => source.clone(),
+            None => {
+                log::warn!("BitmapData.applyFilter: Source is not a BitmapData");
+                return Ok(Value::Undefined);
+            }
+        };
+
+        if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+            storage.apply_filter(
+                &source,
+                (
+                    source_rect.x_min.to_pixels() as i32,
+                    source_rect.y_min.to_pixels() as i32,
+                    (source_rect.x_max - source_rect.x_min).to_pixels() as i32,
+                    (source_rect.y_max - source_rect.y_min).to_pixels() as i32,
+                ),
+                (dest_x as i32, dest_y as i32),
+                &filter,
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `BitmapData`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "BitmapData"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "transparent"),
+        Method::from_builtin(transparent),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getPixel"),
+        Method::from_builtin(get_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getPixel32"),
+        Method::from_builtin(get_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "setPixel"),
+        Method::from_builtin(set_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "setPixel32"),
+        Method::from_builtin(set_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "draw"),
+        Method::from_builtin(draw),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "applyFilter"),
+        Method::from_builtin(apply_filter),
+    ));
+
+    class
+}
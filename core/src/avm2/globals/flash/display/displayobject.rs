@@ -1,13 +1,21 @@
 //! `flash.display.DisplayObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::stage::stage_object;
+use crate::avm2::globals::flash::filters;
+use crate::avm2::globals::flash::geom::rectangle;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{blend_mode_from_name, blend_mode_name, DisplayObject, TDisplayObject};
 use gc_arena::{GcCell, MutationContext};
+use swf::{Color, Matrix, Rectangle};
 
 /// Implements `flash.display.DisplayObject`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -27,13 +35,563 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObject.scale9Grid`'s getter.
+///
+/// Each call returns a new copy of the grid.
+pub fn scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(scaling_grid) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+        .and_then(|mc| mc.scaling_grid())
+    {
+        return Ok(rectangle::from_rectangle(activation, &scaling_grid)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.scale9Grid`'s setter.
+///
+/// The grid is copied out of the given rectangle, and `null` removes it.
+pub fn set_scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let scaling_grid = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => {
+                let object = value.coerce_to_object(activation)?;
+                Some(rectangle::to_rectangle(activation, object)?)
+            }
+        };
+
+        mc.set_scaling_grid(activation.context.gc_context, scaling_grid);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.scrollRect`'s getter.
+///
+/// Each call returns a new copy of the rectangle.
+pub fn scroll_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(scroll_rect) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.scroll_rect())
+    {
+        return Ok(rectangle::from_rectangle(activation, &scroll_rect)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.scrollRect`'s setter.
+///
+/// The rectangle is copied, and `null` removes it.
+pub fn set_scroll_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let scroll_rect = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => {
+                let object = value.coerce_to_object(activation)?;
+                Some(rectangle::to_rectangle(activation, object)?)
+            }
+        };
+
+        dobj.set_scroll_rect(activation.context.gc_context, scroll_rect);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.getBounds`.
+///
+/// The bounds are given in the coordinate space of the target object, or of
+/// this object if there is none. Objects with a `scrollRect` are bounded by it.
+pub fn get_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let target = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => value.coerce_to_object(activation)?.as_display_object(),
+        }
+        .unwrap_or(dobj);
+
+        let matrix = if DisplayObject::ptr_eq(dobj, target) {
+            Matrix::identity()
+        } else {
+            target.global_to_local_matrix() * dobj.local_to_global_matrix()
+        };
+        let bounds = dobj.bounds_with_transform(&matrix);
+        let rect = if bounds.valid {
+            Rectangle {
+                x_min: bounds.x_min,
+                y_min: bounds.y_min,
+                x_max: bounds.x_max,
+                y_max: bounds.y_max,
+            }
+        } else {
+            Rectangle::default()
+        };
+
+        return Ok(rectangle::from_rectangle(activation, &rect)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.width`'s setter.
+pub fn set_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_width = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if new_width >= 0.0 {
+            dobj.set_width(activation.context.gc_context, new_width);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.x`'s getter.
+pub fn x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.x().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.x`'s setter.
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_x(activation.context.gc_context, new_x);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.y`'s getter.
+pub fn y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.y().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.y`'s setter.
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_y = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        dobj.set_y(activation.context.gc_context, new_y);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.stage`.
+///
+/// Objects that aren't on the display list have no stage.
+pub fn stage<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut top = dobj;
+        while let Some(parent) = top.parent() {
+            top = parent;
+        }
+
+        let is_on_stage = activation
+            .context
+            .levels
+            .values()
+            .any(|level| DisplayObject::ptr_eq(*level, top));
+        if is_on_stage {
+            return Ok(stage_object(activation)?.into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.height`'s setter.
+pub fn set_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_height = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        if new_height >= 0.0 {
+            dobj.set_height(activation.context.gc_context, new_height);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.blendMode`'s getter.
+pub fn blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let name = blend_mode_name(dobj.blend_mode());
+        return Ok(AvmString::new(activation.context.gc_context, name).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.blendMode`'s setter.
+pub fn set_blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let blend_mode = blend_mode_from_name(&name).ok_or(
+            "ArgumentError: Error #2008: Parameter blendMode must be one of the accepted values.",
+        )?;
+
+        dobj.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.cacheAsBitmap`'s getter.
+pub fn cache_as_bitmap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        // Objects with filters are always cached.
+        return Ok((dobj.is_bitmap_cached() || !dobj.filters().is_empty()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.cacheAsBitmap`'s setter.
+pub fn set_cache_as_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let is_bitmap_cached = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_is_bitmap_cached(activation.context.gc_context, is_bitmap_cached);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.opaqueBackground`'s getter.
+pub fn opaque_background<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(match dobj.opaque_background() {
+            Some(color) => {
+                let rgb =
+                    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b);
+                rgb.into()
+            }
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.opaqueBackground`'s setter.
+///
+/// `null` removes the background.
+pub fn set_opaque_background<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => Some(Color::from_rgb(value.coerce_to_u32(activation)?, 0xFF)),
+        };
+
+        dobj.set_opaque_background(activation.context.gc_context, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.filters`'s getter.
+///
+/// Filters without a class yet are left out.
+pub fn filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut objects = vec![];
+        for filter in dobj.filters() {
+            if let Some(object) = filters::filter_to_object(activation, &filter)? {
+                objects.push(object.into());
+            }
+        }
+
+        return Ok(ArrayObject::from_array(
+            ArrayStorage::from_args(&objects),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.filters`'s setter.
+///
+/// The filters are copied, so later changes to them have no effect.
+pub fn set_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let values: Vec<Value<'gc>> = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => vec![],
+            value => match value.coerce_to_object(activation)?.as_array_storage() {
+                Some(array) => (0..array.length())
+                    .map(|i| array.get(i).unwrap_or(Value::Undefined))
+                    .collect(),
+                None => vec![],
+            },
+        };
+
+        let mut new_filters = Vec::with_capacity(values.len());
+        for value in values {
+            let object = value.coerce_to_object(activation)?;
+            match filters::object_to_filter(activation, object)? {
+                Some(filter) => new_filters.push(filter),
+                None => log::warn!("DisplayObject.filters: Unsupported filter"),
+            }
+        }
+
+        dobj.set_filters(activation.context.gc_context, new_filters);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "DisplayObject"),
         Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "scale9Grid"),
+        Method::from_builtin(scale_9_grid),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "scale9Grid"),
+        Method::from_builtin(set_scale_9_grid),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "scrollRect"),
+        Method::from_builtin(scroll_rect),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "scrollRect"),
+        Method::from_builtin(set_scroll_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getBounds"),
+        Method::from_builtin(get_bounds),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "x"),
+        Method::from_builtin(x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "x"),
+        Method::from_builtin(set_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "y"),
+        Method::from_builtin(y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "y"),
+        Method::from_builtin(set_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "stage"),
+        Method::from_builtin(stage),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "width"),
+        Method::from_builtin(set_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "height"),
+        Method::from_builtin(set_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "blendMode"),
+        Method::from_builtin(blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "blendMode"),
+        Method::from_builtin(set_blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "cacheAsBitmap"),
+        Method::from_builtin(cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "cacheAsBitmap"),
+        Method::from_builtin(set_cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "opaqueBackground"),
+        Method::from_builtin(opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "opaqueBackground"),
+        Method::from_builtin(set_opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "filters"),
+        Method::from_builtin(filters),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "filters"),
+        Method::from_builtin(set_filters),
+    ));
+
+    class
 }
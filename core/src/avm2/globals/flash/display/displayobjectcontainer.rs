@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::text::textsnapshot;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
@@ -568,6 +569,19 @@ pub fn are_inaccessible_objects_under_point<'gc>(
     Err("DisplayObjectContainer.areInaccessibleObjectsUnderPoint not yet implemented".into())
 }
 
+/// Implements `DisplayObjectContainer.textSnapshot`
+pub fn text_snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(textsnapshot::from_container(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObjectContainer`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -650,6 +664,10 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ),
         Method::from_builtin(are_inaccessible_objects_under_point),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "textSnapshot"),
+        Method::from_builtin(text_snapshot),
+    ));
 
     class
 }
@@ -0,0 +1,417 @@
+//! `flash.display.Graphics` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::shape_utils::DrawCommand;
+use gc_arena::{GcCell, MutationContext};
+use std::f64::consts::FRAC_PI_4;
+use swf::{Color, FillStyle, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+
+/// Implements `flash.display.Graphics`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Graphics class cannot be instantiated.".into())
+}
+
+/// Implements `flash.display.Graphics`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Convert an AVM2 color and alpha into a `Color`.
+fn color_from_args(rgb: u32, alpha: f64) -> Color {
+    let alpha = (alpha.min(1.0).max(0.0) * 255.0) as u8;
+
+    Color::from_rgb(rgb, alpha)
+}
+
+/// Coerce the argument at `index` to a number, or return `default` if it is
+/// not present.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        Some(Value::Undefined) | None => Ok(default),
+        Some(value) => value.coerce_to_number(activation),
+    }
+}
+
+/// Apply a list of draw commands to the drawing of the display object this
+/// `Graphics` object draws into.
+fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    commands: &[DrawCommand],
+) {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            for command in commands {
+                drawing.draw_command(command.clone());
+            }
+        }
+    }
+}
+
+/// Implements `Graphics.beginFill`.
+pub fn begin_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rgb = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+        let alpha = number_arg(activation, args, 1, 1.0)?;
+
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_fill_style(Some(FillStyle::Color(color_from_args(rgb, alpha))));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.endFill`.
+pub fn end_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_fill_style(None);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineStyle`.
+pub fn line_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let thickness = number_arg(activation, args, 0, f64::NAN)?;
+
+        let style = if thickness.is_nan() {
+            None
+        } else {
+            let width = Twips::from_pixels(thickness.min(255.0).max(0.0));
+            let rgb = args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| 0.into())
+                .coerce_to_u32(activation)?;
+            let alpha = number_arg(activation, args, 2, 1.0)?;
+            let is_pixel_hinted = args.get(3).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+            let scale_mode = match args.get(4) {
+                Some(Value::Undefined) | Some(Value::Null) | None => None,
+                Some(v) => Some(v.coerce_to_string(activation)?),
+            };
+            let (allow_scale_x, allow_scale_y) = match scale_mode.as_deref() {
+                Some("none") => (false, false),
+                Some("vertical") => (true, false),
+                Some("horizontal") => (false, true),
+                _ => (true, true),
+            };
+            let caps = match args.get(5) {
+                Some(Value::Undefined) | Some(Value::Null) | None => None,
+                Some(v) => Some(v.coerce_to_string(activation)?),
+            };
+            let cap_style = match caps.as_deref() {
+                Some("square") => LineCapStyle::Square,
+                Some("none") => LineCapStyle::None,
+                _ => LineCapStyle::Round,
+            };
+            let joints = match args.get(6) {
+                Some(Value::Undefined) | Some(Value::Null) | None => None,
+                Some(v) => Some(v.coerce_to_string(activation)?),
+            };
+            let join_style = match joints.as_deref() {
+                Some("miter") => {
+                    let limit = number_arg(activation, args, 7, 3.0)?;
+                    LineJoinStyle::Miter(limit.max(1.0).min(255.0) as f32)
+                }
+                Some("bevel") => LineJoinStyle::Bevel,
+                _ => LineJoinStyle::Round,
+            };
+
+            Some(LineStyle {
+                width,
+                color: color_from_args(rgb, alpha),
+                start_cap: cap_style,
+                end_cap: cap_style,
+                join_style,
+                fill_style: None,
+                allow_scale_x,
+                allow_scale_y,
+                is_pixel_hinted,
+                allow_close: false,
+            })
+        };
+
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.set_line_style(style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            drawing.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.moveTo`.
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = number_arg(activation, args, 0, 0.0)?;
+    let y = number_arg(activation, args, 1, 0.0)?;
+
+    draw(
+        activation,
+        this,
+        &[DrawCommand::MoveTo {
+            x: Twips::from_pixels(x),
+            y: Twips::from_pixels(y),
+        }],
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineTo`.
+pub fn line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = number_arg(activation, args, 0, 0.0)?;
+    let y = number_arg(activation, args, 1, 0.0)?;
+
+    draw(
+        activation,
+        this,
+        &[DrawCommand::LineTo {
+            x: Twips::from_pixels(x),
+            y: Twips::from_pixels(y),
+        }],
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.curveTo`.
+pub fn curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x1 = number_arg(activation, args, 0, 0.0)?;
+    let y1 = number_arg(activation, args, 1, 0.0)?;
+    let x2 = number_arg(activation, args, 2, 0.0)?;
+    let y2 = number_arg(activation, args, 3, 0.0)?;
+
+    draw(
+        activation,
+        this,
+        &[DrawCommand::CurveTo {
+            x1: Twips::from_pixels(x1),
+            y1: Twips::from_pixels(y1),
+            x2: Twips::from_pixels(x2),
+            y2: Twips::from_pixels(y2),
+        }],
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawRect`.
+pub fn draw_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = Twips::from_pixels(number_arg(activation, args, 0, 0.0)?);
+    let y = Twips::from_pixels(number_arg(activation, args, 1, 0.0)?);
+    let width = Twips::from_pixels(number_arg(activation, args, 2, 0.0)?);
+    let height = Twips::from_pixels(number_arg(activation, args, 3, 0.0)?);
+
+    draw(
+        activation,
+        this,
+        &[
+            DrawCommand::MoveTo { x, y },
+            DrawCommand::LineTo { x: x + width, y },
+            DrawCommand::LineTo {
+                x: x + width,
+                y: y + height,
+            },
+            DrawCommand::LineTo { x, y: y + height },
+            DrawCommand::LineTo { x, y },
+        ],
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Build the commands that draw an ellipse inscribed in the given rectangle.
+///
+/// The ellipse is approximated with eight quadratic curves, starting and
+/// ending at its rightmost point.
+fn ellipse_commands(x: f64, y: f64, width: f64, height: f64) -> Vec<DrawCommand> {
+    let radius_x = width / 2.0;
+    let radius_y = height / 2.0;
+    let center_x = x + radius_x;
+    let center_y = y + radius_y;
+
+    // Control points lie on the tangents of each segment's endpoints, which
+    // meet at this distance from the center.
+    let control_scale = 1.0 / (FRAC_PI_4 / 2.0).cos();
+
+    let mut commands = vec![DrawCommand::MoveTo {
+        x: Twips::from_pixels(center_x + radius_x),
+        y: Twips::from_pixels(center_y),
+    }];
+
+    for segment in 0..8 {
+        let control_angle = (segment as f64 + 0.5) * FRAC_PI_4;
+        let anchor_angle = (segment as f64 + 1.0) * FRAC_PI_4;
+
+        commands.push(DrawCommand::CurveTo {
+            x1: Twips::from_pixels(center_x + radius_x * control_scale * control_angle.cos()),
+            y1: Twips::from_pixels(center_y + radius_y * control_scale * control_angle.sin()),
+            x2: Twips::from_pixels(center_x + radius_x * anchor_angle.cos()),
+            y2: Twips::from_pixels(center_y + radius_y * anchor_angle.sin()),
+        });
+    }
+
+    commands
+}
+
+/// Implements `Graphics.drawCircle`.
+pub fn draw_circle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = number_arg(activation, args, 0, 0.0)?;
+    let y = number_arg(activation, args, 1, 0.0)?;
+    let radius = number_arg(activation, args, 2, 0.0)?;
+
+    draw(
+        activation,
+        this,
+        &ellipse_commands(x - radius, y - radius, radius * 2.0, radius * 2.0),
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawEllipse`.
+pub fn draw_ellipse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = number_arg(activation, args, 0, 0.0)?;
+    let y = number_arg(activation, args, 1, 0.0)?;
+    let width = number_arg(activation, args, 2, 0.0)?;
+    let height = number_arg(activation, args, 3, 0.0)?;
+
+    draw(activation, this, &ellipse_commands(x, y, width, height));
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Graphics`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Graphics"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "beginFill"),
+        Method::from_builtin(begin_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "endFill"),
+        Method::from_builtin(end_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "lineStyle"),
+        Method::from_builtin(line_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "moveTo"),
+        Method::from_builtin(move_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "lineTo"),
+        Method::from_builtin(line_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "curveTo"),
+        Method::from_builtin(curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "drawRect"),
+        Method::from_builtin(draw_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "drawCircle"),
+        Method::from_builtin(draw_circle),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "drawEllipse"),
+        Method::from_builtin(draw_ellipse),
+    ));
+
+    class
+}
@@ -4,9 +4,11 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.InteractiveObject`'s instance constructor.
@@ -27,13 +29,151 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.doubleClickEnabled`'s getter.
+pub fn double_click_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.double_click_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.doubleClickEnabled`'s setter.
+pub fn set_double_click_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let double_click_enabled = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_double_click_enabled(activation.context.gc_context, double_click_enabled);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.tabIndex`'s getter.
+///
+/// Objects without an authored or scripted tab index report -1.
+pub fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.tab_index().unwrap_or(-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.tabIndex`'s setter.
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let tab_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        dobj.set_tab_index(
+            activation.context.gc_context,
+            Some(tab_index).filter(|tab_index| *tab_index >= 0),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.contextMenu`'s getter.
+pub fn context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return match this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contextMenu"),
+            activation,
+        )? {
+            Value::Undefined => Ok(Value::Null),
+            context_menu => Ok(context_menu),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `InteractiveObject.contextMenu`'s setter.
+pub fn set_context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let context_menu = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contextMenu"),
+            context_menu,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `InteractiveObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "InteractiveObject"),
         Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "doubleClickEnabled"),
+        Method::from_builtin(double_click_enabled),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "doubleClickEnabled"),
+        Method::from_builtin(set_double_click_enabled),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "tabIndex"),
+        Method::from_builtin(tab_index),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "tabIndex"),
+        Method::from_builtin(set_tab_index),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "contextMenu"),
+        Method::from_builtin(context_menu),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "contextMenu"),
+        Method::from_builtin(set_context_menu),
+    ));
+
+    class
 }
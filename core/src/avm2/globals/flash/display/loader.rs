@@ -0,0 +1,187 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loaderinfo;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use crate::display_object::{MovieClip, TDisplayObject, TDisplayObjectContainer};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.Loader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if this.as_display_object().is_none() {
+            // Loaders are containers for the single movie they load.
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new_with_avm2(
+                SwfSlice::empty(movie),
+                this,
+                activation.context.gc_context,
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+
+        let loader_info = loaderinfo::from_loader(activation, this)?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contentLoaderInfo"),
+            loader_info.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the `LoaderInfo` of a `Loader`.
+fn content_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "contentLoaderInfo"),
+        activation,
+    )?
+    .coerce_to_object(activation)
+}
+
+/// Implements `Loader.load`.
+///
+/// The movie is loaded into a new movie clip, which replaces any content the
+/// loader already had. Movies are loaded into their own library, so AVM1
+/// movies keep running in AVM1 even when loaded by an AVM2 movie.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut request = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let url = request
+            .get_property(
+                request,
+                &QName::new(Namespace::public_namespace(), "url"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        let mut ctr = match this
+            .as_display_object()
+            .and_then(|dobj| dobj.as_container())
+        {
+            Some(ctr) => ctr,
+            None => return Ok(Value::Undefined),
+        };
+
+        let loader_info = content_loader_info(activation, this)?;
+        ctr.remove_range(&mut activation.context, 0..ctr.num_children());
+
+        let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+        let content = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+        ctr.insert_at_index(&mut activation.context, content.into(), 0);
+
+        let fetch = activation
+            .context
+            .navigator
+            .fetch_body(&url, RequestOptions::get());
+        let process = activation.context.load_manager.load_movie_into_loader(
+            activation.context.player.clone().unwrap(),
+            content.into(),
+            fetch,
+            url.to_string(),
+            loader_info,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.content`.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let loader_info = content_loader_info(activation, this)?;
+        return loaderinfo::content(activation, Some(loader_info), &[]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.contentLoaderInfo`.
+pub fn content_loader_info_getter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(content_loader_info(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "contentLoaderInfo"),
+        Method::from_builtin(content_loader_info_getter),
+    ));
+
+    class
+}
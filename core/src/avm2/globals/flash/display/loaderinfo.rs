@@ -0,0 +1,389 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::{httpstatusevent, ioerrorevent};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, StageObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmType;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.LoaderInfo`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let loader = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "loader"),
+            loader,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "content"),
+            Value::Null,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+            0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+            0.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.LoaderInfo`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `LoaderInfo` for a given `Loader`.
+pub fn from_loader<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let loaderinfo_proto = activation.context.avm2.prototypes().loaderinfo;
+    let this = loaderinfo_proto.construct(activation, &[])?;
+    instance_init(activation, Some(this), &[loader.into()])?;
+
+    Ok(this)
+}
+
+/// Get the content of a `LoaderInfo`, once it has been loaded.
+fn loaded_content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    let content = this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "content"),
+        activation,
+    )?;
+
+    Ok(match content {
+        Value::Object(content) => Some(content),
+        _ => None,
+    })
+}
+
+/// Get the movie that has been loaded, raising an error if it has not
+/// finished loading yet.
+fn loaded_movie<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Arc<SwfMovie>, Error> {
+    loaded_content(activation, this)?
+        .and_then(|content| content.as_display_object())
+        .and_then(|content| content.movie())
+        .ok_or_else(|| {
+            "Error: Error #2099: The loading object is not sufficiently loaded to provide this information.".into()
+        })
+}
+
+/// Mark the movie of a `LoaderInfo` as loaded into a given display object,
+/// and fire the `init` and `complete` events on it.
+pub fn content_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    content: DisplayObject<'gc>,
+) -> Result<(), Error> {
+    let content_object = match content.object2() {
+        Value::Object(object) => object,
+        _ => {
+            // AVM1 content does not have an AVM2 object of its own, so it is
+            // represented by an `AVM1Movie`.
+            let avm1movie_proto = activation.context.avm2.prototypes().avm1movie;
+            StageObject::for_display_object(activation.context.gc_context, content, avm1movie_proto)
+                .into()
+        }
+    };
+
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "content"),
+        content_object.into(),
+        activation,
+    )?;
+
+    for event_type in &["init", "complete"] {
+        let event_proto = activation.context.avm2.prototypes().event;
+        let event = EventObject::from_event(
+            activation.context.gc_context,
+            Some(event_proto),
+            Event::new(*event_type),
+        );
+
+        dispatch_event(activation, this, event)?;
+    }
+
+    Ok(())
+}
+
+/// Update how much of the movie of a `LoaderInfo` has been downloaded.
+///
+/// The total length is left unchanged if it is not known yet.
+pub fn content_load_progress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    bytes_loaded: u64,
+    bytes_total: Option<u64>,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+        (bytes_loaded as f64).into(),
+        activation,
+    )?;
+
+    if let Some(bytes_total) = bytes_total {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+            (bytes_total as f64).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Fire the `httpStatus` and `ioError` events on a `LoaderInfo` whose movie
+/// could not be loaded.
+///
+/// The status is `0` if the request failed without an HTTP response.
+pub fn content_load_failed<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    url: &str,
+    status: u16,
+) -> Result<(), Error> {
+    let httpstatusevent_proto = activation.context.avm2.prototypes().httpstatusevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(httpstatusevent_proto),
+        Event::new("httpStatus"),
+    );
+    httpstatusevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "httpStatus".into(),
+            false.into(),
+            false.into(),
+            i32::from(status).into(),
+        ],
+    )?;
+    dispatch_event(activation, this, event)?;
+
+    let ioerrorevent_proto = activation.context.avm2.prototypes().ioerrorevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(ioerrorevent_proto),
+        Event::new("ioError"),
+    );
+    let text = AvmString::new(
+        activation.context.gc_context,
+        format!("Error #2035: URL Not Found. URL: {}", url),
+    );
+    ioerrorevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "ioError".into(),
+            false.into(),
+            false.into(),
+            text.into(),
+            2035.into(),
+        ],
+    )?;
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Implements `LoaderInfo.actionScriptVersion`.
+pub fn action_script_version<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let movie = loaded_movie(activation, this)?;
+        let library = activation.context.library.library_for_movie_mut(movie);
+
+        return Ok(match library.avm_type() {
+            AvmType::Avm1 => 2.into(),
+            AvmType::Avm2 => 3.into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.swfVersion`.
+pub fn swf_version<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(loaded_movie(activation, this)?.version().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.url`.
+pub fn url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let movie = loaded_movie(activation, this)?;
+
+        return Ok(match movie.url() {
+            Some(url) => AvmString::new(activation.context.gc_context, url.to_string()).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.bytesLoaded`.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.bytesTotal`.
+pub fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.content`.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(loaded_content(activation, this)?
+            .map(Value::from)
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LoaderInfo.loader`.
+pub fn loader<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "loader"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `LoaderInfo`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "LoaderInfo"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "actionScriptVersion"),
+        Method::from_builtin(action_script_version),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "swfVersion"),
+        Method::from_builtin(swf_version),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "url"),
+        Method::from_builtin(url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "loader"),
+        Method::from_builtin(loader),
+    ));
+
+    class
+}
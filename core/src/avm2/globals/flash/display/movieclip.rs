@@ -25,7 +25,11 @@ pub fn instance_init<'gc>(
     if let Some(this) = this {
         if this.as_display_object().is_none() {
             let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
-            let new_do = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+            let new_do = MovieClip::new_with_avm2(
+                SwfSlice::empty(movie),
+                this,
+                activation.context.gc_context,
+            );
 
             this.init_display_object(activation.context.gc_context, new_do.into());
         }
@@ -76,16 +80,13 @@ pub fn current_frame<'gc>(
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
     {
-        if let Some(Scene {
+        let Scene {
             name: _,
             start,
             length: _,
-        }) = mc.current_scene()
-        {
-            return Ok(((mc.current_frame() + 1) - start).into());
-        } else {
-            return Ok(mc.current_frame().into());
-        }
+        } = mc.current_scene();
+
+        return Ok(((mc.current_frame() + 1) - start).into());
     }
 
     Ok(Value::Undefined)
@@ -186,11 +187,7 @@ pub fn current_labels<'gc>(
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
     {
-        let scene = mc.current_scene().unwrap_or_else(|| Scene {
-            name: "".to_string(),
-            start: 0,
-            length: mc.total_frames(),
-        });
+        let scene = mc.current_scene();
         return Ok(labels_for_scene(activation, mc, &scene)?.2.into());
     }
 
@@ -207,11 +204,7 @@ pub fn current_scene<'gc>(
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
     {
-        let scene = mc.current_scene().unwrap_or_else(|| Scene {
-            name: "".to_string(),
-            start: 0,
-            length: mc.total_frames(),
-        });
+        let scene = mc.current_scene();
         let (scene_name, scene_length, scene_labels) = labels_for_scene(activation, mc, &scene)?;
         let scene_proto = activation.context.avm2.prototypes().scene;
         let args = [
@@ -241,17 +234,8 @@ pub fn scenes<'gc>(
         .and_then(|dobj| dobj.as_movie_clip())
     {
         let mut scene_objects = Vec::new();
-        let mut mc_scenes = mc.scenes();
-
-        if mc.scenes().is_empty() {
-            mc_scenes.push(Scene {
-                name: "".to_string(),
-                start: 0,
-                length: mc.total_frames(),
-            });
-        }
 
-        for scene in mc_scenes {
+        for scene in mc.scenes() {
             let (scene_name, scene_length, scene_labels) =
                 labels_for_scene(activation, mc, &scene)?;
             let scene_proto = activation.context.avm2.prototypes().scene;
@@ -360,6 +344,11 @@ pub fn goto_and_stop<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Move the playhead of a movie clip to a given frame number or label.
+///
+/// Frame numbers are relative to the given scene, or the current scene if no
+/// scene is given. Labels are looked up in the given scene, or the current
+/// scene and then the rest of the timeline if no scene is given.
 pub fn goto_frame<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     mc: MovieClip<'gc>,
@@ -369,37 +358,41 @@ pub fn goto_frame<'gc>(
     let frame_or_label = args.get(0).cloned().unwrap_or(Value::Null);
 
     let scene = match args.get(1).cloned().unwrap_or(Value::Null) {
-        Value::Null => None,
-        v => mc
-            .scene_label_to_number(&v.coerce_to_string(activation)?)
-            .map(|v| v.saturating_sub(1)),
-    }
-    .unwrap_or(0) as u32;
+        Value::Null | Value::Undefined => None,
+        v => {
+            let scene_name = v.coerce_to_string(activation)?;
+            Some(mc.scene_by_name(&scene_name).ok_or_else(|| {
+                format!(
+                    "ArgumentError: Error #2108: Scene {} was not found.",
+                    scene_name
+                )
+            })?)
+        }
+    };
+    let is_scene_given = scene.is_some();
+    let scene = scene.unwrap_or_else(|| mc.current_scene());
+    let scene_offset = scene.start.saturating_sub(1) as u32;
+
     let frame = match frame_or_label {
-        Value::Integer(i) => i as u32 + scene,
-        Value::Unsigned(i) => i + scene,
+        Value::Integer(i) => i as u32 + scene_offset,
+        Value::Unsigned(i) => i + scene_offset,
+        Value::Number(n) => n as u32 + scene_offset,
         frame_or_label => {
             let frame_or_label = frame_or_label.coerce_to_string(activation)?;
             if let Ok(frame) = frame_or_label.parse::<u32>() {
-                frame + scene
+                frame + scene_offset
             } else {
-                if let Some(scene) = args.get(1).cloned() {
-                    //If the user specified a scene, we need to validate that
-                    //the requested frame exists within that scene.
-                    let scene = scene.coerce_to_string(activation)?;
-                    if !mc.frame_exists_within_scene(&frame_or_label, &scene) {
-                        return Err(format!(
-                            "ArgumentError: Frame label {} not found in scene {}",
-                            frame_or_label, scene
-                        )
-                        .into());
-                    }
-                }
+                let frame = mc.frame_label_to_number_in_scene(&frame_or_label, &scene);
+                let frame = if is_scene_given {
+                    frame
+                } else {
+                    frame.or_else(|| mc.frame_label_to_number(&frame_or_label))
+                };
 
-                mc.frame_label_to_number(&frame_or_label).ok_or_else(|| {
+                frame.ok_or_else(|| {
                     format!(
-                        "ArgumentError: {} is not a valid frame label.",
-                        frame_or_label
+                        "ArgumentError: Error #2109: Frame label {} not found in scene {}.",
+                        frame_or_label, scene.name
                     )
                 })? as u32
             }
@@ -4,17 +4,35 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
 
 /// Implements `flash.display.Sprite`'s instance constructor.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            // Sprites are movie clips without a timeline of their own.
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new_with_avm2(
+                SwfSlice::empty(movie),
+                this,
+                activation.context.gc_context,
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -27,9 +45,118 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Retrieve the `Graphics` object that draws into a display object, creating
+/// it if it does not exist yet.
+///
+/// This is shared between every display object class with a `graphics`
+/// property.
+pub fn graphics<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if let Some(dobj) = this.as_display_object() {
+            let graphics_name = QName::new(Namespace::Private("ruffle".into()), "graphics");
+            if this.has_own_property(&graphics_name)? {
+                return this.get_property(this, &graphics_name, activation);
+            }
+
+            let graphics_proto = activation.context.avm2.prototypes().graphics;
+            let graphics: Value<'gc> = StageObject::for_display_object(
+                activation.context.gc_context,
+                dobj,
+                graphics_proto,
+            )
+            .into();
+            this.set_property(this, &graphics_name, graphics.clone(), activation)?;
+
+            return Ok(graphics);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sprite.buttonMode`'s getter.
+pub fn button_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.button_mode().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sprite.buttonMode`'s setter.
+pub fn set_button_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let button_mode = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        mc.set_button_mode(&mut activation.context, button_mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sprite.useHandCursor`'s getter.
+pub fn use_hand_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.use_hand_cursor().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sprite.useHandCursor`'s setter.
+pub fn set_use_hand_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let use_hand_cursor = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        mc.set_use_hand_cursor(&mut activation.context, use_hand_cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Sprite`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "Sprite"),
         Some(
             QName::new(
@@ -41,5 +168,30 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "graphics"),
+        Method::from_builtin(graphics),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "buttonMode"),
+        Method::from_builtin(button_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "buttonMode"),
+        Method::from_builtin(set_button_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "useHandCursor"),
+        Method::from_builtin(use_hand_cursor),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "useHandCursor"),
+        Method::from_builtin(set_use_hand_cursor),
+    ));
+
+    class
 }
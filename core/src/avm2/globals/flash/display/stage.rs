@@ -0,0 +1,100 @@
+//! `flash.display.Stage` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.Stage`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Stage class cannot be instantiated.".into())
+}
+
+/// Implements `flash.display.Stage`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.invalidate`.
+///
+/// The stage is redrawn on the next frame, and its `render` listeners are
+/// told beforehand.
+pub fn invalidate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    *activation.context.needs_redraw = true;
+
+    Ok(Value::Undefined)
+}
+
+/// Get the object for the stage, which is created the first time it is asked
+/// for.
+pub fn stage_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+    if let Some(stage) = activation.context.avm2.stage() {
+        return Ok(stage);
+    }
+
+    let stage_proto = activation.context.avm2.prototypes().stage;
+    let stage = stage_proto.construct(activation, &[])?;
+    activation.context.avm2.set_stage(stage);
+
+    Ok(stage)
+}
+
+/// Fire the `render` event of the stage, if anything has asked for it.
+pub fn dispatch_render<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error> {
+    if let Some(stage) = activation.context.avm2.stage() {
+        let event_proto = activation.context.avm2.prototypes().event;
+        let event = EventObject::from_event(
+            activation.context.gc_context,
+            Some(event_proto),
+            Event::new("render"),
+        );
+
+        dispatch_event(activation, stage, event)?;
+    }
+
+    Ok(())
+}
+
+/// Construct `Stage`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Stage"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "invalidate"),
+        Method::from_builtin(invalidate),
+    ));
+
+    class
+}
@@ -1,4 +1,16 @@
 //! `flash.events` namespace
 
+pub mod asyncerrorevent;
+pub mod contextmenuevent;
+pub mod errorevent;
+pub mod event;
 pub mod eventdispatcher;
+pub mod httpstatusevent;
 pub mod ieventdispatcher;
+pub mod ioerrorevent;
+pub mod mouseevent;
+pub mod netstatusevent;
+pub mod progressevent;
+pub mod sampledataevent;
+pub mod statusevent;
+pub mod textevent;
@@ -0,0 +1,141 @@
+//! `flash.events.AsyncErrorEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::{errorevent, textevent};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.AsyncErrorEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        // Unlike other error events, the fifth parameter is the error itself
+        // rather than an error ID.
+        errorevent::instance_init(activation, Some(this), args.get(0..4).unwrap_or(args))?;
+        set_error(activation, Some(this), args.get(4..5).unwrap_or(&[]))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.AsyncErrorEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `AsyncErrorEvent.error`'s getter.
+pub fn error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "error"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `AsyncErrorEvent.error`'s setter.
+pub fn set_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let error = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "error"),
+            error,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `AsyncErrorEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                textevent::text(activation, Some(this), &[])?,
+                error(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `AsyncErrorEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "AsyncErrorEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "ErrorEvent").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "error"),
+        Method::from_builtin(error),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "error"),
+        Method::from_builtin(set_error),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "ASYNC_ERROR"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("asyncError".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,225 @@
+//! `flash.events.ContextMenuEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ContextMenuEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        event::instance_init(activation, Some(this), args)?;
+        set_mouse_target(activation, Some(this), args.get(3..4).unwrap_or(&[]))?;
+        set_context_menu_owner(activation, Some(this), args.get(4..5).unwrap_or(&[]))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ContextMenuEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuEvent.mouseTarget`'s getter.
+pub fn mouse_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "mouseTarget"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuEvent.mouseTarget`'s setter.
+pub fn set_mouse_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mouse_target = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "mouseTarget"),
+            mouse_target,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuEvent.contextMenuOwner`'s getter.
+pub fn context_menu_owner<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contextMenuOwner"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuEvent.contextMenuOwner`'s setter.
+pub fn set_context_menu_owner<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let context_menu_owner = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contextMenuOwner"),
+            context_menu_owner,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                mouse_target(activation, Some(this), &[])?,
+                context_menu_owner(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `ContextMenuEvent` of the given type to a `ContextMenu` or a
+/// `ContextMenuItem`.
+pub fn dispatch<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    mouse_target: Object<'gc>,
+    context_menu_owner: Object<'gc>,
+) -> Result<(), Error> {
+    let contextmenuevent_proto = activation.context.avm2.prototypes().contextmenuevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(contextmenuevent_proto),
+        Event::new(event_type),
+    );
+    instance_init(
+        activation,
+        Some(event),
+        &[
+            event_type.into(),
+            false.into(),
+            false.into(),
+            mouse_target.into(),
+            context_menu_owner.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Construct `ContextMenuEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ContextMenuEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "mouseTarget"),
+        Method::from_builtin(mouse_target),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "mouseTarget"),
+        Method::from_builtin(set_mouse_target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "contextMenuOwner"),
+        Method::from_builtin(context_menu_owner),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "contextMenuOwner"),
+        Method::from_builtin(set_context_menu_owner),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("MENU_ITEM_SELECT", "menuItemSelect"),
+        ("MENU_SELECT", "menuSelect"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,127 @@
+//! `flash.events.ErrorEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::textevent;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ErrorEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        textevent::instance_init(activation, Some(this), args)?;
+
+        let error_id = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            error_id.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ErrorEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ErrorEvent.errorID`'s getter.
+pub fn error_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ErrorEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                textevent::text(activation, Some(this), &[])?,
+                error_id(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ErrorEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ErrorEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "TextEvent").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "errorID"),
+        Method::from_builtin(error_id),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "ERROR"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("error".into()),
+    ));
+
+    class
+}
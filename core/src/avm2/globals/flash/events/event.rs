@@ -0,0 +1,356 @@
+//! `flash.events.Event` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.Event`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let bubbles = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let cancelable = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_type(event_type);
+            evt.set_bubbles(bubbles);
+            evt.set_cancelable(cancelable);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.Event`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `bubbles` property's getter
+pub fn bubbles<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt.is_bubbling().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cancelable` property's getter
+pub fn cancelable<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `type` property's getter
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt.event_type().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `target` property's getter
+pub fn target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt.target().map(|o| o.into()).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `currentTarget` property's getter
+pub fn current_target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt
+            .current_target()
+            .map(|o| o.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `eventPhase` property's getter
+pub fn event_phase<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        let event_phase: u32 = evt.phase().into();
+        return Ok(event_phase.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [event_type.into(), bubbles.into(), cancelable.into()];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isDefaultPrevented`
+pub fn is_default_prevented<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `preventDefault`
+pub fn prevent_default<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this.and_then(|this| this.as_event_mut(activation.context.gc_context)) {
+        evt.cancel();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopPropagation`
+pub fn stop_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this.and_then(|this| this.as_event_mut(activation.context.gc_context)) {
+        evt.stop_propagation();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopImmediatePropagation`
+pub fn stop_immediate_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this.and_then(|this| this.as_event_mut(activation.context.gc_context)) {
+        evt.stop_immediate_propagation();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let class_name = this
+            .as_proto_class()
+            .map(|c| c.read().name().local_name())
+            .unwrap_or_else(|| "Event".into());
+
+        if let Some(evt) = this.as_event() {
+            let event_phase: u32 = evt.phase().into();
+
+            return Ok(AvmString::new(
+                activation.context.gc_context,
+                format!(
+                    "[{} type=\"{}\" bubbles={} cancelable={} eventPhase={}]",
+                    class_name,
+                    evt.event_type(),
+                    evt.is_bubbling(),
+                    evt.is_cancelable(),
+                    event_phase
+                ),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Event`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "Event"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bubbles"),
+        Method::from_builtin(bubbles),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "cancelable"),
+        Method::from_builtin(cancelable),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "type"),
+        Method::from_builtin(get_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "target"),
+        Method::from_builtin(target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "currentTarget"),
+        Method::from_builtin(current_target),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "eventPhase"),
+        Method::from_builtin(event_phase),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "isDefaultPrevented"),
+        Method::from_builtin(is_default_prevented),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "preventDefault"),
+        Method::from_builtin(prevent_default),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stopPropagation"),
+        Method::from_builtin(stop_propagation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stopImmediatePropagation"),
+        Method::from_builtin(stop_immediate_propagation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("ACTIVATE", "activate"),
+        ("ADDED", "added"),
+        ("ADDED_TO_STAGE", "addedToStage"),
+        ("CANCEL", "cancel"),
+        ("CHANGE", "change"),
+        ("CHANNEL_MESSAGE", "channelMessage"),
+        ("CHANNEL_STATE", "channelState"),
+        ("CLEAR", "clear"),
+        ("CLOSE", "close"),
+        ("COMPLETE", "complete"),
+        ("CONNECT", "connect"),
+        ("COPY", "copy"),
+        ("CUT", "cut"),
+        ("DEACTIVATE", "deactivate"),
+        ("ENTER_FRAME", "enterFrame"),
+        ("EXIT_FRAME", "exitFrame"),
+        ("FRAME_CONSTRUCTED", "frameConstructed"),
+        ("FULLSCREEN", "fullScreen"),
+        ("ID3", "id3"),
+        ("INIT", "init"),
+        ("MOUSE_LEAVE", "mouseLeave"),
+        ("OPEN", "open"),
+        ("PASTE", "paste"),
+        ("REMOVED", "removed"),
+        ("REMOVED_FROM_STAGE", "removedFromStage"),
+        ("RENDER", "render"),
+        ("RESIZE", "resize"),
+        ("SCROLL", "scroll"),
+        ("SELECT", "select"),
+        ("SELECT_ALL", "selectAll"),
+        ("SOUND_COMPLETE", "soundComplete"),
+        ("TAB_CHILDREN_CHANGE", "tabChildrenChange"),
+        ("TAB_ENABLED_CHANGE", "tabEnabledChange"),
+        ("TAB_INDEX_CHANGE", "tabIndexChange"),
+        ("UNLOAD", "unload"),
+        ("WORKER_STATE", "workerState"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    class
+}
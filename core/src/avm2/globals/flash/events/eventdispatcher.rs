@@ -2,9 +2,11 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event as dispatch_event_internal, dispatch_list};
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
@@ -27,6 +29,177 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `EventDispatcher.addEventListener`.
+pub fn add_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let priority = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+
+        //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
+        if let Some(dispatch_list) = dispatch_list(activation, this, true)? {
+            dispatch_list
+                .as_dispatch_mut(activation.context.gc_context)
+                .ok_or("Internal dispatch list is missing during addEventListener!")?
+                .add_event_listener(event_type.to_string(), priority, listener, use_capture);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.removeEventListener`.
+pub fn remove_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(dispatch_list) = dispatch_list(activation, this, false)? {
+            dispatch_list
+                .as_dispatch_mut(activation.context.gc_context)
+                .ok_or("Internal dispatch list is missing during removeEventListener!")?
+                .remove_event_listener(&event_type, listener, use_capture);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.hasEventListener`.
+pub fn has_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(dispatch_list) = dispatch_list(activation, this, false)? {
+            return Ok(dispatch_list
+                .as_dispatch()
+                .ok_or("Internal dispatch list is missing during hasEventListener!")?
+                .has_event_listener(&event_type)
+                .into());
+        }
+
+        return Ok(false.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.willTrigger`.
+pub fn will_trigger<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut target = this.and_then(|this| this.as_display_object());
+
+    if let Some(this) = this {
+        if has_event_listener(activation, Some(this), args)?.coerce_to_boolean() {
+            return Ok(true.into());
+        }
+    }
+
+    while let Some(dobj) = target.and_then(|t| t.parent()) {
+        if let Value::Object(parent) = dobj.object2() {
+            if has_event_listener(activation, Some(parent), args)?.coerce_to_boolean() {
+                return Ok(true.into());
+            }
+        }
+
+        target = Some(dobj);
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `EventDispatcher.dispatchEvent`.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let already_dispatched = event
+            .as_event()
+            .ok_or("Dispatched Events must be subclasses of Event.")?
+            .target()
+            .is_some();
+
+        // Events that have already been dispatched are cloned first, so that
+        // existing references to the original event are left untouched.
+        if already_dispatched {
+            let clone = event.get_property(
+                event,
+                &QName::new(Namespace::public_namespace(), "clone"),
+                activation,
+            )?;
+            event = clone
+                .coerce_to_object(activation)?
+                .call(Some(event), &[], activation, None)?
+                .coerce_to_object(activation)?;
+
+            if event.as_event().is_none() {
+                return Err("Dispatched Events must be subclasses of Event.".into());
+            }
+        }
+
+        return Ok(dispatch_event_internal(activation, this, event)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `EventDispatcher`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -41,5 +214,28 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         .write(mc)
         .implements(QName::new(Namespace::package("flash.events"), "IEventDispatcher").into());
 
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "addEventListener"),
+        Method::from_builtin(add_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "removeEventListener"),
+        Method::from_builtin(remove_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "hasEventListener"),
+        Method::from_builtin(has_event_listener),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "willTrigger"),
+        Method::from_builtin(will_trigger),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "dispatchEvent"),
+        Method::from_builtin(dispatch_event),
+    ));
+
     class
 }
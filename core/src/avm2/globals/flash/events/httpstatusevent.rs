@@ -0,0 +1,281 @@
+//! `flash.events.HTTPStatusEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.HTTPStatusEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        event::instance_init(activation, Some(this), args)?;
+
+        let status = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "status"),
+            status.into(),
+            activation,
+        )?;
+
+        set_redirected(activation, Some(this), args.get(4..5).unwrap_or(&[]))?;
+        set_response_url(activation, Some(this), &[Value::Null])?;
+        set_response_headers(activation, Some(this), &[Value::Null])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.HTTPStatusEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.status`'s getter.
+pub fn status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "status"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.redirected`'s getter.
+pub fn redirected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "redirected"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.redirected`'s setter.
+pub fn set_redirected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let redirected = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "redirected"),
+            redirected.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.responseURL`'s getter.
+pub fn response_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "responseURL"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.responseURL`'s setter.
+pub fn set_response_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let url = match args.get(0) {
+            Some(Value::Null) | Some(Value::Undefined) | None => Value::Null,
+            Some(url) => url.clone().coerce_to_string(activation)?.into(),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "responseURL"),
+            url,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.responseHeaders`'s getter.
+pub fn response_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "responseHeaders"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.responseHeaders`'s setter.
+pub fn set_response_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let headers = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "responseHeaders"),
+            headers,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTTPStatusEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                status(activation, Some(this), &[])?,
+                redirected(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+            let url = response_url(activation, Some(this), &[])?;
+            set_response_url(activation, Some(new_event), &[url])?;
+            let headers = response_headers(activation, Some(this), &[])?;
+            set_response_headers(activation, Some(new_event), &[headers])?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `HTTPStatusEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "HTTPStatusEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "status"),
+        Method::from_builtin(status),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "redirected"),
+        Method::from_builtin(redirected),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "redirected"),
+        Method::from_builtin(set_redirected),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "responseURL"),
+        Method::from_builtin(response_url),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "responseURL"),
+        Method::from_builtin(set_response_url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "responseHeaders"),
+        Method::from_builtin(response_headers),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "responseHeaders"),
+        Method::from_builtin(set_response_headers),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "HTTP_STATUS"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("httpStatus".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "HTTP_RESPONSE_STATUS"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("httpResponseStatus".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,503 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use gc_arena::{GcCell, MutationContext};
+use std::f64::NAN;
+use swf::Twips;
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut args = args.to_vec();
+        if args.len() < 2 {
+            // Mouse events bubble by default.
+            args.resize(2, Value::Undefined);
+            args[1] = true.into();
+        }
+
+        event::instance_init(activation, Some(this), &args)?;
+
+        let local_x = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| NAN.into())
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| NAN.into())
+            .coerce_to_number(activation)?;
+        let related_object = args.get(5).cloned().unwrap_or(Value::Null);
+        let ctrl_key = args.get(6).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let alt_key = args.get(7).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let shift_key = args.get(8).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let button_down = args.get(9).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        let properties = [
+            ("localX", local_x.into()),
+            ("localY", local_y.into()),
+            ("relatedObject", related_object),
+            ("ctrlKey", ctrl_key.into()),
+            ("altKey", alt_key.into()),
+            ("shiftKey", shift_key.into()),
+            ("buttonDown", button_down.into()),
+            ("delta", delta.into()),
+        ];
+
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get one of the properties stored on a mouse event.
+fn get_private<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Set one of the properties stored on a mouse event.
+fn set_private<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MouseEvent.localX`'s getter.
+pub fn local_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "localX")
+}
+
+/// Implements `MouseEvent.localX`'s setter.
+pub fn set_local_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let local_x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    set_private(activation, this, "localX", local_x.into())
+}
+
+/// Implements `MouseEvent.localY`'s getter.
+pub fn local_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "localY")
+}
+
+/// Implements `MouseEvent.localY`'s setter.
+pub fn set_local_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let local_y = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    set_private(activation, this, "localY", local_y.into())
+}
+
+/// Convert the local coordinates of a mouse event into stage coordinates.
+///
+/// Events without a display object target are assumed to already be in stage
+/// coordinates.
+fn stage_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+) -> Result<(f64, f64), Error> {
+    let local_x = local_x(activation, this, &[])?.coerce_to_number(activation)?;
+    let local_y = local_y(activation, this, &[])?.coerce_to_number(activation)?;
+    let target = this
+        .and_then(|this| this.as_event().and_then(|evt| evt.target()))
+        .and_then(|target| target.as_display_object());
+
+    match target {
+        Some(dobj) if local_x.is_finite() && local_y.is_finite() => {
+            let (x, y) =
+                dobj.local_to_global((Twips::from_pixels(local_x), Twips::from_pixels(local_y)));
+
+            Ok((x.to_pixels(), y.to_pixels()))
+        }
+        _ => Ok((local_x, local_y)),
+    }
+}
+
+/// Implements `MouseEvent.stageX`'s getter.
+pub fn stage_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(stage_position(activation, this)?.0.into())
+}
+
+/// Implements `MouseEvent.stageY`'s getter.
+pub fn stage_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(stage_position(activation, this)?.1.into())
+}
+
+/// Implements `MouseEvent.relatedObject`'s getter.
+pub fn related_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "relatedObject")
+}
+
+/// Implements `MouseEvent.relatedObject`'s setter.
+pub fn set_related_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let related_object = args.get(0).cloned().unwrap_or(Value::Null);
+
+    set_private(activation, this, "relatedObject", related_object)
+}
+
+/// Implements `MouseEvent.ctrlKey`'s getter.
+pub fn ctrl_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "ctrlKey")
+}
+
+/// Implements `MouseEvent.ctrlKey`'s setter.
+pub fn set_ctrl_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let ctrl_key = args.get(0).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+
+    set_private(activation, this, "ctrlKey", ctrl_key.into())
+}
+
+/// Implements `MouseEvent.altKey`'s getter.
+pub fn alt_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "altKey")
+}
+
+/// Implements `MouseEvent.altKey`'s setter.
+pub fn set_alt_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alt_key = args.get(0).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+
+    set_private(activation, this, "altKey", alt_key.into())
+}
+
+/// Implements `MouseEvent.shiftKey`'s getter.
+pub fn shift_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "shiftKey")
+}
+
+/// Implements `MouseEvent.shiftKey`'s setter.
+pub fn set_shift_key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let shift_key = args.get(0).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+
+    set_private(activation, this, "shiftKey", shift_key.into())
+}
+
+/// Implements `MouseEvent.buttonDown`'s getter.
+pub fn button_down<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "buttonDown")
+}
+
+/// Implements `MouseEvent.buttonDown`'s setter.
+pub fn set_button_down<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let button_down = args.get(0).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+
+    set_private(activation, this, "buttonDown", button_down.into())
+}
+
+/// Implements `MouseEvent.delta`'s getter.
+pub fn delta<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "delta")
+}
+
+/// Implements `MouseEvent.delta`'s setter.
+pub fn set_delta<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let delta = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    set_private(activation, this, "delta", delta.into())
+}
+
+/// Implements `MouseEvent.updateAfterEvent`.
+pub fn update_after_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    *activation.context.needs_render = true;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MouseEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                local_x(activation, Some(this), &[])?,
+                local_y(activation, Some(this), &[])?,
+                related_object(activation, Some(this), &[])?,
+                ctrl_key(activation, Some(this), &[])?,
+                alt_key(activation, Some(this), &[])?,
+                shift_key(activation, Some(this), &[])?,
+                button_down(activation, Some(this), &[])?,
+                delta(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "localX"),
+        Method::from_builtin(local_x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "localX"),
+        Method::from_builtin(set_local_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "localY"),
+        Method::from_builtin(local_y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "localY"),
+        Method::from_builtin(set_local_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "stageX"),
+        Method::from_builtin(stage_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "stageY"),
+        Method::from_builtin(stage_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "relatedObject"),
+        Method::from_builtin(related_object),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "relatedObject"),
+        Method::from_builtin(set_related_object),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "ctrlKey"),
+        Method::from_builtin(ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "ctrlKey"),
+        Method::from_builtin(set_ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "altKey"),
+        Method::from_builtin(alt_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "altKey"),
+        Method::from_builtin(set_alt_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "shiftKey"),
+        Method::from_builtin(shift_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "shiftKey"),
+        Method::from_builtin(set_shift_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "buttonDown"),
+        Method::from_builtin(button_down),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "buttonDown"),
+        Method::from_builtin(set_button_down),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "delta"),
+        Method::from_builtin(delta),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "delta"),
+        Method::from_builtin(set_delta),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("CLICK", "click"),
+        ("DOUBLE_CLICK", "doubleClick"),
+        ("MOUSE_DOWN", "mouseDown"),
+        ("MOUSE_MOVE", "mouseMove"),
+        ("MOUSE_OUT", "mouseOut"),
+        ("MOUSE_OVER", "mouseOver"),
+        ("MOUSE_UP", "mouseUp"),
+        ("MOUSE_WHEEL", "mouseWheel"),
+        ("ROLL_OUT", "rollOut"),
+        ("ROLL_OVER", "rollOver"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    class
+}
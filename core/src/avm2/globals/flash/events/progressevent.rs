@@ -0,0 +1,199 @@
+//! `flash.events.ProgressEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::{Trait, TraitAttributes};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ProgressEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        event::instance_init(activation, Some(this), args)?;
+
+        set_bytes_loaded(activation, Some(this), args.get(3..4).unwrap_or(&[]))?;
+        set_bytes_total(activation, Some(this), args.get(4..5).unwrap_or(&[]))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ProgressEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ProgressEvent.bytesLoaded`'s getter.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ProgressEvent.bytesLoaded`'s setter.
+pub fn set_bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let bytes_loaded = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_number(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+            bytes_loaded.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ProgressEvent.bytesTotal`'s getter.
+pub fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ProgressEvent.bytesTotal`'s setter.
+pub fn set_bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let bytes_total = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_number(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+            bytes_total.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ProgressEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(proto) = this.proto() {
+            let (event_type, bubbles, cancelable) = match this.as_event() {
+                Some(evt) => (evt.event_type(), evt.is_bubbling(), evt.is_cancelable()),
+                None => return Ok(Value::Undefined),
+            };
+            let args = [
+                event_type.into(),
+                bubbles.into(),
+                cancelable.into(),
+                bytes_loaded(activation, Some(this), &[])?,
+                bytes_total(activation, Some(this), &[])?,
+            ];
+            let new_event = proto.construct(activation, &args)?;
+
+            instance_init(activation, Some(new_event), &args)?;
+
+            return Ok(new_event.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ProgressEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ProgressEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "bytesLoaded"),
+        Method::from_builtin(set_bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "bytesTotal"),
+        Method::from_builtin(set_bytes_total),
+    ));
+
+    let mut clone_trait = Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    );
+    clone_trait.set_attributes(TraitAttributes::Override.into());
+    write.define_instance_trait(clone_trait);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "PROGRESS"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("progress".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "SOCKET_DATA"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("socketData".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,74 @@
+//! `flash.filters` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use swf::{BlurFilter, Filter};
+
+pub mod color_matrix_filter;
+
+/// Read a number property of a filter, or a default if unset.
+fn number_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+    default: f64,
+) -> Result<f64, Error> {
+    match object.get_property(
+        object,
+        &QName::new(Namespace::public_namespace(), name),
+        activation,
+    )? {
+        Value::Undefined => Ok(default),
+        value => value.coerce_to_number(activation),
+    }
+}
+
+/// Read the properties of a `flash.filters` filter into a filter.
+///
+/// Filters with a `matrix` are read as a `ColorMatrixFilter`, and filters
+/// with a `blurX` or `blurY` as a `BlurFilter`. Other filters are not
+/// supported yet.
+pub fn object_to_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Option<Filter>, Error> {
+    if object.has_property(&QName::new(Namespace::public_namespace(), "matrix"))? {
+        let color_matrix = color_matrix_filter::object_to_filter(activation, object)?;
+        return Ok(Some(Filter::ColorMatrixFilter(Box::new(color_matrix))));
+    }
+
+    let mut has_blur = false;
+    for name in &["blurX", "blurY"] {
+        has_blur |= object.has_property(&QName::new(Namespace::public_namespace(), *name))?;
+    }
+    if has_blur {
+        let quality = number_property(activation, object, "quality", 1.0)?;
+        return Ok(Some(Filter::BlurFilter(Box::new(BlurFilter {
+            blur_x: number_property(activation, object, "blurX", 4.0)?,
+            blur_y: number_property(activation, object, "blurY", 4.0)?,
+            num_passes: quality.max(0.0).min(15.0) as u8,
+        }))));
+    }
+
+    Ok(None)
+}
+
+/// Construct the `flash.filters` object for a filter.
+///
+/// Only color matrix filters have a class yet, so other filters return
+/// `None`.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Result<Option<Object<'gc>>, Error> {
+    Ok(match filter {
+        Filter::ColorMatrixFilter(color_matrix) => Some(color_matrix_filter::filter_to_object(
+            activation,
+            color_matrix,
+        )?),
+        _ => None,
+    })
+}
@@ -0,0 +1,167 @@
+//! `flash.filters.ColorMatrixFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::ColorMatrixFilter;
+
+/// The matrix of a filter that leaves colors unchanged.
+const IDENTITY: [f64; 20] = [
+    1.0, 0.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, 0.0, //
+];
+
+/// Read the 20 entries of a matrix array. Missing entries are zero.
+fn array_to_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    array: Object<'gc>,
+) -> Result<[f64; 20], Error> {
+    let values: Vec<Value<'gc>> = match array.as_array_storage() {
+        Some(array) => (0..20)
+            .map(|i| array.get(i).unwrap_or(Value::Undefined))
+            .collect(),
+        None => vec![],
+    };
+
+    let mut matrix = [0.0; 20];
+    for (value, entry) in values.into_iter().zip(matrix.iter_mut()) {
+        *entry = match value {
+            Value::Undefined | Value::Null => 0.0,
+            value => value.coerce_to_number(activation)?,
+        };
+    }
+
+    Ok(matrix)
+}
+
+/// Construct an array holding the entries of a matrix.
+fn matrix_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: &[f64; 20],
+) -> Object<'gc> {
+    let values: Vec<Value<'gc>> = matrix.iter().map(|v| (*v).into()).collect();
+
+    ArrayObject::from_array(
+        ArrayStorage::from_args(&values),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+}
+
+/// Implements `flash.filters.ColorMatrixFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let matrix = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => IDENTITY,
+            matrix => {
+                let matrix = matrix.coerce_to_object(activation)?;
+                array_to_matrix(activation, matrix)?
+            }
+        };
+        let matrix = matrix_to_array(activation, &matrix);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "matrix"),
+            matrix.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.filters.ColorMatrixFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read the matrix of a `ColorMatrixFilter` object into a filter.
+pub fn object_to_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<ColorMatrixFilter, Error> {
+    let matrix = object.get_property(
+        object,
+        &QName::new(Namespace::public_namespace(), "matrix"),
+        activation,
+    )?;
+    let matrix = match matrix {
+        Value::Undefined | Value::Null => IDENTITY,
+        matrix => {
+            let matrix = matrix.coerce_to_object(activation)?;
+            array_to_matrix(activation, matrix)?
+        }
+    };
+
+    Ok(ColorMatrixFilter { matrix })
+}
+
+/// Construct a `ColorMatrixFilter` object holding the given filter.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &ColorMatrixFilter,
+) -> Result<Object<'gc>, Error> {
+    let color_matrix_filter_proto = activation.context.avm2.prototypes().colormatrixfilter;
+    let object = color_matrix_filter_proto.construct(activation, &[])?;
+    let matrix = matrix_to_array(activation, &filter.matrix);
+    instance_init(activation, Some(object), &[matrix.into()])?;
+
+    Ok(object)
+}
+
+/// Implements `ColorMatrixFilter.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let filter = object_to_filter(activation, this)?;
+        return Ok(filter_to_object(activation, &filter)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorMatrixFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.filters"), "ColorMatrixFilter"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public_namespace(), "matrix"),
+        QName::new(Namespace::public_namespace(), "Array").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clone"),
+        Method::from_builtin(clone),
+    ));
+
+    class
+}
@@ -0,0 +1,3 @@
+//! `flash.geom` namespace
+
+pub mod rectangle;
@@ -0,0 +1,153 @@
+//! `flash.geom.Rectangle` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// The public properties of a rectangle, in constructor order.
+const PROPERTIES: [&str; 4] = ["x", "y", "width", "height"];
+
+/// Implements `flash.geom.Rectangle`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        for (i, name) in PROPERTIES.iter().enumerate() {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| 0.into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Rectangle`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `Rectangle` object holding the given rectangle.
+pub fn from_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: &swf::Rectangle,
+) -> Result<Object<'gc>, Error> {
+    let rectangle_proto = activation.context.avm2.prototypes().rectangle;
+    let object = rectangle_proto.construct(activation, &[])?;
+    let args = [
+        rect.x_min.to_pixels().into(),
+        rect.y_min.to_pixels().into(),
+        (rect.x_max - rect.x_min).to_pixels().into(),
+        (rect.y_max - rect.y_min).to_pixels().into(),
+    ];
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(object)
+}
+
+/// Read the rectangle held by a `Rectangle` object.
+pub fn to_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<swf::Rectangle, Error> {
+    let mut values = [0.0; 4];
+    for (name, value) in PROPERTIES.iter().zip(values.iter_mut()) {
+        *value = object
+            .get_property(
+                object,
+                &QName::new(Namespace::public_namespace(), *name),
+                activation,
+            )?
+            .coerce_to_number(activation)?;
+    }
+
+    let [x, y, width, height] = values;
+    Ok(swf::Rectangle {
+        x_min: Twips::from_pixels(x),
+        y_min: Twips::from_pixels(y),
+        x_max: Twips::from_pixels(x + width),
+        y_max: Twips::from_pixels(y + height),
+    })
+}
+
+/// Implements `Rectangle.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut values = Vec::with_capacity(PROPERTIES.len());
+        for name in PROPERTIES.iter() {
+            let value = this
+                .get_property(
+                    this,
+                    &QName::new(Namespace::public_namespace(), *name),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+            values.push(value);
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!(
+                "(x={}, y={}, w={}, h={})",
+                values[0], values[1], values[2], values[3]
+            ),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Rectangle`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Rectangle"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in PROPERTIES.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(Value::Number(0.0)),
+        ));
+    }
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
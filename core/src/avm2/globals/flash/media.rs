@@ -0,0 +1,6 @@
+//! `flash.media` namespace
+
+pub mod sound;
+pub mod soundchannel;
+pub mod soundmixer;
+pub mod soundtransform;
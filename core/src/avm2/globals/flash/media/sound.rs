@@ -0,0 +1,204 @@
+//! `flash.media.Sound` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::dispatch_list;
+use crate::avm2::globals::flash::media::{soundchannel, soundtransform};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::sound::GeneratedSound;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.Sound`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.Sound`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sound.play`.
+///
+/// Only sounds generated by `sampleData` event handlers can currently be
+/// played; all other sounds have no data and yield `null`.
+///
+/// The `SoundTransform` given as the third argument, if any, is applied to
+/// the returned channel.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let has_sample_data_listener = match dispatch_list(activation, this, false)? {
+            Some(dispatch_list) => dispatch_list
+                .as_dispatch()
+                .map(|d| d.has_event_listener("sampleData"))
+                .unwrap_or(false),
+            None => false,
+        };
+
+        if !has_sample_data_listener {
+            return Ok(Value::Null);
+        }
+
+        let instance = match activation.context.audio.start_generated_sound() {
+            Ok(instance) => instance,
+            Err(e) => {
+                log::error!("Could not start generated sound: {}", e);
+                return Ok(Value::Null);
+            }
+        };
+
+        let soundchannel_proto = activation.context.avm2.prototypes().soundchannel;
+        let channel = soundchannel_proto.construct(activation, &[])?;
+        soundchannel::instance_init(activation, Some(channel), &[])?;
+
+        if let Some(Value::Object(transform)) = args.get(2) {
+            let transform = soundtransform::store_transform(activation, channel, *transform)?;
+            activation
+                .context
+                .audio
+                .set_sound_transform(instance, transform);
+        }
+
+        activation
+            .context
+            .avm2
+            .add_generated_sound(GeneratedSound::new(instance, this, channel));
+
+        return Ok(channel.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sound.length`.
+///
+/// Only sounds linked to library sounds have a length.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(duration) = this
+        .and_then(|this| activation.context.avm2.sound_for_object(this))
+        .and_then(|sound| activation.context.audio.get_sound_duration(sound))
+    {
+        return Ok(duration.into());
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Sound.extract`.
+///
+/// The samples are written into the target `ByteArray` as interleaved stereo
+/// floats at 44.1kHz, and the number of sample frames written is returned. A
+/// negative `startPosition` continues from where the last extraction ended.
+///
+/// Only sounds linked to library sounds have samples to extract.
+pub fn extract<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let target = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let length = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let start_position = match args.get(2) {
+            Some(start_position) => start_position.coerce_to_number(activation)?,
+            None => -1.0,
+        };
+
+        let position_name = QName::new(Namespace::Private("ruffle".into()), "extractPosition");
+        let start = if start_position >= 0.0 {
+            start_position as usize
+        } else {
+            match this.get_property(this, &position_name, activation)? {
+                Value::Undefined => 0,
+                position => position.coerce_to_u32(activation)? as usize,
+            }
+        };
+
+        let samples = activation
+            .context
+            .avm2
+            .sound_for_object(this)
+            .and_then(|sound| {
+                activation
+                    .context
+                    .audio
+                    .extract_sound_samples(sound, start, length as usize)
+            })
+            .unwrap_or_default();
+
+        if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+            for [left, right] in samples.iter() {
+                bytearray.write_f32(*left);
+                bytearray.write_f32(*right);
+            }
+        }
+
+        this.set_property(
+            this,
+            &position_name,
+            (start + samples.len()).into(),
+            activation,
+        )?;
+
+        return Ok(samples.len().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Sound`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "Sound"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "play"),
+        Method::from_builtin(play),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "extract"),
+        Method::from_builtin(extract),
+    ));
+
+    class
+}
@@ -0,0 +1,104 @@
+//! `flash.media.SoundMixer` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::media::soundtransform;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::sound::stop_all_sounds;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.SoundMixer`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.SoundMixer`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundMixer.soundTransform`'s getter.
+///
+/// The global transform is stored on the `SoundMixer` class itself.
+pub fn sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(soundtransform::stored_transform(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundMixer.soundTransform`'s setter.
+pub fn set_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let transform = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let transform = soundtransform::store_transform(activation, this, transform)?;
+        activation.context.audio.set_master_transform(transform);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundMixer.stopAll`.
+pub fn stop_all<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    stop_all_sounds(activation);
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SoundMixer`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "SoundMixer"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "soundTransform"),
+        Method::from_builtin(sound_transform),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "soundTransform"),
+        Method::from_builtin(set_sound_transform),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "stopAll"),
+        Method::from_builtin(stop_all),
+    ));
+
+    class
+}
@@ -0,0 +1,173 @@
+//! `flash.media.SoundTransform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::SoundTransform;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.SoundTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let volume = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 1.into())
+            .coerce_to_number(activation)?;
+        let pan = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_number(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "volume"),
+            volume.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "pan"),
+            pan.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.SoundTransform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `SoundTransform` object holding the given transform.
+pub fn from_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    transform: SoundTransform,
+) -> Result<Object<'gc>, Error> {
+    let soundtransform_proto = activation.context.avm2.prototypes().soundtransform;
+    let object = soundtransform_proto.construct(activation, &[])?;
+    let args = [
+        f64::from(transform.volume).into(),
+        f64::from(transform.pan).into(),
+    ];
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(object)
+}
+
+/// Read the volume and pan held by a `SoundTransform` object.
+fn volume_and_pan<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<(f64, f64), Error> {
+    let volume = object
+        .get_property(
+            object,
+            &QName::new(Namespace::public_namespace(), "volume"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let pan = object
+        .get_property(
+            object,
+            &QName::new(Namespace::public_namespace(), "pan"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+
+    Ok((volume, pan))
+}
+
+/// Read the transform held by a `SoundTransform` object.
+pub fn to_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<SoundTransform, Error> {
+    let (volume, pan) = volume_and_pan(activation, object)?;
+
+    Ok(SoundTransform {
+        volume: volume as f32,
+        pan: pan as f32,
+    })
+}
+
+/// Construct a new `SoundTransform` object with the same values as another.
+pub fn copy_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let (volume, pan) = volume_and_pan(activation, object)?;
+    let soundtransform_proto = activation.context.avm2.prototypes().soundtransform;
+    let copy = soundtransform_proto.construct(activation, &[])?;
+    instance_init(activation, Some(copy), &[volume.into(), pan.into()])?;
+
+    Ok(copy)
+}
+
+/// Retrieve a copy of the `SoundTransform` stored on an object by
+/// `store_transform`.
+///
+/// Objects that have never had a transform stored play at full volume and
+/// without any panning.
+pub fn stored_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let stored = object.get_property(
+        object,
+        &QName::new(Namespace::Private("ruffle".into()), "soundTransform"),
+        activation,
+    )?;
+
+    match stored {
+        Value::Object(stored) => copy_transform(activation, stored),
+        _ => from_transform(activation, SoundTransform::default()),
+    }
+}
+
+/// Store a copy of a `SoundTransform` on an object, such as a
+/// `SoundChannel`, and return the transform it holds.
+///
+/// A copy is kept so that later changes to the `SoundTransform` that was
+/// assigned have no effect until it is assigned again, as in Flash Player.
+pub fn store_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    transform: Object<'gc>,
+) -> Result<SoundTransform, Error> {
+    let copy = copy_transform(activation, transform)?;
+    object.set_property(
+        object,
+        &QName::new(Namespace::Private("ruffle".into()), "soundTransform"),
+        copy.into(),
+        activation,
+    )?;
+
+    to_transform(activation, copy)
+}
+
+/// Construct `SoundTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::package("flash.media"), "SoundTransform"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
@@ -0,0 +1,133 @@
+//! `flash.net` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::net::urlrequest::{
+    request_data, request_method, request_options, request_url,
+};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+pub mod filefilter;
+pub mod filereference;
+pub mod localconnection;
+pub mod sharedobject;
+pub mod url_loader;
+pub mod urlrequest;
+pub mod urlvariables;
+
+/// Implements `flash.net.registerClassAlias`.
+pub fn register_class_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => {
+            return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into())
+        }
+        Some(alias) => alias.clone().coerce_to_string(activation)?,
+    };
+    let class = match args.get(1) {
+        Some(Value::Object(class)) => *class,
+        _ => return Err("TypeError: Error #2007: Parameter classObject must be non-null.".into()),
+    };
+
+    activation
+        .context
+        .avm2
+        .global_domain()
+        .register_class_alias(alias.to_string(), class, activation.context.gc_context);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.getClassByAlias`.
+pub fn get_class_by_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => {
+            return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into())
+        }
+        Some(alias) => alias.clone().coerce_to_string(activation)?,
+    };
+
+    match activation
+        .context
+        .avm2
+        .global_domain()
+        .get_class_by_alias(&alias)
+    {
+        Some(class) => Ok(class.into()),
+        None => Err(format!(
+            "ReferenceError: Error #1014: Class {} could not be found.",
+            alias
+        )
+        .into()),
+    }
+}
+
+/// Get the `URLRequest` argument of a navigation function.
+fn request_arg<'gc>(args: &[Value<'gc>]) -> Result<Object<'gc>, Error> {
+    match args.get(0) {
+        Some(Value::Object(request)) => Ok(*request),
+        _ => Err("TypeError: Error #2007: Parameter request must be non-null.".into()),
+    }
+}
+
+/// Implements `flash.net.navigateToURL`.
+///
+/// The request's data is sent as variables, using the request's method.
+pub fn navigate_to_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let request = request_arg(args)?;
+    let window = match args.get(1) {
+        None | Some(Value::Undefined) | Some(Value::Null) => None,
+        Some(window) => Some(window.clone().coerce_to_string(activation)?.to_string()),
+    };
+
+    let url = request_url(activation, request)?;
+    let method = request_method(activation, request)?;
+    let vars_method = request_data(activation, request)?
+        .into_variables()
+        .map(|vars| (method, vars));
+
+    activation
+        .context
+        .navigator
+        .navigate_to_url(url, window, vars_method);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.sendToURL`.
+///
+/// The request is sent in the background, and its response is ignored.
+pub fn send_to_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let request = request_arg(args)?;
+    let (url, options) = request_options(activation, request)?;
+
+    let fetch = activation.context.navigator.fetch(&url, options);
+    activation
+        .context
+        .navigator
+        .spawn_future(Box::pin(async move {
+            if let Err(e) = fetch.await {
+                log::warn!("sendToURL request failed: {}", e);
+            }
+
+            Ok(())
+        }));
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,424 @@
+//! `flash.net.FileReference` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::ioerrorevent;
+use crate::avm2::globals::flash::net::filefilter;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, EventObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, NativeError};
+use crate::backend::ui::SelectedFile;
+use gc_arena::{GcCell, MutationContext};
+use std::path::PathBuf;
+
+/// The error raised when a dialog is requested outside of a user gesture.
+fn user_gesture_error() -> Error {
+    NativeError::security_error(
+        2176,
+        "Certain actions, such as those that display a pop-up window, may only be invoked upon user interaction, for example by a mouse click or button press.",
+    )
+    .into()
+}
+
+/// The error raised when accessing file details before a file was chosen.
+fn incorrect_sequence_error() -> Error {
+    "IllegalOperationError: Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.".into()
+}
+
+/// Implements `flash.net.FileReference`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.FileReference`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Retrieve the file that was chosen for this `FileReference`, if any.
+fn selected_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<SelectedFile>, Error> {
+    let path = match this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "path"),
+        activation,
+    )? {
+        Value::String(path) => path,
+        _ => return Ok(None),
+    };
+    let name = this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "name"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+    let size = this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "size"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+
+    Ok(Some(SelectedFile {
+        path: PathBuf::from(path.to_string()),
+        name: name.to_string(),
+        size: size as u64,
+        modification_date: None,
+    }))
+}
+
+/// Record the file that was chosen for this `FileReference`.
+///
+/// Any previously loaded data is discarded.
+fn set_selected_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    file: &SelectedFile,
+) -> Result<(), Error> {
+    let mc = activation.context.gc_context;
+    let file_type = match file.file_type() {
+        Some(file_type) => AvmString::new(mc, file_type).into(),
+        None => Value::Null,
+    };
+    let properties = [
+        (
+            "path",
+            AvmString::new(mc, file.path.to_string_lossy().into_owned()).into(),
+        ),
+        ("name", AvmString::new(mc, file.name.clone()).into()),
+        ("size", (file.size as f64).into()),
+        ("type", file_type),
+        ("data", Value::Null),
+    ];
+
+    for (name, value) in properties.iter() {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), *name),
+            value.clone(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch a plain `Event` of the given type on a `FileReference`.
+fn dispatch_simple_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().event;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(event_proto),
+        Event::new(event_type),
+    );
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Dispatch an `ioError` event on a `FileReference`.
+fn dispatch_io_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    error: &str,
+) -> Result<(), Error> {
+    let ioerrorevent_proto = activation.context.avm2.prototypes().ioerrorevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(ioerrorevent_proto),
+        Event::new("ioError"),
+    );
+    let text = AvmString::new(
+        activation.context.gc_context,
+        format!("Error #2038: File I/O Error. {}", error),
+    );
+    ioerrorevent::instance_init(
+        activation,
+        Some(event),
+        &["ioError".into(), false.into(), false.into(), text.into()],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Implements `FileReference.browse`.
+///
+/// The open dialog is modal, so `select` or `cancel` has already been
+/// dispatched by the time this returns.
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !activation.context.is_user_gesture {
+            return Err(user_gesture_error());
+        }
+
+        let mut filters = Vec::new();
+        if let Some(Value::Object(filter_array)) = args.get(0) {
+            let filter_objects: Vec<Value<'gc>> = filter_array
+                .as_array_storage()
+                .map(|a| a.iter().map(|v| v.unwrap_or(Value::Undefined)).collect())
+                .unwrap_or_default();
+
+            for filter in filter_objects {
+                let filter = filter.coerce_to_object(activation)?;
+                filters.push(filefilter::to_file_filter(activation, filter)?);
+            }
+        }
+
+        match activation.context.ui.display_file_open_dialog(&filters) {
+            Some(file) => {
+                set_selected_file(activation, this, &file)?;
+                dispatch_simple_event(activation, this, "select")?;
+            }
+            None => dispatch_simple_event(activation, this, "cancel")?,
+        }
+
+        return Ok(true.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`.
+///
+/// The file is read immediately, so `complete` or `ioError` has already been
+/// dispatched by the time this returns.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let file = selected_file(activation, this)?.ok_or_else(incorrect_sequence_error)?;
+
+        dispatch_simple_event(activation, this, "open")?;
+
+        match activation.context.ui.load_file(&file) {
+            Ok(bytes) => {
+                let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+                let data = ByteArrayObject::from_storage(
+                    ByteArrayStorage::from_vec(bytes),
+                    bytearray_proto,
+                    activation.context.gc_context,
+                );
+
+                this.set_property(
+                    this,
+                    &QName::new(Namespace::Private("ruffle".into()), "data"),
+                    data.into(),
+                    activation,
+                )?;
+                dispatch_simple_event(activation, this, "complete")?;
+            }
+            Err(e) => dispatch_io_error(activation, this, &e.to_string())?,
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.save`.
+///
+/// The save dialog is modal, so `select` and `complete`, `cancel`, or
+/// `ioError` have already been dispatched by the time this returns.
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let data = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Null | Value::Undefined => {
+                return Err("ArgumentError: Error #2007: Parameter data must be non-null.".into())
+            }
+            Value::Object(o) if o.as_bytearray().is_some() => {
+                o.as_bytearray().unwrap().bytes().to_vec()
+            }
+            v => v.coerce_to_string(activation)?.as_bytes().to_vec(),
+        };
+        let default_name = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => "".into(),
+            v => v.coerce_to_string(activation)?.to_string(),
+        };
+
+        if !activation.context.is_user_gesture {
+            return Err(user_gesture_error());
+        }
+
+        match activation
+            .context
+            .ui
+            .display_file_save_dialog(&default_name, &data)
+        {
+            Ok(Some(file)) => {
+                set_selected_file(activation, this, &file)?;
+                dispatch_simple_event(activation, this, "select")?;
+                dispatch_simple_event(activation, this, "complete")?;
+            }
+            Ok(None) => dispatch_simple_event(activation, this, "cancel")?,
+            Err(e) => dispatch_io_error(activation, this, &e.to_string())?,
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Retrieve a detail of the chosen file, erroring if no file was chosen.
+fn file_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if selected_file(activation, this)?.is_none() {
+            return Err(incorrect_sequence_error());
+        }
+
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    file_property(activation, this, "name")
+}
+
+/// Implements `FileReference.size`.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    file_property(activation, this, "size")
+}
+
+/// Implements `FileReference.type`.
+pub fn get_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    file_property(activation, this, "type")
+}
+
+/// Implements `FileReference.modificationDate`.
+pub fn modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if selected_file(activation, this)?.is_none() {
+            return Err(incorrect_sequence_error());
+        }
+    }
+
+    //TODO: Return a `Date` once AVM2 has one.
+    Ok(Value::Null)
+}
+
+/// Implements `FileReference.data`.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return match this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            activation,
+        )? {
+            Value::Object(data) => Ok(data.into()),
+            _ => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `FileReference`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "FileReference"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "browse"),
+        Method::from_builtin(browse),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "save"),
+        Method::from_builtin(save),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "type"),
+        Method::from_builtin(get_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "modificationDate"),
+        Method::from_builtin(modification_date),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(data),
+    ));
+
+    class
+}
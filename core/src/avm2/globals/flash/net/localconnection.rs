@@ -0,0 +1,484 @@
+//! `flash.net.LocalConnection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::{asyncerrorevent, statusevent};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, EventObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::external::Value as ExternalValue;
+use crate::local_connection::{
+    movie_domain, qualified_name, LocalConnectionMessage, LocalConnectionObject,
+};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.LocalConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let array_proto = activation.context.avm2.prototypes().array;
+        let allowed_domains = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            array_proto,
+            activation.context.gc_context,
+        );
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "allowedDomains"),
+            allowed_domains.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "client"),
+            this.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "isPerUser"),
+            false.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.LocalConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The domain of the movie using a `LocalConnection`.
+fn own_domain(activation: &mut Activation<'_, '_, '_>) -> String {
+    movie_domain(activation.context.swf.url())
+}
+
+/// Retrieve a string parameter that may not be null.
+fn string_param<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &str,
+) -> Result<AvmString<'gc>, Error> {
+    match args.get(index) {
+        Some(Value::Undefined) | Some(Value::Null) | None => Err(format!(
+            "TypeError: Error #2007: Parameter {} must be non-null.",
+            name
+        )
+        .into()),
+        Some(value) => value.clone().coerce_to_string(activation),
+    }
+}
+
+/// Implements `LocalConnection.connect`.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = string_param(activation, args, 0, "connectionName")?;
+        let domain = own_domain(activation);
+        let name = qualified_name(&domain, &name);
+
+        if !activation.context.local_connections.connect(
+            name,
+            LocalConnectionObject::Avm2(this),
+            domain,
+        ) {
+            return Err(
+                "ArgumentError: Error #2082: Connect failed because the object is already connected."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !activation
+            .context
+            .local_connections
+            .close(LocalConnectionObject::Avm2(this))
+        {
+            return Err(
+                "ArgumentError: Error #2083: Close failed because the object is not connected."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.send`.
+///
+/// The message is delivered at the end of the frame, after which a `status`
+/// event is dispatched on this object.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let connection_name = string_param(activation, args, 0, "connectionName")?;
+        let method_name = string_param(activation, args, 1, "methodName")?;
+
+        let mut arguments = Vec::new();
+        for arg in args.get(2..).unwrap_or(&[]) {
+            arguments.push(ExternalValue::from_avm2(activation, arg.clone())?);
+        }
+
+        let sender_domain = own_domain(activation);
+        activation
+            .context
+            .local_connections
+            .send(LocalConnectionMessage {
+                sender: LocalConnectionObject::Avm2(this),
+                connection_name: qualified_name(&sender_domain, &connection_name),
+                sender_domain,
+                method_name: method_name.to_string(),
+                arguments,
+            });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.domain`'s getter.
+pub fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let domain = own_domain(activation);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.allowDomain` and
+/// `LocalConnection.allowInsecureDomain`.
+///
+/// Each domain given is permitted to send messages to this object. `*`
+/// permits all domains.
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let allowed_domains = this
+            .get_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), "allowedDomains"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        for arg in args {
+            let domain = arg.clone().coerce_to_string(activation)?;
+            if let Some(mut storage) =
+                allowed_domains.as_array_storage_mut(activation.context.gc_context)
+            {
+                storage.push(domain.into());
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.client`'s getter.
+pub fn client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "client"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.client`'s setter.
+pub fn set_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let client = match args.get(0) {
+            Some(Value::Object(client)) => *client,
+            _ => return Err("TypeError: Error #2004: One of the parameters is invalid.".into()),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "client"),
+            client.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.isPerUser`'s getter.
+pub fn is_per_user<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "isPerUser"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.isPerUser`'s setter.
+///
+/// All connections are visible to every movie in the same player, so this
+/// has no effect.
+pub fn set_is_per_user<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let is_per_user = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "isPerUser"),
+            is_per_user.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Check if a `LocalConnection` accepts messages from another domain.
+fn is_domain_allowed<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    sender_domain: &str,
+) -> Result<bool, Error> {
+    let allowed_domains = this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "allowedDomains"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let allowed_domains: Vec<Value<'gc>> = match allowed_domains.as_array_storage() {
+        Some(storage) => storage.iter().flatten().collect(),
+        None => return Ok(false),
+    };
+
+    for domain in allowed_domains {
+        let domain = domain.coerce_to_string(activation)?;
+        if &*domain == "*" || domain.eq_ignore_ascii_case(sender_domain) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Dispatch an `asyncError` event on a `LocalConnection`.
+fn dispatch_async_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    text: String,
+) -> Result<(), Error> {
+    let asyncerrorevent_proto = activation.context.avm2.prototypes().asyncerrorevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(asyncerrorevent_proto),
+        Event::new("asyncError"),
+    );
+    let text = AvmString::new(activation.context.gc_context, text);
+    asyncerrorevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "asyncError".into(),
+            false.into(),
+            false.into(),
+            text.into(),
+            Value::Null,
+        ],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Deliver a message to a connected `LocalConnection`.
+///
+/// The method is called on the connection's `client`. If it cannot be
+/// called, an `asyncError` event is dispatched instead. Returns `false` if
+/// the sender's domain is not allowed to send messages to this object.
+pub fn receive_message<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    same_domain: bool,
+    sender_domain: &str,
+    method_name: &str,
+    arguments: Vec<ExternalValue>,
+) -> Result<bool, Error> {
+    if !same_domain && !is_domain_allowed(activation, this, sender_domain)? {
+        return Ok(false);
+    }
+
+    let mut client = client(activation, Some(this), &[])?.coerce_to_object(activation)?;
+    let args: Vec<Value<'gc>> = arguments
+        .into_iter()
+        .map(|arg| arg.into_avm2(activation))
+        .collect();
+
+    let name = AvmString::new(activation.context.gc_context, method_name.to_string());
+    let method = client.get_property(client, &QName::dynamic_name(name), activation)?;
+    let result = match method {
+        Value::Object(method) => method
+            .call(Some(client), &args, activation, client.proto())
+            .map(|_| ()),
+        _ => Err(format!(
+            "Error #2095: flash.net.LocalConnection was unable to invoke callback {}.",
+            method_name
+        )
+        .into()),
+    };
+
+    if let Err(e) = result {
+        dispatch_async_error(activation, this, e.to_string())?;
+    }
+
+    Ok(true)
+}
+
+/// Dispatch a `status` event on a `LocalConnection`, reporting whether or not
+/// a message it sent was delivered.
+pub fn dispatch_status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    delivered: bool,
+) -> Result<(), Error> {
+    let level = if delivered { "status" } else { "error" };
+
+    let statusevent_proto = activation.context.avm2.prototypes().statusevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(statusevent_proto),
+        Event::new("status"),
+    );
+    statusevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "status".into(),
+            false.into(),
+            false.into(),
+            Value::Null,
+            level.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Construct `LocalConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "LocalConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "connect"),
+        Method::from_builtin(connect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "allowDomain"),
+        Method::from_builtin(allow_domain),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "allowInsecureDomain"),
+        Method::from_builtin(allow_domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "domain"),
+        Method::from_builtin(domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "client"),
+        Method::from_builtin(client),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "client"),
+        Method::from_builtin(set_client),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "isPerUser"),
+        Method::from_builtin(is_per_user),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "isPerUser"),
+        Method::from_builtin(set_is_per_user),
+    ));
+
+    class
+}
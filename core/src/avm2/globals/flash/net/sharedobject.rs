@@ -0,0 +1,383 @@
+//! `flash.net.SharedObject` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::netstatusevent;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The version of AMF that shared object data is stored with.
+const AMF3_VERSION: u32 = 3;
+
+/// Implements `flash.net.SharedObject`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.SharedObject`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Encode a shared object's data in the `.sol` file format.
+///
+/// Each public property of `data` is written as a name followed by its AMF3
+/// value.
+fn serialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &str,
+    mut data: Object<'gc>,
+) -> Result<Vec<u8>, Error> {
+    let mut writer = Amf3Writer::new();
+    let mut index = 1;
+
+    while let Some(property) = data.get_enumerant_name(index) {
+        index += 1;
+        if !property.namespace().is_public() {
+            continue;
+        }
+
+        let value = data.get_property(data, &property, activation)?;
+        if let Value::Object(o) = value {
+            if o.as_executable().is_some() {
+                continue;
+            }
+        }
+
+        writer.write_string(&property.local_name());
+        writer.write_value(activation, value)?;
+        writer.write_u8(0);
+    }
+
+    let mut body = Vec::new();
+    body.extend_from_slice(b"TCSO");
+    body.extend_from_slice(&[0x00, 0x04, 0x00, 0x00, 0x00, 0x00]);
+    body.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    body.extend_from_slice(name.as_bytes());
+    body.extend_from_slice(&AMF3_VERSION.to_be_bytes());
+    body.extend_from_slice(&writer.into_bytes());
+
+    let mut bytes = vec![0x00, 0xBF];
+    bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&body);
+
+    Ok(bytes)
+}
+
+/// Decode shared object data from the `.sol` file format into a fresh object.
+fn deserialize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    bytes: &[u8],
+) -> Result<Object<'gc>, Error> {
+    if bytes.len() < 18 || bytes[0..2] != [0x00, 0xBF][..] || &bytes[6..10] != b"TCSO" {
+        return Err("Invalid shared object header".into());
+    }
+
+    let name_len = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+    let body_start = 18 + name_len + 4;
+    if bytes.len() < body_start {
+        return Err("Invalid shared object header".into());
+    }
+
+    let mut version = [0; 4];
+    version.copy_from_slice(&bytes[body_start - 4..body_start]);
+    if u32::from_be_bytes(version) != AMF3_VERSION {
+        return Err("Shared object data is not encoded with AMF3".into());
+    }
+
+    let object_proto = activation.context.avm2.prototypes().object;
+    let mut data = ScriptObject::object(activation.context.gc_context, object_proto);
+    let mut reader = Amf3Reader::new(&bytes[body_start..]);
+
+    while !reader.is_at_end() {
+        let name = reader.read_string()?;
+        let value = reader.read_value(activation)?;
+        reader.read_u8()?;
+
+        let name = AvmString::new(activation.context.gc_context, name);
+        data.set_property(
+            data,
+            &QName::new(Namespace::public_namespace(), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(data)
+}
+
+/// Retrieve the name a shared object is stored under.
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<AvmString<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "name"),
+        activation,
+    )?
+    .coerce_to_string(activation)
+}
+
+/// Write a shared object's data to the storage backend.
+///
+/// Returns `false` if the data could not be stored, either because the
+/// backend failed, or because it cannot hold `min_disk_space` bytes.
+pub fn flush_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    min_disk_space: usize,
+) -> Result<bool, Error> {
+    let name = name(activation, this)?;
+    let data = data(activation, Some(this), &[])?.coerce_to_object(activation)?;
+    let bytes = serialize(activation, &name, data)?;
+
+    let required_space = min_disk_space.max(bytes.len());
+    let has_space = activation
+        .context
+        .storage
+        .max_size()
+        .map_or(true, |max_size| max_size >= required_space);
+
+    Ok(has_space && activation.context.storage.put_bytes(&name, &bytes))
+}
+
+/// Dispatch a `netStatus` event on a shared object.
+fn dispatch_net_status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    level: &'static str,
+    code: &'static str,
+) -> Result<(), Error> {
+    let object_proto = activation.context.avm2.prototypes().object;
+    let mut info = ScriptObject::object(activation.context.gc_context, object_proto);
+    info.set_property(
+        info,
+        &QName::new(Namespace::public_namespace(), "level"),
+        level.into(),
+        activation,
+    )?;
+    info.set_property(
+        info,
+        &QName::new(Namespace::public_namespace(), "code"),
+        code.into(),
+        activation,
+    )?;
+
+    let netstatusevent_proto = activation.context.avm2.prototypes().netstatusevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(netstatusevent_proto),
+        Event::new("netStatus"),
+    );
+    netstatusevent::instance_init(
+        activation,
+        Some(event),
+        &["netStatus".into(), false.into(), false.into(), info.into()],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Implements `SharedObject.getLocal`.
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(so) = activation.context.avm2.shared_object(&name) {
+        return Ok(so.into());
+    }
+
+    if let Some(Value::String(_)) = args.get(1) {
+        log::warn!("SharedObject.getLocal: localPath is not yet supported");
+    }
+
+    let sharedobject_proto = activation.context.avm2.prototypes().sharedobject;
+    let mut this = sharedobject_proto.construct(activation, &[])?;
+    instance_init(activation, Some(this), &[])?;
+
+    let saved = match activation.context.storage.get_bytes(&name) {
+        Some(bytes) => match deserialize(activation, &bytes) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                log::warn!("Could not load shared object {}: {}", name, e);
+                None
+            }
+        },
+        None => None,
+    };
+    let object_proto = activation.context.avm2.prototypes().object;
+    let data =
+        saved.unwrap_or_else(|| ScriptObject::object(activation.context.gc_context, object_proto));
+
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "name"),
+        name.into(),
+        activation,
+    )?;
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "data"),
+        data.into(),
+        activation,
+    )?;
+
+    activation
+        .context
+        .avm2
+        .add_shared_object(name.to_string(), this);
+
+    Ok(this.into())
+}
+
+/// Implements `SharedObject.data`'s getter.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.flush`.
+///
+/// A `netStatus` event reporting whether or not the data was stored is
+/// dispatched before this returns.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let min_disk_space = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+
+        if flush_data(activation, this, min_disk_space as usize)? {
+            dispatch_net_status(activation, this, "status", "SharedObject.Flush.Success")?;
+
+            return Ok("flushed".into());
+        }
+
+        // Flash Player would ask the user for more space here; we act as
+        // though they declined.
+        dispatch_net_status(activation, this, "error", "SharedObject.Flush.Failed")?;
+
+        return Ok("pending".into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = name(activation, this)?;
+        activation.context.storage.remove_key(&name);
+
+        let object_proto = activation.context.avm2.prototypes().object;
+        let data = ScriptObject::object(activation.context.gc_context, object_proto);
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            data.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.size`'s getter.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = name(activation, this)?;
+        let data = data(activation, Some(this), &[])?.coerce_to_object(activation)?;
+        let bytes = serialize(activation, &name, data)?;
+
+        return Ok(bytes.len().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SharedObject`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "SharedObject"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getLocal"),
+        Method::from_builtin(get_local),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "flush"),
+        Method::from_builtin(flush),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clear"),
+        Method::from_builtin(clear),
+    ));
+
+    class
+}
@@ -0,0 +1,424 @@
+//! `flash.net.URLLoader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::{httpstatusevent, ioerrorevent, progressevent};
+use crate::avm2::globals::flash::net::urlrequest::request_options;
+use crate::avm2::globals::flash::net::urlvariables;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, EventObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::Response;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.URLLoader`'s instance constructor.
+///
+/// If a request is given, it is loaded immediately.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            Value::Undefined,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "dataFormat"),
+            "text".into(),
+            activation,
+        )?;
+        set_bytes(activation, this, 0, 0)?;
+
+        if let Some(Value::Object(_)) = args.get(0) {
+            load(activation, Some(this), args)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLLoader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get a private property of a `URLLoader`.
+fn loader_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Set how much of the data of a `URLLoader` has been loaded.
+fn set_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    bytes_loaded: usize,
+    bytes_total: usize,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "bytesLoaded"),
+        bytes_loaded.into(),
+        activation,
+    )?;
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "bytesTotal"),
+        bytes_total.into(),
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Implements `URLLoader.data`'s getter.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    loader_property(activation, this, "data")
+}
+
+/// Implements `URLLoader.data`'s setter.
+pub fn set_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let data = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            data,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLLoader.dataFormat`'s getter.
+pub fn data_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    loader_property(activation, this, "dataFormat")
+}
+
+/// Implements `URLLoader.dataFormat`'s setter.
+pub fn set_data_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let data_format = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "dataFormat"),
+            data_format.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLLoader.bytesLoaded`'s getter.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    loader_property(activation, this, "bytesLoaded")
+}
+
+/// Implements `URLLoader.bytesTotal`'s getter.
+pub fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    loader_property(activation, this, "bytesTotal")
+}
+
+/// Implements `URLLoader.load`.
+///
+/// The request is made in the background, and its events are dispatched
+/// once the response arrives.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let request = match args.get(0) {
+            Some(Value::Object(request)) => *request,
+            _ => return Err("TypeError: Error #2007: Parameter request must be non-null.".into()),
+        };
+        let (url, options) = request_options(activation, request)?;
+
+        set_bytes(activation, this, 0, 0)?;
+
+        let fetch = activation.context.navigator.fetch_response(&url, options);
+        let process = activation.context.load_manager.load_data_into_url_loader(
+            activation.context.player.clone().unwrap(),
+            this,
+            fetch,
+            url,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Dispatch an event with no extra properties on a `URLLoader`.
+fn dispatch_simple_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().event;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(event_proto),
+        Event::new(event_type),
+    );
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Fire the `open` event of a `URLLoader` whose request has been made.
+pub fn load_started<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<(), Error> {
+    dispatch_simple_event(activation, this, "open")
+}
+
+/// Store the response to the request of a `URLLoader`, and fire its
+/// `progress`, `httpStatus` and `complete` events.
+///
+/// Unsuccessful responses fire `httpStatus` and `ioError` instead. The
+/// response is `None` if the request failed without an HTTP response.
+pub fn load_response<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    url: &str,
+    response: Option<Response>,
+) -> Result<(), Error> {
+    let response = match response {
+        Some(response) if response.is_success() => response,
+        response => {
+            let status = response.map(|response| response.status).unwrap_or(0);
+            dispatch_http_status(activation, this, status)?;
+            return dispatch_io_error(activation, this, url);
+        }
+    };
+
+    let length = response.body.len();
+    set_bytes(activation, this, length, length)?;
+
+    let progressevent_proto = activation.context.avm2.prototypes().progressevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(progressevent_proto),
+        Event::new("progress"),
+    );
+    progressevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "progress".into(),
+            false.into(),
+            false.into(),
+            length.into(),
+            length.into(),
+        ],
+    )?;
+    dispatch_event(activation, this, event)?;
+
+    let data_format =
+        loader_property(activation, Some(this), "dataFormat")?.coerce_to_string(activation)?;
+    let data = match data_format.as_str() {
+        "binary" => {
+            let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+            ByteArrayObject::from_storage(
+                ByteArrayStorage::from_vec(response.body),
+                bytearray_proto,
+                activation.context.gc_context,
+            )
+            .into()
+        }
+        data_format => {
+            // Text is decoded as UTF-8, without its byte order mark.
+            let text = String::from_utf8_lossy(&response.body);
+            let text = AvmString::new(
+                activation.context.gc_context,
+                text.trim_start_matches('\u{feff}').to_string(),
+            );
+
+            if data_format == "variables" {
+                let urlvariables_proto = activation.context.avm2.prototypes().urlvariables;
+                let variables = urlvariables_proto.construct(activation, &[])?;
+                urlvariables::instance_init(activation, Some(variables), &[text.into()])?;
+                variables.into()
+            } else {
+                text.into()
+            }
+        }
+    };
+    this.set_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "data"),
+        data,
+        activation,
+    )?;
+
+    dispatch_http_status(activation, this, response.status)?;
+    dispatch_simple_event(activation, this, "complete")
+}
+
+/// Dispatch an `httpStatus` event on a `URLLoader`.
+fn dispatch_http_status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    status: u16,
+) -> Result<(), Error> {
+    let httpstatusevent_proto = activation.context.avm2.prototypes().httpstatusevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(httpstatusevent_proto),
+        Event::new("httpStatus"),
+    );
+    httpstatusevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "httpStatus".into(),
+            false.into(),
+            false.into(),
+            i32::from(status).into(),
+        ],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Dispatch an `ioError` event on a `URLLoader` whose request failed.
+fn dispatch_io_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    url: &str,
+) -> Result<(), Error> {
+    let ioerrorevent_proto = activation.context.avm2.prototypes().ioerrorevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(ioerrorevent_proto),
+        Event::new("ioError"),
+    );
+    let text = AvmString::new(
+        activation.context.gc_context,
+        format!("Error #2032: Stream Error. URL: {}", url),
+    );
+    ioerrorevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "ioError".into(),
+            false.into(),
+            false.into(),
+            text.into(),
+            2032.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, this, event)?;
+
+    Ok(())
+}
+
+/// Construct `URLLoader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLLoader"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(set_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "dataFormat"),
+        Method::from_builtin(data_format),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "dataFormat"),
+        Method::from_builtin(set_data_format),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "load"),
+        Method::from_builtin(load),
+    ));
+
+    class
+}
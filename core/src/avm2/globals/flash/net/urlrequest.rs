@@ -0,0 +1,499 @@
+//! `flash.net.URLRequest` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::{NavigationMethod, RequestOptions};
+use gc_arena::{GcCell, MutationContext};
+use indexmap::IndexMap;
+use url::form_urlencoded;
+
+/// Implements `flash.net.URLRequest`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let url = match args.get(0) {
+            None | Some(Value::Undefined) | Some(Value::Null) => Value::Null,
+            Some(url) => url.clone().coerce_to_string(activation)?.into(),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "url"),
+            url,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "method"),
+            "GET".into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "data"),
+            Value::Null,
+            activation,
+        )?;
+
+        let request_headers = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        );
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "requestHeaders"),
+            request_headers.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "contentType"),
+            DEFAULT_CONTENT_TYPE.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLRequest`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The MIME type of the data sent along with a request, unless another is set.
+const DEFAULT_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Get a private property of a `URLRequest`.
+fn private_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Set a private property of a `URLRequest`.
+fn set_private_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLRequest.url`'s getter.
+pub fn url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    private_property(activation, this, "url")
+}
+
+/// Implements `URLRequest.url`'s setter.
+pub fn set_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let url = match args.get(0) {
+            None | Some(Value::Undefined) | Some(Value::Null) => Value::Null,
+            Some(url) => url.clone().coerce_to_string(activation)?.into(),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "url"),
+            url,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLRequest.method`'s getter.
+pub fn method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    private_property(activation, this, "method")
+}
+
+/// Implements `URLRequest.method`'s setter.
+///
+/// Only `GET` and `POST` are accepted, in any case.
+pub fn set_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let method = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let method =
+        match NavigationMethod::from_method_str(&method.to_ascii_uppercase()) {
+            Some(NavigationMethod::GET) => "GET",
+            Some(NavigationMethod::POST) => "POST",
+            None => return Err(
+                "ArgumentError: Error #2008: Parameter method must be one of the accepted values."
+                    .into(),
+            ),
+        };
+
+    set_private_property(activation, this, "method", method.into())
+}
+
+/// Implements `URLRequest.data`'s getter.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    private_property(activation, this, "data")
+}
+
+/// Implements `URLRequest.data`'s setter.
+pub fn set_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let data = args.get(0).cloned().unwrap_or(Value::Null);
+
+    set_private_property(activation, this, "data", data)
+}
+
+/// Implements `URLRequest.requestHeaders`'s getter.
+pub fn request_headers_getter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    private_property(activation, this, "requestHeaders")
+}
+
+/// Implements `URLRequest.requestHeaders`'s setter.
+pub fn set_request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let request_headers = args.get(0).cloned().unwrap_or(Value::Null);
+
+    set_private_property(activation, this, "requestHeaders", request_headers)
+}
+
+/// Implements `URLRequest.contentType`'s getter.
+pub fn content_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    private_property(activation, this, "contentType")
+}
+
+/// Implements `URLRequest.contentType`'s setter.
+pub fn set_content_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let content_type = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Value::Null,
+        Some(content_type) => content_type.clone().coerce_to_string(activation)?.into(),
+    };
+
+    set_private_property(activation, this, "contentType", content_type)
+}
+
+/// The data sent along with a `URLRequest`.
+pub enum RequestData {
+    /// The request sends no data.
+    None,
+
+    /// URL-encoded variables, from a `URLVariables` object or a string.
+    Variables(IndexMap<String, String>),
+
+    /// Raw bytes, from a `ByteArray`.
+    Bytes(Vec<u8>),
+}
+
+impl RequestData {
+    /// Get the data as variables, decoding raw bytes as URL-encoded text.
+    pub fn into_variables(self) -> Option<IndexMap<String, String>> {
+        match self {
+            RequestData::None => None,
+            RequestData::Variables(vars) => Some(vars),
+            RequestData::Bytes(bytes) => Some(decode_variables(&bytes)),
+        }
+    }
+}
+
+/// Decode URL-encoded variables.
+fn decode_variables(data: &[u8]) -> IndexMap<String, String> {
+    form_urlencoded::parse(data).into_owned().collect()
+}
+
+/// Get a public property of a `URLRequest`.
+fn request_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut request: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    request.get_property(
+        request,
+        &QName::new(Namespace::public_namespace(), name),
+        activation,
+    )
+}
+
+/// Get the URL of a `URLRequest`.
+pub fn request_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<String, Error> {
+    match request_property(activation, request, "url")? {
+        Value::Undefined | Value::Null => {
+            Err("TypeError: Error #2007: Parameter url must be non-null.".into())
+        }
+        url => Ok(url.coerce_to_string(activation)?.to_string()),
+    }
+}
+
+/// Get the HTTP method of a `URLRequest`.
+///
+/// Any method other than `POST` is treated as `GET`.
+pub fn request_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<NavigationMethod, Error> {
+    match request_property(activation, request, "method")? {
+        Value::Undefined | Value::Null => Ok(NavigationMethod::GET),
+        method => {
+            let method = method.coerce_to_string(activation)?;
+            Ok(
+                NavigationMethod::from_method_str(&method.to_ascii_uppercase())
+                    .unwrap_or(NavigationMethod::GET),
+            )
+        }
+    }
+}
+
+/// Get the data sent along with a `URLRequest`.
+///
+/// Strings are decoded as URL-encoded variables, and any other object sends
+/// its public, enumerable properties.
+pub fn request_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<RequestData, Error> {
+    match request_property(activation, request, "data")? {
+        Value::Undefined | Value::Null => Ok(RequestData::None),
+        Value::Object(mut data) => {
+            if let Some(bytearray) = data.as_bytearray() {
+                return Ok(RequestData::Bytes(bytearray.bytes().to_vec()));
+            }
+
+            let mut vars = IndexMap::new();
+            let mut index = 1;
+            while let Some(name) = data.get_enumerant_name(index) {
+                if name.namespace().is_public() {
+                    let value = data
+                        .get_property(data, &name, activation)?
+                        .coerce_to_string(activation)?;
+                    vars.insert(name.local_name().to_string(), value.to_string());
+                }
+                index += 1;
+            }
+
+            Ok(RequestData::Variables(vars))
+        }
+        data => {
+            let data = data.coerce_to_string(activation)?;
+            Ok(RequestData::Variables(decode_variables(data.as_bytes())))
+        }
+    }
+}
+
+/// Get the HTTP headers of a `URLRequest`, as name/value pairs.
+pub fn request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error> {
+    let headers = match request_property(activation, request, "requestHeaders")? {
+        Value::Object(headers) => headers,
+        _ => return Ok(Vec::new()),
+    };
+    let headers: Vec<Value<'gc>> = match headers.as_array_storage() {
+        Some(array) => array
+            .iter()
+            .map(|h| h.unwrap_or(Value::Undefined))
+            .collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut pairs = Vec::with_capacity(headers.len());
+    for header in headers {
+        if let Value::Object(header) = header {
+            let name =
+                request_property(activation, header, "name")?.coerce_to_string(activation)?;
+            let value =
+                request_property(activation, header, "value")?.coerce_to_string(activation)?;
+            pairs.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// Get the MIME type of the data sent along with a `URLRequest`.
+pub fn request_content_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<String, Error> {
+    match request_property(activation, request, "contentType")? {
+        Value::Undefined | Value::Null => Ok(DEFAULT_CONTENT_TYPE.to_string()),
+        content_type => Ok(content_type.coerce_to_string(activation)?.to_string()),
+    }
+}
+
+/// Build the URL and request options to fetch a `URLRequest` with.
+///
+/// `GET` requests append their variables to the URL, and `POST` requests send
+/// their data as the request body.
+pub fn request_options<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    request: Object<'gc>,
+) -> Result<(String, RequestOptions), Error> {
+    let mut url = request_url(activation, request)?;
+    let method = request_method(activation, request)?;
+    let data = request_data(activation, request)?;
+    let headers = request_headers(activation, request)?;
+
+    let options = match method {
+        NavigationMethod::GET => {
+            if let Some(vars) = data.into_variables() {
+                let query = form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(vars.iter())
+                    .finish();
+                if !query.is_empty() {
+                    url.push(if url.contains('?') { '&' } else { '?' });
+                    url.push_str(&query);
+                }
+            }
+            RequestOptions::get()
+        }
+        NavigationMethod::POST => {
+            let body = match data {
+                RequestData::None => None,
+                RequestData::Variables(vars) => Some(
+                    form_urlencoded::Serializer::new(String::new())
+                        .extend_pairs(vars.iter())
+                        .finish()
+                        .into_bytes(),
+                ),
+                RequestData::Bytes(bytes) => Some(bytes),
+            };
+            let content_type = request_content_type(activation, request)?;
+            RequestOptions::post(body.map(|body| (body, content_type)))
+        }
+    };
+
+    Ok((url, options.with_headers(headers)))
+}
+
+/// Construct `URLRequest`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLRequest"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "url"),
+        Method::from_builtin(url),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "url"),
+        Method::from_builtin(set_url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "method"),
+        Method::from_builtin(method),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "method"),
+        Method::from_builtin(set_method),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "data"),
+        Method::from_builtin(set_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "requestHeaders"),
+        Method::from_builtin(request_headers_getter),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "requestHeaders"),
+        Method::from_builtin(set_request_headers),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "contentType"),
+        Method::from_builtin(content_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "contentType"),
+        Method::from_builtin(set_content_type),
+    ));
+
+    class
+}
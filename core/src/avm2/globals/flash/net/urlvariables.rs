@@ -0,0 +1,182 @@
+//! `flash.net.URLVariables` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use indexmap::IndexMap;
+use url::form_urlencoded;
+
+/// Implements `flash.net.URLVariables`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(Value::Undefined),
+        Some(_) => decode(activation, this, args),
+    }
+}
+
+/// Implements `flash.net.URLVariables`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLVariables.decode`.
+///
+/// Each URL-encoded variable is set as a public property of the object. A
+/// variable that appears more than once is set to an array of its values.
+pub fn decode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let mut vars: IndexMap<String, Vec<String>> = IndexMap::new();
+        for pair in source.split('&').filter(|pair| !pair.is_empty()) {
+            if !pair.contains('=') {
+                return Err("Error: Error #2101: The String passed to URLVariables.decode() must be a URL-encoded query string containing name/value pairs.".into());
+            }
+
+            for (name, value) in form_urlencoded::parse(pair.as_bytes()) {
+                vars.entry(name.into_owned())
+                    .or_default()
+                    .push(value.into_owned());
+            }
+        }
+
+        for (name, values) in vars {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let mut values: Vec<Value<'gc>> = values
+                .into_iter()
+                .map(|value| AvmString::new(activation.context.gc_context, value).into())
+                .collect();
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                ArrayObject::from_array(
+                    ArrayStorage::from_args(&values),
+                    activation.context.avm2.prototypes().array,
+                    activation.context.gc_context,
+                )
+                .into()
+            };
+
+            this.set_property(this, &QName::dynamic_name(name), value, activation)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLVariables.toString`.
+///
+/// Every public, enumerable property is encoded as a `name=value` pair, with
+/// array properties giving one pair per element.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut pairs = Vec::new();
+        let mut index = 1;
+        while let Some(name) = this.get_enumerant_name(index) {
+            index += 1;
+            if !name.namespace().is_public() {
+                continue;
+            }
+
+            let value = this.get_property(this, &name, activation)?;
+            let values: Vec<Value<'gc>> = match value {
+                Value::Object(object) => match object.as_array_storage() {
+                    Some(array) => array
+                        .iter()
+                        .map(|value| value.unwrap_or(Value::Undefined))
+                        .collect(),
+                    None => vec![value],
+                },
+                value => vec![value],
+            };
+
+            for value in values {
+                let value = value.coerce_to_string(activation)?;
+                pairs.push(format!("{}={}", escape(&name.local_name()), escape(&value)));
+            }
+        }
+
+        return Ok(AvmString::new(activation.context.gc_context, pairs.join("&")).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Percent-encode a variable name or value the way Flash does.
+///
+/// Only ASCII letters, digits, and `-_.!~*'()` are left as-is; every other
+/// character is encoded as UTF-8, including spaces.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    escaped
+}
+
+/// Construct `URLVariables`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLVariables"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "decode"),
+        Method::from_builtin(decode),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
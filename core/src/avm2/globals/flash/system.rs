@@ -1,3 +1,9 @@
 //! `flash.system` namespace
 
 pub mod application_domain;
+pub mod messagechannel;
+pub mod messagechannelstate;
+pub mod system;
+pub mod worker;
+pub mod workerdomain;
+pub mod workerstate;
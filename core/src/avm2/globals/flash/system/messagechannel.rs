@@ -0,0 +1,196 @@
+//! `flash.system.MessageChannel` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::system::worker::{from_shared_value, to_shared_value};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::ChannelHandle;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.system.MessageChannel`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: MessageChannel class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.MessageChannel`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the `MessageChannel` object of a message channel, creating it if this
+/// player has not seen the channel before.
+pub fn channel_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    handle: ChannelHandle,
+) -> Result<Object<'gc>, Error> {
+    if let Some(object) = activation.context.avm2.workers().channel_object(&handle) {
+        return Ok(object);
+    }
+
+    let messagechannel_proto = activation.context.avm2.prototypes().messagechannel;
+    let object = messagechannel_proto.construct(activation, &[])?;
+    activation
+        .context
+        .avm2
+        .workers()
+        .add_channel(object, handle);
+
+    Ok(object)
+}
+
+/// Get the message channel represented by a `MessageChannel` object.
+fn channel_handle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<ChannelHandle, Error> {
+    activation
+        .context
+        .avm2
+        .workers()
+        .channel_handle(this)
+        .ok_or_else(|| "MessageChannel object does not belong to any channel".into())
+}
+
+/// Implements `MessageChannel.messageAvailable`.
+pub fn message_available<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(channel_handle(activation, this)?.message_available().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.state`.
+pub fn state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(channel_handle(activation, this)?.state().as_str().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.send`.
+///
+/// Only the sending worker may send messages over a channel. Messages sent
+/// over a closed channel are dropped.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let handle = channel_handle(activation, this)?;
+        let current = activation.context.avm2.workers().current().clone();
+        if !Arc::ptr_eq(handle.sender(), &current) {
+            return Err(
+                "IllegalOperationError: Error #3721: Only the sending worker may send messages."
+                    .into(),
+            );
+        }
+
+        let message = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let message = to_shared_value(activation, message)?;
+        handle.send(message);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`.
+///
+/// Returns `null` if no message is available. Blocking until a message
+/// arrives is not supported.
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let handle = channel_handle(activation, this)?;
+        let current = activation.context.avm2.workers().current().clone();
+        if !Arc::ptr_eq(handle.receiver(), &current) {
+            return Err(
+                "IllegalOperationError: Error #3722: Only the receiving worker may receive messages."
+                    .into(),
+            );
+        }
+
+        return match handle.receive() {
+            Some(message) => from_shared_value(activation, message),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        channel_handle(activation, this)?.close();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `MessageChannel`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "MessageChannel"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "messageAvailable"),
+        Method::from_builtin(message_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "state"),
+        Method::from_builtin(state),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "receive"),
+        Method::from_builtin(receive),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "close"),
+        Method::from_builtin(close),
+    ));
+
+    class
+}
@@ -0,0 +1,93 @@
+//! `flash.system.System` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.System`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: System class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.System`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `System.totalMemory`.
+///
+/// This is the size of the garbage-collected heap, which is what scripts
+/// allocate from.
+pub fn total_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let total_memory = activation.context.gc_stats.total_allocated;
+    Ok((total_memory.min(u32::MAX as usize) as u32).into())
+}
+
+/// Implements `System.totalMemoryNumber`.
+pub fn total_memory_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((activation.context.gc_stats.total_allocated as f64).into())
+}
+
+/// Implements `System.gc`.
+///
+/// Nothing can be collected while a script runs, so the collection is done
+/// once the script has finished.
+pub fn gc<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    *activation.context.needs_collect = true;
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `System`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "System"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "totalMemory"),
+        Method::from_builtin(total_memory),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "totalMemoryNumber"),
+        Method::from_builtin(total_memory_number),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "gc"),
+        Method::from_builtin(gc),
+    ));
+
+    class
+}
@@ -0,0 +1,330 @@
+//! `flash.system.Worker` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::system::messagechannel;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ByteArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::{is_supported, ChannelData, SharedValue, WorkerData, WorkerHandle};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.Worker`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Worker class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.Worker`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the `Worker` object of a worker, creating it if this player has not
+/// seen the worker before.
+///
+/// Each worker has only one object per player, so that workers can be
+/// compared with one another.
+pub fn worker_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    handle: WorkerHandle,
+) -> Result<Object<'gc>, Error> {
+    if let Some(object) = activation.context.avm2.workers().worker_object(&handle) {
+        return Ok(object);
+    }
+
+    let worker_proto = activation.context.avm2.prototypes().worker;
+    let object = worker_proto.construct(activation, &[])?;
+    activation.context.avm2.workers().add_worker(object, handle);
+
+    Ok(object)
+}
+
+/// Get the worker represented by a `Worker` object.
+fn worker_handle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<WorkerHandle, Error> {
+    activation
+        .context
+        .avm2
+        .workers()
+        .worker_handle(this)
+        .ok_or_else(|| "Worker object does not belong to any worker".into())
+}
+
+/// Prepare a value to be passed to another worker.
+///
+/// Workers and message channels are passed by reference; everything else is
+/// copied as AMF3.
+pub fn to_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<SharedValue, Error> {
+    if let Value::Object(object) = value {
+        let workers = activation.context.avm2.workers();
+        if let Some(handle) = workers.worker_handle(object) {
+            return Ok(SharedValue::Worker(handle));
+        }
+        if let Some(handle) = workers.channel_handle(object) {
+            return Ok(SharedValue::MessageChannel(handle));
+        }
+    }
+
+    let mut writer = Amf3Writer::new();
+    writer.write_value(activation, value)?;
+
+    Ok(SharedValue::Amf(writer.into_bytes()))
+}
+
+/// Retrieve a value that was passed from another worker.
+pub fn from_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: SharedValue,
+) -> Result<Value<'gc>, Error> {
+    match value {
+        SharedValue::Amf(bytes) => Amf3Reader::new(&bytes).read_value(activation),
+        SharedValue::Worker(handle) => Ok(worker_object(activation, handle)?.into()),
+        SharedValue::MessageChannel(handle) => {
+            Ok(messagechannel::channel_object(activation, handle)?.into())
+        }
+    }
+}
+
+/// Set a shared property of the current worker to a `ByteArray` holding the
+/// given bytes.
+pub fn set_shared_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    key: &str,
+    bytes: Vec<u8>,
+) -> Result<(), Error> {
+    let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+    let bytearray = ByteArrayObject::from_storage(
+        ByteArrayStorage::from_vec(bytes),
+        bytearray_proto,
+        activation.context.gc_context,
+    );
+    let value = to_shared_value(activation, bytearray.into())?;
+
+    activation
+        .context
+        .avm2
+        .workers()
+        .current()
+        .set_shared_property(key.to_string(), Some(value));
+
+    Ok(())
+}
+
+/// Implements `Worker.current`.
+pub fn current<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let handle = activation.context.avm2.workers().current().clone();
+
+    Ok(worker_object(activation, handle)?.into())
+}
+
+/// Implements `Worker.isSupported`.
+pub fn is_supported_getter<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(is_supported().into())
+}
+
+/// Implements `Worker.isPrimordial`.
+pub fn is_primordial<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(worker_handle(activation, this)?.is_primordial().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.state`.
+pub fn state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(worker_handle(activation, this)?.state().as_str().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.start`.
+///
+/// The worker's movie starts running on a thread of its own. The `workerState`
+/// event fires at the end of the frame.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let handle = worker_handle(activation, this)?;
+        WorkerData::start(&handle);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.terminate`.
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(worker_handle(activation, this)?.terminate().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.getSharedProperty`.
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return match worker_handle(activation, this)?.shared_property(&key) {
+            Some(value) => from_shared_value(activation, value),
+            None => Ok(Value::Undefined),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.setSharedProperty`.
+///
+/// Setting a property to `undefined` removes it.
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let value = match args.get(1).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined => None,
+            value => Some(to_shared_value(activation, value)?),
+        };
+
+        worker_handle(activation, this)?.set_shared_property(key.to_string(), value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.createMessageChannel`.
+///
+/// This worker sends messages over the new channel, and `receiver` receives
+/// them.
+pub fn create_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let receiver = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let sender = worker_handle(activation, this)?;
+        let receiver = worker_handle(activation, receiver)?;
+
+        let handle = ChannelData::new(sender, receiver);
+        return Ok(messagechannel::channel_object(activation, handle)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Worker`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Worker"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "current"),
+        Method::from_builtin(current),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "isSupported"),
+        Method::from_builtin(is_supported_getter),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "isPrimordial"),
+        Method::from_builtin(is_primordial),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "state"),
+        Method::from_builtin(state),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "start"),
+        Method::from_builtin(start),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "terminate"),
+        Method::from_builtin(terminate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getSharedProperty"),
+        Method::from_builtin(get_shared_property),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "setSharedProperty"),
+        Method::from_builtin(set_shared_property),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "createMessageChannel"),
+        Method::from_builtin(create_message_channel),
+    ));
+
+    class
+}
@@ -0,0 +1,119 @@
+//! `flash.system.WorkerDomain` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::system::worker::worker_object;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::{is_supported, WorkerData};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.WorkerDomain`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: WorkerDomain class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.WorkerDomain`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `WorkerDomain.current`.
+///
+/// The domain is created the first time it is asked for, and kept on the
+/// class from then on.
+pub fn current<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = QName::new(Namespace::Private("ruffle".into()), "current");
+        if let Value::Object(current) = this.get_property(this, &name, activation)? {
+            return Ok(current.into());
+        }
+
+        let workerdomain_proto = activation.context.avm2.prototypes().workerdomain;
+        let current = workerdomain_proto.construct(activation, &[])?;
+        this.set_property(this, &name, current.into(), activation)?;
+
+        return Ok(current.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `WorkerDomain.isSupported`.
+pub fn is_supported_getter<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(is_supported().into())
+}
+
+/// Implements `WorkerDomain.createWorker`.
+///
+/// The new worker runs the SWF held by the given `ByteArray` once it is
+/// started.
+pub fn create_worker<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let swf = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let swf = swf
+        .as_bytearray()
+        .map(|bytearray| bytearray.bytes().to_vec())
+        .ok_or("TypeError: Error #1034: Type Coercion failed: cannot convert to flash.utils.ByteArray.")?;
+
+    let parent = activation.context.avm2.workers().current().clone();
+    let handle = WorkerData::background(&parent, swf);
+
+    Ok(worker_object(activation, handle)?.into())
+}
+
+/// Construct `WorkerDomain`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "WorkerDomain"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "current"),
+        Method::from_builtin(current),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "isSupported"),
+        Method::from_builtin(is_supported_getter),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "createWorker"),
+        Method::from_builtin(create_worker),
+    ));
+
+    class
+}
@@ -0,0 +1,4 @@
+//! `flash.text` namespace
+
+pub mod textfield;
+pub mod textsnapshot;
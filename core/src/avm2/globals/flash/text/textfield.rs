@@ -0,0 +1,557 @@
+//! `flash.text.TextField` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{AntiAliasType, EditText, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.TextField`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_none() {
+            let movie = activation.context.swf.clone();
+            let new_do = EditText::new(&mut activation.context, movie, 0.0, 0.0, 100.0, 100.0);
+            new_do.set_object2(activation.context.gc_context, this);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.TextField`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Retrieve the `EditText` backing a `TextField` object.
+fn edit_text<'gc>(this: Option<Object<'gc>>) -> Option<EditText<'gc>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_edit_text())
+}
+
+/// Implements `TextField.text`'s getter.
+pub fn text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(AvmString::new(activation.context.gc_context, text_field.text()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.text`'s setter.
+pub fn set_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        text_field.set_text(text.to_string(), &mut activation.context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.text_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.type`'s getter.
+pub fn get_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let tf_type = match text_field.is_editable() {
+            true => "input",
+            false => "dynamic",
+        };
+
+        return Ok(AvmString::new(activation.context.gc_context, tf_type).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.type`'s setter.
+pub fn set_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let tf_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        match tf_type.as_str() {
+            "input" => text_field.set_editable(true, &mut activation.context),
+            "dynamic" => text_field.set_editable(false, &mut activation.context),
+            _ => return Err(
+                "ArgumentError: Error #2008: Parameter type must be one of the accepted values."
+                    .into(),
+            ),
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.restrict`'s getter.
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(match text_field.restrict() {
+            Some(restrict) => AvmString::new(activation.context.gc_context, restrict).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.restrict`'s setter.
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => {
+                text_field.set_restrict(None, activation.context.gc_context)
+            }
+            value => {
+                let restrict = value.coerce_to_string(activation)?;
+                text_field.set_restrict(Some(&restrict), activation.context.gc_context);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.maxChars`'s getter.
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.maxChars`'s setter.
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        text_field.set_max_chars(max_chars, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.scrollV`'s getter.
+pub fn scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.scrollV`'s setter.
+pub fn set_scroll_v<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let scroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        text_field.set_scroll(scroll.into(), activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.maxScrollV`'s getter.
+pub fn max_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.max_scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.mouseWheelEnabled`'s getter.
+pub fn mouse_wheel_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.mouseWheelEnabled`'s setter.
+pub fn set_mouse_wheel_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let is_enabled = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        text_field.set_mouse_wheel_enabled(is_enabled, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.antiAliasType`'s getter.
+pub fn anti_alias_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            text_field.anti_alias_type().as_str(),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.antiAliasType`'s setter.
+pub fn set_anti_alias_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        match AntiAliasType::from_name(&name) {
+            Some(anti_alias_type) => {
+                text_field.set_anti_alias_type(anti_alias_type, activation.context.gc_context)
+            }
+            None => {
+                return Err(
+                    "ArgumentError: Error #2008: Parameter antiAliasType must be one of the accepted values."
+                        .into(),
+                )
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.sharpness`'s getter.
+pub fn sharpness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.sharpness().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.sharpness`'s setter.
+pub fn set_sharpness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let sharpness = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        text_field.set_sharpness(sharpness, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.thickness`'s getter.
+pub fn thickness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        return Ok(text_field.thickness().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.thickness`'s setter.
+pub fn set_thickness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let thickness = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        text_field.set_thickness(thickness, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.appendText`.
+///
+/// Characters disallowed by `restrict` are dropped from the appended text,
+/// which is also cut short to fit within `maxChars`.
+pub fn append_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let new_text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let end = text_field.text_length();
+        let new_text = text_field.filter_input(end, end, &new_text);
+
+        text_field.replace_text(end, end, &new_text, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.replaceText`.
+///
+/// Characters disallowed by `restrict` are dropped from the new text, which
+/// is also cut short to fit within `maxChars`.
+pub fn replace_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(text_field) = edit_text(this) {
+        let begin_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)? as usize;
+        let end_index = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)? as usize;
+        let new_text = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let length = text_field.text_length();
+        let end_index = end_index.min(length);
+        let begin_index = begin_index.min(end_index);
+        let new_text = text_field.filter_input(begin_index, end_index, &new_text);
+
+        text_field.replace_text(begin_index, end_index, &new_text, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextField`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextField"),
+        Some(QName::new(Namespace::package("flash.display"), "InteractiveObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "text"),
+        Method::from_builtin(text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "text"),
+        Method::from_builtin(set_text),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "type"),
+        Method::from_builtin(get_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "type"),
+        Method::from_builtin(set_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "restrict"),
+        Method::from_builtin(restrict),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "restrict"),
+        Method::from_builtin(set_restrict),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "maxChars"),
+        Method::from_builtin(max_chars),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "maxChars"),
+        Method::from_builtin(set_max_chars),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "scrollV"),
+        Method::from_builtin(scroll_v),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "scrollV"),
+        Method::from_builtin(set_scroll_v),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "maxScrollV"),
+        Method::from_builtin(max_scroll_v),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "mouseWheelEnabled"),
+        Method::from_builtin(mouse_wheel_enabled),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "mouseWheelEnabled"),
+        Method::from_builtin(set_mouse_wheel_enabled),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "antiAliasType"),
+        Method::from_builtin(anti_alias_type),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "antiAliasType"),
+        Method::from_builtin(set_anti_alias_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "sharpness"),
+        Method::from_builtin(sharpness),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "sharpness"),
+        Method::from_builtin(set_sharpness),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "thickness"),
+        Method::from_builtin(thickness),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "thickness"),
+        Method::from_builtin(set_thickness),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "appendText"),
+        Method::from_builtin(append_text),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "replaceText"),
+        Method::from_builtin(replace_text),
+    ));
+
+    class
+}
@@ -0,0 +1,535 @@
+//! `flash.text.TextSnapshot` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::array::build_array;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{
+    DisplayObject, StaticTextChar, TDisplayObject, TDisplayObjectContainer, Text,
+};
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// A character of a snapshot, along with the static text it is part of.
+struct SnapshotChar<'gc> {
+    text: Text<'gc>,
+
+    /// The index of the character within its static text.
+    index: usize,
+
+    character: StaticTextChar,
+
+    /// The transform from the local space of the static text to the local
+    /// space of the snapshot's container.
+    text_matrix: Matrix,
+}
+
+impl SnapshotChar<'_> {
+    /// The corners of the box of this character, in the local space of the
+    /// snapshot's container.
+    fn corners(&self) -> [(Twips, Twips); 4] {
+        let mut corners = self.character.corners();
+        for corner in corners.iter_mut() {
+            *corner = self.text_matrix * *corner;
+        }
+        corners
+    }
+}
+
+/// Implements `flash.text.TextSnapshot`'s instance constructor.
+///
+/// Flash Player only creates snapshots through
+/// `DisplayObjectContainer.textSnapshot`, which passes the container here.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let container = match args.get(0) {
+            Some(Value::Object(container))
+                if container
+                    .as_display_object()
+                    .and_then(|dobj| dobj.as_container())
+                    .is_some() =>
+            {
+                Value::Object(*container)
+            }
+            _ => Value::Null,
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "container"),
+            container,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.TextSnapshot`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `TextSnapshot` of the static text in a container.
+pub fn from_container<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    container: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let textsnapshot_proto = activation.context.avm2.prototypes().textsnapshot;
+    let object = textsnapshot_proto.construct(activation, &[])?;
+    instance_init(activation, Some(object), &[container.into()])?;
+
+    Ok(object)
+}
+
+/// Collect the characters of every static text directly inside the
+/// snapshot's container, in depth order.
+fn snapshot_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Vec<SnapshotChar<'gc>>, Error> {
+    let container = this
+        .get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "container"),
+            activation,
+        )?
+        .coerce_to_object(activation)
+        .ok()
+        .and_then(|container| container.as_display_object())
+        .and_then(|container| container.as_container());
+
+    let mut chars = vec![];
+    if let Some(container) = container {
+        for child in container.iter_render_list() {
+            if let DisplayObject::Text(text) = child {
+                let text_matrix = *text.matrix();
+                for (index, mut character) in
+                    text.chars(&activation.context).into_iter().enumerate()
+                {
+                    // Every static text starts on a line of its own.
+                    character.new_line |= index == 0 && !chars.is_empty();
+                    chars.push(SnapshotChar {
+                        text,
+                        index,
+                        character,
+                        text_matrix,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(chars)
+}
+
+/// Coerce a pair of `beginIndex` and `endIndex` arguments into a range of
+/// characters of the snapshot.
+fn char_range<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    len: usize,
+) -> Result<std::ops::Range<usize>, Error> {
+    let begin = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let end = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    let clamp = |index: f64| {
+        if index.is_nan() {
+            0
+        } else {
+            index.max(0.0).min(len as f64) as usize
+        }
+    };
+    let begin = clamp(begin);
+    let end = clamp(end).max(begin);
+    Ok(begin..end)
+}
+
+/// Concatenate the characters of a snapshot, optionally separating lines
+/// with newlines.
+fn chars_to_string<'a>(
+    chars: impl Iterator<Item = &'a StaticTextChar>,
+    include_line_endings: bool,
+) -> String {
+    let mut text = String::new();
+    for character in chars {
+        if include_line_endings && character.new_line && !text.is_empty() {
+            text.push('\n');
+        }
+        text.push(character.character);
+    }
+    text
+}
+
+/// Implements `TextSnapshot.charCount`.
+pub fn char_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok((snapshot_chars(activation, this)?.len() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.getText`.
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let range = char_range(activation, args, chars.len())?;
+        let include_line_endings = args
+            .get(2)
+            .map(|value| value.coerce_to_boolean())
+            .unwrap_or(false);
+
+        let text = chars_to_string(
+            chars[range].iter().map(|c| &c.character),
+            include_line_endings,
+        );
+        return Ok(AvmString::new(activation.context.gc_context, text).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.findText`.
+pub fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let begin = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let needle = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let case_sensitive = args
+            .get(2)
+            .map(|value| value.coerce_to_boolean())
+            .unwrap_or(false);
+
+        let fold = |c: char| {
+            if case_sensitive {
+                c
+            } else {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+        };
+        let haystack: Vec<char> = chars.iter().map(|c| fold(c.character.character)).collect();
+        let needle: Vec<char> = needle.chars().map(fold).collect();
+
+        if begin >= 0 && !needle.is_empty() {
+            let found =
+                (begin as usize..haystack.len()).find(|&i| haystack[i..].starts_with(&needle[..]));
+            if let Some(i) = found {
+                return Ok((i as i32).into());
+            }
+        }
+
+        return Ok((-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.getSelected`.
+pub fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let range = char_range(activation, args, chars.len())?;
+
+        let selected = chars[range]
+            .iter()
+            .any(|c| c.text.is_char_selected(c.index));
+        return Ok(selected.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelected`.
+pub fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let range = char_range(activation, args, chars.len())?;
+        let selected = args
+            .get(2)
+            .map(|value| value.coerce_to_boolean())
+            .unwrap_or(false);
+
+        for c in &chars[range] {
+            c.text
+                .set_char_selected(activation.context.gc_context, c.index, selected);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.getSelectedText`.
+pub fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let include_line_endings = args
+            .get(0)
+            .map(|value| value.coerce_to_boolean())
+            .unwrap_or(false);
+
+        let text = chars_to_string(
+            chars
+                .iter()
+                .filter(|c| c.text.is_char_selected(c.index))
+                .map(|c| &c.character),
+            include_line_endings,
+        );
+        return Ok(AvmString::new(activation.context.gc_context, text).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelectColor`.
+pub fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let color = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0xFFFF00.into())
+            .coerce_to_u32(activation)?;
+
+        // The highlight is always opaque.
+        let color = swf::Color::from_rgb(color, 0xFF);
+        for c in &chars {
+            c.text
+                .set_select_color(activation.context.gc_context, color.clone());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.hitTestTextNearPos`.
+///
+/// Returns the index of the character whose box contains the point, or
+/// otherwise of the closest character within `maxDistance` of it.
+pub fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let y = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let max_distance = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_number(activation)?;
+
+        let mut nearest = None;
+        for (i, c) in chars.iter().enumerate() {
+            let corners = c.corners();
+            let (mut x_min, mut x_max) = (f64::INFINITY, f64::NEG_INFINITY);
+            let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for (corner_x, corner_y) in corners.iter() {
+                x_min = x_min.min(corner_x.to_pixels());
+                x_max = x_max.max(corner_x.to_pixels());
+                y_min = y_min.min(corner_y.to_pixels());
+                y_max = y_max.max(corner_y.to_pixels());
+            }
+
+            let dx = (x_min - x).max(x - x_max).max(0.0);
+            let dy = (y_min - y).max(y - y_max).max(0.0);
+            let distance = (dx * dx + dy * dy).sqrt();
+            if distance <= max_distance && nearest.map_or(true, |(_, nearest)| distance < nearest) {
+                nearest = Some((i, distance));
+            }
+        }
+
+        return Ok(nearest.map_or(-1, |(i, _)| i as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.getTextRunInfo`.
+///
+/// Each character in the range is described by an object holding its index,
+/// selection state, font and color, along with the transform of its text and
+/// the corners of its box in the space of the snapshot's container.
+pub fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let chars = snapshot_chars(activation, this)?;
+        let range = char_range(activation, args, chars.len())?;
+        let object_proto = activation.context.avm2.prototypes().object;
+
+        let mut runs = ArrayStorage::new(0);
+        for (i, c) in chars.iter().enumerate().take(range.end).skip(range.start) {
+            let matrix = c.text_matrix * c.character.matrix;
+            let font = AvmString::new(activation.context.gc_context, c.character.font_name.clone());
+            let color = &c.character.color;
+            let mut properties: Vec<(&'static str, Value<'gc>)> = vec![
+                ("indexInRun", (i as i32).into()),
+                ("selected", c.text.is_char_selected(c.index).into()),
+                ("font", font.into()),
+                (
+                    "color",
+                    (u32::from(color.r) << 16 | u32::from(color.g) << 8 | u32::from(color.b))
+                        .into(),
+                ),
+                ("height", c.character.height.to_pixels().into()),
+                ("matrix_a", f64::from(matrix.a).into()),
+                ("matrix_b", f64::from(matrix.b).into()),
+                ("matrix_c", f64::from(matrix.c).into()),
+                ("matrix_d", f64::from(matrix.d).into()),
+                ("matrix_tx", matrix.tx.to_pixels().into()),
+                ("matrix_ty", matrix.ty.to_pixels().into()),
+            ];
+
+            let corner_names = [
+                ("corner0x", "corner0y"),
+                ("corner1x", "corner1y"),
+                ("corner2x", "corner2y"),
+                ("corner3x", "corner3y"),
+            ];
+            for ((x_name, y_name), (x, y)) in corner_names.iter().zip(c.corners().iter()) {
+                properties.push((*x_name, x.to_pixels().into()));
+                properties.push((*y_name, y.to_pixels().into()));
+            }
+
+            let mut run = ScriptObject::object(activation.context.gc_context, object_proto);
+            for (name, value) in properties {
+                run.set_property(
+                    run,
+                    &QName::new(Namespace::public_namespace(), name),
+                    value,
+                    activation,
+                )?;
+            }
+            runs.push(run.into());
+        }
+
+        return build_array(activation, runs);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextSnapshot`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextSnapshot"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "charCount"),
+        Method::from_builtin(char_count),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "findText"),
+        Method::from_builtin(find_text),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getSelected"),
+        Method::from_builtin(get_selected),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getSelectedText"),
+        Method::from_builtin(get_selected_text),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getText"),
+        Method::from_builtin(get_text),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getTextRunInfo"),
+        Method::from_builtin(get_text_run_info),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "hitTestTextNearPos"),
+        Method::from_builtin(hit_test_text_near_pos),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "setSelectColor"),
+        Method::from_builtin(set_select_color),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "setSelected"),
+        Method::from_builtin(set_selected),
+    ));
+
+    class
+}
@@ -0,0 +1,4 @@
+//! `flash.ui` namespace
+
+pub mod context_menu;
+pub mod context_menu_item;
@@ -0,0 +1,244 @@
+//! `flash.ui.ContextMenu` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::contextmenuevent;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::ContextMenuItem;
+use crate::context_menu::{BuiltInItemFlags, ContextMenuCallback, ContextMenuState};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.ContextMenu`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let custom_items = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        );
+        set_custom_items(activation, Some(this), &[custom_items.into()])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.ContextMenu`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.customItems`'s getter.
+pub fn custom_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "customItems"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.customItems`'s setter.
+pub fn set_custom_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let custom_items = args.get(0).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "customItems"),
+            custom_items,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.hideBuiltInItems`.
+pub fn hide_built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "builtInItemsHidden"),
+            true.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Build the context menu for a right click on a display object.
+///
+/// The `ContextMenu` used is that of the object, or of its closest ancestor
+/// that has one. It is sent a `menuSelect` event before the menu is built.
+pub fn open<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: DisplayObject<'gc>,
+) -> Result<ContextMenuState<'gc>, Error> {
+    let mouse_target = target.object2().coerce_to_object(activation)?;
+
+    let mut node = Some(target);
+    while let Some(display_object) = node {
+        if let Value::Object(mut owner) = display_object.object2() {
+            let menu = owner.get_property(
+                owner,
+                &QName::new(Namespace::public_namespace(), "contextMenu"),
+                activation,
+            )?;
+            if let Value::Object(menu) = menu {
+                contextmenuevent::dispatch(activation, menu, "menuSelect", mouse_target, owner)?;
+                return make_context_menu_state(Some(menu), mouse_target, owner, activation);
+            }
+        }
+        node = display_object.parent();
+    }
+
+    make_context_menu_state(None, mouse_target, mouse_target, activation)
+}
+
+/// Build the context menu to show for an AVM2 `ContextMenu`.
+///
+/// `mouse_target` is the object that was right clicked, and `owner` is the
+/// display object that the menu belongs to.
+pub fn make_context_menu_state<'gc>(
+    menu: Option<Object<'gc>>,
+    mouse_target: Object<'gc>,
+    owner: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ContextMenuState<'gc>, Error> {
+    let mut result = ContextMenuState::new();
+    let mut builtin_items = BuiltInItemFlags::default();
+
+    if let Some(mut menu) = menu {
+        let hidden = menu
+            .get_property(
+                menu,
+                &QName::new(Namespace::Private("ruffle".into()), "builtInItemsHidden"),
+                activation,
+            )?
+            .coerce_to_boolean();
+        if hidden {
+            builtin_items = BuiltInItemFlags {
+                play: false,
+                rewind: false,
+                forward_and_back: false,
+            };
+        }
+
+        let custom_items = custom_items(activation, Some(menu), &[])?;
+        let custom_items: Vec<Value<'gc>> = match custom_items {
+            Value::Object(custom_items) => match custom_items.as_array_storage() {
+                Some(array) => (0..array.length())
+                    .map(|i| array.get(i).unwrap_or(Value::Undefined))
+                    .collect(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        for item in custom_items {
+            let mut item = match item {
+                Value::Object(item) => item,
+                _ => continue,
+            };
+            let mut get = |name: &'static str, activation: &mut Activation<'_, 'gc, '_>| {
+                item.get_property(
+                    item,
+                    &QName::new(Namespace::public_namespace(), name),
+                    activation,
+                )
+            };
+            if !get("visible", activation)?.coerce_to_boolean() {
+                continue;
+            }
+
+            let caption = get("caption", activation)?
+                .coerce_to_string(activation)?
+                .to_string();
+            let enabled = get("enabled", activation)?.coerce_to_boolean();
+            let separator_before = get("separatorBefore", activation)?.coerce_to_boolean();
+            result.push(
+                ContextMenuItem {
+                    caption,
+                    enabled,
+                    separator_before,
+                    checked: false,
+                },
+                ContextMenuCallback::Avm2 {
+                    item,
+                    mouse_target,
+                    owner,
+                },
+            );
+        }
+    }
+
+    let root = activation
+        .context
+        .levels
+        .get(&0)
+        .and_then(|root| root.as_movie_clip());
+    if let Some(root) = root {
+        result.build_builtin_items(builtin_items, root);
+    }
+
+    Ok(result)
+}
+
+/// Construct `ContextMenu`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "ContextMenu"),
+        Some(QName::new(Namespace::package("flash.display"), "NativeMenu").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "customItems"),
+        Method::from_builtin(custom_items),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "customItems"),
+        Method::from_builtin(set_custom_items),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "hideBuiltInItems"),
+        Method::from_builtin(hide_built_in_items),
+    ));
+
+    class
+}
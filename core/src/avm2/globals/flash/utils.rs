@@ -0,0 +1,6 @@
+//! `flash.utils` namespace
+
+pub mod bytearray;
+pub mod idatainput;
+pub mod idataoutput;
+pub mod iexternalizable;
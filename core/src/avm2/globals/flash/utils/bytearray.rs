@@ -0,0 +1,826 @@
+//! `flash.utils.ByteArray` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
+use crate::avm2::bytearray::Endian;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The error raised when reading past the end of a `ByteArray`.
+fn eof_error() -> Error {
+    "EOFError: Error #2030: End of file was encountered.".into()
+}
+
+/// Implements `flash.utils.ByteArray`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.ByteArray`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.and_then(|this| this.as_bytearray()) {
+        return Ok(bytearray.len().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_len(length as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s getter.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.and_then(|this| this.as_bytearray()) {
+        return Ok(bytearray.position().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let position = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_position(position as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.bytesAvailable`.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.and_then(|this| this.as_bytearray()) {
+        return Ok(bytearray.bytes_available().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s getter.
+pub fn endian<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.and_then(|this| this.as_bytearray()) {
+        return Ok(bytearray.endian().as_str().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s setter.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let endian = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let endian = match &*endian {
+            "bigEndian" => Endian::Big,
+            "littleEndian" => Endian::Little,
+            _ => return Err(
+                "ArgumentError: Error #2008: Parameter type must be one of the accepted values."
+                    .into(),
+            ),
+        };
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_endian(endian);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.clear();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeBoolean`.
+pub fn write_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u8(value as u8);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeByte`.
+pub fn write_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u8(value as u8);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeShort`.
+pub fn write_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u16(value as u16);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeInt`.
+pub fn write_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u32(value as u32);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUnsignedInt`.
+pub fn write_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u32(value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeFloat`.
+pub fn write_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_f32(value as f32);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeDouble`.
+pub fn write_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_f64(value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTF`.
+pub fn write_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if value.len() > u16::MAX as usize {
+        return Err("RangeError: Error #2006: The supplied index is out of bounds.".into());
+    }
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_u16(value.len() as u16);
+        bytearray.write_bytes(value.as_bytes());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTFBytes`.
+pub fn write_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        bytearray.write_bytes(value.as_bytes());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeBytes`.
+pub fn write_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let offset = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+        let length = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)? as usize;
+
+        let bytes = {
+            let source = source
+                .as_bytearray()
+                .ok_or("TypeError: Error #1034: Type Coercion failed: expected ByteArray.")?;
+            let source = source.bytes();
+            let end = if length == 0 {
+                source.len()
+            } else {
+                offset.saturating_add(length)
+            };
+
+            source
+                .get(offset..end)
+                .ok_or("RangeError: Error #2006: The supplied index is out of bounds.")?
+                .to_vec()
+        };
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&bytes);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readBoolean`.
+pub fn read_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok((bytearray.read_u8().ok_or_else(eof_error)? != 0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readByte`.
+pub fn read_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok((bytearray.read_u8().ok_or_else(eof_error)? as i8 as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedByte`.
+pub fn read_unsigned_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok(bytearray.read_u8().ok_or_else(eof_error)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readShort`.
+pub fn read_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok((bytearray.read_u16().ok_or_else(eof_error)? as i16).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedShort`.
+pub fn read_unsigned_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok(bytearray.read_u16().ok_or_else(eof_error)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readInt`.
+pub fn read_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok((bytearray.read_u32().ok_or_else(eof_error)? as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedInt`.
+pub fn read_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok(bytearray.read_u32().ok_or_else(eof_error)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readFloat`.
+pub fn read_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok(bytearray.read_f32().ok_or_else(eof_error)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readDouble`.
+pub fn read_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut bytearray) =
+        this.and_then(|this| this.as_bytearray_mut(activation.context.gc_context))
+    {
+        return Ok(bytearray.read_f64().ok_or_else(eof_error)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTF`.
+pub fn read_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let string = {
+            let mut bytearray = match this.as_bytearray_mut(activation.context.gc_context) {
+                Some(bytearray) => bytearray,
+                None => return Ok(Value::Undefined),
+            };
+            let length = bytearray.read_u16().ok_or_else(eof_error)?;
+            let bytes = bytearray
+                .read_bytes(length as usize)
+                .ok_or_else(eof_error)?;
+
+            String::from_utf8_lossy(bytes).into_owned()
+        };
+
+        return Ok(AvmString::new(activation.context.gc_context, string).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        let string = {
+            let mut bytearray = match this.as_bytearray_mut(activation.context.gc_context) {
+                Some(bytearray) => bytearray,
+                None => return Ok(Value::Undefined),
+            };
+            let bytes = bytearray
+                .read_bytes(length as usize)
+                .ok_or_else(eof_error)?;
+
+            String::from_utf8_lossy(bytes).into_owned()
+        };
+
+        return Ok(AvmString::new(activation.context.gc_context, string).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeObject`.
+///
+/// Values are always encoded as AMF3.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let mut writer = Amf3Writer::new();
+        writer.write_value(activation, value)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&writer.into_bytes());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readObject`.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (bytes, position) = match this.as_bytearray() {
+            Some(bytearray) => {
+                let position = bytearray.position();
+                let bytes = bytearray.bytes().get(position..).unwrap_or(&[]).to_vec();
+                (bytes, position)
+            }
+            None => return Ok(Value::Undefined),
+        };
+
+        if bytes.is_empty() {
+            return Err(eof_error());
+        }
+
+        let mut reader = Amf3Reader::new(&bytes);
+        let value = reader.read_value(activation)?;
+
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_position(position + reader.position());
+        }
+
+        return Ok(value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.and_then(|this| this.as_bytearray()) {
+        let string = String::from_utf8_lossy(bytearray.bytes()).into_owned();
+
+        return Ok(AvmString::new(activation.context.gc_context, string).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ByteArray`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "ByteArray"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.implements(QName::new(Namespace::package("flash.utils"), "IDataInput").into());
+    write.implements(QName::new(Namespace::package("flash.utils"), "IDataOutput").into());
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "position"),
+        Method::from_builtin(position),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "position"),
+        Method::from_builtin(set_position),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "bytesAvailable"),
+        Method::from_builtin(bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "endian"),
+        Method::from_builtin(endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeBoolean"),
+        Method::from_builtin(write_boolean),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeByte"),
+        Method::from_builtin(write_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeShort"),
+        Method::from_builtin(write_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeInt"),
+        Method::from_builtin(write_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeUnsignedInt"),
+        Method::from_builtin(write_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeFloat"),
+        Method::from_builtin(write_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeDouble"),
+        Method::from_builtin(write_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeUTF"),
+        Method::from_builtin(write_utf),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeUTFBytes"),
+        Method::from_builtin(write_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeBytes"),
+        Method::from_builtin(write_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readBoolean"),
+        Method::from_builtin(read_boolean),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readByte"),
+        Method::from_builtin(read_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readUnsignedByte"),
+        Method::from_builtin(read_unsigned_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readShort"),
+        Method::from_builtin(read_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readUnsignedShort"),
+        Method::from_builtin(read_unsigned_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readInt"),
+        Method::from_builtin(read_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readUnsignedInt"),
+        Method::from_builtin(read_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readFloat"),
+        Method::from_builtin(read_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readDouble"),
+        Method::from_builtin(read_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readUTF"),
+        Method::from_builtin(read_utf),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readUTFBytes"),
+        Method::from_builtin(read_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "writeObject"),
+        Method::from_builtin(write_object),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "readObject"),
+        Method::from_builtin(read_object),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
@@ -0,0 +1,94 @@
+//! `flash.utils.IDataInput` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::ieventdispatcher::bodiless_method;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.IDataInput`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `IDataInput`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "IDataInput"),
+        None,
+        Method::from_builtin(bodiless_method),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Interface.into());
+    write.define_instance_trait(Trait::from_getter(
+        QName::dynamic_name("bytesAvailable"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::dynamic_name("endian"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readBoolean"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readByte"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readDouble"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readFloat"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readInt"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readObject"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readShort"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readUnsignedByte"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readUnsignedInt"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readUnsignedShort"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readUTF"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readUTFBytes"),
+        Method::from_builtin(bodiless_method),
+    ));
+
+    class
+}
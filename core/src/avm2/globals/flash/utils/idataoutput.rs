@@ -0,0 +1,86 @@
+//! `flash.utils.IDataOutput` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::ieventdispatcher::bodiless_method;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.IDataOutput`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `IDataOutput`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "IDataOutput"),
+        None,
+        Method::from_builtin(bodiless_method),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Interface.into());
+    write.define_instance_trait(Trait::from_getter(
+        QName::dynamic_name("endian"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeBoolean"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeByte"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeBytes"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeDouble"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeFloat"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeInt"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeObject"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeShort"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeUnsignedInt"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeUTF"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeUTFBytes"),
+        Method::from_builtin(bodiless_method),
+    ));
+
+    class
+}
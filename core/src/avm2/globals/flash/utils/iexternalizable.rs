@@ -0,0 +1,46 @@
+//! `flash.utils.IExternalizable` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::ieventdispatcher::bodiless_method;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.IExternalizable`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `IExternalizable`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "IExternalizable"),
+        None,
+        Method::from_builtin(bodiless_method),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::Interface.into());
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("readExternal"),
+        Method::from_builtin(bodiless_method),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::dynamic_name("writeExternal"),
+        Method::from_builtin(bodiless_method),
+    ));
+
+    class
+}
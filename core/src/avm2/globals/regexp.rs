@@ -0,0 +1,293 @@
+//! `RegExp` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::regexp::{utf16_len, RegExp, RegExpFlags};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `RegExp`'s instance initializer.
+///
+/// The pattern may also be given as another `RegExp`, whose pattern and
+/// flags are copied.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let regexp = match source {
+            Value::Object(source) if source.as_regexp().is_some() => {
+                source.as_regexp().unwrap().clone()
+            }
+            Value::Undefined => RegExp::default(),
+            source => {
+                let source = source.coerce_to_string(activation)?;
+                let flags = match args.get(1).cloned().unwrap_or(Value::Undefined) {
+                    Value::Undefined => RegExpFlags::default(),
+                    flags => RegExpFlags::from_flags_str(&flags.coerce_to_string(activation)?),
+                };
+                RegExp::new(&source, flags)
+            }
+        };
+
+        if let Some(mut this_regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            *this_regexp = regexp;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.source`.
+pub fn source<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        let source = regexp.source().to_string();
+        return Ok(AvmString::new(activation.context.gc_context, source).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.global`.
+pub fn global<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        return Ok(regexp.flags().global.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.ignoreCase`.
+pub fn ignore_case<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        return Ok(regexp.flags().ignore_case.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.multiline`.
+pub fn multiline<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        return Ok(regexp.flags().multiline.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.dotall`.
+pub fn dotall<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        return Ok(regexp.flags().dotall.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.lastIndex`'s getter.
+pub fn last_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(regexp) = this.and_then(|this| this.as_regexp()) {
+        return Ok(regexp.last_index().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.lastIndex`'s setter.
+pub fn set_last_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let last_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            regexp.set_last_index(last_index.max(0) as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.exec`.
+///
+/// A match is returned as an `Array` of the matched text followed by each
+/// capture group, which is `undefined` if the group didn't take part in the
+/// match. The array also has an `index` property holding the position of the
+/// match, and an `input` property holding the searched string. Without a
+/// match, `exec` returns `null`.
+pub fn exec<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let matched = match this.as_regexp_mut(activation.context.gc_context) {
+            Some(mut regexp) => regexp.exec(&text),
+            None => return Ok(Value::Undefined),
+        };
+        let matched = match matched {
+            Some(matched) => matched,
+            None => return Ok(Value::Null),
+        };
+
+        let mut storage = ArrayStorage::new(0);
+        for group in matched.groups() {
+            storage.push(match group {
+                Some(range) => {
+                    AvmString::new(activation.context.gc_context, text[range].to_string()).into()
+                }
+                None => Value::Undefined,
+            });
+        }
+
+        let mut array = ArrayObject::from_array(
+            storage,
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        );
+        array.set_property(
+            array,
+            &QName::dynamic_name("index"),
+            utf16_len(&text[..matched.start()]).into(),
+            activation,
+        )?;
+        array.set_property(
+            array,
+            &QName::dynamic_name("input"),
+            text.into(),
+            activation,
+        )?;
+
+        return Ok(array.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.test`.
+pub fn test<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let text = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            return Ok(regexp.exec(&text).is_some().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `RegExp`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(""), "RegExp"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "source"),
+        Method::from_builtin(source),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "global"),
+        Method::from_builtin(global),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "ignoreCase"),
+        Method::from_builtin(ignore_case),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "multiline"),
+        Method::from_builtin(multiline),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "dotall"),
+        Method::from_builtin(dotall),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "lastIndex"),
+        Method::from_builtin(last_index),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "lastIndex"),
+        Method::from_builtin(set_last_index),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "exec"),
+        Method::from_builtin(exec),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "test"),
+        Method::from_builtin(test),
+    ));
+
+    class
+}
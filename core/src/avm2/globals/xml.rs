@@ -0,0 +1,776 @@
+//! `XML` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{NamespaceObject, Object, TObject, XmlObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::xml::{XMLDocument, XMLName, XMLNode};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `XML`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| "".into())
+            .coerce_to_string(activation)?;
+        let mc = activation.context.gc_context;
+        let node = parse_node(mc, &source)?;
+
+        if let Some(default_namespace) = activation.default_xml_namespace() {
+            apply_default_namespace(mc, node, &default_namespace);
+        }
+
+        if let Some(mut nodes) = this.as_xml_nodes_mut(mc) {
+            *nodes = vec![node];
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Parse a string into a single XML node.
+///
+/// A document holding a single element is that element; anything else (such
+/// as plain text) is kept as-is.
+fn parse_node<'gc>(mc: MutationContext<'gc, '_>, source: &str) -> Result<XMLNode<'gc>, Error> {
+    let mut root = XMLDocument::new(mc).as_node();
+
+    root.replace_with_str(mc, source, true)?;
+
+    let mut children: Vec<XMLNode<'gc>> = root
+        .children()
+        .into_iter()
+        .flatten()
+        .filter(|child| child.is_element() || child.is_text())
+        .collect();
+
+    Ok(if children.len() == 1 {
+        children.remove(0)
+    } else {
+        XMLNode::new_text(mc, source, root.document())
+    })
+}
+
+/// Put an element, and the unprefixed elements below it, in the default XML
+/// namespace.
+///
+/// This only applies to elements that do not declare a default namespace of
+/// their own.
+fn apply_default_namespace<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: XMLNode<'gc>,
+    default_namespace: &str,
+) {
+    if node.is_element() && !default_namespace.is_empty() && namespace_uri(node).is_none() {
+        node.set_attribute_value(mc, &XMLName::from_parts(None, "xmlns"), default_namespace);
+    }
+}
+
+/// Look up the namespace URI of an element's name, if it has one.
+fn namespace_uri(node: XMLNode<'_>) -> Option<String> {
+    let tag_name = node.tag_name()?;
+
+    node.lookup_uri_for_namespace(tag_name.prefix().unwrap_or(""))
+}
+
+/// Convert a value into the XML nodes it stands for.
+///
+/// `XML` and `XMLList` objects yield the nodes they hold, while any other
+/// value is coerced to a string and parsed.
+fn value_to_nodes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<XMLNode<'gc>>, Error> {
+    if let Value::Object(object) = value {
+        if let Some(nodes) = object.as_xml_nodes() {
+            return Ok(nodes.clone());
+        }
+    }
+
+    let source = value.coerce_to_string(activation)?;
+
+    Ok(vec![parse_node(activation.context.gc_context, &source)?])
+}
+
+/// Merge adjacent text children of a node and drop whitespace-only ones.
+///
+/// Elements are normalized recursively and keep the text on either side of
+/// them apart.
+fn normalize_node<'gc>(mc: MutationContext<'gc, '_>, mut node: XMLNode<'gc>) -> Result<(), Error> {
+    let mut index = 0;
+
+    while let Some(child) = node.get_child_by_index(index) {
+        if child.is_element() {
+            normalize_node(mc, child)?;
+        } else if child.is_text() {
+            let mut text = child.node_value().unwrap_or_default();
+            let mut merged = false;
+
+            while let Some(next) = node.get_child_by_index(index + 1) {
+                if !next.is_text() {
+                    break;
+                }
+
+                text.push_str(&next.node_value().unwrap_or_default());
+                node.remove_child(mc, next)?;
+                merged = true;
+            }
+
+            if text.trim().is_empty() {
+                node.remove_child(mc, child)?;
+                continue;
+            }
+
+            if merged {
+                node.remove_child(mc, child)?;
+                node.insert_child(mc, index, XMLNode::new_text(mc, &text, node.document()))?;
+            }
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Determine if a node has simple content, i.e. it has no child elements.
+pub fn has_simple_content(node: XMLNode<'_>) -> bool {
+    node.children()
+        .into_iter()
+        .flatten()
+        .all(|child| !child.is_element())
+}
+
+/// Produce the string value of a node as defined by E4X's `toString`.
+///
+/// Nodes with simple content yield their text, while any other node is
+/// serialized as XML.
+pub fn node_to_string(node: XMLNode<'_>) -> Result<String, Error> {
+    if !has_simple_content(node) {
+        return node_to_xml_string(node);
+    }
+
+    if let Some(value) = node.node_value() {
+        return Ok(value);
+    }
+
+    Ok(node
+        .children()
+        .into_iter()
+        .flatten()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.node_value())
+        .collect())
+}
+
+/// Serialize a node as XML.
+///
+/// Each namespace declaration is written on the outermost element it is in
+/// scope for: elements don't repeat declarations made by an element around
+/// them, and namespaces inherited from outside of the node are declared on
+/// the elements that use them.
+pub fn node_to_xml_string(node: XMLNode<'_>) -> Result<String, Error> {
+    let mut output = String::new();
+    write_node(node, &[], &mut output);
+    Ok(output)
+}
+
+/// Write a node, and everything below it, as XML.
+///
+/// `scope` holds the namespace prefixes and URIs declared by the elements
+/// written around this node, innermost last.
+fn write_node(node: XMLNode<'_>, scope: &[(String, String)], output: &mut String) {
+    if node.is_text() {
+        output.push_str(&escape_element_value(
+            &node.node_value().unwrap_or_default(),
+        ));
+        return;
+    }
+
+    if node.is_comment() {
+        output.push_str("<!--");
+        output.push_str(&node.node_value().unwrap_or_default());
+        output.push_str("-->");
+        return;
+    }
+
+    let tag_name = match node.tag_name() {
+        Some(tag_name) => tag_name,
+        None => return,
+    };
+
+    let mut scope = scope.to_vec();
+    let mut declarations = Vec::new();
+    let mut attributes = Vec::new();
+    for key in node.attribute_keys() {
+        let name = XMLName::from_str(&key);
+        let value = node.attribute_value(&name).unwrap_or_default();
+        match namespace_declaration_prefix(&name) {
+            Some(prefix) => declare(&mut scope, &mut declarations, prefix, &value),
+            None => attributes.push((key, value)),
+        }
+    }
+
+    let mut used_prefixes = vec![tag_name.prefix().unwrap_or("").to_string()];
+    for (key, _) in &attributes {
+        if let Some(prefix) = XMLName::from_str(key).prefix() {
+            if prefix != "xml" {
+                used_prefixes.push(prefix.to_string());
+            }
+        }
+    }
+    for prefix in used_prefixes {
+        if !scope.iter().any(|(p, _)| *p == prefix) {
+            if let Some(uri) = node.lookup_uri_for_namespace(&prefix) {
+                declare(&mut scope, &mut declarations, &prefix, &uri);
+            }
+        }
+    }
+
+    output.push('<');
+    output.push_str(&tag_name.node_name());
+    for (prefix, uri) in &declarations {
+        if prefix.is_empty() {
+            output.push_str(" xmlns");
+        } else {
+            output.push_str(" xmlns:");
+            output.push_str(prefix);
+        }
+        output.push_str("=\"");
+        output.push_str(&escape_attribute_value(uri));
+        output.push('"');
+    }
+    for (key, value) in &attributes {
+        output.push(' ');
+        output.push_str(key);
+        output.push_str("=\"");
+        output.push_str(&escape_attribute_value(value));
+        output.push('"');
+    }
+
+    if node.children_len() == 0 {
+        output.push_str("/>");
+        return;
+    }
+
+    output.push('>');
+    for child in node.children().into_iter().flatten() {
+        write_node(child, &scope, output);
+    }
+    output.push_str("</");
+    output.push_str(&tag_name.node_name());
+    output.push('>');
+}
+
+/// Add a namespace declaration to the scope of an element, unless it is
+/// already in scope.
+fn declare(
+    scope: &mut Vec<(String, String)>,
+    declarations: &mut Vec<(String, String)>,
+    prefix: &str,
+    uri: &str,
+) {
+    let in_scope = scope
+        .iter()
+        .rev()
+        .find(|(p, _)| p == prefix)
+        .map_or(false, |(_, u)| u == uri);
+
+    if !in_scope {
+        scope.push((prefix.to_string(), uri.to_string()));
+        declarations.push((prefix.to_string(), uri.to_string()));
+    }
+}
+
+/// Get the prefix declared by an `xmlns` attribute, which is empty for the
+/// default namespace.
+fn namespace_declaration_prefix(name: &XMLName) -> Option<&str> {
+    match (name.prefix(), name.local_name()) {
+        (Some("xmlns"), prefix) => Some(prefix),
+        (None, "xmlns") => Some(""),
+        _ => None,
+    }
+}
+
+/// The name of the `xmlns` attribute declaring a prefix.
+fn namespace_declaration_name(prefix: &str) -> XMLName {
+    if prefix.is_empty() {
+        XMLName::from_parts(None, "xmlns")
+    } else {
+        XMLName::from_parts(Some("xmlns"), prefix)
+    }
+}
+
+/// Escape text as E4X's `EscapeElementValue` does.
+fn escape_element_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape an attribute value as E4X's `EscapeAttributeValue` does.
+fn escape_attribute_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("&quot;"),
+            '<' => escaped.push_str("&lt;"),
+            '&' => escaped.push_str("&amp;"),
+            '\u{a}' => escaped.push_str("&#xA;"),
+            '\u{d}' => escaped.push_str("&#xD;"),
+            '\u{9}' => escaped.push_str("&#x9;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Collect the namespace prefixes used by an element and everything below
+/// it, without duplicates.
+///
+/// Unprefixed element names use the default namespace, whose prefix is
+/// empty; unprefixed attribute names are in no namespace at all.
+fn used_prefixes(node: XMLNode<'_>, prefixes: &mut Vec<String>) {
+    if let Some(tag_name) = node.tag_name() {
+        let mut names = vec![tag_name.prefix().unwrap_or("").to_string()];
+        for key in node.attribute_keys() {
+            let name = XMLName::from_str(&key);
+            if namespace_declaration_prefix(&name).is_none() {
+                if let Some(prefix) = name.prefix() {
+                    if prefix != "xml" {
+                        names.push(prefix.to_string());
+                    }
+                }
+            }
+        }
+
+        for name in names {
+            if !prefixes.contains(&name) {
+                prefixes.push(name);
+            }
+        }
+    }
+
+    for child in node.children().into_iter().flatten() {
+        used_prefixes(child, prefixes);
+    }
+}
+
+/// Remove a node from its parent, if it has one.
+///
+/// The namespaces the node uses that were declared by its ancestors are
+/// declared on the node itself, so that it keeps them once it's detached.
+pub fn detach_node<'gc>(mc: MutationContext<'gc, '_>, node: XMLNode<'gc>) -> Result<(), Error> {
+    let mut parent = match node.parent()? {
+        Some(parent) => parent,
+        None => return Ok(()),
+    };
+
+    let mut prefixes = Vec::new();
+    used_prefixes(node, &mut prefixes);
+    let declarations: Vec<(XMLName, String)> = prefixes
+        .iter()
+        .map(|prefix| (prefix, namespace_declaration_name(prefix)))
+        .filter(|(_, name)| node.attribute_value(name).is_none())
+        .filter_map(|(prefix, name)| Some((name, node.lookup_uri_for_namespace(prefix)?)))
+        .collect();
+
+    parent.remove_child(mc, node)?;
+
+    for (name, uri) in declarations {
+        node.set_attribute_value(mc, &name, &uri);
+    }
+
+    Ok(())
+}
+
+/// Determine if a multiname matches any name.
+fn is_wildcard(multiname: &Multiname<'_>) -> bool {
+    match multiname.local_name() {
+        Some(name) => &*name == "*",
+        None => true,
+    }
+}
+
+/// Determine if the local name of a node (or attribute) matches a multiname.
+fn name_matches(multiname: &Multiname<'_>, local_name: &str) -> bool {
+    is_wildcard(multiname) || multiname.local_name().map_or(false, |n| &*n == local_name)
+}
+
+/// Determine if an element is in the namespace that a multiname refers to.
+///
+/// Unqualified names refer to the default XML namespace, if one was set.
+/// Otherwise, they match elements in any namespace.
+fn namespace_matches(
+    multiname: &Multiname<'_>,
+    node: XMLNode<'_>,
+    default_namespace: Option<&str>,
+) -> bool {
+    match default_namespace {
+        Some(default_namespace) if multiname.namespace_set().any(|ns| ns.is_public()) => {
+            namespace_uri(node).unwrap_or_default() == default_namespace
+        }
+        _ => true,
+    }
+}
+
+/// Collect all descendants of a node that match a given name.
+///
+/// This implements the E4X descendants operator (`..`). Element names match
+/// any child element of that name at any depth, while the wildcard also
+/// matches text. Attribute names (`..@name`) match the attributes of the node
+/// itself as well as those of every element below it; as attributes are not
+/// nodes of their own, their values are collected as text nodes.
+///
+/// Unqualified element names only match elements in the default XML
+/// namespace, if one is given.
+pub fn descendants<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: XMLNode<'gc>,
+    multiname: &Multiname<'gc>,
+    default_namespace: Option<&str>,
+    output: &mut Vec<XMLNode<'gc>>,
+) {
+    if multiname.is_attribute() {
+        for key in node.attribute_keys() {
+            let name = XMLName::from_str(&key);
+
+            if name_matches(multiname, name.local_name()) {
+                if let Some(value) = node.attribute_value(&name) {
+                    output.push(XMLNode::new_text(mc, &value, node.document()));
+                }
+            }
+        }
+    }
+
+    for child in node.children().into_iter().flatten() {
+        if !multiname.is_attribute() {
+            let is_match = match child.tag_name() {
+                Some(tag_name) => {
+                    name_matches(multiname, tag_name.local_name())
+                        && namespace_matches(multiname, child, default_namespace)
+                }
+                None => child.is_text() && is_wildcard(multiname),
+            };
+
+            if is_match {
+                output.push(child);
+            }
+        }
+
+        descendants(mc, child, multiname, default_namespace, output);
+    }
+}
+
+/// Implements `XML.length`
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            return Ok(nodes.len().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            let string = match nodes.first() {
+                Some(node) => node_to_string(*node)?,
+                None => String::new(),
+            };
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.toXMLString`
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            let string = match nodes.first() {
+                Some(node) => node_to_xml_string(*node)?,
+                None => String::new(),
+            };
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.name`
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            if let Some(tag_name) = nodes.first().and_then(|node| node.tag_name()) {
+                return Ok(AvmString::new(
+                    activation.context.gc_context,
+                    tag_name.node_name().into_owned(),
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `XML.namespace`
+///
+/// Without a prefix, this is the namespace of the element's name. Otherwise,
+/// it is the namespace declared with that prefix, if any.
+pub fn namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let node = this.as_xml_nodes().and_then(|nodes| nodes.first().cloned());
+        let node = match node {
+            Some(node) if node.is_element() => node,
+            _ => return Ok(Value::Null),
+        };
+
+        let uri = match args.get(0) {
+            Some(prefix) => {
+                let prefix = prefix.clone().coerce_to_string(activation)?;
+                match node.lookup_uri_for_namespace(&prefix) {
+                    Some(uri) => uri,
+                    None => return Ok(Value::Undefined),
+                }
+            }
+            None => namespace_uri(node).unwrap_or_default(),
+        };
+
+        let namespace_proto = activation.context.avm2.prototypes().namespace;
+        return Ok(NamespaceObject::from_namespace(
+            Namespace::Namespace(AvmString::new(activation.context.gc_context, uri)),
+            namespace_proto,
+            activation.context.gc_context,
+        )?
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.appendChild`
+pub fn append_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let children =
+            value_to_nodes(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        let node = this.as_xml_nodes().and_then(|nodes| nodes.first().copied());
+
+        if let Some(mut node) = node {
+            let mc = activation.context.gc_context;
+            for child in children {
+                detach_node(mc, child)?;
+                node.append_child(mc, child)?;
+            }
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.insertChildBefore`
+///
+/// A `null` reference child appends the new children to the end.
+pub fn insert_child_before<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let reference = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Null | Value::Undefined => None,
+            value => Some(value_to_nodes(activation, value)?),
+        };
+        let children =
+            value_to_nodes(activation, args.get(1).cloned().unwrap_or(Value::Undefined))?;
+        let node = this.as_xml_nodes().and_then(|nodes| nodes.first().copied());
+
+        if let Some(mut node) = node {
+            let reference = match reference {
+                Some(reference) => match reference.first() {
+                    Some(reference) if node.child_position(*reference).is_some() => {
+                        Some(*reference)
+                    }
+                    _ => return Ok(Value::Undefined),
+                },
+                None => None,
+            };
+
+            let mc = activation.context.gc_context;
+            for child in children.iter() {
+                detach_node(mc, *child)?;
+            }
+
+            // Detaching the new children may have moved the reference child.
+            let mut position = reference
+                .and_then(|reference| node.child_position(reference))
+                .unwrap_or_else(|| node.children_len());
+            for child in children {
+                node.insert_child(mc, position, child)?;
+                position += 1;
+            }
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.children`
+pub fn children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            let children = nodes
+                .iter()
+                .flat_map(|node| node.children().into_iter().flatten())
+                .filter(|child| child.is_element() || child.is_text())
+                .collect();
+
+            return Ok(XmlObject::from_nodes(
+                children,
+                activation.context.avm2.prototypes().xml_list,
+                activation.context.gc_context,
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.normalize`
+pub fn normalize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let nodes = this.as_xml_nodes().map(|nodes| nodes.clone());
+
+        for node in nodes.into_iter().flatten() {
+            normalize_node(activation.context.gc_context, node)?;
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `XML`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(""), "XML"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "appendChild"),
+        Method::from_builtin(append_child),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "children"),
+        Method::from_builtin(children),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "insertChildBefore"),
+        Method::from_builtin(insert_child_before),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "namespace"),
+        Method::from_builtin(namespace),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "normalize"),
+        Method::from_builtin(normalize),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toXMLString"),
+        Method::from_builtin(to_xml_string),
+    ));
+
+    class
+}
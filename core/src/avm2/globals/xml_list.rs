@@ -0,0 +1,127 @@
+//! `XMLList` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::xml::{has_simple_content, node_to_string, node_to_xml_string};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `XMLList`'s instance initializer.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList.length`
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            return Ok(nodes.len().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList.toString`
+///
+/// A list of nodes with simple content yields their concatenated text;
+/// otherwise, each node is serialized on a line of its own.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            let string = if nodes.iter().all(|node| has_simple_content(*node)) {
+                nodes
+                    .iter()
+                    .map(|node| node_to_string(*node))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join("")
+            } else {
+                nodes
+                    .iter()
+                    .map(|node| node_to_xml_string(*node))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join("\n")
+            };
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList.toXMLString`
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(nodes) = this.as_xml_nodes() {
+            let string = nodes
+                .iter()
+                .map(|node| node_to_xml_string(*node))
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n");
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `XMLList`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(""), "XMLList"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toXMLString"),
+        Method::from_builtin(to_xml_string),
+    ));
+
+    class
+}
@@ -158,8 +158,10 @@ impl<'gc> QName<'gc> {
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::abc_is_attribute(abc_multiname);
 
-        Ok(match abc_multiname? {
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } => Self {
                 ns: Namespace::from_abc_namespace(translation_unit, namespace.clone(), mc)?,
                 name: translation_unit.pool_string(name.0, mc)?,
@@ -193,6 +195,17 @@ impl<'gc> QName<'gc> {
     pub fn namespace(&self) -> &Namespace<'gc> {
         &self.ns
     }
+
+    /// Get the fully qualified name of this `QName`, as it appears in error
+    /// messages (e.g. `flash.display.MovieClip`).
+    pub fn to_qualified_name(&self) -> String {
+        let uri = self.ns.as_uri();
+        if uri.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{}.{}", uri, self.name)
+        }
+    }
 }
 
 /// A `Multiname` consists of a name which could be resolved in one or more
@@ -207,6 +220,9 @@ impl<'gc> QName<'gc> {
 pub struct Multiname<'gc> {
     ns: Vec<Namespace<'gc>>,
     name: Option<AvmString<'gc>>,
+
+    /// Whether or not this name refers to an XML attribute.
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
@@ -244,6 +260,18 @@ impl<'gc> Multiname<'gc> {
         Ok(result)
     }
 
+    /// Determine if an ABC multiname refers to an XML attribute.
+    fn abc_is_attribute(abc_multiname: &AbcMultiname) -> bool {
+        matches!(
+            abc_multiname,
+            AbcMultiname::QNameA { .. }
+                | AbcMultiname::RTQNameA { .. }
+                | AbcMultiname::RTQNameLA
+                | AbcMultiname::MultinameA { .. }
+                | AbcMultiname::MultinameLA { .. }
+        )
+    }
+
     /// Read a multiname from the ABC constant pool, copying it into the most
     /// general form of multiname.
     pub fn from_abc_multiname(
@@ -261,8 +289,10 @@ impl<'gc> Multiname<'gc> {
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::abc_is_attribute(abc_multiname);
 
-        Ok(match abc_multiname? {
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -272,6 +302,7 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -280,6 +311,7 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![ns],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -288,6 +320,7 @@ impl<'gc> Multiname<'gc> {
                 Self {
                     ns: vec![ns],
                     name: Some(name),
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -304,6 +337,7 @@ impl<'gc> Multiname<'gc> {
                     activation.context.gc_context,
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
+                is_attribute,
             },
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => {
@@ -315,6 +349,7 @@ impl<'gc> Multiname<'gc> {
                         activation.context.gc_context,
                     )?,
                     name: Some(name),
+                    is_attribute,
                 }
             }
         })
@@ -340,8 +375,10 @@ impl<'gc> Multiname<'gc> {
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::abc_is_attribute(abc_multiname);
 
-        Ok(match abc_multiname? {
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -350,6 +387,7 @@ impl<'gc> Multiname<'gc> {
                         mc,
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -362,6 +400,7 @@ impl<'gc> Multiname<'gc> {
             } => Self {
                 ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
+                is_attribute,
             },
             _ => return Err(format!("Multiname {} is not static", multiname_index.0).into()),
         })
@@ -372,6 +411,7 @@ impl<'gc> Multiname<'gc> {
         Self {
             ns: vec![Namespace::Any],
             name: None,
+            is_attribute: false,
         }
     }
 
@@ -383,6 +423,11 @@ impl<'gc> Multiname<'gc> {
         self.name
     }
 
+    /// Indicates if this name refers to an XML attribute (e.g. `@name`).
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
     pub fn includes_dynamic_namespace(&self) -> bool {
         for ns in self.ns.iter() {
             if ns.is_dynamic() {
@@ -399,6 +444,7 @@ impl<'gc> From<QName<'gc>> for Multiname<'gc> {
         Self {
             ns: vec![q.ns],
             name: Some(q.name),
+            is_attribute: false,
         }
     }
 }
@@ -2,37 +2,54 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
+use crate::avm2::events::{DispatchList, Event};
 use crate::avm2::function::Executable;
 use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::regexp::RegExp;
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::{Hint, Value};
 use crate::avm2::Error;
+use crate::bitmap_data::BitmapDataStorage;
 use crate::display_object::DisplayObject;
+use crate::xml::XMLNode;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
 mod array_object;
+mod bitmapdata_object;
+mod bytearray_object;
 mod custom_object;
+mod dispatch_object;
 mod domain_object;
+mod event_object;
 mod function_object;
 mod namespace_object;
 mod primitive_object;
+mod regexp_object;
 mod script_object;
 mod stage_object;
+mod xml_object;
 
 pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bitmapdata_object::BitmapDataObject;
+pub use crate::avm2::object::bytearray_object::ByteArrayObject;
+pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::DomainObject;
+pub use crate::avm2::object::event_object::EventObject;
 pub use crate::avm2::object::function_object::{implicit_deriver, FunctionObject};
 pub use crate::avm2::object::namespace_object::NamespaceObject;
 pub use crate::avm2::object::primitive_object::PrimitiveObject;
+pub use crate::avm2::object::regexp_object::RegExpObject;
 pub use crate::avm2::object::script_object::ScriptObject;
 pub use crate::avm2::object::stage_object::StageObject;
+pub use crate::avm2::object::xml_object::XmlObject;
 
 /// Represents an object that can be directly interacted with by the AVM2
 /// runtime.
@@ -47,6 +64,12 @@ pub use crate::avm2::object::stage_object::StageObject;
         ArrayObject(ArrayObject<'gc>),
         StageObject(StageObject<'gc>),
         DomainObject(DomainObject<'gc>),
+        EventObject(EventObject<'gc>),
+        DispatchObject(DispatchObject<'gc>),
+        ByteArrayObject(ByteArrayObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
+        XmlObject(XmlObject<'gc>),
+        RegExpObject(RegExpObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -466,6 +489,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             trait_entry.kind()
         );
 
+        // Methods and accessors resolve `super` against the class that
+        // provides them, regardless of how they end up being called.
+        let base_proto = self.get_base_proto(&trait_name)?;
+
         match trait_entry.kind() {
             TraitKind::Slot {
                 slot_id,
@@ -491,6 +518,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     scope,
                     fn_proto,
                     Some(receiver),
+                    base_proto,
                 );
                 self.install_method(
                     activation.context.gc_context,
@@ -510,6 +538,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     scope,
                     fn_proto,
                     Some(receiver),
+                    base_proto,
                 );
                 self.install_getter(
                     activation.context.gc_context,
@@ -529,6 +558,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     scope,
                     fn_proto,
                     Some(receiver),
+                    base_proto,
                 );
                 self.install_setter(
                     activation.context.gc_context,
@@ -540,44 +570,19 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                 Ok(function.into())
             }
             TraitKind::Class { slot_id, class } => {
-                let class_read = class.read();
-                let super_class = if let Some(sc_name) = class_read.super_class_name() {
-                    let super_name = self
-                        .resolve_multiname(sc_name)?
-                        .unwrap_or_else(|| QName::dynamic_name("Object"));
-
-                    let super_class = if let Some(scope) = scope {
-                        scope
-                            .write(activation.context.gc_context)
-                            .resolve(&super_name.clone().into(), activation)?
-                    } else {
-                        None
-                    };
-
-                    Some(
-                        super_class
-                            .ok_or_else(|| {
-                                format!(
-                                    "Could not resolve superclass {:?}",
-                                    super_name.local_name()
-                                )
-                            })?
-                            .coerce_to_object(activation)?,
-                    )
-                } else {
-                    None
-                };
-
-                let (class_object, _cinit) =
-                    FunctionObject::from_class(activation, *class, super_class, scope)?;
+                // The class object is created by the script initializer's
+                // `newclass`, which runs the class initializer of each base
+                // class before its subclasses. Until then the slot is null,
+                // which is also what a class initializer sees when it refers
+                // to a class that isn't initialized yet.
                 self.install_const(
                     activation.context.gc_context,
-                    class_read.name().clone(),
+                    class.read().name().clone(),
                     *slot_id,
-                    class_object.into(),
+                    Value::Null,
                 );
 
-                Ok(class_object.into())
+                Ok(Value::Null)
             }
             TraitKind::Function {
                 slot_id, function, ..
@@ -588,6 +593,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     scope,
                     fn_proto,
                     None,
+                    None,
                 );
                 let es3_proto = ScriptObject::object(
                     activation.context.gc_context,
@@ -798,10 +804,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This only yields `None` for bare objects.
     fn as_proto_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
         let mut class = self.as_class();
+        let mut proto = self.proto();
 
         while class.is_none() {
-            if let Some(proto) = self.proto() {
-                class = proto.as_class();
+            if let Some(p) = proto {
+                class = p.as_class();
+                proto = p.proto();
             } else {
                 return None;
             }
@@ -848,6 +856,69 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_application_domain(&self) -> Option<Domain<'gc>> {
         None
     }
+
+    /// Unwrap this object's event.
+    fn as_event(&self) -> Option<Ref<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's mutable event.
+    fn as_event_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's dispatch list.
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's mutable dispatch list.
+    fn as_dispatch_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's byte array storage.
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object's mutable byte array storage.
+    fn as_bytearray_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object's bitmap storage.
+    fn as_bitmap_data(&self) -> Option<Ref<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object's mutable bitmap storage.
+    fn as_bitmap_data_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object's XML nodes.
+    fn as_xml_nodes(&self) -> Option<Ref<Vec<XMLNode<'gc>>>> {
+        None
+    }
+
+    /// Unwrap this object's mutable XML nodes.
+    fn as_xml_nodes_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<XMLNode<'gc>>>> {
+        None
+    }
+
+    /// Unwrap this object's regular expression.
+    fn as_regexp(&self) -> Option<Ref<RegExp>> {
+        None
+    }
+
+    /// Unwrap this object's mutable regular expression.
+    fn as_regexp_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -0,0 +1,87 @@
+//! Internal dispatch list objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::DispatchList;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// Internal-only object which holds the event listeners registered on an
+/// `EventDispatcher`.
+///
+/// Dispatchers may be any kind of host object (including display objects),
+/// so the list is stored in a private property on the dispatcher rather than
+/// on the dispatcher's object itself. Scripts cannot construct or derive
+/// from this object.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DispatchObject<'gc>(GcCell<'gc, DispatchObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DispatchObjectData<'gc> {
+    /// The base of this object.
+    base: ScriptObjectData<'gc>,
+
+    /// The dispatch list this object holds.
+    dispatch: DispatchList<'gc>,
+}
+
+impl<'gc> DispatchObject<'gc> {
+    /// Construct an empty dispatch list.
+    pub fn empty_list(mc: MutationContext<'gc, '_>) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(None, ScriptObjectClass::NoClass);
+
+        DispatchObject(GcCell::allocate(
+            mc,
+            DispatchObjectData {
+                base,
+                dispatch: DispatchList::new(),
+            },
+        ))
+        .into()
+    }
+}
+
+impl<'gc> TObject<'gc> for DispatchObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn construct(
+        &self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        Err("Cannot construct internal event dispatcher structures.".into())
+    }
+
+    fn derive(
+        &self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _class: GcCell<'gc, Class<'gc>>,
+        _scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        Err("Cannot subclass internal event dispatcher structures.".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Err("Cannot use internal event dispatcher structures as values.".into())
+    }
+
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        Some(Ref::map(self.0.read(), |o| &o.dispatch))
+    }
+
+    fn as_dispatch_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |o| &mut o.dispatch))
+    }
+}
@@ -0,0 +1,108 @@
+//! Object representation for events
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::Event;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which represents a Flash event.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct EventObject<'gc>(GcCell<'gc, EventObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct EventObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The event this object holds.
+    event: Event<'gc>,
+}
+
+impl<'gc> EventObject<'gc> {
+    /// Box an event into an object.
+    pub fn from_event(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        event: Event<'gc>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        EventObject(GcCell::allocate(mc, EventObjectData { base, event })).into()
+    }
+
+    /// Construct an event subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(EventObject(GcCell::allocate(
+            mc,
+            EventObjectData {
+                base,
+                event: Event::new(""),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for EventObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_event(&self) -> Option<Ref<Event<'gc>>> {
+        Some(Ref::map(self.0.read(), |eod| &eod.event))
+    }
+
+    fn as_event_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Event<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |eod| &mut eod.event))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::EventObject(*self);
+
+        Ok(EventObject::from_event(
+            activation.context.gc_context,
+            Some(this),
+            Event::new(""),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::EventObject(*self);
+
+        EventObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
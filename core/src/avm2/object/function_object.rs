@@ -11,7 +11,7 @@ use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, NativeError};
 use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
 use gc_arena::{Collect, GcCell, MutationContext};
 
@@ -90,6 +90,21 @@ impl<'gc> FunctionObject<'gc> {
     {
         let class_read = class.read();
         let class_proto = if let Some(mut base_class) = base_class {
+            if base_class
+                .as_class()
+                .map(|c| c.read().is_final())
+                .unwrap_or(false)
+            {
+                return Err(NativeError::verify_error(
+                    1103,
+                    format!(
+                        "Class {} cannot extend final base class.",
+                        class_read.name().local_name()
+                    ),
+                )
+                .into());
+            }
+
             let super_proto: Result<_, Error> = base_class
                 .get_property(
                     base_class,
@@ -109,7 +124,17 @@ impl<'gc> FunctionObject<'gc> {
                     .into()
                 });
 
-            derive(super_proto?, activation, class, scope)?
+            let super_proto = super_proto?;
+            let mut proto = Some(super_proto);
+            while let Some(proto_object) = proto {
+                if let Some(proto_class) = proto_object.as_class() {
+                    class_read.validate_final_overrides(&proto_class.read())?;
+                }
+
+                proto = proto_object.proto();
+            }
+
+            derive(super_proto, activation, class, scope)?
         } else {
             ScriptObject::bare_object(activation.context.gc_context)
         };
@@ -151,6 +176,23 @@ impl<'gc> FunctionObject<'gc> {
             interfaces.push(iface_proto);
         }
 
+        // Interfaces also implement every interface they extend. Walk the
+        // interface graph breadth-first so that each one is only listed once,
+        // even if it is reachable through more than one path.
+        let mut i = 0;
+        while i < interfaces.len() {
+            for super_interface in interfaces[i].interfaces() {
+                if !interfaces
+                    .iter()
+                    .any(|iface| Object::ptr_eq(*iface, super_interface))
+                {
+                    interfaces.push(super_interface);
+                }
+            }
+
+            i += 1;
+        }
+
         if !interfaces.is_empty() {
             class_proto.set_interfaces(activation.context.gc_context, interfaces);
         }
@@ -172,6 +214,7 @@ impl<'gc> FunctionObject<'gc> {
                     initializer,
                     scope,
                     None,
+                    Some(class_proto),
                     activation.context.gc_context,
                 )),
             },
@@ -196,6 +239,7 @@ impl<'gc> FunctionObject<'gc> {
             scope,
             class_constr_proto,
             None,
+            None,
         );
 
         Ok((constr, class_constr))
@@ -211,8 +255,11 @@ impl<'gc> FunctionObject<'gc> {
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         fn_proto: Object<'gc>,
         receiver: Option<Object<'gc>>,
+        base_proto: Option<Object<'gc>>,
     ) -> Object<'gc> {
-        let exec = Some(Executable::from_method(method, scope, receiver, mc));
+        let exec = Some(Executable::from_method(
+            method, scope, receiver, base_proto, mc,
+        ));
 
         FunctionObject(GcCell::allocate(
             mc,
@@ -234,7 +281,7 @@ impl<'gc> FunctionObject<'gc> {
             mc,
             FunctionObjectData {
                 base: ScriptObjectData::base_new(Some(fn_proto), ScriptObjectClass::NoClass),
-                exec: Some(Executable::from_method(nf.into(), None, None, mc)),
+                exec: Some(Executable::from_method(nf.into(), None, None, None, mc)),
             },
         ))
         .into()
@@ -256,7 +303,7 @@ impl<'gc> FunctionObject<'gc> {
             mc,
             FunctionObjectData {
                 base: ScriptObjectData::base_new(Some(fn_proto), class),
-                exec: Some(Executable::from_method(constr.into(), None, None, mc)),
+                exec: Some(Executable::from_method(constr.into(), None, None, None, mc)),
             },
         ))
         .into();
@@ -0,0 +1,108 @@
+//! Regular expression objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::regexp::RegExp;
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a regular expression.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct RegExpObject<'gc>(GcCell<'gc, RegExpObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct RegExpObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The expression this object holds.
+    regexp: RegExp,
+}
+
+impl<'gc> RegExpObject<'gc> {
+    /// Wrap an existing expression in an object.
+    pub fn from_regexp(
+        regexp: RegExp,
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        RegExpObject(GcCell::allocate(mc, RegExpObjectData { base, regexp })).into()
+    }
+
+    /// Construct a primitive subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(RegExpObject(GcCell::allocate(
+            mc,
+            RegExpObjectData {
+                base,
+                regexp: RegExp::default(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for RegExpObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_regexp(&self) -> Option<Ref<RegExp>> {
+        Some(Ref::map(self.0.read(), |rod| &rod.regexp))
+    }
+
+    fn as_regexp_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp>> {
+        Some(RefMut::map(self.0.write(mc), |rod| &mut rod.regexp))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::RegExpObject(*self);
+
+        Ok(RegExpObject::from_regexp(
+            RegExp::default(),
+            this,
+            activation.context.gc_context,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::RegExpObject(*self);
+
+        RegExpObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,235 @@
+//! XML-structured objects
+
+use crate::avm1::AvmString;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::xml;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use crate::xml::XMLNode;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a sequence of XML nodes.
+///
+/// Both `XML` and `XMLList` are represented by this object: an `XML` value is
+/// simply a list with exactly one node in it.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XmlObject<'gc>(GcCell<'gc, XmlObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XmlObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The XML nodes this object holds.
+    nodes: Vec<XMLNode<'gc>>,
+}
+
+impl<'gc> XmlObject<'gc> {
+    /// Wrap a list of existing XML nodes in an object.
+    pub fn from_nodes(
+        nodes: Vec<XMLNode<'gc>>,
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        XmlObject(GcCell::allocate(mc, XmlObjectData { base, nodes })).into()
+    }
+
+    /// Construct a primitive subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(XmlObject(GcCell::allocate(
+            mc,
+            XmlObjectData {
+                base,
+                nodes: Vec::new(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for XmlObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let node = self.0.read().nodes.get(index).copied();
+
+                return Ok(match node {
+                    Some(node) => XmlObject::from_nodes(
+                        vec![node],
+                        activation.context.avm2.prototypes().xml,
+                        activation.context.gc_context,
+                    )
+                    .into(),
+                    None => Value::Undefined,
+                });
+            }
+        }
+
+        let read = self.0.read();
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(activation.context.gc_context);
+
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(activation.context.gc_context);
+
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let mut write = self.0.write(gc_context);
+                if index < write.nodes.len() {
+                    // Deleting a node from a list also removes it from its parent.
+                    let node = write.nodes.remove(index);
+                    if let Err(e) = xml::detach_node(gc_context, node) {
+                        log::warn!("Couldn't detach deleted XML node: {}", e);
+                    }
+                }
+
+                return true;
+            }
+        }
+
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return Ok(index < self.0.read().nodes.len());
+            }
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+        if let Ok(index) = local_name.parse::<usize>() {
+            if index < self.0.read().nodes.len() {
+                return Ok(Some(Namespace::public_namespace()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml_nodes(&self) -> Option<Ref<Vec<XMLNode<'gc>>>> {
+        Some(Ref::map(self.0.read(), |xod| &xod.nodes))
+    }
+
+    fn as_xml_nodes_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<XMLNode<'gc>>>> {
+        Some(RefMut::map(self.0.write(mc), |xod| &mut xod.nodes))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+
+        Ok(XmlObject::from_nodes(
+            Vec::new(),
+            this,
+            activation.context.gc_context,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+
+        XmlObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,165 @@
+//! RegExp support types
+
+use gc_arena::Collect;
+
+/// The flags of a `RegExp`, as given in its flags string.
+#[derive(Clone, Copy, Collect, Debug, Default, PartialEq, Eq)]
+#[collect(require_static)]
+pub struct RegExpFlags {
+    /// `g`: Matches continue from `lastIndex`.
+    pub global: bool,
+
+    /// `i`: Matches ignore case.
+    pub ignore_case: bool,
+
+    /// `m`: `^` and `$` also match at line breaks.
+    pub multiline: bool,
+
+    /// `s`: `.` also matches line breaks.
+    pub dotall: bool,
+}
+
+impl RegExpFlags {
+    /// Parse a flags string, ignoring unknown flags.
+    pub fn from_flags_str(flags: &str) -> Self {
+        let mut result = Self::default();
+        for flag in flags.chars() {
+            match flag {
+                'g' => result.global = true,
+                'i' => result.ignore_case = true,
+                'm' => result.multiline = true,
+                's' => result.dotall = true,
+                _ => (),
+            }
+        }
+        result
+    }
+}
+
+/// The storage portion of a `RegExp`.
+///
+/// The pattern is compiled when it is first matched against.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct RegExp {
+    /// The pattern, as given to the constructor.
+    source: String,
+
+    flags: RegExpFlags,
+
+    /// Where the next match of a global expression starts, in UTF-16 code
+    /// units.
+    last_index: usize,
+
+    /// The compiled pattern, or `None` if it failed to compile.
+    compiled: Option<Option<regress::Regex>>,
+}
+
+impl Default for RegExp {
+    fn default() -> Self {
+        Self::new("", RegExpFlags::default())
+    }
+}
+
+impl RegExp {
+    /// Construct an expression from its pattern and flags.
+    pub fn new(source: &str, flags: RegExpFlags) -> Self {
+        Self {
+            source: source.to_string(),
+            flags,
+            last_index: 0,
+            compiled: None,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn flags(&self) -> RegExpFlags {
+        self.flags
+    }
+
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    pub fn set_last_index(&mut self, last_index: usize) {
+        self.last_index = last_index;
+    }
+
+    /// Find the next match in `text`.
+    ///
+    /// Global expressions search from `lastIndex`, and leave it after the
+    /// match, or at 0 if there is none. The ranges of the returned match are
+    /// byte offsets into `text`.
+    pub fn exec(&mut self, text: &str) -> Option<regress::Match> {
+        let start = if self.flags.global {
+            match utf16_index_to_byte_offset(text, self.last_index) {
+                Some(start) => start,
+                None => {
+                    self.last_index = 0;
+                    return None;
+                }
+            }
+        } else {
+            0
+        };
+
+        let matched = self
+            .regex()
+            .and_then(|regex| regex.find_from(text, start).next());
+
+        if self.flags.global {
+            self.last_index = matched
+                .as_ref()
+                .map_or(0, |matched| utf16_len(&text[..matched.end()]));
+        }
+
+        matched
+    }
+
+    fn regex(&mut self) -> Option<&regress::Regex> {
+        if self.compiled.is_none() {
+            let flags = regress::Flags {
+                icase: self.flags.ignore_case,
+                multiline: self.flags.multiline,
+                dot_all: self.flags.dotall,
+                no_opt: false,
+            };
+            let regex = regress::Regex::with_flags(&self.source, flags);
+            if let Err(e) = &regex {
+                log::warn!("Couldn't compile RegExp /{}/: {}", self.source, e);
+            }
+            self.compiled = Some(regex.ok());
+        }
+
+        self.compiled.as_ref().and_then(|regex| regex.as_ref())
+    }
+}
+
+/// The length of a string in UTF-16 code units, which is how ActionScript
+/// measures string positions.
+pub fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Convert a position in UTF-16 code units into a byte offset into `text`.
+///
+/// Returns `None` if the position is past the end of the string. Positions
+/// inside of a surrogate pair round up to the next character.
+fn utf16_index_to_byte_offset(text: &str, index: usize) -> Option<usize> {
+    let mut utf16_pos = 0;
+    for (byte_offset, c) in text.char_indices() {
+        if utf16_pos >= index {
+            return Some(byte_offset);
+        }
+        utf16_pos += c.len_utf16();
+    }
+
+    if utf16_pos >= index {
+        Some(text.len())
+    } else {
+        None
+    }
+}
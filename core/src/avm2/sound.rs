@@ -0,0 +1,235 @@
+//! Dynamically generated sound support
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::sampledataevent;
+use crate::avm2::object::{ByteArrayObject, EventObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::{SoundInstanceHandle, SoundTransform};
+use crate::collect::CollectWrapper;
+use gc_arena::Collect;
+
+/// The least number of samples a `sampleData` handler must provide to keep a
+/// generated sound playing.
+///
+/// Providing fewer samples than this signals the end of the sound.
+pub const MIN_SAMPLES_PER_EVENT: usize = 2048;
+
+/// The most number of samples a `sampleData` handler may provide at once.
+pub const MAX_SAMPLES_PER_EVENT: usize = 8192;
+
+/// The most number of `sampleData` events a single sound will be sent per
+/// frame, regardless of how many samples the audio backend asks for.
+const MAX_EVENTS_PER_FRAME: usize = 4;
+
+/// A `Sound` object whose samples are provided by a `sampleData` event
+/// handler, along with the channel it is playing on.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct GeneratedSound<'gc> {
+    /// The audio backend's instance of this sound.
+    instance: CollectWrapper<SoundInstanceHandle>,
+
+    /// The `Sound` that `sampleData` events are dispatched to.
+    sound: Object<'gc>,
+
+    /// The `SoundChannel` returned when the sound was played.
+    channel: Object<'gc>,
+
+    /// The number of samples that have been generated so far.
+    position: usize,
+
+    /// Whether or not the handler has signalled the end of the sound.
+    finished: bool,
+}
+
+impl<'gc> GeneratedSound<'gc> {
+    pub fn new(instance: SoundInstanceHandle, sound: Object<'gc>, channel: Object<'gc>) -> Self {
+        Self {
+            instance: CollectWrapper(instance),
+            sound,
+            channel,
+            position: 0,
+            finished: false,
+        }
+    }
+
+    pub fn instance(&self) -> SoundInstanceHandle {
+        self.instance.0
+    }
+
+    pub fn channel(&self) -> Object<'gc> {
+        self.channel
+    }
+
+    /// The number of samples that have been generated so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Ask the `sampleData` handler of a generated sound for more samples.
+///
+/// Returns the samples that were written into the event's `data`, as well as
+/// whether or not the handler provided too few samples to keep playing.
+fn request_samples<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sound: Object<'gc>,
+    position: usize,
+) -> Result<(Vec<[f32; 2]>, bool), Error> {
+    let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+    let data = ByteArrayObject::from_storage(
+        ByteArrayStorage::new(),
+        bytearray_proto,
+        activation.context.gc_context,
+    );
+
+    let sampledataevent_proto = activation.context.avm2.prototypes().sampledataevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(sampledataevent_proto),
+        Event::new("sampleData"),
+    );
+    sampledataevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "sampleData".into(),
+            false.into(),
+            false.into(),
+            (position as f64).into(),
+            data.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, sound, event)?;
+
+    let mut samples = Vec::new();
+    if let Some(mut bytearray) = data.as_bytearray_mut(activation.context.gc_context) {
+        bytearray.set_position(0);
+        while samples.len() < MAX_SAMPLES_PER_EVENT {
+            match (bytearray.read_f32(), bytearray.read_f32()) {
+                (Some(left), Some(right)) => samples.push([left, right]),
+                _ => break,
+            }
+        }
+    }
+
+    let finished = samples.len() < MIN_SAMPLES_PER_EVENT;
+
+    Ok((samples, finished))
+}
+
+/// Feed every generated sound that the audio backend wants more samples for.
+///
+/// Sounds which have stopped playing are dropped, and their channel is sent
+/// a `soundComplete` event.
+pub fn generate_sound_samples<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error> {
+    let sounds = activation.context.avm2.generated_sounds().to_vec();
+
+    for mut sound in sounds {
+        let mut events = 0;
+
+        while let Some(needed) = activation
+            .context
+            .audio
+            .generated_sound_samples_needed(sound.instance())
+        {
+            if needed == 0 || sound.finished || events >= MAX_EVENTS_PER_FRAME {
+                break;
+            }
+
+            let (samples, finished) = request_samples(activation, sound.sound, sound.position)?;
+
+            activation
+                .context
+                .audio
+                .append_generated_samples(sound.instance(), &samples, finished);
+            sound.position += samples.len();
+            sound.finished = finished;
+            events += 1;
+        }
+
+        let is_playing = activation
+            .context
+            .audio
+            .generated_sound_samples_needed(sound.instance())
+            .is_some();
+
+        if is_playing {
+            activation.context.avm2.update_generated_sound(sound);
+        } else if activation
+            .context
+            .avm2
+            .remove_generated_sound(sound.channel)
+            .is_some()
+        {
+            let event_proto = activation.context.avm2.prototypes().event;
+            let event = EventObject::from_event(
+                activation.context.gc_context,
+                Some(event_proto),
+                Event::new("soundComplete"),
+            );
+
+            dispatch_event(activation, sound.channel, event)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Stop the generated sound playing on a given channel, if there is one.
+pub fn stop_generated_sound<'gc>(activation: &mut Activation<'_, 'gc, '_>, channel: Object<'gc>) {
+    if let Some(sound) = activation.context.avm2.remove_generated_sound(channel) {
+        activation.context.audio.stop_sound(sound.instance());
+    }
+}
+
+/// Stop every sound, including generated sounds.
+///
+/// Unlike a sound finishing on its own, channels stopped this way are not
+/// sent a `soundComplete` event.
+pub fn stop_all_sounds<'gc>(activation: &mut Activation<'_, 'gc, '_>) {
+    activation.context.avm2.remove_all_generated_sounds();
+    activation.context.audio.stop_all_sounds();
+}
+
+/// Change the volume and panning of the generated sound playing on a given
+/// channel, if there is one.
+pub fn set_generated_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    channel: Object<'gc>,
+    transform: SoundTransform,
+) {
+    let instance = activation
+        .context
+        .avm2
+        .generated_sounds()
+        .iter()
+        .find(|s| Object::ptr_eq(s.channel, channel))
+        .map(|s| s.instance());
+
+    if let Some(instance) = instance {
+        activation
+            .context
+            .audio
+            .set_sound_transform(instance, transform);
+    }
+}
+
+/// Retrieve the playback position of the generated sound on a channel, in
+/// milliseconds.
+pub fn generated_sound_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    channel: Object<'gc>,
+) -> Option<Value<'gc>> {
+    activation
+        .context
+        .avm2
+        .generated_sounds()
+        .iter()
+        .find(|s| Object::ptr_eq(s.channel, channel))
+        .map(|s| (s.position as f64 * 1000.0 / 44100.0).into())
+}
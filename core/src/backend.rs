@@ -5,3 +5,5 @@ pub mod log;
 pub mod navigator;
 pub mod render;
 pub mod storage;
+pub mod ui;
+pub mod video;
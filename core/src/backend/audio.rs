@@ -2,6 +2,7 @@ use downcast_rs::Downcast;
 use generational_arena::{Arena, Index};
 
 pub mod decoders;
+pub mod sound_info;
 pub mod swf {
     pub use swf::{
         read, AudioCompression, CharacterId, Sound, SoundEnvelope, SoundEnvelopePoint, SoundEvent,
@@ -51,6 +52,33 @@ pub trait AudioBackend: Downcast {
         handle: &swf::SoundStreamHead,
     ) -> Result<AudioStreamHandle, Error>;
 
+    /// Starts playing a sound instance whose samples are generated at runtime,
+    /// such as by an AVM2 `sampleData` event handler.
+    ///
+    /// The sound plays silence until samples are provided with
+    /// `append_generated_samples`.
+    fn start_generated_sound(&mut self) -> Result<SoundInstanceHandle, Error> {
+        Err("Generated sounds are not supported by this audio backend".into())
+    }
+
+    /// Returns how many more sample frames a generated sound needs to keep
+    /// playing without gaps.
+    /// Returns `None` if the sound has finished or was stopped.
+    fn generated_sound_samples_needed(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    /// Queues 44.1kHz stereo sample frames onto the end of a generated sound.
+    /// If `end_of_stream` is set, the sound will stop once all of its queued
+    /// samples have played.
+    fn append_generated_samples(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _samples: &[[f32; 2]],
+        _end_of_stream: bool,
+    ) {
+    }
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -59,6 +87,34 @@ pub trait AudioBackend: Downcast {
     /// Should be called whenever a MovieClip timeline stops playing or seeks to a new frame.
     fn stop_stream(&mut self, stream: AudioStreamHandle);
 
+    /// Returns how many milliseconds of a stream sound have played since it
+    /// was started.
+    /// Returns `None` if the stream is not playing, or if this backend can't
+    /// tell. The player uses this to keep timelines in sync with their stream.
+    fn get_stream_position(&self, _stream: AudioStreamHandle) -> Option<f64> {
+        None
+    }
+
+    /// Returns whether a sound instance is still playing.
+    /// Event sounds stop playing once they reach their end.
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool;
+
+    /// Returns how many milliseconds of an event sound have played since it
+    /// was started, over all of its loops.
+    /// Returns `None` if the sound is not playing, or if this backend can't
+    /// tell.
+    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<f64> {
+        None
+    }
+
+    /// Sets the volume and panning of a playing sound instance.
+    /// No-op if the sound is not playing.
+    fn set_sound_transform(&mut self, _sound: SoundInstanceHandle, _transform: SoundTransform) {}
+
+    /// Sets the volume and panning applied to everything the player outputs,
+    /// on top of the transform of each sound.
+    fn set_master_transform(&mut self, _transform: SoundTransform) {}
+
     /// Good ol' stopAllSounds() :-)
     fn stop_all_sounds(&mut self);
 
@@ -75,6 +131,18 @@ pub trait AudioBackend: Downcast {
     /// Returns `None` if sound is not registered.
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32>;
 
+    /// Decode part of a sound into stereo samples at 44.1kHz, as used by
+    /// `Sound.extract`. `start` and `length` are in 44.1kHz sample frames.
+    /// Returns `None` if the sound is not registered or can't be decoded.
+    fn extract_sound_samples(
+        &self,
+        _sound: SoundHandle,
+        _start: usize,
+        _length: usize,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
     // TODO: Eventually remove this/move it to library.
     fn is_loading_complete(&self) -> bool {
         true
@@ -91,59 +159,380 @@ pub trait AudioBackend: Downcast {
 
 impl_downcast!(AudioBackend);
 
+/// The volume and stereo panning of a sound, as set by an AVM2
+/// `SoundTransform`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundTransform {
+    /// The volume of the sound, from 0 (silent) to 1 (full volume).
+    pub volume: f32,
+
+    /// The balance of the sound, from -1 (fully left) to 1 (fully right).
+    pub pan: f32,
+}
+
+impl SoundTransform {
+    /// The volume of the left and right channels after panning.
+    ///
+    /// Panning towards one side fades out the other side, while the side
+    /// being panned towards is left at full volume.
+    pub fn channel_volumes(&self) -> [f32; 2] {
+        let pan = self.pan.clamp(-1.0, 1.0);
+        [
+            self.volume * (1.0 - pan.max(0.0)),
+            self.volume * (1.0 + pan.min(0.0)),
+        ]
+    }
+}
+
+impl Default for SoundTransform {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            pan: 0.0,
+        }
+    }
+}
+
+/// Starts, or stops, an event sound as directed by the `SoundInfo` of a
+/// `StartSound` tag or a button sound.
+///
+/// `SoundEvent::Start` sounds are "no multiple" sounds, which don't start if
+/// an instance of the same sound is already playing.
+pub fn start_sound_with_info(
+    audio: &mut dyn AudioBackend,
+    sound: SoundHandle,
+    settings: &swf::SoundInfo,
+) -> Option<SoundInstanceHandle> {
+    match settings.event {
+        swf::SoundEvent::Event => audio.start_sound(sound, settings).ok(),
+        swf::SoundEvent::Start => {
+            if audio.is_sound_playing_with_handle(sound) {
+                None
+            } else {
+                audio.start_sound(sound, settings).ok()
+            }
+        }
+        swf::SoundEvent::Stop => {
+            audio.stop_sounds_with_handle(sound);
+            None
+        }
+    }
+}
+
+/// Decode part of a sound into stereo samples at 44.1kHz.
+///
+/// `start` and `length` are in 44.1kHz sample frames, and the sound is
+/// linearly resampled to that rate. Fewer than `length` samples are returned
+/// if the sound ends first.
+pub fn decode_sound_samples(
+    format: &swf::SoundFormat,
+    data: &[u8],
+    skip_sample_frames: u16,
+    start: usize,
+    length: usize,
+) -> Result<Vec<[f32; 2]>, Error> {
+    let decoder = decoders::make_decoder(format, std::io::Cursor::new(data))?;
+    let step = f64::from(decoder.sample_rate()) / 44100.0;
+    let end = ((start + length) as f64 * step).ceil() as usize + 1;
+    let frames: Vec<[i16; 2]> = decoder.skip(skip_sample_frames.into()).take(end).collect();
+
+    let mut samples = Vec::with_capacity(length);
+    for i in start..start + length {
+        let position = i as f64 * step;
+        let index = position as usize;
+        let (a, b) = match (frames.get(index), frames.get(index + 1)) {
+            (Some(a), Some(b)) => (a, b),
+            (Some(a), None) => (a, a),
+            _ => break,
+        };
+        let t = (position - index as f64) as f32;
+        let lerp = |a: i16, b: i16| (f32::from(a) + (f32::from(b) - f32::from(a)) * t) / 32768.0;
+        samples.push([lerp(a[0], b[0]), lerp(a[1], b[1])]);
+    }
+
+    Ok(samples)
+}
+
 /// Audio backend that ignores all audio.
+///
+/// The settings of every event sound it is asked to start are recorded, as
+/// are the stream sounds it starts, the samples of generated sounds, any
+/// changes to volume and calls to stop all sounds, so that tests can check
+/// what a movie would have played.
+///
+/// Event sounds don't progress on their own. Tests move them along with
+/// `advance_sounds`, and they stop playing once they reach their end.
 pub struct NullAudioBackend {
-    sounds: Arena<()>,
+    sounds: Arena<NullSound>,
+    sound_instances: Arena<NullSoundInstance>,
     streams: Arena<()>,
+    started_streams: Vec<(swf::CharacterId, u16)>,
+    started_sounds: Vec<(SoundHandle, swf::SoundInfo)>,
+    sound_transforms: Vec<(SoundInstanceHandle, SoundTransform)>,
+    master_transform: SoundTransform,
+    stop_all_count: usize,
+    generated_samples: Vec<[f32; 2]>,
+}
+
+/// A sound registered with the `NullAudioBackend`.
+struct NullSound {
+    /// The length of the sound in milliseconds.
+    duration: f64,
+
+    /// The format of the sound's data.
+    format: swf::SoundFormat,
+
+    /// The encoded data of the sound, kept for `extract_sound_samples`.
+    data: Vec<u8>,
+
+    /// Number of samples to skip encoder delay.
+    skip_sample_frames: u16,
+}
+
+/// A sound playing on the `NullAudioBackend`.
+struct NullSoundInstance {
+    /// The sound this is an instance of.
+    /// `None` if this is a generated sound.
+    sound: Option<SoundHandle>,
+
+    /// How many milliseconds of this sound have played.
+    position: f64,
+
+    /// How many milliseconds this sound plays for, over all of its loops.
+    /// `None` if this is a generated sound, which plays until its samples run
+    /// out.
+    length: Option<f64>,
 }
 
 impl NullAudioBackend {
     pub fn new() -> NullAudioBackend {
         NullAudioBackend {
             streams: Arena::new(),
+            started_streams: Vec::new(),
             sounds: Arena::new(),
+            sound_instances: Arena::new(),
+            started_sounds: Vec::new(),
+            sound_transforms: Vec::new(),
+            master_transform: SoundTransform::default(),
+            stop_all_count: 0,
+            generated_samples: Vec::new(),
         }
     }
+
+    /// Move the playhead of every playing event sound forward by `dt`
+    /// milliseconds.
+    ///
+    /// Sounds that reach their end stop playing.
+    pub fn advance_sounds(&mut self, dt: f64) {
+        self.sound_instances
+            .retain(|_, instance| match instance.length {
+                Some(length) => {
+                    instance.position += dt;
+                    instance.position < length
+                }
+                None => true,
+            });
+    }
+
+    /// List every event sound started so far, along with its settings.
+    pub fn started_sounds(&self) -> &[(SoundHandle, swf::SoundInfo)] {
+        &self.started_sounds
+    }
+
+    /// List the clip and starting frame of every stream sound started so far.
+    pub fn started_streams(&self) -> &[(swf::CharacterId, u16)] {
+        &self.started_streams
+    }
+
+    /// How many stream sounds are playing, i.e. have been started and not
+    /// yet stopped.
+    pub fn playing_stream_count(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// List every transform set on a playing sound so far, in order.
+    pub fn sound_transforms(&self) -> &[(SoundInstanceHandle, SoundTransform)] {
+        &self.sound_transforms
+    }
+
+    /// The transform most recently applied to all sounds.
+    pub fn master_transform(&self) -> SoundTransform {
+        self.master_transform
+    }
+
+    /// How many times all sounds have been stopped.
+    pub fn stop_all_count(&self) -> usize {
+        self.stop_all_count
+    }
+
+    /// List every sample frame appended to a generated sound so far, in
+    /// order.
+    pub fn generated_samples(&self) -> &[[f32; 2]] {
+        &self.generated_samples
+    }
 }
 
 impl AudioBackend for NullAudioBackend {
     fn play(&mut self) {}
     fn pause(&mut self) {}
-    fn register_sound(&mut self, _sound: &swf::Sound) -> Result<SoundHandle, Error> {
-        Ok(self.sounds.insert(()))
+    fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        let duration = if sound.format.sample_rate > 0 {
+            f64::from(sound.num_samples) * 1000.0 / f64::from(sound.format.sample_rate)
+        } else {
+            0.0
+        };
+
+        // Slice off latency seek for MP3 data.
+        let (skip_sample_frames, data) =
+            if sound.format.compression == swf::AudioCompression::Mp3 && sound.data.len() >= 2 {
+                let skip_sample_frames = u16::from(sound.data[0]) | (u16::from(sound.data[1]) << 8);
+                (skip_sample_frames, &sound.data[2..])
+            } else {
+                (0, &sound.data[..])
+            };
+
+        Ok(self.sounds.insert(NullSound {
+            duration,
+            format: sound.format.clone(),
+            data: data.to_vec(),
+            skip_sample_frames,
+        }))
     }
 
     fn start_sound(
         &mut self,
-        _sound: SoundHandle,
-        _sound_info: &swf::SoundInfo,
+        sound: SoundHandle,
+        sound_info: &swf::SoundInfo,
     ) -> Result<SoundInstanceHandle, Error> {
-        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+        self.started_sounds.push((sound, sound_info.clone()));
+
+        // `in_sample` and `out_sample` are always given at 44.1kHz.
+        let duration = self.sounds.get(sound).map(|s| s.duration).unwrap_or(0.0);
+        let start = sound_info
+            .in_sample
+            .map(|sample| f64::from(sample) * 1000.0 / 44100.0)
+            .unwrap_or(0.0);
+        let end = sound_info
+            .out_sample
+            .map(|sample| f64::from(sample) * 1000.0 / 44100.0)
+            .unwrap_or(duration);
+        let length = (end - start).max(0.0) * f64::from(sound_info.num_loops.max(1));
+
+        Ok(self.sound_instances.insert(NullSoundInstance {
+            sound: Some(sound),
+            position: 0.0,
+            length: Some(length),
+        }))
     }
 
     fn start_stream(
         &mut self,
-        _clip_id: crate::prelude::CharacterId,
-        _stream_start_frame: u16,
+        clip_id: crate::prelude::CharacterId,
+        stream_start_frame: u16,
         _clip_data: crate::tag_utils::SwfSlice,
         _handle: &swf::SoundStreamHead,
     ) -> Result<AudioStreamHandle, Error> {
+        self.started_streams.push((clip_id, stream_start_frame));
         Ok(self.streams.insert(()))
     }
 
-    fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
+    fn start_generated_sound(&mut self) -> Result<SoundInstanceHandle, Error> {
+        Ok(self.sound_instances.insert(NullSoundInstance {
+            sound: None,
+            position: 0.0,
+            length: None,
+        }))
+    }
+
+    /// Generated sounds are consumed as soon as their samples are appended,
+    /// so they always want the minimum amount of samples a `sampleData`
+    /// event may provide.
+    fn generated_sound_samples_needed(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        self.sound_instances
+            .get(instance)
+            .filter(|instance| instance.length.is_none())
+            .map(|_| 2048)
+    }
+
+    fn append_generated_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        end_of_stream: bool,
+    ) {
+        if self.sound_instances.contains(instance) {
+            self.generated_samples.extend_from_slice(samples);
+        }
+
+        if end_of_stream {
+            self.sound_instances.remove(instance);
+        }
+    }
+
+    fn stop_sound(&mut self, sound: SoundInstanceHandle) {
+        self.sound_instances.remove(sound);
+    }
 
     fn stop_stream(&mut self, stream: AudioStreamHandle) {
         self.streams.remove(stream);
     }
-    fn stop_all_sounds(&mut self) {}
-    fn stop_sounds_with_handle(&mut self, _handle: SoundHandle) {}
+
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+        self.sound_instances.contains(instance)
+    }
+
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<f64> {
+        self.sound_instances
+            .get(instance)
+            .map(|instance| instance.position)
+    }
+
+    fn set_sound_transform(&mut self, sound: SoundInstanceHandle, transform: SoundTransform) {
+        self.sound_transforms.push((sound, transform));
+    }
+
+    fn set_master_transform(&mut self, transform: SoundTransform) {
+        self.master_transform = transform;
+    }
+
+    fn stop_all_sounds(&mut self) {
+        // `Arena::clear` doesn't bump the generation of the arena, so remove
+        // an instance first to make sure stale handles stay stale.
+        // See https://github.com/fitzgen/generational-arena/issues/30
+        if let Some((i, _)) = self.sound_instances.iter().next() {
+            self.sound_instances.remove(i);
+        }
+        self.sound_instances.clear();
+        self.stop_all_count += 1;
+    }
+    fn stop_sounds_with_handle(&mut self, handle: SoundHandle) {
+        let handle = Some(handle);
+        self.sound_instances
+            .retain(|_, instance| instance.sound != handle);
+    }
     fn is_sound_playing_with_handle(&mut self, _handle: SoundHandle) -> bool {
         false
     }
 
-    fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
-        None
+    fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32> {
+        self.sounds.get(sound).map(|sound| sound.duration as u32)
+    }
+
+    fn extract_sound_samples(
+        &self,
+        sound: SoundHandle,
+        start: usize,
+        length: usize,
+    ) -> Option<Vec<[f32; 2]>> {
+        let sound = self.sounds.get(sound)?;
+        decode_sound_samples(
+            &sound.format,
+            &sound.data,
+            sound.skip_sample_frames,
+            start,
+            length,
+        )
+        .ok()
     }
 }
 
@@ -152,3 +541,199 @@ impl Default for NullAudioBackend {
         NullAudioBackend::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An audio backend that records the sounds it was asked to play.
+    #[derive(Default)]
+    struct ScriptedAudioBackend {
+        sounds: Arena<()>,
+        playing: Vec<(SoundHandle, swf::SoundInfo)>,
+    }
+
+    impl AudioBackend for ScriptedAudioBackend {
+        fn play(&mut self) {}
+        fn pause(&mut self) {}
+        fn register_sound(&mut self, _sound: &swf::Sound) -> Result<SoundHandle, Error> {
+            Ok(self.sounds.insert(()))
+        }
+
+        fn start_sound(
+            &mut self,
+            sound: SoundHandle,
+            settings: &swf::SoundInfo,
+        ) -> Result<SoundInstanceHandle, Error> {
+            self.playing.push((sound, settings.clone()));
+            Ok(SoundInstanceHandle::from_raw_parts(
+                self.playing.len() - 1,
+                0,
+            ))
+        }
+
+        fn start_stream(
+            &mut self,
+            _clip_id: crate::prelude::CharacterId,
+            _clip_frame: u16,
+            _clip_data: crate::tag_utils::SwfSlice,
+            _handle: &swf::SoundStreamHead,
+        ) -> Result<AudioStreamHandle, Error> {
+            Err("Streams are not supported".into())
+        }
+
+        fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
+        fn stop_stream(&mut self, _stream: AudioStreamHandle) {}
+        fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+            instance.into_raw_parts().0 < self.playing.len()
+        }
+        fn stop_all_sounds(&mut self) {
+            self.playing.clear();
+        }
+        fn stop_sounds_with_handle(&mut self, handle: SoundHandle) {
+            self.playing.retain(|(sound, _)| *sound != handle);
+        }
+        fn is_sound_playing_with_handle(&mut self, handle: SoundHandle) -> bool {
+            self.playing.iter().any(|(sound, _)| *sound == handle)
+        }
+        fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
+            None
+        }
+    }
+
+    fn sound_info(event: swf::SoundEvent) -> swf::SoundInfo {
+        swf::SoundInfo {
+            event,
+            in_sample: Some(4410),
+            out_sample: Some(44100),
+            num_loops: 3,
+            envelope: None,
+        }
+    }
+
+    #[test]
+    fn event_sounds_play_multiple_times() {
+        let mut audio = ScriptedAudioBackend::default();
+        let sound = audio.sounds.insert(());
+        let settings = sound_info(swf::SoundEvent::Event);
+
+        assert!(start_sound_with_info(&mut audio, sound, &settings).is_some());
+        assert!(start_sound_with_info(&mut audio, sound, &settings).is_some());
+        assert_eq!(audio.playing.len(), 2);
+        assert_eq!(audio.playing[0], (sound, settings));
+    }
+
+    #[test]
+    fn start_sounds_do_not_restart() {
+        let mut audio = ScriptedAudioBackend::default();
+        let sound = audio.sounds.insert(());
+        let other = audio.sounds.insert(());
+        let settings = sound_info(swf::SoundEvent::Start);
+
+        assert!(start_sound_with_info(&mut audio, sound, &settings).is_some());
+        assert!(start_sound_with_info(&mut audio, sound, &settings).is_none());
+        assert!(start_sound_with_info(&mut audio, other, &settings).is_some());
+        assert_eq!(audio.playing.len(), 2);
+    }
+
+    #[test]
+    fn stop_sounds_stop_playing_instances() {
+        let mut audio = ScriptedAudioBackend::default();
+        let sound = audio.sounds.insert(());
+        let other = audio.sounds.insert(());
+        start_sound_with_info(&mut audio, sound, &sound_info(swf::SoundEvent::Event));
+        start_sound_with_info(&mut audio, other, &sound_info(swf::SoundEvent::Event));
+
+        assert!(
+            start_sound_with_info(&mut audio, sound, &sound_info(swf::SoundEvent::Stop)).is_none()
+        );
+        assert_eq!(audio.playing.len(), 1);
+        assert_eq!(audio.playing[0].0, other);
+    }
+
+    /// A mono 16-bit PCM sound at 22.05kHz.
+    fn pcm_sound(num_samples: u32, samples: &[i16]) -> swf::Sound {
+        swf::Sound {
+            id: 1,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate: 22050,
+                is_stereo: false,
+                is_16_bit: true,
+            },
+            num_samples,
+            data: samples
+                .iter()
+                .flat_map(|s| s.to_le_bytes().to_vec())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn null_sounds_extract_resampled_samples() {
+        let mut audio = NullAudioBackend::new();
+        let sound = audio
+            .register_sound(&pcm_sound(3, &[0, 16384, -16384]))
+            .unwrap();
+
+        let left = |samples: Vec<[f32; 2]>| samples.iter().map(|s| s[0]).collect::<Vec<_>>();
+        assert_eq!(
+            left(audio.extract_sound_samples(sound, 0, 10).unwrap()),
+            vec![0.0, 0.25, 0.5, 0.0, -0.5, -0.5]
+        );
+        assert_eq!(
+            audio.extract_sound_samples(sound, 2, 2).unwrap(),
+            vec![[0.5, 0.5], [0.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn null_sounds_finish_at_their_end() {
+        let mut audio = NullAudioBackend::new();
+        let sound = audio.register_sound(&pcm_sound(22050, &[])).unwrap();
+        let once = audio
+            .start_sound(sound, &sound_info(swf::SoundEvent::Event))
+            .unwrap();
+        let forever = audio.start_generated_sound().unwrap();
+
+        // Played from 0.1s to 1s, three times.
+        audio.advance_sounds(2000.0);
+        assert_eq!(audio.get_sound_position(once), Some(2000.0));
+        assert!(audio.is_sound_playing(once));
+
+        audio.advance_sounds(700.0);
+        assert_eq!(audio.get_sound_position(once), None);
+        assert!(!audio.is_sound_playing(once));
+        assert!(audio.is_sound_playing(forever));
+    }
+
+    #[test]
+    fn null_generated_sounds_record_their_samples() {
+        let mut audio = NullAudioBackend::new();
+        let generated = audio.start_generated_sound().unwrap();
+
+        audio.append_generated_samples(generated, &[[0.5, -0.5]; 2048], false);
+        assert_eq!(audio.generated_sound_samples_needed(generated), Some(2048));
+        audio.append_generated_samples(generated, &[[0.25, 0.0]], true);
+        assert_eq!(audio.generated_sound_samples_needed(generated), None);
+
+        // Samples for a sound that has stopped go nowhere.
+        audio.append_generated_samples(generated, &[[1.0, 1.0]], true);
+
+        let samples = audio.generated_samples();
+        assert_eq!(samples.len(), 2049);
+        assert_eq!(samples[0], [0.5, -0.5]);
+        assert_eq!(samples[2048], [0.25, 0.0]);
+    }
+
+    #[test]
+    fn sound_transform_pans_by_fading_other_side() {
+        let transform = |volume, pan| SoundTransform { volume, pan };
+
+        assert_eq!(transform(1.0, 0.0).channel_volumes(), [1.0, 1.0]);
+        assert_eq!(transform(0.5, 0.0).channel_volumes(), [0.5, 0.5]);
+        assert_eq!(transform(1.0, 0.5).channel_volumes(), [0.5, 1.0]);
+        assert_eq!(transform(1.0, -1.0).channel_volumes(), [1.0, 0.0]);
+        assert_eq!(transform(0.5, 2.0).channel_volumes(), [0.0, 0.5]);
+    }
+}
@@ -2,10 +2,12 @@
 
 mod adpcm;
 mod mp3;
+mod nellymoser;
 mod pcm;
 
 pub use adpcm::AdpcmDecoder;
 pub use mp3::Mp3Decoder;
+pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
 
 use crate::tag_utils::SwfSlice;
@@ -56,6 +58,15 @@ pub fn make_decoder<'a, R: 'a + Send + Read>(
             format.sample_rate.into(),
             data,
         )),
+        AudioCompression::Nellymoser16Khz => Box::new(NellymoserDecoder::new(data, 16000)),
+        AudioCompression::Nellymoser8Khz => Box::new(NellymoserDecoder::new(data, 8000)),
+        AudioCompression::Nellymoser => {
+            log::warn!(
+                "make_decoder: Nellymoser sound at {} Hz is unsupported; playing silence",
+                format.sample_rate
+            );
+            Box::new(NellymoserDecoder::silent(data, format.sample_rate))
+        }
         _ => {
             let msg = format!(
                 "make_decoder: Unhandled audio compression {:?}",
@@ -83,6 +94,9 @@ pub trait StreamDecoder: Decoder {}
 struct StandardStreamDecoder {
     /// The underlying decoder. The decoder will get its data from a `StreamTagReader`.
     decoder: Box<dyn Decoder + Send>,
+
+    /// The number of sample frames left to skip before the stream starts.
+    skip_sample_frames: u16,
 }
 
 impl StandardStreamDecoder {
@@ -90,10 +104,25 @@ impl StandardStreamDecoder {
     /// `swf_data` should be the tag data of the MovieClip that contains the stream.
     fn new(format: &SoundFormat, swf_data: SwfSlice) -> Result<Self, Error> {
         // Create a tag reader to get the audio data from SoundStreamBlock tags.
-        let tag_reader = StreamTagReader::new(format.compression, swf_data);
+        let mut tag_reader = StreamTagReader::new(format.compression, swf_data);
+
+        // MP3 frames don't line up with SWF frames, so the first block of the
+        // stream may begin with audio that belongs to the previous frame.
+        // Its seek samples tell how much of it to skip.
+        // SWF19 p.188
+        let skip_sample_frames = if format.compression == AudioCompression::Mp3 {
+            tag_reader.next();
+            tag_reader.seek_samples.max(0) as u16
+        } else {
+            0
+        };
+
         // Wrap the tag reader in the decoder.
         let decoder = make_decoder(format, tag_reader)?;
-        Ok(Self { decoder })
+        Ok(Self {
+            decoder,
+            skip_sample_frames,
+        })
     }
 }
 
@@ -111,6 +140,11 @@ impl Iterator for StandardStreamDecoder {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        while self.skip_sample_frames > 0 {
+            self.skip_sample_frames -= 1;
+            self.decoder.next()?;
+        }
+
         self.decoder.next()
     }
 }
@@ -217,6 +251,9 @@ struct StreamTagReader {
     current_frame: u16,
     current_audio_data: SwfSlice,
     compression: AudioCompression,
+
+    /// The seek samples of the last MP3 `SoundStreamBlock` that was read.
+    seek_samples: i16,
 }
 
 impl StreamTagReader {
@@ -230,6 +267,7 @@ impl StreamTagReader {
             reader: swf::read::Reader::new(Cursor::new(swf_data), version),
             current_frame: 1,
             current_audio_data,
+            seek_samples: 0,
         }
     }
 }
@@ -240,6 +278,7 @@ impl Iterator for StreamTagReader {
     fn next(&mut self) -> Option<Self::Item> {
         let current_frame = &mut self.current_frame;
         let audio_data = &mut self.current_audio_data;
+        let seek_samples = &mut self.seek_samples;
         let compression = self.compression;
         let mut found = false;
         // MP3 stream blocks store seek samples and sample count in the first 4 bytes.
@@ -262,6 +301,10 @@ impl Iterator for StreamTagReader {
                     let pos = reader.get_ref().get_ref().start + pos;
                     found = true;
                     if tag_len >= skip_len {
+                        if compression == AudioCompression::Mp3 {
+                            let data = reader.get_ref().get_ref().movie.data();
+                            *seek_samples = i16::from_le_bytes([data[pos + 2], data[pos + 3]]);
+                        }
                         *audio_data = SwfSlice {
                             movie: std::sync::Arc::clone(&reader.get_ref().get_ref().movie),
                             start: pos + skip_len,
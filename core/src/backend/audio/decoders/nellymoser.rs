@@ -0,0 +1,524 @@
+use super::{Decoder, SeekableDecoder};
+use bitstream_io::{BigEndian, BitReader};
+use std::io::{Cursor, Read};
+
+/// The number of frequency bands whose gains are stored in a block header.
+const NUM_BANDS: usize = 23;
+
+/// The size of an encoded block in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// The number of bits used by the band gains at the start of a block.
+const HEADER_BITS: u32 = 116;
+
+/// The number of bits used by the coefficients of each half of a block.
+const DETAIL_BITS: i32 = 198;
+
+/// The number of MDCT coefficients in each half of a block.
+const BUF_LEN: usize = 128;
+
+/// The number of MDCT coefficients that are actually coded; the rest are zero.
+const FILL_LEN: usize = 124;
+
+/// The maximum number of bits a single coefficient may use.
+const BIT_CAP: i32 = 6;
+
+const BASE_OFF: i32 = 4228;
+const BASE_SHIFT: i32 = 19;
+
+/// The number of samples decoded from each block.
+const SAMPLES_PER_BLOCK: usize = 2 * BUF_LEN;
+
+/// Decoder for Nellymoser Asao audio data in a Flash file.
+/// Flash exports this when you use the "Speech" compression setting, and it is
+/// also used by microphone audio recorded into FLVs.
+///
+/// Each 64-byte block holds the band gains followed by two sets of 124 quantized
+/// MDCT coefficients, which decode to 256 mono samples.
+pub struct NellymoserDecoder<R: Read> {
+    inner: R,
+    sample_rate: u16,
+
+    /// Whether this decoder only outputs silence for the blocks it reads.
+    /// Used for the variants of Nellymoser that we can't decode.
+    silent: bool,
+
+    /// The decoded samples of the current block.
+    samples: [i16; SAMPLES_PER_BLOCK],
+    cur_sample: usize,
+
+    /// The windowed second half of the previous inverse MDCT,
+    /// which overlaps the first half of the next one.
+    overlap: [f32; BUF_LEN],
+
+    /// The state of the generator used for the signs of noise-filled coefficients.
+    noise_seed: u32,
+
+    /// `cos` values of the inverse MDCT, indexed by `sample * BUF_LEN + coefficient`.
+    imdct_table: Vec<f32>,
+
+    /// The sine window applied to the output of the inverse MDCT.
+    window: Vec<f32>,
+}
+
+impl<R: Read> NellymoserDecoder<R> {
+    const DEQUANTIZATION_TABLE: [f32; 127] = [
+        0.0, -0.847256, 0.722471, -1.524748, -0.453148, 0.375361, 1.47179, -1.982258, -1.192938,
+        -0.582937, -0.069378, 0.390957, 0.90692, 1.486274, 2.221541, -2.388787, -1.806754,
+        -1.410542, -1.077361, -0.799501, -0.555811, -0.333402, -0.132449, 0.056802, 0.254877,
+        0.477355, 0.738685, 1.044306, 1.395446, 1.809875, 2.391876, -2.389383, -1.988468,
+        -1.751404, -1.564312, -1.392213, -1.216465, -1.04695, -0.89051, -0.764558, -0.645458,
+        -0.525928, -0.405955, -0.302972, -0.20969, -0.123987, -0.047923, 0.025773, 0.100134,
+        0.173718, 0.258554, 0.35229, 0.456988, 0.576775, 0.700316, 0.842552, 1.009388, 1.182135,
+        1.353456, 1.532082, 1.733262, 1.972235, 2.397814, -2.575631, -2.057332, -1.898492,
+        -1.772781, -1.66626, -1.574218, -1.499332, -1.431664, -1.365228, -1.300099, -1.228093,
+        -1.158858, -1.092125, -1.013574, -0.920285, -0.828705, -0.737489, -0.644776, -0.559094,
+        -0.485714, -0.411032, -0.34597, -0.285116, -0.234162, -0.187058, -0.14425, -0.110717,
+        -0.073968, -0.036561, -0.007329, 0.020361, 0.047904, 0.075197, 0.0981, 0.122039, 0.1459,
+        0.169435, 0.197046, 0.225243, 0.255687, 0.28701, 0.31971, 0.352583, 0.388907, 0.433492,
+        0.476946, 0.520482, 0.564453, 0.612204, 0.668593, 0.734165, 0.803216, 0.878404, 0.956621,
+        1.039707, 1.129377, 1.221116, 1.308028, 1.40248, 1.505682, 1.622773, 1.772496, 1.943088,
+        2.290393,
+    ];
+
+    const BAND_SIZES_TABLE: [usize; NUM_BANDS] = [
+        2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 5, 5, 6, 6, 7, 8, 10, 12, 14, 15,
+    ];
+
+    const INIT_TABLE: [u16; 64] = [
+        3134, 5342, 6870, 7792, 8569, 9185, 9744, 10191, 10631, 11061, 11434, 11770, 12116, 12513,
+        12925, 13300, 13674, 14027, 14352, 14716, 15117, 15477, 15824, 16157, 16513, 16804, 17090,
+        17401, 17679, 17948, 18238, 18520, 18764, 19078, 19381, 19678, 19941, 20222, 20503, 20774,
+        21042, 21324, 21585, 21851, 22100, 22371, 22626, 22883, 23137, 23395, 23637, 23895, 24127,
+        24369, 24597, 24837, 25071, 25305, 25543, 25786, 26049, 26291, 26559, 26829,
+    ];
+
+    const DELTA_TABLE: [i16; 32] = [
+        -11725, -9420, -7910, -6801, -5948, -5233, -4599, -4039, -3507, -3030, -2596, -2170, -1774,
+        -1383, -1016, -660, -329, -1, 337, 696, 1085, 1512, 1962, 2433, 2968, 3569, 4314, 5279,
+        6622, 8154, 10089, 11698,
+    ];
+
+    pub fn new(inner: R, sample_rate: u16) -> Self {
+        let imdct_table = (0..2 * BUF_LEN)
+            .flat_map(|n| {
+                (0..BUF_LEN).map(move |k| {
+                    let n = n as f64 + 0.5 + (BUF_LEN / 2) as f64;
+                    let k = k as f64 + 0.5;
+                    (std::f64::consts::PI / BUF_LEN as f64 * n * k).cos() as f32
+                })
+            })
+            .collect();
+        let window = (0..2 * BUF_LEN)
+            .map(|n| {
+                let n = n as f64 + 0.5;
+                (std::f64::consts::PI / (2 * BUF_LEN) as f64 * n).sin() as f32
+            })
+            .collect();
+
+        NellymoserDecoder {
+            inner,
+            sample_rate,
+            silent: false,
+            samples: [0; SAMPLES_PER_BLOCK],
+            cur_sample: SAMPLES_PER_BLOCK,
+            overlap: [0.0; BUF_LEN],
+            noise_seed: 0,
+            imdct_table,
+            window,
+        }
+    }
+
+    /// Creates a decoder that reads blocks of Nellymoser data, but outputs silence for them.
+    pub fn silent(inner: R, sample_rate: u16) -> Self {
+        NellymoserDecoder {
+            inner,
+            sample_rate,
+            silent: true,
+            samples: [0; SAMPLES_PER_BLOCK],
+            cur_sample: SAMPLES_PER_BLOCK,
+            overlap: [0.0; BUF_LEN],
+            noise_seed: 0,
+            imdct_table: Vec::new(),
+            window: Vec::new(),
+        }
+    }
+
+    /// Reads the next block and decodes it into `self.samples`.
+    fn next_block(&mut self) -> Result<(), std::io::Error> {
+        let mut block = [0u8; BLOCK_LEN];
+        self.inner.read_exact(&mut block)?;
+        self.cur_sample = 0;
+        if !self.silent {
+            self.decode_block(&block)?;
+        }
+        Ok(())
+    }
+
+    fn decode_block(&mut self, block: &[u8; BLOCK_LEN]) -> Result<(), std::io::Error> {
+        // The header holds the gain of each band as a delta from the band before it.
+        let mut reader = BitReader::<_, BigEndian>::new(&block[..]);
+        let mut gains = [0.0; FILL_LEN];
+        let mut pows = [0.0; FILL_LEN];
+        let mut gain = f32::from(Self::INIT_TABLE[reader.read::<u8>(6)? as usize]);
+        let mut i = 0;
+        for (band, &band_size) in Self::BAND_SIZES_TABLE.iter().enumerate() {
+            if band > 0 {
+                gain += f32::from(Self::DELTA_TABLE[reader.read::<u8>(5)? as usize]);
+            }
+            // Scale the coefficients so that the inverse MDCT outputs 16-bit samples.
+            let pow = (gain / 2048.0).exp2() / 8.0;
+            for _ in 0..band_size {
+                gains[i] = gain;
+                pows[i] = pow;
+                i += 1;
+            }
+        }
+
+        // The bits available for the coefficients are shared out by band gain,
+        // so the bit allocation doesn't have to be stored.
+        let bits = sample_bits(&gains);
+
+        for half in 0..2 {
+            let mut reader = BitReader::<_, BigEndian>::new(&block[..]);
+            reader.skip(HEADER_BITS + half as u32 * DETAIL_BITS as u32)?;
+
+            let mut coefficients = [0.0; BUF_LEN];
+            for ((coefficient, &num_bits), &pow) in
+                coefficients.iter_mut().zip(bits.iter()).zip(pows.iter())
+            {
+                if num_bits > 0 {
+                    let index = (1 << num_bits) - 1 + reader.read::<u16>(num_bits as u32)?;
+                    *coefficient = Self::DEQUANTIZATION_TABLE[index as usize] * pow;
+                } else {
+                    // Coefficients without any bits are filled with noise of the band's power.
+                    *coefficient = std::f32::consts::FRAC_1_SQRT_2 * pow;
+                    if self.next_noise_sign() {
+                        *coefficient = -*coefficient;
+                    }
+                }
+            }
+
+            self.inverse_mdct(&coefficients, half * BUF_LEN..(half + 1) * BUF_LEN);
+        }
+
+        Ok(())
+    }
+
+    /// Transforms a set of coefficients back into samples, overlapping them with
+    /// the previous set to produce the samples in `range`.
+    fn inverse_mdct(&mut self, coefficients: &[f32; BUF_LEN], range: std::ops::Range<usize>) {
+        let mut output = [0.0; 2 * BUF_LEN];
+        for (n, out) in output.iter_mut().enumerate() {
+            let row = &self.imdct_table[n * BUF_LEN..(n + 1) * BUF_LEN];
+            let sum: f32 = row
+                .iter()
+                .zip(coefficients.iter())
+                .map(|(cos, coefficient)| cos * coefficient)
+                .sum();
+            *out = sum * self.window[n];
+        }
+
+        for (i, sample) in self.samples[range].iter_mut().enumerate() {
+            *sample = (self.overlap[i] + output[i]).round() as i16;
+            self.overlap[i] = output[BUF_LEN + i];
+        }
+    }
+
+    /// Returns whether the next noise-filled coefficient should be negative.
+    fn next_noise_sign(&mut self) -> bool {
+        self.noise_seed = self
+            .noise_seed
+            .wrapping_mul(1_664_525)
+            .wrapping_add(1_013_904_223);
+        self.noise_seed & 0x8000_0000 != 0
+    }
+}
+
+/// Allocates the detail bits of a half block to each coefficient, based on
+/// the gains of their bands.
+fn sample_bits(gains: &[f32; FILL_LEN]) -> [i32; FILL_LEN] {
+    let mut max = 0;
+    for &gain in gains.iter() {
+        if gain > max as f32 {
+            max = gain as i32;
+        }
+    }
+    let mut shift = -16 + headroom(&mut max);
+
+    let mut shifted_gains = [0i16; FILL_LEN];
+    let mut sum = 0;
+    for (shifted, &gain) in shifted_gains.iter_mut().zip(gains.iter()) {
+        let gain = i32::from(signed_shift(gain as i32, shift) as i16);
+        *shifted = ((3 * gain) >> 2) as i16;
+        sum += i32::from(*shifted);
+    }
+
+    shift += 11;
+    let gain_shift = shift;
+    sum -= DETAIL_BITS << shift;
+    shift += headroom(&mut sum);
+    let mut small_off = (BASE_OFF * (sum >> 16)) >> 15;
+    shift = gain_shift - (BASE_SHIFT + shift - 31);
+    small_off = signed_shift(small_off, shift);
+
+    let mut bitsum = sum_bits(&shifted_gains, gain_shift, small_off);
+    if bitsum != DETAIL_BITS {
+        // Step the offset until the bit count crosses the number of available bits...
+        let mut off = bitsum - DETAIL_BITS;
+        shift = 0;
+        while off.abs() <= 16383 {
+            off *= 2;
+            shift += 1;
+        }
+        off = (off * BASE_OFF) >> 15;
+        shift = gain_shift - (BASE_SHIFT + shift - 15);
+        off = signed_shift(off, shift);
+
+        let mut last_off = small_off;
+        let mut last_bitsum = bitsum;
+        let mut step = 1;
+        while step < 20 {
+            last_off = small_off;
+            small_off += off;
+            last_bitsum = bitsum;
+            bitsum = sum_bits(&shifted_gains, gain_shift, small_off);
+            if (bitsum - DETAIL_BITS) * (last_bitsum - DETAIL_BITS) <= 0 {
+                break;
+            }
+            step += 1;
+        }
+
+        let (mut big_off, mut big_bitsum, mut small_bitsum);
+        if bitsum > DETAIL_BITS {
+            big_off = small_off;
+            small_off = last_off;
+            big_bitsum = bitsum;
+            small_bitsum = last_bitsum;
+        } else {
+            big_off = last_off;
+            big_bitsum = last_bitsum;
+            small_bitsum = bitsum;
+        }
+
+        // ...then bisect between the last two offsets.
+        while bitsum != DETAIL_BITS && step <= 19 {
+            off = (big_off + small_off) >> 1;
+            bitsum = sum_bits(&shifted_gains, gain_shift, off);
+            if bitsum > DETAIL_BITS {
+                big_off = off;
+                big_bitsum = bitsum;
+            } else {
+                small_off = off;
+                small_bitsum = bitsum;
+            }
+            step += 1;
+        }
+
+        if (big_bitsum - DETAIL_BITS).abs() >= (small_bitsum - DETAIL_BITS).abs() {
+            bitsum = small_bitsum;
+        } else {
+            small_off = big_off;
+            bitsum = big_bitsum;
+        }
+    }
+
+    let mut bits = [0; FILL_LEN];
+    for (bits, &gain) in bits.iter_mut().zip(shifted_gains.iter()) {
+        let num_bits = i32::from(gain) - small_off;
+        let num_bits = ((num_bits >> (gain_shift - 1)) + 1) >> 1;
+        *bits = num_bits.clamp(0, BIT_CAP);
+    }
+
+    // If we couldn't hit the number of available bits exactly, take the
+    // excess from the highest coefficients.
+    if bitsum > DETAIL_BITS {
+        let mut total = 0;
+        let mut i = 0;
+        while total < DETAIL_BITS && i < FILL_LEN {
+            total += bits[i];
+            i += 1;
+        }
+        bits[i - 1] -= total - DETAIL_BITS;
+        for bits in &mut bits[i..] {
+            *bits = 0;
+        }
+    }
+
+    bits
+}
+
+/// Counts the bits allocated by `sample_bits` for the given offset.
+fn sum_bits(shifted_gains: &[i16; FILL_LEN], shift: i32, off: i32) -> i32 {
+    // The reference implementation truncates the offset to 16 bits here.
+    let off = i32::from(off as i16);
+    shifted_gains
+        .iter()
+        .map(|&gain| {
+            let num_bits = i32::from(gain) - off;
+            let num_bits = ((num_bits >> (shift - 1)) + 1) >> 1;
+            num_bits.clamp(0, BIT_CAP)
+        })
+        .sum()
+}
+
+/// Normalizes `value` so that its highest bit is bit 30, returning the shift used.
+fn headroom(value: &mut i32) -> i32 {
+    if *value == 0 {
+        return 31;
+    }
+    let magnitude = value.wrapping_abs() as u32;
+    let shift = 30 - (31 - magnitude.leading_zeros() as i32);
+    *value = signed_shift(*value, shift);
+    shift
+}
+
+/// Shifts left for positive `shift`, and right for negative `shift`.
+fn signed_shift(value: i32, shift: i32) -> i32 {
+    if shift > 0 {
+        value.wrapping_shl(shift as u32)
+    } else {
+        value >> (-shift).min(31)
+    }
+}
+
+impl<R: Read> Iterator for NellymoserDecoder<R> {
+    type Item = [i16; 2];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_sample >= SAMPLES_PER_BLOCK {
+            self.next_block().ok()?;
+        }
+        let sample = self.samples[self.cur_sample];
+        self.cur_sample += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<R: Read> Decoder for NellymoserDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+impl<R: AsRef<[u8]>> SeekableDecoder for NellymoserDecoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+        self.cur_sample = SAMPLES_PER_BLOCK;
+        self.overlap = [0.0; BUF_LEN];
+        self.noise_seed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a block from a band gain header and a repeating pattern for the coefficient bits.
+    fn block(init: u8, delta: u8, pattern: u8) -> [u8; BLOCK_LEN] {
+        let mut bits = vec![];
+        bits.extend((0..6).rev().map(|i| (init >> i) & 1));
+        for _ in 1..NUM_BANDS {
+            bits.extend((0..5).rev().map(|i| (delta >> i) & 1));
+        }
+        while bits.len() < BLOCK_LEN * 8 {
+            bits.extend((0..8).rev().map(|i| (pattern >> i) & 1));
+        }
+
+        let mut block = [0; BLOCK_LEN];
+        for (i, bit) in bits.iter().take(BLOCK_LEN * 8).enumerate() {
+            block[i / 8] |= bit << (7 - i % 8);
+        }
+        block
+    }
+
+    fn decode(data: &[u8]) -> Vec<i16> {
+        NellymoserDecoder::new(data, 8000)
+            .map(|[left, right]| {
+                assert_eq!(left, right);
+                left
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bit_allocation_fits_block() {
+        let mut gains = [0.0; FILL_LEN];
+        for (init, delta) in &[(0, 0), (40, 17), (63, 31), (20, 20), (50, 10)] {
+            let mut gain = f32::from(NellymoserDecoder::<&[u8]>::INIT_TABLE[*init]);
+            let mut i = 0;
+            for (band, &band_size) in NellymoserDecoder::<&[u8]>::BAND_SIZES_TABLE
+                .iter()
+                .enumerate()
+            {
+                if band > 0 {
+                    gain += f32::from(NellymoserDecoder::<&[u8]>::DELTA_TABLE[*delta]);
+                }
+                for _ in 0..band_size {
+                    gains[i] = gain;
+                    i += 1;
+                }
+            }
+            assert_eq!(i, FILL_LEN);
+
+            let bits = sample_bits(&gains);
+            assert!(bits.iter().all(|&bits| (0..=BIT_CAP).contains(&bits)));
+            assert!(bits.iter().sum::<i32>() <= DETAIL_BITS);
+        }
+    }
+
+    #[test]
+    fn decode_quiet_block() {
+        // The lowest gains make every band inaudible.
+        let samples = decode(&[0; BLOCK_LEN]);
+        assert_eq!(samples.len(), SAMPLES_PER_BLOCK);
+        assert!(samples.iter().all(|sample| sample.abs() <= 1));
+    }
+
+    #[test]
+    fn decode_loud_blocks() {
+        let mut data = vec![];
+        for _ in 0..4 {
+            data.extend_from_slice(&block(40, 17, 0b1001_0010));
+        }
+        let samples = decode(&data);
+        assert_eq!(samples.len(), 4 * SAMPLES_PER_BLOCK);
+
+        // The first half of the first block only holds the fade-in of the window.
+        let peak = |samples: &[i16]| samples.iter().map(|s| i32::from(*s).abs()).max();
+        assert!(peak(&samples[SAMPLES_PER_BLOCK..]) > Some(1000));
+    }
+
+    #[test]
+    fn decode_ignores_partial_block() {
+        let data = [0; BLOCK_LEN * 2 + BLOCK_LEN / 2];
+        assert_eq!(decode(&data).len(), 2 * SAMPLES_PER_BLOCK);
+    }
+
+    #[test]
+    fn silent_decoder() {
+        let data = block(63, 31, 0xFF);
+        let samples: Vec<_> = NellymoserDecoder::silent(&data[..], 22050).collect();
+        assert_eq!(samples.len(), SAMPLES_PER_BLOCK);
+        assert!(samples.iter().all(|&sample| sample == [0, 0]));
+    }
+
+    #[test]
+    fn reset_restarts_decoding() {
+        let mut data = vec![];
+        for pattern in &[0x5A, 0xC3, 0x0F] {
+            data.extend_from_slice(&block(40, 17, *pattern));
+        }
+        let mut decoder = NellymoserDecoder::new(Cursor::new(data), 16000);
+        let first: Vec<_> = decoder.by_ref().collect();
+        decoder.reset();
+        let second: Vec<_> = decoder.collect();
+        assert_eq!(first, second);
+    }
+}
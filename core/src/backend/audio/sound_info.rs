@@ -0,0 +1,235 @@
+//! Playback settings of event sounds.
+//!
+//! A `SoundInfo` from a `StartSound` tag, a button sound or `Sound.start`
+//! describes which part of a sound plays, how often it repeats and how its
+//! volume changes over time. The positions it uses are always counted in
+//! 44.1kHz samples, regardless of the sample rate of the sound itself.
+
+use crate::backend::audio::swf;
+
+/// The sample rate that `SoundInfo` positions are measured in.
+const SOUND_INFO_SAMPLE_RATE: u64 = 44100;
+
+/// The part of an event sound to play, and how many times to play it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoundRange {
+    /// The first sample frame to play, in the sample rate of the sound.
+    pub start_sample_frame: u32,
+
+    /// The sample frame to stop playing at, in the sample rate of the sound.
+    /// This sample frame itself is not played.
+    pub end_sample_frame: u32,
+
+    /// How many times the range is played. Always at least 1.
+    pub num_loops: u16,
+}
+
+impl SoundRange {
+    /// Compute the range of a sound to play.
+    ///
+    /// `num_sample_frames` is the length of the sound, and
+    /// `skip_sample_frames` the encoder delay at its start which is never
+    /// played. The in and out points of `settings` are relative to the end of
+    /// the encoder delay, and are clamped to the length of the sound.
+    pub fn new(
+        settings: &swf::SoundInfo,
+        sample_rate: u16,
+        num_sample_frames: u32,
+        skip_sample_frames: u16,
+    ) -> Self {
+        let to_sample_frame = |sample: u32| {
+            let sample_frame = u64::from(sample) * u64::from(sample_rate) / SOUND_INFO_SAMPLE_RATE;
+            sample_frame.min(u64::from(num_sample_frames)) as u32
+        };
+        let end_sample_frame = settings
+            .out_sample
+            .map(to_sample_frame)
+            .unwrap_or(num_sample_frames);
+        let start_sample_frame = settings
+            .in_sample
+            .map(to_sample_frame)
+            .unwrap_or(0)
+            .min(end_sample_frame);
+
+        let skip_sample_frames = u32::from(skip_sample_frames);
+        Self {
+            start_sample_frame: start_sample_frame + skip_sample_frames,
+            end_sample_frame: end_sample_frame + skip_sample_frames,
+            num_loops: settings.num_loops.max(1),
+        }
+    }
+
+    /// The number of sample frames played in a single loop.
+    pub fn loop_sample_frames(&self) -> u32 {
+        self.end_sample_frame - self.start_sample_frame
+    }
+
+    /// The number of sample frames played over all loops.
+    pub fn total_sample_frames(&self) -> u64 {
+        u64::from(self.loop_sample_frames()) * u64::from(self.num_loops)
+    }
+}
+
+/// A volume envelope of an event sound.
+///
+/// The envelope is a piecewise-linear gain for each channel, over the 44.1kHz
+/// sample timeline that starts when the sound starts playing. Before its first
+/// point and after its last point, the volume of that point is held.
+#[derive(Clone, Debug)]
+pub struct SoundEnvelope {
+    points: Vec<swf::SoundEnvelopePoint>,
+}
+
+impl SoundEnvelope {
+    pub fn new(points: &[swf::SoundEnvelopePoint]) -> Self {
+        let mut points = points.to_vec();
+        if points
+            .windows(2)
+            .any(|pair| pair[0].sample > pair[1].sample)
+        {
+            log::warn!("Sound envelope points are out of order");
+            points.sort_by_key(|point| point.sample);
+        }
+
+        Self { points }
+    }
+
+    /// The left and right gain at a 44.1kHz sample position.
+    pub fn volume_at(&self, sample: u32) -> [f32; 2] {
+        // The index of the first point after `sample`.
+        let next = match self.points.binary_search_by(|point| {
+            if point.sample <= sample {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        }) {
+            Ok(i) | Err(i) => i,
+        };
+
+        match (
+            next.checked_sub(1).map(|i| &self.points[i]),
+            self.points.get(next),
+        ) {
+            (Some(prev), Some(next)) => {
+                let t = (sample - prev.sample) as f32 / (next.sample - prev.sample) as f32;
+                [
+                    prev.left_volume + (next.left_volume - prev.left_volume) * t,
+                    prev.right_volume + (next.right_volume - prev.right_volume) * t,
+                ]
+            }
+            (Some(point), None) | (None, Some(point)) => [point.left_volume, point.right_volume],
+            (None, None) => [1.0, 1.0],
+        }
+    }
+
+    /// The left and right gain after playing a number of sample frames of a
+    /// sound with the given sample rate.
+    pub fn volume_after(&self, sample_frames: u64, sample_rate: u16) -> [f32; 2] {
+        let sample = sample_frames * SOUND_INFO_SAMPLE_RATE / u64::from(sample_rate).max(1);
+        self.volume_at(sample.min(u64::from(u32::MAX)) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sound_info(
+        in_sample: Option<u32>,
+        out_sample: Option<u32>,
+        num_loops: u16,
+    ) -> swf::SoundInfo {
+        swf::SoundInfo {
+            event: swf::SoundEvent::Event,
+            in_sample,
+            out_sample,
+            num_loops,
+            envelope: None,
+        }
+    }
+
+    fn point(sample: u32, left_volume: f32, right_volume: f32) -> swf::SoundEnvelopePoint {
+        swf::SoundEnvelopePoint {
+            sample,
+            left_volume,
+            right_volume,
+        }
+    }
+
+    #[test]
+    fn range_defaults_to_whole_sound() {
+        let range = SoundRange::new(&sound_info(None, None, 1), 44100, 1000, 0);
+        assert_eq!(
+            range,
+            SoundRange {
+                start_sample_frame: 0,
+                end_sample_frame: 1000,
+                num_loops: 1,
+            }
+        );
+        assert_eq!(range.total_sample_frames(), 1000);
+    }
+
+    #[test]
+    fn range_converts_from_44100hz() {
+        let range = SoundRange::new(&sound_info(Some(4410), Some(22050), 3), 11025, 11025, 0);
+        assert_eq!(range.start_sample_frame, 1102);
+        assert_eq!(range.end_sample_frame, 5512);
+        assert_eq!(range.num_loops, 3);
+        assert_eq!(range.total_sample_frames(), 3 * 4410);
+    }
+
+    #[test]
+    fn range_skips_encoder_delay() {
+        let range = SoundRange::new(&sound_info(Some(100), None, 1), 44100, 1000, 576);
+        assert_eq!(range.start_sample_frame, 676);
+        assert_eq!(range.end_sample_frame, 1576);
+    }
+
+    #[test]
+    fn range_is_clamped_to_sound() {
+        let range = SoundRange::new(&sound_info(Some(5000), Some(9000), 0), 44100, 1000, 0);
+        assert_eq!(range.start_sample_frame, 1000);
+        assert_eq!(range.end_sample_frame, 1000);
+        assert_eq!(range.num_loops, 1);
+        assert_eq!(range.total_sample_frames(), 0);
+    }
+
+    #[test]
+    fn envelope_interpolates_linearly() {
+        let envelope = SoundEnvelope::new(&[point(100, 0.0, 1.0), point(300, 1.0, 0.0)]);
+        assert_eq!(envelope.volume_at(0), [0.0, 1.0]);
+        assert_eq!(envelope.volume_at(100), [0.0, 1.0]);
+        assert_eq!(envelope.volume_at(150), [0.25, 0.75]);
+        assert_eq!(envelope.volume_at(200), [0.5, 0.5]);
+        assert_eq!(envelope.volume_at(300), [1.0, 0.0]);
+        assert_eq!(envelope.volume_at(100_000), [1.0, 0.0]);
+    }
+
+    #[test]
+    fn envelope_steps_at_repeated_points() {
+        let envelope = SoundEnvelope::new(&[
+            point(0, 1.0, 1.0),
+            point(100, 1.0, 1.0),
+            point(100, 0.0, 0.0),
+            point(200, 0.5, 0.5),
+        ]);
+        assert_eq!(envelope.volume_at(99), [1.0, 1.0]);
+        assert_eq!(envelope.volume_at(100), [0.0, 0.0]);
+        assert_eq!(envelope.volume_at(150), [0.25, 0.25]);
+    }
+
+    #[test]
+    fn envelope_uses_44100hz_timeline() {
+        let envelope = SoundEnvelope::new(&[point(0, 0.0, 0.0), point(44100, 1.0, 1.0)]);
+        assert_eq!(envelope.volume_after(11025, 22050), [0.5, 0.5]);
+        assert_eq!(envelope.volume_after(22050, 44100), [0.5, 0.5]);
+    }
+
+    #[test]
+    fn empty_envelope_is_full_volume() {
+        let envelope = SoundEnvelope::new(&[]);
+        assert_eq!(envelope.volume_at(1234), [1.0, 1.0]);
+    }
+}
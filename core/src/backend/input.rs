@@ -19,15 +19,33 @@ pub trait InputBackend: Downcast {
 
     /// Set the clipboard to the given content
     fn set_clipboard_content(&mut self, content: String);
+
+    /// The longest time between two clicks for them to make a double click,
+    /// in milliseconds.
+    fn double_click_threshold_ms(&self) -> u32 {
+        500
+    }
 }
 impl_downcast!(InputBackend);
 
 /// Input backend that does nothing
-pub struct NullInputBackend {}
+///
+/// The last mouse cursor set by the player is kept so that it can be
+/// checked.
+pub struct NullInputBackend {
+    mouse_cursor: MouseCursor,
+}
 
 impl NullInputBackend {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            mouse_cursor: MouseCursor::Arrow,
+        }
+    }
+
+    /// The mouse cursor that the player last asked for.
+    pub fn mouse_cursor(&self) -> MouseCursor {
+        self.mouse_cursor
     }
 }
 
@@ -52,7 +70,9 @@ impl InputBackend for NullInputBackend {
 
     fn show_mouse(&mut self) {}
 
-    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        self.mouse_cursor = cursor;
+    }
 
     fn set_clipboard_content(&mut self, _content: String) {}
 }
@@ -83,3 +103,17 @@ pub enum MouseCursor {
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
 }
+
+/// A button on the mouse.
+/// Communicated from the input backend to the core in mouse button events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseButton {
+    /// The primary button, usually the left one.
+    Left,
+
+    /// The secondary button, which opens the context menu.
+    Right,
+
+    /// The middle button, usually the mouse wheel.
+    Middle,
+}
@@ -3,12 +3,14 @@
 use crate::loader::Error;
 use indexmap::IndexMap;
 use std::borrow::Cow;
-use std::collections::VecDeque;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::ptr::null;
+use std::rc::Rc;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use std::time::Duration;
@@ -54,6 +56,28 @@ pub fn url_from_relative_path<P: AsRef<Path>>(
     Err(ParseError::RelativeUrlWithoutBase)
 }
 
+/// Attempt to convert a `file:///` URL into a filesystem path.
+///
+/// This is the desktop version of this function. On non-Unix, non-Windows,
+/// non-Redox environments, this function always yields `None`.
+#[cfg(any(unix, windows, target_os = "redox"))]
+pub fn path_from_file_url(url: &str) -> Option<PathBuf> {
+    match Url::parse(url) {
+        Ok(url) if url.scheme() == "file" => url.to_file_path().ok(),
+        _ => None,
+    }
+}
+
+/// Attempt to convert a `file:///` URL into a filesystem path.
+///
+/// This is the web version of this function, which always yields `None`. On
+/// Unix, Windows, or Redox, this function actually carries out the above
+/// instructions.
+#[cfg(not(any(unix, windows, target_os = "redox")))]
+pub fn path_from_file_url(_url: &str) -> Option<PathBuf> {
+    None
+}
+
 /// Attempt to convert a relative URL into an absolute URL, using the base URL
 /// if necessary.
 ///
@@ -69,7 +93,7 @@ pub fn url_from_relative_url(base: &str, relative: &str) -> Result<Url, ParseErr
 }
 
 /// Enumerates all possible navigation methods.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum NavigationMethod {
     /// Indicates that navigation should generate a GET request.
     GET,
@@ -107,6 +131,9 @@ pub struct RequestOptions {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// Additional HTTP headers to send with the request, as name/value pairs.
+    headers: Vec<(String, String)>,
 }
 
 impl RequestOptions {
@@ -115,6 +142,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::GET,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -123,9 +151,16 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::POST,
             body,
+            headers: Vec::new(),
         }
     }
 
+    /// Add HTTP headers to be sent with this request.
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
     /// Retrieve the navigation method for this request.
     pub fn method(&self) -> NavigationMethod {
         self.method
@@ -135,12 +170,152 @@ impl RequestOptions {
     pub fn body(&self) -> &Option<(Vec<u8>, String)> {
         &self.body
     }
+
+    /// Retrieve the additional HTTP headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+}
+
+/// The response to a fetch, along with its HTTP metadata.
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// The HTTP status code of the response.
+    pub status: u16,
+
+    /// The HTTP headers of the response, as name/value pairs.
+    pub headers: Vec<(String, String)>,
+
+    /// The contents of the response body.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Construct a `200 OK` response with no headers.
+    pub fn ok(body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    /// Construct an empty response with the given status code.
+    pub fn with_status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Add HTTP headers to this response.
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Whether the status code of this response indicates success.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
 }
 
 /// Type alias for pinned, boxed, and owned futures that output a falliable
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// The body of a response, read in chunks as it arrives.
+pub trait ResponseBody {
+    /// The length of the body in bytes, if it is known before all of it has
+    /// arrived.
+    fn total_len(&self) -> Option<u64>;
+
+    /// Wait for the next chunk of the body to arrive.
+    ///
+    /// Yields `None` once the whole body has been read.
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error>;
+}
+
+/// A response body that arrives all at once, at the end of a fetch.
+pub struct FetchedBody {
+    /// The fetch of the body, until it has been read.
+    fetch: Option<OwnedFuture<Vec<u8>, Error>>,
+}
+
+impl FetchedBody {
+    pub fn new(fetch: OwnedFuture<Vec<u8>, Error>) -> Self {
+        Self { fetch: Some(fetch) }
+    }
+}
+
+impl ResponseBody for FetchedBody {
+    fn total_len(&self) -> Option<u64> {
+        None
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        match self.fetch.take() {
+            Some(fetch) => Box::pin(async move { Ok(Some(fetch.await?)) }),
+            None => Box::pin(async { Ok(None) }),
+        }
+    }
+}
+
+/// A response body that has already arrived, and is read in chunks of a fixed
+/// size.
+///
+/// Each chunk arrives the next time the executor polls its reader after the
+/// previous chunk, to simulate a slow download.
+pub struct ChunkedBody {
+    /// The chunks of the body that have not been read yet.
+    chunks: VecDeque<Vec<u8>>,
+
+    /// The length of the whole body.
+    total_len: u64,
+}
+
+impl ChunkedBody {
+    pub fn new(body: &[u8], chunk_size: usize) -> Self {
+        Self {
+            chunks: body.chunks(chunk_size.max(1)).map(<[u8]>::to_vec).collect(),
+            total_len: body.len() as u64,
+        }
+    }
+}
+
+impl ResponseBody for ChunkedBody {
+    fn total_len(&self) -> Option<u64> {
+        Some(self.total_len)
+    }
+
+    fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
+        let chunk = self.chunks.pop_front();
+        Box::pin(async move {
+            YieldOnce(false).await;
+            Ok(chunk)
+        })
+    }
+}
+
+/// A future that is pending the first time it is polled, giving other
+/// futures a chance to run before it completes.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -175,6 +350,35 @@ pub trait NavigatorBackend {
     /// Fetch data at a given URL and return it some time in the future.
     fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error>;
 
+    /// Fetch data at a given URL, along with the HTTP status and headers of
+    /// the response.
+    ///
+    /// Unlike `fetch`, responses with an unsuccessful status are not errors.
+    /// Backends that can't inspect responses report every successful fetch
+    /// as a `200 OK` response with no headers.
+    fn fetch_response(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Response, Error> {
+        let fetch = self.fetch(url, request_options);
+        Box::pin(async move { Ok(Response::ok(fetch.await?)) })
+    }
+
+    /// Fetch data at a given URL, so that its body can be read as it
+    /// arrives.
+    ///
+    /// Backends that can't stream responses yield the whole body as a single
+    /// chunk, once it has arrived.
+    fn fetch_body(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Box<dyn ResponseBody>, Error> {
+        let fetch = self.fetch(url, request_options);
+        Box::pin(async move { Ok(Box::new(FetchedBody::new(fetch)) as Box<dyn ResponseBody>) })
+    }
+
     /// Get the amount of time since the SWF was launched.
     /// Used by the `getTimer` ActionScript call.
     fn time_since_launch(&mut self) -> Duration;
@@ -300,16 +504,50 @@ impl NullExecutor {
     }
 }
 
+/// A fetch made through a `NullNavigatorBackend`.
+#[derive(Clone, Debug)]
+pub struct FetchRequest {
+    /// The URL that was fetched, as given to the backend.
+    pub url: String,
+
+    /// The HTTP method of the request.
+    pub method: NavigationMethod,
+
+    /// The additional HTTP headers of the request.
+    pub headers: Vec<(String, String)>,
+
+    /// The body of the request and its mime type, if it has one.
+    pub body: Option<(Vec<u8>, String)>,
+}
+
 /// A null implementation for platforms that do not live in a web browser.
 ///
 /// The NullNavigatorBackend includes a trivial executor that holds owned
 /// futures and runs them to completion, blockingly.
+///
+/// Fetches read files relative to the base path, unless a canned response has
+/// been provided for the URL or the URL echoes requests. Every fetch is
+/// recorded, so that tests can inspect the requests a movie made. Response
+/// bodies can be delivered in chunks, to simulate a slow download.
 pub struct NullNavigatorBackend {
     /// The channel upon which all spawned futures will be sent.
     channel: Option<Sender<OwnedFuture<(), Error>>>,
 
     /// The base path for all relative fetches.
     relative_base_path: PathBuf,
+
+    /// Every fetch made through this backend, in order.
+    requests: Rc<RefCell<Vec<FetchRequest>>>,
+
+    /// Responses served in place of files, keyed by URL.
+    responses: HashMap<String, Response>,
+
+    /// URLs whose responses echo back the body of the request.
+    echo_urls: HashSet<String>,
+
+    /// The size of the chunks that response bodies are delivered in, if they
+    /// are not delivered all at once.
+    chunk_size: Option<usize>,
 }
 
 impl NullNavigatorBackend {
@@ -319,6 +557,10 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: None,
             relative_base_path: PathBuf::new(),
+            requests: Rc::new(RefCell::new(Vec::new())),
+            responses: HashMap::new(),
+            echo_urls: HashSet::new(),
+            chunk_size: None,
         }
     }
 
@@ -334,8 +576,40 @@ impl NullNavigatorBackend {
         NullNavigatorBackend {
             channel: Some(channel),
             relative_base_path,
+            requests: Rc::new(RefCell::new(Vec::new())),
+            responses: HashMap::new(),
+            echo_urls: HashSet::new(),
+            chunk_size: None,
         }
     }
+
+    /// Serve a canned response whenever the given URL is fetched, instead of
+    /// reading it from disk.
+    pub fn with_response(mut self, url: &str, response: Response) -> Self {
+        self.responses.insert(url.to_string(), response);
+        self
+    }
+
+    /// Respond to every fetch of the given URL with the body of its request,
+    /// like a server that echoes what is posted to it.
+    pub fn with_echo(mut self, url: &str) -> Self {
+        self.echo_urls.insert(url.to_string());
+        self
+    }
+
+    /// Deliver response bodies in chunks of the given size, for movies that
+    /// read their bodies as they arrive.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Get a shared handle to the list of fetches made through this backend.
+    ///
+    /// The handle stays valid after the backend is given to a player.
+    pub fn requests(&self) -> Rc<RefCell<Vec<FetchRequest>>> {
+        self.requests.clone()
+    }
 }
 
 impl Default for NullNavigatorBackend {
@@ -353,11 +627,72 @@ impl NavigatorBackend for NullNavigatorBackend {
     ) {
     }
 
-    fn fetch(&self, url: &str, _opts: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
-        let mut path = self.relative_base_path.clone();
-        path.push(url);
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        let response = self.fetch_response(url, options);
+        let url = url.to_string();
 
-        Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
+        Box::pin(async move {
+            let response = response.await?;
+            if response.is_success() {
+                Ok(response.body)
+            } else {
+                Err(Error::HttpStatus(url, response.status))
+            }
+        })
+    }
+
+    fn fetch_response(&self, url: &str, options: RequestOptions) -> OwnedFuture<Response, Error> {
+        self.requests.borrow_mut().push(FetchRequest {
+            url: url.to_string(),
+            method: options.method(),
+            headers: options.headers().to_vec(),
+            body: options.body().clone(),
+        });
+
+        if let Some(response) = self.responses.get(url) {
+            let response = response.clone();
+            return Box::pin(async move { Ok(response) });
+        }
+
+        if self.echo_urls.contains(url) {
+            let body = options
+                .body()
+                .as_ref()
+                .map(|(body, _mime)| body.clone())
+                .unwrap_or_default();
+            return Box::pin(async move { Ok(Response::ok(body)) });
+        }
+
+        // Absolute `file:///` URLs, such as those resolved against the URL of
+        // a movie, are read as they are.
+        let path = path_from_file_url(url).unwrap_or_else(|| {
+            let mut path = self.relative_base_path.clone();
+            path.push(url);
+            path
+        });
+
+        Box::pin(async move {
+            fs::read(path)
+                .map(Response::ok)
+                .map_err(Error::NetworkError)
+        })
+    }
+
+    fn fetch_body(
+        &self,
+        url: &str,
+        options: RequestOptions,
+    ) -> OwnedFuture<Box<dyn ResponseBody>, Error> {
+        let fetch = self.fetch(url, options);
+        let chunk_size = self.chunk_size;
+
+        Box::pin(async move {
+            let body: Box<dyn ResponseBody> = match chunk_size {
+                Some(chunk_size) => Box::new(ChunkedBody::new(&fetch.await?, chunk_size)),
+                None => Box::new(FetchedBody::new(fetch)),
+            };
+            Ok(body)
+        })
     }
 
     fn time_since_launch(&mut self) -> Duration {
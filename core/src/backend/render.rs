@@ -32,9 +32,36 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
+    /// Register a bitmap from premultiplied RGBA pixels, such as a decoded
+    /// frame of video.
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error>;
+
+    /// Replace the pixels of a bitmap registered with `register_bitmap_raw`.
+    ///
+    /// The new pixels may be of a different size than the old ones.
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error>;
+
     fn begin_frame(&mut self, clear: Color);
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform);
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool);
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
+
+    /// Renders a glyph of a text field that uses advanced anti-aliasing.
+    ///
+    /// Backends that can't hint glyphs draw them as ordinary shapes.
+    fn render_glyph(&mut self, shape: ShapeHandle, transform: &Transform, _hinting: &GlyphHinting) {
+        self.render_shape(shape, transform)
+    }
     fn draw_rect(&mut self, color: Color, matrix: &Matrix);
     fn end_frame(&mut self);
     fn draw_letterbox(&mut self, letterbox: Letterbox);
@@ -42,6 +69,42 @@ pub trait RenderBackend: Downcast {
     fn activate_mask(&mut self);
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
+
+    /// Begins a group of draws that is composited onto the content below it
+    /// using the given blend mode.
+    ///
+    /// Groups may nest, and each call is matched by a call to `pop_blend_mode`.
+    /// `BlendMode::Alpha` and `BlendMode::Erase` only affect the content of an
+    /// enclosing `BlendMode::Layer` group, and are drawn normally elsewhere.
+    fn push_blend_mode(&mut self, _blend: swf::BlendMode) {}
+
+    /// Ends the current blend group, compositing it onto the content below it.
+    fn pop_blend_mode(&mut self) {}
+
+    /// Begins a group of draws that is rendered offscreen, filtered with the
+    /// given filters in order, and then composited onto the content below it.
+    ///
+    /// `bounds` is the area of the viewport that the filtered group may cover,
+    /// in twips. Filter sizes are in viewport pixels. Each call is matched by
+    /// a call to `pop_filters`. Filters that the backend can't apply are
+    /// skipped.
+    fn push_filters(&mut self, _filters: &[swf::Filter], _bounds: &swf::Rectangle) {}
+
+    /// Ends the current filter group, compositing it onto the content below it.
+    fn pop_filters(&mut self) {}
+
+    /// Sets the rendering quality of the stage.
+    ///
+    /// At `StageQuality::Low`, bitmaps are never smoothed, regardless of the
+    /// smoothing flags of their fills.
+    fn set_quality(&mut self, _quality: StageQuality) {}
+
+    /// Tells the backend which area of the viewport has changed since the last
+    /// frame, just before the frame begins.
+    ///
+    /// `None` means that anywhere may have changed. Backends may use this to
+    /// limit what they redraw, but are free to redraw everything.
+    fn set_dirty_region(&mut self, _region: Option<&swf::Rectangle>) {}
 }
 impl_downcast!(RenderBackend);
 
@@ -61,6 +124,59 @@ pub struct BitmapInfo {
     pub height: u16,
 }
 
+/// How a glyph of a text field that uses advanced anti-aliasing should be
+/// fitted to the pixel grid.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GlyphHinting {
+    pub grid_fit: swf::TextGridFit,
+
+    /// The stroke thickness adjustment, from -200 to 200.
+    pub thickness: f32,
+
+    /// The edge sharpness adjustment, from -400 to 400.
+    pub sharpness: f32,
+
+    /// The alignment zone of the glyph, if its font has one.
+    pub align_zone: Option<AlignZone>,
+}
+
+/// The area of a glyph that should be aligned to the pixel grid, in EM-square
+/// coordinates.
+///
+/// A zone with a width or height of 0 only aligns its left or bottom edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlignZone {
+    pub left: f32,
+    pub width: f32,
+    pub bottom: f32,
+    pub height: f32,
+}
+
+impl AlignZone {
+    pub fn from_swf_zone(zone: &swf::FontAlignZone) -> Self {
+        Self {
+            left: f16_to_f32(zone.left),
+            width: f16_to_f32(zone.width),
+            bottom: f16_to_f32(zone.bottom),
+            height: f16_to_f32(zone.height),
+        }
+    }
+}
+
+/// Decode the bits of a half-precision float, as stored in alignment zones.
+fn f16_to_f32(bits: i16) -> f32 {
+    let bits = bits as u16;
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Letterbox {
     None,
@@ -68,6 +184,165 @@ pub enum Letterbox {
     Pillarbox(f32),
 }
 
+/// The rendering quality of the stage, as set by `_quality` or
+/// `Stage.quality`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StageQuality {
+    Low,
+    Medium,
+    High,
+    Best,
+}
+
+impl StageQuality {
+    /// Parses a quality name, such as `"LOW"` or `"high"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "low" => Some(StageQuality::Low),
+            "medium" => Some(StageQuality::Medium),
+            "high" => Some(StageQuality::High),
+            "best" => Some(StageQuality::Best),
+            _ => None,
+        }
+    }
+
+    /// The name of this quality, as returned by `_quality`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+        }
+    }
+
+    /// Whether bitmaps that request smoothing may be smoothed at this quality.
+    pub fn allows_bitmap_smoothing(self) -> bool {
+        self != StageQuality::Low
+    }
+}
+
+impl Default for StageQuality {
+    fn default() -> Self {
+        StageQuality::High
+    }
+}
+
+/// The radius in pixels of one pass of a box blur of the given size.
+///
+/// Blur sizes are rounded down to an even number of pixels, so a blur of
+/// 5 pixels averages each pixel with the 2 pixels on either side of it.
+pub fn blur_radius(blur: f64) -> u32 {
+    (blur.max(0.0) / 2.0).floor() as u32
+}
+
+/// Returns how far the given filters may draw outside of the bounds of an
+/// object, horizontally and vertically, in pixels.
+pub fn filter_margins(filters: &[swf::Filter]) -> (f64, f64) {
+    use swf::Filter;
+
+    filters
+        .iter()
+        .fold((0.0, 0.0), |(margin_x, margin_y), filter| {
+            let (blur_x, blur_y, num_passes, distance, angle, is_inner) = match filter {
+                Filter::BlurFilter(blur) => {
+                    (blur.blur_x, blur.blur_y, blur.num_passes, 0.0, 0.0, false)
+                }
+                Filter::DropShadowFilter(shadow) => (
+                    shadow.blur_x,
+                    shadow.blur_y,
+                    shadow.num_passes,
+                    shadow.distance,
+                    shadow.angle,
+                    shadow.is_inner,
+                ),
+                Filter::GlowFilter(glow) => (
+                    glow.blur_x,
+                    glow.blur_y,
+                    glow.num_passes,
+                    0.0,
+                    0.0,
+                    glow.is_inner,
+                ),
+                Filter::GradientGlowFilter(glow) => (
+                    glow.blur_x,
+                    glow.blur_y,
+                    glow.num_passes,
+                    glow.distance,
+                    glow.angle,
+                    glow.is_inner,
+                ),
+                Filter::BevelFilter(bevel) => (
+                    bevel.blur_x,
+                    bevel.blur_y,
+                    bevel.num_passes,
+                    bevel.distance,
+                    bevel.angle,
+                    bevel.is_inner,
+                ),
+                Filter::GradientBevelFilter(bevel) => (
+                    bevel.blur_x,
+                    bevel.blur_y,
+                    bevel.num_passes,
+                    bevel.distance,
+                    bevel.angle,
+                    bevel.is_inner,
+                ),
+                Filter::ConvolutionFilter(_) | Filter::ColorMatrixFilter(_) => {
+                    return (margin_x, margin_y)
+                }
+            };
+            // Inner filters only draw inside of the object, but blurring them
+            // still reads the pixels around it.
+            let distance = if is_inner { 0.0 } else { distance };
+            let passes = f64::from(num_passes);
+            (
+                margin_x + f64::from(blur_radius(blur_x)) * passes + (distance * angle.cos()).abs(),
+                margin_y + f64::from(blur_radius(blur_y)) * passes + (distance * angle.sin()).abs(),
+            )
+        })
+}
+
+/// Returns a copy of a filter with its sizes and distances multiplied by the
+/// given scale, such as to convert them from stage pixels to viewport pixels.
+pub fn scale_filter(filter: &swf::Filter, scale: f64) -> swf::Filter {
+    use swf::Filter;
+
+    let mut filter = filter.clone();
+    match &mut filter {
+        Filter::BlurFilter(blur) => {
+            blur.blur_x *= scale;
+            blur.blur_y *= scale;
+        }
+        Filter::DropShadowFilter(shadow) => {
+            shadow.blur_x *= scale;
+            shadow.blur_y *= scale;
+            shadow.distance *= scale;
+        }
+        Filter::GlowFilter(glow) => {
+            glow.blur_x *= scale;
+            glow.blur_y *= scale;
+        }
+        Filter::GradientGlowFilter(glow) => {
+            glow.blur_x *= scale;
+            glow.blur_y *= scale;
+            glow.distance *= scale;
+        }
+        Filter::BevelFilter(bevel) => {
+            bevel.blur_x *= scale;
+            bevel.blur_y *= scale;
+            bevel.distance *= scale;
+        }
+        Filter::GradientBevelFilter(bevel) => {
+            bevel.blur_x *= scale;
+            bevel.blur_y *= scale;
+            bevel.distance *= scale;
+        }
+        Filter::ConvolutionFilter(_) | Filter::ColorMatrixFilter(_) => (),
+    }
+    filter
+}
+
 pub struct NullRenderer;
 
 impl NullRenderer {
@@ -136,9 +411,26 @@ impl RenderBackend for NullRenderer {
             height: 0,
         })
     }
+    fn register_bitmap_raw(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(0))
+    }
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(bitmap)
+    }
     fn begin_frame(&mut self, _clear: Color) {}
     fn end_frame(&mut self) {}
-    fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform) {}
+    fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform, _smoothing: bool) {}
     fn render_shape(&mut self, _shape: ShapeHandle, _transform: &Transform) {}
     fn draw_rect(&mut self, _color: Color, _matrix: &Matrix) {}
     fn draw_letterbox(&mut self, _letterbox: Letterbox) {}
@@ -151,6 +443,8 @@ impl RenderBackend for NullRenderer {
 /// The format of image data in a DefineBitsJpeg2/3 tag.
 /// Generally this will be JPEG, but according to SWF19, these tags can also contain PNG and GIF data.
 /// SWF19 pp.138-139
+///
+/// Images loaded with `loadMovie` or `Loader.load` are in the same formats.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum JpegTagFormat {
     Jpeg,
@@ -175,6 +469,20 @@ pub enum BitmapFormat {
     Rgba(Vec<u8>),
 }
 
+impl BitmapFormat {
+    /// Converts the image data to RGBA, such as for `register_bitmap_raw`.
+    /// RGB images are made opaque.
+    pub fn into_rgba(self) -> Vec<u8> {
+        match self {
+            BitmapFormat::Rgb(rgb) => rgb
+                .chunks_exact(3)
+                .flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            BitmapFormat::Rgba(rgba) => rgba,
+        }
+    }
+}
+
 /// Determines the format of the image data in `data` from a DefineBitsJPEG2/3 tag.
 pub fn determine_jpeg_tag_format(data: &[u8]) -> JpegTagFormat {
     match data {
@@ -182,6 +490,8 @@ pub fn determine_jpeg_tag_format(data: &[u8]) -> JpegTagFormat {
         [0xff, 0xd9, 0xff, 0xd8, ..] => JpegTagFormat::Jpeg, // erroneous header in SWF
         [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, ..] => JpegTagFormat::Png,
         [0x47, 0x49, 0x46, 0x38, 0x39, 0x61, ..] => JpegTagFormat::Gif,
+        // Older GIF87a data is accepted as well.
+        [0x47, 0x49, 0x46, 0x38, 0x37, 0x61, ..] => JpegTagFormat::Gif,
         _ => JpegTagFormat::Unknown,
     }
 }
@@ -265,19 +575,35 @@ pub fn decode_jpeg(
     let metadata = decoder.info().ok_or("Unable to get image info")?;
     let decoded_data = decoder.decode()?;
 
+    // Expand grayscale JPEGs to RGB.
+    let decoded_data = match metadata.pixel_format {
+        jpeg_decoder::PixelFormat::RGB24 => decoded_data,
+        jpeg_decoder::PixelFormat::L8 => decoded_data
+            .into_iter()
+            .flat_map(|luma| std::iter::repeat(luma).take(3))
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => return Err("CMYK JPEGs are not supported".into()),
+    };
+
     // Decompress the alpha data (DEFLATE compression).
-    if let Some(alpha_data) = alpha_data {
+    // A DefineBitsJPEG3 without any alpha data is opaque.
+    if let Some(alpha_data) = alpha_data.filter(|alpha_data| !alpha_data.is_empty()) {
         let alpha_data = decompress_zlib(alpha_data)?;
 
-        if alpha_data.len() == decoded_data.len() / 3 {
+        // Any data past the end of the alpha plane is ignored.
+        if alpha_data.len() >= decoded_data.len() / 3 {
             let mut rgba = Vec::with_capacity((decoded_data.len() / 3) * 4);
             let mut i = 0;
             let mut a = 0;
             while i < decoded_data.len() {
-                rgba.push(decoded_data[i]);
-                rgba.push(decoded_data[i + 1]);
-                rgba.push(decoded_data[i + 2]);
-                rgba.push(alpha_data[a]);
+                // The JPEG data should already be premultiplied, but some SWFs
+                // leave color in transparent pixels. Flash Player clamps the
+                // color to the alpha value, so do the same.
+                let alpha = alpha_data[a];
+                rgba.push(decoded_data[i].min(alpha));
+                rgba.push(decoded_data[i + 1].min(alpha));
+                rgba.push(decoded_data[i + 2].min(alpha));
+                rgba.push(alpha);
                 i += 3;
                 a += 1;
             }
@@ -314,18 +640,41 @@ pub fn decode_define_bits_lossless(
     // Decompress the image data (DEFLATE compression).
     let mut decoded_data = decompress_zlib(&swf_tag.data[..])?;
 
+    // Make sure there is enough data for every pixel, so that a truncated
+    // image can't cause out of bounds reads below.
+    // Each row of pixels is padded to a multiple of 32 bits.
+    let width = swf_tag.width as usize;
+    let height = swf_tag.height as usize;
+    let expected_len = match swf_tag.format {
+        swf::BitmapFormat::ColorMap8 => {
+            let palette_entry_len = if swf_tag.version == 1 { 3 } else { 4 };
+            (swf_tag.num_colors as usize + 1) * palette_entry_len + ((width + 3) & !3) * height
+        }
+        swf::BitmapFormat::Rgb15 => ((width * 2 + 3) & !3) * height,
+        swf::BitmapFormat::Rgb32 => width * height * 4,
+    };
+    if decoded_data.len() < expected_len {
+        return Err("Not enough image data in DefineBitsLossless".into());
+    }
+    decoded_data.truncate(expected_len);
+
     // Swizzle/de-palettize the bitmap.
     let out_data = match (swf_tag.version, swf_tag.format) {
         (1, swf::BitmapFormat::Rgb15) => {
-            let mut out_data: Vec<u8> = Vec::with_capacity(decoded_data.len() * 2);
+            let mut out_data: Vec<u8> = Vec::with_capacity(width * height * 4);
             let mut i = 0;
-            while i < decoded_data.len() {
-                let compressed: u16 = ((decoded_data[i] as u16) << 8) | decoded_data[i + 1] as u16;
-                out_data.push(rgb5_component(compressed, 10));
-                out_data.push(rgb5_component(compressed, 5));
-                out_data.push(rgb5_component(compressed, 0));
-                out_data.push(0xff);
-                i += 2;
+            let padded_width = (swf_tag.width + 0b1) & !0b1;
+            for _ in 0..swf_tag.height {
+                for _ in 0..swf_tag.width {
+                    let compressed: u16 =
+                        ((decoded_data[i] as u16) << 8) | decoded_data[i + 1] as u16;
+                    out_data.push(rgb5_component(compressed, 10));
+                    out_data.push(rgb5_component(compressed, 5));
+                    out_data.push(rgb5_component(compressed, 0));
+                    out_data.push(0xff);
+                    i += 2;
+                }
+                i += ((padded_width - swf_tag.width) * 2) as usize;
             }
             out_data
         }
@@ -424,7 +773,14 @@ pub fn decode_define_bits_lossless(
             }
             out_data
         }
-        _ => unimplemented!("{:?} {:?}", swf_tag.version, swf_tag.format),
+        (version, format) => {
+            return Err(format!(
+                "Unsupported DefineBitsLossless{} format {:?}",
+                if version == 1 { "" } else { "2" },
+                format
+            )
+            .into())
+        }
     };
 
     Ok(Bitmap {
@@ -434,45 +790,91 @@ pub fn decode_define_bits_lossless(
     })
 }
 
-/// Decodes the bitmap data in DefineBitsLossless tag into RGBA.
-/// DefineBitsLossless is Zlib encoded pixel data (similar to PNG), possibly
-/// palletized.
+/// Decodes PNG data from a DefineBitsJPEG2/3 tag.
+/// Palettized and interlaced PNGs are supported.
+/// The decoded bitmap will have pre-multiplied alpha.
 pub fn decode_png(data: &[u8]) -> Result<Bitmap, Error> {
     use png::{ColorType, Transformations};
 
     let mut decoder = png::Decoder::new(data);
-    // EXPAND expands palettized types to RGB.
-    decoder.set_transformations(Transformations::EXPAND);
+    // EXPAND expands palettized types to RGB, low bit depths to 8 bits, and
+    // transparency chunks to an alpha channel.
+    decoder.set_transformations(Transformations::EXPAND | Transformations::STRIP_16);
     let (info, mut reader) = decoder.read_info()?;
 
-    let mut data = vec![0; info.buffer_size()];
+    let mut data = vec![0; reader.output_buffer_size()];
     reader.next_frame(&mut data)?;
 
+    let data = match reader.output_color_type().0 {
+        ColorType::RGB => BitmapFormat::Rgb(data),
+        ColorType::RGBA => {
+            premultiply_alpha_rgba(&mut data);
+            BitmapFormat::Rgba(data)
+        }
+        ColorType::Grayscale => BitmapFormat::Rgb(
+            data.into_iter()
+                .flat_map(|luma| std::iter::repeat(luma).take(3))
+                .collect(),
+        ),
+        ColorType::GrayscaleAlpha => {
+            let mut rgba: Vec<u8> = data
+                .chunks_exact(2)
+                .flat_map(|la| vec![la[0], la[0], la[0], la[1]])
+                .collect();
+            premultiply_alpha_rgba(&mut rgba);
+            BitmapFormat::Rgba(rgba)
+        }
+        ColorType::Indexed => return Err("Unexpanded palettized PNG".into()),
+    };
+
     Ok(Bitmap {
         width: info.width,
         height: info.height,
-        data: if info.color_type == ColorType::RGBA {
-            BitmapFormat::Rgba(data)
-        } else {
-            // EXPAND expands other types to RGB.
-            BitmapFormat::Rgb(data)
-        },
+        data,
     })
 }
 
-/// Decodes the bitmap data in DefineBitsLossless tag into RGBA.
-/// DefineBitsLossless is Zlib encoded pixel data (similar to PNG), possibly
-/// palletized.
+/// Decodes the first frame of GIF data from a DefineBitsJPEG2/3 tag.
+/// Like Flash Player, any further frames of an animated GIF are ignored.
+/// The decoded bitmap will have pre-multiplied alpha.
 pub fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     let mut decode_options = gif::DecodeOptions::new();
     decode_options.set_color_output(gif::ColorOutput::RGBA);
     let mut reader = decode_options.read_info(data)?;
+    let (width, height) = (u32::from(reader.width()), u32::from(reader.height()));
     let frame = reader.read_next_frame()?.ok_or("No frames in GIF")?;
 
+    // The first frame may only cover part of the image; the rest of the
+    // image is transparent.
+    let mut rgba = vec![0; width as usize * height as usize * 4];
+    let (left, top) = (usize::from(frame.left), usize::from(frame.top));
+    let frame_width = usize::from(frame.width);
+    for (y, row) in frame.buffer.chunks_exact(frame_width * 4).enumerate() {
+        let y = top + y;
+        if y >= height as usize || left >= width as usize {
+            break;
+        }
+        let len = row.len().min((width as usize - left) * 4);
+        let start = (y * width as usize + left) * 4;
+        rgba[start..start + len].copy_from_slice(&row[..len]);
+    }
+
+    premultiply_alpha_rgba(&mut rgba);
     Ok(Bitmap {
-        width: frame.width.into(),
-        height: frame.height.into(),
-        data: BitmapFormat::Rgba(frame.buffer.to_vec()),
+        width,
+        height,
+        data: BitmapFormat::Rgba(rgba),
+    })
+}
+
+/// Converts standard RGBA to RGBA premultiplied alpha, the format used by
+/// images in SWFs.
+pub fn premultiply_alpha_rgba(rgba: &mut [u8]) {
+    rgba.chunks_exact_mut(4).for_each(|rgba| {
+        let a = u16::from(rgba[3]);
+        for c in &mut rgba[..3] {
+            *c = ((u16::from(*c) * a + 127) / 255) as u8;
+        }
     })
 }
 
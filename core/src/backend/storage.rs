@@ -11,17 +11,36 @@ pub trait StorageBackend: Downcast {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// Retrieve binary data stored with `put_bytes`.
+    ///
+    /// By default, each byte is stored as a single character of a string.
+    fn get_bytes(&self, name: &str) -> Option<Vec<u8>> {
+        self.get_string(name)
+            .map(|s| s.chars().map(|c| c as u8).collect())
+    }
+
+    fn put_bytes(&mut self, name: &str, value: &[u8]) -> bool {
+        self.put_string(name, value.iter().map(|b| *b as char).collect())
+    }
+
+    /// The most bytes that may be stored under a single name, if limited.
+    fn max_size(&self) -> Option<usize> {
+        None
+    }
 }
 impl_downcast!(StorageBackend);
 
 pub struct MemoryStorageBackend {
     pub map: HashMap<String, String>,
+    pub max_size: Option<usize>,
 }
 
 impl Default for MemoryStorageBackend {
     fn default() -> Self {
         MemoryStorageBackend {
             map: HashMap::new(),
+            max_size: None,
         }
     }
 }
@@ -39,4 +58,8 @@ impl StorageBackend for MemoryStorageBackend {
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn max_size(&self) -> Option<usize> {
+        self.max_size
+    }
 }
@@ -0,0 +1,275 @@
+use chrono::{DateTime, Utc};
+use downcast_rs::Downcast;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+type Error = Box<dyn std::error::Error>;
+
+/// A filter restricting the files that a file dialog offers to the user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The name of this filter, as shown to the user (e.g. `Images`).
+    pub description: String,
+
+    /// A semicolon-separated list of patterns, such as `*.png;*.jpg`.
+    pub extensions: String,
+}
+
+impl FileFilter {
+    /// List the patterns of this filter individually.
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.extensions
+            .split(';')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+    }
+}
+
+/// A file that the user chose in a file dialog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedFile {
+    /// Where the file is located.
+    pub path: PathBuf,
+
+    /// The name of the file, without any directories.
+    pub name: String,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// When the file was last modified, if known.
+    pub modification_date: Option<DateTime<Utc>>,
+}
+
+impl SelectedFile {
+    /// Describe a file on the local filesystem.
+    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let metadata = std::fs::metadata(&path)?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name,
+            size: metadata.len(),
+            modification_date: metadata.modified().ok().map(DateTime::from),
+            path,
+        })
+    }
+
+    /// The extension of this file, including the leading dot, as reported by
+    /// `FileReference.type`.
+    pub fn file_type(&self) -> Option<String> {
+        self.path
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+    }
+}
+
+/// An item of the context menu that is shown when the movie is right
+/// clicked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextMenuItem {
+    /// The text of this item.
+    pub caption: String,
+
+    /// Whether this item can be chosen.
+    pub enabled: bool,
+
+    /// Whether a separator is drawn above this item.
+    pub separator_before: bool,
+
+    /// Whether this item is drawn with a check mark.
+    pub checked: bool,
+}
+
+/// User interface dialogs that movies can ask the player to display, and
+/// other access to the user's system.
+///
+/// Dialogs are modal: each method returns once the user has closed the
+/// dialog.
+pub trait UiBackend: Downcast {
+    /// Ask the user to choose a file to open.
+    ///
+    /// Returns `None` if the user cancelled the dialog.
+    fn display_file_open_dialog(&mut self, filters: &[FileFilter]) -> Option<SelectedFile>;
+
+    /// Read the contents of a file previously chosen by the user.
+    fn load_file(&mut self, file: &SelectedFile) -> Result<Vec<u8>, Error>;
+
+    /// Ask the user where to save some data, and save it there.
+    ///
+    /// Returns `Ok(None)` if the user cancelled the dialog.
+    fn display_file_save_dialog(
+        &mut self,
+        default_name: &str,
+        data: &[u8],
+    ) -> Result<Option<SelectedFile>, Error>;
+
+    /// Find a font installed on the system, for text fields that use device
+    /// fonts.
+    ///
+    /// Returns the contents of a TrueType or OpenType font file, or `None` if
+    /// no font with the given family name is available. The style flags are
+    /// hints: a regular font may be returned if the requested style is not
+    /// installed.
+    fn load_device_font(&mut self, name: &str, is_bold: bool, is_italic: bool) -> Option<Vec<u8>>;
+
+    /// Set the items of the context menu that is about to be shown.
+    ///
+    /// This is called whenever the movie is right clicked. When the user
+    /// chooses one of the items, it should be reported by its index to
+    /// `Player::on_context_menu_select`.
+    fn build_context_menu(&mut self, items: &[ContextMenuItem]);
+
+    /// Whether the player is currently shown fullscreen.
+    fn is_fullscreen(&self) -> bool;
+
+    /// Show the player fullscreen, or return it to its normal size.
+    ///
+    /// Movies may only enter fullscreen in response to user input, which the
+    /// player checks before calling this.
+    fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Error>;
+}
+impl_downcast!(UiBackend);
+
+/// UI backend that does not display any dialogs.
+///
+/// All dialogs are cancelled, unless a file has been chosen in advance with
+/// `set_auto_select`. In that case, open dialogs immediately select that file
+/// and save dialogs immediately accept the suggested name. Saved files are
+/// kept in memory rather than being written to disk.
+///
+/// No device fonts are installed unless they are added with
+/// `add_device_font`, so that text layout does not depend on the fonts of the
+/// machine running the player: every device font falls back to the bundled
+/// default font.
+///
+/// The items of the last context menu, and every request to enter or leave
+/// fullscreen, are kept so that they can be checked.
+pub struct NullUiBackend {
+    auto_select: Option<PathBuf>,
+    saved_files: Vec<(String, Vec<u8>)>,
+    device_fonts: HashMap<String, Vec<u8>>,
+    context_menu: Vec<ContextMenuItem>,
+    fullscreen_requests: Vec<bool>,
+}
+
+impl NullUiBackend {
+    pub fn new() -> Self {
+        Self {
+            auto_select: None,
+            saved_files: Vec::new(),
+            device_fonts: HashMap::new(),
+            context_menu: Vec::new(),
+            fullscreen_requests: Vec::new(),
+        }
+    }
+
+    /// Construct a UI backend that accepts every dialog, opening the given
+    /// file.
+    pub fn with_auto_select(path: impl Into<PathBuf>) -> Self {
+        Self {
+            auto_select: Some(path.into()),
+            ..Self::new()
+        }
+    }
+
+    /// Install a device font under the given family name.
+    ///
+    /// The same font data is used for every style of the family.
+    pub fn add_device_font(&mut self, name: &str, data: Vec<u8>) {
+        self.device_fonts.insert(name.to_string(), data);
+    }
+
+    /// Change the file that will be selected in open dialogs.
+    ///
+    /// Passing `None` causes all dialogs to be cancelled.
+    pub fn set_auto_select(&mut self, path: Option<PathBuf>) {
+        self.auto_select = path;
+    }
+
+    /// List every file saved with a save dialog, by name.
+    pub fn saved_files(&self) -> &[(String, Vec<u8>)] {
+        &self.saved_files
+    }
+
+    /// The items of the last context menu that was built.
+    pub fn context_menu(&self) -> &[ContextMenuItem] {
+        &self.context_menu
+    }
+
+    /// Every request to enter (`true`) or leave (`false`) fullscreen, in
+    /// order.
+    pub fn fullscreen_requests(&self) -> &[bool] {
+        &self.fullscreen_requests
+    }
+}
+
+impl UiBackend for NullUiBackend {
+    fn display_file_open_dialog(&mut self, _filters: &[FileFilter]) -> Option<SelectedFile> {
+        let path = self.auto_select.clone()?;
+
+        match SelectedFile::from_path(path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                log::warn!("Could not auto-select file: {}", e);
+                None
+            }
+        }
+    }
+
+    fn load_file(&mut self, file: &SelectedFile) -> Result<Vec<u8>, Error> {
+        Ok(std::fs::read(&file.path)?)
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        default_name: &str,
+        data: &[u8],
+    ) -> Result<Option<SelectedFile>, Error> {
+        if self.auto_select.is_none() {
+            return Ok(None);
+        }
+
+        let name = default_name.to_string();
+        self.saved_files.push((name.clone(), data.to_vec()));
+
+        Ok(Some(SelectedFile {
+            path: PathBuf::from(&name),
+            name,
+            size: data.len() as u64,
+            modification_date: None,
+        }))
+    }
+
+    fn load_device_font(
+        &mut self,
+        name: &str,
+        _is_bold: bool,
+        _is_italic: bool,
+    ) -> Option<Vec<u8>> {
+        self.device_fonts.get(name).cloned()
+    }
+
+    fn build_context_menu(&mut self, items: &[ContextMenuItem]) {
+        self.context_menu = items.to_vec();
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.fullscreen_requests.last().copied().unwrap_or(false)
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Error> {
+        self.fullscreen_requests.push(is_full);
+        Ok(())
+    }
+}
+
+impl Default for NullUiBackend {
+    fn default() -> Self {
+        NullUiBackend::new()
+    }
+}
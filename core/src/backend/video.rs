@@ -0,0 +1,210 @@
+//! Video decoder backends
+
+use downcast_rs::Downcast;
+use generational_arena::{Arena, Index};
+use swf::{VideoCodec, VideoDeblocking};
+
+mod screen;
+
+pub use screen::ScreenVideoDecoder;
+
+pub type VideoStreamHandle = Index;
+
+type Error = Box<dyn std::error::Error>;
+
+/// An encoded frame of video, as found in a `VideoFrame` tag.
+#[derive(Copy, Clone, Debug)]
+pub struct EncodedFrame<'a> {
+    /// The codec the frame was encoded with.
+    pub codec: VideoCodec,
+
+    /// The encoded frame data.
+    pub data: &'a [u8],
+
+    /// The index of this frame in its stream.
+    pub frame_id: u32,
+}
+
+/// A decoded frame of video.
+///
+/// Pixels are stored as RGBA, from the top-left to the bottom-right of the
+/// frame, ready to be uploaded to the renderer.
+#[derive(Clone)]
+pub struct DecodedFrame {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+}
+
+/// Which previous frames, if any, must be decoded before a given frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameDependency {
+    /// The frame can be decoded on its own; this is a keyframe.
+    None,
+
+    /// The frame refers to the frames before it, back to the most recent
+    /// keyframe.
+    Past,
+}
+
+impl FrameDependency {
+    pub fn is_keyframe(self) -> bool {
+        self == FrameDependency::None
+    }
+}
+
+pub trait VideoBackend: Downcast {
+    /// Register a new video stream, such as one defined by a
+    /// `DefineVideoStream` tag.
+    fn register_video_stream(
+        &mut self,
+        num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error>;
+
+    /// Determine which frames a given frame depends on, without decoding it.
+    ///
+    /// This is called for every frame of a stream as the movie loads, so that
+    /// seeking knows which keyframe to start decoding from.
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error>;
+
+    /// Decode a frame of a video stream.
+    ///
+    /// Frames that are not keyframes are decoded against the last frame that
+    /// was decoded on the same stream. It is up to the caller to decode
+    /// frames in order, starting from a keyframe.
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<DecodedFrame, Error>;
+}
+impl_downcast!(VideoBackend);
+
+/// A video backend that does not decode any video.
+pub struct NullVideoBackend {
+    streams: Arena<()>,
+}
+
+impl NullVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for NullVideoBackend {
+    fn default() -> Self {
+        NullVideoBackend::new()
+    }
+}
+
+impl VideoBackend for NullVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        _size: (u16, u16),
+        _codec: VideoCodec,
+        _deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        Ok(self.streams.insert(()))
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        Ok(FrameDependency::None)
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+    ) -> Result<DecodedFrame, Error> {
+        Err("Video decoding is not supported".into())
+    }
+}
+
+/// A decoder for a single stream of video in a particular codec.
+pub trait VideoDecoder {
+    /// Determine which frames a given frame depends on, without decoding it.
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error>;
+
+    /// Decode a frame, against the last frame decoded if it is not a
+    /// keyframe.
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+}
+
+/// A video backend that decodes video in software, using decoders written in
+/// Rust.
+///
+/// Currently, only Screen Video is supported.
+pub struct SoftwareVideoBackend {
+    streams: Arena<Box<dyn VideoDecoder>>,
+}
+
+impl SoftwareVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for SoftwareVideoBackend {
+    fn default() -> Self {
+        SoftwareVideoBackend::new()
+    }
+}
+
+impl VideoBackend for SoftwareVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        _deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        let decoder: Box<dyn VideoDecoder> = match codec {
+            VideoCodec::ScreenVideo => Box::new(ScreenVideoDecoder::new(size)),
+            _ => return Err(format!("Unsupported video codec {:?}", codec).into()),
+        };
+
+        Ok(self.streams.insert(decoder))
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        let decoder = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+
+        decoder.preload_frame(encoded_frame)
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<DecodedFrame, Error> {
+        let decoder = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+
+        decoder.decode_frame(encoded_frame)
+    }
+}
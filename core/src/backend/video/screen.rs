@@ -0,0 +1,258 @@
+//! Screen Video (V1) decoder
+
+use crate::backend::video::{DecodedFrame, EncodedFrame, Error, FrameDependency, VideoDecoder};
+use std::io::Read;
+
+/// The frame type of a keyframe, as stored in the high nibble of the first
+/// byte of a frame.
+const KEYFRAME: u8 = 1;
+
+/// A decoder for the Screen Video codec.
+///
+/// Screen video frames are split into a grid of blocks, each of which is
+/// zlib-compressed BGR data. Blocks that did not change since the last frame
+/// are left empty.
+pub struct ScreenVideoDecoder {
+    /// The size of the stream, as declared in its `DefineVideoStream` tag.
+    size: (u16, u16),
+
+    /// The last frame decoded, as RGBA, which interframes are decoded
+    /// against.
+    last_frame: Option<DecodedFrame>,
+}
+
+impl ScreenVideoDecoder {
+    pub fn new(size: (u16, u16)) -> Self {
+        Self {
+            size,
+            last_frame: None,
+        }
+    }
+}
+
+/// Read the `(block size, image size)` pair of a screen video packet header.
+fn read_dimension(data: &[u8]) -> Result<(usize, usize), Error> {
+    match data {
+        [high, low, ..] => {
+            let value = u16::from_be_bytes([*high, *low]);
+            let block_size = ((value >> 12) as usize + 1) * 16;
+            let image_size = (value & 0xfff) as usize;
+            Ok((block_size, image_size))
+        }
+        _ => Err("Screen video frame is too short".into()),
+    }
+}
+
+impl VideoDecoder for ScreenVideoDecoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        // SWF video frames start with the frame type and codec ID of the
+        // frame, the same as `VIDEODATA` in FLV files. This tells us whether
+        // the frame is a keyframe without having to look at its blocks.
+        let frame_type = encoded_frame
+            .data
+            .first()
+            .ok_or("Screen video frame is empty")?
+            >> 4;
+
+        if frame_type == KEYFRAME {
+            Ok(FrameDependency::None)
+        } else {
+            Ok(FrameDependency::Past)
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let is_keyframe = self.preload_frame(encoded_frame)?.is_keyframe();
+        let data = &encoded_frame.data[1..];
+
+        let (block_width, width) = read_dimension(data)?;
+        let (block_height, height) = read_dimension(&data[2..])?;
+        let mut data = &data[4..];
+
+        if (width, height) != (self.size.0 as usize, self.size.1 as usize) {
+            log::warn!(
+                "Screen video frame is {}x{}, but its stream is {}x{}",
+                width,
+                height,
+                self.size.0,
+                self.size.1
+            );
+        }
+
+        let mut rgba = match &self.last_frame {
+            _ if is_keyframe => vec![0; width * height * 4],
+            Some(last_frame)
+                if (last_frame.width as usize, last_frame.height as usize) == (width, height) =>
+            {
+                last_frame.rgba.clone()
+            }
+            _ => return Err("Screen video interframe has no frame to decode against".into()),
+        };
+
+        // Blocks are stored in rows, starting from the bottom-left of the
+        // image. The pixels within each block are stored in the same order.
+        let columns = (width + block_width - 1) / block_width;
+        let rows = (height + block_height - 1) / block_height;
+        for row in 0..rows {
+            for column in 0..columns {
+                let data_size = match data {
+                    [high, low, ..] => u16::from_be_bytes([*high, *low]) as usize,
+                    _ => return Err("Screen video frame is missing blocks".into()),
+                };
+                let block_data = data
+                    .get(2..2 + data_size)
+                    .ok_or("Screen video block is truncated")?;
+                data = &data[2 + data_size..];
+
+                if data_size == 0 {
+                    // This block is unchanged since the last frame.
+                    continue;
+                }
+
+                let x_min = column * block_width;
+                let y_min = row * block_height;
+                let this_block_width = block_width.min(width - x_min);
+                let this_block_height = block_height.min(height - y_min);
+
+                let mut bgr = Vec::with_capacity(this_block_width * this_block_height * 3);
+                flate2::read::ZlibDecoder::new(block_data).read_to_end(&mut bgr)?;
+                if bgr.len() < this_block_width * this_block_height * 3 {
+                    return Err("Screen video block has too little data".into());
+                }
+
+                for (y, line) in bgr
+                    .chunks_exact(this_block_width * 3)
+                    .take(this_block_height)
+                    .enumerate()
+                {
+                    let image_y = height - 1 - (y_min + y);
+                    let start = (image_y * width + x_min) * 4;
+                    let out = &mut rgba[start..start + this_block_width * 4];
+                    for (pixel, color) in out.chunks_exact_mut(4).zip(line.chunks_exact(3)) {
+                        pixel[0] = color[2];
+                        pixel[1] = color[1];
+                        pixel[2] = color[0];
+                        pixel[3] = 255;
+                    }
+                }
+            }
+        }
+
+        let frame = DecodedFrame {
+            width: width as u16,
+            height: height as u16,
+            rgba,
+        };
+
+        self.last_frame = Some(frame.clone());
+
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+    use swf::VideoCodec;
+
+    /// Encode a screen video frame with 16x16 blocks, from a list of blocks
+    /// that are either unchanged or filled with a single BGR color.
+    fn encode_frame(frame_type: u8, size: (u16, u16), blocks: &[Option<[u8; 3]>]) -> Vec<u8> {
+        let mut data = vec![frame_type << 4 | 3];
+        data.extend_from_slice(&size.0.to_be_bytes());
+        data.extend_from_slice(&size.1.to_be_bytes());
+
+        let columns = (size.0 as usize + 15) / 16;
+        for (i, block) in blocks.iter().enumerate() {
+            match block {
+                Some(color) => {
+                    let x_min = (i % columns) * 16;
+                    let y_min = (i / columns) * 16;
+                    let block_width = 16.min(size.0 as usize - x_min);
+                    let block_height = 16.min(size.1 as usize - y_min);
+
+                    let mut encoder = ZlibEncoder::new(Vec::new(), Default::default());
+                    for _ in 0..block_width * block_height {
+                        encoder.write_all(color).unwrap();
+                    }
+                    let compressed = encoder.finish().unwrap();
+
+                    data.extend_from_slice(&(compressed.len() as u16).to_be_bytes());
+                    data.extend_from_slice(&compressed);
+                }
+                None => data.extend_from_slice(&[0, 0]),
+            }
+        }
+
+        data
+    }
+
+    fn pixel(frame: &DecodedFrame, x: usize, y: usize) -> &[u8] {
+        let start = (y * frame.width as usize + x) * 4;
+        &frame.rgba[start..start + 4]
+    }
+
+    #[test]
+    fn decode_keyframe_and_interframe() {
+        let mut decoder = ScreenVideoDecoder::new((20, 20));
+
+        // 20x20 is split into a 2x2 grid of blocks, the bottom-left of which
+        // is 16x16.
+        let keyframe = encode_frame(
+            KEYFRAME,
+            (20, 20),
+            &[
+                Some([255, 0, 0]),
+                Some([0, 255, 0]),
+                Some([0, 0, 255]),
+                Some([255, 255, 255]),
+            ],
+        );
+        let encoded = EncodedFrame {
+            codec: VideoCodec::ScreenVideo,
+            data: &keyframe,
+            frame_id: 0,
+        };
+        assert_eq!(
+            decoder.preload_frame(encoded).unwrap(),
+            FrameDependency::None
+        );
+
+        let frame = decoder.decode_frame(encoded).unwrap();
+        assert_eq!((frame.width, frame.height), (20, 20));
+        assert_eq!(pixel(&frame, 0, 19), &[0, 0, 255, 255]);
+        assert_eq!(pixel(&frame, 19, 19), &[0, 255, 0, 255]);
+        assert_eq!(pixel(&frame, 0, 0), &[255, 0, 0, 255]);
+        assert_eq!(pixel(&frame, 19, 0), &[255, 255, 255, 255]);
+
+        let interframe = encode_frame(2, (20, 20), &[None, None, None, Some([0, 0, 0])]);
+        let encoded = EncodedFrame {
+            codec: VideoCodec::ScreenVideo,
+            data: &interframe,
+            frame_id: 1,
+        };
+        assert_eq!(
+            decoder.preload_frame(encoded).unwrap(),
+            FrameDependency::Past
+        );
+
+        let frame = decoder.decode_frame(encoded).unwrap();
+        assert_eq!(pixel(&frame, 0, 19), &[0, 0, 255, 255]);
+        assert_eq!(pixel(&frame, 19, 0), &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn interframe_without_keyframe() {
+        let mut decoder = ScreenVideoDecoder::new((16, 16));
+        let interframe = encode_frame(2, (16, 16), &[None]);
+        let encoded = EncodedFrame {
+            codec: VideoCodec::ScreenVideo,
+            data: &interframe,
+            frame_id: 1,
+        };
+
+        assert!(decoder.decode_frame(encoded).is_err());
+    }
+}
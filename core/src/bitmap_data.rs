@@ -0,0 +1,561 @@
+//! BitmapData support types
+
+use crate::backend::render::{blur_radius, Bitmap, BitmapFormat, BitmapHandle, RenderBackend};
+use crate::bounding_box::BoundingBox;
+use crate::color_transform::ColorTransform;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::shape_utils::{self, DrawCommand, DrawPath};
+use crate::transform::Transform;
+use gc_arena::Collect;
+use std::cell::Cell;
+use swf::{Color, ConvolutionFilter, FillStyle, Filter, Twips};
+
+/// The storage portion of a `BitmapData`.
+///
+/// Pixels are stored as unmultiplied ARGB values, in rows starting from the
+/// top left of the image. They are uploaded to the renderer the first time
+/// the bitmap is drawn after they change.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct BitmapDataStorage {
+    width: u32,
+    height: u32,
+
+    /// Whether the pixels may be partially transparent.
+    transparent: bool,
+
+    /// The pixels of the image.
+    pixels: Vec<u32>,
+
+    /// The texture these pixels were last uploaded to, if any.
+    bitmap_handle: Cell<Option<BitmapHandle>>,
+
+    /// Whether the pixels have changed since they were last uploaded.
+    dirty: Cell<bool>,
+}
+
+impl Default for BitmapDataStorage {
+    fn default() -> Self {
+        Self::new(0, 0, true, 0)
+    }
+}
+
+impl BitmapDataStorage {
+    /// Construct storage of the given size, filled with a single color.
+    pub fn new(width: u32, height: u32, transparent: bool, fill_color: u32) -> Self {
+        let fill_color = if transparent {
+            fill_color
+        } else {
+            fill_color | 0xFF00_0000
+        };
+
+        Self {
+            width,
+            height,
+            transparent,
+            pixels: vec![fill_color; width as usize * height as usize],
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Construct storage holding the pixels of a decoded image.
+    pub fn from_bitmap(bitmap: Bitmap, transparent: bool) -> Self {
+        let pixels = match bitmap.data {
+            BitmapFormat::Rgb(rgb) => rgb
+                .chunks_exact(3)
+                .map(|p| {
+                    0xFF00_0000 | u32::from(p[0]) << 16 | u32::from(p[1]) << 8 | u32::from(p[2])
+                })
+                .collect(),
+            BitmapFormat::Rgba(rgba) => rgba
+                .chunks_exact(4)
+                .map(|p| {
+                    // Decoded images have premultiplied alpha.
+                    let a = u32::from(p[3]);
+                    let unmultiply = |c: u8| {
+                        if a == 0 {
+                            0
+                        } else {
+                            (u32::from(c) * 255 / a).min(255)
+                        }
+                    };
+                    a << 24 | unmultiply(p[0]) << 16 | unmultiply(p[1]) << 8 | unmultiply(p[2])
+                })
+                .collect(),
+        };
+
+        Self {
+            width: bitmap.width,
+            height: bitmap.height,
+            transparent,
+            pixels,
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Whether the pixels have changed since they were last uploaded.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Get the ARGB value of a pixel.
+    ///
+    /// Pixels outside of the image are returned as zero.
+    pub fn get_pixel32(&self, x: i32, y: i32) -> u32 {
+        self.index(x, y).map(|i| self.pixels[i]).unwrap_or(0)
+    }
+
+    /// Set the ARGB value of a pixel.
+    ///
+    /// Opaque images ignore the alpha channel, and pixels outside of the image
+    /// are not set.
+    pub fn set_pixel32(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = if self.transparent {
+                color
+            } else {
+                color | 0xFF00_0000
+            };
+            self.dirty.set(true);
+        }
+    }
+
+    /// Set the RGB value of a pixel, keeping its alpha channel.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
+        let alpha = self.get_pixel32(x, y) & 0xFF00_0000;
+        self.set_pixel32(x, y, alpha | (color & 0x00FF_FFFF));
+    }
+
+    /// Blend a color over a pixel.
+    fn blend_pixel(&mut self, x: i32, y: i32, color: u32) {
+        let src_a = color >> 24;
+        if src_a == 0 {
+            return;
+        }
+
+        let dst = self.get_pixel32(x, y);
+        let dst_a = dst >> 24;
+        let out_a = src_a + dst_a * (255 - src_a) / 255;
+        let blend = |shift: u32| {
+            let src_c = (color >> shift) & 0xFF;
+            let dst_c = (dst >> shift) & 0xFF;
+            ((src_c * src_a * 255 + dst_c * dst_a * (255 - src_a)) / (out_a * 255)).min(255)
+        };
+
+        self.set_pixel32(
+            x,
+            y,
+            out_a << 24 | blend(16) << 16 | blend(8) << 8 | blend(0),
+        );
+    }
+
+    /// Rasterize a display object and its children into these pixels.
+    ///
+    /// Each pixel is point-sampled at its center. Only solid fills and
+    /// strokes are drawn; gradients, bitmap fills, text, masks, filters and
+    /// blend modes are not supported yet.
+    pub fn draw<'gc>(&mut self, object: DisplayObject<'gc>, transform: &Transform) {
+        object.rasterize_self(self, transform);
+
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                if !child.visible() || child.clip_depth() > 0 {
+                    continue;
+                }
+
+                let child_transform = Transform {
+                    matrix: transform.matrix * *child.matrix(),
+                    color_transform: transform.color_transform * *child.color_transform(),
+                };
+                self.draw(child, &child_transform);
+            }
+        }
+    }
+
+    /// Rasterize a list of paths with the given transform.
+    pub fn draw_paths(&mut self, paths: &[DrawPath], transform: &Transform) {
+        let matrix = transform.matrix;
+        if (matrix.a * matrix.d - matrix.b * matrix.c).abs() <= f32::EPSILON {
+            return;
+        }
+
+        let mut inverse = matrix;
+        inverse.invert();
+
+        for path in paths {
+            let (color, commands, stroke_width) = match path {
+                DrawPath::Fill {
+                    style: FillStyle::Color(color),
+                    commands,
+                } => (color, commands, None),
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => (&style.color, commands, Some(style.width)),
+                _ => continue,
+            };
+            let color = transformed_color(color, &transform.color_transform);
+
+            let bounds =
+                path_bounds(commands, stroke_width.unwrap_or_else(Twips::zero)).transform(&matrix);
+            if !bounds.valid {
+                continue;
+            }
+
+            // Strokes are at least a pixel wide, so pad by one pixel.
+            let x_min = (bounds.x_min.to_pixels().floor() as i32 - 1).max(0);
+            let y_min = (bounds.y_min.to_pixels().floor() as i32 - 1).max(0);
+            let x_max = (bounds.x_max.to_pixels().ceil() as i32 + 1).min(self.width as i32);
+            let y_max = (bounds.y_max.to_pixels().ceil() as i32 + 1).min(self.height as i32);
+
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    let point = inverse
+                        * (
+                            Twips::from_pixels(f64::from(x) + 0.5),
+                            Twips::from_pixels(f64::from(y) + 0.5),
+                        );
+                    let hit = match stroke_width {
+                        Some(width) => shape_utils::draw_command_stroke_hit_test(
+                            commands, width, point, &matrix,
+                        ),
+                        None => shape_utils::draw_command_fill_hit_test(commands, point),
+                    };
+
+                    if hit {
+                        self.blend_pixel(x, y, color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply a filter to an area of another image, writing the result into
+    /// these pixels with its top left corner at `dest`.
+    ///
+    /// `source_rect` is given as x, y, width and height. Blurs and
+    /// convolutions also read the source pixels around it. Only blur, color
+    /// matrix and convolution filters are supported; other filters copy the
+    /// source pixels unchanged.
+    pub fn apply_filter(
+        &mut self,
+        source: &BitmapDataStorage,
+        source_rect: (i32, i32, i32, i32),
+        dest: (i32, i32),
+        filter: &Filter,
+    ) {
+        let (src_x, src_y, width, height) = source_rect;
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        if let Filter::ConvolutionFilter(convolution) = filter {
+            self.convolve(source, source_rect, dest, convolution);
+            return;
+        }
+
+        let (margin_x, margin_y) = match filter {
+            Filter::BlurFilter(blur) => {
+                let passes = u32::from(blur.num_passes);
+                (
+                    (blur_radius(blur.blur_x) * passes) as i32,
+                    (blur_radius(blur.blur_y) * passes) as i32,
+                )
+            }
+            _ => (0, 0),
+        };
+        let buffer_width = width + 2 * margin_x;
+        let buffer_height = height + 2 * margin_y;
+        let mut buffer: Vec<[f32; 4]> = (0..buffer_height)
+            .flat_map(|y| {
+                (0..buffer_width).map(move |x| {
+                    premultiplied(source.get_pixel32(src_x - margin_x + x, src_y - margin_y + y))
+                })
+            })
+            .collect();
+
+        match filter {
+            Filter::BlurFilter(blur) => {
+                let (width, height) = (buffer_width as usize, buffer_height as usize);
+                let radius_x = blur_radius(blur.blur_x) as usize;
+                let radius_y = blur_radius(blur.blur_y) as usize;
+                for _ in 0..blur.num_passes {
+                    box_blur(&mut buffer, width, height, radius_x, true);
+                    box_blur(&mut buffer, width, height, radius_y, false);
+                }
+            }
+            Filter::ColorMatrixFilter(color_matrix) => {
+                for pixel in buffer.iter_mut() {
+                    let argb = unpremultiplied(*pixel);
+                    let channels = [
+                        ((argb >> 16) & 0xFF) as f32,
+                        ((argb >> 8) & 0xFF) as f32,
+                        (argb & 0xFF) as f32,
+                        (argb >> 24) as f32,
+                    ];
+                    let row = |i: usize| {
+                        let m = &color_matrix.matrix[i * 5..i * 5 + 5];
+                        let value = channels
+                            .iter()
+                            .zip(m)
+                            .fold(m[4] as f32, |sum, (c, m)| sum + c * *m as f32);
+                        value.round().max(0.0).min(255.0) as u32
+                    };
+                    *pixel = premultiplied(row(3) << 24 | row(0) << 16 | row(1) << 8 | row(2));
+                }
+            }
+            _ => (),
+        }
+
+        let (dest_x, dest_y) = dest;
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = buffer[((y + margin_y) * buffer_width + x + margin_x) as usize];
+                self.set_pixel32(dest_x + x, dest_y + y, unpremultiplied(pixel));
+            }
+        }
+    }
+
+    /// Apply a convolution matrix to an area of another image.
+    ///
+    /// Each channel is convolved separately on unpremultiplied values.
+    /// Pixels outside of the source image are either clamped to its edges or
+    /// replaced by the filter's default color.
+    fn convolve(
+        &mut self,
+        source: &BitmapDataStorage,
+        source_rect: (i32, i32, i32, i32),
+        dest: (i32, i32),
+        filter: &ConvolutionFilter,
+    ) {
+        let (src_x, src_y, width, height) = source_rect;
+        let cols = i32::from(filter.num_matrix_cols);
+        let rows = i32::from(filter.num_matrix_rows);
+        let divisor = if filter.divisor == 0.0 {
+            1.0
+        } else {
+            filter.divisor
+        };
+        let color = &filter.default_color;
+        let default_color = u32::from(color.a) << 24
+            | u32::from(color.r) << 16
+            | u32::from(color.g) << 8
+            | u32::from(color.b);
+        let (max_x, max_y) = (source.width as i32 - 1, source.height as i32 - 1);
+        let sample = |x: i32, y: i32| {
+            if filter.is_clamped {
+                source.get_pixel32(x.max(0).min(max_x), y.max(0).min(max_y))
+            } else if x < 0 || y < 0 || x > max_x || y > max_y {
+                default_color
+            } else {
+                source.get_pixel32(x, y)
+            }
+        };
+
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in src_y..src_y + height {
+            for x in src_x..src_x + width {
+                let mut sums = [0.0; 4];
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let weight = filter
+                            .matrix
+                            .get((row * cols + col) as usize)
+                            .copied()
+                            .unwrap_or(0.0);
+                        let argb = sample(x + col - cols / 2, y + row - rows / 2);
+                        for (i, sum) in sums.iter_mut().enumerate() {
+                            *sum += weight * f64::from((argb >> (24 - 8 * i)) & 0xFF);
+                        }
+                    }
+                }
+                let channel =
+                    |sum: f64| (sum / divisor + filter.bias).round().max(0.0).min(255.0) as u32;
+                let alpha = if filter.is_preserve_alpha {
+                    source.get_pixel32(x, y) >> 24
+                } else {
+                    channel(sums[0])
+                };
+                pixels.push(
+                    alpha << 24 | channel(sums[1]) << 16 | channel(sums[2]) << 8 | channel(sums[3]),
+                );
+            }
+        }
+
+        let (dest_x, dest_y) = dest;
+        for (i, color) in pixels.into_iter().enumerate() {
+            let (x, y) = (i as i32 % width, i as i32 / width);
+            self.set_pixel32(dest_x + x, dest_y + y, color);
+        }
+    }
+
+    /// Get the texture holding these pixels, uploading them if they changed
+    /// since the last call.
+    ///
+    /// Empty images have no texture.
+    pub fn bitmap_handle(&self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        if self.pixels.is_empty() {
+            return None;
+        }
+
+        if self.dirty.get() || self.bitmap_handle.get().is_none() {
+            let rgba = self.premultiplied_rgba();
+            let handle = match self.bitmap_handle.get() {
+                Some(handle) => renderer.update_texture(handle, self.width, self.height, rgba),
+                None => renderer.register_bitmap_raw(self.width, self.height, rgba),
+            };
+
+            match handle {
+                Ok(handle) => {
+                    self.bitmap_handle.set(Some(handle));
+                    self.dirty.set(false);
+                }
+                Err(e) => log::error!("Could not upload BitmapData: {}", e),
+            }
+        }
+
+        self.bitmap_handle.get()
+    }
+
+    /// The pixels as premultiplied RGBA bytes, as expected by the renderer.
+    fn premultiplied_rgba(&self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for &argb in &self.pixels {
+            let a = argb >> 24;
+            let premultiply = |c: u32| ((c & 0xFF) * a / 255) as u8;
+            rgba.push(premultiply(argb >> 16));
+            rgba.push(premultiply(argb >> 8));
+            rgba.push(premultiply(argb));
+            rgba.push(a as u8);
+        }
+
+        rgba
+    }
+}
+
+/// The local bounds of a path, padded by a stroke width.
+fn path_bounds(commands: &[DrawCommand], stroke_width: Twips) -> BoundingBox {
+    let mut bounds = BoundingBox::default();
+    for command in commands {
+        if let DrawCommand::CurveTo { x1, y1, .. } = *command {
+            bounds.encompass(x1, y1);
+        }
+        let (x, y) = command.end_point();
+        bounds.encompass(x, y);
+    }
+
+    if bounds.valid {
+        bounds.x_min -= stroke_width;
+        bounds.y_min -= stroke_width;
+        bounds.x_max += stroke_width;
+        bounds.y_max += stroke_width;
+    }
+
+    bounds
+}
+
+/// Convert an ARGB value to premultiplied alpha, red, green and blue
+/// channels.
+fn premultiplied(argb: u32) -> [f32; 4] {
+    let a = (argb >> 24) as f32;
+    let channel = |shift: u32| ((argb >> shift) & 0xFF) as f32 * a / 255.0;
+    [a, channel(16), channel(8), channel(0)]
+}
+
+/// Convert premultiplied alpha, red, green and blue channels back to ARGB.
+fn unpremultiplied(pixel: [f32; 4]) -> u32 {
+    let a = pixel[0].round().max(0.0).min(255.0);
+    if a == 0.0 {
+        return 0;
+    }
+
+    let channel = |value: f32| (value * 255.0 / a).round().max(0.0).min(255.0) as u32;
+    (a as u32) << 24 | channel(pixel[1]) << 16 | channel(pixel[2]) << 8 | channel(pixel[3])
+}
+
+/// Blur the rows or columns of an image with a box of `2 * radius + 1`
+/// pixels. Pixels outside of the image count as transparent.
+fn box_blur(pixels: &mut [[f32; 4]], width: usize, height: usize, radius: usize, horizontal: bool) {
+    if radius == 0 {
+        return;
+    }
+
+    let (lines, length) = if horizontal {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let index = |line: usize, i: usize| {
+        if horizontal {
+            line * width + i
+        } else {
+            i * width + line
+        }
+    };
+    let size = (2 * radius + 1) as f32;
+    let mut line_pixels = vec![[0.0; 4]; length];
+
+    for line in 0..lines {
+        for (i, pixel) in line_pixels.iter_mut().enumerate() {
+            *pixel = pixels[index(line, i)];
+        }
+
+        let mut sum = [0.0f32; 4];
+        for pixel in line_pixels.iter().take(radius) {
+            for (s, c) in sum.iter_mut().zip(pixel) {
+                *s += c;
+            }
+        }
+
+        for i in 0..length {
+            if let Some(entering) = line_pixels.get(i + radius) {
+                for (s, c) in sum.iter_mut().zip(entering) {
+                    *s += c;
+                }
+            }
+            if i > radius {
+                for (s, c) in sum.iter_mut().zip(&line_pixels[i - radius - 1]) {
+                    *s -= c;
+                }
+            }
+
+            let out = &mut pixels[index(line, i)];
+            for (o, s) in out.iter_mut().zip(&sum) {
+                *o = s / size;
+            }
+        }
+    }
+}
+
+/// Apply a color transform to a color, returning it as ARGB.
+fn transformed_color(color: &Color, color_transform: &ColorTransform) -> u32 {
+    let channel = |value: u8, mult: f32, add: f32| {
+        ((f32::from(value) * mult + add * 255.0).max(0.0).min(255.0)) as u32
+    };
+
+    channel(color.a, color_transform.a_mult, color_transform.a_add) << 24
+        | channel(color.r, color_transform.r_mult, color_transform.r_add) << 16
+        | channel(color.g, color_transform.g_mult, color_transform.g_add) << 8
+        | channel(color.b, color_transform.b_mult, color_transform.b_add)
+}
@@ -8,14 +8,24 @@ use crate::backend::input::InputBackend;
 use crate::backend::locale::LocaleBackend;
 use crate::backend::log::LogBackend;
 use crate::backend::storage::StorageBackend;
-use crate::backend::{audio::AudioBackend, navigator::NavigatorBackend, render::RenderBackend};
-use crate::display_object::EditText;
+use crate::backend::ui::UiBackend;
+use crate::backend::video::VideoBackend;
+use crate::backend::{
+    audio::AudioBackend,
+    navigator::NavigatorBackend,
+    render::{RenderBackend, StageQuality},
+};
+use crate::context_menu::ContextMenuState;
+use crate::display_object::{EditText, GotoSnapshotSettings};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
+use crate::gc_stats::GcStats;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::shape_cache::ShapeCache;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::TransformStack;
 use core::fmt;
@@ -55,6 +65,18 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// Requests a that the player re-renders after this execution (e.g. due to `updateAfterEvent`).
     pub needs_render: &'a mut bool,
 
+    /// Whether `Stage.invalidate` has been called since the stage's `render`
+    /// event was last fired.
+    pub needs_redraw: &'a mut bool,
+
+    /// Requests a full garbage collection after this execution (e.g. due to
+    /// `System.gc`).
+    pub needs_collect: &'a mut bool,
+
+    /// Statistics about the garbage-collected heap, as of the start of this
+    /// update.
+    pub gc_stats: &'a GcStats,
+
     /// The root SWF file.
     pub swf: &'a Arc<SwfMovie>,
 
@@ -79,6 +101,12 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The logging backend, used for trace output capturing
     pub log: &'a mut dyn LogBackend,
 
+    /// The UI backend, used to display dialogs
+    pub ui: &'a mut dyn UiBackend,
+
+    /// The video backend, used to decode video frames
+    pub video: &'a mut dyn VideoBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -92,6 +120,10 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The display object that the mouse is currently hovering over.
     pub mouse_hovered_object: Option<DisplayObject<'gc>>,
 
+    /// The display object that the mouse button was pressed on, while it is
+    /// held down.
+    pub mouse_pressed_object: Option<DisplayObject<'gc>>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
@@ -101,6 +133,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The dimensions of the stage.
     pub stage_size: (Twips, Twips),
 
+    /// The rendering quality of the stage.
+    pub quality: &'a mut StageQuality,
+
     /// Weak reference to the player.
     ///
     /// Recipients of an update context may upgrade the reference to ensure
@@ -137,6 +172,12 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'a mut ExternalInterface<'gc>,
 
+    /// Local connections between movies, shared by both VMs.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
+    /// The context menu that is being shown, if any.
+    pub current_context_menu: &'a mut Option<ContextMenuState<'gc>>,
+
     /// The instant at which the current update started.
     pub update_start: Instant,
 
@@ -144,8 +185,21 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// is raised. This defaults to 15 seconds but can be changed.
     pub max_execution_duration: Duration,
 
+    /// How movie clips keep snapshots of their timelines to speed up rewinds.
+    pub goto_snapshots: GotoSnapshotSettings,
+
+    /// The number of frames that gotos to earlier frames have replayed.
+    pub rewind_frames_replayed: &'a mut u64,
+
+    /// The number of times text fields have laid out their text.
+    pub text_relayouts: &'a mut u64,
+
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
+
+    /// Whether or not the current update is in response to user input, such
+    /// as a mouse click or key press.
+    pub is_user_gesture: bool,
 }
 
 unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context> {
@@ -155,6 +209,8 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.library.trace(cc);
         self.player_version.trace(cc);
         self.needs_render.trace(cc);
+        self.needs_redraw.trace(cc);
+        self.needs_collect.trace(cc);
         self.swf.trace(cc);
         self.audio.trace(cc);
         self.navigator.trace(cc);
@@ -165,6 +221,7 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.levels.trace(cc);
         self.system_prototypes.trace(cc);
         self.mouse_hovered_object.trace(cc);
+        self.mouse_pressed_object.trace(cc);
         self.mouse_position.trace(cc);
         self.drag_object.trace(cc);
         self.load_manager.trace(cc);
@@ -175,6 +232,8 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.timers.trace(cc);
         self.avm1.trace(cc);
         self.avm2.trace(cc);
+        self.local_connections.trace(cc);
+        self.current_context_menu.trace(cc);
         self.focus_tracker.trace(cc);
     }
 }
@@ -198,21 +257,28 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             library: self.library,
             player_version: self.player_version,
             needs_render: self.needs_render,
+            needs_redraw: self.needs_redraw,
+            needs_collect: self.needs_collect,
+            gc_stats: self.gc_stats,
             swf: self.swf,
             audio: self.audio,
             navigator: self.navigator,
             renderer: self.renderer,
             locale: self.locale,
             log: self.log,
+            ui: self.ui,
+            video: self.video,
             input: self.input,
             storage: self.storage,
             rng: self.rng,
             levels: self.levels,
             system_prototypes: self.system_prototypes.clone(),
             mouse_hovered_object: self.mouse_hovered_object,
+            mouse_pressed_object: self.mouse_pressed_object,
             mouse_position: self.mouse_position,
             drag_object: self.drag_object,
             stage_size: self.stage_size,
+            quality: self.quality,
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
@@ -223,9 +289,15 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             avm1: self.avm1,
             avm2: self.avm2,
             external_interface: self.external_interface,
+            local_connections: self.local_connections,
+            current_context_menu: self.current_context_menu,
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
+            goto_snapshots: self.goto_snapshots,
+            rewind_frames_replayed: self.rewind_frames_replayed,
+            text_relayouts: self.text_relayouts,
             focus_tracker: self.focus_tracker,
+            is_user_gesture: self.is_user_gesture,
         }
     }
 }
@@ -326,11 +398,18 @@ pub struct RenderContext<'a, 'gc> {
     /// The library, which provides access to fonts and other definitions when rendering.
     pub library: &'a Library<'gc>,
 
+    /// The shapes registered by display objects at runtime, shared between
+    /// those with the same contents.
+    pub shape_cache: &'a mut ShapeCache,
+
     /// The transform stack controls the matrix and color transform as we traverse the display hierarchy.
     pub transform_stack: &'a mut TransformStack,
     /// The bounds of the current viewport in twips. Used for culling.
     pub view_bounds: BoundingBox,
 
+    /// The scale from stage pixels to viewport pixels, used to scale filters.
+    pub view_scale: f64,
+
     /// The stack of clip depths, used in masking.
     pub clip_depth_stack: Vec<Depth>,
 
@@ -0,0 +1,195 @@
+//! The context menu shown when the movie is right clicked.
+//!
+//! The menu is built by the player when the mouse button is pressed, and
+//! handed to the UI backend to be displayed. The actions of its items are
+//! kept here until the user chooses one.
+
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{Avm2, Object as Avm2Object};
+use crate::backend::ui::ContextMenuItem;
+use crate::context::{ActionType, UpdateContext};
+use crate::display_object::{MovieClip, TDisplayObject};
+use gc_arena::{Collect, CollectionContext};
+
+/// The built-in items that a movie allows in its context menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuiltInItemFlags {
+    pub play: bool,
+    pub rewind: bool,
+    pub forward_and_back: bool,
+}
+
+impl Default for BuiltInItemFlags {
+    fn default() -> Self {
+        Self {
+            play: true,
+            rewind: true,
+            forward_and_back: true,
+        }
+    }
+}
+
+/// The context menu that is being shown.
+#[derive(Default)]
+pub struct ContextMenuState<'gc> {
+    info: Vec<ContextMenuItem>,
+    callbacks: Vec<ContextMenuCallback<'gc>>,
+}
+
+unsafe impl<'gc> Collect for ContextMenuState<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        self.callbacks.trace(cc);
+    }
+}
+
+impl<'gc> ContextMenuState<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The items of this menu, as shown to the user.
+    pub fn info(&self) -> &[ContextMenuItem] {
+        &self.info
+    }
+
+    /// The action of the item at the given index.
+    ///
+    /// Returns `None` if there is no such item, or if it is disabled.
+    pub fn callback(&self, index: usize) -> Option<ContextMenuCallback<'gc>> {
+        match self.info.get(index) {
+            Some(item) if item.enabled => self.callbacks.get(index).copied(),
+            _ => None,
+        }
+    }
+
+    pub fn push(&mut self, item: ContextMenuItem, callback: ContextMenuCallback<'gc>) {
+        self.info.push(item);
+        self.callbacks.push(callback);
+    }
+
+    /// Add the built-in items for controlling the timeline of the root movie.
+    ///
+    /// These are only shown for movies with more than one frame.
+    pub fn build_builtin_items(&mut self, flags: BuiltInItemFlags, root: MovieClip<'gc>) {
+        if root.total_frames() <= 1 {
+            return;
+        }
+
+        let current_frame = root.current_frame();
+        let has_previous_frame = current_frame > 1;
+        let has_next_frame = current_frame < root.total_frames();
+        let mut items = Vec::new();
+        if flags.play {
+            items.push(("Play", true, root.playing(), ContextMenuCallback::Play));
+        }
+        if flags.rewind {
+            items.push((
+                "Rewind",
+                has_previous_frame,
+                false,
+                ContextMenuCallback::Rewind,
+            ));
+        }
+        if flags.forward_and_back {
+            items.push((
+                "Forward",
+                has_next_frame,
+                false,
+                ContextMenuCallback::Forward,
+            ));
+            items.push(("Back", has_previous_frame, false, ContextMenuCallback::Back));
+        }
+
+        // The built-in items are set apart from the movie's own items.
+        let mut separator_before = !self.info.is_empty();
+        for (caption, enabled, checked, callback) in items {
+            self.push(
+                ContextMenuItem {
+                    caption: caption.to_string(),
+                    enabled,
+                    separator_before,
+                    checked,
+                },
+                callback,
+            );
+            separator_before = false;
+        }
+    }
+}
+
+/// What happens when an item of the context menu is chosen.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub enum ContextMenuCallback<'gc> {
+    /// Toggle whether the root movie is playing.
+    Play,
+
+    /// Go back to the first frame of the root movie.
+    Rewind,
+
+    /// Go to the next frame of the root movie.
+    Forward,
+
+    /// Go to the previous frame of the root movie.
+    Back,
+
+    /// Call `onSelect` on an AVM1 `ContextMenuItem`.
+    Avm1 {
+        item: Avm1Object<'gc>,
+        target: Avm1Object<'gc>,
+    },
+
+    /// Fire `menuItemSelect` on an AVM2 `ContextMenuItem`.
+    Avm2 {
+        item: Avm2Object<'gc>,
+        mouse_target: Avm2Object<'gc>,
+        owner: Avm2Object<'gc>,
+    },
+}
+
+impl<'gc> ContextMenuCallback<'gc> {
+    /// Run this action.
+    ///
+    /// AVM1 scripts are queued rather than run immediately, while AVM2 events
+    /// are dispatched right away.
+    pub fn run(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let root = match context.levels.get(&0) {
+            Some(root) => *root,
+            None => return,
+        };
+
+        match (self, root.as_movie_clip()) {
+            (ContextMenuCallback::Avm1 { item, target }, _) => {
+                context.action_queue.queue_actions(
+                    root,
+                    ActionType::Method {
+                        object: item,
+                        name: "onSelect",
+                        args: vec![target.into(), item.into()],
+                    },
+                    false,
+                );
+            }
+            (
+                ContextMenuCallback::Avm2 {
+                    item,
+                    mouse_target,
+                    owner,
+                },
+                _,
+            ) => Avm2::dispatch_context_menu_item_select(context, item, mouse_target, owner),
+            (ContextMenuCallback::Play, Some(clip)) => {
+                if clip.playing() {
+                    clip.stop(context);
+                } else {
+                    clip.play(context);
+                }
+            }
+            (ContextMenuCallback::Rewind, Some(clip)) => clip.goto_frame(context, 1, true),
+            (ContextMenuCallback::Forward, Some(clip)) => clip.next_frame(context),
+            (ContextMenuCallback::Back, Some(clip)) => clip.prev_frame(context),
+            _ => (),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::avm1::{Object as Avm1Object, TObject, Value as Avm1Value};
-use crate::avm2::Value as Avm2Value;
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+use crate::bitmap_data::BitmapDataStorage;
 use crate::context::{RenderContext, UpdateContext};
+use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
@@ -14,6 +16,8 @@ use std::cell::{Ref, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+use swf::{BlendMode, Filter, Rectangle};
 
 mod bitmap;
 mod button;
@@ -23,20 +27,23 @@ mod graphic;
 mod morph_shape;
 mod movie_clip;
 mod text;
+mod video;
 
 use crate::avm1::activation::Activation;
 use crate::backend::input::MouseCursor;
+use crate::backend::render;
 pub use crate::display_object::container::{
     DisplayObjectContainer, Lists, TDisplayObjectContainer,
 };
 use crate::events::{ClipEvent, ClipEventResult};
-pub use bitmap::Bitmap;
+pub use bitmap::{Bitmap, BitmapSource};
 pub use button::Button;
-pub use edit_text::{AutoSizeMode, EditText, TextSelection};
+pub use edit_text::{AntiAliasType, AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
-pub use movie_clip::{MovieClip, Scene};
-pub use text::Text;
+pub use movie_clip::{GotoSnapshotSettings, MovieClip, Scene, StreamDrift};
+pub use text::{StaticTextChar, Text};
+pub use video::Video;
 
 #[derive(Clone, Debug)]
 pub struct DisplayObjectBase<'gc> {
@@ -47,6 +54,28 @@ pub struct DisplayObjectBase<'gc> {
     name: String,
     clip_depth: Depth,
 
+    /// The blend mode used when compositing this object onto its parent.
+    blend_mode: BlendMode,
+
+    /// The filters applied to this object when it is rendered.
+    filters: Vec<Filter>,
+
+    /// The color filling the bounds of this object behind its contents, if any.
+    opaque_background: Option<Color>,
+
+    /// The area of this object that is displayed, in its own coordinates.
+    /// Its top-left corner is shown at the object's origin.
+    scroll_rect: Option<Rectangle>,
+
+    /// The position of this object in the tab order, set either by a
+    /// `SetTabIndex` tag or by script.
+    tab_index: Option<i32>,
+
+    /// The area of the stage this object covered when its changes were last
+    /// taken, or `None` if that isn't known because a parent has changed
+    /// since.
+    dirty_bounds: Option<BoundingBox>,
+
     // Cached transform properties `_xscale`, `_yscale`, `_rotation`.
     // These are expensive to calculate, so they will be calculated and cached
     // when AS requests one of these properties.
@@ -65,6 +94,9 @@ pub struct DisplayObjectBase<'gc> {
     /// The next sibling of this display object in order of execution.
     next_sibling: Option<DisplayObject<'gc>>,
 
+    /// When this object was last clicked, for telling double clicks apart.
+    last_click_time: Option<Duration>,
+
     /// Bit flags for various display object properites.
     flags: EnumSet<DisplayObjectFlags>,
 }
@@ -78,13 +110,20 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             transform: Default::default(),
             name: Default::default(),
             clip_depth: Default::default(),
+            blend_mode: BlendMode::Normal,
+            filters: vec![],
+            opaque_background: None,
+            scroll_rect: None,
+            tab_index: None,
+            dirty_bounds: Some(BoundingBox::default()),
             rotation: Degrees::from_radians(0.0),
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
             prev_sibling: None,
             next_sibling: None,
-            flags: DisplayObjectFlags::Visible.into(),
+            last_click_time: None,
+            flags: DisplayObjectFlags::Visible | DisplayObjectFlags::Dirty,
         }
     }
 }
@@ -102,7 +141,7 @@ unsafe impl<'gc> Collect for DisplayObjectBase<'gc> {
 impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
-        self.flags = DisplayObjectFlags::Visible.into();
+        self.flags = DisplayObjectFlags::Visible | DisplayObjectFlags::Dirty;
     }
 
     fn id(&self) -> CharacterId {
@@ -128,9 +167,11 @@ impl<'gc> DisplayObjectBase<'gc> {
         &self.transform.matrix
     }
     fn matrix_mut(&mut self, _context: MutationContext<'gc, '_>) -> &mut Matrix {
+        self.set_dirty(true);
         &mut self.transform.matrix
     }
     fn set_matrix(&mut self, _context: MutationContext<'gc, '_>, matrix: &Matrix) {
+        self.set_dirty(true);
         self.transform.matrix = *matrix;
         self.flags.remove(DisplayObjectFlags::ScaleRotationCached);
     }
@@ -138,6 +179,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         &self.transform.color_transform
     }
     fn color_transform_mut(&mut self) -> &mut ColorTransform {
+        self.set_dirty(true);
         &mut self.transform.color_transform
     }
     fn set_color_transform(
@@ -145,6 +187,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         _context: MutationContext<'gc, '_>,
         color_transform: &ColorTransform,
     ) {
+        self.set_dirty(true);
         self.transform.color_transform = *color_transform;
     }
     fn x(&self) -> f64 {
@@ -152,6 +195,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_x(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_dirty(true);
         self.transform.matrix.tx = Twips::from_pixels(value)
     }
     fn y(&self) -> f64 {
@@ -159,6 +203,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_y(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_dirty(true);
         self.transform.matrix.ty = Twips::from_pixels(value)
     }
 
@@ -201,6 +246,7 @@ impl<'gc> DisplayObjectBase<'gc> {
 
     fn set_scale(&mut self, scale_x: f32, scale_y: f32, rotation: f32) {
         self.cache_scale_rotation();
+        self.set_dirty(true);
         let mut matrix = &mut self.transform.matrix;
         let rotation = rotation.to_radians();
         let cos_x = f32::cos(rotation);
@@ -220,6 +266,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_rotation(&mut self, degrees: Degrees) {
         self.set_transformed_by_script(true);
+        self.set_dirty(true);
         self.cache_scale_rotation();
         self.rotation = degrees;
         let cos_x = f64::cos(degrees.into_radians());
@@ -238,6 +285,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_scale_x(&mut self, value: Percent) {
         self.set_transformed_by_script(true);
+        self.set_dirty(true);
         self.cache_scale_rotation();
         self.scale_x = value;
         let cos = f64::cos(self.rotation.into_radians());
@@ -252,6 +300,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_scale_y(&mut self, value: Percent) {
         self.set_transformed_by_script(true);
+        self.set_dirty(true);
         self.cache_scale_rotation();
         self.scale_y = value;
         let cos = f64::cos(self.rotation.into_radians() + self.skew);
@@ -278,8 +327,43 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.clip_depth
     }
     fn set_clip_depth(&mut self, _context: MutationContext<'gc, '_>, depth: Depth) {
+        self.set_dirty(true);
         self.clip_depth = depth;
     }
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+    fn set_blend_mode(&mut self, _context: MutationContext<'gc, '_>, blend_mode: BlendMode) {
+        self.set_dirty(true);
+        self.blend_mode = blend_mode;
+    }
+    fn filters(&self) -> Vec<Filter> {
+        self.filters.clone()
+    }
+    fn set_filters(&mut self, _context: MutationContext<'gc, '_>, filters: Vec<Filter>) {
+        self.set_dirty(true);
+        self.filters = filters;
+    }
+    fn opaque_background(&self) -> Option<Color> {
+        self.opaque_background.clone()
+    }
+    fn set_opaque_background(&mut self, _context: MutationContext<'gc, '_>, color: Option<Color>) {
+        self.set_dirty(true);
+        self.opaque_background = color;
+    }
+    fn scroll_rect(&self) -> Option<Rectangle> {
+        self.scroll_rect.clone()
+    }
+    fn set_scroll_rect(&mut self, _context: MutationContext<'gc, '_>, rect: Option<Rectangle>) {
+        self.set_dirty(true);
+        self.scroll_rect = rect;
+    }
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+    fn set_tab_index(&mut self, _context: MutationContext<'gc, '_>, tab_index: Option<i32>) {
+        self.tab_index = tab_index;
+    }
     fn parent(&self) -> Option<DisplayObject<'gc>> {
         self.parent
     }
@@ -326,6 +410,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_visible(&mut self, value: bool) {
+        self.set_dirty(true);
         if value {
             self.flags.insert(DisplayObjectFlags::Visible);
         } else {
@@ -333,6 +418,19 @@ impl<'gc> DisplayObjectBase<'gc> {
         }
     }
 
+    fn is_bitmap_cached(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CacheAsBitmap)
+    }
+
+    fn set_is_bitmap_cached(&mut self, value: bool) {
+        self.set_dirty(true);
+        if value {
+            self.flags.insert(DisplayObjectFlags::CacheAsBitmap);
+        } else {
+            self.flags.remove(DisplayObjectFlags::CacheAsBitmap);
+        }
+    }
+
     fn transformed_by_script(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::TransformedByScript)
     }
@@ -345,6 +443,46 @@ impl<'gc> DisplayObjectBase<'gc> {
         }
     }
 
+    fn double_click_enabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::DoubleClickEnabled)
+    }
+
+    fn set_double_click_enabled(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::DoubleClickEnabled);
+        } else {
+            self.flags.remove(DisplayObjectFlags::DoubleClickEnabled);
+        }
+    }
+
+    fn last_click_time(&self) -> Option<Duration> {
+        self.last_click_time
+    }
+
+    fn set_last_click_time(&mut self, time: Option<Duration>) {
+        self.last_click_time = time;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::Dirty)
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::Dirty);
+        } else {
+            self.flags.remove(DisplayObjectFlags::Dirty);
+        }
+    }
+
+    fn dirty_bounds(&self) -> Option<BoundingBox> {
+        self.dirty_bounds.clone()
+    }
+
+    fn set_dirty_bounds(&mut self, bounds: Option<BoundingBox>) {
+        self.dirty_bounds = bounds;
+    }
+
     fn placed_by_script(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::PlacedByScript)
     }
@@ -379,6 +517,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         MorphShape(MorphShape<'gc>),
         MovieClip(MovieClip<'gc>),
         Text(Text<'gc>),
+        Video(Video<'gc>),
     }
 )]
 pub trait TDisplayObject<'gc>:
@@ -404,7 +543,7 @@ pub trait TDisplayObject<'gc>:
 
     /// The local bounding box of this object including children, in its parent's coordinate system.
     fn local_bounds(&self) -> BoundingBox {
-        self.bounds_with_transform(&self.matrix())
+        self.bounds_with_transform(&self.scrolled_matrix())
     }
 
     /// The world bounding box of this object including children, relative to the stage.
@@ -416,12 +555,18 @@ pub trait TDisplayObject<'gc>:
     /// This function recurses down and transforms the AABB each child before adding
     /// it to the bounding box. This gives a tighter AABB then if we simply transformed
     /// the overall AABB.
+    ///
+    /// Objects with a scroll rectangle are bounded by it instead.
     fn bounds_with_transform(&self, matrix: &Matrix) -> BoundingBox {
+        if let Some(scroll_rect) = self.scroll_rect() {
+            return BoundingBox::from(scroll_rect).transform(matrix);
+        }
+
         let mut bounds = self.self_bounds().transform(matrix);
 
         if let Some(ctr) = self.as_container() {
             for child in ctr.iter_execution_list() {
-                let matrix = *matrix * *child.matrix();
+                let matrix = *matrix * child.scrolled_matrix();
                 bounds.union(&child.bounds_with_transform(&matrix));
             }
         }
@@ -444,12 +589,24 @@ pub trait TDisplayObject<'gc>:
         color_transform: &ColorTransform,
     );
 
+    /// Returns the matrix for transforming from this object's local space to
+    /// its parent's, including the scrolling applied by its scroll rectangle.
+    fn scrolled_matrix(&self) -> Matrix {
+        let matrix = *self.matrix();
+        match self.scroll_rect() {
+            Some(scroll_rect) => {
+                matrix * Matrix::translate(scroll_rect.x_min * -1, scroll_rect.y_min * -1)
+            }
+            None => matrix,
+        }
+    }
+
     /// Returns the matrix for transforming from this object's local space to global stage space.
     fn local_to_global_matrix(&self) -> Matrix {
         let mut node = self.parent();
-        let mut matrix = *self.matrix();
+        let mut matrix = self.scrolled_matrix();
         while let Some(display_object) = node {
-            matrix = *display_object.matrix() * matrix;
+            matrix = display_object.scrolled_matrix() * matrix;
             node = display_object.parent();
         }
 
@@ -459,9 +616,9 @@ pub trait TDisplayObject<'gc>:
     /// Returns the matrix for transforming from global stage to this object's local space.
     fn global_to_local_matrix(&self) -> Matrix {
         let mut node = self.parent();
-        let mut matrix = *self.matrix();
+        let mut matrix = self.scrolled_matrix();
         while let Some(display_object) = node {
-            matrix = *display_object.matrix() * matrix;
+            matrix = display_object.scrolled_matrix() * matrix;
             node = display_object.parent();
         }
 
@@ -655,6 +812,40 @@ pub trait TDisplayObject<'gc>:
 
     fn clip_depth(&self) -> Depth;
     fn set_clip_depth(&self, context: MutationContext<'gc, '_>, depth: Depth);
+
+    /// The blend mode used when compositing this object onto its parent.
+    fn blend_mode(&self) -> BlendMode;
+    fn set_blend_mode(&self, context: MutationContext<'gc, '_>, blend_mode: BlendMode);
+
+    /// The filters applied to this object when it is rendered.
+    fn filters(&self) -> Vec<Filter>;
+    fn set_filters(&self, context: MutationContext<'gc, '_>, filters: Vec<Filter>);
+
+    /// The color drawn behind this object, filling its bounds.
+    /// Returned by the `opaqueBackground` ActionScript property.
+    fn opaque_background(&self) -> Option<Color>;
+    fn set_opaque_background(&self, context: MutationContext<'gc, '_>, color: Option<Color>);
+
+    /// The area of this object that is displayed, in its own coordinates.
+    /// The object is clipped to it, and its contents are scrolled so that
+    /// the rectangle's top-left corner lies at the object's origin.
+    /// Returned by the `scrollRect` ActionScript property.
+    fn scroll_rect(&self) -> Option<Rectangle>;
+    fn set_scroll_rect(&self, context: MutationContext<'gc, '_>, rect: Option<Rectangle>);
+
+    /// The position of this object in the tab order, if it has one.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<i32>;
+    fn set_tab_index(&self, context: MutationContext<'gc, '_>, tab_index: Option<i32>);
+
+    /// Tests if a given stage position point lies within the scroll
+    /// rectangle of this object. Objects without one are never clipped.
+    fn hit_test_scroll_rect(&self, pos: (Twips, Twips)) -> bool {
+        match self.scroll_rect() {
+            Some(scroll_rect) => BoundingBox::from(scroll_rect).contains(self.global_to_local(pos)),
+            None => true,
+        }
+    }
     fn parent(&self) -> Option<DisplayObject<'gc>>;
     fn set_parent(&self, context: MutationContext<'gc, '_>, parent: Option<DisplayObject<'gc>>);
     fn prev_sibling(&self) -> Option<DisplayObject<'gc>>;
@@ -700,6 +891,15 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object has been asked to be cached as a bitmap
+    /// when rendered.
+    /// The `cacheAsBitmap` ActionScript property is also true for any object
+    /// with filters.
+    fn is_bitmap_cached(&self) -> bool;
+
+    /// Sets whether this display object is cached as a bitmap when rendered.
+    fn set_is_bitmap_cached(&self, context: MutationContext<'gc, '_>, value: bool);
+
     /// Whether this display object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     fn transformed_by_script(&self) -> bool;
@@ -708,6 +908,111 @@ pub trait TDisplayObject<'gc>:
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     fn set_transformed_by_script(&self, context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object receives double click events
+    /// (`doubleClickEnabled` property).
+    fn double_click_enabled(&self) -> bool;
+
+    /// Sets whether this display object receives double click events.
+    fn set_double_click_enabled(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// The time since launch at which this display object was last clicked,
+    /// unless that click ended a double click.
+    fn last_click_time(&self) -> Option<Duration>;
+
+    /// Sets the time at which this display object was last clicked.
+    fn set_last_click_time(&self, context: MutationContext<'gc, '_>, time: Option<Duration>);
+
+    /// Whether this display object has changed in a way that needs the stage
+    /// to be redrawn, since the changes to the stage were last taken.
+    fn is_dirty(&self) -> bool;
+
+    /// Sets whether this display object needs the stage to be redrawn.
+    ///
+    /// Changes to the properties of display objects, their children and their
+    /// contents set this flag themselves.
+    fn set_dirty(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// The area of the stage this object covered when its changes were last
+    /// taken, if it is known.
+    fn dirty_bounds(&self) -> Option<BoundingBox>;
+
+    fn set_dirty_bounds(&self, context: MutationContext<'gc, '_>, bounds: Option<BoundingBox>);
+
+    /// Whether the contents of this display object have changed in a way that
+    /// isn't tracked by its dirty flag, such as the pixels of a `BitmapData`
+    /// that is shared with other objects.
+    fn contents_changed(&self) -> bool {
+        false
+    }
+
+    /// Whether this object or any of its children need the stage to be
+    /// redrawn.
+    fn is_dirty_recursive(&self) -> bool {
+        if self.is_dirty() || self.contents_changed() {
+            return true;
+        }
+
+        self.as_container()
+            .map(|container| {
+                container
+                    .iter_render_list()
+                    .any(|child| child.is_dirty_recursive())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Take the area of the stage that has changed in this object and its
+    /// children, clearing their dirty flags.
+    ///
+    /// The area covers both where the changed objects are now, and where they
+    /// were when their changes were last taken. Children of a changed object
+    /// are covered by it, and no longer know where they were; if one of those
+    /// changes later, the whole stage is taken as changed.
+    fn take_dirty_region(
+        &self,
+        context: MutationContext<'gc, '_>,
+        parent_dirty: bool,
+    ) -> DirtyRegion {
+        let dirty = self.is_dirty() || self.contents_changed();
+        let mut region = DirtyRegion::default();
+        if parent_dirty {
+            self.set_dirty_bounds(context, None);
+        } else if dirty {
+            let bounds = self.world_bounds();
+            match self.dirty_bounds() {
+                Some(old_bounds) => region.add(&old_bounds),
+                None => region.add_whole_stage(),
+            }
+            region.add(&bounds);
+            self.set_dirty_bounds(context, Some(bounds));
+        }
+        if self.is_dirty() {
+            self.set_dirty(context, false);
+        }
+
+        if let Some(container) = self.as_container() {
+            for child in container.iter_render_list() {
+                region.union(&child.take_dirty_region(context, parent_dirty || dirty));
+            }
+        }
+
+        // Our bounds have to keep covering the changes to our children, as
+        // they are redrawn from them if we change later.
+        if !parent_dirty && !dirty && !region.is_empty() {
+            let bounds = self.dirty_bounds().and_then(|mut bounds| {
+                if region.whole_stage {
+                    None
+                } else {
+                    bounds.union(&region.bounds);
+                    Some(bounds)
+                }
+            });
+            self.set_dirty_bounds(context, bounds);
+        }
+
+        region
+    }
+
     /// Called whenever the focus tracker has deemed this display object worthy, or no longer worthy,
     /// of being the currently focused object.
     /// This should only be called by the focus manager. To change a focus, go through that.
@@ -742,6 +1047,115 @@ pub trait TDisplayObject<'gc>:
     fn run_frame(&self, _context: &mut UpdateContext<'_, 'gc, '_>) {}
     fn render(&self, _context: &mut RenderContext<'_, 'gc>) {}
 
+    /// Rasterize the content of this object, excluding its children, into a
+    /// bitmap for `BitmapData.draw`.
+    fn rasterize_self(&self, _storage: &mut BitmapDataStorage, _transform: &Transform) {}
+
+    /// Renders this object, compositing it onto its parent with its blend mode.
+    fn render_with_blend_mode(&self, context: &mut RenderContext<'_, 'gc>) {
+        let blend_mode = self.blend_mode();
+        if blend_mode == BlendMode::Normal {
+            self.render_with_filters(context);
+        } else {
+            context.renderer.push_blend_mode(blend_mode);
+            self.render_with_filters(context);
+            context.renderer.pop_blend_mode();
+        }
+    }
+
+    /// Renders this object, applying its filters to it.
+    ///
+    /// Filter sizes are in stage pixels, regardless of the transform of the
+    /// object, so they are only scaled along with the stage.
+    fn render_with_filters(&self, context: &mut RenderContext<'_, 'gc>) {
+        let filters = self.filters();
+        let matrix = context.transform_stack.transform().matrix * self.scrolled_matrix();
+        let bounds = self.bounds_with_transform(&matrix);
+        if filters.is_empty() || !bounds.valid {
+            self.render_with_scroll_rect(context);
+            return;
+        }
+
+        let filters: Vec<Filter> = filters
+            .iter()
+            .map(|filter| render::scale_filter(filter, context.view_scale))
+            .collect();
+        let (margin_x, margin_y) = render::filter_margins(&filters);
+        let margin_x = Twips::from_pixels(margin_x.ceil());
+        let margin_y = Twips::from_pixels(margin_y.ceil());
+        let bounds = swf::Rectangle {
+            x_min: bounds.x_min - margin_x,
+            x_max: bounds.x_max + margin_x,
+            y_min: bounds.y_min - margin_y,
+            y_max: bounds.y_max + margin_y,
+        };
+
+        context.renderer.push_filters(&filters, &bounds);
+        self.render_with_scroll_rect(context);
+        context.renderer.pop_filters();
+    }
+
+    /// Renders this object clipped to its scroll rectangle and scrolled by
+    /// it, if it has one.
+    fn render_with_scroll_rect(&self, context: &mut RenderContext<'_, 'gc>) {
+        let scroll_rect = match self.scroll_rect() {
+            Some(scroll_rect) => scroll_rect,
+            None => {
+                self.render_with_opaque_background(context);
+                return;
+            }
+        };
+
+        let matrix = *self.matrix();
+        let clip = context.transform_stack.transform().matrix
+            * matrix
+            * Matrix::create_box(
+                (scroll_rect.x_max - scroll_rect.x_min).to_pixels() as f32,
+                (scroll_rect.y_max - scroll_rect.y_min).to_pixels() as f32,
+                0.0,
+                Twips::zero(),
+                Twips::zero(),
+            );
+        context.renderer.push_mask();
+        context.renderer.draw_rect(Color::from_rgb(0, 0xff), &clip);
+        context.renderer.activate_mask();
+
+        // `render` pushes this object's own matrix, so the scrolling is
+        // applied in its parent's space to end up beneath that matrix.
+        let mut inverse = matrix;
+        inverse.invert();
+        context.transform_stack.push(&Transform {
+            matrix: self.scrolled_matrix() * inverse,
+            ..Default::default()
+        });
+        self.render_with_opaque_background(context);
+        context.transform_stack.pop();
+
+        context.renderer.deactivate_mask();
+        context.renderer.draw_rect(Color::from_rgb(0, 0xff), &clip);
+        context.renderer.pop_mask();
+    }
+
+    /// Renders this object on top of its opaque background, if it has one.
+    fn render_with_opaque_background(&self, context: &mut RenderContext<'_, 'gc>) {
+        if let Some(color) = self.opaque_background() {
+            let bounds = self.self_bounds();
+            if bounds.valid {
+                let background = Matrix::create_box(
+                    bounds.width().to_pixels() as f32,
+                    bounds.height().to_pixels() as f32,
+                    0.0,
+                    bounds.x_min,
+                    bounds.y_min,
+                );
+                let matrix = context.transform_stack.transform().matrix * *self.matrix();
+                context.renderer.draw_rect(color, &(matrix * background));
+            }
+        }
+
+        self.render(context);
+    }
+
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // Unload children.
         if let Some(ctr) = self.as_container() {
@@ -772,15 +1186,29 @@ pub trait TDisplayObject<'gc>:
     fn as_morph_shape(&self) -> Option<MorphShape<'gc>> {
         None
     }
+    fn as_bitmap(&self) -> Option<Bitmap<'gc>> {
+        None
+    }
+    fn as_video(&self) -> Option<Video<'gc>> {
+        None
+    }
     fn as_container(self) -> Option<DisplayObjectContainer<'gc>> {
         None
     }
 
+    /// Retrieve the drawing that the drawing API draws into, for display
+    /// objects that support it.
+    fn as_drawing(&self, _gc_context: MutationContext<'gc, '_>) -> Option<RefMut<Drawing>> {
+        None
+    }
+
     fn apply_place_object(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         place_object: &swf::PlaceObject,
     ) {
+        let gc_context = context.gc_context;
+
         // PlaceObject tags only apply if this onject has not been dynamically moved by AS code.
         if !self.transformed_by_script() {
             if let Some(matrix) = &place_object.matrix {
@@ -795,9 +1223,26 @@ pub trait TDisplayObject<'gc>:
             if let Some(clip_depth) = place_object.clip_depth {
                 self.set_clip_depth(gc_context, clip_depth.into());
             }
+            if let Some(blend_mode) = place_object.blend_mode {
+                self.set_blend_mode(gc_context, blend_mode);
+            }
+            if let Some(filters) = &place_object.filters {
+                self.set_filters(gc_context, filters.clone());
+            }
+            if let Some(is_bitmap_cached) = place_object.is_bitmap_cached {
+                self.set_is_bitmap_cached(gc_context, is_bitmap_cached);
+            }
+            if let Some(is_visible) = place_object.is_visible {
+                self.set_visible(gc_context, is_visible);
+            }
+            if let Some(background_color) = &place_object.background_color {
+                self.set_opaque_background(gc_context, Some(background_color.clone()));
+            }
             if let Some(ratio) = place_object.ratio {
                 if let Some(mut morph_shape) = self.as_morph_shape() {
                     morph_shape.set_ratio(gc_context, ratio);
+                } else if let Some(video) = self.as_video() {
+                    video.seek(context, ratio.into());
                 }
             }
             // Clip events only apply to movie clips.
@@ -828,6 +1273,11 @@ pub trait TDisplayObject<'gc>:
         self.set_matrix(gc_context, &*other.matrix());
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
+        self.set_blend_mode(gc_context, other.blend_mode());
+        self.set_filters(gc_context, other.filters());
+        self.set_is_bitmap_cached(gc_context, other.is_bitmap_cached());
+        self.set_opaque_background(gc_context, other.opaque_background());
+        self.set_scroll_rect(gc_context, other.scroll_rect());
         self.set_name(gc_context, &*other.name());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
@@ -847,6 +1297,12 @@ pub trait TDisplayObject<'gc>:
         Avm2Value::Undefined // todo: see above
     }
 
+    /// Associate an AVM2 object with this display object.
+    ///
+    /// This is used for display objects constructed by script, whose object
+    /// is created before the display object itself.
+    fn set_object2(&self, _mc: MutationContext<'gc, '_>, _to: Avm2Object<'gc>) {}
+
     /// Tests if a given stage position point intersects with the world bounds of this object.
     fn hit_test_bounds(&self, pos: (Twips, Twips)) -> bool {
         self.world_bounds().contains(pos)
@@ -913,20 +1369,25 @@ pub trait TDisplayObject<'gc>:
 
     /// Obtain the top-most parent of the display tree hierarchy.
     ///
+    /// AVM1 movies loaded into an AVM2 movie are their own root, so the
+    /// search stops short of any ancestor with an AVM2 object.
+    ///
     /// This function can panic in the rare case that a top-level display
     /// object has not been post-instantiated, or that a top-level display
     /// object does not implement `object`.
     fn root(&self) -> DisplayObject<'gc> {
-        let mut parent = self.parent();
+        let mut parent = self
+            .parent()
+            .filter(|p| !matches!(p.object2(), Avm2Value::Object(_)));
 
         while let Some(p) = parent {
             let grandparent = p.parent();
 
-            if grandparent.is_none() {
-                break;
+            match grandparent {
+                None => break,
+                Some(gp) if matches!(gp.object2(), Avm2Value::Object(_)) => break,
+                Some(_) => parent = grandparent,
             }
-
-            parent = grandparent;
         }
 
         parent
@@ -1056,6 +1517,65 @@ macro_rules! impl_display_object_sansbounds {
         ) {
             self.0.write(context).$field.set_clip_depth(context, depth)
         }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            blend_mode: swf::BlendMode,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_blend_mode(context, blend_mode)
+        }
+        fn filters(&self) -> Vec<swf::Filter> {
+            self.0.read().$field.filters()
+        }
+        fn set_filters(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            filters: Vec<swf::Filter>,
+        ) {
+            self.0.write(context).$field.set_filters(context, filters)
+        }
+        fn opaque_background(&self) -> Option<swf::Color> {
+            self.0.read().$field.opaque_background()
+        }
+        fn set_opaque_background(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            color: Option<swf::Color>,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_opaque_background(context, color)
+        }
+        fn scroll_rect(&self) -> Option<swf::Rectangle> {
+            self.0.read().$field.scroll_rect()
+        }
+        fn set_scroll_rect(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            rect: Option<swf::Rectangle>,
+        ) {
+            self.0.write(context).$field.set_scroll_rect(context, rect)
+        }
+        fn tab_index(&self) -> Option<i32> {
+            self.0.read().$field.tab_index()
+        }
+        fn set_tab_index(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            tab_index: Option<i32>,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_tab_index(context, tab_index)
+        }
         fn parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
             self.0.read().$field.parent()
         }
@@ -1098,6 +1618,12 @@ macro_rules! impl_display_object_sansbounds {
         fn set_visible(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_visible(value);
         }
+        fn is_bitmap_cached(&self) -> bool {
+            self.0.read().$field.is_bitmap_cached()
+        }
+        fn set_is_bitmap_cached(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_is_bitmap_cached(value);
+        }
         fn transformed_by_script(&self) -> bool {
             self.0.read().$field.transformed_by_script()
         }
@@ -1111,6 +1637,42 @@ macro_rules! impl_display_object_sansbounds {
                 .$field
                 .set_transformed_by_script(value)
         }
+        fn double_click_enabled(&self) -> bool {
+            self.0.read().$field.double_click_enabled()
+        }
+        fn set_double_click_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: bool,
+        ) {
+            self.0.write(context).$field.set_double_click_enabled(value);
+        }
+        fn last_click_time(&self) -> Option<std::time::Duration> {
+            self.0.read().$field.last_click_time()
+        }
+        fn set_last_click_time(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            time: Option<std::time::Duration>,
+        ) {
+            self.0.write(context).$field.set_last_click_time(time);
+        }
+        fn is_dirty(&self) -> bool {
+            self.0.read().$field.is_dirty()
+        }
+        fn set_dirty(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_dirty(value)
+        }
+        fn dirty_bounds(&self) -> Option<crate::bounding_box::BoundingBox> {
+            self.0.read().$field.dirty_bounds()
+        }
+        fn set_dirty_bounds(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            bounds: Option<crate::bounding_box::BoundingBox>,
+        ) {
+            self.0.write(context).$field.set_dirty_bounds(bounds)
+        }
         fn placed_by_script(&self) -> bool {
             self.0.read().$field.placed_by_script()
         }
@@ -1174,6 +1736,9 @@ enum DisplayObjectFlags {
     /// If this object is visible (`_visible` property).
     Visible,
 
+    /// Whether this object is cached as a bitmap (`cacheAsBitmap` property).
+    CacheAsBitmap,
+
     /// Whether the `_xscale`, `_yscale` and `_rotation` of the object have been calculated and cached.
     ScaleRotationCached,
 
@@ -1184,4 +1749,86 @@ enum DisplayObjectFlags {
     /// Whether this object has been placed on the timeline by ActionScript 3.
     /// When this flag is set, changes from SWF `RemoveObject` tags are ignored.
     PlacedByScript,
+
+    /// Whether this object has changed in a way that needs the stage to be
+    /// redrawn.
+    Dirty,
+
+    /// Whether this object receives double click events (`doubleClickEnabled` property).
+    DoubleClickEnabled,
+}
+
+/// The area of the stage that has changed since it was last rendered.
+#[derive(Clone, Debug, Default)]
+pub struct DirtyRegion {
+    /// The bounds of the changes, in stage coordinates.
+    pub bounds: BoundingBox,
+
+    /// Whether the changes may cover anywhere on the stage.
+    pub whole_stage: bool,
+}
+
+impl DirtyRegion {
+    /// Whether nothing has changed.
+    pub fn is_empty(&self) -> bool {
+        !self.whole_stage && !self.bounds.valid
+    }
+
+    /// Add an area of the stage to the region.
+    pub fn add(&mut self, bounds: &BoundingBox) {
+        self.bounds.union(bounds);
+    }
+
+    /// Mark the whole stage as changed.
+    pub fn add_whole_stage(&mut self) {
+        self.whole_stage = true;
+    }
+
+    /// Add another region to this one.
+    pub fn union(&mut self, other: &DirtyRegion) {
+        self.bounds.union(&other.bounds);
+        self.whole_stage |= other.whole_stage;
+    }
+}
+
+/// The name of a blend mode, as used by the `blendMode` ActionScript property.
+pub fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::Normal => "normal",
+        BlendMode::Layer => "layer",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Darken => "darken",
+        BlendMode::Difference => "difference",
+        BlendMode::Add => "add",
+        BlendMode::Subtract => "subtract",
+        BlendMode::Invert => "invert",
+        BlendMode::Alpha => "alpha",
+        BlendMode::Erase => "erase",
+        BlendMode::Overlay => "overlay",
+        BlendMode::HardLight => "hardlight",
+    }
+}
+
+/// Look up a blend mode by the name used by the `blendMode` ActionScript
+/// property.
+pub fn blend_mode_from_name(name: &str) -> Option<BlendMode> {
+    Some(match name {
+        "normal" => BlendMode::Normal,
+        "layer" => BlendMode::Layer,
+        "multiply" => BlendMode::Multiply,
+        "screen" => BlendMode::Screen,
+        "lighten" => BlendMode::Lighten,
+        "darken" => BlendMode::Darken,
+        "difference" => BlendMode::Difference,
+        "add" => BlendMode::Add,
+        "subtract" => BlendMode::Subtract,
+        "invert" => BlendMode::Invert,
+        "alpha" => BlendMode::Alpha,
+        "erase" => BlendMode::Erase,
+        "overlay" => BlendMode::Overlay,
+        "hardlight" => BlendMode::HardLight,
+        _ => return None,
+    })
 }
@@ -1,11 +1,13 @@
 //! Bitmap display object
 
-use crate::backend::render::BitmapHandle;
+use crate::avm2::{Object as Avm2Object, TObject as _, Value as Avm2Value};
+use crate::backend::render::{self, BitmapHandle};
+use crate::bitmap_data::BitmapDataStorage;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, Gc, GcCell};
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
 
 /// A Bitmap display object is a raw bitamp on the stage.
 /// This can only be instanitated on the display list in SWFv9 AVM2 files.
@@ -21,7 +23,22 @@ pub struct Bitmap<'gc>(GcCell<'gc, BitmapData<'gc>>);
 #[derive(Clone, Debug)]
 pub struct BitmapData<'gc> {
     base: DisplayObjectBase<'gc>,
-    static_data: Gc<'gc, BitmapStatic>,
+
+    /// The bitmap defined in the SWF, if this bitmap was placed from the
+    /// timeline.
+    static_data: Option<Gc<'gc, BitmapStatic>>,
+
+    /// The AVM2 `BitmapData` whose pixels are drawn, if one was set by script.
+    ///
+    /// This takes precedence over the bitmap defined in the SWF.
+    bitmap_data: Option<Avm2Object<'gc>>,
+
+    /// The AVM2 object associated with this bitmap, if it was constructed by
+    /// script.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Whether this bitmap is smoothed when scaled or rotated.
+    smoothing: bool,
 }
 
 impl<'gc> Bitmap<'gc> {
@@ -31,35 +48,123 @@ impl<'gc> Bitmap<'gc> {
         bitmap_handle: BitmapHandle,
         width: u16,
         height: u16,
+        source: BitmapSource,
     ) -> Self {
         Bitmap(GcCell::allocate(
             context.gc_context,
             BitmapData {
                 base: Default::default(),
-                static_data: Gc::allocate(
+                static_data: Some(Gc::allocate(
                     context.gc_context,
                     BitmapStatic {
                         id,
                         bitmap_handle,
                         width,
                         height,
+                        source,
                     },
-                ),
+                )),
+                bitmap_data: None,
+                avm2_object: None,
+                smoothing: false,
+            },
+        ))
+    }
+
+    /// Construct an empty bitmap for an AVM2 `Bitmap` object.
+    ///
+    /// Its pixels are drawn from the given `BitmapData` object, if any.
+    pub fn new_with_avm2(
+        gc_context: MutationContext<'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+        bitmap_data: Option<Avm2Object<'gc>>,
+    ) -> Self {
+        Bitmap(GcCell::allocate(
+            gc_context,
+            BitmapData {
+                base: Default::default(),
+                static_data: None,
+                bitmap_data,
+                avm2_object: Some(avm2_object),
+                smoothing: false,
             },
         ))
     }
 
     #[allow(dead_code)]
-    pub fn bitmap_handle(self) -> BitmapHandle {
-        self.0.read().static_data.bitmap_handle
+    pub fn bitmap_handle(self) -> Option<BitmapHandle> {
+        self.0.read().static_data.map(|s| s.bitmap_handle)
+    }
+
+    /// Decode the pixels of the bitmap defined in the SWF, such as for
+    /// `BitmapData.loadBitmap`.
+    pub fn decode_pixels(self) -> Result<BitmapDataStorage, Box<dyn std::error::Error>> {
+        let static_data = self
+            .0
+            .read()
+            .static_data
+            .ok_or("Bitmap was not defined in the SWF")?;
+        match &static_data.source {
+            BitmapSource::Jpeg { data, alpha_data } => {
+                let bitmap = render::decode_define_bits_jpeg(data, alpha_data.as_deref())?;
+                let transparent = matches!(bitmap.data, render::BitmapFormat::Rgba(_));
+                Ok(BitmapDataStorage::from_bitmap(bitmap, transparent))
+            }
+            BitmapSource::Lossless(define_bits_lossless) => {
+                let bitmap = render::decode_define_bits_lossless(define_bits_lossless)?;
+                let transparent = define_bits_lossless.version >= 2;
+                Ok(BitmapDataStorage::from_bitmap(bitmap, transparent))
+            }
+        }
+    }
+
+    /// The AVM2 `BitmapData` object drawn by this bitmap, if any.
+    pub fn bitmap_data(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().bitmap_data
+    }
+
+    pub fn set_bitmap_data(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        bitmap_data: Option<Avm2Object<'gc>>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.bitmap_data = bitmap_data;
+        write.base.set_dirty(true);
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, gc_context: MutationContext<'gc, '_>, smoothing: bool) {
+        let mut write = self.0.write(gc_context);
+        write.smoothing = smoothing;
+        write.base.set_dirty(true);
     }
 
     pub fn width(self) -> u16 {
-        self.0.read().static_data.width
+        let read = self.0.read();
+        if let Some(bitmap_data) = read.bitmap_data {
+            bitmap_data
+                .as_bitmap_data()
+                .map(|storage| storage.width() as u16)
+                .unwrap_or(0)
+        } else {
+            read.static_data.map(|s| s.width).unwrap_or(0)
+        }
     }
 
     pub fn height(self) -> u16 {
-        self.0.read().static_data.height
+        let read = self.0.read();
+        if let Some(bitmap_data) = read.bitmap_data {
+            bitmap_data
+                .as_bitmap_data()
+                .map(|storage| storage.height() as u16)
+                .unwrap_or(0)
+        } else {
+            read.static_data.map(|s| s.height).unwrap_or(0)
+        }
     }
 }
 
@@ -67,15 +172,15 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
     impl_display_object!(base);
 
     fn id(&self) -> CharacterId {
-        self.0.read().static_data.id
+        self.0.read().static_data.map(|s| s.id).unwrap_or_default()
     }
 
     fn self_bounds(&self) -> BoundingBox {
         BoundingBox {
             x_min: Twips::new(0),
             y_min: Twips::new(0),
-            x_max: Twips::new(Bitmap::width(*self)),
-            y_max: Twips::new(Bitmap::height(*self)),
+            x_max: Twips::from_pixels(Bitmap::width(*self).into()),
+            y_max: Twips::from_pixels(Bitmap::height(*self).into()),
             valid: true,
         }
     }
@@ -84,20 +189,61 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         // Noop
     }
 
+    fn contents_changed(&self) -> bool {
+        // Pixels drawn into a `BitmapData` are uploaded when it's next rendered.
+        self.0
+            .read()
+            .bitmap_data
+            .and_then(|bitmap_data| {
+                bitmap_data
+                    .as_bitmap_data()
+                    .map(|storage| storage.is_dirty())
+            })
+            .unwrap_or(false)
+    }
+
     fn render(&self, context: &mut RenderContext) {
         if !self.world_bounds().intersects(&context.view_bounds) {
             // Off-screen; culled
             return;
         }
 
-        context.transform_stack.push(&*self.transform());
+        let read = self.0.read();
+        let bitmap_handle = if let Some(bitmap_data) = read.bitmap_data {
+            bitmap_data
+                .as_bitmap_data()
+                .and_then(|storage| storage.bitmap_handle(context.renderer))
+        } else {
+            read.static_data.map(|s| s.bitmap_handle)
+        };
+
+        if let Some(bitmap_handle) = bitmap_handle {
+            context.transform_stack.push(&*self.transform());
+
+            context.renderer.render_bitmap(
+                bitmap_handle,
+                context.transform_stack.transform(),
+                self.smoothing(),
+            );
+
+            context.transform_stack.pop();
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
 
-        context.renderer.render_bitmap(
-            self.0.read().static_data.bitmap_handle,
-            context.transform_stack.transform(),
-        );
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).avm2_object = Some(to);
+    }
 
-        context.transform_stack.pop();
+    fn as_bitmap(&self) -> Option<Self> {
+        Some(*self)
     }
 }
 
@@ -105,6 +251,8 @@ unsafe impl<'gc> gc_arena::Collect for BitmapData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.static_data.trace(cc);
+        self.bitmap_data.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
@@ -115,6 +263,24 @@ struct BitmapStatic {
     bitmap_handle: BitmapHandle,
     width: u16,
     height: u16,
+
+    /// The encoded image, kept so that its pixels can be read by script.
+    /// The renderer does not give access to its copy of them.
+    source: BitmapSource,
+}
+
+/// The encoded image of a bitmap defined in the SWF.
+#[derive(Clone, Debug)]
+pub enum BitmapSource {
+    /// JPEG, PNG or GIF data from a `DefineBits` tag, with any JPEG tables
+    /// already applied.
+    Jpeg {
+        data: Vec<u8>,
+        alpha_data: Option<Vec<u8>>,
+    },
+
+    /// A `DefineBitsLossless` tag.
+    Lossless(swf::DefineBitsLossless),
 }
 
 unsafe impl<'gc> gc_arena::Collect for BitmapStatic {
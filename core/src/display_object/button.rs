@@ -1,4 +1,5 @@
 use crate::avm1::{Object, StageObject, Value};
+use crate::backend::audio::start_sound_with_info;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::container::ChildContainer;
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
@@ -124,6 +125,7 @@ impl<'gc> Button<'gc> {
         let movie = self.movie().unwrap();
         let mut write = self.0.write(context.gc_context);
         write.state = state;
+        write.base.set_dirty(true);
         let swf_state = match state {
             ButtonState::Up => swf::ButtonState::Up,
             ButtonState::Over => swf::ButtonState::Over,
@@ -166,6 +168,14 @@ impl<'gc> Button<'gc> {
         self.0.read().enabled
     }
 
+    /// Whether this button is tracked as a menu item.
+    ///
+    /// Menu buttons react to the mouse being dragged over them, even if the
+    /// mouse button was pressed elsewhere.
+    pub fn is_tracked_as_menu(self) -> bool {
+        self.0.read().tracking == ButtonTracking::Menu
+    }
+
     pub fn set_enabled(self, context: &mut UpdateContext<'_, 'gc, '_>, enabled: bool) {
         self.0.write(context.gc_context).enabled = enabled;
         if !enabled {
@@ -300,7 +310,7 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
         // The button is hovered if the mouse is over any child nodes.
-        if self.visible() {
+        if self.visible() && self.hit_test_scroll_rect(point) {
             for child in self.0.read().hit_area.values() {
                 if child.hit_test_shape(context, point) {
                     return Some(self_node);
@@ -354,53 +364,73 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
             }
         }
 
-        let mut handled = ClipEventResult::NotHandled;
         let self_display_object = (*self).into();
         let mut write = self.0.write(context.gc_context);
+        let static_data = write.static_data;
+        let static_data = static_data.read();
+        let is_menu = write.tracking == ButtonTracking::Menu;
 
         // Translate the clip event to a button event, based on how the button state changes.
-        let cur_state = write.state;
-        let new_state = match event {
-            ClipEvent::RollOut => ButtonState::Up,
-            ClipEvent::RollOver => ButtonState::Over,
-            ClipEvent::Press => ButtonState::Down,
-            ClipEvent::Release => ButtonState::Over,
-            ClipEvent::KeyPress { key_code } => {
-                handled = write.run_actions(
+        let (new_state, condition, sound) = match (event, write.state) {
+            (ClipEvent::KeyPress { key_code }, _) => {
+                return write.run_actions(
                     context,
                     swf::ButtonActionCondition::KeyPress,
                     Some(key_code),
                 );
-                cur_state
             }
+            (ClipEvent::RollOver, _) => (
+                ButtonState::Over,
+                swf::ButtonActionCondition::IdleToOverUp,
+                static_data.up_to_over_sound.as_ref(),
+            ),
+            (ClipEvent::RollOut, _) => (
+                ButtonState::Up,
+                swf::ButtonActionCondition::OverUpToIdle,
+                static_data.over_to_up_sound.as_ref(),
+            ),
+            (ClipEvent::Press, _) => (
+                ButtonState::Down,
+                swf::ButtonActionCondition::OverUpToOverDown,
+                static_data.over_to_down_sound.as_ref(),
+            ),
+            (ClipEvent::Release, ButtonState::Down) => (
+                ButtonState::Over,
+                swf::ButtonActionCondition::OverDownToOverUp,
+                static_data.down_to_over_sound.as_ref(),
+            ),
+            // Menu buttons go idle when the mouse is dragged off of them...
+            (ClipEvent::DragOut, ButtonState::Down) if is_menu => (
+                ButtonState::Up,
+                swf::ButtonActionCondition::OverDownToIdle,
+                None,
+            ),
+            // ...while push buttons stay pressed and show their over state.
+            (ClipEvent::DragOut, ButtonState::Down) => (
+                ButtonState::Over,
+                swf::ButtonActionCondition::OverDownToOutDown,
+                None,
+            ),
+            (ClipEvent::DragOver, _) if is_menu => (
+                ButtonState::Down,
+                swf::ButtonActionCondition::IdleToOverDown,
+                None,
+            ),
+            (ClipEvent::DragOver, _) => (
+                ButtonState::Down,
+                swf::ButtonActionCondition::OutDownToOverDown,
+                None,
+            ),
+            (ClipEvent::ReleaseOutside, ButtonState::Over) if !is_menu => (
+                ButtonState::Up,
+                swf::ButtonActionCondition::OutDownToIdle,
+                static_data.over_to_up_sound.as_ref(),
+            ),
             _ => return ClipEventResult::NotHandled,
         };
 
-        match (cur_state, new_state) {
-            (ButtonState::Up, ButtonState::Over) => {
-                write.run_actions(context, swf::ButtonActionCondition::IdleToOverUp, None);
-                write.play_sound(context, write.static_data.read().up_to_over_sound.as_ref());
-            }
-            (ButtonState::Over, ButtonState::Up) => {
-                write.run_actions(context, swf::ButtonActionCondition::OverUpToIdle, None);
-                write.play_sound(context, write.static_data.read().over_to_up_sound.as_ref());
-            }
-            (ButtonState::Over, ButtonState::Down) => {
-                write.run_actions(context, swf::ButtonActionCondition::OverUpToOverDown, None);
-                write.play_sound(
-                    context,
-                    write.static_data.read().over_to_down_sound.as_ref(),
-                );
-            }
-            (ButtonState::Down, ButtonState::Over) => {
-                write.run_actions(context, swf::ButtonActionCondition::OverDownToOverUp, None);
-                write.play_sound(
-                    context,
-                    write.static_data.read().down_to_over_sound.as_ref(),
-                );
-            }
-            _ => (),
-        };
+        let handled = write.run_actions(context, condition, None);
+        write.play_sound(context, sound);
 
         // Queue ActionScript-defined event handlers after the SWF defined ones.
         // (e.g., clip.onRelease = foo).
@@ -419,6 +449,7 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         }
 
         if write.state != new_state {
+            drop(static_data);
             drop(write);
             self.set_state(context, new_state);
         }
@@ -460,7 +491,7 @@ impl<'gc> ButtonData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.audio.start_sound(sound_handle, sound_info);
+                start_sound_with_info(context.audio, sound_handle, sound_info);
             }
         }
     }
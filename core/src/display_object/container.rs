@@ -12,6 +12,7 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::ops::RangeBounds;
+use swf::Twips;
 
 /// The three lists that a display object container is supposed to maintain.
 #[derive(EnumSetType)]
@@ -212,8 +213,14 @@ pub trait TDisplayObjectContainer<'gc>:
             }
             if context.allow_mask && child.clip_depth() > 0 && child.allow_as_mask() {
                 // Push and render the mask.
+                // Nested masks intersect with the masks below them on the
+                // stack, so they can't outlast them.
                 clip_depth_stack.push((clip_depth, child));
-                clip_depth = child.clip_depth();
+                clip_depth = if clip_depth > 0 {
+                    child.clip_depth().min(clip_depth)
+                } else {
+                    child.clip_depth()
+                };
                 context.renderer.push_mask();
                 context.allow_mask = false;
                 child.render(context);
@@ -221,7 +228,7 @@ pub trait TDisplayObjectContainer<'gc>:
                 context.renderer.activate_mask();
             } else if child.visible() {
                 // Normal child.
-                child.render(context);
+                child.render_with_blend_mode(context);
             }
         }
 
@@ -234,6 +241,43 @@ pub trait TDisplayObjectContainer<'gc>:
             context.renderer.pop_mask();
         }
     }
+
+    /// Tests if a given stage position point is inside the masks that apply
+    /// to the child at the given depth.
+    ///
+    /// This considers the same stack of clip-depth masks as `render_children`,
+    /// so a point must hit every nested mask to hit a masked child.
+    fn hit_test_masks(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        depth: Depth,
+        point: (Twips, Twips),
+    ) -> bool {
+        let mut mask_stack: Vec<(Depth, DisplayObject<'gc>)> = vec![];
+        for child in self.iter_render_list() {
+            let child_depth = child.depth();
+            if child_depth >= depth {
+                break;
+            }
+
+            while matches!(mask_stack.last(), Some((clip_depth, _)) if child_depth >= *clip_depth) {
+                mask_stack.pop();
+            }
+
+            if child.clip_depth() > 0 && child.allow_as_mask() {
+                let clip_depth = match mask_stack.last() {
+                    Some((clip_depth, _)) => child.clip_depth().min(*clip_depth),
+                    None => child.clip_depth(),
+                };
+                mask_stack.push((clip_depth, child));
+            }
+        }
+
+        mask_stack
+            .into_iter()
+            .filter(|(clip_depth, _)| depth < *clip_depth)
+            .all(|(_, mask)| mask.hit_test_shape(context, point))
+    }
 }
 
 #[macro_export]
@@ -322,6 +366,7 @@ macro_rules! impl_display_object_container {
 
             drop(write);
 
+            self.set_dirty(context.gc_context, true);
             child.set_parent(context.gc_context, Some(self.into()));
             child.set_place_frame(context.gc_context, 0);
             child.set_depth(context.gc_context, depth);
@@ -354,6 +399,7 @@ macro_rules! impl_display_object_container {
                 child,
                 depth,
             );
+            self.set_dirty(context.gc_context, true);
         }
 
         fn insert_at_index(
@@ -368,6 +414,7 @@ macro_rules! impl_display_object_container {
                 child,
                 index,
             );
+            self.set_dirty(context.gc_context, true);
         }
 
         fn swap_at_index(
@@ -380,6 +427,7 @@ macro_rules! impl_display_object_container {
                 .write(context.gc_context)
                 .$field
                 .swap_at_id(index1, index2);
+            self.set_dirty(context.gc_context, true);
         }
 
         fn remove_child(
@@ -404,6 +452,10 @@ macro_rules! impl_display_object_container {
 
             drop(write);
 
+            if removed_from_render_list {
+                self.set_dirty(context.gc_context, true);
+            }
+
             if removed_from_execution_list {
                 child.unload(context);
 
@@ -422,6 +474,8 @@ macro_rules! impl_display_object_container {
         where
             R: RangeBounds<usize>,
         {
+            self.set_dirty(context.gc_context, true);
+
             let mut write = self.0.write(context.gc_context);
             let removed_list: Vec<DisplayObject<'gc>> =
                 write.$field.drain_render_range(range).collect();
@@ -443,7 +497,8 @@ macro_rules! impl_display_object_container {
         }
 
         fn clear(&mut self, gc_context: MutationContext<'gc, '_>) {
-            self.0.write(gc_context).$field.clear(gc_context)
+            self.0.write(gc_context).$field.clear(gc_context);
+            self.set_dirty(gc_context, true);
         }
 
         fn is_empty(self) -> bool {
@@ -694,14 +749,27 @@ impl<'gc> ChildContainer<'gc> {
         child: DisplayObject<'gc>,
         id: usize,
     ) {
+        let old_id = self
+            .render_list
+            .iter()
+            .position(|x| DisplayObject::ptr_eq(*x, child));
+
+        if old_id.is_none() {
+            // The container being inserted into is already borrowed, so only
+            // other containers can be asked to give up the child.
+            if let Some(mut old_parent) = child
+                .parent()
+                .filter(|old_parent| !DisplayObject::ptr_eq(*old_parent, parent))
+                .and_then(|old_parent| old_parent.as_container())
+            {
+                old_parent.remove_child(context, child, EnumSet::all());
+            }
+        }
+
         child.set_place_frame(context.gc_context, 0);
         child.set_parent(context.gc_context, Some(parent));
 
-        if let Some(old_id) = self
-            .render_list
-            .iter()
-            .position(|x| DisplayObject::ptr_eq(*x, child))
-        {
+        if let Some(old_id) = old_id {
             match old_id.cmp(&id) {
                 Ordering::Less if id < self.render_list.len() => {
                     self.render_list[old_id..=id].rotate_left(1)
@@ -714,12 +782,6 @@ impl<'gc> ChildContainer<'gc> {
                 Ordering::Equal => {}
             }
         } else {
-            if let Some(old_parent) = child.parent() {
-                if let Some(mut old_parent) = old_parent.as_container() {
-                    old_parent.remove_child(context, child, EnumSet::all());
-                }
-            }
-
             self.render_list.insert(id, child);
             self.add_child_to_exec_list(context.gc_context, child);
         }
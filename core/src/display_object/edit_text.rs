@@ -2,12 +2,14 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::globals::text_field::attach_virtual_properties;
 use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::backend::input::MouseCursor;
+use crate::backend::render::{AlignZone, GlyphHinting, ShapeHandle};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
-use crate::font::{round_down_to_pixel, Glyph};
+use crate::font::round_down_to_pixel;
 use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
@@ -25,7 +27,7 @@ use swf::Twips;
 pub type Error = Box<dyn std::error::Error>;
 
 /// The kind of autosizing behavior an `EditText` should have, if any
-#[derive(Copy, Clone, Debug, Collect)]
+#[derive(Copy, Clone, Debug, Collect, PartialEq, Eq)]
 #[collect(no_drop)]
 pub enum AutoSizeMode {
     None,
@@ -34,6 +36,86 @@ pub enum AutoSizeMode {
     Right,
 }
 
+/// How the glyphs of an `EditText` are anti-aliased.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AntiAliasType {
+    /// Glyphs are drawn like any other shape.
+    Normal,
+
+    /// Glyphs are fitted to the pixel grid, which keeps small text legible.
+    Advanced,
+}
+
+impl AntiAliasType {
+    /// Parses an anti-aliasing type name, as used by `antiAliasType`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "normal" => Some(AntiAliasType::Normal),
+            "advanced" => Some(AntiAliasType::Advanced),
+            _ => None,
+        }
+    }
+
+    /// The name of this anti-aliasing type, as returned by `antiAliasType`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AntiAliasType::Normal => "normal",
+            AntiAliasType::Advanced => "advanced",
+        }
+    }
+}
+
+/// The settings used to draw the glyphs of an `EditText`.
+///
+/// These come from a `CSMTextSettings` tag, or are set by scripts.
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct TextRenderSettings {
+    anti_alias_type: AntiAliasType,
+    grid_fit: swf::TextGridFit,
+
+    /// The stroke thickness adjustment, from -200 to 200.
+    thickness: f64,
+
+    /// The edge sharpness adjustment, from -400 to 400.
+    sharpness: f64,
+}
+
+impl Default for TextRenderSettings {
+    fn default() -> Self {
+        Self {
+            anti_alias_type: AntiAliasType::Normal,
+            grid_fit: swf::TextGridFit::Pixel,
+            thickness: 0.0,
+            sharpness: 0.0,
+        }
+    }
+}
+
+impl From<swf::CsmTextSettings> for TextRenderSettings {
+    fn from(settings: swf::CsmTextSettings) -> Self {
+        Self {
+            anti_alias_type: if settings.use_advanced_rendering {
+                AntiAliasType::Advanced
+            } else {
+                AntiAliasType::Normal
+            },
+            grid_fit: settings.grid_fit,
+            thickness: clamp_setting(settings.thickness.into(), 200.0),
+            sharpness: clamp_setting(settings.sharpness.into(), 400.0),
+        }
+    }
+}
+
+/// Clamp a thickness or sharpness value to `-limit..=limit`, treating NaN as 0.
+fn clamp_setting(value: f64, limit: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.max(-limit).min(limit)
+    }
+}
+
 /// A dynamic text field.
 /// The text in this text field can be changed dynamically.
 /// It may be selectable or editable by the user, depending on the text field properties.
@@ -106,6 +188,19 @@ pub struct EditTextData<'gc> {
     /// The calculated layout box.
     layout: Vec<LayoutBox<'gc>>,
 
+    /// The positioned glyphs of each layout box, in the same order as
+    /// `layout`.
+    ///
+    /// These are rebuilt whenever the text is laid out again, so that drawing
+    /// and hit testing the text does not have to evaluate its fonts each time.
+    glyph_runs: Vec<Option<GlyphRun>>,
+
+    /// The bottom edge of each line of laid-out text, from top to bottom.
+    line_bottoms: Vec<Twips>,
+
+    /// Whether the field has been resized since its text was last laid out.
+    is_size_changed: bool,
+
     /// The intrinsic bounds of the laid-out text.
     intrinsic_bounds: BoxBounds<Twips>,
 
@@ -115,6 +210,9 @@ pub struct EditTextData<'gc> {
     /// The AVM1 object handle
     object: Option<Object<'gc>>,
 
+    /// The AVM2 object handle
+    avm2_object: Option<Avm2Object<'gc>>,
+
     /// The variable path that this text field is bound to (AVM1 only).
     variable: Option<String>,
 
@@ -129,6 +227,23 @@ pub struct EditTextData<'gc> {
 
     /// Whether or not this EditText has the current keyboard focus
     has_focus: bool,
+
+    /// The characters the user is allowed to type into this text field, or
+    /// `None` if any character may be typed.
+    restrict: Option<EditTextRestrict>,
+
+    /// The most characters that may be entered into this text field while it
+    /// is editable, or 0 if there is no limit.
+    max_chars: i32,
+
+    /// The first visible line of text, starting from 1.
+    scroll: usize,
+
+    /// Whether the mouse wheel scrolls this text field.
+    is_mouse_wheel_enabled: bool,
+
+    /// How the glyphs of this text field are anti-aliased and hinted.
+    render_settings: TextRenderSettings,
 }
 
 impl<'gc> EditText<'gc> {
@@ -143,6 +258,7 @@ impl<'gc> EditText<'gc> {
         let is_selectable = swf_tag.is_selectable;
         let is_editable = !swf_tag.is_read_only;
         let is_html = swf_tag.is_html;
+        let max_chars = swf_tag.max_length.map(i32::from).unwrap_or(0);
         let document = XMLDocument::new(context.gc_context);
         let text = swf_tag.initial_text.clone().unwrap_or_default();
         let default_format = TextFormat::from_swf_tag(swf_tag.clone(), swf_movie.clone(), context);
@@ -211,7 +327,11 @@ impl<'gc> EditText<'gc> {
                 is_html,
                 drawing: Drawing::new(),
                 object: None,
+                avm2_object: None,
                 layout,
+                glyph_runs: Vec::new(),
+                line_bottoms: Vec::new(),
+                is_size_changed: false,
                 intrinsic_bounds,
                 bounds,
                 autosize: AutoSizeMode::None,
@@ -220,9 +340,15 @@ impl<'gc> EditText<'gc> {
                 firing_variable_binding: false,
                 selection: None,
                 has_focus: false,
+                restrict: None,
+                max_chars,
+                scroll: 1,
+                is_mouse_wheel_enabled: true,
+                render_settings: TextRenderSettings::default(),
             },
         ));
 
+        et.0.write(context.gc_context).rebuild_render_cache();
         et.redraw_border(context.gc_context);
 
         et
@@ -254,7 +380,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -299,12 +425,16 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let len = edit_text.text_spans.text().len();
         let tf = edit_text.text_spans.default_format().clone();
+        let old_spans = edit_text.text_spans.clone();
 
         edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
 
+        let is_changed = edit_text.text_spans != old_spans || edit_text.is_size_changed;
         drop(edit_text);
 
-        self.relayout(context);
+        if is_changed {
+            self.relayout(context);
+        }
 
         Ok(())
     }
@@ -368,13 +498,17 @@ impl<'gc> EditText<'gc> {
     /// is retained.
     pub fn set_html_tree(self, doc: XMLDocument<'gc>, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut write = self.0.write(context.gc_context);
+        let old_spans = write.text_spans.clone();
 
         write.document = doc;
         write.text_spans.lower_from_html(doc);
 
+        let is_changed = write.text_spans != old_spans || write.is_size_changed;
         drop(write);
 
-        self.relayout(context);
+        if is_changed {
+            self.relayout(context);
+        }
     }
 
     pub fn text_length(self) -> usize {
@@ -403,11 +537,17 @@ impl<'gc> EditText<'gc> {
         tf: TextFormat,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .set_text_format(from, to, &tf);
-        self.relayout(context);
+        let mut write = self.0.write(context.gc_context);
+        let old_spans = write.text_spans.clone();
+
+        write.text_spans.set_text_format(from, to, &tf);
+
+        let is_changed = write.text_spans != old_spans || write.is_size_changed;
+        drop(write);
+
+        if is_changed {
+            self.relayout(context);
+        }
     }
 
     pub fn is_editable(self) -> bool {
@@ -423,8 +563,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_multiline(self, is_multiline: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
-        self.0.write(context.gc_context).is_multiline = is_multiline;
-        self.relayout(context);
+        if self.0.read().is_multiline != is_multiline {
+            self.0.write(context.gc_context).is_multiline = is_multiline;
+            self.relayout(context);
+        }
     }
 
     pub fn is_selectable(self) -> bool {
@@ -440,8 +582,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_word_wrap(self, is_word_wrap: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
-        self.0.write(context.gc_context).is_word_wrap = is_word_wrap;
-        self.relayout(context);
+        if self.0.read().is_word_wrap != is_word_wrap {
+            self.0.write(context.gc_context).is_word_wrap = is_word_wrap;
+            self.relayout(context);
+        }
     }
 
     pub fn autosize(self) -> AutoSizeMode {
@@ -449,8 +593,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_autosize(self, asm: AutoSizeMode, context: &mut UpdateContext<'_, 'gc, '_>) {
-        self.0.write(context.gc_context).autosize = asm;
-        self.relayout(context);
+        if self.0.read().autosize != asm {
+            self.0.write(context.gc_context).autosize = asm;
+            self.relayout(context);
+        }
     }
 
     pub fn has_border(self) -> bool {
@@ -471,8 +617,10 @@ impl<'gc> EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         is_device_font: bool,
     ) {
-        self.0.write(context.gc_context).is_device_font = is_device_font;
-        self.relayout(context);
+        if self.0.read().is_device_font != is_device_font {
+            self.0.write(context.gc_context).is_device_font = is_device_font;
+            self.relayout(context);
+        }
     }
 
     pub fn is_html(self) -> bool {
@@ -483,6 +631,181 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).is_html = is_html;
     }
 
+    /// The `restrict` string of this text field, if any.
+    pub fn restrict(self) -> Option<String> {
+        self.0
+            .read()
+            .restrict
+            .as_ref()
+            .map(|restrict| restrict.source().to_string())
+    }
+
+    /// Set or clear the characters the user is allowed to type into this
+    /// text field.
+    pub fn set_restrict(self, restrict: Option<&str>, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).restrict = restrict.map(EditTextRestrict::from_pattern);
+    }
+
+    /// Whether or not the `restrict` setting of this text field allows a
+    /// character to be entered.
+    pub fn is_character_allowed(self, character: char) -> bool {
+        self.0
+            .read()
+            .restrict
+            .as_ref()
+            .map(|restrict| restrict.is_allowed(character))
+            .unwrap_or(true)
+    }
+
+    /// Remove any characters from `text` that the `restrict` setting of this
+    /// text field disallows.
+    pub fn filter_restricted(self, text: &str) -> String {
+        text.chars()
+            .filter(|c| self.is_character_allowed(*c))
+            .collect()
+    }
+
+    /// The most characters that may be entered into this text field while it
+    /// is editable, or 0 if there is no limit.
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).max_chars = max_chars;
+    }
+
+    /// The first visible line of text, starting from 1.
+    pub fn scroll(self) -> usize {
+        self.0.read().scroll
+    }
+
+    /// Scroll the text so that the given line is at the top, as far as the
+    /// text can be scrolled.
+    pub fn set_scroll(self, scroll: f64, context: MutationContext<'gc, '_>) {
+        let max_scroll = self.max_scroll();
+        let scroll = if scroll.is_nan() || scroll < 1.0 {
+            1
+        } else {
+            (scroll as usize).min(max_scroll)
+        };
+
+        let mut edit_text = self.0.write(context);
+        if edit_text.scroll != scroll {
+            edit_text.scroll = scroll;
+            edit_text.base.set_dirty(true);
+        }
+    }
+
+    /// The highest line that can be scrolled to the top, which leaves the last
+    /// line at the bottom of the text field.
+    pub fn max_scroll(self) -> usize {
+        let edit_text = self.0.read();
+        let line_bottoms = &edit_text.line_bottoms;
+        let last_bottom = match line_bottoms.last() {
+            Some(bottom) => *bottom,
+            None => return 1,
+        };
+        let visible_height =
+            edit_text.bounds.height() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0);
+
+        // The line tops are the bottoms of the lines above them.
+        let first_line_that_fits = std::iter::once(Twips::zero())
+            .chain(line_bottoms.iter().copied())
+            .position(|top| last_bottom - top <= visible_height)
+            .unwrap_or(0);
+        first_line_that_fits.min(line_bottoms.len() - 1) + 1
+    }
+
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        self.0.read().is_mouse_wheel_enabled
+    }
+
+    pub fn set_mouse_wheel_enabled(self, is_enabled: bool, context: MutationContext<'gc, '_>) {
+        self.0.write(context).is_mouse_wheel_enabled = is_enabled;
+    }
+
+    /// Replace all of the anti-aliasing settings of this text field, as a
+    /// `CSMTextSettings` tag does.
+    pub fn set_render_settings(
+        self,
+        settings: TextRenderSettings,
+        context: MutationContext<'gc, '_>,
+    ) {
+        let mut edit_text = self.0.write(context);
+        edit_text.render_settings = settings;
+        edit_text.base.set_dirty(true);
+    }
+
+    pub fn anti_alias_type(self) -> AntiAliasType {
+        self.0.read().render_settings.anti_alias_type
+    }
+
+    pub fn set_anti_alias_type(
+        self,
+        anti_alias_type: AntiAliasType,
+        context: MutationContext<'gc, '_>,
+    ) {
+        let mut edit_text = self.0.write(context);
+        edit_text.render_settings.anti_alias_type = anti_alias_type;
+        edit_text.base.set_dirty(true);
+    }
+
+    /// The edge sharpness adjustment used by advanced anti-aliasing.
+    pub fn sharpness(self) -> f64 {
+        self.0.read().render_settings.sharpness
+    }
+
+    /// Set the edge sharpness, which is clamped to the range -400 to 400.
+    pub fn set_sharpness(self, sharpness: f64, context: MutationContext<'gc, '_>) {
+        let mut edit_text = self.0.write(context);
+        edit_text.render_settings.sharpness = clamp_setting(sharpness, 400.0);
+        edit_text.base.set_dirty(true);
+    }
+
+    /// The stroke thickness adjustment used by advanced anti-aliasing.
+    pub fn thickness(self) -> f64 {
+        self.0.read().render_settings.thickness
+    }
+
+    /// Set the stroke thickness, which is clamped to the range -200 to 200.
+    pub fn set_thickness(self, thickness: f64, context: MutationContext<'gc, '_>) {
+        let mut edit_text = self.0.write(context);
+        edit_text.render_settings.thickness = clamp_setting(thickness, 200.0);
+        edit_text.base.set_dirty(true);
+    }
+
+    /// How many characters may be entered in place of the text between
+    /// `from` and `to` before this text field reaches its `maxChars` limit.
+    ///
+    /// Returns `None` if there is no limit.
+    pub fn available_chars(self, from: usize, to: usize) -> Option<usize> {
+        let read = self.0.read();
+        if !read.is_editable || read.max_chars <= 0 {
+            return None;
+        }
+
+        // The limit counts characters, not bytes.
+        let text = read.text_spans.text();
+        let replaced = text.get(from..to).map(|s| s.chars().count()).unwrap_or(0);
+        let kept = text.chars().count().saturating_sub(replaced);
+
+        Some((read.max_chars as usize).saturating_sub(kept))
+    }
+
+    /// Prepare `text` to be entered in place of the text between `from` and
+    /// `to`.
+    ///
+    /// Characters that the `restrict` setting of this text field disallows
+    /// are removed, and the rest is cut short to fit within `maxChars`.
+    pub fn filter_input(self, from: usize, to: usize, text: &str) -> String {
+        let text = self.filter_restricted(text);
+        match self.available_chars(from, to) {
+            Some(available) => text.chars().take(available).collect(),
+            None => text,
+        }
+    }
+
     pub fn replace_text(
         self,
         from: usize,
@@ -502,7 +825,7 @@ impl<'gc> EditText<'gc> {
     /// This `text_transform` is separate from and relative to the base
     /// transform that this `EditText` automatically gets by virtue of being a
     /// `DisplayObject`.
-    pub fn text_transform(self, color: swf::Color, baseline_adjustment: Twips) -> Transform {
+    pub fn text_transform(color: swf::Color, baseline_adjustment: Twips) -> Transform {
         let mut transform: Transform = Default::default();
         transform.color_transform.r_mult = f32::from(color.r) / 255.0;
         transform.color_transform.g_mult = f32::from(color.g) / 255.0;
@@ -585,6 +908,7 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context);
 
         write.drawing.clear();
+        write.base.set_dirty(true);
 
         if write.has_border {
             let bounds = write.bounds.clone();
@@ -635,6 +959,8 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        *context.text_relayouts += 1;
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
@@ -651,7 +977,10 @@ impl<'gc> EditText<'gc> {
         );
 
         edit_text.layout = new_layout;
+        edit_text.rebuild_render_cache();
+        edit_text.is_size_changed = false;
         edit_text.intrinsic_bounds = intrinsic_bounds;
+        edit_text.base.set_dirty(true);
 
         match autosize {
             AutoSizeMode::None => {}
@@ -691,6 +1020,12 @@ impl<'gc> EditText<'gc> {
                 edit_text.base.set_transformed_by_script(true);
             }
         }
+        drop(edit_text);
+
+        // Keep the scroll position within the new text.
+        let max_scroll = self.max_scroll();
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.scroll = edit_text.scroll.min(max_scroll);
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -706,106 +1041,110 @@ impl<'gc> EditText<'gc> {
     }
 
     /// Render a layout box, plus its children.
-    fn render_layout_box(self, context: &mut RenderContext<'_, 'gc>, lbox: &LayoutBox<'gc>) {
+    fn render_layout_box(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        lbox: &LayoutBox<'gc>,
+        glyph_run: Option<&GlyphRun>,
+    ) {
         let box_transform: Transform = lbox.bounds().origin().into();
         context.transform_stack.push(&box_transform);
 
         let edit_text = self.0.read();
         let selection = edit_text.selection;
 
-        let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
-            if let Some(selection) = selection {
-                if selection.is_caret()
-                    && edit_text.is_editable
-                    && selection.start() >= *start
-                    && selection.end() <= *end
-                    && Utc::now().timestamp_subsec_millis() / 500 == 0
-                {
-                    Some((selection.start() - start, end - start))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
         // If the font can't be found or has no glyph information, use the "device font" instead.
         // We're cheating a bit and not actually rendering text using the OS/web.
         // Instead, we embed an SWF version of Noto Sans to use as the "device font", and render
         // it the same as any other SWF outline text.
-        if let Some((text, _tf, font, params, color)) =
-            lbox.as_renderable_text(edit_text.text_spans.text())
-        {
-            let baseline_adjustmnet =
-                font.get_baseline_for_height(params.height()) - params.height();
-            font.evaluate(
-                text,
-                self.text_transform(color, baseline_adjustmnet),
-                params,
-                |pos, transform, glyph: &Glyph, advance, x| {
-                    // If it's highlighted, override the color.
-                    // TODO: We should draw a black background and change the color to white,
-                    //  but for now let's just change it to be slightly different colour.
-                    match selection {
-                        Some(selection) if selection.contains(pos) => {
-                            context.transform_stack.push(&Transform {
-                                matrix: transform.matrix,
-                                color_transform: transform.color_transform
-                                    * ColorTransform {
-                                        r_mult: 0.75,
-                                        g_mult: 0.75,
-                                        b_mult: 0.75,
-                                        a_mult: 1.0,
-                                        r_add: 0.0,
-                                        g_add: 0.0,
-                                        b_add: 1.0,
-                                        a_add: 0.0,
-                                    },
-                            });
-                        }
-                        _ => {
-                            context.transform_stack.push(&transform);
-                        }
+        if let Some(glyph_run) = glyph_run {
+            let caret = match (glyph_run.text_range, selection) {
+                (Some((start, end)), Some(selection))
+                    if selection.is_caret()
+                        && edit_text.is_editable
+                        && selection.start() >= start
+                        && selection.end() <= end
+                        && Utc::now().timestamp_subsec_millis() / 500 == 0 =>
+                {
+                    Some((selection.start() - start, end - start))
+                }
+                _ => None,
+            };
+
+            for glyph in glyph_run.glyphs.iter() {
+                let pos = glyph.pos;
+
+                // If it's highlighted, override the color.
+                // TODO: We should draw a black background and change the color to white,
+                //  but for now let's just change it to be slightly different colour.
+                match selection {
+                    Some(selection) if selection.contains(pos) => {
+                        context.transform_stack.push(&Transform {
+                            matrix: glyph.transform.matrix,
+                            color_transform: glyph.transform.color_transform
+                                * ColorTransform {
+                                    r_mult: 0.75,
+                                    g_mult: 0.75,
+                                    b_mult: 0.75,
+                                    a_mult: 1.0,
+                                    r_add: 0.0,
+                                    g_add: 0.0,
+                                    b_add: 1.0,
+                                    a_add: 0.0,
+                                },
+                        });
                     }
+                    _ => {
+                        context.transform_stack.push(&glyph.transform);
+                    }
+                }
 
-                    // Render glyph.
-                    context
+                // Render glyph.
+                match edit_text.render_settings.anti_alias_type {
+                    AntiAliasType::Normal => context
                         .renderer
-                        .render_shape(glyph.shape_handle, context.transform_stack.transform());
-                    context.transform_stack.pop();
-
-                    if let Some((caret_pos, length)) = caret {
-                        if caret_pos == pos {
-                            let caret = context.transform_stack.transform().matrix
-                                * Matrix::create_box(
-                                    1.0,
-                                    params.height().to_pixels() as f32,
-                                    0.0,
-                                    x + Twips::from_pixels(-1.0),
-                                    Twips::from_pixels(2.0),
-                                );
-                            context
-                                .renderer
-                                .draw_rect(Color::from_rgb(0x000000, 0xFF), &caret);
-                        } else if pos == length - 1 && caret_pos == length {
-                            let caret = context.transform_stack.transform().matrix
-                                * Matrix::create_box(
-                                    1.0,
-                                    params.height().to_pixels() as f32,
-                                    0.0,
-                                    x + advance,
-                                    Twips::from_pixels(2.0),
-                                );
-                            context
-                                .renderer
-                                .draw_rect(Color::from_rgb(0x000000, 0xFF), &caret);
-                        }
+                        .render_shape(glyph.shape_handle, context.transform_stack.transform()),
+                    AntiAliasType::Advanced => context.renderer.render_glyph(
+                        glyph.shape_handle,
+                        context.transform_stack.transform(),
+                        &GlyphHinting {
+                            grid_fit: edit_text.render_settings.grid_fit,
+                            thickness: edit_text.render_settings.thickness as f32,
+                            sharpness: edit_text.render_settings.sharpness as f32,
+                            align_zone: glyph.align_zone,
+                        },
+                    ),
+                }
+                context.transform_stack.pop();
+
+                if let Some((caret_pos, length)) = caret {
+                    if caret_pos == pos {
+                        let caret = context.transform_stack.transform().matrix
+                            * Matrix::create_box(
+                                1.0,
+                                glyph_run.height.to_pixels() as f32,
+                                0.0,
+                                glyph.x + Twips::from_pixels(-1.0),
+                                Twips::from_pixels(2.0),
+                            );
+                        context
+                            .renderer
+                            .draw_rect(Color::from_rgb(0x000000, 0xFF), &caret);
+                    } else if pos == length - 1 && caret_pos == length {
+                        let caret = context.transform_stack.transform().matrix
+                            * Matrix::create_box(
+                                1.0,
+                                glyph_run.height.to_pixels() as f32,
+                                0.0,
+                                glyph.x + glyph.advance,
+                                Twips::from_pixels(2.0),
+                            );
+                        context
+                            .renderer
+                            .draw_rect(Color::from_rgb(0x000000, 0xFF), &caret);
                     }
-                },
-            );
+                }
+            }
         }
 
         if let Some(drawing) = lbox.as_renderable_drawing() {
@@ -955,6 +1294,7 @@ impl<'gc> EditText<'gc> {
         gc_context: MutationContext<'gc, '_>,
     ) {
         let mut text = self.0.write(gc_context);
+        text.base.set_dirty(true);
         if let Some(mut selection) = selection {
             selection.clamp(text.text_spans.text().len());
             text.selection = Some(selection);
@@ -968,39 +1308,30 @@ impl<'gc> EditText<'gc> {
         let position = self.global_to_local(position);
         let position = (
             position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
+            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING) + text.scroll_offset(),
         );
 
-        for layout_box in text.layout.iter() {
+        for (layout_box, glyph_run) in text.layout.iter().zip(text.glyph_runs.iter()) {
             let transform: Transform = layout_box.bounds().origin().into();
             let mut matrix = transform.matrix;
             matrix.invert();
             let local_position = matrix * position;
 
-            if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
-            {
+            if let Some(glyph_run) = glyph_run {
                 let mut result = None;
-                let baseline_adjustment =
-                    font.get_baseline_for_height(params.height()) - params.height();
-                font.evaluate(
-                    text,
-                    self.text_transform(color, baseline_adjustment),
-                    params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.0 >= x
-                            && local_position.0 <= x + advance
-                            && local_position.1 >= Twips::zero()
-                            && local_position.1 <= params.height()
-                        {
-                            if local_position.0 >= x + (advance / 2) {
-                                result = Some(pos + 1);
-                            } else {
-                                result = Some(pos);
-                            }
+                for glyph in glyph_run.glyphs.iter() {
+                    if local_position.0 >= glyph.x
+                        && local_position.0 <= glyph.x + glyph.advance
+                        && local_position.1 >= Twips::zero()
+                        && local_position.1 <= glyph_run.height
+                    {
+                        if local_position.0 >= glyph.x + (glyph.advance / 2) {
+                            result = Some(glyph.pos + 1);
+                        } else {
+                            result = Some(glyph.pos);
                         }
-                    },
-                );
+                    }
+                }
                 if result.is_some() {
                     return result;
                 }
@@ -1048,7 +1379,12 @@ impl<'gc> EditText<'gc> {
                         changed = true;
                     }
                 }
-                32..=126 => {
+                32..=126
+                    if self.is_character_allowed(character)
+                        && self
+                            .available_chars(selection.start(), selection.end())
+                            .map_or(true, |available| available > 0) =>
+                {
                     // ASCII
                     // TODO: Make this actually good and not basic ASCII :)
                     self.replace_text(
@@ -1168,6 +1504,18 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             .unwrap_or(Value::Undefined)
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).avm2_object = Some(to);
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().bounds.clone()
     }
@@ -1212,6 +1560,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         write.bounds.set_width(Twips::from_pixels(value));
         write.base.set_transformed_by_script(true);
+        write.is_size_changed = true;
 
         drop(write);
         self.redraw_border(gc_context);
@@ -1226,6 +1575,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         write.bounds.set_height(Twips::from_pixels(value));
         write.base.set_transformed_by_script(true);
+        write.is_size_changed = true;
 
         drop(write);
         self.redraw_border(gc_context);
@@ -1276,7 +1626,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         context.transform_stack.push(&Transform {
             matrix: Matrix {
                 tx: Twips::from_pixels(Self::INTERNAL_PADDING),
-                ty: Twips::from_pixels(Self::INTERNAL_PADDING),
+                ty: Twips::from_pixels(Self::INTERNAL_PADDING) - edit_text.scroll_offset(),
                 ..Default::default()
             },
             ..Default::default()
@@ -1307,8 +1657,16 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 }
             }
         } else {
-            for layout_box in edit_text.layout.iter() {
-                self.render_layout_box(context, layout_box);
+            // Only the lines within the visible (scrolled) part of the field are drawn.
+            let visible_top =
+                edit_text.scroll_offset() - Twips::from_pixels(Self::INTERNAL_PADDING);
+            let visible_bottom = visible_top + edit_text.bounds.height();
+            let boxes = edit_text.layout.iter().zip(edit_text.glyph_runs.iter());
+            for (layout_box, glyph_run) in boxes {
+                let bounds = layout_box.bounds();
+                if bounds.extent_y() > visible_top && bounds.offset_y() < visible_bottom {
+                    self.render_layout_box(context, layout_box, glyph_run.as_ref());
+                }
             }
         }
 
@@ -1376,6 +1734,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     fn on_focus_changed(&self, context: MutationContext<'gc, '_>, focused: bool) {
         let mut text = self.0.write(context);
         text.has_focus = focused;
+        text.base.set_dirty(true);
         if !focused {
             text.selection = None;
         }
@@ -1442,6 +1801,116 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 }
 
+impl<'gc> EditTextData<'gc> {
+    /// Rebuild the glyph runs and line bottoms from the current layout.
+    ///
+    /// This must be called whenever the layout changes.
+    fn rebuild_render_cache(&mut self) {
+        let text = self.text_spans.text();
+        self.glyph_runs = self
+            .layout
+            .iter()
+            .map(|layout_box| GlyphRun::from_layout_box(layout_box, text))
+            .collect();
+
+        // Boxes on the same line are aligned to the bottom of the line, so each
+        // distinct bottom edge is a line.
+        let mut bottoms: Vec<Twips> = self
+            .layout
+            .iter()
+            .filter(|layout_box| layout_box.is_text_box() || layout_box.is_bullet())
+            .map(|layout_box| layout_box.bounds().extent_y())
+            .collect();
+        bottoms.sort_unstable();
+        bottoms.dedup();
+        self.line_bottoms = bottoms;
+    }
+
+    /// How far the text is moved up to show the scrolled-to line at the top.
+    fn scroll_offset(&self) -> Twips {
+        match self.scroll.checked_sub(2) {
+            Some(line) => self
+                .line_bottoms
+                .get(line)
+                .copied()
+                .unwrap_or_else(Twips::zero),
+            None => Twips::zero(),
+        }
+    }
+}
+
+/// The glyphs of a single layout box, positioned relative to that box.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct GlyphRun {
+    /// The range of the text that this run draws, if it draws part of the
+    /// text (rather than a bullet).
+    text_range: Option<(usize, usize)>,
+
+    /// The height of the text, used to size the caret.
+    height: Twips,
+
+    /// The glyphs to draw.
+    glyphs: Vec<PositionedGlyph>,
+}
+
+/// A glyph that has been positioned by the layout.
+#[derive(Clone, Debug)]
+struct PositionedGlyph {
+    /// The index of the character this glyph draws within its run.
+    pos: usize,
+
+    /// The shape of the glyph.
+    shape_handle: ShapeHandle,
+
+    /// The alignment zone of the glyph, used to hint it.
+    align_zone: Option<AlignZone>,
+
+    /// The transform of the glyph, relative to its layout box.
+    transform: Transform,
+
+    /// The distance to the next glyph.
+    advance: Twips,
+
+    /// The offset of the glyph from the start of its layout box.
+    x: Twips,
+}
+
+impl GlyphRun {
+    /// Position the glyphs of a layout box, if it contains text.
+    fn from_layout_box(layout_box: &LayoutBox<'_>, text: &str) -> Option<Self> {
+        let (text, _tf, font, params, color) = layout_box.as_renderable_text(text)?;
+        let text_range = match layout_box.content() {
+            LayoutContent::Text { start, end, .. } => Some((*start, *end)),
+            _ => None,
+        };
+        let baseline_adjustment = font.get_baseline_for_height(params.height()) - params.height();
+        let mut glyphs = Vec::new();
+
+        font.evaluate(
+            text,
+            EditText::text_transform(color, baseline_adjustment),
+            params,
+            |pos, transform, glyph, advance, x| {
+                glyphs.push(PositionedGlyph {
+                    pos,
+                    shape_handle: glyph.shape_handle,
+                    align_zone: font.get_align_zone_for_code(glyph.code),
+                    transform: transform.clone(),
+                    advance,
+                    x,
+                })
+            },
+        );
+
+        Some(Self {
+            text_range,
+            height: params.height(),
+            glyphs,
+        })
+    }
+}
+
 /// Static data shared between all instances of a text object.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -1532,3 +2001,126 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+/// The set of characters that may be typed into a text field, as parsed from
+/// its `restrict` string.
+///
+/// The string lists allowed characters, with `-` denoting a range such as
+/// `A-Z`. A `^` switches between allowing and disallowing the characters that
+/// follow it, and a string that begins with `^` allows every character not
+/// listed. `\` escapes the next character, so `\-`, `\^` and `\\` stand for
+/// themselves. When ranges overlap, the one listed last wins.
+#[derive(Clone, Debug)]
+pub struct EditTextRestrict {
+    /// The string this restriction was parsed from.
+    source: String,
+
+    /// Whether characters not covered by any range are allowed.
+    allow_by_default: bool,
+
+    /// Inclusive character ranges, in order, along with whether each range
+    /// allows or disallows the characters in it.
+    ranges: Vec<(bool, char, char)>,
+}
+
+unsafe impl Collect for EditTextRestrict {
+    #[inline]
+    fn needs_trace() -> bool {
+        false
+    }
+}
+
+impl EditTextRestrict {
+    pub fn from_pattern(source: &str) -> Self {
+        // Resolve escapes first, remembering which characters were escaped
+        // and thus lose their special meaning.
+        let mut tokens = Vec::new();
+        let mut chars = source.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    tokens.push((escaped, true));
+                }
+            } else {
+                tokens.push((c, false));
+            }
+        }
+
+        let mut allow_by_default = false;
+        let mut allow = true;
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let (c, escaped) = tokens[i];
+            if c == '^' && !escaped {
+                if i == 0 {
+                    allow_by_default = true;
+                }
+                allow = !allow;
+                i += 1;
+            } else if let (Some(('-', false)), Some(&(end, _))) =
+                (tokens.get(i + 1).copied(), tokens.get(i + 2))
+            {
+                ranges.push((allow, c, end));
+                i += 3;
+            } else {
+                ranges.push((allow, c, c));
+                i += 1;
+            }
+        }
+
+        Self {
+            source: source.to_string(),
+            allow_by_default,
+            ranges,
+        }
+    }
+
+    /// The string this restriction was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether a character may be typed under this restriction.
+    pub fn is_allowed(&self, character: char) -> bool {
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(_, start, end)| (*start..=*end).contains(&character))
+            .map(|(allow, _, _)| *allow)
+            .unwrap_or(self.allow_by_default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditTextRestrict;
+
+    fn allowed(pattern: &str, text: &str) -> String {
+        let restrict = EditTextRestrict::from_pattern(pattern);
+        text.chars().filter(|c| restrict.is_allowed(*c)).collect()
+    }
+
+    #[test]
+    fn restrict_ranges() {
+        assert_eq!(allowed("0-9", "a1b2-3"), "123");
+        assert_eq!(allowed("A-Z0-9", "aA1-z"), "A1");
+        assert_eq!(allowed("a-c-", "abcd-"), "abc-");
+        assert_eq!(allowed("", "abc"), "");
+    }
+
+    #[test]
+    fn restrict_negation() {
+        assert_eq!(allowed("^aeiou", "restrict"), "rstrct");
+        assert_eq!(allowed("A-Z^Q", "QUERY"), "UERY");
+        assert_eq!(allowed("^a-z^m", "ammo1"), "mm1");
+        assert_eq!(allowed("^", "any"), "any");
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        assert_eq!(allowed("\\-\\^", "a-^b"), "-^");
+        assert_eq!(allowed("\\\\0-9", "\\1a"), "\\1");
+        assert_eq!(allowed("^\\^", "a^b"), "ab");
+    }
+}
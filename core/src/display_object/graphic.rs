@@ -1,9 +1,16 @@
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::backend::render::ShapeHandle;
+use crate::bitmap_data::BitmapDataStorage;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::drawing::Drawing;
 use crate::prelude::*;
+use crate::shape_cache::CachedShape;
+use crate::shape_utils::{DistilledShape, HitTestCache, NineSlice};
+use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, GcCell};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{RefCell, RefMut};
 
 #[derive(Clone, Debug, Collect, Copy)]
 #[collect(no_drop)]
@@ -12,7 +19,20 @@ pub struct Graphic<'gc>(GcCell<'gc, GraphicData<'gc>>);
 #[derive(Clone, Debug)]
 pub struct GraphicData<'gc> {
     base: DisplayObjectBase<'gc>,
-    static_data: gc_arena::Gc<'gc, GraphicStatic>,
+
+    /// The shape defined in the SWF, if this graphic was placed from the
+    /// timeline.
+    static_data: Option<gc_arena::Gc<'gc, GraphicStatic>>,
+
+    /// Additional content drawn by script.
+    drawing: Drawing,
+
+    /// The AVM2 object associated with this graphic, if it was constructed by
+    /// script.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    /// The shape as last drawn by a parent with a scaling grid.
+    sliced_shape: RefCell<Option<SlicedShape>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -22,31 +42,111 @@ impl<'gc> Graphic<'gc> {
             bounds: swf_shape.shape_bounds.clone().into(),
             render_handle: context.renderer.register_shape((&swf_shape).into()),
             shape: swf_shape,
+            hit_test_cache: HitTestCache::new(),
         };
         Graphic(GcCell::allocate(
             context.gc_context,
             GraphicData {
                 base: Default::default(),
-                static_data: gc_arena::Gc::allocate(context.gc_context, static_data),
+                static_data: Some(gc_arena::Gc::allocate(context.gc_context, static_data)),
+                drawing: Drawing::new(),
+                avm2_object: None,
+                sliced_shape: RefCell::new(None),
+            },
+        ))
+    }
+
+    /// Construct an empty graphic, to be drawn into by script.
+    pub fn new_with_avm2(
+        gc_context: MutationContext<'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+    ) -> Self {
+        Graphic(GcCell::allocate(
+            gc_context,
+            GraphicData {
+                base: Default::default(),
+                static_data: None,
+                drawing: Drawing::new(),
+                avm2_object: Some(avm2_object),
+                sliced_shape: RefCell::new(None),
             },
         ))
     }
+
+    /// The 9-slice transform this graphic is drawn with, if its parent has a
+    /// scaling grid.
+    fn nine_slice(&self) -> Option<NineSlice> {
+        self.parent()?.as_movie_clip()?.nine_slice()
+    }
+
+    /// Render the shape of this graphic through its parent's 9-slice
+    /// transform.
+    ///
+    /// The sliced shape is in the parent's coordinate space, so it is drawn
+    /// without this graphic's matrix. It is only rebuilt when the transform or
+    /// the matrix changes.
+    fn render_sliced(&self, context: &mut RenderContext, nine_slice: NineSlice) {
+        let read = self.0.read();
+        let static_data = match &read.static_data {
+            Some(static_data) => static_data,
+            None => return,
+        };
+
+        let matrix = *self.matrix();
+        let mut sliced_shape = read.sliced_shape.borrow_mut();
+        let render_handle = match &*sliced_shape {
+            Some(sliced) if sliced.nine_slice == nine_slice && sliced.matrix == matrix => {
+                sliced.shape.handle()
+            }
+            _ => {
+                let shape = nine_slice.slice_shape(&static_data.shape, &matrix);
+                let old = sliced_shape.take().map(|sliced| sliced.shape);
+                let shape = context
+                    .shape_cache
+                    .replace(context.renderer, old, (&shape).into());
+                let render_handle = shape.handle();
+                *sliced_shape = Some(SlicedShape {
+                    nine_slice,
+                    matrix,
+                    shape,
+                });
+                render_handle
+            }
+        };
+
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::identity(),
+            color_transform: self.color_transform().clone(),
+        });
+        context
+            .renderer
+            .render_shape(render_handle, context.transform_stack.transform());
+        context.transform_stack.pop();
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     impl_display_object!(base);
 
     fn id(&self) -> CharacterId {
-        self.0.read().static_data.id
+        self.0.read().static_data.map(|s| s.id).unwrap_or_default()
     }
 
     fn self_bounds(&self) -> BoundingBox {
-        self.0.read().static_data.bounds.clone()
+        let read = self.0.read();
+        let mut bounds = read.drawing.self_bounds();
+        if let Some(static_data) = &read.static_data {
+            bounds.union(&static_data.bounds);
+        }
+        bounds
     }
 
     fn world_bounds(&self) -> BoundingBox {
         // TODO: Use dirty flags and cache this.
         let mut bounds = self.local_bounds();
+        if let Some(nine_slice) = self.nine_slice() {
+            bounds = nine_slice.map_bounds(&bounds);
+        }
         let mut node = self.parent();
         while let Some(display_object) = node {
             bounds = bounds.transform(&*display_object.matrix());
@@ -65,16 +165,35 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             return;
         }
 
+        let nine_slice = self.nine_slice();
+        if let Some(nine_slice) = nine_slice.clone() {
+            self.render_sliced(context, nine_slice);
+        }
+
         context.transform_stack.push(&*self.transform());
 
-        context.renderer.render_shape(
-            self.0.read().static_data.render_handle,
-            context.transform_stack.transform(),
-        );
+        if nine_slice.is_none() {
+            if let Some(static_data) = &self.0.read().static_data {
+                context.renderer.render_shape(
+                    static_data.render_handle,
+                    context.transform_stack.transform(),
+                );
+            }
+        }
+
+        self.0.read().drawing.render(context);
 
         context.transform_stack.pop();
     }
 
+    fn rasterize_self(&self, storage: &mut BitmapDataStorage, transform: &Transform) {
+        let read = self.0.read();
+        if let Some(static_data) = &read.static_data {
+            storage.draw_paths(&DistilledShape::from(&static_data.shape).paths, transform);
+        }
+        storage.draw_paths(&read.drawing.paths(), transform);
+    }
+
     fn hit_test_shape(
         &self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
@@ -83,19 +202,58 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Transform point to local coordinates and test.
         if self.world_bounds().contains(point) {
             let local_matrix = self.global_to_local_matrix();
-            let point = local_matrix * point;
-            let shape = &self.0.read().static_data.shape;
-            crate::shape_utils::shape_hit_test(shape, point, &local_matrix)
+            let local_point = local_matrix * point;
+            let read = self.0.read();
+            if let Some(static_data) = &read.static_data {
+                // A sliced shape is tested by undoing the slicing of the
+                // point, in the parent's coordinate space.
+                let shape_point = match (self.nine_slice(), self.parent()) {
+                    (Some(nine_slice), Some(parent)) => {
+                        let mut matrix = *self.matrix();
+                        matrix.invert();
+                        let point = nine_slice.unmap_point(parent.global_to_local_matrix() * point);
+                        matrix * point
+                    }
+                    _ => local_point,
+                };
+                if static_data.hit_test_cache.hit_test(
+                    &static_data.shape,
+                    shape_point,
+                    &local_matrix,
+                ) {
+                    return true;
+                }
+            }
+            read.drawing.hit_test(local_point, &local_matrix)
         } else {
             false
         }
     }
+
+    fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<Drawing>> {
+        let mut write = self.0.write(gc_context);
+        write.base.set_dirty(true);
+        Some(RefMut::map(write, |graphic| &mut graphic.drawing))
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).avm2_object = Some(to);
+    }
 }
 
 unsafe impl<'gc> gc_arena::Collect for GraphicData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.static_data.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
@@ -106,6 +264,16 @@ struct GraphicStatic {
     shape: swf::Shape,
     render_handle: ShapeHandle,
     bounds: BoundingBox,
+    hit_test_cache: HitTestCache,
+}
+
+/// A shape sliced by a 9-slice transform, and the transform and placement
+/// matrix it was sliced with.
+#[derive(Clone, Debug)]
+struct SlicedShape {
+    nine_slice: NineSlice,
+    matrix: Matrix,
+    shape: CachedShape,
 }
 
 unsafe impl<'gc> gc_arena::Collect for GraphicStatic {
@@ -2,8 +2,10 @@ use crate::backend::render::{RenderBackend, ShapeHandle};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::shape_utils::HitTestCache;
 use crate::types::{Degrees, Percent};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cell::{Cell, Ref, RefCell};
 use swf::Twips;
 
 #[derive(Clone, Debug, Collect, Copy)]
@@ -37,7 +39,11 @@ impl<'gc> MorphShape<'gc> {
     }
 
     pub fn set_ratio(&mut self, gc_context: MutationContext<'gc, '_>, ratio: u16) {
-        self.0.write(gc_context).ratio = ratio;
+        let mut write = self.0.write(gc_context);
+        if write.ratio != ratio {
+            write.ratio = ratio;
+            write.base.set_dirty(true);
+        }
     }
 }
 
@@ -59,24 +65,18 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
     fn render(&self, context: &mut RenderContext) {
         context.transform_stack.push(&*self.transform());
 
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            context
-                .renderer
-                .render_shape(frame.shape_handle, context.transform_stack.transform());
-        } else {
-            log::warn!("Missing ratio for morph shape");
-        }
+        let read = self.0.read();
+        let shape_handle = read.static_data.shape_handle(context.renderer, read.ratio);
+        context
+            .renderer
+            .render_shape(shape_handle, context.transform_stack.transform());
 
         context.transform_stack.pop();
     }
 
     fn self_bounds(&self) -> BoundingBox {
-        // TODO: Use the bounds of the current ratio.
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            frame.bounds.clone()
-        } else {
-            BoundingBox::default()
-        }
+        let read = self.0.read();
+        read.static_data.bounds(read.ratio)
     }
 
     fn hit_test_shape(
@@ -85,13 +85,13 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         point: (Twips, Twips),
     ) -> bool {
         if self.world_bounds().contains(point) {
-            if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-                let local_matrix = self.global_to_local_matrix();
-                let point = local_matrix * point;
-                return crate::shape_utils::shape_hit_test(&frame.shape, point, &local_matrix);
-            } else {
-                log::warn!("Missing ratio for morph shape");
-            }
+            let local_matrix = self.global_to_local_matrix();
+            let point = local_matrix * point;
+            let read = self.0.read();
+            let frame = read.static_data.frame(read.ratio);
+            return frame
+                .hit_test_cache
+                .hit_test(&frame.shape, point, &local_matrix);
         }
 
         false
@@ -107,28 +107,46 @@ unsafe impl<'gc> gc_arena::Collect for MorphShapeData<'gc> {
 }
 
 /// A precalculated intermediate frame for a morph shape.
+///
+/// Bounds and hit tests use the interpolated shape directly, so it is only
+/// tessellated once the frame is rendered.
 struct Frame {
-    shape_handle: ShapeHandle,
     shape: swf::Shape,
-    bounds: BoundingBox,
+    hit_test_cache: HitTestCache,
+    shape_handle: Cell<Option<ShapeHandle>>,
 }
 
 /// Static data shared between all instances of a morph shape.
-#[allow(dead_code)]
 pub struct MorphShapeStatic {
     id: CharacterId,
     start: swf::MorphShape,
     end: swf::MorphShape,
-    frames: fnv::FnvHashMap<u16, Frame>,
+    has_non_scaling_strokes: bool,
+    has_scaling_strokes: bool,
+
+    /// The shapes that have been interpolated so far, by ratio.
+    ///
+    /// Ratios are only ever given to 16 bits of precision, so every ratio a
+    /// morph shape is displayed at is interpolated and tessellated exactly
+    /// once.
+    frames: RefCell<fnv::FnvHashMap<u16, Frame>>,
 }
 
 impl MorphShapeStatic {
     pub fn from_swf_tag(renderer: &mut dyn RenderBackend, swf_tag: &swf::DefineMorphShape) -> Self {
-        let mut morph_shape = Self {
+        // `DefineMorphShape` has no stroke flags; its strokes always scale.
+        let (has_non_scaling_strokes, has_scaling_strokes) = if swf_tag.version >= 2 {
+            (swf_tag.has_non_scaling_strokes, swf_tag.has_scaling_strokes)
+        } else {
+            (false, true)
+        };
+        let morph_shape = Self {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
             end: swf_tag.end.clone(),
-            frames: fnv::FnvHashMap::default(),
+            has_non_scaling_strokes,
+            has_scaling_strokes,
+            frames: RefCell::new(fnv::FnvHashMap::default()),
         };
         // Pre-register the start and end states.
         morph_shape.register_ratio(renderer, 0);
@@ -136,25 +154,62 @@ impl MorphShapeStatic {
         morph_shape
     }
 
-    pub fn register_ratio(&mut self, renderer: &mut dyn RenderBackend, ratio: u16) {
-        if self.frames.contains_key(&ratio) {
-            // Already registered.
-            return;
+    /// Tessellate the shape at a given ratio, if it hasn't been already.
+    pub fn register_ratio(&self, renderer: &mut dyn RenderBackend, ratio: u16) {
+        self.shape_handle(renderer, ratio);
+    }
+
+    /// Get the shape at a given ratio, interpolating it if needed.
+    fn frame(&self, ratio: u16) -> Ref<'_, Frame> {
+        if !self.frames.borrow().contains_key(&ratio) {
+            let frame = Frame {
+                shape: self.interpolate(ratio),
+                hit_test_cache: HitTestCache::new(),
+                shape_handle: Cell::new(None),
+            };
+            self.frames.borrow_mut().insert(ratio, frame);
         }
 
-        // Interpolate MorphShapes into a Shape.
-        use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
-        // Start shape is ratio 65535, end shape is ratio 0.
+        Ref::map(self.frames.borrow(), |frames| &frames[&ratio])
+    }
+
+    /// Get the tessellated shape at a given ratio, tessellating it if needed.
+    fn shape_handle(&self, renderer: &mut dyn RenderBackend, ratio: u16) -> ShapeHandle {
+        let frame = self.frame(ratio);
+        if let Some(shape_handle) = frame.shape_handle.get() {
+            return shape_handle;
+        }
+
+        let shape_handle = renderer.register_shape((&frame.shape).into());
+        frame.shape_handle.set(Some(shape_handle));
+        shape_handle
+    }
+
+    /// The bounds of the shape at a given ratio, including its strokes.
+    fn bounds(&self, ratio: u16) -> BoundingBox {
+        (&self.frame(ratio).shape.shape_bounds).into()
+    }
+
+    /// The weights of the start and end shapes at a given ratio.
+    ///
+    /// Ratio 0 is the start shape, and ratio 65535 is the end shape.
+    fn weights(ratio: u16) -> (f32, f32) {
         let b = f32::from(ratio) / 65535.0;
-        let a = 1.0 - b;
-        let fill_styles: Vec<FillStyle> = self
+        (1.0 - b, b)
+    }
+
+    /// Interpolate the start and end shapes into a `Shape` at a given ratio.
+    fn interpolate(&self, ratio: u16) -> swf::Shape {
+        use swf::{LineStyle, ShapeRecord, ShapeStyles, StyleChangeData};
+        let (a, b) = Self::weights(ratio);
+        let fill_styles = self
             .start
             .fill_styles
             .iter()
             .zip(self.end.fill_styles.iter())
             .map(|(start, end)| lerp_fill(start, end, a, b))
             .collect();
-        let line_styles: Vec<LineStyle> = self
+        let line_styles = self
             .start
             .line_styles
             .iter()
@@ -165,7 +220,10 @@ impl MorphShapeStatic {
                 start_cap: start.start_cap,
                 end_cap: start.end_cap,
                 join_style: start.join_style,
-                fill_style: None,
+                fill_style: match (&start.fill_style, &end.fill_style) {
+                    (Some(start), Some(end)) => Some(lerp_fill(start, end, a, b)),
+                    _ => None,
+                },
                 allow_scale_x: start.allow_scale_x,
                 allow_scale_y: start.allow_scale_y,
                 is_pixel_hinted: start.is_pixel_hinted,
@@ -182,29 +240,24 @@ impl MorphShapeStatic {
         let mut start_y = Twips::new(0);
         let mut end_x = Twips::new(0);
         let mut end_y = Twips::new(0);
-        // TODO: Feels like this could be cleaned up a bit.
         // We step through both the start records and end records, interpolating edges pairwise.
         // Fill style/line style changes should only appear in the start records.
         // However, StyleChangeRecord move_to can appear it both start and end records,
         // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
         // in case one side is missing a move_to; it will implicitly use the last pen position.
+        // Edges are interpolated between absolute positions, so that rounding errors don't
+        // build up along a path and leave it unclosed.
         while let (Some(s), Some(e)) = (start, end) {
             match (s, e) {
                 (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
                     let mut style_change = start_change.clone();
-                    if let Some((s_x, s_y)) = start_change.move_to {
-                        if let Some((e_x, e_y)) = end_change.move_to {
-                            start_x = s_x;
-                            start_y = s_y;
-                            end_x = e_x;
-                            end_y = e_y;
-                            style_change.move_to = Some((
-                                lerp_twips(start_x, end_x, a, b),
-                                lerp_twips(start_y, end_y, a, b),
-                            ));
-                        } else {
-                            panic!("Expected move_to for morph shape")
-                        }
+                    Self::update_pos(&mut start_x, &mut start_y, s);
+                    Self::update_pos(&mut end_x, &mut end_y, e);
+                    if start_change.move_to.is_some() || end_change.move_to.is_some() {
+                        style_change.move_to = Some((
+                            lerp_twips(start_x, end_x, a, b),
+                            lerp_twips(start_y, end_y, a, b),
+                        ));
                     }
                     shape.push(ShapeRecord::StyleChange(style_change));
                     start = start_iter.next();
@@ -212,35 +265,35 @@ impl MorphShapeStatic {
                 }
                 (ShapeRecord::StyleChange(start_change), _) => {
                     let mut style_change = start_change.clone();
-                    if let Some((s_x, s_y)) = start_change.move_to {
-                        start_x = s_x;
-                        start_y = s_y;
+                    Self::update_pos(&mut start_x, &mut start_y, s);
+                    if start_change.move_to.is_some() {
                         style_change.move_to = Some((
                             lerp_twips(start_x, end_x, a, b),
                             lerp_twips(start_y, end_y, a, b),
                         ));
                     }
                     shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
                     start = start_iter.next();
                 }
                 (_, ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = end_change.clone();
-                    if let Some((e_x, e_y)) = end_change.move_to {
-                        end_x = e_x;
-                        end_y = e_y;
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
+                    // Only the pen position of end records is used.
+                    Self::update_pos(&mut end_x, &mut end_y, e);
+                    if end_change.move_to.is_some() {
+                        shape.push(ShapeRecord::StyleChange(StyleChangeData {
+                            move_to: Some((
+                                lerp_twips(start_x, end_x, a, b),
+                                lerp_twips(start_y, end_y, a, b),
+                            )),
+                            fill_style_0: None,
+                            fill_style_1: None,
+                            line_style: None,
+                            new_styles: None,
+                        }));
                     }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut end_x, &mut end_y, s);
                     end = end_iter.next();
-                    continue;
                 }
                 _ => {
-                    shape.push(lerp_edges(s, e, a, b));
+                    shape.push(lerp_edges(s, e, (start_x, start_y), (end_x, end_y), a, b));
                     Self::update_pos(&mut start_x, &mut start_y, s);
                     Self::update_pos(&mut end_x, &mut end_y, e);
                     start = start_iter.next();
@@ -249,30 +302,20 @@ impl MorphShapeStatic {
             }
         }
 
-        let styles = ShapeStyles {
-            fill_styles,
-            line_styles,
-        };
-
-        let bounds = crate::shape_utils::calculate_shape_bounds(&shape[..]);
-        let shape = swf::Shape {
+        swf::Shape {
             version: 4,
             id: 0,
-            shape_bounds: bounds.clone(),
-            edge_bounds: bounds.clone(),
+            shape_bounds: lerp_rectangle(&self.start.shape_bounds, &self.end.shape_bounds, a, b),
+            edge_bounds: lerp_rectangle(&self.start.edge_bounds, &self.end.edge_bounds, a, b),
             has_fill_winding_rule: false,
-            has_non_scaling_strokes: false,
-            has_scaling_strokes: true,
-            styles,
-            shape,
-        };
-
-        let frame = Frame {
-            shape_handle: renderer.register_shape((&shape).into()),
+            has_non_scaling_strokes: self.has_non_scaling_strokes,
+            has_scaling_strokes: self.has_scaling_strokes,
+            styles: ShapeStyles {
+                fill_styles,
+                line_styles,
+            },
             shape,
-            bounds: bounds.into(),
-        };
-        self.frames.insert(ratio, frame);
+        }
     }
 
     fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
@@ -324,7 +367,16 @@ fn lerp_color(start: &Color, end: &Color, a: f32, b: f32) -> Color {
 }
 
 fn lerp_twips(start: Twips, end: Twips, a: f32, b: f32) -> Twips {
-    Twips::new((start.get() as f32 * a + end.get() as f32 * b) as i32)
+    Twips::new((start.get() as f32 * a + end.get() as f32 * b).round() as i32)
+}
+
+fn lerp_rectangle(start: &swf::Rectangle, end: &swf::Rectangle, a: f32, b: f32) -> swf::Rectangle {
+    swf::Rectangle {
+        x_min: lerp_twips(start.x_min, end.x_min, a, b),
+        x_max: lerp_twips(start.x_max, end.x_max, a, b),
+        y_min: lerp_twips(start.y_min, end.y_min, a, b),
+        y_max: lerp_twips(start.y_max, end.y_max, a, b),
+    }
 }
 
 fn lerp_fill(start: &swf::FillStyle, end: &swf::FillStyle, a: f32, b: f32) -> swf::FillStyle {
@@ -391,13 +443,20 @@ fn lerp_fill(start: &swf::FillStyle, end: &swf::FillStyle, a: f32, b: f32) -> sw
     }
 }
 
+/// Interpolate a pair of edges, given the absolute pen positions they start from.
+///
+/// The edges are interpolated between their absolute control and anchor points, so the
+/// interpolated edge ends exactly where the interpolated pen position will be.
 fn lerp_edges(
     start: &swf::ShapeRecord,
     end: &swf::ShapeRecord,
+    start_pos: (Twips, Twips),
+    end_pos: (Twips, Twips),
     a: f32,
     b: f32,
 ) -> swf::ShapeRecord {
     use swf::ShapeRecord;
+    let pen = lerp_point(start_pos, end_pos, a, b);
     match (start, end) {
         (
             &ShapeRecord::StraightEdge {
@@ -408,82 +467,70 @@ fn lerp_edges(
                 delta_x: end_dx,
                 delta_y: end_dy,
             },
-        ) => ShapeRecord::StraightEdge {
-            delta_x: lerp_twips(start_dx, end_dx, a, b),
-            delta_y: lerp_twips(start_dy, end_dy, a, b),
-        },
-
-        (
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: start_cdx,
-                control_delta_y: start_cdy,
-                anchor_delta_x: start_adx,
-                anchor_delta_y: start_ady,
-            },
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: end_cdx,
-                control_delta_y: end_cdy,
-                anchor_delta_x: end_adx,
-                anchor_delta_y: end_ady,
-            },
-        ) => ShapeRecord::CurvedEdge {
-            control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-            control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-            anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-            anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
-        },
-
-        (
-            &ShapeRecord::StraightEdge {
-                delta_x: start_dx,
-                delta_y: start_dy,
-            },
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: end_cdx,
-                control_delta_y: end_cdy,
-                anchor_delta_x: end_adx,
-                anchor_delta_y: end_ady,
-            },
         ) => {
-            let start_cdx = start_dx / 2;
-            let start_cdy = start_dy / 2;
-            let start_adx = start_cdx;
-            let start_ady = start_cdy;
-            ShapeRecord::CurvedEdge {
-                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
+            let anchor = lerp_point(
+                (start_pos.0 + start_dx, start_pos.1 + start_dy),
+                (end_pos.0 + end_dx, end_pos.1 + end_dy),
+                a,
+                b,
+            );
+            ShapeRecord::StraightEdge {
+                delta_x: anchor.0 - pen.0,
+                delta_y: anchor.1 - pen.1,
             }
         }
 
-        (
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: start_cdx,
-                control_delta_y: start_cdy,
-                anchor_delta_x: start_adx,
-                anchor_delta_y: start_ady,
-            },
-            &ShapeRecord::StraightEdge {
-                delta_x: end_dx,
-                delta_y: end_dy,
-            },
-        ) => {
-            let end_cdx = end_dx / 2;
-            let end_cdy = end_dy / 2;
-            let end_adx = end_cdx;
-            let end_ady = end_cdy;
+        _ => {
+            // Straight edges are morphed to and from curves as a curve with its control point
+            // halfway along the edge.
+            let (start_control, start_anchor) = edge_points(start, start_pos);
+            let (end_control, end_anchor) = edge_points(end, end_pos);
+            let control = lerp_point(start_control, end_control, a, b);
+            let anchor = lerp_point(start_anchor, end_anchor, a, b);
             ShapeRecord::CurvedEdge {
-                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
+                control_delta_x: control.0 - pen.0,
+                control_delta_y: control.1 - pen.1,
+                anchor_delta_x: anchor.0 - control.0,
+                anchor_delta_y: anchor.1 - control.1,
             }
         }
-        _ => unreachable!("{:?} {:?}", start, end),
     }
 }
 
+/// The absolute control and anchor points of an edge starting at a given position.
+fn edge_points(
+    edge: &swf::ShapeRecord,
+    (x, y): (Twips, Twips),
+) -> ((Twips, Twips), (Twips, Twips)) {
+    use swf::ShapeRecord;
+    match *edge {
+        ShapeRecord::StraightEdge { delta_x, delta_y } => (
+            (x + delta_x / 2, y + delta_y / 2),
+            (x + delta_x, y + delta_y),
+        ),
+        ShapeRecord::CurvedEdge {
+            control_delta_x,
+            control_delta_y,
+            anchor_delta_x,
+            anchor_delta_y,
+        } => {
+            let control = (x + control_delta_x, y + control_delta_y);
+            (
+                control,
+                (control.0 + anchor_delta_x, control.1 + anchor_delta_y),
+            )
+        }
+        ShapeRecord::StyleChange(_) => unreachable!("{:?}", edge),
+    }
+}
+
+fn lerp_point(start: (Twips, Twips), end: (Twips, Twips), a: f32, b: f32) -> (Twips, Twips) {
+    (
+        lerp_twips(start.0, end.0, a, b),
+        lerp_twips(start.1, end.1, a, b),
+    )
+}
+
 fn lerp_matrix(start: &swf::Matrix, end: &swf::Matrix, a: f32, b: f32) -> swf::Matrix {
     // TODO: Lerping a matrix element-wise is geometrically wrong,
     // but I doubt Flash is decomposing the matrix into scale-rotate-translate?
@@ -501,13 +548,19 @@ fn lerp_gradient(start: &swf::Gradient, end: &swf::Gradient, a: f32, b: f32) ->
     use swf::{Gradient, GradientRecord};
     // Morph gradients are guaranteed to have the same number of records in the start/end gradient.
     debug_assert!(start.records.len() == end.records.len());
+    // Gradient ratios must never decrease, so clamp each record to the one before it.
+    let mut min_ratio = 0;
     let records: Vec<GradientRecord> = start
         .records
         .iter()
         .zip(end.records.iter())
-        .map(|(start, end)| swf::GradientRecord {
-            ratio: (f32::from(start.ratio) * a + f32::from(end.ratio) * b) as u8,
-            color: lerp_color(&start.color, &end.color, a, b),
+        .map(|(start, end)| {
+            let ratio = (f32::from(start.ratio) * a + f32::from(end.ratio) * b).round() as u8;
+            min_ratio = ratio.max(min_ratio);
+            swf::GradientRecord {
+                ratio: min_ratio,
+                color: lerp_color(&start.color, &end.color, a, b),
+            }
         })
         .collect();
 
@@ -7,28 +7,33 @@ use crate::avm2::{
     Avm2, Error as Avm2Error, Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
     StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
-use crate::backend::audio::AudioStreamHandle;
+use crate::backend::audio::{start_sound_with_info, AudioStreamHandle};
+use crate::backend::navigator::{url_from_relative_url, RequestOptions};
+use crate::backend::render::glue_tables_to_jpeg;
+use crate::bitmap_data::BitmapDataStorage;
 
 use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
 use crate::character::Character;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::container::{ChildContainer, TDisplayObjectContainer};
 use crate::display_object::{
-    Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
+    Bitmap, BitmapSource, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic,
+    TDisplayObject, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::font::Font;
 use crate::prelude::*;
-use crate::shape_utils::DrawCommand;
+use crate::shape_utils::{DrawCommand, NineSlice};
 use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
+use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
-use std::cell::Ref;
-use std::collections::HashMap;
+use std::cell::{Ref, RefMut};
+use std::collections::{hash_map::Entry, HashMap};
 use std::convert::TryFrom;
 use std::sync::Arc;
 use swf::read::SwfRead;
@@ -51,7 +56,7 @@ pub struct MovieClipData<'gc> {
     static_data: Gc<'gc, MovieClipStatic>,
     tag_stream_pos: u64,
     current_frame: FrameNumber,
-    audio_stream: Option<AudioStreamHandle>,
+    audio_stream: Option<AudioStream>,
     container: ChildContainer<'gc>,
     object: Option<AvmObject<'gc>>,
     clip_actions: Vec<ClipAction>,
@@ -63,6 +68,30 @@ pub struct MovieClipData<'gc> {
     is_focusable: bool,
     has_focus: bool,
     enabled: bool,
+
+    /// Whether this clip acts as a button, as set by `Sprite.buttonMode`.
+    button_mode: bool,
+
+    /// Whether the hand cursor is shown over this clip when it acts as a
+    /// button, as set by `Sprite.useHandCursor`.
+    use_hand_cursor: bool,
+
+    /// The 9-slice scaling grid of this clip, in its own coordinates.
+    scaling_grid: Option<swf::Rectangle>,
+
+    /// How far the tags of this clip have been preloaded.
+    preload_progress: PreloadProgress,
+
+    /// The frame of each import of assets from another movie that has yet
+    /// to finish.
+    ///
+    /// Like frames that have yet to load, these frames don't play until
+    /// their imports finish.
+    pending_imports: Vec<FrameNumber>,
+
+    /// Snapshots of the aggregated timeline at regular frames, which rewinds
+    /// start from instead of frame 1.
+    goto_snapshots: Vec<GotoSnapshot>,
 }
 
 unsafe impl<'gc> Collect for MovieClipData<'gc> {
@@ -99,10 +128,35 @@ impl<'gc> MovieClip<'gc> {
                 is_focusable: false,
                 has_focus: false,
                 enabled: true,
+                button_mode: false,
+                use_hand_cursor: true,
+                scaling_grid: None,
+                preload_progress: Default::default(),
+                pending_imports: Vec::new(),
+                goto_snapshots: Vec::new(),
             },
         ))
     }
 
+    /// Construct a movie clip with a single empty frame, to be populated by
+    /// script.
+    ///
+    /// Unlike clips instantiated from a timeline, the new clip is already on
+    /// its first frame.
+    pub fn new_with_avm2(
+        swf: SwfSlice,
+        avm2_object: Avm2Object<'gc>,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Self {
+        let clip = Self::new(swf, gc_context);
+        let mut write = clip.0.write(gc_context);
+        write.object = Some(avm2_object.into());
+        write.current_frame = 1;
+        drop(write);
+
+        clip
+    }
+
     pub fn new_with_data(
         gc_context: MutationContext<'gc, '_>,
         id: CharacterId,
@@ -122,6 +176,7 @@ impl<'gc> MovieClip<'gc> {
                         audio_stream_info: None,
                         frame_labels: HashMap::new(),
                         scene_labels: HashMap::new(),
+                        frames: Vec::new(),
                     },
                 ),
                 tag_stream_pos: 0,
@@ -138,6 +193,12 @@ impl<'gc> MovieClip<'gc> {
                 is_focusable: false,
                 has_focus: false,
                 enabled: true,
+                button_mode: false,
+                use_hand_cursor: true,
+                scaling_grid: None,
+                preload_progress: Default::default(),
+                pending_imports: Vec::new(),
+                goto_snapshots: Vec::new(),
             },
         ))
     }
@@ -159,12 +220,24 @@ impl<'gc> MovieClip<'gc> {
     /// load boundary.
     pub fn replace_with_movie(
         &mut self,
-        gc_context: MutationContext<'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         movie: Option<Arc<SwfMovie>>,
     ) {
         self.0
-            .write(gc_context)
-            .replace_with_movie(gc_context, movie)
+            .write(context.gc_context)
+            .replace_with_movie(context, movie)
+    }
+
+    /// Replace the movie of this clip with a part of the same movie that has
+    /// more of its data loaded.
+    ///
+    /// Playback continues where it was. The newly arrived tags are picked up
+    /// by the next call to `preload`.
+    pub fn extend_movie(self, gc_context: MutationContext<'gc, '_>, movie: Arc<SwfMovie>) {
+        let mut write = self.0.write(gc_context);
+        let mut static_data = (&*write.static_data).clone();
+        static_data.swf = movie.into();
+        write.static_data = Gc::allocate(gc_context, static_data);
     }
 
     pub fn preload(
@@ -177,9 +250,20 @@ impl<'gc> MovieClip<'gc> {
         // Should be able to hoist this up somewhere, or use MaybeUninit.
         let mut static_data = (&*self.0.read().static_data).clone();
         let data = self.0.read().static_data.swf.clone();
-        let mut reader = data.read_from(self.0.read().tag_stream_pos);
-        let mut cur_frame = 1;
-        let mut ids = fnv::FnvHashMap::default();
+
+        // A movie that is still loading is preloaded a piece at a time,
+        // picking up where the last piece left off.
+        // TODO: Morph shapes defined in an earlier piece don't get the ratios
+        // used in later pieces.
+        let mut progress = std::mem::take(&mut self.0.write(context.gc_context).preload_progress);
+        if progress.next_tag_pos > 0 && !progress.is_loading {
+            // Already preloaded in full.
+            self.0.write(context.gc_context).preload_progress = progress;
+            return;
+        }
+
+        let mut reader = data.read_from(progress.next_tag_pos);
+        let mut cur_frame = progress.frames_loaded + 1;
         let version = reader.version();
         let tag_callback = |reader: &mut SwfStream<&[u8]>, tag_code, tag_len| {
             let data = *reader.get_inner().get_ref();
@@ -238,10 +322,18 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_button_2(context, reader),
+                TagCode::CsmTextSettings => self
+                    .0
+                    .write(context.gc_context)
+                    .csm_text_settings(context, reader),
                 TagCode::DefineButtonCxform => self
                     .0
                     .write(context.gc_context)
                     .define_button_cxform(context, reader, tag_len),
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::DefineButtonSound => self
                     .0
                     .write(context.gc_context)
@@ -266,6 +358,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_4(context, reader),
+                TagCode::DefineFontAlignZones => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_align_zones(context, reader, tag_len),
                 TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                     context,
                     reader,
@@ -312,6 +408,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_text(context, reader, 2),
+                TagCode::DefineVideoStream => self
+                    .0
+                    .write(context.gc_context)
+                    .define_video_stream(context, reader),
                 TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
                 TagCode::DoAbc => self.do_abc(context, reader, tag_len),
                 TagCode::SymbolClass => self.symbol_class(context, reader),
@@ -322,6 +422,8 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .export_assets(context, reader),
+                TagCode::ImportAssets => self.import_assets(context, reader, cur_frame, 1),
+                TagCode::ImportAssets2 => self.import_assets(context, reader, cur_frame, 2),
                 TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
                     context,
                     reader,
@@ -337,7 +439,7 @@ impl<'gc> MovieClip<'gc> {
                     context,
                     reader,
                     tag_len,
-                    &mut ids,
+                    &mut progress,
                     morph_shapes,
                     1,
                 ),
@@ -345,7 +447,7 @@ impl<'gc> MovieClip<'gc> {
                     context,
                     reader,
                     tag_len,
-                    &mut ids,
+                    &mut progress,
                     morph_shapes,
                     2,
                 ),
@@ -353,7 +455,7 @@ impl<'gc> MovieClip<'gc> {
                     context,
                     reader,
                     tag_len,
-                    &mut ids,
+                    &mut progress,
                     morph_shapes,
                     3,
                 ),
@@ -361,23 +463,48 @@ impl<'gc> MovieClip<'gc> {
                     context,
                     reader,
                     tag_len,
-                    &mut ids,
+                    &mut progress,
                     morph_shapes,
                     4,
                 ),
-                TagCode::RemoveObject => self
-                    .0
-                    .write(context.gc_context)
-                    .preload_remove_object(context, reader, &mut ids, 1),
-                TagCode::RemoveObject2 => self
-                    .0
-                    .write(context.gc_context)
-                    .preload_remove_object(context, reader, &mut ids, 2),
+                TagCode::RemoveObject => self.0.write(context.gc_context).preload_remove_object(
+                    context,
+                    reader,
+                    &mut progress,
+                    1,
+                ),
+                TagCode::RemoveObject2 => self.0.write(context.gc_context).preload_remove_object(
+                    context,
+                    reader,
+                    &mut progress,
+                    2,
+                ),
                 TagCode::ShowFrame => self.0.write(context.gc_context).preload_show_frame(
                     context,
                     reader,
                     &mut cur_frame,
+                    &mut progress,
+                    &mut static_data,
+                    (tag_pos + tag_len) as u64,
+                ),
+                TagCode::DoAction => self.0.write(context.gc_context).preload_do_action(
+                    reader,
+                    tag_len,
+                    &mut progress,
+                    &static_data,
                 ),
+                TagCode::SetBackgroundColor => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_set_background_color(reader, &mut progress),
+                TagCode::SetTabIndex => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_set_tab_index(reader, &mut progress),
+                TagCode::StartSound => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_start_sound_1(reader, &mut progress),
                 TagCode::ScriptLimits => self
                     .0
                     .write(context.gc_context)
@@ -395,19 +522,41 @@ impl<'gc> MovieClip<'gc> {
                         context,
                         reader,
                         cur_frame,
+                        &mut progress,
                         &mut static_data,
                         tag_len,
                     )
                 }
+                TagCode::VideoFrame => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_video_frame(context, reader, tag_len),
                 _ => Ok(()),
             }
         };
-        let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::End);
-        self.0.write(context.gc_context).static_data =
-            Gc::allocate(context.gc_context, static_data);
+        let result = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::End);
+
+        // If the movie is still loading, the tags ran out before the `End` tag.
+        let is_loading = result.is_err() && !data.movie.is_loaded();
+
+        // Tags after the last `ShowFrame` still run on the last frame.
+        if !is_loading && !progress.frame_ops.is_empty() {
+            static_data.frames.push(PreparsedFrame {
+                next_tag_pos: reader.get_ref().position(),
+                ops: std::mem::take(&mut progress.frame_ops),
+            });
+        }
+
+        progress.next_tag_pos = reader.get_ref().position();
+        progress.frames_loaded = cur_frame - 1;
+        progress.is_loading = is_loading;
+        let mut write = self.0.write(context.gc_context);
+        write.static_data = Gc::allocate(context.gc_context, static_data);
+        write.preload_progress = progress;
+        drop(write);
 
         // Finalize audio stream.
-        if self.0.read().static_data.audio_stream_info.is_some() {
+        if !is_loading && self.0.read().static_data.audio_stream_info.is_some() {
             context.audio.preload_sound_stream_end(self.0.read().id());
         }
     }
@@ -546,6 +695,30 @@ impl<'gc> MovieClip<'gc> {
                             library.get_character_by_id(id)
                         {
                             mc.set_avm2_constructor(activation.context.gc_context, Some(proto))
+                        } else if let Some(Character::Sound(sound)) =
+                            library.get_character_by_id(id)
+                        {
+                            // Instances of the class are found by their prototype.
+                            let sound = *sound;
+                            let mut class = proto;
+                            match class
+                                .get_property(
+                                    class,
+                                    &Avm2QName::new(Avm2Namespace::public_namespace(), "prototype"),
+                                    &mut activation,
+                                )
+                                .and_then(|v| v.coerce_to_object(&mut activation))
+                            {
+                                Ok(sound_proto) => activation
+                                    .context
+                                    .avm2
+                                    .register_sound_symbol(sound_proto, sound),
+                                Err(e) => log::warn!(
+                                    "Got AVM2 error {} when linking symbol class {} to sound",
+                                    e,
+                                    class_name
+                                ),
+                            }
                         } else {
                             log::warn!(
                                 "Symbol class {} cannot be assigned to invalid character id {}",
@@ -566,6 +739,69 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
+    /// Start loading the symbols imported from another movie.
+    ///
+    /// The imported symbols are added to this movie's library once the other
+    /// movie has loaded. Until then, this frame doesn't play.
+    #[inline]
+    fn import_assets(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&[u8]>,
+        cur_frame: FrameNumber,
+        version: u8,
+    ) -> DecodeResult {
+        let url = reader.read_c_string()?;
+        if version >= 2 {
+            reader.read_u8()?; // Reserved; must be 1
+            reader.read_u8()?; // Reserved; must be 0
+        }
+        let num_imports = reader.read_u16()?;
+        let mut imports = Vec::with_capacity(num_imports.into());
+        for _ in 0..num_imports {
+            imports.push(swf::ExportedAsset {
+                id: reader.read_u16()?,
+                name: reader.read_c_string()?,
+            });
+        }
+
+        let movie = self
+            .movie()
+            .ok_or("Attempted to import assets into movie without any")?;
+        let player = context
+            .player
+            .clone()
+            .ok_or("Attempted to import assets without a player")?;
+
+        // Imports are relative to the movie that imports them.
+        let url = match movie.url() {
+            Some(movie_url) => url_from_relative_url(movie_url, &url)
+                .map(|url| url.into_string())
+                .unwrap_or(url),
+            None => url,
+        };
+        let fetch = context.navigator.fetch(&url, RequestOptions::get());
+        let process = context
+            .load_manager
+            .load_imported_assets(player, self, cur_frame, imports, fetch, url);
+        self.0
+            .write(context.gc_context)
+            .pending_imports
+            .push(cur_frame);
+        context.navigator.spawn_future(process);
+
+        Ok(())
+    }
+
+    /// Let a frame that imports assets play, now that one of its imports
+    /// has finished.
+    pub fn finish_import(self, gc_context: MutationContext<'gc, '_>, frame: FrameNumber) {
+        let mut mc = self.0.write(gc_context);
+        if let Some(index) = mc.pending_imports.iter().position(|f| *f == frame) {
+            mc.pending_imports.remove(index);
+        }
+    }
+
     #[inline]
     fn scene_and_frame_labels(
         self,
@@ -595,14 +831,19 @@ impl<'gc> MovieClip<'gc> {
             );
         }
 
-        for FrameLabelData { frame_num, label } in sfl_data.frame_labels {
+        for FrameLabelData {
+            frame_num,
+            mut label,
+        } in sfl_data.frame_labels
+        {
+            // Frame labels are case insensitive (ASCII).
+            label.make_ascii_lowercase();
             static_data.frame_labels.insert(label, frame_num as u16 + 1);
         }
 
         Ok(())
     }
 
-    #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
     }
@@ -664,8 +905,47 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().current_frame
     }
 
+    /// How far this clip's timeline has drifted from the stream sound playing
+    /// on it, at the given frame rate.
+    /// Returns `None` if no stream sound is playing.
+    pub fn audio_stream_drift(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        frame_rate: f64,
+    ) -> Option<StreamDrift> {
+        let mc = self.0.read();
+        let audio_stream = mc.audio_stream.as_ref()?;
+        let sample_rate = mc
+            .static_data
+            .audio_stream_info
+            .as_ref()?
+            .stream_format
+            .sample_rate;
+
+        let frame_time = 1000.0 / frame_rate;
+        let timeline_position =
+            f64::from(mc.current_frame.saturating_sub(audio_stream.start_frame)) * frame_time;
+        let (audio_position, is_hypothetical) =
+            match context.audio.get_stream_position(audio_stream.handle) {
+                Some(position) => (position, false),
+                None => (
+                    f64::from(audio_stream.streamed_sample_frames) * 1000.0
+                        / f64::from(sample_rate),
+                    true,
+                ),
+            };
+
+        Some(StreamDrift {
+            frames: (timeline_position - audio_position) / frame_time,
+            is_hypothetical,
+        })
+    }
+
     /// Return the current scene.
-    pub fn current_scene(self) -> Option<Scene> {
+    ///
+    /// Clips without any scene data have a single unnamed scene that spans
+    /// their whole timeline.
+    pub fn current_scene(self) -> Scene {
         let current_frame = self.0.read().current_frame();
 
         self.filter_scenes(
@@ -687,20 +967,12 @@ impl<'gc> MovieClip<'gc> {
                         .unwrap_or(true)
             },
         )
+        .unwrap_or_else(|| self.whole_timeline_scene())
     }
 
     /// Return the previous scene.
     pub fn previous_scene(self) -> Option<Scene> {
-        let current_frame = self
-            .current_scene()
-            .map(
-                |Scene {
-                     name: _,
-                     start,
-                     length: _,
-                 }| start,
-            )
-            .unwrap_or_else(|| self.current_frame());
+        let current_frame = self.current_scene().start;
 
         self.filter_scenes(
             |best,
@@ -750,7 +1022,8 @@ impl<'gc> MovieClip<'gc> {
 
     /// Return all scenes in the movie.
     ///
-    /// Scenes will be sorted in playback order.
+    /// Scenes will be sorted in playback order. Clips without any scene data
+    /// have a single unnamed scene that spans their whole timeline.
     pub fn scenes(self) -> Vec<Scene> {
         let read = self.0.read();
         let mut out = Vec::new();
@@ -759,6 +1032,10 @@ impl<'gc> MovieClip<'gc> {
             out.push(scene.clone());
         }
 
+        if out.is_empty() {
+            out.push(self.whole_timeline_scene());
+        }
+
         out.sort_unstable_by(
             |Scene {
                  name: _,
@@ -775,6 +1052,15 @@ impl<'gc> MovieClip<'gc> {
         out
     }
 
+    /// The scene of a clip without any scene data.
+    fn whole_timeline_scene(self) -> Scene {
+        Scene {
+            name: "".to_string(),
+            start: 1,
+            length: self.total_frames(),
+        }
+    }
+
     /// Scan through the list of scenes and yield the best one, if available,
     /// according to a given criterion function.
     fn filter_scenes<F>(self, mut cond: F) -> Option<Scene>
@@ -836,8 +1122,7 @@ impl<'gc> MovieClip<'gc> {
     }
 
     pub fn frames_loaded(self) -> FrameNumber {
-        // TODO(Herschel): root needs to progressively stream in frames.
-        self.0.read().static_data.total_frames
+        self.0.read().frames_loaded()
     }
 
     pub fn set_avm1_constructor(
@@ -884,47 +1169,27 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().static_data.frame_labels.get(&label).copied()
     }
 
-    pub fn scene_label_to_number(self, scene_label: &str) -> Option<FrameNumber> {
+    /// Return the scene with a given name.
+    pub fn scene_by_name(self, scene_name: &str) -> Option<Scene> {
         //TODO: Are scene labels also case insensitive?
         self.0
             .read()
             .static_data
             .scene_labels
-            .get(scene_label)
-            .map(|Scene { start, .. }| start)
-            .copied()
+            .get(scene_name)
+            .cloned()
     }
 
-    pub fn frame_exists_within_scene(self, frame_label: &str, scene_label: &str) -> bool {
-        let scene = self.scene_label_to_number(scene_label);
-        let frame = self.frame_label_to_number(frame_label);
-
-        if scene.is_none() || frame.is_none() {
-            return false;
-        }
-
-        let scene = scene.unwrap();
-        let frame = frame.unwrap();
-
-        if scene <= frame {
-            let mut end = self.total_frames();
-            for (
-                _label,
-                Scene {
-                    start: new_scene_start,
-                    ..
-                },
-            ) in self.0.read().static_data.scene_labels.iter()
-            {
-                if *new_scene_start < end && *new_scene_start > scene {
-                    end = *new_scene_start;
-                }
-            }
+    /// Find the frame number of a frame label, if it is within a given scene.
+    pub fn frame_label_to_number_in_scene(
+        self,
+        frame_label: &str,
+        scene: &Scene,
+    ) -> Option<FrameNumber> {
+        let end = scene.start.saturating_add(scene.length);
 
-            frame < end
-        } else {
-            false
-        }
+        self.frame_label_to_number(frame_label)
+            .filter(|frame| *frame >= scene.start && *frame < end)
     }
 
     /// Gets the clip events for this movieclip.
@@ -948,35 +1213,15 @@ impl<'gc> MovieClip<'gc> {
         _context: &mut UpdateContext<'_, 'gc, '_>,
         frame: FrameNumber,
     ) -> impl DoubleEndedIterator<Item = SwfSlice> {
-        use swf::{read::Reader, TagCode};
-
         let mut actions: SmallVec<[SwfSlice; 2]> = SmallVec::new();
 
-        // Iterate through this clip's tags, counting frames until we reach the target frame.
         if frame > 0 && frame <= self.total_frames() {
-            let mut cur_frame = 1;
-            let clip = self.0.read();
-            let len = clip.tag_stream_len();
-            let mut reader = clip.static_data.swf.read_from(0);
-            while cur_frame <= frame && reader.get_ref().position() < len as u64 {
-                let tag_callback =
-                    |reader: &mut Reader<std::io::Cursor<&[u8]>>, tag_code, tag_len| {
-                        match tag_code {
-                            TagCode::ShowFrame => cur_frame += 1,
-                            TagCode::DoAction if cur_frame == frame => {
-                                // On the target frame, add any DoAction tags to the array.
-                                if let Some(code) =
-                                    clip.static_data.swf.resize_to_reader(reader, tag_len)
-                                {
-                                    actions.push(code)
-                                }
-                            }
-                            _ => (),
-                        }
-                        Ok(())
-                    };
-
-                let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::ShowFrame);
+            let static_data = self.0.read().static_data;
+            if let Some(preparsed) = static_data.frames.get(usize::from(frame) - 1) {
+                actions.extend(preparsed.ops.iter().filter_map(|op| match op {
+                    TimelineOp::DoAction(code) => Some(code.clone()),
+                    _ => None,
+                }));
             }
         }
 
@@ -990,11 +1235,13 @@ impl<'gc> MovieClip<'gc> {
     ) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.set_fill_style(style);
+        mc.base.set_dirty(true);
     }
 
     pub fn clear(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.clear();
+        mc.base.set_dirty(true);
     }
 
     pub fn set_line_style(
@@ -1004,11 +1251,13 @@ impl<'gc> MovieClip<'gc> {
     ) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.set_line_style(style);
+        mc.base.set_dirty(true);
     }
 
     pub fn draw_command(self, context: &mut UpdateContext<'_, 'gc, '_>, command: DrawCommand) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.draw_command(command);
+        mc.base.set_dirty(true);
     }
 
     pub fn run_clip_event(
@@ -1027,6 +1276,13 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         run_display_actions: bool,
     ) {
+        // Wait for the next frame to load.
+        if self.current_frame() >= self.frames_loaded()
+            && self.frames_loaded() < self.total_frames()
+        {
+            return;
+        }
+
         // Advance frame number.
         if self.current_frame() < self.total_frames() {
             self.0.write(context.gc_context).current_frame += 1;
@@ -1041,54 +1297,39 @@ impl<'gc> MovieClip<'gc> {
             self.stop(context);
         }
 
-        let mc = self.0.read();
-        let _tag_pos = mc.tag_stream_pos;
-        let data = mc.static_data.swf.clone();
-        let mut reader = data.read_from(mc.tag_stream_pos);
+        let static_data = self.0.read().static_data;
         let mut has_stream_block = false;
-        drop(mc);
-
-        let version = reader.version();
-        use swf::TagCode;
-        let tag_callback = |reader: &mut SwfStream<&[u8]>, tag_code, tag_len| {
-            let data = *reader.get_inner().get_ref();
-            let tag_pos = reader.get_inner().position() as usize;
-            let tag_slice = data
-                .get(tag_pos..tag_pos + tag_len)
-                .ok_or("Not enough data for tag")?;
-            let reader = &mut SwfStream::new(std::io::Cursor::new(tag_slice), version);
-            match tag_code {
-                TagCode::DoAction => self.do_action(self_display_object, context, reader, tag_len),
-                TagCode::PlaceObject if run_display_actions => {
-                    self.place_object(self_display_object, context, reader, tag_len, 1)
-                }
-                TagCode::PlaceObject2 if run_display_actions => {
-                    self.place_object(self_display_object, context, reader, tag_len, 2)
-                }
-                TagCode::PlaceObject3 if run_display_actions => {
-                    self.place_object(self_display_object, context, reader, tag_len, 3)
-                }
-                TagCode::PlaceObject4 if run_display_actions => {
-                    self.place_object(self_display_object, context, reader, tag_len, 4)
-                }
-                TagCode::RemoveObject if run_display_actions => {
-                    self.remove_object(context, reader, 1)
-                }
-                TagCode::RemoveObject2 if run_display_actions => {
-                    self.remove_object(context, reader, 2)
-                }
-                TagCode::SetBackgroundColor => self.set_background_color(context, reader),
-                TagCode::StartSound => self.start_sound_1(context, reader),
-                TagCode::SoundStreamBlock => {
-                    has_stream_block = true;
-                    self.sound_stream_block(context, reader)
+        let frame_index = usize::from(self.current_frame()) - 1;
+        if let Some(preparsed) = static_data.frames.get(frame_index) {
+            for op in &preparsed.ops {
+                match op {
+                    TimelineOp::DoAction(code) => {
+                        self.do_action(self_display_object, context, code.clone())
+                    }
+                    TimelineOp::PlaceObject(place_object) if run_display_actions => {
+                        self.place_object(self_display_object, context, place_object)
+                    }
+                    TimelineOp::RemoveObject(depth) if run_display_actions => {
+                        self.remove_object(context, *depth)
+                    }
+                    TimelineOp::SetBackgroundColor(color) => {
+                        self.set_background_color(context, color.clone())
+                    }
+                    TimelineOp::SetTabIndex { depth, tab_index } if run_display_actions => {
+                        self.set_child_tab_index(context, *depth, *tab_index)
+                    }
+                    TimelineOp::StartSound(start_sound) => self.start_sound_1(context, start_sound),
+                    TimelineOp::SoundStreamBlock(block_sample_frames) => {
+                        has_stream_block = true;
+                        if let Err(e) = self.sound_stream_block(context, *block_sample_frames) {
+                            log::error!("Error streaming sound block: {}", e);
+                        }
+                    }
+                    _ => (),
                 }
-                _ => Ok(()),
             }
-        };
-        let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::ShowFrame);
-
-        self.0.write(context.gc_context).tag_stream_pos = reader.get_ref().position();
+            self.0.write(context.gc_context).tag_stream_pos = preparsed.next_tag_pos;
+        }
 
         // If we are playing a streaming sound, there should(?) be a `SoundStreamBlock` on each frame.
         if !has_stream_block {
@@ -1137,7 +1378,7 @@ impl<'gc> MovieClip<'gc> {
                     }
                 }
                 // Run first frame.
-                child.apply_place_object(context.gc_context, place_object);
+                child.apply_place_object(context, place_object);
                 child.post_instantiation(context, child, None, Instantiator::Movie, false);
                 child.run_frame(context);
             }
@@ -1188,14 +1429,36 @@ impl<'gc> MovieClip<'gc> {
         // This map will maintain a map of depth -> placement commands.
         // TODO: Move this to UpdateContext to avoid allocations.
         let mut goto_commands = vec![];
+        let mut index = 0;
+
+        // Tab indices set by the intermediate frames. These are applied once
+        // the children they refer to exist.
+        let mut tab_indices = vec![];
 
         self.0.write(context.gc_context).stop_audio_stream(context);
 
         let is_rewind = if frame < self.current_frame() {
             // Because we can only step forward, we have to start at frame 1
-            // when rewinding.
-            self.0.write(context.gc_context).tag_stream_pos = 0;
-            self.0.write(context.gc_context).current_frame = 0;
+            // when rewinding, or from the last snapshot before the destination frame.
+            // The snapshot must be from before the destination frame, as its
+            // tags are re-run below.
+            let mut write = self.0.write(context.gc_context);
+            let snapshot = write
+                .goto_snapshots
+                .iter()
+                .rev()
+                .find(|snapshot| snapshot.frame < frame)
+                .cloned();
+            if let Some(snapshot) = snapshot {
+                goto_commands = snapshot.goto_commands;
+                index = snapshot.index;
+                write.tag_stream_pos = snapshot.tag_stream_pos;
+                write.current_frame = snapshot.frame;
+            } else {
+                write.tag_stream_pos = 0;
+                write.current_frame = 0;
+            }
+            drop(write);
 
             // Remove all display objects that were created after the destination frame.
             // TODO: We want to do something like self.children.retain here,
@@ -1229,95 +1492,73 @@ impl<'gc> MovieClip<'gc> {
 
         // Step through the intermediate frames, and aggregate the deltas of each frame.
         let mc = self.0.read();
+        let static_data = mc.static_data;
         let mut frame_pos = mc.tag_stream_pos;
-        let data = mc.static_data.swf.clone();
-        let mut reader = data.read_from(mc.tag_stream_pos);
-        let mut index = 0;
 
-        let len = mc.tag_stream_len() as u64;
         // Sanity; let's make sure we don't seek way too far.
-        // TODO: This should be self.frames_loaded() when we implement that.
-        let clamped_frame = if frame <= mc.total_frames() {
+        let clamped_frame = if frame <= mc.frames_loaded() {
             frame
         } else {
-            mc.total_frames()
+            mc.frames_loaded()
         };
+        // Long timelines take snapshots as rewinds pass through them.
+        let snapshot_interval = context.goto_snapshots.interval;
+        let takes_snapshots = is_rewind
+            && snapshot_interval > 0
+            && mc.total_frames() >= context.goto_snapshots.min_total_frames;
         drop(mc);
 
-        while self.current_frame() < clamped_frame && frame_pos < len {
-            self.0.write(context.gc_context).current_frame += 1;
-            frame_pos = reader.get_inner().position();
-
-            let version = reader.version();
-            use swf::TagCode;
-            let tag_callback = |reader: &mut SwfStream<&[u8]>, tag_code, tag_len| {
-                let data = *reader.get_inner().get_ref();
-                let tag_pos = reader.get_inner().position() as usize;
-                let tag_slice = &data[tag_pos..tag_pos + tag_len];
-                let reader = &mut SwfStream::new(std::io::Cursor::new(tag_slice), version);
-                match tag_code {
-                    TagCode::PlaceObject => {
-                        index += 1;
-                        let mut mc = self.0.write(context.gc_context);
-
-                        mc.goto_place_object(
-                            reader,
-                            tag_len,
-                            1,
-                            &mut goto_commands,
-                            is_rewind,
-                            index,
-                        )
-                    }
-                    TagCode::PlaceObject2 => {
-                        index += 1;
-                        let mut mc = self.0.write(context.gc_context);
-
-                        mc.goto_place_object(
-                            reader,
-                            tag_len,
-                            2,
-                            &mut goto_commands,
-                            is_rewind,
-                            index,
-                        )
-                    }
-                    TagCode::PlaceObject3 => {
-                        index += 1;
-                        let mut mc = self.0.write(context.gc_context);
+        while self.current_frame() < clamped_frame {
+            let preparsed = match static_data.frames.get(usize::from(self.current_frame())) {
+                Some(preparsed) => preparsed,
+                None => break,
+            };
+            let mut write = self.0.write(context.gc_context);
+            write.current_frame += 1;
+            frame_pos = write.tag_stream_pos;
+            write.tag_stream_pos = preparsed.next_tag_pos;
+            drop(write);
+            if is_rewind {
+                *context.rewind_frames_replayed += 1;
+            }
 
-                        mc.goto_place_object(
-                            reader,
-                            tag_len,
-                            3,
-                            &mut goto_commands,
-                            is_rewind,
-                            index,
-                        )
-                    }
-                    TagCode::PlaceObject4 => {
+            for op in &preparsed.ops {
+                match op {
+                    TimelineOp::PlaceObject(place_object) => {
                         index += 1;
-                        let mut mc = self.0.write(context.gc_context);
-
-                        mc.goto_place_object(
-                            reader,
-                            tag_len,
-                            4,
+                        self.0.write(context.gc_context).goto_place_object(
+                            place_object,
                             &mut goto_commands,
                             is_rewind,
                             index,
-                        )
+                        );
                     }
-                    TagCode::RemoveObject => {
-                        self.goto_remove_object(reader, 1, context, &mut goto_commands, is_rewind)
+                    TimelineOp::RemoveObject(depth) => {
+                        self.goto_remove_object(*depth, context, &mut goto_commands, is_rewind)
                     }
-                    TagCode::RemoveObject2 => {
-                        self.goto_remove_object(reader, 2, context, &mut goto_commands, is_rewind)
+                    TimelineOp::SetTabIndex { depth, tab_index } => {
+                        tab_indices.push((*depth, *tab_index))
                     }
-                    _ => Ok(()),
+                    _ => (),
                 }
-            };
-            let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::ShowFrame);
+            }
+
+            let current_frame = self.current_frame();
+            if takes_snapshots && current_frame % snapshot_interval == 0 {
+                let mut write = self.0.write(context.gc_context);
+                if write
+                    .goto_snapshots
+                    .last()
+                    .map_or(true, |snapshot| snapshot.frame < current_frame)
+                {
+                    write.goto_snapshots.push(GotoSnapshot {
+                        frame: current_frame,
+                        tag_stream_pos: preparsed.next_tag_pos,
+                        index,
+                        goto_commands: goto_commands.clone(),
+                    });
+                }
+            }
         }
         let hit_target_frame = self.0.read().current_frame == frame;
 
@@ -1335,7 +1576,7 @@ impl<'gc> MovieClip<'gc> {
                 // If it's a rewind, we removed any dead children above, so we always
                 // modify the previous child.
                 Some(prev_child) if params.id() == 0 || is_rewind => {
-                    prev_child.apply_place_object(context.gc_context, &params.place_object);
+                    prev_child.apply_place_object(context, &params.place_object);
                 }
                 _ => {
                     if let Some(child) = clip.instantiate_child(
@@ -1384,6 +1625,10 @@ impl<'gc> MovieClip<'gc> {
             .iter()
             .filter(|params| params.frame >= frame)
             .for_each(|goto| run_goto_command(self, context, goto));
+
+        for (depth, tab_index) in tab_indices {
+            self.set_child_tab_index(context, depth, tab_index);
+        }
     }
 
     fn construct_as_avm1_object(
@@ -1562,6 +1807,10 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Register a script to run whenever this clip enters a given frame.
+    ///
+    /// Scripts added for the frame the clip is currently on are also queued
+    /// to run, as the clip has already entered that frame.
     pub fn register_frame_script(
         self,
         frame_id: FrameNumber,
@@ -1573,6 +1822,20 @@ impl<'gc> MovieClip<'gc> {
         write
             .frame_scripts
             .push(Avm2FrameScript { frame_id, callable });
+
+        if write.current_frame == frame_id {
+            if let Some(avm2_object) = write.object.and_then(|o| o.as_avm2_object().ok()) {
+                context.action_queue.queue_actions(
+                    self.into(),
+                    ActionType::Callable2 {
+                        callable,
+                        reciever: Some(avm2_object),
+                        args: Vec::new(),
+                    },
+                    false,
+                );
+            }
+        }
     }
 
     fn run_frame_scripts(self, frame_id: FrameNumber, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -1610,20 +1873,13 @@ impl<'gc> MovieClip<'gc> {
 
     /// Handle a RemoveObject tag when running a goto action.
     #[inline]
-    fn goto_remove_object<'a>(
+    fn goto_remove_object(
         mut self,
-        reader: &mut SwfStream<&'a [u8]>,
-        version: u8,
+        depth: Depth,
         context: &mut UpdateContext<'_, 'gc, '_>,
         goto_commands: &mut Vec<GotoPlaceObject>,
         is_rewind: bool,
-    ) -> DecodeResult {
-        let remove_object = if version == 1 {
-            reader.read_remove_object_1()
-        } else {
-            reader.read_remove_object_2()
-        }?;
-        let depth = Depth::from(remove_object.depth);
+    ) {
         if let Some(i) = goto_commands.iter().position(|o| o.depth() == depth) {
             goto_commands.swap_remove(i);
         }
@@ -1644,7 +1900,6 @@ impl<'gc> MovieClip<'gc> {
                 }
             }
         }
-        Ok(())
     }
 
     pub fn enabled(self) -> bool {
@@ -1654,6 +1909,56 @@ impl<'gc> MovieClip<'gc> {
     pub fn set_enabled(self, context: &mut UpdateContext<'_, 'gc, '_>, enabled: bool) {
         self.0.write(context.gc_context).enabled = enabled;
     }
+
+    pub fn button_mode(self) -> bool {
+        self.0.read().button_mode
+    }
+
+    pub fn set_button_mode(self, context: &mut UpdateContext<'_, 'gc, '_>, button_mode: bool) {
+        self.0.write(context.gc_context).button_mode = button_mode;
+    }
+
+    pub fn use_hand_cursor(self) -> bool {
+        self.0.read().use_hand_cursor
+    }
+
+    pub fn set_use_hand_cursor(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        use_hand_cursor: bool,
+    ) {
+        self.0.write(context.gc_context).use_hand_cursor = use_hand_cursor;
+    }
+
+    /// The 9-slice scaling grid of this clip, if it has one.
+    pub fn scaling_grid(self) -> Option<swf::Rectangle> {
+        self.0.read().scaling_grid.clone()
+    }
+
+    pub fn set_scaling_grid(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        scaling_grid: Option<swf::Rectangle>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.scaling_grid = scaling_grid;
+        write.base.set_dirty(true);
+    }
+
+    /// The 9-slice transform that the shapes of this clip are drawn with.
+    ///
+    /// Returns `None` if the clip has no scaling grid, or if it is rotated or
+    /// skewed, in which case Flash scales the clip normally.
+    pub fn nine_slice(self) -> Option<NineSlice> {
+        let scaling_grid = self.scaling_grid()?;
+        let matrix = *self.matrix();
+        if matrix.b != 0.0 || matrix.c != 0.0 {
+            return None;
+        }
+
+        let bounds = self.bounds_with_transform(&Matrix::identity());
+        NineSlice::new(&scaling_grid, &bounds, matrix.a.into(), matrix.d.into())
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1709,6 +2014,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         context.transform_stack.pop();
     }
 
+    fn rasterize_self(&self, storage: &mut BitmapDataStorage, transform: &Transform) {
+        storage.draw_paths(&self.0.read().drawing.paths(), transform);
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().drawing.self_bounds()
     }
@@ -1724,7 +2033,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     ) -> bool {
         if self.world_bounds().contains(point) {
             for child in self.iter_execution_list() {
-                if child.hit_test_shape(context, point) {
+                // Masks are only hit through the children they mask.
+                if child.clip_depth() > 0 && child.allow_as_mask() {
+                    continue;
+                }
+
+                if child.hit_test_shape(context, point)
+                    && self.hit_test_masks(context, child.depth(), point)
+                {
                     return true;
                 }
             }
@@ -1745,7 +2061,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
-        if self.visible() {
+        if self.visible() && self.hit_test_scroll_rect(point) {
             if self.world_bounds().contains(point) {
                 // This movieclip operates in "button mode" if it has a mouse handler,
                 // either via on(..) or via property mc.onRelease, etc.
@@ -1773,8 +2089,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             // Maybe we could skip recursing down at all if !world_bounds.contains(point),
             // but a child button can have an invisible hit area outside the parent's bounds.
             for child in self.iter_render_list().rev() {
+                if child.clip_depth() > 0 && child.allow_as_mask() {
+                    continue;
+                }
+
                 let result = child.mouse_pick(context, child, point);
-                if result.is_some() {
+                if result.is_some() && self.hit_test_masks(context, child.depth(), point) {
                     return result;
                 }
             }
@@ -1815,6 +2135,13 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         Some(self.into())
     }
 
+    fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<Drawing>> {
+        // The drawing is only borrowed to change it.
+        let mut write = self.0.write(gc_context);
+        write.base.set_dirty(true);
+        Some(RefMut::map(write, |mc| &mut mc.drawing))
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1864,6 +2191,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             .unwrap_or(Avm2Value::Undefined)
     }
 
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).object = Some(to.into());
+    }
+
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         for child in self.iter_execution_list() {
             child.unload(context);
@@ -1918,15 +2249,16 @@ impl<'gc> MovieClipData<'gc> {
     /// empty movie of the same SWF version.
     pub fn replace_with_movie(
         &mut self,
-        gc_context: MutationContext<'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         movie: Option<Arc<SwfMovie>>,
     ) {
         let movie = movie.unwrap_or_else(|| Arc::new(SwfMovie::empty(self.movie().version())));
         let total_frames = movie.header().num_frames;
 
+        self.stop_audio_stream(context);
         self.base.reset_for_movie_load();
         self.static_data = Gc::allocate(
-            gc_context,
+            context.gc_context,
             MovieClipStatic {
                 id: 0,
                 swf: movie.into(),
@@ -1934,12 +2266,15 @@ impl<'gc> MovieClipData<'gc> {
                 audio_stream_info: None,
                 frame_labels: HashMap::new(),
                 scene_labels: HashMap::new(),
+                frames: Vec::new(),
             },
         );
         self.tag_stream_pos = 0;
+        self.preload_progress = Default::default();
+        self.pending_imports.clear();
+        self.goto_snapshots.clear();
         self.flags = MovieClipFlags::Playing.into();
         self.current_frame = 0;
-        self.audio_stream = None;
         self.container = ChildContainer::new();
     }
 
@@ -1955,6 +2290,20 @@ impl<'gc> MovieClipData<'gc> {
         self.static_data.total_frames
     }
 
+    fn frames_loaded(&self) -> FrameNumber {
+        let frames_loaded = if self.preload_progress.is_loading {
+            self.preload_progress.frames_loaded
+        } else {
+            self.total_frames()
+        };
+
+        // A frame that imports assets isn't ready until the import finishes.
+        match self.pending_imports.iter().min() {
+            Some(&frame) => frames_loaded.min(frame - 1),
+            None => frames_loaded,
+        }
+    }
+
     fn playing(&self) -> bool {
         self.flags.contains(MovieClipFlags::Playing)
     }
@@ -1993,32 +2342,22 @@ impl<'gc> MovieClipData<'gc> {
 
     /// Handles a PlaceObject tag when running a goto action.
     #[inline]
-    fn goto_place_object<'a>(
+    fn goto_place_object(
         &mut self,
-        reader: &mut SwfStream<&'a [u8]>,
-        tag_len: usize,
-        version: u8,
+        place_object: &swf::PlaceObject,
         goto_commands: &mut Vec<GotoPlaceObject>,
         is_rewind: bool,
         index: usize,
-    ) -> DecodeResult {
-        let place_object = if version == 1 {
-            reader.read_place_object(tag_len)
-        } else {
-            reader.read_place_object_2_or_3(version)
-        }?;
-
+    ) {
         // We merge the deltas from this PlaceObject with the previous command.
         let depth = Depth::from(place_object.depth);
         let mut goto_place =
-            GotoPlaceObject::new(self.current_frame(), place_object, is_rewind, index);
+            GotoPlaceObject::new(self.current_frame(), place_object.clone(), is_rewind, index);
         if let Some(i) = goto_commands.iter().position(|o| o.depth() == depth) {
             goto_commands[i].merge(&mut goto_place);
         } else {
             goto_commands.push(goto_place);
         }
-
-        Ok(())
     }
 
     /// Run all actions for the given clip event.
@@ -2122,7 +2461,7 @@ impl<'gc> MovieClipData<'gc> {
     /// Stops the audio stream if one is playing.
     fn stop_audio_stream(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
         if let Some(audio_stream) = self.audio_stream.take() {
-            context.audio.stop_stream(audio_stream);
+            context.audio.stop_stream(audio_stream.handle);
         }
     }
 
@@ -2144,17 +2483,19 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let bitmap_info = context
             .renderer
             .register_bitmap_png(&define_bits_lossless)?;
+        let id = define_bits_lossless.id;
         let bitmap = crate::display_object::Bitmap::new(
             context,
-            define_bits_lossless.id,
+            id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapSource::Lossless(define_bits_lossless),
         );
         context
             .library
             .library_for_movie_mut(self.movie())
-            .register_character(define_bits_lossless.id, Character::Bitmap(bitmap));
+            .register_character(id, Character::Bitmap(bitmap));
         Ok(())
     }
 
@@ -2196,7 +2537,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<&'a [u8]>,
         tag_len: usize,
-        ids: &mut fnv::FnvHashMap<Depth, CharacterId>,
+        progress: &mut PreloadProgress,
         morph_shapes: &mut fnv::FnvHashMap<CharacterId, MorphShapeStatic>,
         version: u8,
     ) -> DecodeResult {
@@ -2206,6 +2547,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         } else {
             reader.read_place_object_2_or_3(version)
         }?;
+        let ids = &mut progress.ids;
         match place_object.action {
             PlaceObjectAction::Place(id) => {
                 if let Some(morph_shape) = morph_shapes.get_mut(&id) {
@@ -2237,6 +2579,9 @@ impl<'gc, 'a> MovieClipData<'gc> {
             }
         };
 
+        progress
+            .frame_ops
+            .push(TimelineOp::PlaceObject(Box::new(place_object)));
         Ok(())
     }
 
@@ -2246,6 +2591,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<&'a [u8]>,
         cur_frame: FrameNumber,
+        progress: &mut PreloadProgress,
         static_data: &mut MovieClipStatic,
         tag_len: usize,
     ) -> DecodeResult {
@@ -2258,6 +2604,20 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 .preload_sound_stream_block(self.id(), cur_frame, data);
         }
 
+        // MP3 blocks start with their sample count. Other formats have the
+        // same number of samples in every block.
+        let block_sample_frames = match &static_data.audio_stream_info {
+            Some(stream_info)
+                if stream_info.stream_format.compression == swf::AudioCompression::Mp3 =>
+            {
+                reader.read_u16()?
+            }
+            Some(stream_info) => stream_info.num_samples_per_block,
+            None => 0,
+        };
+        progress
+            .frame_ops
+            .push(TimelineOp::SoundStreamBlock(block_sample_frames));
         Ok(())
     }
 
@@ -2290,20 +2650,24 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let data_len = tag_len - 2;
         let mut jpeg_data = Vec::with_capacity(data_len);
         reader.get_mut().read_to_end(&mut jpeg_data)?;
-        let bitmap_info = context.renderer.register_bitmap_jpeg(
-            id,
-            &jpeg_data,
-            context
-                .library
-                .library_for_movie_mut(self.movie())
-                .jpeg_tables(),
-        )?;
+        let jpeg_tables = context
+            .library
+            .library_for_movie_mut(self.movie())
+            .jpeg_tables();
+        let bitmap_info = context
+            .renderer
+            .register_bitmap_jpeg(id, &jpeg_data, jpeg_tables)?;
+        let source = BitmapSource::Jpeg {
+            data: glue_tables_to_jpeg(&jpeg_data, jpeg_tables).into_owned(),
+            alpha_data: None,
+        };
         let bitmap = crate::display_object::Bitmap::new(
             context,
             id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            source,
         );
         context
             .library
@@ -2325,12 +2689,17 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let mut jpeg_data = Vec::with_capacity(data_len);
         reader.get_mut().read_to_end(&mut jpeg_data)?;
         let bitmap_info = context.renderer.register_bitmap_jpeg_2(id, &jpeg_data)?;
+        let source = BitmapSource::Jpeg {
+            data: jpeg_data,
+            alpha_data: None,
+        };
         let bitmap = crate::display_object::Bitmap::new(
             context,
             id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            source,
         );
         context
             .library
@@ -2363,12 +2732,17 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let bitmap_info = context
             .renderer
             .register_bitmap_jpeg_3(id, &jpeg_data, &alpha_data)?;
+        let source = BitmapSource::Jpeg {
+            data: jpeg_data,
+            alpha_data: Some(alpha_data),
+        };
         let bitmap = Bitmap::new(
             context,
             id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            source,
         );
         context
             .library
@@ -2387,8 +2761,9 @@ impl<'gc, 'a> MovieClipData<'gc> {
         use std::io::Read;
         let id = reader.read_u16()?;
         let jpeg_len = reader.read_u32()? as usize;
+        // TODO: Apply the deblocking filter to the decoded image.
         let _deblocking = reader.read_u16()?;
-        let alpha_len = tag_len - 6 - jpeg_len;
+        let alpha_len = tag_len - 8 - jpeg_len;
         let mut jpeg_data = Vec::with_capacity(jpeg_len);
         let mut alpha_data = Vec::with_capacity(alpha_len);
         reader
@@ -2402,12 +2777,17 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let bitmap_info = context
             .renderer
             .register_bitmap_jpeg_3(id, &jpeg_data, &alpha_data)?;
+        let source = BitmapSource::Jpeg {
+            data: jpeg_data,
+            alpha_data: Some(alpha_data),
+        };
         let bitmap = Bitmap::new(
             context,
             id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            source,
         );
         context
             .library
@@ -2486,6 +2866,63 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let id = reader.read_u16()?;
+        let splitter_rect = reader.read_rectangle()?;
+        if let Some(character) = context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(id)
+        {
+            if let Character::MovieClip(clip) = character {
+                clip.set_scaling_grid(context.gc_context, Some(splitter_rect));
+            } else {
+                log::warn!(
+                    "DefineScalingGrid: Tried to apply on non-movie clip ID {}",
+                    id
+                );
+            }
+        } else {
+            log::warn!("DefineScalingGrid: Character ID {} doesn't exist", id);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn csm_text_settings(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let settings = reader.read_csm_text_settings()?;
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(settings.id)
+        {
+            Some(Character::EditText(edit_text)) => {
+                edit_text.set_render_settings(settings.into(), context.gc_context);
+            }
+            Some(Character::Text(_)) => {
+                // TODO: Static text is always drawn with normal anti-aliasing.
+            }
+            Some(_) => log::warn!(
+                "CSMTextSettings: Tried to apply on non-text character ID {}",
+                settings.id
+            ),
+            None => log::warn!(
+                "CSMTextSettings: Character ID {} doesn't exist",
+                settings.id
+            ),
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_button_sound(
         &mut self,
@@ -2604,10 +3041,45 @@ impl<'gc, 'a> MovieClipData<'gc> {
     #[inline]
     fn define_font_4(
         &mut self,
-        _context: &mut UpdateContext<'_, 'gc, '_>,
-        _reader: &mut SwfStream<&'a [u8]>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let font = reader.read_define_font_4()?;
+        match Font::from_font4_tag(context.gc_context, context.renderer, &font) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font.id, Character::Font(font_object)),
+            Err(e) => log::warn!("Unable to load DefineFont4 font {}: {}", font.id, e),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn define_font_align_zones(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+        tag_len: usize,
     ) -> DecodeResult {
-        log::warn!("DefineFont4 tag (TLF text) is not implemented");
+        use std::io::Read;
+        // Zones are read until the end of the tag, so limit the reader to it.
+        let version = reader.version();
+        let mut tag_reader =
+            swf::read::Reader::new(reader.get_mut().by_ref().take(tag_len as u64), version);
+        if let swf::Tag::DefineFontAlignZones { id, zones, .. } =
+            tag_reader.read_define_font_align_zones()?
+        {
+            match context
+                .library
+                .library_for_movie_mut(self.movie())
+                .get_font(id)
+            {
+                Some(font) => font.set_align_zones(&zones),
+                None => log::warn!("DefineFontAlignZones: Font ID {} doesn't exist", id),
+            }
+        }
         Ok(())
     }
 
@@ -2646,7 +3118,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             id,
             self.static_data
                 .swf
-                .resize_to_reader(reader, tag_len - 4)
+                .resize_to_reader(reader, tag_len.saturating_sub(4))
                 .ok_or_else(|| {
                     std::io::Error::new(
                         std::io::ErrorKind::Other,
@@ -2682,6 +3154,61 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_video_stream(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let streamdef = reader.read_define_video_stream()?;
+        let id = streamdef.id;
+        let video = Video::from_swf_tag(context, streamdef);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::Video(video));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_video_frame(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+        tag_len: usize,
+    ) -> DecodeResult {
+        let stream_id = reader.read_character_id()?;
+        let frame_num = reader.read_u16()?;
+        let data = self
+            .static_data
+            .swf
+            .resize_to_reader(reader, tag_len.saturating_sub(4))
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "Cannot define video frame with invalid offset and length!",
+                )
+            })?;
+
+        let video = context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(stream_id);
+        match video {
+            Some(Character::Video(video)) => {
+                let video = *video;
+                video.preload_swf_frame(context, frame_num.into(), data);
+            }
+            _ => log::warn!(
+                "Attempted to preload frame {} of non-video character {}",
+                frame_num,
+                stream_id
+            ),
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn script_limits(
         &mut self,
@@ -2724,12 +3251,15 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let mut frame_label = reader.read_frame_label(tag_len)?;
         // Frame labels are case insensitive (ASCII).
         frame_label.label.make_ascii_lowercase();
-        if let std::collections::hash_map::Entry::Vacant(v) =
-            static_data.frame_labels.entry(frame_label.label)
-        {
-            v.insert(cur_frame);
-        } else {
-            log::warn!("Movie clip {}: Duplicated frame label", self.id());
+        match static_data.frame_labels.entry(frame_label.label) {
+            Entry::Vacant(v) => {
+                v.insert(cur_frame);
+            }
+            // Labels are also listed by any `DefineSceneAndFrameLabelData` tag.
+            Entry::Occupied(o) if *o.get() == cur_frame => {}
+            Entry::Occupied(_) => {
+                log::warn!("Movie clip {}: Duplicated frame label", self.id());
+            }
         }
         Ok(())
     }
@@ -2757,7 +3287,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         &mut self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut SwfStream<&'a [u8]>,
-        ids: &mut fnv::FnvHashMap<Depth, CharacterId>,
+        progress: &mut PreloadProgress,
         version: u8,
     ) -> DecodeResult {
         let remove_object = if version == 1 {
@@ -2765,7 +3295,71 @@ impl<'gc, 'a> MovieClipData<'gc> {
         } else {
             reader.read_remove_object_2()
         }?;
-        ids.remove(&remove_object.depth.into());
+        progress.ids.remove(&remove_object.depth.into());
+        progress
+            .frame_ops
+            .push(TimelineOp::RemoveObject(remove_object.depth.into()));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_do_action(
+        &mut self,
+        reader: &mut SwfStream<&'a [u8]>,
+        tag_len: usize,
+        progress: &mut PreloadProgress,
+        static_data: &MovieClipStatic,
+    ) -> DecodeResult {
+        // The actions themselves are only parsed when they run.
+        let slice = static_data
+            .swf
+            .resize_to_reader(reader, tag_len)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "Invalid source or tag length when preloading action",
+                )
+            })?;
+        progress.frame_ops.push(TimelineOp::DoAction(slice));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_set_background_color(
+        &mut self,
+        reader: &mut SwfStream<&'a [u8]>,
+        progress: &mut PreloadProgress,
+    ) -> DecodeResult {
+        let color = reader.read_rgb()?;
+        progress
+            .frame_ops
+            .push(TimelineOp::SetBackgroundColor(color));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_set_tab_index(
+        &mut self,
+        reader: &mut SwfStream<&'a [u8]>,
+        progress: &mut PreloadProgress,
+    ) -> DecodeResult {
+        let depth = reader.read_u16()?;
+        let tab_index = reader.read_u16()?;
+        progress.frame_ops.push(TimelineOp::SetTabIndex {
+            depth: depth.into(),
+            tab_index: tab_index.into(),
+        });
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_start_sound_1(
+        &mut self,
+        reader: &mut SwfStream<&'a [u8]>,
+        progress: &mut PreloadProgress,
+    ) -> DecodeResult {
+        let start_sound = reader.read_start_sound_1()?;
+        progress.frame_ops.push(TimelineOp::StartSound(start_sound));
         Ok(())
     }
 
@@ -2775,155 +3369,139 @@ impl<'gc, 'a> MovieClipData<'gc> {
         _context: &mut UpdateContext<'_, 'gc, '_>,
         _reader: &mut SwfStream<&'a [u8]>,
         cur_frame: &mut FrameNumber,
+        progress: &mut PreloadProgress,
+        static_data: &mut MovieClipStatic,
+        next_tag_pos: u64,
     ) -> DecodeResult {
+        static_data.frames.push(PreparsedFrame {
+            next_tag_pos,
+            ops: std::mem::take(&mut progress.frame_ops),
+        });
         *cur_frame += 1;
         Ok(())
     }
 }
 
 // Control tags
-impl<'gc, 'a> MovieClip<'gc> {
+impl<'gc> MovieClip<'gc> {
     #[inline]
     fn do_action(
         self,
         self_display_object: DisplayObject<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        reader: &mut SwfStream<&'a [u8]>,
-        tag_len: usize,
-    ) -> DecodeResult {
+        code: SwfSlice,
+    ) {
         let movie = self.movie().unwrap();
         let library = context.library.library_for_movie_mut(movie);
         if let Err(e) = library.check_avm_type(AvmType::Avm1) {
             log::warn!("{}", e);
 
-            return Ok(());
+            return;
         }
 
         // Queue the actions.
-        let slice = self
-            .0
-            .read()
-            .static_data
-            .swf
-            .resize_to_reader(reader, tag_len)
-            .ok_or_else(|| {
-                std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Invalid source or tag length when running action",
-                )
-            })?;
         context.action_queue.queue_actions(
             self_display_object,
-            ActionType::Normal { bytecode: slice },
+            ActionType::Normal { bytecode: code },
             false,
         );
-        Ok(())
     }
 
     fn place_object(
         self,
         self_display_object: DisplayObject<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        reader: &mut SwfStream<&'a [u8]>,
-        tag_len: usize,
-        version: u8,
-    ) -> DecodeResult {
-        let place_object = if version == 1 {
-            reader.read_place_object(tag_len)
-        } else {
-            reader.read_place_object_2_or_3(version)
-        }?;
+        place_object: &swf::PlaceObject,
+    ) {
         use swf::PlaceObjectAction;
         match place_object.action {
             PlaceObjectAction::Place(id) | PlaceObjectAction::Replace(id) => {
-                if let Some(child) = self.instantiate_child(
+                self.instantiate_child(
                     self_display_object,
                     context,
                     id,
                     place_object.depth.into(),
-                    &place_object,
+                    place_object,
                     matches!(place_object.action, PlaceObjectAction::Replace(_)),
-                ) {
-                    child
-                } else {
-                    return Ok(());
-                }
+                );
             }
             PlaceObjectAction::Modify => {
                 if let Some(child) = self.child_by_depth(place_object.depth.into()) {
-                    child.apply_place_object(context.gc_context, &place_object);
-                    child
-                } else {
-                    return Ok(());
+                    child.apply_place_object(context, place_object);
                 }
             }
-        };
-
-        Ok(())
+        }
     }
 
     #[inline]
-    fn remove_object(
-        mut self,
-        context: &mut UpdateContext<'_, 'gc, '_>,
-        reader: &mut SwfStream<&'a [u8]>,
-        version: u8,
-    ) -> DecodeResult {
-        let remove_object = if version == 1 {
-            reader.read_remove_object_1()
-        } else {
-            reader.read_remove_object_2()
-        }?;
-
-        if let Some(child) = self.child_by_depth(remove_object.depth.into()) {
+    fn remove_object(mut self, context: &mut UpdateContext<'_, 'gc, '_>, depth: Depth) {
+        if let Some(child) = self.child_by_depth(depth) {
             if !child.placed_by_script() {
                 self.remove_child(context, child, EnumSet::all());
             } else {
                 self.remove_child(context, child, Lists::Depth.into());
             }
         }
+    }
 
-        Ok(())
+    #[inline]
+    fn set_background_color(self, context: &mut UpdateContext<'_, 'gc, '_>, color: swf::Color) {
+        *context.background_color = color;
+        *context.needs_render = true;
     }
 
+    /// Gives the child at `depth` its authored place in the tab order.
+    ///
+    /// Scripts may later override it through `tabIndex`.
     #[inline]
-    fn set_background_color(
+    fn set_child_tab_index(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        reader: &mut SwfStream<&'a [u8]>,
-    ) -> DecodeResult {
-        *context.background_color = reader.read_rgb()?;
-        Ok(())
+        depth: Depth,
+        tab_index: i32,
+    ) {
+        if let Some(child) = self.child_by_depth(depth) {
+            child.set_tab_index(context.gc_context, Some(tab_index));
+        }
     }
 
     #[inline]
     fn sound_stream_block(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        _reader: &mut SwfStream<&'a [u8]>,
+        block_sample_frames: u16,
     ) -> DecodeResult {
         let mut mc = self.0.write(context.gc_context);
-        if mc.playing() {
-            if let (Some(stream_info), None) = (&mc.static_data.audio_stream_info, mc.audio_stream)
-            {
-                let slice = mc
-                    .static_data
-                    .swf
-                    .to_start_and_end(mc.tag_stream_pos as usize, mc.tag_stream_len())
-                    .ok_or_else(|| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::Other,
-                            "Invalid slice generated when constructing sound stream block",
-                        )
-                    })?;
-                let audio_stream = context.audio.start_stream(
-                    mc.id(),
-                    mc.current_frame() + 1,
-                    slice,
-                    &stream_info,
-                );
-                mc.audio_stream = audio_stream.ok();
-            }
+        let static_data = mc.static_data;
+        let stream_info = match &static_data.audio_stream_info {
+            Some(stream_info) if mc.playing() => stream_info,
+            _ => return Ok(()),
+        };
+
+        if let Some(audio_stream) = &mut mc.audio_stream {
+            audio_stream.streamed_sample_frames += u32::from(audio_stream.block_sample_frames);
+            audio_stream.block_sample_frames = block_sample_frames;
+        } else {
+            let slice = mc
+                .static_data
+                .swf
+                .to_start_and_end(mc.tag_stream_pos as usize, mc.tag_stream_len())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "Invalid slice generated when constructing sound stream block",
+                    )
+                })?;
+            let start_frame = mc.current_frame();
+            let audio_stream = context
+                .audio
+                .start_stream(mc.id(), start_frame, slice, stream_info);
+            mc.audio_stream = audio_stream.ok().map(|handle| AudioStream {
+                handle,
+                start_frame,
+                streamed_sample_frames: 0,
+                block_sample_frames,
+            });
         }
 
         Ok(())
@@ -2933,34 +3511,16 @@ impl<'gc, 'a> MovieClip<'gc> {
     fn start_sound_1(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        reader: &mut SwfStream<&'a [u8]>,
-    ) -> DecodeResult {
-        let start_sound = reader.read_start_sound_1()?;
+        start_sound: &swf::StartSound,
+    ) {
         if let Some(handle) = context
             .library
             .library_for_movie_mut(self.movie().unwrap()) // TODO
             .get_sound(start_sound.id)
         {
-            use swf::SoundEvent;
             // The sound event type is controlled by the "Sync" setting in the Flash IDE.
-            match start_sound.sound_info.event {
-                // "Event" sounds always play, independent of the timeline.
-                SoundEvent::Event => {
-                    let _ = context.audio.start_sound(handle, &start_sound.sound_info);
-                }
-
-                // "Start" sounds only play if an instance of the same sound is not already playing.
-                SoundEvent::Start => {
-                    if !context.audio.is_sound_playing_with_handle(handle) {
-                        let _ = context.audio.start_sound(handle, &start_sound.sound_info);
-                    }
-                }
-
-                // "Stop" stops any active instances of a given sound.
-                SoundEvent::Stop => context.audio.stop_sounds_with_handle(handle),
-            }
+            start_sound_with_info(context.audio, handle, &start_sound.sound_info);
         }
-        Ok(())
     }
 }
 
@@ -2991,6 +3551,9 @@ struct MovieClipStatic {
     scene_labels: HashMap<String, Scene>,
     audio_stream_info: Option<swf::SoundStreamHead>,
     total_frames: FrameNumber,
+
+    /// The timeline operations of each preloaded frame.
+    frames: Vec<PreparsedFrame>,
 }
 
 impl MovieClipStatic {
@@ -3002,6 +3565,7 @@ impl MovieClipStatic {
             frame_labels: HashMap::new(),
             scene_labels: HashMap::new(),
             audio_stream_info: None,
+            frames: Vec::new(),
         }
     }
 }
@@ -3013,9 +3577,135 @@ unsafe impl<'gc> Collect for MovieClipStatic {
     }
 }
 
+/// A stream sound playing along with a movie clip's timeline.
+#[derive(Clone, Debug)]
+struct AudioStream {
+    handle: AudioStreamHandle,
+
+    /// The frame the stream started playing on.
+    start_frame: FrameNumber,
+
+    /// The number of sample frames in the blocks streamed before the current
+    /// frame.
+    streamed_sample_frames: u32,
+
+    /// The number of sample frames in the current frame's block.
+    block_sample_frames: u16,
+}
+
+/// How far a timeline has drifted from the stream sound playing on it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StreamDrift {
+    /// How many frames the timeline is ahead of its stream sound.
+    /// This is negative when the timeline has fallen behind.
+    pub frames: f64,
+
+    /// Whether the audio backend couldn't tell how far the stream has played.
+    /// The drift is then measured against the audio that the timeline has
+    /// streamed so far, as if it played exactly as it was delivered.
+    pub is_hypothetical: bool,
+}
+
+/// How far the tags of a movie clip have been preloaded.
+#[derive(Clone, Debug, Default)]
+struct PreloadProgress {
+    /// The position of the next tag to preload.
+    next_tag_pos: u64,
+
+    /// The number of frames preloaded so far.
+    frames_loaded: FrameNumber,
+
+    /// Whether the rest of the movie has yet to arrive.
+    is_loading: bool,
+
+    /// The morph shapes placed at each depth.
+    ids: fnv::FnvHashMap<Depth, CharacterId>,
+
+    /// The timeline operations of the frame being preloaded.
+    frame_ops: Vec<TimelineOp>,
+}
+
+/// The timeline operations of a frame, decoded from its tags when the clip
+/// is preloaded.
+///
+/// Frames run from these rather than from their tags, so looping clips and
+/// gotos don't decode the same tags over and over.
+#[derive(Clone, Debug, Default)]
+struct PreparsedFrame {
+    /// The position of the tags of the next frame.
+    next_tag_pos: u64,
+
+    /// The operations of this frame, in the order of their tags.
+    ops: Vec<TimelineOp>,
+}
+
+/// A timeline operation from a tag of a frame.
+#[derive(Clone, Debug)]
+enum TimelineOp {
+    PlaceObject(Box<swf::PlaceObject>),
+    RemoveObject(Depth),
+
+    /// The actions of a `DoAction` tag, which are parsed when they run.
+    DoAction(SwfSlice),
+
+    SetBackgroundColor(swf::Color),
+
+    /// A `SetTabIndex` tag, giving the object at a depth its tab order.
+    SetTabIndex {
+        depth: Depth,
+        tab_index: i32,
+    },
+
+    StartSound(swf::StartSound),
+
+    /// A block of the stream sound, with the number of sample frames in it.
+    SoundStreamBlock(u16),
+}
+
+/// Settings for the snapshots that `MovieClip`s take of their timelines.
+///
+/// A rewind has to replay a clip's timeline from its first frame. Clips
+/// with long timelines instead record the placement commands aggregated up to
+/// every `interval` frames as rewinds pass through them, so that later
+/// rewinds only replay the frames since the last snapshot.
+#[derive(Clone, Copy, Debug)]
+pub struct GotoSnapshotSettings {
+    /// The number of frames between snapshots, or 0 to take none.
+    pub interval: FrameNumber,
+
+    /// The number of frames a clip needs for it to take snapshots.
+    pub min_total_frames: FrameNumber,
+}
+
+impl Default for GotoSnapshotSettings {
+    fn default() -> Self {
+        Self {
+            interval: 50,
+            min_total_frames: 200,
+        }
+    }
+}
+
+/// The state of a rewind that has aggregated a clip's timeline up to and
+/// including a given frame.
+#[derive(Clone, Debug)]
+struct GotoSnapshot {
+    /// The frame that the timeline was aggregated up to.
+    frame: FrameNumber,
+
+    /// The position of the tags of the next frame.
+    tag_stream_pos: u64,
+
+    /// The index of the last place command.
+    index: usize,
+
+    /// The aggregated placement commands.
+    goto_commands: Vec<GotoPlaceObject>,
+}
+
 /// Stores the placement settings for display objects during a
 /// goto command.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct GotoPlaceObject {
     /// The frame number that this character was first placed on.
     frame: FrameNumber,
@@ -3052,6 +3742,15 @@ impl GotoPlaceObject {
                 if place_object.class_name.is_none() {
                     place_object.class_name = Some(Default::default());
                 }
+                if place_object.filters.is_none() {
+                    place_object.filters = Some(Default::default());
+                }
+                if place_object.blend_mode.is_none() {
+                    place_object.blend_mode = Some(swf::BlendMode::Normal);
+                }
+                if place_object.is_bitmap_cached.is_none() {
+                    place_object.is_bitmap_cached = Some(false);
+                }
             }
         }
 
@@ -3117,6 +3816,18 @@ impl GotoPlaceObject {
         if next_place.background_color.is_some() {
             cur_place.background_color = next_place.background_color.take();
         }
+        if next_place.filters.is_some() {
+            cur_place.filters = next_place.filters.take();
+        }
+        if next_place.blend_mode.is_some() {
+            cur_place.blend_mode = next_place.blend_mode.take();
+        }
+        if next_place.is_bitmap_cached.is_some() {
+            cur_place.is_bitmap_cached = next_place.is_bitmap_cached.take();
+        }
+        if next_place.is_visible.is_some() {
+            cur_place.is_visible = next_place.is_visible.take();
+        }
         // TODO: Other stuff.
     }
 }
@@ -1,10 +1,11 @@
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::font::Font;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, GcCell};
+use gc_arena::{Collect, GcCell, MutationContext};
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Collect, Copy)]
@@ -15,6 +16,56 @@ pub struct Text<'gc>(GcCell<'gc, TextData<'gc>>);
 pub struct TextData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: gc_arena::Gc<'gc, TextStatic>,
+
+    /// Which characters have been selected with `TextSnapshot.setSelected`.
+    /// Characters past the end of this list are not selected.
+    selection: Vec<bool>,
+
+    /// The color that selected characters are highlighted with.
+    select_color: swf::Color,
+}
+
+/// A character of static text, as seen by `TextSnapshot`.
+#[derive(Clone, Debug)]
+pub struct StaticTextChar {
+    /// The character drawn by the glyph.
+    pub character: char,
+
+    /// Whether this character is on a different line than the one before it.
+    pub new_line: bool,
+
+    /// The name of the font the character is drawn with.
+    pub font_name: String,
+
+    pub color: swf::Color,
+
+    /// The height of the font the character is drawn with.
+    pub height: Twips,
+
+    /// The transform from the origin of the glyph to the local space of the
+    /// text. The origin of a glyph is on its baseline.
+    pub matrix: Matrix,
+
+    pub advance: Twips,
+
+    /// The distance from the baseline to the top of the character's box.
+    pub ascent: Twips,
+
+    /// The distance from the baseline to the bottom of the character's box.
+    pub descent: Twips,
+}
+
+impl StaticTextChar {
+    /// The corners of the box of this character in the local space of the
+    /// text, clockwise from the top left.
+    pub fn corners(&self) -> [(Twips, Twips); 4] {
+        [
+            self.matrix * (Twips::zero(), Twips::zero() - self.ascent),
+            self.matrix * (self.advance, Twips::zero() - self.ascent),
+            self.matrix * (self.advance, self.descent),
+            self.matrix * (Twips::zero(), self.descent),
+        ]
+    }
 }
 
 impl<'gc> Text<'gc> {
@@ -37,9 +88,102 @@ impl<'gc> Text<'gc> {
                         text_blocks: tag.records.clone(),
                     },
                 ),
+                selection: Vec::new(),
+                select_color: swf::Color::from_rgb(0xFFFF00, 0xFF),
             },
         ))
     }
+
+    /// List the characters drawn by this text, in drawing order.
+    ///
+    /// Glyphs that are missing from their font are skipped, as they are not
+    /// drawn either.
+    pub fn chars(self, context: &UpdateContext<'_, 'gc, '_>) -> Vec<StaticTextChar> {
+        let tf = self.0.read();
+        let library = match context
+            .library
+            .library_for_movie(tf.static_data.swf.clone())
+        {
+            Some(library) => library,
+            None => return vec![],
+        };
+
+        let mut chars = vec![];
+        let mut color = swf::Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        let mut font_id = 0;
+        let mut height = Twips::new(0);
+        let (mut x, mut y) = (Twips::new(0), Twips::new(0));
+        let mut line_y = None;
+        for block in &tf.static_data.text_blocks {
+            if let Some(block_x) = block.x_offset {
+                x = block_x;
+            }
+            if let Some(block_y) = block.y_offset {
+                y = block_y;
+            }
+            color = block.color.as_ref().unwrap_or(&color).clone();
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+
+            if let Some(font) = library.get_font(font_id) {
+                let font_name = font.descriptor().class().to_string();
+                let (ascent, descent) = char_box_extent(font, height);
+
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        chars.push(StaticTextChar {
+                            character: std::char::from_u32(u32::from(glyph.code))
+                                .unwrap_or(std::char::REPLACEMENT_CHARACTER),
+                            new_line: line_y.map_or(false, |line_y| line_y != y),
+                            font_name: font_name.clone(),
+                            color: color.clone(),
+                            height,
+                            matrix: tf.static_data.text_transform * Matrix::translate(x, y),
+                            advance: Twips::new(c.advance),
+                            ascent,
+                            descent,
+                        });
+                        line_y = Some(y);
+                        x += Twips::new(c.advance);
+                    }
+                }
+            }
+        }
+
+        chars
+    }
+
+    /// Returns whether a character of this text is selected.
+    pub fn is_char_selected(self, index: usize) -> bool {
+        self.0.read().selection.get(index).copied().unwrap_or(false)
+    }
+
+    /// Select or deselect a character of this text.
+    pub fn set_char_selected(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        index: usize,
+        selected: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if write.selection.len() <= index {
+            if !selected {
+                return;
+            }
+            write.selection.resize(index + 1, false);
+        }
+        write.selection[index] = selected;
+    }
+
+    /// Set the color that selected characters are highlighted with.
+    pub fn set_select_color(self, gc_context: MutationContext<'gc, '_>, color: swf::Color) {
+        self.0.write(gc_context).select_color = color;
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -74,6 +218,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         let mut font_id = 0;
         let mut height = Twips::new(0);
         let mut transform: Transform = Default::default();
+        let mut char_index = 0;
         for block in &tf.static_data.text_blocks {
             if let Some(x) = block.x_offset {
                 transform.matrix.tx = x;
@@ -99,6 +244,23 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.color_transform.a_mult = f32::from(color.a) / 255.0;
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        if tf.selection.get(char_index).copied().unwrap_or(false) {
+                            // Highlight the box of the character behind it.
+                            let (ascent, descent) = char_box_extent(font, height);
+                            let highlight = Matrix::create_box(
+                                Twips::new(c.advance).to_pixels() as f32,
+                                (ascent + descent).to_pixels() as f32,
+                                0.0,
+                                transform.matrix.tx,
+                                transform.matrix.ty - ascent,
+                            );
+                            context.renderer.draw_rect(
+                                tf.select_color.clone(),
+                                &(context.transform_stack.transform().matrix * highlight),
+                            );
+                        }
+                        char_index += 1;
+
                         context.transform_stack.push(&transform);
                         context
                             .renderer
@@ -159,12 +321,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                             matrix.invert();
                             let point = matrix * point;
                             let glyph_bounds = BoundingBox::from(&glyph.shape.shape_bounds);
-                            if glyph_bounds.contains(point)
-                                && crate::shape_utils::shape_hit_test(
-                                    &glyph.shape,
-                                    point,
-                                    &local_matrix,
-                                )
+                            if glyph_bounds.contains(point) && glyph.hit_test(point, &local_matrix)
                             {
                                 return true;
                             }
@@ -180,6 +337,18 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
     }
 }
 
+/// The distances from the baseline to the top and bottom of the box of a
+/// character drawn with a font at a given height.
+fn char_box_extent(font: Font<'_>, height: Twips) -> (Twips, Twips) {
+    let ascent = font.get_baseline_for_height(height);
+    if ascent == Twips::new(0) {
+        // Fonts without layout metrics are drawn above the baseline.
+        return (height, Twips::new(0));
+    }
+    let descent = font.get_line_height_for_height(height) - ascent;
+    (ascent, descent.max(Twips::new(0)))
+}
+
 unsafe impl<'gc> gc_arena::Collect for TextData<'gc> {
     #[inline]
     fn trace(&self, cc: gc_arena::CollectionContext) {
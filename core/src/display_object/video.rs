@@ -0,0 +1,410 @@
+//! Video player display object
+
+use crate::avm1::{Object as Avm1Object, StageObject, Value as Avm1Value};
+use crate::backend::render::BitmapHandle;
+use crate::backend::video::{EncodedFrame, VideoStreamHandle};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::prelude::*;
+use crate::tag_utils::SwfSlice;
+use crate::transform::Transform;
+use crate::types::{Degrees, Percent};
+use crate::vminterface::Instantiator;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::{BTreeMap, BTreeSet};
+use swf::DefineVideoStream;
+
+/// A Video display object is a frame of video on the stage.
+///
+/// Video can be embedded in a movie's timeline with the `DefineVideoStream`
+/// and `VideoFrame` tags. The frame shown by each instance is chosen by the
+/// ratio of its `PlaceObject` tags.
+#[derive(Clone, Debug, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Video<'gc>(GcCell<'gc, VideoData<'gc>>);
+
+#[derive(Clone, Debug)]
+pub struct VideoData<'gc> {
+    base: DisplayObjectBase<'gc>,
+
+    /// The video frames, shared between all instances.
+    source: GcCell<'gc, VideoSource>,
+
+    /// The decoder stream of this instance, once it has been instantiated.
+    ///
+    /// Each instance needs its own stream, as decoders keep the last frame
+    /// they decoded to decode the next one against.
+    stream: Option<VideoStreamHandle>,
+
+    /// The frame this instance should be showing.
+    target_frame: u32,
+
+    /// The last frame decoded by this instance's stream.
+    decoded_frame: Option<u32>,
+
+    /// The decoded frame, as uploaded to the renderer.
+    bitmap: Option<VideoBitmap>,
+
+    /// Whether or not the video is smoothed when scaled.
+    smoothing: bool,
+
+    /// The AVM1 object of this video.
+    object: Option<Avm1Object<'gc>>,
+}
+
+/// A decoded frame that has been uploaded to the renderer.
+#[derive(Clone, Copy, Debug)]
+struct VideoBitmap {
+    handle: BitmapHandle,
+    width: u16,
+    height: u16,
+}
+
+/// The frames of a video stream embedded in a movie.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct VideoSource {
+    streamdef: DefineVideoStream,
+
+    /// The data of each frame of the stream, by frame number.
+    frames: BTreeMap<u32, SwfSlice>,
+
+    /// The frames that can be decoded without decoding any frames before
+    /// them.
+    keyframes: BTreeSet<u32>,
+
+    /// A stream used to inspect frames as they are loaded, without decoding
+    /// them.
+    preload_stream: Option<VideoStreamHandle>,
+}
+
+impl<'gc> Video<'gc> {
+    pub fn from_swf_tag(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        streamdef: DefineVideoStream,
+    ) -> Self {
+        let preload_stream = match context.video.register_video_stream(
+            streamdef.num_frames.into(),
+            (streamdef.width, streamdef.height),
+            streamdef.codec,
+            streamdef.deblocking,
+        ) {
+            Ok(stream) => Some(stream),
+            Err(e) => {
+                log::error!("Unable to register video stream {}: {}", streamdef.id, e);
+                None
+            }
+        };
+
+        let smoothing = streamdef.is_smoothed;
+        let source = GcCell::allocate(
+            context.gc_context,
+            VideoSource {
+                streamdef,
+                frames: BTreeMap::new(),
+                keyframes: BTreeSet::new(),
+                preload_stream,
+            },
+        );
+
+        Video(GcCell::allocate(
+            context.gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: None,
+                target_frame: 0,
+                decoded_frame: None,
+                bitmap: None,
+                smoothing,
+                object: None,
+            },
+        ))
+    }
+
+    /// Add a frame from a `VideoFrame` tag to this video's stream.
+    pub fn preload_swf_frame(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        frame_id: u32,
+        data: SwfSlice,
+    ) {
+        let source = self.0.read().source;
+        let mut source = source.write(context.gc_context);
+
+        if let Some(stream) = source.preload_stream {
+            let dependency = context.video.preload_video_stream_frame(
+                stream,
+                EncodedFrame {
+                    codec: source.streamdef.codec,
+                    data: data.data(),
+                    frame_id,
+                },
+            );
+
+            match dependency {
+                Ok(dependency) if dependency.is_keyframe() => {
+                    source.keyframes.insert(frame_id);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!(
+                    "Unable to preload frame {} of video stream {}: {}",
+                    frame_id,
+                    source.streamdef.id,
+                    e
+                ),
+            }
+        }
+
+        source.frames.insert(frame_id, data);
+    }
+
+    /// The width of the last frame decoded, in pixels.
+    pub fn decoded_width(self) -> u16 {
+        self.0.read().bitmap.map(|b| b.width).unwrap_or(0)
+    }
+
+    /// The height of the last frame decoded, in pixels.
+    pub fn decoded_height(self) -> u16 {
+        self.0.read().bitmap.map(|b| b.height).unwrap_or(0)
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, gc_context: MutationContext<'gc, '_>, smoothing: bool) {
+        let mut write = self.0.write(gc_context);
+        write.smoothing = smoothing;
+        write.base.set_dirty(true);
+    }
+
+    /// Show a given frame of the video, decoding any frames it depends on.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc, '_>, frame_id: u32) {
+        self.0.write(context.gc_context).target_frame = frame_id;
+
+        let read = self.0.read();
+        if read.stream.is_none() || read.decoded_frame == Some(frame_id) {
+            return;
+        }
+
+        // Continue from the last frame decoded if it's on the way, otherwise
+        // start again from the closest keyframe.
+        let source = read.source.read();
+        let last_keyframe = source.keyframes.range(..=frame_id).next_back().copied();
+        let first_frame = match (read.decoded_frame, last_keyframe) {
+            (Some(decoded), Some(keyframe)) if decoded < frame_id && decoded >= keyframe => {
+                decoded + 1
+            }
+            (_, Some(keyframe)) => keyframe,
+            (_, None) => {
+                log::warn!(
+                    "No keyframe to decode frame {} of video stream {} from",
+                    frame_id,
+                    source.streamdef.id
+                );
+                return;
+            }
+        };
+        drop(source);
+        drop(read);
+
+        for frame in first_frame..=frame_id {
+            if !self.decode_frame(context, frame) {
+                break;
+            }
+        }
+    }
+
+    /// Decode a single frame and upload it to the renderer.
+    ///
+    /// Returns `false` if the frame could not be decoded.
+    fn decode_frame(self, context: &mut UpdateContext<'_, 'gc, '_>, frame_id: u32) -> bool {
+        let read = self.0.read();
+        let source = read.source.read();
+        let (stream, data) = match (read.stream, source.frames.get(&frame_id)) {
+            (Some(stream), Some(data)) => (stream, data),
+            // Frames missing from the stream are skipped.
+            _ => return true,
+        };
+
+        let decoded = context.video.decode_video_stream_frame(
+            stream,
+            EncodedFrame {
+                codec: source.streamdef.codec,
+                data: data.data(),
+                frame_id,
+            },
+        );
+        let decoded = match decoded {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                log::error!(
+                    "Unable to decode frame {} of video stream {}: {}",
+                    frame_id,
+                    source.streamdef.id,
+                    e
+                );
+                return false;
+            }
+        };
+
+        let (width, height) = (decoded.width, decoded.height);
+        let handle = match read.bitmap {
+            Some(bitmap) => context.renderer.update_texture(
+                bitmap.handle,
+                width.into(),
+                height.into(),
+                decoded.rgba,
+            ),
+            None => context
+                .renderer
+                .register_bitmap_raw(width.into(), height.into(), decoded.rgba),
+        };
+        drop(source);
+        drop(read);
+
+        let mut write = self.0.write(context.gc_context);
+        write.decoded_frame = Some(frame_id);
+        write.base.set_dirty(true);
+        match handle {
+            Ok(handle) => {
+                write.bitmap = Some(VideoBitmap {
+                    handle,
+                    width,
+                    height,
+                });
+                true
+            }
+            Err(e) => {
+                log::error!("Unable to upload video frame: {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl<'gc> TDisplayObject<'gc> for Video<'gc> {
+    impl_display_object!(base);
+
+    fn id(&self) -> CharacterId {
+        self.0.read().source.read().streamdef.id
+    }
+
+    fn as_video(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        _run_frame: bool,
+    ) {
+        self.set_default_instance_name(context);
+
+        let mut write = self.0.write(context.gc_context);
+        if write.object.is_none() {
+            let object = StageObject::for_display_object(
+                context.gc_context,
+                display_object,
+                Some(context.system_prototypes.video),
+            );
+            write.object = Some(object.into());
+        }
+
+        if write.stream.is_none() {
+            let source = write.source.read();
+            let stream = context.video.register_video_stream(
+                source.streamdef.num_frames.into(),
+                (source.streamdef.width, source.streamdef.height),
+                source.streamdef.codec,
+                source.streamdef.deblocking,
+            );
+            let id = source.streamdef.id;
+            drop(source);
+
+            match stream {
+                Ok(stream) => write.stream = Some(stream),
+                Err(e) => log::error!("Unable to register video stream {}: {}", id, e),
+            }
+        }
+
+        let target_frame = write.target_frame;
+        drop(write);
+
+        self.seek(context, target_frame);
+    }
+
+    fn object(&self) -> Avm1Value<'gc> {
+        self.0
+            .read()
+            .object
+            .map(Avm1Value::from)
+            .unwrap_or(Avm1Value::Undefined)
+    }
+
+    fn self_bounds(&self) -> BoundingBox {
+        let read = self.0.read();
+        let source = read.source.read();
+
+        BoundingBox {
+            x_min: Twips::new(0),
+            y_min: Twips::new(0),
+            x_max: Twips::from_pixels(source.streamdef.width.into()),
+            y_max: Twips::from_pixels(source.streamdef.height.into()),
+            valid: true,
+        }
+    }
+
+    fn run_frame(&self, _context: &mut UpdateContext) {
+        // Noop
+    }
+
+    fn render(&self, context: &mut RenderContext) {
+        if !self.world_bounds().intersects(&context.view_bounds) {
+            // Off-screen; culled
+            return;
+        }
+
+        let read = self.0.read();
+        let bitmap = if let Some(bitmap) = read.bitmap {
+            bitmap
+        } else {
+            return;
+        };
+
+        context.transform_stack.push(&*self.transform());
+
+        // Stretch the frame to the size of the stream, in case they differ.
+        let source = read.source.read();
+        context.transform_stack.push(&Transform {
+            matrix: Matrix {
+                a: f32::from(source.streamdef.width) / f32::from(bitmap.width.max(1)),
+                d: f32::from(source.streamdef.height) / f32::from(bitmap.height.max(1)),
+                ..Default::default()
+            },
+            color_transform: Default::default(),
+        });
+
+        context.renderer.render_bitmap(
+            bitmap.handle,
+            context.transform_stack.transform(),
+            source.streamdef.is_smoothed,
+        );
+
+        context.transform_stack.pop();
+        context.transform_stack.pop();
+    }
+}
+
+unsafe impl<'gc> gc_arena::Collect for VideoData<'gc> {
+    #[inline]
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+        self.source.trace(cc);
+        self.object.trace(cc);
+    }
+}
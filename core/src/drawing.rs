@@ -1,15 +1,15 @@
-use crate::backend::render::ShapeHandle;
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
+use crate::shape_cache::CachedShape;
 use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
 use gc_arena::Collect;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use swf::{FillStyle, LineStyle, Twips};
 
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct Drawing {
-    render_handle: Cell<Option<ShapeHandle>>,
+    render_handle: RefCell<Option<CachedShape>>,
     shape_bounds: BoundingBox,
     edge_bounds: BoundingBox,
     dirty: Cell<bool>,
@@ -23,7 +23,7 @@ pub struct Drawing {
 impl Drawing {
     pub fn new() -> Self {
         Self {
-            render_handle: Cell::new(None),
+            render_handle: RefCell::new(None),
             shape_bounds: BoundingBox::default(),
             edge_bounds: BoundingBox::default(),
             dirty: Cell::new(false),
@@ -139,62 +139,66 @@ impl Drawing {
     pub fn render(&self, context: &mut RenderContext) {
         if self.dirty.get() {
             self.dirty.set(false);
-            let mut paths = Vec::new();
-
-            for (style, commands) in &self.fills {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
-
-            // TODO: If the current_fill is not closed, we should automatically close current_line
-
-            if let Some((style, commands)) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
-
-            for (style, commands) in &self.lines {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
-
-            if let Some((style, commands)) = &self.current_line {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
-
             let shape = DistilledShape {
-                paths,
+                paths: self.paths(),
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
             };
 
-            if let Some(handle) = self.render_handle.get() {
-                context.renderer.replace_shape(shape, handle);
-            } else {
-                self.render_handle
-                    .set(Some(context.renderer.register_shape(shape)));
-            }
+            // Drawings with the same contents share their tessellation, so
+            // the old shape is only replaced if nothing else is using it.
+            let mut render_handle = self.render_handle.borrow_mut();
+            let old = render_handle.take();
+            *render_handle = Some(context.shape_cache.replace(context.renderer, old, shape));
         }
 
-        if let Some(handle) = self.render_handle.get() {
+        if let Some(shape) = &*self.render_handle.borrow() {
             context
                 .renderer
-                .render_shape(handle, context.transform_stack.transform());
+                .render_shape(shape.handle(), context.transform_stack.transform());
         }
     }
 
+    /// The fills and strokes of this drawing, in drawing order.
+    pub fn paths(&self) -> Vec<DrawPath> {
+        let mut paths = Vec::new();
+
+        for (style, commands) in &self.fills {
+            paths.push(DrawPath::Fill {
+                style,
+                commands: commands.to_owned(),
+            })
+        }
+
+        // TODO: If the current_fill is not closed, we should automatically close current_line
+
+        if let Some((style, commands)) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style,
+                commands: commands.to_owned(),
+            })
+        }
+
+        for (style, commands) in &self.lines {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
+
+        if let Some((style, commands)) = &self.current_line {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
+
+        paths
+    }
+
     pub fn self_bounds(&self) -> BoundingBox {
         self.shape_bounds.clone()
     }
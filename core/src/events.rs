@@ -1,3 +1,4 @@
+use crate::backend::input::MouseButton;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[allow(clippy::enum_variant_names)]
@@ -6,8 +7,8 @@ pub enum PlayerEvent {
     KeyDown { key_code: KeyCode },
     KeyUp { key_code: KeyCode },
     MouseMove { x: f64, y: f64 },
-    MouseUp { x: f64, y: f64 },
-    MouseDown { x: f64, y: f64 },
+    MouseUp { x: f64, y: f64, button: MouseButton },
+    MouseDown { x: f64, y: f64, button: MouseButton },
     MouseLeft,
     MouseWheel { delta: MouseWheelDelta },
     TextInput { codepoint: char },
@@ -123,6 +124,7 @@ impl ClipEvent {
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
+    Tab = 9,
     Return = 13,
     Shift = 16,
     Control = 17,
@@ -347,6 +349,7 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
         KeyCode::Insert => ButtonKeyCode::Insert,
         KeyCode::Delete => ButtonKeyCode::Delete,
         KeyCode::Backspace => ButtonKeyCode::Backspace,
+        KeyCode::Tab => ButtonKeyCode::Tab,
         KeyCode::Return => ButtonKeyCode::Return,
         KeyCode::Up => ButtonKeyCode::Up,
         KeyCode::Down => ButtonKeyCode::Down,
@@ -6,6 +6,11 @@ use crate::avm1::Value as Avm1Value;
 use crate::avm1::{
     AvmString as Avm1String, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
 };
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    AvmString as Avm2String, Error as Avm2Error, Namespace as Avm2Namespace, QName as Avm2QName,
+    ScriptObject as Avm2ScriptObject, TObject as _, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use gc_arena::{Collect, CollectionContext};
 use std::collections::BTreeMap;
@@ -183,6 +188,91 @@ impl Value {
             }
         }
     }
+
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, Avm2Error> {
+        Ok(match value {
+            Avm2Value::Undefined | Avm2Value::Null => Value::Null,
+            Avm2Value::Bool(value) => Value::Bool(value),
+            Avm2Value::Number(value) => Value::Number(value),
+            Avm2Value::Unsigned(value) => Value::Number(f64::from(value)),
+            Avm2Value::Integer(value) => Value::Number(f64::from(value)),
+            Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(mut object) => {
+                let array = object
+                    .as_array_storage()
+                    .map(|storage| storage.iter().collect::<Vec<_>>());
+                if let Some(array) = array {
+                    let mut values = Vec::new();
+                    for value in array {
+                        values.push(Value::from_avm2(
+                            activation,
+                            value.unwrap_or(Avm2Value::Undefined),
+                        )?);
+                    }
+                    Value::List(values)
+                } else {
+                    let mut values = BTreeMap::new();
+                    let mut index = 1;
+                    while let Some(name) = object.get_enumerant_name(index) {
+                        index += 1;
+                        if !name.namespace().is_public() {
+                            continue;
+                        }
+
+                        let value = object.get_property(object, &name, activation)?;
+                        values.insert(
+                            name.local_name().to_string(),
+                            Value::from_avm2(activation, value)?,
+                        );
+                    }
+                    Value::Object(values)
+                }
+            }
+        })
+    }
+
+    pub fn into_avm2<'gc>(self, activation: &mut Avm2Activation<'_, 'gc, '_>) -> Avm2Value<'gc> {
+        match self {
+            Value::Null => Avm2Value::Null,
+            Value::Bool(value) => Avm2Value::Bool(value),
+            Value::Number(value) => Avm2Value::Number(value),
+            Value::String(value) => {
+                Avm2Value::String(Avm2String::new(activation.context.gc_context, value))
+            }
+            Value::Object(values) => {
+                let object_proto = activation.context.avm2.prototypes().object;
+                let mut object =
+                    Avm2ScriptObject::object(activation.context.gc_context, object_proto);
+                for (key, value) in values {
+                    let key = Avm2String::new(activation.context.gc_context, key);
+                    let value = value.into_avm2(activation);
+                    let _ = object.set_property(
+                        object,
+                        &Avm2QName::new(Avm2Namespace::public_namespace(), key),
+                        value,
+                        activation,
+                    );
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let values: Vec<Avm2Value<'gc>> = values
+                    .into_iter()
+                    .map(|v| v.into_avm2(activation))
+                    .collect();
+                let array_proto = activation.context.avm2.prototypes().array;
+                Avm2ArrayObject::from_array(
+                    Avm2ArrayStorage::from_args(&values),
+                    array_proto,
+                    activation.context.gc_context,
+                )
+                .into()
+            }
+        }
+    }
 }
 
 #[derive(Collect, Clone)]
@@ -1,5 +1,6 @@
 use crate::avm1::{Avm1, Value};
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObjectContainer;
 pub use crate::display_object::{DisplayObject, TDisplayObject};
 use gc_arena::{Collect, GcCell, MutationContext};
 
@@ -43,6 +44,33 @@ impl<'gc> FocusTracker<'gc> {
 
         log::info!("Focus is now on {:?}", focused_element);
 
+        let old_value = old.map(|v| v.object()).unwrap_or(Value::Null);
+        let new_value = focused_element.map(|v| v.object()).unwrap_or(Value::Null);
+        if context.swf.version() >= 6 {
+            // The object losing focus hears about it first, and each object is
+            // told about the other one.
+            if let (Some(old), Value::Object(object)) = (old, &old_value) {
+                Avm1::run_stack_frame_for_method(
+                    old,
+                    *object,
+                    context.swf.version(),
+                    context,
+                    "onKillFocus",
+                    &[new_value.clone()],
+                );
+            }
+            if let (Some(new), Value::Object(object)) = (focused_element, &new_value) {
+                Avm1::run_stack_frame_for_method(
+                    new,
+                    *object,
+                    context.swf.version(),
+                    context,
+                    "onSetFocus",
+                    &[old_value.clone()],
+                );
+            }
+        }
+
         let level0 = context.levels.get(&0).copied().unwrap();
         Avm1::notify_system_listeners(
             level0,
@@ -50,10 +78,67 @@ impl<'gc> FocusTracker<'gc> {
             context,
             "Selection",
             "onSetFocus",
-            &[
-                old.map(|v| v.object()).unwrap_or(Value::Null),
-                focused_element.map(|v| v.object()).unwrap_or(Value::Null),
-            ],
+            &[old_value, new_value],
         );
     }
+
+    /// Moves the focus to the next object in the tab order, or to the
+    /// previous one if `reverse` is set, wrapping around at either end.
+    ///
+    /// Objects with a tab index are visited in the order of their indices,
+    /// and leave every other object out of the tab order. Without any, the
+    /// focusable objects are visited in the order that they are rendered.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let mut tab_order = vec![];
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().copied().collect();
+        for level in levels {
+            collect_tab_order(level, &mut tab_order);
+        }
+
+        if tab_order.iter().any(|object| object.tab_index().is_some()) {
+            tab_order.retain(|object| object.tab_index().is_some());
+            tab_order.sort_by_key(|object| object.tab_index());
+        }
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let len = tab_order.len();
+        let current = self.get().and_then(|focus| {
+            tab_order
+                .iter()
+                .position(|object| object.as_ptr() == focus.as_ptr())
+        });
+        let next = match current {
+            Some(index) if reverse => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None if reverse => len - 1,
+            None => 0,
+        };
+        self.set(Some(tab_order[next]), context);
+    }
+}
+
+/// Collects the visible objects that can be tabbed to, in render order.
+///
+/// Text fields can only be tabbed to if they are editable, although scripts
+/// may focus any of them.
+fn collect_tab_order<'gc>(object: DisplayObject<'gc>, tab_order: &mut Vec<DisplayObject<'gc>>) {
+    if !object.visible() {
+        return;
+    }
+
+    let is_tabbable = match object.as_edit_text() {
+        Some(text) => text.is_editable(),
+        None => object.is_focusable(),
+    };
+    if is_tabbable {
+        tab_order.push(object);
+    }
+
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            collect_tab_order(child, tab_order);
+        }
+    }
 }
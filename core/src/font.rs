@@ -1,8 +1,10 @@
-use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::backend::render::{AlignZone, RenderBackend, ShapeHandle};
 use crate::html::TextSpan;
 use crate::prelude::*;
+use crate::shape_utils::HitTestCache;
 use crate::transform::Transform;
 use gc_arena::{Collect, Gc, MutationContext};
+use std::cell::RefCell;
 
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
 pub fn round_down_to_pixel(t: Twips) -> Twips {
@@ -94,6 +96,12 @@ struct FontData {
 
     /// The identity of the font.
     descriptor: FontDescriptor,
+
+    /// The alignment zones of the glyphs, from a `DefineFontAlignZones` tag.
+    ///
+    /// The tag follows the font that it applies to, so this is filled in
+    /// after the font has been created.
+    align_zones: RefCell<Vec<AlignZone>>,
 }
 
 impl<'gc> Font<'gc> {
@@ -109,6 +117,8 @@ impl<'gc> Font<'gc> {
                 shape_handle: renderer.register_glyph_shape(swf_glyph),
                 advance: swf_glyph.advance.unwrap_or(0),
                 shape: crate::shape_utils::swf_glyph_to_shape(swf_glyph),
+                code: swf_glyph.code,
+                hit_test_cache: HitTestCache::new(),
             };
             let index = glyphs.len();
             glyphs.push(glyph);
@@ -145,6 +155,120 @@ impl<'gc> Font<'gc> {
                 descent,
                 leading,
                 descriptor,
+                align_zones: RefCell::new(Vec::new()),
+            },
+        )))
+    }
+
+    /// Construct a font from a `DefineFont4` tag.
+    ///
+    /// The tag embeds an OpenType font with CFF outlines, which are parsed
+    /// with `from_font_data`.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+    ) -> Result<Font<'gc>, Error> {
+        let descriptor = FontDescriptor::from_parts(&tag.name, tag.is_bold, tag.is_italic);
+
+        let data = match &tag.data {
+            Some(data) => data,
+            // A `DefineFont4` without font data refers to a device font.
+            None => {
+                return Ok(Font(Gc::allocate(
+                    gc_context,
+                    FontData {
+                        glyphs: vec![],
+                        code_point_to_glyph: fnv::FnvHashMap::default(),
+                        scale: 1000.0,
+                        kerning_pairs: fnv::FnvHashMap::default(),
+                        ascent: 0,
+                        descent: 0,
+                        leading: 0,
+                        descriptor,
+                        align_zones: RefCell::new(Vec::new()),
+                    },
+                )))
+            }
+        };
+
+        Self::from_font_data(gc_context, renderer, data, descriptor)
+    }
+
+    /// Construct a font from the data of a TrueType or OpenType font file.
+    ///
+    /// Outlines are converted into the same glyph shapes that `DefineFont3`
+    /// fonts use. Glyph coordinates are kept in font units, so the scale is
+    /// the font's own EM-square size (usually 1000 or 2048) rather than 1024
+    /// or 20480.
+    pub fn from_font_data(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        data: &[u8],
+        descriptor: FontDescriptor,
+    ) -> Result<Font<'gc>, Error> {
+        let face = ttf_parser::Face::from_slice(data, 0)?;
+
+        // Only glyphs reachable through a Unicode character map can be used
+        // by text fields; code points outside the BMP are not supported yet.
+        let mut code_points = vec![];
+        for subtable in face.character_mapping_subtables() {
+            if !subtable.is_unicode() {
+                continue;
+            }
+
+            subtable.codepoints(|code_point| {
+                if code_point <= u32::from(u16::MAX) {
+                    match subtable.glyph_index(code_point) {
+                        Some(glyph_id) if glyph_id.0 != 0 => {
+                            code_points.push((code_point as u16, glyph_id))
+                        }
+                        _ => (),
+                    }
+                }
+            });
+        }
+        code_points.sort_by_key(|(code_point, _)| *code_point);
+        code_points.dedup_by_key(|(code_point, _)| *code_point);
+
+        let mut glyphs = vec![];
+        let mut code_point_to_glyph = fnv::FnvHashMap::default();
+        for (code_point, glyph_id) in code_points {
+            let mut outline = GlyphOutlineBuilder::default();
+            face.outline_glyph(glyph_id, &mut outline);
+
+            let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0);
+            let swf_glyph = swf::Glyph {
+                shape_records: outline.shape_records,
+                code: code_point,
+                advance: Some(advance.min(i16::MAX as u16) as i16),
+                bounds: None,
+            };
+            let glyph = Glyph {
+                shape_handle: renderer.register_glyph_shape(&swf_glyph),
+                advance: swf_glyph.advance.unwrap_or(0),
+                shape: crate::shape_utils::swf_glyph_to_shape(&swf_glyph),
+                code: code_point,
+                hit_test_cache: HitTestCache::new(),
+            };
+            let index = glyphs.len();
+            glyphs.push(glyph);
+            code_point_to_glyph.insert(code_point, index);
+        }
+
+        // TODO: Read kerning pairs from the `kern` and `GPOS` tables.
+        Ok(Font(Gc::allocate(
+            gc_context,
+            FontData {
+                glyphs,
+                code_point_to_glyph,
+                scale: f32::from(face.units_per_em().unwrap_or(1000)),
+                kerning_pairs: fnv::FnvHashMap::default(),
+                ascent: face.ascender().max(0) as u16,
+                descent: face.descender().saturating_neg().max(0) as u16,
+                leading: face.line_gap(),
+                descriptor,
+                align_zones: RefCell::new(Vec::new()),
             },
         )))
     }
@@ -194,6 +318,22 @@ impl<'gc> Font<'gc> {
         Twips::new((self.0.leading as f32 * scale) as i32)
     }
 
+    /// Return the distance between the baselines of two lines of this font at
+    /// a given height, not counting any extra leading from the text format.
+    ///
+    /// This is the sum of the font's ascent and descent. Fonts without layout
+    /// metrics fall back to the height plus the font's leading.
+    pub fn get_line_height_for_height(self, height: Twips) -> Twips {
+        let extent = u32::from(self.0.ascent) + u32::from(self.0.descent);
+        if extent == 0 {
+            return height + self.get_leading_for_height(height);
+        }
+
+        let scale = height.get() as f32 / self.scale();
+
+        Twips::new((extent as f32 * scale) as i32)
+    }
+
     /// Get the baseline from the top of the glyph at a given height.
     pub fn get_baseline_for_height(self, height: Twips) -> Twips {
         let scale = height.get() as f32 / self.scale();
@@ -359,6 +499,19 @@ impl<'gc> Font<'gc> {
     pub fn descriptor(self) -> FontDescriptor {
         self.0.descriptor.clone()
     }
+
+    /// Attach the alignment zones from a `DefineFontAlignZones` tag to this
+    /// font, one for each glyph in glyph order.
+    pub fn set_align_zones(self, zones: &[swf::FontAlignZone]) {
+        *self.0.align_zones.borrow_mut() = zones.iter().map(AlignZone::from_swf_zone).collect();
+    }
+
+    /// Returns the alignment zone of the glyph drawn for a UTF-16 code unit,
+    /// if this font has one.
+    pub fn get_align_zone_for_code(self, code: u16) -> Option<AlignZone> {
+        let index = *self.0.code_point_to_glyph.get(&code)?;
+        self.0.align_zones.borrow().get(index).copied()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -366,6 +519,157 @@ pub struct Glyph {
     pub shape_handle: ShapeHandle,
     pub shape: swf::Shape,
     pub advance: i16,
+
+    /// The UTF-16 code unit this glyph is drawn for.
+    pub code: u16,
+
+    hit_test_cache: HitTestCache,
+}
+
+impl Glyph {
+    /// Test whether the given point in glyph space is inside this glyph.
+    pub fn hit_test(&self, point: (Twips, Twips), local_matrix: &Matrix) -> bool {
+        self.hit_test_cache
+            .hit_test(&self.shape, point, local_matrix)
+    }
+}
+
+/// Converts OpenType glyph outlines into SWF glyph shape records.
+///
+/// Font outlines are Y-up, while SWF glyphs are Y-down, so every point is
+/// flipped vertically. Cubic curves (as used by CFF charstrings) are
+/// approximated with quadratic curves, which are all that SWF shapes support.
+#[derive(Default)]
+struct GlyphOutlineBuilder {
+    shape_records: Vec<swf::ShapeRecord>,
+
+    /// The start of the current contour, in font units.
+    start: (f32, f32),
+
+    /// The current pen position, in font units.
+    pen: (f32, f32),
+
+    /// The pen position as last written to the shape records, after rounding.
+    written: (i32, i32),
+}
+
+impl GlyphOutlineBuilder {
+    /// The maximum distance, in font units, that an approximated curve may
+    /// stray from the original cubic curve.
+    const CUBIC_TOLERANCE: f32 = 0.5;
+
+    fn point(x: f32, y: f32) -> (i32, i32) {
+        (x.round() as i32, -(y.round() as i32))
+    }
+
+    fn quad(&mut self, control: (f32, f32), anchor: (f32, f32)) {
+        let control = Self::point(control.0, control.1);
+        let anchor = Self::point(anchor.0, anchor.1);
+        self.shape_records.push(swf::ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(control.0 - self.written.0),
+            control_delta_y: Twips::new(control.1 - self.written.1),
+            anchor_delta_x: Twips::new(anchor.0 - control.0),
+            anchor_delta_y: Twips::new(anchor.1 - control.1),
+        });
+        self.written = anchor;
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = Self::point(x, y);
+        let is_first = self.shape_records.is_empty();
+        self.shape_records
+            .push(swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some((Twips::new(point.0), Twips::new(point.1))),
+                fill_style_0: if is_first { Some(1) } else { None },
+                fill_style_1: None,
+                line_style: None,
+                new_styles: None,
+            }));
+        self.start = (x, y);
+        self.pen = (x, y);
+        self.written = point;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = Self::point(x, y);
+        self.shape_records.push(swf::ShapeRecord::StraightEdge {
+            delta_x: Twips::new(point.0 - self.written.0),
+            delta_y: Twips::new(point.1 - self.written.1),
+        });
+        self.pen = (x, y);
+        self.written = point;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.quad((x1, y1), (x, y));
+        self.pen = (x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // Split the cubic into enough pieces that each can be replaced by a
+        // single quadratic curve within tolerance.
+        let (x0, y0) = self.pen;
+        let dx = x - 3.0 * x2 + 3.0 * x1 - x0;
+        let dy = y - 3.0 * y2 + 3.0 * y1 - y0;
+        let error = (dx * dx + dy * dy).sqrt() * 3f32.sqrt() / 36.0;
+        let pieces = (error / Self::CUBIC_TOLERANCE).cbrt().ceil().max(1.0) as usize;
+
+        let point_at = |t: f32| {
+            let mt = 1.0 - t;
+            let a = mt * mt * mt;
+            let b = 3.0 * mt * mt * t;
+            let c = 3.0 * mt * t * t;
+            let d = t * t * t;
+            (
+                a * x0 + b * x1 + c * x2 + d * x,
+                a * y0 + b * y1 + c * y2 + d * y,
+            )
+        };
+        let tangent_at = |t: f32| {
+            let mt = 1.0 - t;
+            let a = 3.0 * mt * mt;
+            let b = 6.0 * mt * t;
+            let c = 3.0 * t * t;
+            (
+                a * (x1 - x0) + b * (x2 - x1) + c * (x - x2),
+                a * (y1 - y0) + b * (y2 - y1) + c * (y - y2),
+            )
+        };
+
+        for i in 0..pieces {
+            let t0 = i as f32 / pieces as f32;
+            let t1 = (i + 1) as f32 / pieces as f32;
+            let dt = t1 - t0;
+            let p0 = point_at(t0);
+            let p3 = if i + 1 == pieces {
+                (x, y)
+            } else {
+                point_at(t1)
+            };
+            let d0 = tangent_at(t0);
+            let d1 = tangent_at(t1);
+
+            // The control points of this piece as a cubic, merged into the
+            // single control point of the best-fitting quadratic.
+            let c1 = (p0.0 + d0.0 * dt / 3.0, p0.1 + d0.1 * dt / 3.0);
+            let c2 = (p3.0 - d1.0 * dt / 3.0, p3.1 - d1.1 * dt / 3.0);
+            let control = (
+                (3.0 * (c1.0 + c2.0) - p0.0 - p3.0) / 4.0,
+                (3.0 * (c1.1 + c2.1) - p0.1 - p3.1) / 4.0,
+            );
+            self.quad(control, p3);
+        }
+        self.pen = (x, y);
+    }
+
+    fn close(&mut self) {
+        if self.written != Self::point(self.start.0, self.start.1) {
+            let (x, y) = self.start;
+            self.line_to(x, y);
+        }
+    }
 }
 
 /// Structure which identifies a particular font by name and properties.
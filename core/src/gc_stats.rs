@@ -0,0 +1,48 @@
+//! Garbage collection pacing and statistics.
+
+use std::time::Duration;
+
+/// When the player does full garbage collections.
+///
+/// The player always collects incrementally after every update, doing an
+/// amount of work proportional to what was allocated. Full collections pause
+/// the player until everything unreachable has been freed, so by default
+/// they are only done when a movie or the embedder asks for one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GcPacing {
+    /// Collect fully every this many frames.
+    pub frame_interval: Option<u32>,
+
+    /// Collect fully once this many bytes have been allocated since the last
+    /// full collection.
+    pub allocation_threshold: Option<usize>,
+}
+
+/// Statistics about the garbage-collected heap of a player.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GcStats {
+    /// The number of bytes allocated on the heap.
+    pub total_allocated: usize,
+
+    /// The number of bytes that were allocated right after the last full
+    /// collection.
+    pub allocated_after_last_collect: usize,
+
+    /// The number of bytes freed by the last full collection.
+    pub freed_by_last_collect: usize,
+
+    /// How long the last full collection took.
+    pub last_collect_time: Duration,
+
+    /// The number of full collections done so far.
+    pub full_collects: u64,
+}
+
+impl GcStats {
+    /// The number of bytes allocated since the last full collection, less
+    /// what incremental collection has freed since.
+    pub fn allocated_since_last_collect(&self) -> usize {
+        self.total_allocated
+            .saturating_sub(self.allocated_after_last_collect)
+    }
+}
@@ -119,10 +119,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     /// Calculate the line-to-line leading present on this line, including the
     /// font-leading above.
     fn line_leading_adjustment(&self) -> Twips {
-        self.font
-            .map(|f| f.get_leading_for_height(self.max_font_size))
-            .unwrap_or_else(|| Twips::new(0))
-            + Twips::from_pixels(self.current_line_span.leading)
+        self.font_leading_adjustment() + Twips::from_pixels(self.current_line_span.leading)
     }
 
     /// Determine the effective alignment mode for the current line of text.
@@ -398,13 +395,13 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ) -> Option<Font<'gc>> {
         let library = context.library.library_for_movie_mut(self.movie.clone());
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, look for a matching font on the system,
+        // and fallback to using our embedded Noto Sans if there is none.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
         if let Some(font) = library
             .get_font_by_name(&span.font, span.bold, span.italic)
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| library.device_font())
+            .or_else(|| self.device_font(context, span))
         {
             self.font = Some(font);
             return self.font;
@@ -413,6 +410,31 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         None
     }
 
+    /// Find the device font to use for a span, falling back to our embedded
+    /// Noto Sans if no suitable font is installed.
+    fn device_font(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        span: &TextSpan,
+    ) -> Option<Font<'gc>> {
+        context
+            .library
+            .get_device_font(
+                context.gc_context,
+                context.renderer,
+                context.ui,
+                &span.font,
+                span.bold,
+                span.italic,
+            )
+            .or_else(|| {
+                context
+                    .library
+                    .library_for_movie_mut(self.movie.clone())
+                    .device_font()
+            })
+    }
+
     /// Append text to the current line of the ongoing layout operation.
     ///
     /// The text given may or may not be separated into fragments, depending on
@@ -462,7 +484,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         if let Some(bullet_font) = library
             .get_font_by_name(&span.font, span.bold, span.italic)
             .filter(|f| f.has_glyphs())
-            .or_else(|| library.device_font())
+            .or_else(|| self.device_font(context, span))
             .or(self.font)
         {
             let mut bullet_cursor = self.cursor;
@@ -92,7 +92,7 @@ fn process_html_entity(src: &str) -> Cow<str> {
 /// means that multiple regions of text apply. When setting the format of a
 /// particular region of text, `None` means that the existing setting for that
 /// property will be retained.
-#[derive(Clone, Debug, Collect, Default)]
+#[derive(Clone, Debug, Collect, Default, PartialEq)]
 #[collect(require_static)]
 pub struct TextFormat {
     pub font: Option<String>,
@@ -671,7 +671,7 @@ impl TextFormat {
 ///
 /// This struct also contains a resolved version of the `TextFormat` structure
 /// listed above.
-#[derive(Clone, Debug, Collect)]
+#[derive(Clone, Debug, Collect, PartialEq)]
 #[collect(require_static)]
 pub struct TextSpan {
     /// How many characters are subsumed by this text span.
@@ -879,7 +879,7 @@ impl TextSpan {
 }
 
 /// Struct which contains text formatted by `TextSpan`s.
-#[derive(Clone, Debug, Collect)]
+#[derive(Clone, Debug, Collect, PartialEq)]
 #[collect(require_static)]
 pub struct FormatSpans {
     text: String,
@@ -18,36 +18,45 @@ extern crate downcast_rs;
 #[macro_use]
 mod avm1;
 mod avm2;
+mod bitmap_data;
 mod bounding_box;
 mod character;
 mod collect;
 pub mod color_transform;
 pub mod context;
+mod context_menu;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
 pub mod focus_tracker;
 mod font;
+mod gc_stats;
 mod html;
 mod library;
 pub mod loader;
+mod local_connection;
 mod player;
 mod prelude;
 pub mod property_map;
+mod shape_cache;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
 mod transform;
 mod types;
 mod vminterface;
+mod worker;
 mod xml;
 
 pub mod backend;
 pub mod external;
 
 pub use chrono;
+pub use display_object::GotoSnapshotSettings;
 pub use events::PlayerEvent;
+pub use gc_stats::{GcPacing, GcStats};
 pub use indexmap;
 pub use player::Player;
+pub use shape_cache::ShapeCache;
 pub use swf;
 pub use swf::Color;
@@ -1,11 +1,13 @@
 use crate::avm2::Domain as Avm2Domain;
 use crate::backend::audio::SoundHandle;
+use crate::backend::render::RenderBackend;
+use crate::backend::ui::UiBackend;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
 use crate::font::{Font, FontDescriptor};
 use crate::prelude::*;
 use crate::property_map::{Entry, PropertyMap};
-use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::tag_utils::{MovieIdentity, SwfMovie, SwfSlice};
 use crate::vminterface::AvmType;
 use gc_arena::{Collect, MutationContext};
 use std::collections::HashMap;
@@ -141,6 +143,7 @@ impl<'gc> MovieLibrary<'gc> {
             Character::MovieClip(movie_clip) => Ok(movie_clip.instantiate(gc_context)),
             Character::Button(button) => Ok(button.instantiate(gc_context)),
             Character::Text(text) => Ok(text.instantiate(gc_context)),
+            Character::Video(video) => Ok(video.instantiate(gc_context)),
             _ => Err("Not a DisplayObject".into()),
         }
     }
@@ -242,7 +245,16 @@ impl<'gc> MovieLibrary<'gc> {
 /// Symbol library for multiple movies.
 pub struct Library<'gc> {
     /// All the movie libraries.
-    movie_libraries: PtrWeakKeyHashMap<Weak<SwfMovie>, MovieLibrary<'gc>>,
+    ///
+    /// Libraries are keyed by the identity of their movie, so that a movie
+    /// that is loaded progressively keeps its library as more of it arrives.
+    movie_libraries: PtrWeakKeyHashMap<Weak<MovieIdentity>, MovieLibrary<'gc>>,
+
+    /// Device fonts loaded from the system, keyed by the requested name.
+    ///
+    /// Fonts that are not available are cached as `None`, so that the UI
+    /// backend is only asked for each font once.
+    device_fonts: HashMap<FontDescriptor, Option<Font<'gc>>>,
 }
 
 unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
@@ -251,16 +263,89 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
         for (_, val) in self.movie_libraries.iter() {
             val.trace(cc);
         }
+        for val in self.device_fonts.values() {
+            val.trace(cc);
+        }
     }
 }
 
 impl<'gc> Library<'gc> {
+    /// Find the device font to use for a font name.
+    ///
+    /// Fonts are looked up by their exact name first. Names that are not
+    /// installed, as well as the generic `_sans`, `_serif` and `_typewriter`
+    /// names, are then mapped to the usual system font for that kind of
+    /// font, with unknown names treated as `_sans`.
+    ///
+    /// Yields `None` if none of these fonts are installed, in which case the
+    /// bundled device font of the movie should be used.
+    pub fn get_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        ui: &mut dyn UiBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let fallback_names: &[&str] = match name {
+            "_serif" => &["Times New Roman", "Times"],
+            "_typewriter" => &["Courier New", "Courier"],
+            "_sans" => &["Arial", "Helvetica"],
+            name => {
+                if let Some(font) =
+                    self.load_device_font(gc_context, renderer, ui, name, is_bold, is_italic)
+                {
+                    return Some(font);
+                }
+
+                &["Arial", "Helvetica"]
+            }
+        };
+
+        fallback_names.iter().find_map(|fallback_name| {
+            self.load_device_font(gc_context, renderer, ui, fallback_name, is_bold, is_italic)
+        })
+    }
+
+    /// Load a single device font from the UI backend, if it is installed.
+    fn load_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        ui: &mut dyn UiBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.device_fonts.get(&descriptor) {
+            return *font;
+        }
+
+        let font = match ui.load_device_font(name, is_bold, is_italic) {
+            Some(data) => {
+                match Font::from_font_data(gc_context, renderer, &data, descriptor.clone()) {
+                    Ok(font) => Some(font),
+                    Err(e) => {
+                        log::warn!("Unable to load device font {}: {}", name, e);
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        self.device_fonts.insert(descriptor, font);
+
+        font
+    }
+
     pub fn library_for_movie(&self, movie: Arc<SwfMovie>) -> Option<&MovieLibrary<'gc>> {
-        self.movie_libraries.get(&movie)
+        self.movie_libraries.get(movie.identity())
     }
 
     pub fn library_for_movie_mut(&mut self, movie: Arc<SwfMovie>) -> &mut MovieLibrary<'gc> {
-        if !self.movie_libraries.contains_key(&movie) {
+        if !self.movie_libraries.contains_key(movie.identity()) {
             let slice = SwfSlice::from(movie.clone());
             let mut reader = slice.read_from(0);
             let vm_type = if movie.header().version > 8 {
@@ -285,10 +370,10 @@ impl<'gc> Library<'gc> {
             };
 
             self.movie_libraries
-                .insert(movie.clone(), MovieLibrary::new(vm_type));
+                .insert(movie.identity().clone(), MovieLibrary::new(vm_type));
         };
 
-        self.movie_libraries.get_mut(&movie).unwrap()
+        self.movie_libraries.get_mut(movie.identity()).unwrap()
     }
 }
 
@@ -296,6 +381,7 @@ impl<'gc> Default for Library<'gc> {
     fn default() -> Self {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
+            device_fonts: HashMap::new(),
         }
     }
 }
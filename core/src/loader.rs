@@ -2,10 +2,15 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
-use crate::avm2::Domain as Avm2Domain;
-use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::avm2::{Avm2, Domain as Avm2Domain, Object as Avm2Object};
+use crate::backend::navigator::{OwnedFuture, Response, ResponseBody};
+use crate::backend::render::{self, JpegTagFormat};
+use crate::character::Character;
+use crate::context::{ActionQueue, ActionType, UpdateContext};
+use crate::display_object::{
+    Bitmap, BitmapSource, DisplayObject, MorphShape, MovieClip, TDisplayObject,
+    TDisplayObjectContainer,
+};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::property_map::PropertyMap;
 use crate::tag_utils::SwfMovie;
@@ -41,12 +46,21 @@ pub enum Error {
     #[error("Non-XML loader spawned as XML loader")]
     NotXmlLoader,
 
+    #[error("Non-URLLoader loader spawned as URLLoader loader")]
+    NotUrlLoader,
+
+    #[error("Non-import loader spawned as import loader")]
+    NotImportLoader,
+
     #[error("Could not fetch movie {0}")]
     FetchError(String),
 
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
     #[error("Invalid XML encoding")]
     InvalidXmlEncoding(#[from] FromUtf8Error),
 
@@ -56,6 +70,9 @@ pub enum Error {
     #[error("Network unavailable.")]
     NetworkUnavailable,
 
+    #[error("Request to {0} failed with HTTP status {1}")]
+    HttpStatus(String, u16),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -125,7 +142,7 @@ impl<'gc> LoadManager<'gc> {
     pub fn load_root_movie(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         url: String,
         parameters: PropertyMap<String>,
     ) -> OwnedFuture<(), Error> {
@@ -145,7 +162,7 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         url: String,
         target_broadcaster: Option<Object<'gc>>,
     ) -> OwnedFuture<(), Error> {
@@ -153,7 +170,10 @@ impl<'gc> LoadManager<'gc> {
             self_handle: None,
             target_clip,
             target_broadcaster,
+            target_loader_info: None,
             load_complete: false,
+            bytes_loaded: 0,
+            bytes_total: None,
         };
         let handle = self.add_loader(loader);
 
@@ -163,6 +183,75 @@ impl<'gc> LoadManager<'gc> {
         loader.movie_loader(player, fetch, url)
     }
 
+    /// Kick off a movie load on behalf of an AVM2 `Loader`.
+    ///
+    /// The movie is loaded into `target_clip`, and `target_loader_info` is
+    /// told once it has been loaded.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_movie_into_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_clip: DisplayObject<'gc>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
+        url: String,
+        target_loader_info: Avm2Object<'gc>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::Movie {
+            self_handle: None,
+            target_clip,
+            target_broadcaster: None,
+            target_loader_info: Some(target_loader_info),
+            load_complete: false,
+            bytes_loaded: 0,
+            bytes_total: None,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.movie_loader(player, fetch, url)
+    }
+
+    /// Get the download progress of a movie that is being loaded into a clip.
+    ///
+    /// Returns `None` if no movie is being downloaded into the clip.
+    pub fn movie_load_progress(
+        &self,
+        target_clip: DisplayObject<'gc>,
+    ) -> Option<(u64, Option<u64>)> {
+        self.0.iter().find_map(|(_, loader)| match loader {
+            Loader::Movie {
+                target_clip: clip,
+                load_complete: false,
+                bytes_loaded,
+                bytes_total,
+                ..
+            } if DisplayObject::ptr_eq(*clip, target_clip) => Some((*bytes_loaded, *bytes_total)),
+            _ => None,
+        })
+    }
+
+    /// Cancel the load of a movie into a clip, if one is in progress.
+    ///
+    /// Returns the event broadcaster of the cancelled load, which should be
+    /// told that the load failed.
+    pub fn cancel_movie_load(&mut self, target_clip: DisplayObject<'gc>) -> Option<Object<'gc>> {
+        let (handle, broadcaster) = self.0.iter().find_map(|(handle, loader)| match loader {
+            Loader::Movie {
+                target_clip: clip,
+                target_broadcaster,
+                load_complete: false,
+                ..
+            } if DisplayObject::ptr_eq(*clip, target_clip) => Some((handle, *target_broadcaster)),
+            _ => None,
+        })?;
+
+        self.0.remove(handle);
+        broadcaster
+    }
+
     /// Indicates that a movie clip has initialized (ran its first frame).
     ///
     /// Interested loaders will be invoked from here.
@@ -186,6 +275,35 @@ impl<'gc> LoadManager<'gc> {
         }
     }
 
+    /// Kick off a load of the symbols a movie imports from another movie.
+    ///
+    /// `frame` of `target_clip` is held back until the import finishes.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_imported_assets(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_clip: MovieClip<'gc>,
+        frame: u16,
+        imports: Vec<swf::ExportedAsset>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::ImportAssets {
+            self_handle: None,
+            target_clip,
+            importing_movie: target_clip.movie(),
+            frame,
+            imports,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.import_assets_loader(player, fetch, url)
+    }
+
     /// Kick off a form data load into an AVM1 object.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -250,6 +368,28 @@ impl<'gc> LoadManager<'gc> {
 
         loader.xml_loader(player, fetch)
     }
+
+    /// Kick off a data load on behalf of an AVM2 `URLLoader`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        fetch: OwnedFuture<Response, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::URLLoader {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.url_loader(player, fetch, url)
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -258,6 +398,152 @@ impl<'gc> Default for LoadManager<'gc> {
     }
 }
 
+/// Read the whole body of a movie that is being loaded into a clip.
+///
+/// The download progress of the movie is reported as its chunks arrive. If
+/// the load is cancelled while the movie is downloading, this yields
+/// `Error::Cancelled`.
+async fn read_movie_body(
+    player: &Arc<Mutex<Player>>,
+    handle: Handle,
+    fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
+) -> Result<Vec<u8>, Error> {
+    let mut body = fetch.await?;
+    let mut data = Vec::new();
+
+    // Each chunk is reported once the next one has arrived, so that the
+    // last chunk of a body of unknown length is reported with its length.
+    let mut next_chunk = body.next_chunk().await?;
+    while let Some(chunk) = next_chunk {
+        data.extend_from_slice(&chunk);
+        next_chunk = body.next_chunk().await?;
+
+        let bytes_loaded = data.len() as u64;
+        let bytes_total = match body.total_len() {
+            Some(bytes_total) => Some(bytes_total),
+            None if next_chunk.is_none() => Some(bytes_loaded),
+            None => None,
+        };
+        report_movie_progress(player, handle, bytes_loaded, bytes_total)?;
+    }
+
+    Ok(data)
+}
+
+/// The content of a file that was loaded into a clip.
+enum MovieContent {
+    /// A SWF movie.
+    Swf(SwfMovie),
+
+    /// A JPEG, PNG or GIF image, which is shown in an otherwise empty movie.
+    Image {
+        data: Vec<u8>,
+        bitmap: render::Bitmap,
+    },
+}
+
+impl MovieContent {
+    /// Determine the kind of a loaded file from its data, and parse it.
+    fn from_data(data: Vec<u8>, url: &str) -> Result<Self, Error> {
+        if render::determine_jpeg_tag_format(&data) == JpegTagFormat::Unknown {
+            return Ok(MovieContent::Swf(SwfMovie::from_data(
+                &data,
+                Some(url.to_string()),
+            )?));
+        }
+
+        let bitmap = render::decode_define_bits_jpeg(&data, None)
+            .map_err(|e| Error::InvalidImage(e.to_string()))?;
+        Ok(MovieContent::Image { data, bitmap })
+    }
+}
+
+/// Show a loaded image on the stage of the otherwise empty movie that was
+/// loaded into a clip.
+fn place_loaded_image<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    clip: DisplayObject<'gc>,
+    data: Vec<u8>,
+    bitmap: render::Bitmap,
+) {
+    let (width, height) = (bitmap.width, bitmap.height);
+    let handle = match uc
+        .renderer
+        .register_bitmap_raw(width, height, bitmap.data.into_rgba())
+    {
+        Ok(handle) => handle,
+        Err(e) => {
+            log::error!("Could not register loaded image: {}", e);
+            return;
+        }
+    };
+
+    let source = BitmapSource::Jpeg {
+        data,
+        alpha_data: None,
+    };
+    let image: DisplayObject<'gc> =
+        Bitmap::new(uc, 0, handle, width as u16, height as u16, source).into();
+    if let Some(mc) = clip.as_movie_clip() {
+        mc.replace_at_depth(uc, image, 1);
+        image.set_depth(uc.gc_context, 1);
+        image.set_parent(uc.gc_context, Some(clip));
+        image.post_instantiation(uc, image, None, Instantiator::Movie, false);
+    }
+}
+
+/// Report the download progress of a movie that is being loaded into a clip
+/// to the player, and to the broadcaster and `LoaderInfo` of the load.
+fn report_movie_progress(
+    player: &Arc<Mutex<Player>>,
+    handle: Handle,
+    bytes_loaded: u64,
+    bytes_total: Option<u64>,
+) -> Result<(), Error> {
+    let mut player = player.lock().expect("Could not lock player!!");
+    player.report_load_progress(bytes_loaded, bytes_total);
+    player.update(|uc| {
+        let (clip, broadcaster, loader_info) = match uc.load_manager.get_loader_mut(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                target_broadcaster,
+                target_loader_info,
+                bytes_loaded: loader_bytes_loaded,
+                bytes_total: loader_bytes_total,
+                ..
+            }) => {
+                *loader_bytes_loaded = bytes_loaded;
+                *loader_bytes_total = bytes_total;
+                (*target_clip, *target_broadcaster, *target_loader_info)
+            }
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        if let Some(broadcaster) = broadcaster {
+            Avm1::run_stack_frame_for_method(
+                clip,
+                broadcaster,
+                NEWEST_PLAYER_VERSION,
+                uc,
+                "broadcastMessage",
+                &[
+                    "onLoadProgress".into(),
+                    clip.object(),
+                    (bytes_loaded as f64).into(),
+                    bytes_total.map_or(Value::Undefined, |total| (total as f64).into()),
+                ],
+            );
+        }
+
+        if let Some(loader_info) = loader_info {
+            Avm2::loader_content_progress(uc, loader_info, bytes_loaded, bytes_total);
+        }
+
+        Ok(())
+    })
+}
+
 /// A struct that holds garbage-collected pointers for asynchronous code.
 pub enum Loader<'gc> {
     /// Loader that is loading the root movie of a player.
@@ -278,6 +564,10 @@ pub enum Loader<'gc> {
         /// into.
         target_broadcaster: Option<Object<'gc>>,
 
+        /// The `LoaderInfo` of the AVM2 `Loader` that requested this load,
+        /// if any.
+        target_loader_info: Option<Avm2Object<'gc>>,
+
         /// Indicates that the load has completed.
         ///
         /// This flag exists to prevent a situation in which loading a movie
@@ -287,6 +577,12 @@ pub enum Loader<'gc> {
         /// or an error has occurred (in which case we don't care about the
         /// loader anymore).
         load_complete: bool,
+
+        /// The number of bytes of the movie that have been downloaded.
+        bytes_loaded: u64,
+
+        /// The length of the movie, once it is known.
+        bytes_total: Option<u64>,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -323,6 +619,35 @@ pub enum Loader<'gc> {
         /// The target node whose contents will be replaced with the parsed XML.
         target_node: XMLNode<'gc>,
     },
+
+    /// Loader that is loading data into an AVM2 `URLLoader`.
+    URLLoader {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The `URLLoader` to give the response to.
+        target_object: Avm2Object<'gc>,
+    },
+
+    /// Loader that is loading the movie that another movie imports symbols
+    /// from, with an `ImportAssets` tag.
+    ImportAssets {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The clip whose timeline imports the symbols.
+        target_clip: MovieClip<'gc>,
+
+        /// The movie whose library the symbols are imported into.
+        importing_movie: Option<Arc<SwfMovie>>,
+
+        /// The frame of the importing clip that waits for the import.
+        frame: u16,
+
+        /// The character IDs to give the imported symbols in the importing
+        /// movie, along with the names they are exported under.
+        imports: Vec<swf::ExportedAsset>,
+    },
 }
 
 unsafe impl<'gc> Collect for Loader<'gc> {
@@ -332,14 +657,18 @@ unsafe impl<'gc> Collect for Loader<'gc> {
             Loader::Movie {
                 target_clip,
                 target_broadcaster,
+                target_loader_info,
                 ..
             } => {
                 target_clip.trace(cc);
                 target_broadcaster.trace(cc);
+                target_loader_info.trace(cc);
             }
             Loader::Form { target_object, .. } => target_object.trace(cc),
             Loader::LoadVars { target_object, .. } => target_object.trace(cc),
             Loader::XML { target_node, .. } => target_node.trace(cc),
+            Loader::URLLoader { target_object, .. } => target_object.trace(cc),
+            Loader::ImportAssets { target_clip, .. } => target_clip.trace(cc),
         }
     }
 }
@@ -356,6 +685,8 @@ impl<'gc> Loader<'gc> {
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
             Loader::XML { self_handle, .. } => *self_handle = Some(handle),
+            Loader::URLLoader { self_handle, .. } => *self_handle = Some(handle),
+            Loader::ImportAssets { self_handle, .. } => *self_handle = Some(handle),
         }
     }
 
@@ -363,7 +694,7 @@ impl<'gc> Loader<'gc> {
     pub fn root_movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         mut url: String,
         parameters: PropertyMap<String>,
     ) -> OwnedFuture<(), Error> {
@@ -388,19 +719,46 @@ impl<'gc> Loader<'gc> {
                     Ok(())
                 })?;
 
-            let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
+            let mut body = match fetch.await {
+                Ok(body) => body,
+                Err(_) => return Err(Error::FetchError(url)),
+            };
+            let bytes_total = body.total_len();
 
-            if let Ok((_length, mut movie)) = data {
-                for (key, value) in parameters.iter() {
-                    movie.parameters_mut().insert(key, value.to_owned(), false);
-                }
-                player.lock().unwrap().set_root_movie(Arc::new(movie));
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .begin_root_movie_stream(Some(url.clone()), parameters);
+
+            // The movie begins to play as soon as enough of it has arrived.
+            let mut bytes_loaded = 0;
+            loop {
+                let chunk = match body.next_chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    Err(_) => return Err(Error::FetchError(url)),
+                };
+                bytes_loaded += chunk.len() as u64;
 
-                Ok(())
-            } else {
-                Err(Error::FetchError(url))
+                let mut player = player.lock().expect("Could not lock player!!");
+                player.push_root_movie_bytes(&chunk)?;
+                player.report_load_progress(bytes_loaded, bytes_total);
             }
+
+            let finish = {
+                let mut player = player.lock().expect("Could not lock player!!");
+                if bytes_total.is_none() {
+                    player.report_load_progress(bytes_loaded, Some(bytes_loaded));
+                }
+                player.finish_root_movie_stream()
+            };
+            let movie = finish.await?;
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update_root_movie(Arc::new(movie));
+
+            Ok(())
         })
     }
 
@@ -414,7 +772,7 @@ impl<'gc> Loader<'gc> {
     pub fn movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        fetch: OwnedFuture<Vec<u8>, Error>,
+        fetch: OwnedFuture<Box<dyn ResponseBody>, Error>,
         mut url: String,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
@@ -445,9 +803,7 @@ impl<'gc> Loader<'gc> {
 
                     clip.as_movie_clip().unwrap().unload(uc);
 
-                    clip.as_movie_clip()
-                        .unwrap()
-                        .replace_with_movie(uc.gc_context, None);
+                    clip.as_movie_clip().unwrap().replace_with_movie(uc, None);
 
                     if let Some(broadcaster) = broadcaster {
                         Avm1::run_stack_frame_for_method(
@@ -456,16 +812,26 @@ impl<'gc> Loader<'gc> {
                             NEWEST_PLAYER_VERSION,
                             uc,
                             "broadcastMessage",
-                            &["onLoadStart".into(), Value::Object(broadcaster)],
+                            &["onLoadStart".into(), clip.object()],
                         );
                     }
 
                     Ok(())
                 })?;
 
-            let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
-            if let Ok((length, movie)) = data {
+            let data = match read_movie_body(&player, handle, fetch).await {
+                // A cancelled load has already reported its failure.
+                Err(Error::Cancelled) => return Ok(()),
+                data => data.and_then(|data| MovieContent::from_data(data, &url)),
+            };
+            if let Ok(content) = data {
+                let (movie, image) = match content {
+                    MovieContent::Swf(movie) => (movie, None),
+                    MovieContent::Image { data, bitmap } => (
+                        SwfMovie::from_image(bitmap.width, bitmap.height, data.len(), url.clone()),
+                        Some((data, bitmap)),
+                    ),
+                };
                 let movie = Arc::new(movie);
 
                 player
@@ -478,42 +844,32 @@ impl<'gc> Loader<'gc> {
                             .library_for_movie_mut(movie.clone())
                             .set_avm2_domain(domain);
 
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
-                                ],
-                            );
-                        }
+                        let (clip, broadcaster, loader_info) =
+                            match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    target_broadcaster,
+                                    target_loader_info,
+                                    ..
+                                }) => (*target_clip, *target_broadcaster, *target_loader_info),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
 
                         let mut mc = clip
                             .as_movie_clip()
                             .expect("Attempted to load movie into not movie clip");
 
-                        mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
+                        mc.replace_with_movie(uc, Some(movie.clone()));
                         mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
 
                         let mut morph_shapes = fnv::FnvHashMap::default();
                         mc.preload(uc, &mut morph_shapes);
 
+                        if let Some((data, bitmap)) = image {
+                            place_loaded_image(uc, clip, data, bitmap);
+                        }
+
                         // Finalize morph shapes.
                         for (id, static_data) in morph_shapes {
                             let morph_shape = MorphShape::new(uc.gc_context, static_data);
@@ -532,10 +888,14 @@ impl<'gc> Loader<'gc> {
                                 NEWEST_PLAYER_VERSION,
                                 uc,
                                 "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
+                                &["onLoadComplete".into(), clip.object()],
                             );
                         }
 
+                        if let Some(loader_info) = loader_info {
+                            Avm2::loader_content_loaded(uc, loader_info, clip);
+                        }
+
                         if let Some(Loader::Movie { load_complete, .. }) =
                             uc.load_manager.get_loader_mut(handle)
                         {
@@ -545,24 +905,28 @@ impl<'gc> Loader<'gc> {
                         Ok(())
                     })
             } else {
-                //TODO: Inspect the fetch error.
-                //This requires cooperation from the backend to send abstract
-                //error types we can actually inspect.
-                //This also can get errors from decoding an invalid SWF file,
-                //too. We should distinguish those to player code.
+                //TODO: This also can get errors from decoding an invalid SWF
+                //file. We should distinguish those to player code.
+                let status = match &data {
+                    Err(Error::HttpStatus(_, status)) => *status,
+                    _ => 0,
+                };
+
                 player
                     .lock()
                     .expect("Could not lock player!!")
                     .update(|uc| -> Result<(), Error> {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
+                        let (clip, broadcaster, loader_info) =
+                            match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    target_broadcaster,
+                                    target_loader_info,
+                                    ..
+                                }) => (*target_clip, *target_broadcaster, *target_loader_info),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
 
                         if let Some(broadcaster) = broadcaster {
                             Avm1::run_stack_frame_for_method(
@@ -573,12 +937,16 @@ impl<'gc> Loader<'gc> {
                                 "broadcastMessage",
                                 &[
                                     "onLoadError".into(),
-                                    Value::Object(broadcaster),
+                                    clip.object(),
                                     "LoadNeverCompleted".into(),
                                 ],
                             );
                         }
 
+                        if let Some(loader_info) = loader_info {
+                            Avm2::loader_content_failed(uc, loader_info, &url, status);
+                        }
+
                         if let Some(Loader::Movie { load_complete, .. }) =
                             uc.load_manager.get_loader_mut(handle)
                         {
@@ -692,6 +1060,113 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Construct a future for the given import loader.
+    ///
+    /// Once the movie the symbols are imported from has loaded, each symbol
+    /// is added to the importing movie's library under the character ID and
+    /// export name it is imported with. Symbols that can't be imported are
+    /// left out. Either way, the importing frame is then allowed to play.
+    pub fn import_assets_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::ImportAssets { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotImportLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let movie = match fetch.await {
+                Ok(data) => SwfMovie::from_data(&data, Some(url.clone())).map_err(Error::from),
+                Err(e) => Err(e),
+            };
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let (clip, importing_movie, frame, imports) =
+                        match uc.load_manager.get_loader(handle) {
+                            Some(Loader::ImportAssets {
+                                target_clip,
+                                importing_movie,
+                                frame,
+                                imports,
+                                ..
+                            }) => (
+                                *target_clip,
+                                importing_movie.clone(),
+                                *frame,
+                                imports.clone(),
+                            ),
+                            None => return Err(Error::Cancelled),
+                            _ => return Err(Error::NotImportLoader),
+                        };
+
+                    match (movie, importing_movie) {
+                        (Ok(movie), Some(importing_movie)) => {
+                            let movie = Arc::new(movie);
+                            let domain =
+                                Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain());
+                            uc.library
+                                .library_for_movie_mut(movie.clone())
+                                .set_avm2_domain(domain);
+
+                            // The library movie is never displayed; it only
+                            // has to define its symbols.
+                            let library_clip = MovieClip::from_movie(uc.gc_context, movie.clone());
+                            let mut morph_shapes = fnv::FnvHashMap::default();
+                            library_clip.preload(uc, &mut morph_shapes);
+                            for (id, static_data) in morph_shapes {
+                                let morph_shape = MorphShape::new(uc.gc_context, static_data);
+                                uc.library
+                                    .library_for_movie_mut(movie.clone())
+                                    .register_character(id, Character::MorphShape(morph_shape));
+                            }
+
+                            for import in imports {
+                                let character = uc
+                                    .library
+                                    .library_for_movie(movie.clone())
+                                    .and_then(|library| {
+                                        library.get_character_by_export_name(&import.name)
+                                    })
+                                    .cloned();
+                                match character {
+                                    Some(character) => {
+                                        let library = uc
+                                            .library
+                                            .library_for_movie_mut(importing_movie.clone());
+                                        library.register_character(import.id, character);
+                                        library.register_export(import.id, &import.name);
+                                    }
+                                    None => log::warn!(
+                                        "Can't import {}: {} doesn't export it",
+                                        import.name,
+                                        url
+                                    ),
+                                }
+                            }
+                        }
+                        (Err(e), _) => log::error!("Unable to import assets from {}: {}", url, e),
+                        (Ok(_), None) => {}
+                    }
+
+                    clip.finish_import(uc.gc_context, frame);
+
+                    Ok(())
+                })
+        })
+    }
+
     /// Event handler morally equivalent to `onLoad` on a movie clip.
     ///
     /// Returns `true` if the loader has completed and should be removed.
@@ -832,4 +1307,59 @@ impl<'gc> Loader<'gc> {
             Ok(())
         })
     }
+
+    /// Creates a future for an AVM2 `URLLoader` load call.
+    pub fn url_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Response, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::URLLoader { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotUrlLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let target = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::URLLoader { target_object, .. }) => target_object,
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotUrlLoader),
+                    };
+
+                    Avm2::url_loader_started(uc, target);
+
+                    Ok(())
+                })?;
+
+            // Requests that fail without an HTTP response are reported with
+            // a status of 0.
+            let response = fetch.await.ok();
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let target = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::URLLoader { target_object, .. }) => target_object,
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotUrlLoader),
+                    };
+
+                    Avm2::url_loader_response(uc, target, &url, response);
+
+                    Ok(())
+                })
+        })
+    }
 }
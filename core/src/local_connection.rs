@@ -0,0 +1,226 @@
+//! Communication between movies running in the same player.
+//!
+//! Both AVM1 and AVM2 `LocalConnection` objects are registered here, so that
+//! movies written for either VM can send messages to one another.
+
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{Avm2, Object as Avm2Object};
+use crate::context::UpdateContext;
+use crate::external::Value as ExternalValue;
+use gc_arena::{Collect, CollectionContext};
+use std::collections::HashMap;
+use url::Url;
+
+/// The domain reported for movies that were not loaded from a web server.
+const LOCAL_DOMAIN: &str = "localhost";
+
+/// A `LocalConnection` object belonging to either VM.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub enum LocalConnectionObject<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+impl<'gc> LocalConnectionObject<'gc> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Avm1(a), Self::Avm1(b)) => Avm1Object::ptr_eq(a, b),
+            (Self::Avm2(a), Self::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// An object listening for messages under a particular connection name.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+struct Connection<'gc> {
+    /// The object that will receive messages.
+    object: LocalConnectionObject<'gc>,
+
+    /// The domain of the movie that made the connection.
+    domain: String,
+}
+
+/// A message waiting to be delivered to a connection.
+pub struct LocalConnectionMessage<'gc> {
+    /// The `LocalConnection` that sent this message, which will be told
+    /// whether or not it was delivered.
+    pub sender: LocalConnectionObject<'gc>,
+
+    /// The domain of the movie that sent this message.
+    pub sender_domain: String,
+
+    /// The fully qualified name of the connection to deliver this message to.
+    pub connection_name: String,
+
+    /// The name of the method to call on the receiver.
+    pub method_name: String,
+
+    /// The arguments of the method call.
+    ///
+    /// These are stored independently of either VM so that messages can pass
+    /// between AVM1 and AVM2 movies.
+    pub arguments: Vec<ExternalValue>,
+}
+
+unsafe impl<'gc> Collect for LocalConnectionMessage<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        self.sender.trace(cc);
+    }
+}
+
+/// All local connections open in a player.
+pub struct LocalConnections<'gc> {
+    /// Connections that are listening for messages, by their fully qualified
+    /// name.
+    connections: HashMap<String, Connection<'gc>>,
+
+    /// Messages that will be delivered at the end of the current frame.
+    messages: Vec<LocalConnectionMessage<'gc>>,
+}
+
+unsafe impl<'gc> Collect for LocalConnections<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        self.connections.trace(cc);
+        self.messages.trace(cc);
+    }
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn new() -> Self {
+        Self {
+            connections: HashMap::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Start listening for messages on a connection.
+    ///
+    /// `name` must already be qualified with `qualified_name`. Returns `false`
+    /// if the name is already in use, or if the object is already listening
+    /// on another connection.
+    pub fn connect(
+        &mut self,
+        name: String,
+        object: LocalConnectionObject<'gc>,
+        domain: String,
+    ) -> bool {
+        if self.connections.contains_key(&name) || self.connection_name(object).is_some() {
+            return false;
+        }
+
+        self.connections.insert(name, Connection { object, domain });
+        true
+    }
+
+    /// Stop listening for messages.
+    ///
+    /// Returns `false` if the object was not listening on any connection.
+    pub fn close(&mut self, object: LocalConnectionObject<'gc>) -> bool {
+        let len = self.connections.len();
+        self.connections
+            .retain(|_, connection| !connection.object.ptr_eq(object));
+        self.connections.len() != len
+    }
+
+    /// Retrieve the name of the connection an object is listening on.
+    pub fn connection_name(&self, object: LocalConnectionObject<'gc>) -> Option<&str> {
+        self.connections
+            .iter()
+            .find(|(_, connection)| connection.object.ptr_eq(object))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Queue a message for delivery at the end of the current frame.
+    pub fn send(&mut self, message: LocalConnectionMessage<'gc>) {
+        self.messages.push(message);
+    }
+
+    /// Deliver all queued messages, and tell each sender whether or not its
+    /// message was accepted.
+    ///
+    /// Messages sent while delivering are left for the next frame.
+    pub fn deliver_messages(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let messages = std::mem::take(&mut context.local_connections.messages);
+
+        for message in messages {
+            let receiver = context
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+                .cloned();
+
+            let delivered = match receiver {
+                Some(Connection {
+                    object: LocalConnectionObject::Avm1(object),
+                    domain,
+                }) => crate::avm1::globals::local_connection::receive_message(
+                    context,
+                    object,
+                    domain == message.sender_domain,
+                    &message.sender_domain,
+                    &message.method_name,
+                    message.arguments,
+                ),
+                Some(Connection {
+                    object: LocalConnectionObject::Avm2(object),
+                    domain,
+                }) => Avm2::receive_local_connection_message(
+                    context,
+                    object,
+                    domain == message.sender_domain,
+                    &message.sender_domain,
+                    &message.method_name,
+                    message.arguments,
+                ),
+                None => false,
+            };
+
+            match message.sender {
+                LocalConnectionObject::Avm1(object) => {
+                    crate::avm1::globals::local_connection::notify_status(
+                        context, object, delivered,
+                    )
+                }
+                LocalConnectionObject::Avm2(object) => {
+                    Avm2::dispatch_local_connection_status(context, object, delivered)
+                }
+            }
+        }
+    }
+}
+
+impl<'gc> Default for LocalConnections<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Determine the domain of a movie loaded from the given URL.
+///
+/// Movies that were not loaded from a web server, such as local files, all
+/// share the domain `localhost`.
+pub fn movie_domain(url: Option<&str>) -> String {
+    url.and_then(|url| Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| LOCAL_DOMAIN.to_string())
+}
+
+/// Qualify a connection name with the domain of the movie that uses it.
+///
+/// Names beginning with an underscore are shared between all domains and are
+/// left alone, as are names that already specify a domain. Connection names
+/// are case-insensitive.
+pub fn qualified_name(domain: &str, name: &str) -> String {
+    let name = name.to_lowercase();
+    if name.starts_with('_') || name.contains(':') {
+        name
+    } else {
+        format!("{}:{}", domain, name)
+    }
+}
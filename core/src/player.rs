@@ -1,29 +1,39 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::debug::VariableDumper;
+use crate::avm1::globals::context_menu;
 use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
 use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
 use crate::avm2::{Avm2, Domain as Avm2Domain};
-use crate::backend::input::{InputBackend, MouseCursor};
+use crate::backend::input::{InputBackend, MouseButton, MouseCursor};
 use crate::backend::locale::LocaleBackend;
-use crate::backend::navigator::{NavigatorBackend, RequestOptions};
+use crate::backend::navigator::{NavigatorBackend, OwnedFuture, RequestOptions};
 use crate::backend::storage::StorageBackend;
+use crate::backend::ui::UiBackend;
+use crate::backend::video::VideoBackend;
 use crate::backend::{
     audio::AudioBackend, log::LogBackend, render::Letterbox, render::RenderBackend,
+    render::StageQuality,
 };
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
-use crate::display_object::{EditText, MorphShape, MovieClip};
+use crate::context_menu::{BuiltInItemFlags, ContextMenuState};
+use crate::display_object::{
+    DirtyRegion, EditText, GotoSnapshotSettings, MorphShape, MovieClip, StreamDrift,
+};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
+use crate::gc_stats::{GcPacing, GcStats};
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::prelude::*;
 use crate::property_map::PropertyMap;
-use crate::tag_utils::SwfMovie;
+use crate::shape_cache::ShapeCache;
+use crate::tag_utils::{self, SwfMovie, SwfMovieStream};
 use crate::transform::TransformStack;
-use crate::vminterface::Instantiator;
+use crate::vminterface::{AvmType, Instantiator};
 use enumset::EnumSet;
 use gc_arena::{make_arena, ArenaParameters, Collect, GcCell};
 use instant::Instant;
@@ -59,6 +69,9 @@ struct GcRootData<'gc> {
 
     mouse_hovered_object: Option<DisplayObject<'gc>>, // TODO: Remove GcCell wrapped inside GcCell.
 
+    /// The object that the mouse button was pressed on, while it is held down.
+    mouse_pressed_object: Option<DisplayObject<'gc>>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -85,6 +98,12 @@ struct GcRootData<'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     external_interface: ExternalInterface<'gc>,
 
+    /// Local connections between movies, shared by both VMs.
+    local_connections: LocalConnections<'gc>,
+
+    /// The context menu that is being shown, if any.
+    current_context_menu: Option<ContextMenuState<'gc>>,
+
     /// A tracker for the current keyboard focused element
     focus_tracker: FocusTracker<'gc>,
 }
@@ -107,6 +126,8 @@ impl<'gc> GcRootData<'gc> {
         &mut Vec<EditText<'gc>>,
         &mut Timers<'gc>,
         &mut ExternalInterface<'gc>,
+        &mut LocalConnections<'gc>,
+        &mut Option<ContextMenuState<'gc>>,
     ) {
         (
             &mut self.levels,
@@ -120,6 +141,8 @@ impl<'gc> GcRootData<'gc> {
             &mut self.unbound_text_fields,
             &mut self.timers,
             &mut self.external_interface,
+            &mut self.local_connections,
+            &mut self.current_context_menu,
         )
     }
 }
@@ -134,6 +157,12 @@ type Input = Box<dyn InputBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Locale = Box<dyn LocaleBackend>;
 type Log = Box<dyn LogBackend>;
+type Ui = Box<dyn UiBackend>;
+type Video = Box<dyn VideoBackend>;
+
+/// A callback given the number of bytes of a movie that have been downloaded,
+/// and the total length of the movie if it is known.
+pub type LoadProgressCallback = Box<dyn Fn(u64, Option<u64>) + Send>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -150,15 +179,31 @@ pub struct Player {
 
     swf: Arc<SwfMovie>,
 
+    /// The root movie, while it is being loaded progressively.
+    root_movie_stream: Option<SwfMovieStream>,
+
     is_playing: bool,
+
+    /// Whether the stage has to be redrawn even if no display object has
+    /// changed, such as after `updateAfterEvent` or a resize.
     needs_render: bool,
 
+    /// The number of calls to `render` that were skipped because nothing on
+    /// the stage had changed.
+    skipped_frames: u64,
+
+    /// Whether `Stage.invalidate` has been called, so that the stage's
+    /// `render` event fires at the start of the next frame.
+    needs_redraw: bool,
+
     audio: Audio,
     renderer: Renderer,
     pub navigator: Navigator,
     input: Input,
     locale: Locale,
     log: Log,
+    ui: Ui,
+    video: Video,
     transform_stack: TransformStack,
     view_matrix: Matrix,
     inverse_view_matrix: Matrix,
@@ -168,8 +213,26 @@ pub struct Player {
     rng: SmallRng,
 
     gc_arena: GcArena,
+
+    /// When full garbage collections are done.
+    gc_pacing: GcPacing,
+
+    /// Statistics about the garbage-collected heap, as of the start of the
+    /// last update.
+    gc_stats: GcStats,
+
+    /// The number of frames run since the last full garbage collection.
+    frames_since_collect: u32,
+
+    /// Whether a movie has asked for a full garbage collection, which is done
+    /// at the end of the current update.
+    needs_collect: bool,
+
     background_color: Color,
 
+    /// The rendering quality of the stage.
+    quality: StageQuality,
+
     frame_rate: f64,
     frame_accumulator: f64,
 
@@ -181,10 +244,18 @@ pub struct Player {
 
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
+    is_right_mouse_down: bool,
+    is_middle_mouse_down: bool,
 
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
 
+    /// Whether or not a user input event is currently being handled.
+    ///
+    /// Certain actions, such as displaying dialogs, are only allowed in
+    /// response to user input.
+    is_user_gesture: bool,
+
     system: SystemProperties,
 
     /// The current instance ID. Used to generate default `instanceN` names.
@@ -197,6 +268,22 @@ pub struct Player {
     /// is raised. This defaults to 15 seconds but can be changed.
     max_execution_duration: Duration,
 
+    /// How movie clips keep snapshots of their timelines to speed up rewinds.
+    goto_snapshots: GotoSnapshotSettings,
+
+    /// The number of frames that gotos to earlier frames have replayed.
+    rewind_frames_replayed: u64,
+
+    /// The number of times text fields have laid out their text.
+    text_relayouts: u64,
+
+    /// The shapes registered by display objects at runtime.
+    shape_cache: ShapeCache,
+
+    /// A callback told how much of a movie has been downloaded, as its bytes
+    /// arrive.
+    load_progress_callback: Option<LoadProgressCallback>,
+
     /// Self-reference to ourselves.
     ///
     /// This is a weak reference that is upgraded and handed out in various
@@ -214,6 +301,8 @@ impl Player {
         storage: Storage,
         locale: Locale,
         log: Log,
+        ui: Ui,
+        video: Video,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let fake_movie = Arc::new(SwfMovie::empty(NEWEST_PLAYER_VERSION));
         let movie_width = 550;
@@ -224,9 +313,12 @@ impl Player {
             player_version: NEWEST_PLAYER_VERSION,
 
             swf: fake_movie.clone(),
+            root_movie_stream: None,
 
             is_playing: false,
             needs_render: true,
+            skipped_frames: 0,
+            needs_redraw: false,
 
             background_color: Color {
                 r: 255,
@@ -247,6 +339,7 @@ impl Player {
                         library: Library::default(),
                         levels: BTreeMap::new(),
                         mouse_hovered_object: None,
+                        mouse_pressed_object: None,
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -256,6 +349,8 @@ impl Player {
                         unbound_text_fields: Vec::new(),
                         timers: Timers::new(),
                         external_interface: ExternalInterface::new(),
+                        local_connections: LocalConnections::new(),
+                        current_context_menu: None,
                         focus_tracker: FocusTracker::new(gc_context),
                     },
                 ))
@@ -269,10 +364,14 @@ impl Player {
             viewport_width: movie_width,
             viewport_height: movie_height,
             letterbox: Letterbox::None,
+            quality: StageQuality::default(),
 
             mouse_pos: (Twips::new(0), Twips::new(0)),
             is_mouse_down: false,
+            is_right_mouse_down: false,
+            is_middle_mouse_down: false,
             mouse_cursor: MouseCursor::Arrow,
+            is_user_gesture: false,
 
             renderer,
             audio,
@@ -280,12 +379,23 @@ impl Player {
             input,
             locale,
             log,
+            ui,
+            video,
             self_reference: None,
             system: SystemProperties::default(),
             instance_counter: 0,
             time_til_next_timer: None,
             storage,
             max_execution_duration: Duration::from_secs(15),
+            goto_snapshots: Default::default(),
+            rewind_frames_replayed: 0,
+            text_relayouts: 0,
+            shape_cache: ShapeCache::new(),
+            load_progress_callback: None,
+            gc_pacing: Default::default(),
+            gc_stats: Default::default(),
+            frames_since_collect: 0,
+            needs_collect: false,
         };
 
         player.mutate_with_update_context(|context| {
@@ -320,7 +430,9 @@ impl Player {
     /// off.
     pub fn fetch_root_movie(&mut self, movie_url: &str, parameters: PropertyMap<String>) {
         self.mutate_with_update_context(|context| {
-            let fetch = context.navigator.fetch(movie_url, RequestOptions::get());
+            let fetch = context
+                .navigator
+                .fetch_body(movie_url, RequestOptions::get());
             let process = context.load_manager.load_root_movie(
                 context.player.clone().unwrap(),
                 fetch,
@@ -332,6 +444,72 @@ impl Player {
         });
     }
 
+    /// Begin loading the root movie progressively.
+    ///
+    /// The bytes of the movie are given to the player with
+    /// `push_root_movie_bytes` as they arrive. The movie begins to play as
+    /// soon as its first frame has loaded.
+    pub fn begin_root_movie_stream(
+        &mut self,
+        url: Option<String>,
+        parameters: PropertyMap<String>,
+    ) {
+        let mut stream = SwfMovieStream::new(url);
+        for (key, value) in parameters.iter() {
+            stream.parameters_mut().insert(key, value.to_owned(), false);
+        }
+        self.root_movie_stream = Some(stream);
+    }
+
+    /// Decode more bytes of the root movie.
+    pub fn push_root_movie_bytes(&mut self, chunk: &[u8]) -> Result<(), tag_utils::Error> {
+        let stream = self
+            .root_movie_stream
+            .as_mut()
+            .ok_or("The root movie is not being loaded")?;
+        let frames_loaded = stream.frames_loaded();
+        let was_complete = stream.is_complete();
+        stream.push_bytes(chunk)?;
+
+        // Hand the player more of the movie whenever another frame arrives,
+        // or once the rest of the movie has arrived.
+        if stream.frames_loaded() > frames_loaded || stream.is_complete() != was_complete {
+            if let Some(movie) = stream.movie() {
+                self.update_root_movie(Arc::new(movie));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finish loading the root movie, once all of its bytes have been pushed.
+    ///
+    /// The returned future completes with the full movie, which should then
+    /// be given to `update_root_movie`.
+    pub fn finish_root_movie_stream(&mut self) -> OwnedFuture<SwfMovie, tag_utils::Error> {
+        match self.root_movie_stream.take() {
+            Some(stream) => stream.finish(),
+            None => Box::pin(async { Err("The root movie is not being loaded".into()) }),
+        }
+    }
+
+    /// Give the player the root movie, or more of a root movie that is being
+    /// loaded progressively.
+    pub fn update_root_movie(&mut self, movie: Arc<SwfMovie>) {
+        if !self.swf.is_same_movie(&movie) {
+            self.set_root_movie(movie);
+            return;
+        }
+
+        self.swf = movie.clone();
+        self.mutate_with_update_context(|context| {
+            if let Some(root) = context.levels.get(&0).and_then(|root| root.as_movie_clip()) {
+                root.extend_movie(context.gc_context, movie);
+            }
+        });
+        self.preload();
+    }
+
     /// Change the root movie.
     ///
     /// This should only be called once, as it makes no attempt at removing
@@ -434,7 +612,7 @@ impl Player {
             let mut frame = 0;
             while frame < MAX_FRAMES_PER_TICK && self.frame_accumulator >= frame_time {
                 self.frame_accumulator -= frame_time;
-                self.run_frame();
+                self.run_synced_frame();
                 frame += 1;
             }
 
@@ -484,8 +662,29 @@ impl Player {
         self.is_playing = v;
     }
 
+    /// Whether anything on the stage has changed since it was last rendered,
+    /// or a redraw has been asked for.
     pub fn needs_render(&self) -> bool {
-        self.needs_render
+        self.needs_render || self.needs_redraw || self.is_stage_dirty()
+    }
+
+    /// Whether any display object has changed since the stage was last
+    /// rendered.
+    fn is_stage_dirty(&self) -> bool {
+        self.gc_arena.mutate(|_gc_context, gc_root| {
+            gc_root
+                .0
+                .read()
+                .levels
+                .values()
+                .any(|level| level.is_dirty_recursive())
+        })
+    }
+
+    /// The number of calls to `render` that were skipped because nothing on
+    /// the stage had changed.
+    pub fn skipped_frames(&self) -> u64 {
+        self.skipped_frames
     }
 
     pub fn movie_width(&self) -> u32 {
@@ -504,11 +703,21 @@ impl Player {
         self.viewport_width = width;
         self.viewport_height = height;
         self.build_matrices();
+        self.needs_render = true;
     }
 
     pub fn handle_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
 
+        self.is_user_gesture = matches!(
+            event,
+            PlayerEvent::KeyDown { .. }
+                | PlayerEvent::KeyUp { .. }
+                | PlayerEvent::MouseDown { .. }
+                | PlayerEvent::MouseUp { .. }
+                | PlayerEvent::TextInput { .. }
+        );
+
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::V,
@@ -573,8 +782,8 @@ impl Player {
 
         // Update mouse position from mouse events.
         if let PlayerEvent::MouseMove { x, y }
-        | PlayerEvent::MouseDown { x, y }
-        | PlayerEvent::MouseUp { x, y } = event
+        | PlayerEvent::MouseDown { x, y, .. }
+        | PlayerEvent::MouseUp { x, y, .. } = event
         {
             self.mouse_pos =
                 self.inverse_view_matrix * (Twips::from_pixels(x), Twips::from_pixels(y));
@@ -619,6 +828,18 @@ impl Player {
             });
         }
 
+        // Tab moves the focus through the tab order; Shift+Tab moves it back.
+        if let PlayerEvent::KeyDown {
+            key_code: KeyCode::Tab,
+        } = event
+        {
+            let reverse = self.input.is_key_down(KeyCode::Shift);
+            self.mutate_with_update_context(|context| {
+                let tracker = context.focus_tracker;
+                tracker.cycle(context, reverse);
+            });
+        }
+
         if let PlayerEvent::TextInput { codepoint } = event {
             self.mutate_with_update_context(|context| {
                 if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
@@ -627,6 +848,18 @@ impl Player {
             });
         }
 
+        // The mouse wheel scrolls the text field under the mouse.
+        if let PlayerEvent::MouseWheel { delta } = event {
+            self.mutate_with_update_context(|context| {
+                if let Some(text) = context.mouse_hovered_object.and_then(|o| o.as_edit_text()) {
+                    if text.is_mouse_wheel_enabled() {
+                        let scroll = text.scroll() as f64 - delta.lines().round();
+                        text.set_scroll(scroll, context.gc_context);
+                    }
+                }
+            });
+        }
+
         // Propagte clip events.
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
@@ -640,11 +873,17 @@ impl Player {
                     Some(ClipEvent::MouseMove),
                     Some(("Mouse", "onMouseMove", vec![])),
                 ),
-                PlayerEvent::MouseUp { .. } => (
+                PlayerEvent::MouseUp {
+                    button: MouseButton::Left,
+                    ..
+                } => (
                     Some(ClipEvent::MouseUp),
                     Some(("Mouse", "onMouseUp", vec![])),
                 ),
-                PlayerEvent::MouseDown { .. } => (
+                PlayerEvent::MouseDown {
+                    button: MouseButton::Left,
+                    ..
+                } => (
                     Some(ClipEvent::MouseDown),
                     Some(("Mouse", "onMouseDown", vec![])),
                 ),
@@ -677,42 +916,170 @@ impl Player {
             }
         });
 
-        let mut is_mouse_down = self.is_mouse_down;
+        let was_button_down = match event {
+            PlayerEvent::MouseUp { button, .. } => *self.mouse_button_down_mut(button),
+            _ => false,
+        };
         self.mutate_with_update_context(|context| {
             if let Some(node) = context.mouse_hovered_object {
                 if node.removed() {
                     context.mouse_hovered_object = None;
                 }
             }
+            if let Some(node) = context.mouse_pressed_object {
+                if node.removed() {
+                    context.mouse_pressed_object = None;
+                }
+            }
 
             match event {
-                PlayerEvent::MouseDown { .. } => {
-                    is_mouse_down = true;
+                PlayerEvent::MouseDown {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     needs_render = true;
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Press);
                     }
+                    context.mouse_pressed_object = context.mouse_hovered_object;
                 }
 
-                PlayerEvent::MouseUp { .. } => {
-                    is_mouse_down = false;
+                PlayerEvent::MouseUp {
+                    button: MouseButton::Left,
+                    ..
+                } => {
                     needs_render = true;
-                    if let Some(node) = context.mouse_hovered_object {
-                        node.handle_clip_event(context, ClipEvent::Release);
+                    let hovered = context.mouse_hovered_object;
+                    let pressed = context.mouse_pressed_object.take();
+                    let is_pressed = |node: DisplayObject<'_>| {
+                        pressed.map(|p| p.as_ptr()) == Some(node.as_ptr())
+                    };
+
+                    if let Some(node) = hovered {
+                        if is_pressed(node) || Self::is_tracked_as_menu(node) {
+                            node.handle_clip_event(context, ClipEvent::Release);
+                        }
+                    }
+
+                    if let Some(node) = pressed {
+                        if hovered.map(|h| h.as_ptr()) != Some(node.as_ptr()) {
+                            node.handle_clip_event(context, ClipEvent::ReleaseOutside);
+
+                            // The hovered object was not rolled over while the mouse was down.
+                            if let Some(node) = hovered {
+                                if !Self::is_tracked_as_menu(node) {
+                                    node.handle_clip_event(context, ClipEvent::RollOver);
+                                }
+                            }
+                        }
                     }
                 }
 
+                PlayerEvent::MouseDown {
+                    button: MouseButton::Right,
+                    ..
+                } => Self::open_context_menu(context),
+
                 _ => (),
             }
 
+            // AVM2 mouse events target the interactive object under the
+            // cursor, or the root if there is none.
+            let avm2_events: &[&'static str] = match event {
+                PlayerEvent::MouseDown { button, .. } => match button {
+                    MouseButton::Left => &["mouseDown"],
+                    MouseButton::Right => &["rightMouseDown"],
+                    MouseButton::Middle => &["middleMouseDown"],
+                },
+                PlayerEvent::MouseUp { button, .. } => match (button, was_button_down) {
+                    (MouseButton::Left, true) => &["mouseUp", "click"],
+                    (MouseButton::Left, false) => &["mouseUp"],
+                    (MouseButton::Right, true) => &["rightMouseUp", "rightClick"],
+                    (MouseButton::Right, false) => &["rightMouseUp"],
+                    (MouseButton::Middle, true) => &["middleMouseUp", "middleClick"],
+                    (MouseButton::Middle, false) => &["middleMouseUp"],
+                },
+                PlayerEvent::MouseWheel { .. } => &["mouseWheel"],
+                _ => &[],
+            };
+            let wheel_delta = match event {
+                PlayerEvent::MouseWheel { delta } => delta.lines().round() as i32,
+                _ => 0,
+            };
+            if let Some(root) = context.levels.get(&0).copied() {
+                let target = if avm2_events.is_empty() {
+                    root
+                } else {
+                    let mouse_pos = *context.mouse_position;
+                    Self::mouse_pick_interactive(context, root, mouse_pos, AvmType::Avm2)
+                        .unwrap_or(root)
+                };
+                let is_double_click =
+                    avm2_events.contains(&"click") && Self::is_double_click(context, target);
+                let double_click_events: &[&'static str] = if is_double_click {
+                    &["doubleClick"]
+                } else {
+                    &[]
+                };
+                for event_type in avm2_events.iter().chain(double_click_events) {
+                    if let Err(e) =
+                        Avm2::dispatch_mouse_event(context, target, event_type, wheel_delta)
+                    {
+                        log::error!("Unhandled AVM2 exception in {} handler: {}", event_type, e);
+                    }
+                }
+            }
+
             Self::run_actions(context);
         });
-        self.is_mouse_down = is_mouse_down;
+        match event {
+            PlayerEvent::MouseDown { button, .. } => *self.mouse_button_down_mut(button) = true,
+            PlayerEvent::MouseUp { button, .. } => *self.mouse_button_down_mut(button) = false,
+            _ => (),
+        }
+        self.is_user_gesture = false;
         if needs_render {
             self.needs_render = true;
         }
     }
 
+    /// Record a click on `target`, and return whether it makes a double click.
+    ///
+    /// The second click has to come within the input backend's threshold of
+    /// the first, and only counts if `target` has `doubleClickEnabled` set.
+    fn is_double_click<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+    ) -> bool {
+        let now = context.navigator.time_since_launch();
+        let threshold = Duration::from_millis(context.input.double_click_threshold_ms().into());
+        let is_double_click = target.double_click_enabled()
+            && target
+                .last_click_time()
+                .and_then(|last_click_time| now.checked_sub(last_click_time))
+                .map_or(false, |elapsed| elapsed <= threshold);
+
+        // The click that ends a double click can't start another one.
+        let last_click_time = if is_double_click { None } else { Some(now) };
+        target.set_last_click_time(context.gc_context, last_click_time);
+        is_double_click
+    }
+
+    /// Run the item at `index` of the context menu that was last passed to
+    /// `UiBackend::build_context_menu`, as the user chose it.
+    ///
+    /// Disabled items are ignored. The menu is closed either way.
+    pub fn on_context_menu_select(&mut self, index: usize) {
+        self.mutate_with_update_context(|context| {
+            let menu = context.current_context_menu.take();
+            if let Some(callback) = menu.and_then(|menu| menu.callback(index)) {
+                callback.run(context);
+                *context.needs_render = true;
+            }
+            Self::run_actions(context);
+        });
+    }
+
     /// Update dragged object, if any.
     fn update_drag(&mut self) {
         let mouse_pos = self.mouse_pos;
@@ -741,13 +1108,148 @@ impl Player {
         });
     }
 
+    /// Whether the given display object is a button tracked as a menu item.
+    ///
+    /// Menu buttons react to the mouse being dragged over them, even if the
+    /// mouse button was pressed elsewhere.
+    fn is_tracked_as_menu(node: DisplayObject<'_>) -> bool {
+        node.as_button()
+            .map(|button| button.is_tracked_as_menu())
+            .unwrap_or(false)
+    }
+
+    /// Whether the given mouse button is held down.
+    fn mouse_button_down_mut(&mut self, button: MouseButton) -> &mut bool {
+        match button {
+            MouseButton::Left => &mut self.is_mouse_down,
+            MouseButton::Right => &mut self.is_right_mouse_down,
+            MouseButton::Middle => &mut self.is_middle_mouse_down,
+        }
+    }
+
+    /// Build the context menu for a right click on the object under the
+    /// mouse, and pass it to the UI backend to be shown.
+    ///
+    /// The `onSelect` of an AVM1 menu is called, and an AVM2 menu is sent
+    /// `menuSelect`, before the menu is built, so that it can update its items.
+    fn open_context_menu<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let root = match context.levels.get(&0) {
+            Some(root) => *root,
+            None => return,
+        };
+        let mouse_pos = *context.mouse_position;
+        if let Avm2Value::Object(_) = root.object2() {
+            let target = Self::mouse_pick_interactive(context, root, mouse_pos, AvmType::Avm2)
+                .unwrap_or(root);
+            if let Some(menu) = Avm2::make_context_menu_state(context, target) {
+                context.ui.build_context_menu(menu.info());
+                *context.current_context_menu = Some(menu);
+            }
+            return;
+        }
+
+        let target =
+            Self::mouse_pick_interactive(context, root, mouse_pos, AvmType::Avm1).unwrap_or(root);
+
+        let mut activation = Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[Context Menu]"),
+        );
+        let menu = if let Value::Object(target_object) = target.object() {
+            let menu = Self::avm1_context_menu(target, &mut activation);
+            if let Some(menu) = menu {
+                let args = [target_object.into(), menu.into()];
+                if let Err(e) = menu.call_method("onSelect", &args, &mut activation) {
+                    crate::avm1::root_error_handler(&mut activation, e);
+                }
+            }
+
+            match context_menu::make_context_menu_state(menu, target_object, &mut activation) {
+                Ok(menu) => menu,
+                Err(e) => {
+                    crate::avm1::root_error_handler(&mut activation, e);
+                    return;
+                }
+            }
+        } else {
+            let mut menu = ContextMenuState::new();
+            if let Some(root) = root.as_movie_clip() {
+                menu.build_builtin_items(BuiltInItemFlags::default(), root);
+            }
+            menu
+        };
+
+        activation.context.ui.build_context_menu(menu.info());
+        *activation.context.current_context_menu = Some(menu);
+    }
+
+    /// Finds the AVM1 `ContextMenu` used by a display object, which is its own
+    /// `menu` or that of its closest ancestor that has one.
+    fn avm1_context_menu<'gc>(
+        target: DisplayObject<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Option<Object<'gc>> {
+        let mut node = Some(target);
+        while let Some(display_object) = node {
+            if let Value::Object(object) = display_object.object() {
+                if let Ok(Value::Object(menu)) = object.get("menu", activation) {
+                    return Some(menu);
+                }
+            }
+            node = display_object.parent();
+        }
+        None
+    }
+
+    /// Finds the innermost interactive object of the given AVM under a stage
+    /// position.
+    ///
+    /// Objects that aren't interactive, such as shapes, are hit through the
+    /// interactive object that contains them.
+    fn mouse_pick_interactive<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        node: DisplayObject<'gc>,
+        point: (Twips, Twips),
+        avm_type: AvmType,
+    ) -> Option<DisplayObject<'gc>> {
+        if !node.visible() || !node.hit_test_scroll_rect(point) {
+            return None;
+        }
+
+        if let Some(ctr) = node.as_container() {
+            for child in ctr.iter_render_list().rev() {
+                if child.clip_depth() > 0 && child.allow_as_mask() {
+                    continue;
+                }
+
+                let result = Self::mouse_pick_interactive(context, child, point, avm_type);
+                if result.is_some() && ctr.hit_test_masks(context, child.depth(), point) {
+                    return result;
+                }
+            }
+        }
+
+        let is_interactive = node.as_movie_clip().is_some()
+            || node.as_button().is_some()
+            || node.as_edit_text().is_some();
+        let has_object = match avm_type {
+            AvmType::Avm1 => matches!(node.object(), Value::Object(_)),
+            AvmType::Avm2 => matches!(node.object2(), Avm2Value::Object(_)),
+        };
+        if is_interactive && has_object && node.hit_test_shape(context, point) {
+            Some(node)
+        } else {
+            None
+        }
+    }
+
     /// Checks to see if a recent update has caused the current mouse hover
     /// node to change.
+    ///
+    /// While the mouse button is held down, only the pressed object and menu
+    /// buttons receive drag events instead of roll overs.
     fn update_roll_over(&mut self) -> bool {
-        // TODO: While the mouse is down, maintain the hovered node.
-        if self.is_mouse_down {
-            return false;
-        }
+        let is_mouse_down = self.is_mouse_down;
         let mouse_pos = self.mouse_pos;
 
         let mut new_cursor = self.mouse_cursor;
@@ -765,18 +1267,32 @@ impl Player {
             let cur_hovered = context.mouse_hovered_object;
 
             if cur_hovered.map(|d| d.as_ptr()) != new_hovered.map(|d| d.as_ptr()) {
+                let pressed = context.mouse_pressed_object;
+                let receives_drag = |node: DisplayObject<'_>| {
+                    pressed.map(|p| p.as_ptr()) == Some(node.as_ptr())
+                        || Self::is_tracked_as_menu(node)
+                };
+
                 // RollOut of previous node.
                 if let Some(node) = cur_hovered {
                     if !node.removed() {
-                        node.handle_clip_event(context, ClipEvent::RollOut);
+                        if !is_mouse_down {
+                            node.handle_clip_event(context, ClipEvent::RollOut);
+                        } else if receives_drag(node) {
+                            node.handle_clip_event(context, ClipEvent::DragOut);
+                        }
                     }
                 }
 
-                // RollOver on new node.I still
+                // RollOver on new node.
                 new_cursor = MouseCursor::Arrow;
                 if let Some(node) = new_hovered {
                     new_cursor = node.mouse_cursor();
-                    node.handle_clip_event(context, ClipEvent::RollOver);
+                    if !is_mouse_down {
+                        node.handle_clip_event(context, ClipEvent::RollOver);
+                    } else if receives_drag(node) {
+                        node.handle_clip_event(context, ClipEvent::DragOver);
+                    }
                 }
 
                 context.mouse_hovered_object = new_hovered;
@@ -788,6 +1304,14 @@ impl Player {
             }
         });
 
+        // AVM2 objects don't take part in the hover tracking above, so the
+        // cursor over them is checked on every update.
+        if let Some(cursor) =
+            self.mutate_with_update_context(|context| Self::avm2_mouse_cursor(context, mouse_pos))
+        {
+            new_cursor = cursor;
+        }
+
         // Update mouse cursor if it has changed.
         if new_cursor != self.mouse_cursor {
             self.mouse_cursor = new_cursor;
@@ -797,6 +1321,42 @@ impl Player {
         hover_changed
     }
 
+    /// The cursor to show over the AVM2 content under the mouse.
+    ///
+    /// Sprites in button mode, and everything in them, show the hand cursor
+    /// unless they turned it off with `useHandCursor`. Returns `None` if the
+    /// root movie doesn't use AVM2, or if the mouse is over an object whose
+    /// cursor is set by the hover tracking.
+    fn avm2_mouse_cursor<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Option<MouseCursor> {
+        if context.mouse_hovered_object.is_some() {
+            return None;
+        }
+
+        let root = context.levels.get(&0).copied()?;
+        if !matches!(root.object2(), Avm2Value::Object(_)) {
+            return None;
+        }
+
+        let mut node = Self::mouse_pick_interactive(context, root, point, AvmType::Avm2);
+        while let Some(display_object) = node {
+            if let Some(mc) = display_object.as_movie_clip() {
+                if mc.button_mode() {
+                    return Some(if mc.use_hand_cursor() {
+                        MouseCursor::Hand
+                    } else {
+                        MouseCursor::Arrow
+                    });
+                }
+            }
+            node = display_object.parent();
+        }
+
+        Some(MouseCursor::Arrow)
+    }
+
     /// Preload the first movie in the player.
     ///
     /// This should only be called once. Further movie loads should preload the
@@ -822,6 +1382,16 @@ impl Player {
 
     pub fn run_frame(&mut self) {
         self.update(|update_context| {
+            // The stage was invalidated since the last frame, so its `render`
+            // listeners are told before it is redrawn.
+            if std::mem::take(update_context.needs_redraw) {
+                Avm2::dispatch_render_event(update_context);
+            }
+
+            // Sounds that finished since the last frame are completed before
+            // this one runs.
+            Avm1::update_playing_sounds(update_context);
+
             // TODO: In what order are levels run?
             // NOTE: We have to copy all the layer pointers into a separate list
             // because level updates can create more levels, which we don't
@@ -831,11 +1401,113 @@ impl Player {
             for level in levels {
                 level.run_frame(update_context);
             }
+
+            if let Err(e) = Avm2::generate_sound_samples(update_context) {
+                log::error!("Unhandled AVM2 exception in sampleData handler: {}", e);
+            }
+
+            LocalConnections::deliver_messages(update_context);
+            Avm2::poll_workers(update_context);
         });
-        self.needs_render = true;
+
+        self.frames_since_collect += 1;
+        if self.is_collect_due() {
+            self.collect_garbage();
+        }
+    }
+
+    /// Whether the garbage collection pacing calls for a full collection.
+    fn is_collect_due(&self) -> bool {
+        let frames_due = match self.gc_pacing.frame_interval {
+            Some(interval) => self.frames_since_collect >= interval,
+            None => false,
+        };
+        let allocation_due = match self.gc_pacing.allocation_threshold {
+            Some(threshold) => self.gc_stats().allocated_since_last_collect() >= threshold,
+            None => false,
+        };
+        frames_due || allocation_due
     }
 
+    /// Collect all garbage now, instead of bit by bit as the player runs.
+    pub fn collect_garbage(&mut self) {
+        let start = Instant::now();
+        let allocated_before = self.gc_arena.total_allocated();
+
+        // A collection may already be in progress, which won't free anything
+        // that became unreachable after it started. Finish it, then do one
+        // more from the start.
+        self.gc_arena.collect_all();
+        self.gc_arena.collect_all();
+
+        let allocated_after = self.gc_arena.total_allocated();
+        self.gc_stats = GcStats {
+            total_allocated: allocated_after,
+            allocated_after_last_collect: allocated_after,
+            freed_by_last_collect: allocated_before.saturating_sub(allocated_after),
+            last_collect_time: start.elapsed(),
+            full_collects: self.gc_stats.full_collects + 1,
+        };
+        self.frames_since_collect = 0;
+    }
+
+    /// Get statistics about the garbage-collected heap.
+    pub fn gc_stats(&self) -> GcStats {
+        GcStats {
+            total_allocated: self.gc_arena.total_allocated(),
+            ..self.gc_stats
+        }
+    }
+
+    pub fn gc_pacing(&self) -> GcPacing {
+        self.gc_pacing
+    }
+
+    /// Set when full garbage collections are done.
+    pub fn set_gc_pacing(&mut self, pacing: GcPacing) {
+        self.gc_pacing = pacing;
+    }
+
+    /// Runs a frame, holding or skipping a frame if the root timeline has
+    /// drifted from its stream sound.
+    ///
+    /// Like Flash, this lets stream sounds drive the frame rate. Hypothetical
+    /// drift is ignored, since there is no audio to keep up with.
+    fn run_synced_frame(&mut self) {
+        match self.stream_drift() {
+            Some(drift) if !drift.is_hypothetical && drift.frames >= 1.0 => {
+                // The timeline is ahead of the audio; hold this frame.
+            }
+            Some(drift) if !drift.is_hypothetical && drift.frames <= -1.0 => {
+                // The timeline is behind the audio; skip a frame to catch up.
+                self.run_frame();
+                self.run_frame();
+            }
+            _ => self.run_frame(),
+        }
+    }
+
+    /// How far the root timeline has drifted from the stream sound playing on
+    /// it, if any.
+    pub fn stream_drift(&mut self) -> Option<StreamDrift> {
+        let frame_rate = self.frame_rate;
+        self.mutate_with_update_context(|context| {
+            let root = context.levels.get(&0)?.as_movie_clip()?;
+            root.audio_stream_drift(context, frame_rate)
+        })
+    }
+
+    /// Render the stage, if anything on it has changed since it was last
+    /// rendered.
+    ///
+    /// The renderer is told which area of the viewport has changed before
+    /// the frame begins.
     pub fn render(&mut self) {
+        if !self.needs_render() {
+            self.skipped_frames += 1;
+            return;
+        }
+
         let view_bounds = BoundingBox {
             x_min: Twips::new(0),
             y_min: Twips::new(0),
@@ -844,9 +1516,34 @@ impl Player {
             valid: true,
         };
 
+        let needs_render = self.needs_render;
+        let dirty_region = self.gc_arena.mutate(|gc_context, gc_root| {
+            let mut region = DirtyRegion::default();
+            for level in gc_root.0.read().levels.values() {
+                region.union(&level.take_dirty_region(gc_context, false));
+            }
+            region
+        });
+        let dirty_bounds = dirty_region.bounds.transform(&self.view_matrix);
+        if needs_render || dirty_region.whole_stage || !dirty_bounds.valid {
+            self.renderer.set_dirty_region(None);
+        } else {
+            self.renderer.set_dirty_region(Some(&swf::Rectangle {
+                x_min: dirty_bounds.x_min,
+                x_max: dirty_bounds.x_max,
+                y_min: dirty_bounds.y_min,
+                y_max: dirty_bounds.y_max,
+            }));
+        }
+
         self.renderer.begin_frame(self.background_color.clone());
 
-        let (renderer, transform_stack) = (&mut self.renderer, &mut self.transform_stack);
+        let view_scale = f64::from(self.view_matrix.a);
+        let (renderer, transform_stack, shape_cache) = (
+            &mut self.renderer,
+            &mut self.transform_stack,
+            &mut self.shape_cache,
+        );
 
         transform_stack.push(&crate::transform::Transform {
             matrix: self.view_matrix,
@@ -857,14 +1554,16 @@ impl Player {
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
                 library: &root_data.library,
+                shape_cache,
                 transform_stack,
                 view_bounds,
+                view_scale,
                 clip_depth_stack: vec![],
                 allow_mask: true,
             };
 
             for (_depth, level) in root_data.levels.iter() {
-                level.render(&mut render_context);
+                level.render_with_blend_mode(&mut render_context);
             }
         });
         transform_stack.pop();
@@ -911,6 +1610,30 @@ impl Player {
         &self.locale
     }
 
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn storage_mut(&mut self) -> &mut dyn StorageBackend {
+        self.storage.deref_mut()
+    }
+
+    pub fn ui(&self) -> &Ui {
+        &self.ui
+    }
+
+    pub fn ui_mut(&mut self) -> &mut dyn UiBackend {
+        self.ui.deref_mut()
+    }
+
+    pub fn video(&self) -> &Video {
+        &self.video
+    }
+
+    pub fn video_mut(&mut self) -> &mut dyn VideoBackend {
+        self.video.deref_mut()
+    }
+
     fn run_actions<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(actions) = context.action_queue.pop_action() {
@@ -1067,6 +1790,8 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        self.gc_stats.total_allocated = self.gc_arena.total_allocated();
+
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
         let (
@@ -1081,14 +1806,24 @@ impl Player {
             mouse_position,
             stage_width,
             stage_height,
+            quality,
             player,
             system_properties,
             instance_counter,
             storage,
             locale,
             logging,
+            ui,
+            video,
             needs_render,
+            needs_redraw,
+            needs_collect,
+            gc_stats,
             max_execution_duration,
+            goto_snapshots,
+            rewind_frames_replayed,
+            text_relayouts,
+            is_user_gesture,
         ) = (
             self.player_version,
             &self.swf,
@@ -1101,19 +1836,30 @@ impl Player {
             &self.mouse_pos,
             Twips::from_pixels(self.movie_width.into()),
             Twips::from_pixels(self.movie_height.into()),
+            &mut self.quality,
             self.self_reference.clone(),
             &mut self.system,
             &mut self.instance_counter,
             self.storage.deref_mut(),
             self.locale.deref_mut(),
             self.log.deref_mut(),
+            self.ui.deref_mut(),
+            self.video.deref_mut(),
             &mut self.needs_render,
+            &mut self.needs_redraw,
+            &mut self.needs_collect,
+            &self.gc_stats,
             self.max_execution_duration,
+            self.goto_snapshots,
+            &mut self.rewind_frames_replayed,
+            &mut self.text_relayouts,
+            self.is_user_gesture,
         );
 
         self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
+            let mouse_pressed_object = root_data.mouse_pressed_object;
             let focus_tracker = root_data.focus_tracker;
             let (
                 levels,
@@ -1127,6 +1873,8 @@ impl Player {
                 unbound_text_fields,
                 timers,
                 external_interface,
+                local_connections,
+                current_context_menu,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1143,9 +1891,11 @@ impl Player {
                 gc_context,
                 levels,
                 mouse_hovered_object,
+                mouse_pressed_object,
                 mouse_position,
                 drag_object,
                 stage_size: (stage_width, stage_height),
+                quality,
                 system_prototypes: avm1.prototypes().clone(),
                 player,
                 load_manager,
@@ -1154,22 +1904,34 @@ impl Player {
                 storage,
                 locale,
                 log: logging,
+                ui,
+                video,
                 shared_objects,
                 unbound_text_fields,
                 timers,
                 needs_render,
+                needs_redraw,
+                needs_collect,
+                gc_stats,
                 avm1,
                 avm2,
                 external_interface,
+                local_connections,
+                current_context_menu,
                 update_start: Instant::now(),
                 max_execution_duration,
+                goto_snapshots,
+                rewind_frames_replayed,
+                text_relayouts,
                 focus_tracker,
+                is_user_gesture,
             };
 
             let ret = f(&mut update_context);
 
-            // Hovered object may have been updated; copy it back to the GC root.
+            // Hovered and pressed objects may have been updated; copy them back to the GC root.
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
+            root_data.mouse_pressed_object = update_context.mouse_pressed_object;
             ret
         })
     }
@@ -1214,6 +1976,9 @@ impl Player {
 
         // GC
         self.gc_arena.collect_debt();
+        if std::mem::take(&mut self.needs_collect) {
+            self.collect_garbage();
+        }
 
         rval
     }
@@ -1226,6 +1991,9 @@ impl Player {
             for so in shared_objects.values() {
                 let _ = crate::avm1::globals::shared_object::flush(&mut activation, *so, &[]);
             }
+            drop(activation);
+
+            Avm2::flush_shared_objects(context);
         });
     }
 
@@ -1262,6 +2030,29 @@ impl Player {
         })
     }
 
+    /// Share a `ByteArray` holding the given bytes with any background
+    /// workers, as a shared property of the primordial worker.
+    pub fn set_worker_shared_byte_array(&mut self, key: &str, bytes: Vec<u8>) {
+        self.update(|context| Avm2::set_worker_shared_byte_array(context, key, bytes));
+    }
+
+    /// Block until every message channel that this player receives from
+    /// holds a message, or until `timeout` has passed.
+    ///
+    /// Background workers run on their own schedule, so this lets a host
+    /// wait for their replies before running the frame that should see them.
+    /// Returns `false` if the messages did not all arrive in time.
+    pub fn wait_for_worker_messages(&mut self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        let channels =
+            self.mutate_with_update_context(|context| context.avm2.receiving_worker_channels());
+
+        channels.iter().all(|channel| {
+            let remaining = timeout.checked_sub(start.elapsed()).unwrap_or_default();
+            channel.wait_for_message(remaining)
+        })
+    }
+
     pub fn log_backend(&self) -> &Log {
         &self.log
     }
@@ -1273,6 +2064,50 @@ impl Player {
     pub fn set_max_execution_duration(&mut self, max_execution_duration: Duration) {
         self.max_execution_duration = max_execution_duration
     }
+
+    pub fn goto_snapshots(&self) -> GotoSnapshotSettings {
+        self.goto_snapshots
+    }
+
+    /// Set how movie clips keep snapshots of their timelines, which lets
+    /// gotos to earlier frames skip replaying the whole timeline.
+    pub fn set_goto_snapshots(&mut self, goto_snapshots: GotoSnapshotSettings) {
+        self.goto_snapshots = goto_snapshots
+    }
+
+    /// The number of frames that gotos to earlier frames have replayed, from
+    /// the start of a timeline or from its last snapshot.
+    pub fn rewind_frames_replayed(&self) -> u64 {
+        self.rewind_frames_replayed
+    }
+
+    /// The number of times text fields have laid out their text again, after
+    /// a change to their text, formatting or size.
+    pub fn text_relayouts(&self) -> u64 {
+        self.text_relayouts
+    }
+
+    /// The shapes registered by display objects at runtime, such as drawing
+    /// API content.
+    pub fn shape_cache(&self) -> &ShapeCache {
+        &self.shape_cache
+    }
+
+    /// Set a callback to be told how much of a movie has been downloaded, as
+    /// its bytes arrive.
+    ///
+    /// This covers both the root movie and movies loaded by it.
+    pub fn set_load_progress_callback(&mut self, callback: LoadProgressCallback) {
+        self.load_progress_callback = Some(callback);
+    }
+
+    /// Tell the load progress callback how much of a movie has been
+    /// downloaded.
+    pub fn report_load_progress(&self, bytes_loaded: u64, bytes_total: Option<u64>) {
+        if let Some(callback) = &self.load_progress_callback {
+            callback(bytes_loaded, bytes_total);
+        }
+    }
 }
 
 pub struct DragObject<'gc> {
@@ -0,0 +1,267 @@
+//! Sharing of tessellated shapes between display objects.
+//!
+//! Shapes from the timeline are registered once per character, but shapes
+//! built at runtime (drawing API content, text borders and decorations,
+//! 9-sliced graphics) used to be registered by every display object that
+//! showed them, and again whenever they were rebuilt. The `ShapeCache` hands
+//! out one render handle per distinct shape instead, so thousands of sprites
+//! drawing the same thing are only tessellated once, and a drawing that is
+//! cleared and redrawn identically is not tessellated again.
+
+use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::bounding_box::BoundingBox;
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use fnv::{FnvHashMap, FnvHasher};
+use std::cell::RefCell;
+use std::fmt;
+use std::hash::Hasher;
+use std::rc::{Rc, Weak};
+use swf::{FillStyle, LineStyle};
+
+/// Shapes that are no longer used by anything, and the hashes they were
+/// cached under.
+type Released = Rc<RefCell<Vec<(u64, ShapeHandle)>>>;
+
+/// The registered shapes that are in use by display objects, keyed by their
+/// contents.
+///
+/// The render handle of a shape stays valid for as long as something holds a
+/// `CachedShape` for it. Once the last one is dropped, the handle is recycled
+/// for the next new shape with `RenderBackend::replace_shape`, so render
+/// backends never see more shapes than are in use at once.
+#[derive(Default)]
+pub struct ShapeCache {
+    /// The shapes in use, by the hash of their geometry.
+    shapes: FnvHashMap<u64, Vec<Weak<CachedShapeData>>>,
+
+    /// Shapes that have been dropped since the cache last looked.
+    released: Released,
+
+    /// Render handles that no shape is using, ready to be reused.
+    free_handles: Vec<ShapeHandle>,
+}
+
+impl ShapeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cached shape for the given contents, registering it with the
+    /// renderer if nothing is using it yet.
+    pub fn register(
+        &mut self,
+        renderer: &mut dyn RenderBackend,
+        shape: DistilledShape,
+    ) -> CachedShape {
+        self.replace(renderer, None, shape)
+    }
+
+    /// Get the cached shape for the new contents of something that was using
+    /// `old`.
+    ///
+    /// If the new contents aren't cached, `old` is given up before the new
+    /// shape is registered, so that its handle can be reused when nothing
+    /// else is using it.
+    pub fn replace(
+        &mut self,
+        renderer: &mut dyn RenderBackend,
+        old: Option<CachedShape>,
+        shape: DistilledShape,
+    ) -> CachedShape {
+        let hash = hash_shape(&shape);
+        let key = ShapeKey::from(&shape);
+        if let Some(cached) = self.get(hash, &key) {
+            return cached;
+        }
+
+        drop(old);
+        self.collect_released();
+
+        let handle = match self.free_handles.pop() {
+            Some(handle) => {
+                renderer.replace_shape(shape, handle);
+                handle
+            }
+            None => renderer.register_shape(shape),
+        };
+        let data = Rc::new(CachedShapeData {
+            handle,
+            hash,
+            key,
+            released: self.released.clone(),
+        });
+        self.shapes
+            .entry(hash)
+            .or_default()
+            .push(Rc::downgrade(&data));
+        CachedShape(data)
+    }
+
+    /// The number of distinct shapes in use.
+    pub fn len(&self) -> usize {
+        self.shapes
+            .values()
+            .flatten()
+            .filter(|shape| shape.strong_count() > 0)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, hash: u64, key: &ShapeKey) -> Option<CachedShape> {
+        self.shapes
+            .get(&hash)?
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|shape| shape.key == *key)
+            .map(CachedShape)
+    }
+
+    /// Forget the shapes that have been dropped, and make their handles
+    /// available for reuse.
+    fn collect_released(&mut self) {
+        for (hash, handle) in self.released.borrow_mut().drain(..) {
+            if let Some(bucket) = self.shapes.get_mut(&hash) {
+                bucket.retain(|shape| shape.strong_count() > 0);
+                if bucket.is_empty() {
+                    self.shapes.remove(&hash);
+                }
+            }
+            self.free_handles.push(handle);
+        }
+    }
+}
+
+/// A shape registered through the `ShapeCache`.
+///
+/// Cloning a `CachedShape` shares the registered shape; its handle is given
+/// back to the cache when the last clone is dropped.
+#[derive(Clone, Debug)]
+pub struct CachedShape(Rc<CachedShapeData>);
+
+impl CachedShape {
+    pub fn handle(&self) -> ShapeHandle {
+        self.0.handle
+    }
+}
+
+struct CachedShapeData {
+    handle: ShapeHandle,
+    hash: u64,
+    key: ShapeKey,
+    released: Released,
+}
+
+impl fmt::Debug for CachedShapeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedShapeData")
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl Drop for CachedShapeData {
+    fn drop(&mut self) {
+        self.released.borrow_mut().push((self.hash, self.handle));
+    }
+}
+
+/// The contents of a cached shape, used to tell shapes with the same hash
+/// apart.
+#[derive(PartialEq)]
+struct ShapeKey {
+    paths: Vec<PathKey>,
+    shape_bounds: BoundingBox,
+    edge_bounds: BoundingBox,
+}
+
+#[derive(PartialEq)]
+enum PathKey {
+    Stroke {
+        style: LineStyle,
+        is_closed: bool,
+        commands: Vec<DrawCommand>,
+    },
+    Fill {
+        style: FillStyle,
+        commands: Vec<DrawCommand>,
+    },
+}
+
+impl From<&DistilledShape<'_>> for ShapeKey {
+    fn from(shape: &DistilledShape<'_>) -> Self {
+        let paths = shape
+            .paths
+            .iter()
+            .map(|path| match path {
+                DrawPath::Stroke {
+                    style,
+                    is_closed,
+                    commands,
+                } => PathKey::Stroke {
+                    style: (*style).clone(),
+                    is_closed: *is_closed,
+                    commands: commands.clone(),
+                },
+                DrawPath::Fill { style, commands } => PathKey::Fill {
+                    style: (*style).clone(),
+                    commands: commands.clone(),
+                },
+            })
+            .collect();
+        Self {
+            paths,
+            shape_bounds: shape.shape_bounds.clone(),
+            edge_bounds: shape.edge_bounds.clone(),
+        }
+    }
+}
+
+/// Hash the geometry of a shape.
+///
+/// Styles are left out, as shapes with the same geometry but different styles
+/// are rare; they are told apart by comparing their `ShapeKey`s.
+fn hash_shape(shape: &DistilledShape) -> u64 {
+    let mut hasher = FnvHasher::default();
+    for path in &shape.paths {
+        let commands = match path {
+            DrawPath::Stroke {
+                is_closed,
+                commands,
+                ..
+            } => {
+                hasher.write_u8(if *is_closed { 2 } else { 1 });
+                commands
+            }
+            DrawPath::Fill { commands, .. } => {
+                hasher.write_u8(0);
+                commands
+            }
+        };
+        hasher.write_usize(commands.len());
+        for command in commands {
+            match *command {
+                DrawCommand::MoveTo { x, y } => {
+                    hasher.write_u8(0);
+                    hasher.write_i32(x.get());
+                    hasher.write_i32(y.get());
+                }
+                DrawCommand::LineTo { x, y } => {
+                    hasher.write_u8(1);
+                    hasher.write_i32(x.get());
+                    hasher.write_i32(y.get());
+                }
+                DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                    hasher.write_u8(2);
+                    hasher.write_i32(x1.get());
+                    hasher.write_i32(y1.get());
+                    hasher.write_i32(x2.get());
+                    hasher.write_i32(y2.get());
+                }
+            }
+        }
+    }
+    hasher.finish()
+}
@@ -1,7 +1,9 @@
 use crate::bounding_box::BoundingBox;
 use fnv::FnvHashMap;
 use smallvec::SmallVec;
+use std::cell::RefCell;
 use std::num::NonZeroU32;
+use std::ops::Range;
 use swf::{CharacterId, FillStyle, LineStyle, Matrix, Shape, ShapeRecord, Twips};
 
 pub fn calculate_shape_bounds(shape_records: &[swf::ShapeRecord]) -> swf::Rectangle {
@@ -599,6 +601,112 @@ mod tests {
         }
     }
 
+    /// The cached hit test gives the same result as walking the shape records,
+    /// for a detailed fill with a hole and a stroked layer on top of it.
+    #[test]
+    fn hit_test_cache() {
+        let style_change = |move_to: (i32, i32), fill_style_0, fill_style_1, line_style| {
+            ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some((Twips::new(move_to.0), Twips::new(move_to.1))),
+                fill_style_0,
+                fill_style_1,
+                line_style,
+                new_styles: None,
+            })
+        };
+
+        // A star with 64 points, with alternately straight and curved sides.
+        let point = |i: usize, radius: f64| {
+            let angle = i as f64 * std::f64::consts::PI / 64.0;
+            (
+                (4000.0 + radius * angle.cos()) as i32,
+                (4000.0 + radius * angle.sin()) as i32,
+            )
+        };
+        let mut records = vec![style_change(point(0, 3000.0), None, Some(1), None)];
+        let mut last = point(0, 3000.0);
+        for i in 1..=128 {
+            let next = point(i, if i % 2 == 0 { 3000.0 } else { 1000.0 });
+            if i % 4 < 2 {
+                records.push(ShapeRecord::StraightEdge {
+                    delta_x: Twips::new(next.0 - last.0),
+                    delta_y: Twips::new(next.1 - last.1),
+                });
+            } else {
+                let control = point(i, 2000.0);
+                records.push(ShapeRecord::CurvedEdge {
+                    control_delta_x: Twips::new(control.0 - last.0),
+                    control_delta_y: Twips::new(control.1 - last.1),
+                    anchor_delta_x: Twips::new(next.0 - control.0),
+                    anchor_delta_y: Twips::new(next.1 - control.1),
+                });
+            }
+            last = next;
+        }
+
+        // A square hole in the middle, drawn with the fill on its left.
+        records.push(style_change((3500, 3500), Some(1), Some(0), None));
+        for &(delta_x, delta_y) in &[(1000, 0), (0, 1000), (-1000, 0), (0, -1000)] {
+            records.push(ShapeRecord::StraightEdge {
+                delta_x: Twips::new(delta_x),
+                delta_y: Twips::new(delta_y),
+            });
+        }
+
+        // A stroked zigzag across the shape, on a new layer.
+        records.push(ShapeRecord::StyleChange(swf::StyleChangeData {
+            move_to: Some((Twips::new(0), Twips::new(4000))),
+            fill_style_0: Some(0),
+            fill_style_1: Some(0),
+            line_style: Some(1),
+            new_styles: Some(swf::ShapeStyles {
+                fill_styles: vec![],
+                line_styles: vec![LineStyle::new_v1(
+                    Twips::new(200),
+                    swf::Color {
+                        r: 0,
+                        g: 0,
+                        b: 0,
+                        a: 255,
+                    },
+                )],
+            }),
+        }));
+        for i in 0..16 {
+            records.push(ShapeRecord::StraightEdge {
+                delta_x: Twips::new(500),
+                delta_y: Twips::new(if i % 2 == 0 { 1500 } else { -1500 }),
+            });
+        }
+
+        let shape = build_shape(records);
+        let cache = HitTestCache::new();
+        let matrices = [Matrix::identity(), Matrix::scale(20.0, 20.0)];
+        let mut hits = 0;
+        let mut misses = 0;
+        for matrix in &matrices {
+            for x in (-200..8200).step_by(37) {
+                for y in (-200..8200).step_by(37) {
+                    let point = (Twips::new(x), Twips::new(y));
+                    let expected = shape_hit_test(&shape, point, matrix);
+                    assert_eq!(
+                        cache.hit_test(&shape, point, matrix),
+                        expected,
+                        "hit test at {:?} with {:?}",
+                        point,
+                        matrix
+                    );
+                    if expected {
+                        hits += 1;
+                    } else {
+                        misses += 1;
+                    }
+                }
+            }
+        }
+        assert!(hits > 0 && misses > 0);
+    }
+
     /// A simple solid square.
     #[test]
     fn basic_shape() {
@@ -859,6 +967,316 @@ pub fn shape_hit_test(
     winding & 0b1 != 0
 }
 
+/// Edge tables for hit testing a shape, built the first time the shape is
+/// tested and reused by every later test.
+///
+/// Walking every record of a detailed shape for each test is slow, so the
+/// edges of each layer are bucketed by the rows of the shape they span, and a
+/// test only evaluates the edges in the buckets around the point.
+#[derive(Clone, Debug, Default)]
+pub struct HitTestCache(RefCell<Option<Vec<HitTestLayer>>>);
+
+impl HitTestCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Test whether the given point in object space is contained within the
+    /// contour of the given shape, with the same result as `shape_hit_test`.
+    ///
+    /// A cache must always be used with the same shape.
+    pub fn hit_test(
+        &self,
+        shape: &swf::Shape,
+        point: (Twips, Twips),
+        local_matrix: &Matrix,
+    ) -> bool {
+        let mut layers = self.0.borrow_mut();
+        let layers = layers.get_or_insert_with(|| HitTestLayer::from_shape(shape));
+        let min_width = f64::from(stroke_minimum_width(local_matrix));
+        layers.iter().any(|layer| layer.hit_test(point, min_width))
+    }
+}
+
+/// The edges of a shape between two sets of new styles.
+#[derive(Clone, Debug)]
+struct HitTestLayer {
+    /// The edges with a fill on only one side, flipped so that they all wind
+    /// the same way around the fill.
+    fills: Vec<HitTestEdge>,
+    fill_buckets: EdgeBuckets,
+
+    /// The stroked edges, with the width of their line style.
+    strokes: Vec<(HitTestEdge, f64)>,
+    stroke_buckets: EdgeBuckets,
+    max_stroke_width: f64,
+}
+
+impl HitTestLayer {
+    fn new(fills: Vec<HitTestEdge>, strokes: Vec<(HitTestEdge, f64)>) -> Self {
+        let fill_ranges: Vec<_> = fills.iter().map(HitTestEdge::y_range).collect();
+        let stroke_ranges: Vec<_> = strokes.iter().map(|(edge, _)| edge.y_range()).collect();
+        let max_stroke_width = strokes.iter().map(|(_, width)| *width).fold(0.0, f64::max);
+        Self {
+            fill_buckets: EdgeBuckets::new(&fill_ranges),
+            fills,
+            stroke_buckets: EdgeBuckets::new(&stroke_ranges),
+            strokes,
+            max_stroke_width,
+        }
+    }
+
+    /// Split a shape into layers, following the same rules as
+    /// `shape_hit_test`.
+    fn from_shape(shape: &swf::Shape) -> Vec<Self> {
+        let mut layers = Vec::new();
+        let mut fills = Vec::new();
+        let mut strokes = Vec::new();
+
+        let mut x = Twips::new(0);
+        let mut y = Twips::new(0);
+        let mut has_fill_style0: bool = false;
+        let mut has_fill_style1: bool = false;
+        let mut stroke_width = None;
+        let mut line_styles = &shape.styles.line_styles;
+
+        for record in &shape.shape {
+            let edge = match record {
+                swf::ShapeRecord::StyleChange(style_change) => {
+                    if let Some(new_styles) = &style_change.new_styles {
+                        if !fills.is_empty() || !strokes.is_empty() {
+                            layers.push(Self::new(
+                                std::mem::take(&mut fills),
+                                std::mem::take(&mut strokes),
+                            ));
+                        }
+                        line_styles = &new_styles.line_styles;
+                    }
+
+                    if let Some((move_x, move_y)) = style_change.move_to {
+                        x = move_x;
+                        y = move_y;
+                    }
+
+                    if let Some(i) = style_change.fill_style_0 {
+                        has_fill_style0 = i > 0;
+                    }
+                    if let Some(i) = style_change.fill_style_1 {
+                        has_fill_style1 = i > 0;
+                    }
+                    if let Some(i) = style_change.line_style {
+                        stroke_width = if i > 0 {
+                            line_styles
+                                .get(i as usize - 1)
+                                .map(|line_style| line_style.width.get() as f64)
+                        } else {
+                            None
+                        };
+                    }
+                    continue;
+                }
+                swf::ShapeRecord::StraightEdge { delta_x, delta_y } => HitTestEdge::Line {
+                    from: (x, y),
+                    to: (x + *delta_x, y + *delta_y),
+                },
+                swf::ShapeRecord::CurvedEdge {
+                    control_delta_x,
+                    control_delta_y,
+                    anchor_delta_x,
+                    anchor_delta_y,
+                } => {
+                    let control = (x + *control_delta_x, y + *control_delta_y);
+                    HitTestEdge::Curve {
+                        from: (x, y),
+                        control,
+                        to: (control.0 + *anchor_delta_x, control.1 + *anchor_delta_y),
+                    }
+                }
+            };
+
+            // Only edges with a fill style on one side can be crossed.
+            if has_fill_style1 {
+                if !has_fill_style0 {
+                    fills.push(edge);
+                }
+            } else if has_fill_style0 {
+                fills.push(edge.reversed());
+            }
+
+            if let Some(width) = stroke_width {
+                strokes.push((edge, width));
+            }
+
+            let (to_x, to_y) = edge.end();
+            x = to_x;
+            y = to_y;
+        }
+
+        if !fills.is_empty() || !strokes.is_empty() {
+            layers.push(Self::new(fills, strokes));
+        }
+        layers
+    }
+
+    /// Test whether the given point is within the fill or the strokes of
+    /// this layer.
+    ///
+    /// `min_width` is the minimum stroke width, in the shape's coordinate
+    /// space.
+    fn hit_test(&self, point: (Twips, Twips), min_width: f64) -> bool {
+        let point_y = point.1.get();
+
+        let winding: i32 = self
+            .fill_buckets
+            .edges(point_y, point_y)
+            .map(|i| self.fills[i].winding_number(point))
+            .sum();
+        if winding & 0b1 != 0 {
+            return true;
+        }
+
+        // Flash renders strokes with a 1px minimum width.
+        let reach = (0.5 * self.max_stroke_width.max(min_width)).ceil() as i32;
+        self.stroke_buckets
+            .edges(point_y - reach, point_y + reach)
+            .any(|i| {
+                let (edge, width) = self.strokes[i];
+                let width = 0.5 * width.max(min_width);
+                edge.hit_test_stroke(point, (width, width * width))
+            })
+    }
+}
+
+/// A single edge of a shape.
+#[derive(Clone, Copy, Debug)]
+enum HitTestEdge {
+    Line {
+        from: (Twips, Twips),
+        to: (Twips, Twips),
+    },
+    Curve {
+        from: (Twips, Twips),
+        control: (Twips, Twips),
+        to: (Twips, Twips),
+    },
+}
+
+impl HitTestEdge {
+    /// The same edge, drawn in the opposite direction.
+    fn reversed(self) -> Self {
+        match self {
+            HitTestEdge::Line { from, to } => HitTestEdge::Line { from: to, to: from },
+            HitTestEdge::Curve { from, control, to } => HitTestEdge::Curve {
+                from: to,
+                control,
+                to: from,
+            },
+        }
+    }
+
+    fn end(&self) -> (Twips, Twips) {
+        match *self {
+            HitTestEdge::Line { to, .. } | HitTestEdge::Curve { to, .. } => to,
+        }
+    }
+
+    /// The top and bottom of this edge, in twips.
+    ///
+    /// A curve never leaves the bounds of its control points.
+    fn y_range(&self) -> (i32, i32) {
+        match *self {
+            HitTestEdge::Line { from, to } => {
+                (from.1.get().min(to.1.get()), from.1.get().max(to.1.get()))
+            }
+            HitTestEdge::Curve { from, control, to } => {
+                let (y0, y1, y2) = (from.1.get(), control.1.get(), to.1.get());
+                (y0.min(y1).min(y2), y0.max(y1).max(y2))
+            }
+        }
+    }
+
+    fn winding_number(&self, point: (Twips, Twips)) -> i32 {
+        match *self {
+            HitTestEdge::Line { from, to } => winding_number_line(point, from, to),
+            HitTestEdge::Curve { from, control, to } => {
+                winding_number_curve(point, from, control, to)
+            }
+        }
+    }
+
+    fn hit_test_stroke(&self, point: (Twips, Twips), stroke_widths: (f64, f64)) -> bool {
+        match *self {
+            HitTestEdge::Line { from, to } => hit_test_stroke(point, from, to, stroke_widths),
+            HitTestEdge::Curve { from, control, to } => {
+                hit_test_stroke_curve(point, from, control, to, stroke_widths)
+            }
+        }
+    }
+}
+
+/// The indices of a list of edges, bucketed by the rows of the shape that
+/// they span.
+///
+/// An edge is in every bucket that its vertical extent overlaps, so the
+/// edges that can be crossed by a horizontal ray are all in the ray's bucket.
+#[derive(Clone, Debug, Default)]
+struct EdgeBuckets {
+    y_min: i32,
+    row_height: i32,
+    buckets: Vec<Vec<u32>>,
+}
+
+impl EdgeBuckets {
+    /// Bucket edges by their `(top, bottom)` extents.
+    fn new(ranges: &[(i32, i32)]) -> Self {
+        let y_min = ranges.iter().map(|range| range.0).min();
+        let y_max = ranges.iter().map(|range| range.1).max();
+        let (y_min, y_max) = match (y_min, y_max) {
+            (Some(y_min), Some(y_max)) => (y_min, y_max),
+            _ => return Self::default(),
+        };
+
+        // With evenly spread edges, this gives as many buckets as there are
+        // edges in each bucket.
+        let count = (ranges.len() as f64).sqrt().ceil() as usize;
+        let mut buckets = Self {
+            y_min,
+            row_height: (y_max - y_min) / count as i32 + 1,
+            buckets: vec![Vec::new(); count],
+        };
+        for (i, &(top, bottom)) in ranges.iter().enumerate() {
+            let rows = buckets.rows(top, bottom);
+            for bucket in &mut buckets.buckets[rows] {
+                bucket.push(i as u32);
+            }
+        }
+        buckets
+    }
+
+    /// The buckets that cover the rows from `top` to `bottom`.
+    fn rows(&self, top: i32, bottom: i32) -> Range<usize> {
+        if bottom < self.y_min || self.buckets.is_empty() {
+            return 0..0;
+        }
+        let len = self.buckets.len();
+        let first = ((top - self.y_min).max(0) / self.row_height) as usize;
+        let last = ((bottom - self.y_min) / self.row_height) as usize + 1;
+        first.min(len)..last.min(len)
+    }
+
+    /// The indices of the edges that may overlap the rows from `top` to
+    /// `bottom`.
+    ///
+    /// An edge that spans more than one of these buckets is given once for
+    /// each of them.
+    fn edges(&self, top: i32, bottom: i32) -> impl Iterator<Item = usize> + '_ {
+        self.buckets[self.rows(top, bottom)]
+            .iter()
+            .flatten()
+            .map(|&i| i as usize)
+    }
+}
+
 /// Test whether the given point is contained with in the paths specified by the draw commands.
 pub fn draw_command_fill_hit_test(
     commands: &[DrawCommand],
@@ -1353,3 +1771,301 @@ pub fn swf_glyph_to_shape(glyph: &swf::Glyph) -> swf::Shape {
         shape: glyph.shape_records.clone(),
     }
 }
+
+/// A 9-slice scaling transform, which Flash applies to the shapes of a clip
+/// with a scaling grid.
+///
+/// Points are mapped within the clip's own coordinate space, so that once the
+/// clip's scale is applied, the corners of the grid keep their size, the edges
+/// stretch along one axis and the center stretches along both.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NineSlice {
+    x: SliceAxis,
+    y: SliceAxis,
+}
+
+impl NineSlice {
+    /// Build the transform of a clip with the given scaling grid, content
+    /// bounds and scale.
+    ///
+    /// Returns `None` if the transform would leave every point in place.
+    pub fn new(
+        scaling_grid: &swf::Rectangle,
+        bounds: &BoundingBox,
+        scale_x: f64,
+        scale_y: f64,
+    ) -> Option<Self> {
+        if !bounds.valid {
+            return None;
+        }
+
+        let x = SliceAxis::new(
+            bounds.x_min,
+            bounds.x_max,
+            scaling_grid.x_min,
+            scaling_grid.x_max,
+            scale_x,
+        );
+        let y = SliceAxis::new(
+            bounds.y_min,
+            bounds.y_max,
+            scaling_grid.y_min,
+            scaling_grid.y_max,
+            scale_y,
+        );
+        if x.is_identity() && y.is_identity() {
+            return None;
+        }
+
+        Some(Self { x, y })
+    }
+
+    /// Map a point from the clip's coordinate space to where it is drawn.
+    pub fn map_point(&self, (x, y): (Twips, Twips)) -> (Twips, Twips) {
+        (self.x.map(x), self.y.map(y))
+    }
+
+    /// Map a drawn point back to the clip's coordinate space.
+    pub fn unmap_point(&self, (x, y): (Twips, Twips)) -> (Twips, Twips) {
+        (self.x.unmap(x), self.y.unmap(y))
+    }
+
+    /// Map a bounding box from the clip's coordinate space to where it is
+    /// drawn.
+    pub fn map_bounds(&self, bounds: &BoundingBox) -> BoundingBox {
+        if !bounds.valid {
+            return bounds.clone();
+        }
+
+        let (x_min, y_min) = self.map_point((bounds.x_min, bounds.y_min));
+        let (x_max, y_max) = self.map_point((bounds.x_max, bounds.y_max));
+        BoundingBox {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+            valid: true,
+        }
+    }
+
+    /// Slice a shape placed in the clip with the given matrix.
+    ///
+    /// The returned shape is in the clip's coordinate space, and should be
+    /// drawn without the placement matrix.
+    pub fn slice_shape(&self, shape: &Shape, matrix: &Matrix) -> Shape {
+        let slice_rect = |rect: &swf::Rectangle| {
+            let bounds = BoundingBox::from(rect.clone()).transform(matrix);
+            let bounds = self.map_bounds(&bounds);
+            swf::Rectangle {
+                x_min: bounds.x_min,
+                x_max: bounds.x_max,
+                y_min: bounds.y_min,
+                y_max: bounds.y_max,
+            }
+        };
+        let slice_styles = |styles: &swf::ShapeStyles| swf::ShapeStyles {
+            fill_styles: styles
+                .fill_styles
+                .iter()
+                .map(|style| transform_fill_style(style, matrix))
+                .collect(),
+            line_styles: styles.line_styles.clone(),
+        };
+
+        let mut position = (Twips::zero(), Twips::zero());
+        let mut sliced_position = self.map_point(*matrix * position);
+        let mut records = Vec::with_capacity(shape.shape.len());
+        for record in &shape.shape {
+            match record {
+                ShapeRecord::StyleChange(style_change) => {
+                    let mut style_change = style_change.clone();
+                    if let Some(move_to) = style_change.move_to {
+                        position = move_to;
+                        sliced_position = self.map_point(*matrix * position);
+                        style_change.move_to = Some(sliced_position);
+                    }
+                    style_change.new_styles = style_change.new_styles.as_ref().map(slice_styles);
+                    records.push(ShapeRecord::StyleChange(style_change));
+                }
+                ShapeRecord::StraightEdge { delta_x, delta_y } => {
+                    let start = *matrix * position;
+                    position = (position.0 + *delta_x, position.1 + *delta_y);
+                    let end = *matrix * position;
+
+                    // Edges are split where they cross the grid, so that each
+                    // piece is mapped linearly.
+                    for t in self.grid_crossings(start, end) {
+                        let lerp = |a: Twips, b: Twips| {
+                            let (a, b) = (a.get() as f64, b.get() as f64);
+                            Twips::new((a + (b - a) * t).round() as i32)
+                        };
+                        let point = (lerp(start.0, end.0), lerp(start.1, end.1));
+                        let anchor = self.map_point(point);
+                        records.push(ShapeRecord::StraightEdge {
+                            delta_x: anchor.0 - sliced_position.0,
+                            delta_y: anchor.1 - sliced_position.1,
+                        });
+                        sliced_position = anchor;
+                    }
+
+                    let anchor = self.map_point(end);
+                    records.push(ShapeRecord::StraightEdge {
+                        delta_x: anchor.0 - sliced_position.0,
+                        delta_y: anchor.1 - sliced_position.1,
+                    });
+                    sliced_position = anchor;
+                }
+                ShapeRecord::CurvedEdge {
+                    control_delta_x,
+                    control_delta_y,
+                    anchor_delta_x,
+                    anchor_delta_y,
+                } => {
+                    let control_position =
+                        (position.0 + *control_delta_x, position.1 + *control_delta_y);
+                    position = (
+                        control_position.0 + *anchor_delta_x,
+                        control_position.1 + *anchor_delta_y,
+                    );
+                    let control = self.map_point(*matrix * control_position);
+                    let anchor = self.map_point(*matrix * position);
+                    records.push(ShapeRecord::CurvedEdge {
+                        control_delta_x: control.0 - sliced_position.0,
+                        control_delta_y: control.1 - sliced_position.1,
+                        anchor_delta_x: anchor.0 - control.0,
+                        anchor_delta_y: anchor.1 - control.1,
+                    });
+                    sliced_position = anchor;
+                }
+            }
+        }
+
+        Shape {
+            version: shape.version,
+            id: shape.id,
+            shape_bounds: slice_rect(&shape.shape_bounds),
+            edge_bounds: slice_rect(&shape.edge_bounds),
+            has_fill_winding_rule: shape.has_fill_winding_rule,
+            has_non_scaling_strokes: shape.has_non_scaling_strokes,
+            has_scaling_strokes: shape.has_scaling_strokes,
+            styles: slice_styles(&shape.styles),
+            shape: records,
+        }
+    }
+
+    /// The fractions along the line from `start` to `end` at which it crosses
+    /// the edges of the grid, in order.
+    fn grid_crossings(&self, start: (Twips, Twips), end: (Twips, Twips)) -> SmallVec<[f64; 4]> {
+        let mut crossings = SmallVec::new();
+        self.x.add_crossings(start.0, end.0, &mut crossings);
+        self.y.add_crossings(start.1, end.1, &mut crossings);
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        crossings
+    }
+}
+
+/// One axis of a `NineSlice`.
+///
+/// The axis is split into three segments at the edges of the grid. Each
+/// segment is mapped linearly onto its sliced counterpart.
+#[derive(Clone, Debug, PartialEq)]
+struct SliceAxis {
+    /// The content's start, the grid's start and end, and the content's end.
+    from: [f64; 4],
+
+    /// Where each of the points in `from` is drawn.
+    to: [f64; 4],
+}
+
+impl SliceAxis {
+    fn new(min: Twips, max: Twips, grid_min: Twips, grid_max: Twips, scale: f64) -> Self {
+        let (min, max) = (min.get() as f64, max.get() as f64);
+        let grid_min = (grid_min.get() as f64).max(min).min(max);
+        let grid_max = (grid_max.get() as f64).max(grid_min).min(max);
+        let from = [min, grid_min, grid_max, max];
+
+        let scale = scale.abs();
+        if scale == 0.0 || !scale.is_finite() || grid_min >= grid_max {
+            return Self { from, to: from };
+        }
+
+        // The margins keep their size after scaling, unless the clip is too
+        // small to hold them, in which case they shrink proportionally.
+        let (start, end) = (grid_min - min, max - grid_max);
+        let (mut start_size, mut end_size) = (start / scale, end / scale);
+        if start_size + end_size > max - min {
+            let factor = (max - min) / (start + end);
+            start_size = start * factor;
+            end_size = end * factor;
+        }
+
+        Self {
+            from,
+            to: [min, min + start_size, max - end_size, max],
+        }
+    }
+
+    fn is_identity(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Add the fractions along the span from `start` to `end` at which it
+    /// crosses the edges of the grid.
+    fn add_crossings(&self, start: Twips, end: Twips, crossings: &mut SmallVec<[f64; 4]>) {
+        let (start, end) = (start.get() as f64, end.get() as f64);
+        for edge in &self.from[1..3] {
+            let t = (edge - start) / (end - start);
+            if t > 0.0 && t < 1.0 {
+                crossings.push(t);
+            }
+        }
+    }
+
+    fn map(&self, value: Twips) -> Twips {
+        Self::interpolate(value, &self.from, &self.to)
+    }
+
+    fn unmap(&self, value: Twips) -> Twips {
+        Self::interpolate(value, &self.to, &self.from)
+    }
+
+    fn interpolate(value: Twips, from: &[f64; 4], to: &[f64; 4]) -> Twips {
+        let value = value.get() as f64;
+        let segment = if value < from[1] {
+            0
+        } else if value < from[2] {
+            1
+        } else {
+            2
+        };
+
+        let length = from[segment + 1] - from[segment];
+        let mapped = if length > 0.0 {
+            let ratio = (to[segment + 1] - to[segment]) / length;
+            to[segment] + (value - from[segment]) * ratio
+        } else {
+            to[segment] + (value - from[segment])
+        };
+        Twips::new(mapped.round() as i32)
+    }
+}
+
+/// Apply a matrix to the gradient or bitmap matrix of a fill style.
+fn transform_fill_style(style: &FillStyle, matrix: &Matrix) -> FillStyle {
+    let mut style = style.clone();
+    match &mut style {
+        FillStyle::LinearGradient(gradient)
+        | FillStyle::RadialGradient(gradient)
+        | FillStyle::FocalGradient { gradient, .. } => {
+            gradient.matrix = *matrix * gradient.matrix;
+        }
+        FillStyle::Bitmap {
+            matrix: bitmap_matrix,
+            ..
+        } => {
+            *bitmap_matrix = *matrix * *bitmap_matrix;
+        }
+        FillStyle::Color(_) => (),
+    }
+    style
+}
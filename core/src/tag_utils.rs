@@ -1,14 +1,30 @@
-use crate::backend::navigator::url_from_relative_path;
+use crate::backend::navigator::{url_from_relative_path, OwnedFuture};
+use crate::player::NEWEST_PLAYER_VERSION;
 use crate::property_map::PropertyMap;
+use flate2::{Decompress, FlushDecompress, Status};
 use gc_arena::Collect;
+use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
-use swf::{Header, TagCode};
+use swf::read::SwfRead;
+use swf::{CharacterId, Compression, Header, TagCode, Twips};
 
 pub type Error = Box<dyn std::error::Error>;
 pub type DecodeResult = Result<(), Error>;
 pub type SwfStream<R> = swf::read::Reader<std::io::Cursor<R>>;
 
+/// Whether movies are loaded in full before they begin to play, instead of
+/// playing while the rest of the movie streams in.
+///
+/// This is enabled by setting the `SWF_STRICT` environment variable at compile
+/// time, which is useful for testing.
+pub const EAGER_LOADING: bool = option_env!("SWF_STRICT").is_some();
+
+/// The length of the signature, version and uncompressed length at the start
+/// of every SWF file.
+const SWF_FILE_HEADER_LEN: usize = 8;
+
 /// An open, fully parsed SWF movie ready to play back, either in a Player or a
 /// MovieClip.
 #[derive(Debug, Clone, Collect)]
@@ -25,8 +41,34 @@ pub struct SwfMovie {
 
     /// Any parameters provided when loading this movie (also known as 'flashvars')
     parameters: PropertyMap<String>,
+
+    /// The assets in this movie's library.
+    symbols: Vec<Symbol>,
+
+    /// The tags at the top level of this movie that are skipped during
+    /// playback.
+    unsupported_tags: Vec<UnsupportedTag>,
+
+    /// The number of bytes of the SWF file that have been loaded, counted
+    /// after decompression.
+    bytes_loaded: usize,
+
+    /// The length of the SWF file after decompression, as given by its header.
+    bytes_total: usize,
+
+    /// The identity of this movie, shared by every part of a movie that is
+    /// loaded progressively.
+    identity: Arc<MovieIdentity>,
 }
 
+/// Identifies a movie, no matter how much of it has been loaded.
+///
+/// Movies that are loaded progressively are replaced by larger parts of
+/// themselves as their data arrives. All of those parts have the same
+/// identity, so that they share a library.
+#[derive(Debug, Default)]
+pub struct MovieIdentity;
+
 impl SwfMovie {
     /// Construct an empty movie.
     pub fn empty(swf_version: u8) -> Self {
@@ -41,9 +83,33 @@ impl SwfMovie {
             data: vec![],
             url: None,
             parameters: PropertyMap::new(),
+            symbols: vec![],
+            unsupported_tags: vec![],
+            bytes_loaded: 0,
+            bytes_total: 0,
+            identity: Default::default(),
         }
     }
 
+    /// Construct a movie for a loaded JPEG, PNG or GIF image.
+    ///
+    /// The movie has no tags of its own; the image is placed on its stage,
+    /// which is the size of the image, once it has loaded.
+    pub fn from_image(width: u32, height: u32, image_len: usize, url: String) -> Self {
+        let mut movie = Self::empty(NEWEST_PLAYER_VERSION);
+        movie.header.stage_size = swf::Rectangle {
+            x_min: Twips::zero(),
+            y_min: Twips::zero(),
+            x_max: Twips::from_pixels(width.into()),
+            y_max: Twips::from_pixels(height.into()),
+        };
+        movie.header.num_frames = 1;
+        movie.url = Some(url);
+        movie.bytes_loaded = image_len;
+        movie.bytes_total = image_len;
+        movie
+    }
+
     /// Construct a movie from an existing movie with any particular data on
     /// it.
     ///
@@ -55,6 +121,11 @@ impl SwfMovie {
             data,
             url: source.url.clone(),
             parameters: source.parameters.clone(),
+            symbols: self.symbols.clone(),
+            unsupported_tags: self.unsupported_tags.clone(),
+            bytes_loaded: self.bytes_loaded,
+            bytes_total: self.bytes_total,
+            identity: Default::default(),
         }
     }
 
@@ -81,12 +152,12 @@ impl SwfMovie {
         // but will otherwise decompress fine up to the End tag.
         // So just warn on this case and try to continue gracefully.
         let data = if header.compression == swf::Compression::Lzma {
-            // TODO: The LZMA decoder is still funky.
-            // It always errors, and doesn't return all the data if you use read_to_end,
-            // but read_exact at least returns the data... why?
-            // Does the decoder need to be flushed somehow?
+            // The LZMA decoder can error after returning all of the data, so
+            // read exactly the uncompressed length.
             let mut data = vec![0u8; swf_stream.uncompressed_length];
-            let _ = reader.get_mut().read_exact(&mut data);
+            if let Err(e) = reader.get_mut().read_exact(&mut data) {
+                return Err(format!("Error decompressing SWF, may be corrupt: {}", e).into());
+            }
             data
         } else {
             let mut data = Vec::with_capacity(swf_stream.uncompressed_length);
@@ -96,11 +167,21 @@ impl SwfMovie {
             data
         };
 
+        let symbols = read_symbols(&data, header.version);
+        let unsupported_tags = read_unsupported_tags(&data, header.version);
+        log_unsupported_tags(&unsupported_tags);
+        let bytes_total = swf_stream.uncompressed_length + SWF_FILE_HEADER_LEN;
+
         Ok(Self {
             header,
             data,
             url,
             parameters: PropertyMap::new(),
+            symbols,
+            unsupported_tags,
+            bytes_loaded: bytes_total,
+            bytes_total,
+            identity: Default::default(),
         })
     }
 
@@ -137,6 +218,509 @@ impl SwfMovie {
     pub fn parameters_mut(&mut self) -> &mut PropertyMap<String> {
         &mut self.parameters
     }
+
+    /// Get the bitmaps, sounds, fonts, videos and movie clips defined in this
+    /// movie's library, in the order they are defined.
+    ///
+    /// Symbols named by an `ExportAssets` or `SymbolClass` tag carry their
+    /// export name.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.symbols.clone()
+    }
+
+    /// Get the tags at the top level of this movie that are skipped because
+    /// they don't affect playback or aren't known at all, in the order they
+    /// first appear.
+    pub fn unsupported_tags(&self) -> &[UnsupportedTag] {
+        &self.unsupported_tags
+    }
+
+    /// Get the number of bytes of the SWF file that have been loaded.
+    ///
+    /// Like Flash Player, this counts the bytes after decompression.
+    pub fn bytes_loaded(&self) -> usize {
+        self.bytes_loaded
+    }
+
+    /// Get the length of the SWF file after decompression.
+    pub fn bytes_total(&self) -> usize {
+        self.bytes_total
+    }
+
+    /// Whether the entire movie has been loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.bytes_loaded >= self.bytes_total
+    }
+
+    /// Get the identity of this movie, which is shared with the other parts
+    /// of the same movie while it is loaded progressively.
+    pub fn identity(&self) -> &Arc<MovieIdentity> {
+        &self.identity
+    }
+
+    /// Whether this is the same movie as another, possibly with a different
+    /// amount of it loaded.
+    pub fn is_same_movie(&self, other: &SwfMovie) -> bool {
+        Arc::ptr_eq(&self.identity, &other.identity)
+    }
+}
+
+/// A SWF movie that is decoded progressively, as its bytes arrive.
+///
+/// Bytes are given to the stream with `push_bytes` as they are received.
+/// Zlib-compressed movies are decompressed as they arrive, and `movie` returns
+/// the part of the movie loaded so far, so that playback can begin before the
+/// rest of the movie has arrived. LZMA-compressed movies can only be decoded
+/// once all of their bytes have arrived.
+pub struct SwfMovieStream {
+    /// The URL the SWF is being downloaded from.
+    url: Option<String>,
+
+    /// Any parameters provided when loading this movie.
+    parameters: PropertyMap<String>,
+
+    /// Bytes that can't be decoded yet: the start of the file before its
+    /// signature has arrived, or the whole file if it is LZMA-compressed.
+    pending: Vec<u8>,
+
+    /// The compression, version and decompressed length of the file, once
+    /// its signature has arrived.
+    file_header: Option<(Compression, u8, usize)>,
+
+    /// The zlib decoder for compressed movies.
+    decompress: Decompress,
+
+    /// Decompressed data following the signature.
+    data: Vec<u8>,
+
+    /// The SWF header, and its length in bytes, once it has arrived.
+    header: Option<(Header, usize)>,
+
+    /// The length of the complete tags at the start of the tag data.
+    tags_len: usize,
+
+    /// The number of `ShowFrame` tags among the complete tags.
+    frames_loaded: u16,
+
+    /// Whether the `End` tag has arrived.
+    is_complete: bool,
+
+    /// The identity shared by every part of the movie.
+    identity: Arc<MovieIdentity>,
+}
+
+impl SwfMovieStream {
+    /// Begin loading a movie.
+    pub fn new(url: Option<String>) -> Self {
+        Self {
+            url,
+            parameters: PropertyMap::new(),
+            pending: vec![],
+            file_header: None,
+            decompress: Decompress::new(true),
+            data: vec![],
+            header: None,
+            tags_len: 0,
+            frames_loaded: 0,
+            is_complete: false,
+            identity: Default::default(),
+        }
+    }
+
+    pub fn parameters_mut(&mut self) -> &mut PropertyMap<String> {
+        &mut self.parameters
+    }
+
+    /// Get the SWF header, once it has arrived.
+    pub fn header(&self) -> Option<&Header> {
+        self.header.as_ref().map(|(header, _)| header)
+    }
+
+    /// Get the number of frames whose tags have all arrived.
+    pub fn frames_loaded(&self) -> u16 {
+        self.frames_loaded
+    }
+
+    /// Whether the `End` tag of the movie has arrived.
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// Get the number of bytes of the SWF file that have arrived, counted
+    /// after decompression.
+    pub fn bytes_loaded(&self) -> usize {
+        match self.file_header {
+            Some((Compression::Lzma, ..)) | None => 0,
+            Some(_) => SWF_FILE_HEADER_LEN + self.data.len(),
+        }
+    }
+
+    /// Get the length of the SWF file after decompression, once its
+    /// signature has arrived.
+    pub fn bytes_total(&self) -> usize {
+        self.file_header
+            .map(|(_, _, len)| len + SWF_FILE_HEADER_LEN)
+            .unwrap_or_default()
+    }
+
+    /// Decode another chunk of the SWF file.
+    pub fn push_bytes(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        let rest;
+        let bytes = if self.file_header.is_some() {
+            chunk
+        } else {
+            self.pending.extend_from_slice(chunk);
+            if self.pending.len() < SWF_FILE_HEADER_LEN {
+                return Ok(());
+            }
+
+            let file_header = read_file_header(&self.pending)?;
+            self.file_header = Some(file_header);
+            let (compression, _, uncompressed_len) = file_header;
+            if compression == Compression::Lzma {
+                return Ok(());
+            }
+
+            self.data.reserve(uncompressed_len);
+            rest = std::mem::take(&mut self.pending).split_off(SWF_FILE_HEADER_LEN);
+            &rest[..]
+        };
+
+        let (compression, version, _) = self.file_header.unwrap();
+        if compression == Compression::Lzma {
+            self.pending.extend_from_slice(bytes);
+            return Ok(());
+        }
+
+        self.decode(bytes, compression)?;
+        self.read_tags(version);
+        Ok(())
+    }
+
+    /// Get the part of the movie that has been loaded so far, up to the last
+    /// complete tag.
+    ///
+    /// Returns `None` if the SWF header has not arrived yet. When loading
+    /// eagerly, this also returns `None` until the whole movie has arrived.
+    pub fn movie(&self) -> Option<SwfMovie> {
+        let (header, header_len) = self.header.as_ref()?;
+        if EAGER_LOADING && !self.is_complete {
+            return None;
+        }
+
+        let data = self.data[*header_len..*header_len + self.tags_len].to_vec();
+        let symbols = read_symbols(&data, header.version);
+        let unsupported_tags = read_unsupported_tags(&data, header.version);
+        let bytes_total = self.bytes_total();
+        let bytes_loaded = if self.is_complete {
+            bytes_total
+        } else {
+            self.bytes_loaded().min(bytes_total)
+        };
+
+        Some(SwfMovie {
+            header: header.clone(),
+            data,
+            url: self.url.clone(),
+            parameters: self.parameters.clone(),
+            symbols,
+            unsupported_tags,
+            bytes_loaded,
+            bytes_total,
+            identity: self.identity.clone(),
+        })
+    }
+
+    /// Finish loading the movie, once all of its bytes have been pushed.
+    ///
+    /// The returned future completes with the full movie.
+    pub fn finish(self) -> OwnedFuture<SwfMovie, Error> {
+        Box::pin(async move { self.into_movie() })
+    }
+
+    fn into_movie(self) -> Result<SwfMovie, Error> {
+        if let Some((Compression::Lzma, ..)) = self.file_header {
+            let mut movie = SwfMovie::from_data(&self.pending, self.url)?;
+            movie.parameters = self.parameters;
+            movie.identity = self.identity;
+            return Ok(movie);
+        }
+
+        let bytes_total = self.bytes_total();
+        let (header, header_len) = self.header.ok_or("SWF header is incomplete")?;
+        let mut data = self.data;
+        let data = data.split_off(header_len);
+        let symbols = read_symbols(&data, header.version);
+        let unsupported_tags = read_unsupported_tags(&data, header.version);
+        log_unsupported_tags(&unsupported_tags);
+
+        Ok(SwfMovie {
+            header,
+            data,
+            url: self.url,
+            parameters: self.parameters,
+            symbols,
+            unsupported_tags,
+            bytes_loaded: bytes_total,
+            bytes_total,
+            identity: self.identity,
+        })
+    }
+
+    /// Decompress bytes following the signature of the file.
+    fn decode(&mut self, bytes: &[u8], compression: Compression) -> Result<(), Error> {
+        if compression == Compression::None {
+            self.data.extend_from_slice(bytes);
+            return Ok(());
+        }
+
+        let mut input = bytes;
+        while !input.is_empty() {
+            if self.data.capacity() - self.data.len() < bytes.len() {
+                self.data.reserve(bytes.len().max(4096));
+            }
+
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+            let status = self
+                .decompress
+                .decompress_vec(input, &mut self.data, FlushDecompress::None)
+                .map_err(|e| format!("Error decompressing SWF, may be corrupt: {}", e))?;
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            input = &input[consumed..];
+
+            // Anything after the end of the zlib stream is ignored.
+            let is_stuck = consumed == 0 && self.decompress.total_out() == total_out;
+            if status == Status::StreamEnd || is_stuck {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the SWF header and any newly completed tags.
+    fn read_tags(&mut self, version: u8) {
+        if self.header.is_none() {
+            let mut reader = swf::read::Reader::new(std::io::Cursor::new(&self.data[..]), version);
+            let stage_size = reader.read_rectangle();
+            let frame_rate = reader.read_fixed8();
+            let num_frames = reader.read_u16();
+            match (stage_size, frame_rate, num_frames) {
+                (Ok(stage_size), Ok(frame_rate), Ok(num_frames)) => {
+                    let (compression, ..) = self.file_header.unwrap();
+                    let header = Header {
+                        version,
+                        compression,
+                        stage_size,
+                        frame_rate,
+                        num_frames,
+                    };
+                    self.header = Some((header, reader.get_ref().position() as usize));
+                }
+                // Wait for the rest of the header to arrive.
+                _ => return,
+            }
+        }
+
+        let header_len = self.header.as_ref().unwrap().1;
+        let tags = &self.data[header_len..];
+        while !self.is_complete {
+            let mut reader =
+                swf::read::Reader::new(std::io::Cursor::new(&tags[self.tags_len..]), version);
+            let (tag_code, tag_len) = match reader.read_tag_code_and_length() {
+                Ok(tag) => tag,
+                Err(_) => break,
+            };
+            let end = self.tags_len + reader.get_ref().position() as usize + tag_len;
+            if end > tags.len() {
+                break;
+            }
+
+            self.tags_len = end;
+            match TagCode::from_u16(tag_code) {
+                Some(TagCode::ShowFrame) => self.frames_loaded += 1,
+                Some(TagCode::End) => self.is_complete = true,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Read the compression, version and decompressed length from the start of a
+/// SWF file.
+fn read_file_header(data: &[u8]) -> Result<(Compression, u8, usize), Error> {
+    let compression = match &data[0..3] {
+        b"FWS" => Compression::None,
+        b"CWS" => Compression::Zlib,
+        b"ZWS" => Compression::Lzma,
+        _ => return Err("Invalid SWF signature".into()),
+    };
+    let version = data[3];
+    let mut len = [0; 4];
+    len.copy_from_slice(&data[4..SWF_FILE_HEADER_LEN]);
+    let uncompressed_len = u32::from_le_bytes(len) as usize;
+
+    Ok((
+        compression,
+        version,
+        uncompressed_len.saturating_sub(SWF_FILE_HEADER_LEN),
+    ))
+}
+
+/// The kind of asset held by a library symbol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolKind {
+    Bitmap,
+    Sound,
+    Font,
+    Video,
+    MovieClip,
+}
+
+/// An asset in the library of a SWF movie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The character ID of the asset.
+    pub character_id: CharacterId,
+
+    /// The name the asset is exported under, if any.
+    pub export_name: Option<String>,
+
+    /// The kind of the asset.
+    pub kind: SymbolKind,
+}
+
+/// Scan the top-level tags of a movie for library symbols and their export
+/// names.
+fn read_symbols(data: &[u8], version: u8) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut export_names = HashMap::new();
+
+    let mut reader = swf::read::Reader::new(std::io::Cursor::new(data), version);
+    let result = decode_tags(
+        &mut reader,
+        |reader, tag_code, _tag_len| {
+            let kind = match tag_code {
+                TagCode::DefineBits
+                | TagCode::DefineBitsJpeg2
+                | TagCode::DefineBitsJpeg3
+                | TagCode::DefineBitsJpeg4
+                | TagCode::DefineBitsLossless
+                | TagCode::DefineBitsLossless2 => SymbolKind::Bitmap,
+                TagCode::DefineSound => SymbolKind::Sound,
+                TagCode::DefineFont
+                | TagCode::DefineFont2
+                | TagCode::DefineFont3
+                | TagCode::DefineFont4 => SymbolKind::Font,
+                TagCode::DefineVideoStream => SymbolKind::Video,
+                TagCode::DefineSprite => SymbolKind::MovieClip,
+                TagCode::ExportAssets => {
+                    for export in reader.read_export_assets()? {
+                        export_names.insert(export.id, export.name);
+                    }
+                    return Ok(());
+                }
+                TagCode::SymbolClass => {
+                    let num_symbols = reader.read_u16()?;
+                    for _ in 0..num_symbols {
+                        let id = reader.read_u16()?;
+                        let class_name = reader.read_c_string()?;
+                        export_names.insert(id, class_name);
+                    }
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            };
+
+            symbols.push(Symbol {
+                character_id: reader.read_character_id()?,
+                export_name: None,
+                kind,
+            });
+            Ok(())
+        },
+        TagCode::End,
+    );
+    if let Err(e) = result {
+        log::warn!("Error reading library symbols: {}", e);
+    }
+
+    for symbol in &mut symbols {
+        symbol.export_name = export_names.get(&symbol.character_id).cloned();
+    }
+
+    symbols
+}
+
+/// Tags that hold debugging or authoring information, or are otherwise
+/// ignored during playback.
+const IGNORED_TAGS: &[TagCode] = &[
+    TagCode::ProductInfo,
+    TagCode::DefineFontInfo,
+    TagCode::DefineFontInfo2,
+    TagCode::EnableDebugger,
+    TagCode::EnableDebugger2,
+    TagCode::DebugId,
+    TagCode::Metadata,
+    TagCode::EnableTelemetry,
+];
+
+/// A kind of tag that is skipped during playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedTag {
+    /// The code of the tag.
+    pub tag_code: u16,
+
+    /// The number of times the tag appears.
+    pub count: usize,
+}
+
+impl fmt::Display for UnsupportedTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match TagCode::from_u16(self.tag_code) {
+            Some(tag) => write!(f, "{:?}", tag)?,
+            None => write!(f, "unknown tag {}", self.tag_code)?,
+        }
+        if self.count > 1 {
+            write!(f, " (x{})", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Scan the top-level tags of a movie for tags that are skipped during
+/// playback.
+///
+/// Only the tag headers are read: every tag is stepped over by its length, so
+/// the contents of unknown tags are never interpreted.
+fn read_unsupported_tags(data: &[u8], version: u8) -> Vec<UnsupportedTag> {
+    let mut unsupported_tags: Vec<UnsupportedTag> = Vec::new();
+
+    let mut reader = swf::read::Reader::new(std::io::Cursor::new(data), version);
+    while let Ok((tag_code, tag_len)) = reader.read_tag_code_and_length() {
+        match TagCode::from_u16(tag_code) {
+            Some(TagCode::End) => break,
+            Some(tag) if !IGNORED_TAGS.contains(&tag) => (),
+            _ => match unsupported_tags.iter_mut().find(|t| t.tag_code == tag_code) {
+                Some(unsupported_tag) => unsupported_tag.count += 1,
+                None => unsupported_tags.push(UnsupportedTag { tag_code, count: 1 }),
+            },
+        }
+
+        let end_pos = reader.get_ref().position() + tag_len as u64;
+        reader.get_mut().set_position(end_pos);
+    }
+
+    unsupported_tags
+}
+
+/// Report the tags of a movie that are skipped, in a single notice.
+fn log_unsupported_tags(unsupported_tags: &[UnsupportedTag]) {
+    if !unsupported_tags.is_empty() {
+        let tags: Vec<String> = unsupported_tags.iter().map(|t| t.to_string()).collect();
+        log::info!("Skipping unsupported tags: {}", tags.join(", "));
+    }
 }
 
 /// A shared-ownership reference to some portion of an SWF datastream.
@@ -317,14 +901,30 @@ where
     use std::io::{Seek, SeekFrom};
     loop {
         let (tag_code, tag_len) = reader.read_tag_code_and_length()?;
-        let end_pos = reader.get_ref().position() + tag_len as u64;
+        let start_pos = reader.get_ref().position();
+        let end_pos = start_pos + tag_len as u64;
+        if end_pos > reader.get_ref().get_ref().as_ref().len() as u64 {
+            return Err(format!(
+                "Tag {} at offset {} is longer than the data left ({} bytes)",
+                tag_code, start_pos, tag_len
+            )
+            .into());
+        }
 
         let tag = TagCode::from_u16(tag_code);
         if let Some(tag) = tag {
+            // A malformed tag is skipped using its length, so that the rest of
+            // the movie still loads.
             let result = tag_callback(reader, tag, tag_len);
 
             if let Err(e) = result {
-                log::error!("Error running definition tag: {:?}, got {}", tag, e);
+                log::error!(
+                    "Error running tag {:?} at offset {} ({} bytes), skipping it: {}",
+                    tag,
+                    start_pos,
+                    tag_len,
+                    e
+                );
             }
 
             if stop_tag == tag {
@@ -332,7 +932,8 @@ where
                 break;
             }
         } else {
-            log::warn!("Unknown tag code: {:?}", tag_code);
+            // Unknown tags are reported once, when the movie is loaded.
+            log::trace!("Skipping unknown tag code: {:?}", tag_code);
         }
 
         reader.get_mut().seek(SeekFrom::Start(end_pos))?;
@@ -0,0 +1,519 @@
+//! Background workers.
+//!
+//! Each background worker runs its movie in a player of its own, on a thread
+//! of its own. `Player` is not `Send` or `Sync`, and can't be made so: its
+//! garbage-collected arena and its backends are tied to the thread that
+//! created them. So no player ever crosses threads. Instead, the worker's SWF
+//! data is moved to the new thread when the worker starts, and its player is
+//! built there.
+//!
+//! The only state that threads share is the `WorkerData` and `ChannelData`
+//! below. Workers exchange data through shared properties and message
+//! channels, which hold values that have been serialized as AMF3.
+
+use crate::avm2::Object as Avm2Object;
+use crate::backend::{
+    audio::NullAudioBackend,
+    input::NullInputBackend,
+    locale::NullLocaleBackend,
+    log::NullLogBackend,
+    navigator::{NullExecutor, NullNavigatorBackend},
+    render::NullRenderer,
+    storage::MemoryStorageBackend,
+    ui::NullUiBackend,
+    video::NullVideoBackend,
+};
+use crate::player::Player;
+use crate::tag_utils::SwfMovie;
+use gc_arena::{Collect, CollectionContext};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// A reference to a worker that may be shared between threads.
+pub type WorkerHandle = Arc<WorkerData>;
+
+/// A reference to a message channel that may be shared between threads.
+pub type ChannelHandle = Arc<ChannelData>;
+
+/// Whether or not background workers can be run on this platform.
+pub fn is_supported() -> bool {
+    cfg!(not(target_arch = "wasm32"))
+}
+
+/// The lifecycle of a worker, as reported by `Worker.state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerState {
+    New,
+    Running,
+    Terminated,
+}
+
+impl WorkerState {
+    /// The name of this state, as used by `flash.system.WorkerState`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkerState::New => "new",
+            WorkerState::Running => "running",
+            WorkerState::Terminated => "terminated",
+        }
+    }
+}
+
+/// The lifecycle of a message channel, as reported by `MessageChannel.state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelState {
+    Open,
+
+    /// The channel has been closed, but still holds messages that may be
+    /// received.
+    Closing,
+
+    Closed,
+}
+
+impl ChannelState {
+    /// The name of this state, as used by `flash.system.MessageChannelState`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelState::Open => "open",
+            ChannelState::Closing => "closing",
+            ChannelState::Closed => "closed",
+        }
+    }
+}
+
+/// A value that may be passed between workers.
+#[derive(Clone)]
+pub enum SharedValue {
+    /// A value serialized as AMF3.
+    ///
+    /// Each worker that reads the value gets a copy of its own.
+    Amf(Vec<u8>),
+
+    /// A worker, which is passed by reference.
+    Worker(WorkerHandle),
+
+    /// A message channel, which is passed by reference.
+    MessageChannel(ChannelHandle),
+}
+
+/// The data of a worker that is shared by every thread that refers to it.
+pub struct WorkerData {
+    /// The worker that created this one, or `None` for the primordial worker
+    /// that the player was created with.
+    ///
+    /// A background worker stops running once its creator has gone away.
+    parent: Option<Weak<WorkerData>>,
+
+    /// The movie that a background worker runs.
+    ///
+    /// This is taken by the worker's thread when the worker starts.
+    swf: Mutex<Option<Vec<u8>>>,
+
+    state: Mutex<WorkerState>,
+
+    /// Values set with `Worker.setSharedProperty`.
+    shared_properties: Mutex<HashMap<String, SharedValue>>,
+}
+
+impl WorkerData {
+    /// Create the worker that a player starts out running in.
+    pub fn primordial() -> WorkerHandle {
+        Arc::new(Self {
+            parent: None,
+            swf: Mutex::new(None),
+            state: Mutex::new(WorkerState::Running),
+            shared_properties: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Create a background worker that will run the given movie once it is
+    /// started.
+    pub fn background(parent: &WorkerHandle, swf: Vec<u8>) -> WorkerHandle {
+        Arc::new(Self {
+            parent: Some(Arc::downgrade(parent)),
+            swf: Mutex::new(Some(swf)),
+            state: Mutex::new(WorkerState::New),
+            shared_properties: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_primordial(&self) -> bool {
+        self.parent.is_none()
+    }
+
+    pub fn state(&self) -> WorkerState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn shared_property(&self, key: &str) -> Option<SharedValue> {
+        self.shared_properties.lock().unwrap().get(key).cloned()
+    }
+
+    /// Set or, if `value` is `None`, remove a shared property.
+    pub fn set_shared_property(&self, key: String, value: Option<SharedValue>) {
+        let mut shared_properties = self.shared_properties.lock().unwrap();
+        match value {
+            Some(value) => shared_properties.insert(key, value),
+            None => shared_properties.remove(&key),
+        };
+    }
+
+    /// Start running a new background worker on a thread of its own.
+    ///
+    /// Returns `false` if the worker has already been started, or cannot be
+    /// started at all.
+    pub fn start(worker: &WorkerHandle) -> bool {
+        if !is_supported() || worker.is_primordial() {
+            return false;
+        }
+
+        let mut state = worker.state.lock().unwrap();
+        if *state != WorkerState::New {
+            return false;
+        }
+        *state = WorkerState::Running;
+        drop(state);
+
+        let swf = worker.swf.lock().unwrap().take().unwrap_or_default();
+        let worker = worker.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = Self::run(&worker, swf) {
+                log::error!("Background worker failed: {}", e);
+            }
+            worker.terminate();
+        });
+
+        true
+    }
+
+    /// Stop running a background worker.
+    ///
+    /// Returns `false` if the worker is primordial or already terminated.
+    pub fn terminate(&self) -> bool {
+        if self.is_primordial() {
+            return false;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let was_terminated = *state == WorkerState::Terminated;
+        *state = WorkerState::Terminated;
+        !was_terminated
+    }
+
+    /// Whether or not a background worker should keep running.
+    fn is_alive(&self) -> bool {
+        self.state() == WorkerState::Running
+            && self.parent.as_ref().and_then(Weak::upgrade).is_some()
+    }
+
+    /// Run the movie of a background worker until it is terminated.
+    fn run(worker: &WorkerHandle, swf: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+        let movie = SwfMovie::from_data(&swf, None)?;
+        let (mut executor, channel) = NullExecutor::new();
+        let player = Player::new(
+            Box::new(NullRenderer),
+            Box::new(NullAudioBackend::new()),
+            Box::new(NullNavigatorBackend::with_base_path("", channel)),
+            Box::new(NullInputBackend::new()),
+            Box::new(MemoryStorageBackend::default()),
+            Box::new(NullLocaleBackend::new()),
+            Box::new(NullLogBackend::new()),
+            Box::new(NullUiBackend::new()),
+            Box::new(NullVideoBackend::new()),
+        )?;
+
+        {
+            let mut player = player.lock().unwrap();
+            player.update(|context| context.avm2.set_current_worker(worker.clone()));
+            player.set_root_movie(Arc::new(movie));
+            player.set_is_playing(true);
+        }
+
+        let mut last_tick = Instant::now();
+        while worker.is_alive() {
+            let sleep_time = {
+                let mut player = player.lock().unwrap();
+                let now = Instant::now();
+                player.tick(now.duration_since(last_tick).as_secs_f64() * 1000.0);
+                last_tick = now;
+                player.time_til_next_frame()
+            };
+
+            executor.poll_all()?;
+            std::thread::sleep(sleep_time.max(Duration::from_millis(1)));
+        }
+
+        Ok(())
+    }
+}
+
+/// The data of a message channel that is shared by both of its ends.
+pub struct ChannelData {
+    /// The worker that sends messages over this channel.
+    sender: WorkerHandle,
+
+    /// The worker that receives messages from this channel.
+    receiver: WorkerHandle,
+
+    state: Mutex<ChannelState>,
+
+    /// Messages that have been sent but not yet received.
+    messages: Mutex<VecDeque<SharedValue>>,
+
+    /// Signalled whenever a message is sent.
+    message_sent: Condvar,
+}
+
+impl ChannelData {
+    pub fn new(sender: WorkerHandle, receiver: WorkerHandle) -> ChannelHandle {
+        Arc::new(Self {
+            sender,
+            receiver,
+            state: Mutex::new(ChannelState::Open),
+            messages: Mutex::new(VecDeque::new()),
+            message_sent: Condvar::new(),
+        })
+    }
+
+    pub fn sender(&self) -> &WorkerHandle {
+        &self.sender
+    }
+
+    pub fn receiver(&self) -> &WorkerHandle {
+        &self.receiver
+    }
+
+    pub fn state(&self) -> ChannelState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn message_available(&self) -> bool {
+        !self.messages.lock().unwrap().is_empty()
+    }
+
+    /// The number of messages waiting to be received.
+    fn pending_messages(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    /// Queue a message for the receiver.
+    ///
+    /// Returns `false` if the channel has been closed.
+    pub fn send(&self, message: SharedValue) -> bool {
+        if self.state() != ChannelState::Open {
+            return false;
+        }
+
+        self.messages.lock().unwrap().push_back(message);
+        self.message_sent.notify_all();
+        true
+    }
+
+    /// Block until a message is waiting to be received, or until `timeout`
+    /// has passed.
+    ///
+    /// Returns `false` if no message arrived in time.
+    pub fn wait_for_message(&self, timeout: Duration) -> bool {
+        let messages = self.messages.lock().unwrap();
+        let (messages, _) = self
+            .message_sent
+            .wait_timeout_while(messages, timeout, |messages| messages.is_empty())
+            .unwrap();
+        !messages.is_empty()
+    }
+
+    /// Take the oldest message that has not been received yet.
+    pub fn receive(&self) -> Option<SharedValue> {
+        let message = self.messages.lock().unwrap().pop_front();
+
+        let mut state = self.state.lock().unwrap();
+        if *state == ChannelState::Closing && !self.message_available() {
+            *state = ChannelState::Closed;
+        }
+
+        message
+    }
+
+    /// Stop accepting messages.
+    ///
+    /// Messages that were already sent may still be received.
+    pub fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        if *state == ChannelState::Open {
+            *state = if self.message_available() {
+                ChannelState::Closing
+            } else {
+                ChannelState::Closed
+            };
+        }
+    }
+}
+
+/// An AVM2 `Worker` object, along with the state it last reported.
+struct WorkerObject<'gc> {
+    object: Avm2Object<'gc>,
+    handle: WorkerHandle,
+    state: WorkerState,
+}
+
+/// An AVM2 `MessageChannel` object, along with the state it last reported.
+struct ChannelObject<'gc> {
+    object: Avm2Object<'gc>,
+    handle: ChannelHandle,
+    state: ChannelState,
+    pending_messages: usize,
+}
+
+/// The workers and message channels known to a player.
+pub struct Workers<'gc> {
+    /// The worker that this player is running.
+    current: WorkerHandle,
+
+    /// Every worker that has been given an AVM2 object in this player.
+    workers: Vec<WorkerObject<'gc>>,
+
+    /// Every message channel that has been given an AVM2 object in this
+    /// player.
+    channels: Vec<ChannelObject<'gc>>,
+}
+
+unsafe impl<'gc> Collect for Workers<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        for worker in &self.workers {
+            worker.object.trace(cc);
+        }
+        for channel in &self.channels {
+            channel.object.trace(cc);
+        }
+    }
+}
+
+impl<'gc> Workers<'gc> {
+    pub fn new() -> Self {
+        Self {
+            current: WorkerData::primordial(),
+            workers: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// The worker that this player is running.
+    pub fn current(&self) -> &WorkerHandle {
+        &self.current
+    }
+
+    /// Make this player run as a given worker.
+    ///
+    /// This must happen before any AVM2 objects have been registered.
+    pub fn set_current(&mut self, worker: WorkerHandle) {
+        self.current = worker;
+    }
+
+    /// Find the AVM2 object of a worker, if it has one.
+    pub fn worker_object(&self, handle: &WorkerHandle) -> Option<Avm2Object<'gc>> {
+        self.workers
+            .iter()
+            .find(|w| Arc::ptr_eq(&w.handle, handle))
+            .map(|w| w.object)
+    }
+
+    /// Find the worker represented by an AVM2 object.
+    pub fn worker_handle(&self, object: Avm2Object<'gc>) -> Option<WorkerHandle> {
+        self.workers
+            .iter()
+            .find(|w| Avm2Object::ptr_eq(w.object, object))
+            .map(|w| w.handle.clone())
+    }
+
+    /// Register the AVM2 object of a worker.
+    pub fn add_worker(&mut self, object: Avm2Object<'gc>, handle: WorkerHandle) {
+        let state = handle.state();
+        self.workers.push(WorkerObject {
+            object,
+            handle,
+            state,
+        });
+    }
+
+    /// Find the AVM2 object of a message channel, if it has one.
+    pub fn channel_object(&self, handle: &ChannelHandle) -> Option<Avm2Object<'gc>> {
+        self.channels
+            .iter()
+            .find(|c| Arc::ptr_eq(&c.handle, handle))
+            .map(|c| c.object)
+    }
+
+    /// Find the message channel represented by an AVM2 object.
+    pub fn channel_handle(&self, object: Avm2Object<'gc>) -> Option<ChannelHandle> {
+        self.channels
+            .iter()
+            .find(|c| Avm2Object::ptr_eq(c.object, object))
+            .map(|c| c.handle.clone())
+    }
+
+    /// Register the AVM2 object of a message channel.
+    pub fn add_channel(&mut self, object: Avm2Object<'gc>, handle: ChannelHandle) {
+        let state = handle.state();
+        let pending_messages = handle.pending_messages();
+        self.channels.push(ChannelObject {
+            object,
+            handle,
+            state,
+            pending_messages,
+        });
+    }
+
+    /// The message channels that this player's worker receives from.
+    pub fn receiving_channels(&self) -> Vec<ChannelHandle> {
+        self.channels
+            .iter()
+            .filter(|c| Arc::ptr_eq(&c.handle.receiver, &self.current))
+            .map(|c| c.handle.clone())
+            .collect()
+    }
+
+    /// Notice any changes made by other workers, and list the events that
+    /// should be fired to report them.
+    ///
+    /// `workerState` fires when a worker changes state, `channelState` when a
+    /// message channel does, and `channelMessage` when new messages arrive at
+    /// a channel this worker receives from.
+    pub fn poll(&mut self) -> Vec<(Avm2Object<'gc>, &'static str)> {
+        let mut events = Vec::new();
+
+        for worker in &mut self.workers {
+            let state = worker.handle.state();
+            if state != worker.state {
+                worker.state = state;
+                events.push((worker.object, "workerState"));
+            }
+        }
+
+        for channel in &mut self.channels {
+            let state = channel.handle.state();
+            if state != channel.state {
+                channel.state = state;
+                events.push((channel.object, "channelState"));
+            }
+
+            let pending_messages = channel.handle.pending_messages();
+            if pending_messages > channel.pending_messages
+                && Arc::ptr_eq(&channel.handle.receiver, &self.current)
+            {
+                events.push((channel.object, "channelMessage"));
+            }
+            channel.pending_messages = pending_messages;
+        }
+
+        events
+    }
+}
+
+impl<'gc> Default for Workers<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,21 +3,35 @@
 //! Trace output can be compared with correct output from the official Flash Payer.
 
 use approx::assert_relative_eq;
+use indexmap::IndexMap;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::LogBackend;
-use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::navigator::{
+    NavigationMethod, NavigatorBackend, NullExecutor, NullNavigatorBackend, OwnedFuture,
+    RequestOptions, Response,
+};
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::{ContextMenuItem, NullUiBackend};
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::backend::{
-    audio::NullAudioBackend, input::NullInputBackend, render::NullRenderer,
+    audio::{sound_info::SoundEnvelope, NullAudioBackend},
+    input::{MouseButton, MouseCursor, NullInputBackend},
+    render::{
+        decode_define_bits_jpeg, decode_define_bits_lossless, determine_jpeg_tag_format,
+        BitmapFormat, JpegTagFormat, NullRenderer,
+    },
 };
 use ruffle_core::context::UpdateContext;
+use ruffle_core::events::{KeyCode, MouseWheelDelta};
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
-use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::loader::Error as LoaderError;
+use ruffle_core::property_map::PropertyMap;
+use ruffle_core::tag_utils::{SwfMovie, Symbol, SymbolKind, UnsupportedTag};
+use ruffle_core::{GcPacing, GotoSnapshotSettings, Player, PlayerEvent};
 use std::cell::RefCell;
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -70,6 +84,7 @@ macro_rules! swf_tests_approx {
 // Inside the folder is expected to be "test.swf" and "output.txt" with the correct output.
 swf_tests! {
     (add_property, "avm1/add_property", 1),
+    (alpha_nesting, "avm1/alpha_nesting", 1),
     (as_transformed_flag, "avm1/as_transformed_flag", 3),
     (as_broadcaster, "avm1/as_broadcaster", 1),
     (as_broadcaster_initialize, "avm1/as_broadcaster_initialize", 1),
@@ -118,6 +133,7 @@ swf_tests! {
     (custom_clip_methods, "avm1/custom_clip_methods", 3),
     (delete, "avm1/delete", 3),
     (selection, "avm1/selection", 1),
+    (selection_focus, "avm1/selection_focus", 1),
     (default_names, "avm1/default_names", 6),
     (array_trivial, "avm1/array_trivial", 1),
     (array_concat, "avm1/array_concat", 1),
@@ -142,7 +158,7 @@ swf_tests! {
     (has_own_property, "avm1/has_own_property", 1),
     (extends_chain, "avm1/extends_chain", 1),
     (is_prototype_of, "avm1/is_prototype_of", 1),
-    #[ignore] (string_coercion, "avm1/string_coercion", 1),
+    (string_coercion, "avm1/string_coercion", 1),
     (lessthan_swf4, "avm1/lessthan_swf4", 1),
     (lessthan2_swf5, "avm1/lessthan2_swf5", 1),
     (lessthan2_swf6, "avm1/lessthan2_swf6", 1),
@@ -163,6 +179,7 @@ swf_tests! {
     (on_construct, "avm1/on_construct", 1),
     (set_variable_scope, "avm1/set_variable_scope", 1),
     (slash_syntax, "avm1/slash_syntax", 2),
+    (slash_syntax_nested, "avm1/slash_syntax_nested", 2),
     (strictequals_swf6, "avm1/strictequals_swf6", 1),
     (string_methods, "avm1/string_methods", 1),
     (string_ops_swf6, "avm1/string_ops_swf6", 1),
@@ -195,6 +212,11 @@ swf_tests! {
     (xml_inspect_parsexml, "avm1/xml_inspect_parsexml", 1),
     (funky_function_calls, "avm1/funky_function_calls", 1),
     (undefined_to_string_swf6, "avm1/undefined_to_string_swf6", 1),
+    (swf5_string_coercion, "avm1/swf5_string_coercion", 1),
+    (swf6_string_coercion, "avm1/swf6_string_coercion", 1),
+    (swf7_string_coercion, "avm1/swf7_string_coercion", 1),
+    (swf5_object_coercion, "avm1/swf5_object_coercion", 1),
+    (swf6_object_coercion, "avm1/swf6_object_coercion", 1),
     (define_function2_preload, "avm1/define_function2_preload", 1),
     (define_function2_preload_order, "avm1/define_function2_preload_order", 1),
     (mcl_as_broadcaster, "avm1/mcl_as_broadcaster", 1),
@@ -206,10 +228,16 @@ swf_tests! {
     (loadmovienum, "avm1/loadmovienum", 2),
     (loadmovie_registerclass, "avm1/loadmovie_registerclass", 2),
     (loadmovie_method, "avm1/loadmovie_method", 2),
+    (localconnection_avm2, "avm1/localconnection_avm2", 3),
+    (video_screen, "avm1/video_screen", 4),
+    (morph_shape2, "avm1/morph_shape2", 4),
+    (morph_shape_hit_test, "avm1/morph_shape_hit_test", 3),
     (unloadmovie, "avm1/unloadmovie", 11),
     (unloadmovienum, "avm1/unloadmovienum", 11),
     (unloadmovie_method, "avm1/unloadmovie_method", 11),
     (mcl_loadclip, "avm1/mcl_loadclip", 11),
+    (loadmovie_image, "avm1/loadmovie_image", 4),
+    (import_assets, "avm1/import_assets", 3),
     (mcl_unloadclip, "avm1/mcl_unloadclip", 11),
     (mcl_getprogress, "avm1/mcl_getprogress", 6),
     (load_vars, "avm1/load_vars", 2),
@@ -217,6 +245,7 @@ swf_tests! {
     (loadvariablesnum, "avm1/loadvariablesnum", 3),
     (loadvariables_method, "avm1/loadvariables_method", 3),
     (xml_load, "avm1/xml_load", 1),
+    (xml_send_and_load, "avm1/xml_send_and_load", 5),
     (with_return, "avm1/with_return", 1),
     (watch, "avm1/watch", 1),
     #[ignore] (watch_virtual_property, "avm1/watch_virtual_property", 1),
@@ -246,6 +275,13 @@ swf_tests! {
     (edittext_html_entity, "avm1/edittext_html_entity", 1),
     #[ignore] (edittext_html_roundtrip, "avm1/edittext_html_roundtrip", 1),
     (edittext_newline_stripping, "avm1/edittext_newline_stripping", 1),
+    (edittext_define_font4, "avm1/edittext_define_font4", 1),
+    (edittext_kerning, "avm1/edittext_kerning", 1),
+    (textfield_anti_alias, "avm1/textfield_anti_alias", 1),
+    #[cfg(feature = "lzma")] (lzma_compressed, "avm1/lzma_compressed", 1),
+    (debug_tags, "avm1/debug_tags", 1),
+    (tag_error_recovery, "avm1/tag_error_recovery", 2),
+    (wait_for_frame, "avm1/wait_for_frame", 4),
     (define_local, "avm1/define_local", 1),
     (textfield_properties, "avm1/textfield_properties", 1),
     (textfield_variable, "avm1/textfield_variable", 8),
@@ -287,13 +323,24 @@ swf_tests! {
     (date_set_year, "avm1/date/setYear", 1),
     (this_scoping, "avm1/this_scoping", 1),
     (bevel_filter, "avm1/bevel_filter", 1),
+    (convolution_filter, "avm1/convolution_filter", 1),
     (as3_hello_world, "avm2/hello_world", 1),
     (as3_function_call, "avm2/function_call", 1),
     (as3_function_call_via_call, "avm2/function_call_via_call", 1),
+    (as3_closure_capture, "avm2/closure_capture", 1),
     (as3_constructor_call, "avm2/constructor_call", 1),
     (as3_class_methods, "avm2/class_methods", 1),
     (as3_es3_inheritance, "avm2/es3_inheritance", 1),
     (as3_es4_inheritance, "avm2/es4_inheritance", 1),
+    (as3_final_class_verify, "avm2/final_class_verify", 1),
+    (as3_class_init_order, "avm2/class_init_order", 1),
+    (as3_text_snapshot, "avm2/text_snapshot", 2),
+    (as3_textfield_restrict, "avm2/textfield_restrict", 1),
+    (as3_textfield_maxchars, "avm2/textfield_maxchars", 1),
+    (as3_textfield_anti_alias, "avm2/textfield_anti_alias", 1),
+    (as3_lazy_script_init, "avm2/lazy_script_init", 1),
+    (as3_sound_extract, "avm2/sound_extract", 1),
+    (as3_stage_invalidate, "avm2/stage_invalidate", 3),
     (as3_stored_properties, "avm2/stored_properties", 1),
     (as3_virtual_properties, "avm2/virtual_properties", 1),
     (as3_es4_oop_prototypes, "avm2/es4_oop_prototypes", 1),
@@ -319,8 +366,11 @@ swf_tests! {
     (as3_if_strictne, "avm2/if_strictne", 1),
     (as3_strict_equality, "avm2/strict_equality", 1),
     (as3_es4_interfaces, "avm2/es4_interfaces", 1),
+    (as3_interface_diamond, "avm2/interface_diamond", 1),
+    (as3_namespace_access, "avm2/namespace_access", 1),
     (as3_istype, "avm2/istype", 1),
     (as3_instanceof, "avm2/instanceof", 1),
+    (as3_coerce_reference, "avm2/coerce_reference", 1),
     (as3_truthiness, "avm2/truthiness", 1),
     (as3_falsiness, "avm2/falsiness", 1),
     (as3_boolean_negation, "avm2/boolean_negation", 1),
@@ -344,6 +394,14 @@ swf_tests! {
     (conflicting_instance_names, "avm1/conflicting_instance_names", 6),
     (button_children, "avm1/button_children", 1),
     (transform, "avm1/transform", 1),
+    (scale9_grid, "avm1/scale9_grid", 1),
+    (place_object_3, "avm1/place_object_3", 2),
+    (loadbitmap, "avm1/loadbitmap", 1),
+    (bitmap_formats, "avm1/bitmap_formats", 1),
+    (mask_hittest_nested, "avm1/mask_hittest_nested", 1),
+    (mask_hittest_siblings, "avm1/mask_hittest_siblings", 1),
+    (as3_scale9_grid, "avm2/scale9_grid", 1),
+    (quality, "avm1/quality", 1),
     (target_path, "avm1/target_path", 1),
     (remove_movie_clip, "avm1/remove_movie_clip", 1),
     (as3_add, "avm2/add", 1),
@@ -408,6 +466,8 @@ swf_tests! {
     (as3_scene_constr, "avm2/scene_constr", 5),
     (as3_movieclip_currentscene, "avm2/movieclip_currentscene", 5),
     (as3_movieclip_scenes, "avm2/movieclip_scenes", 5),
+    (as3_goto_scene, "avm2/goto_scene", 2),
+    (as3_scene_labels, "avm2/scene_labels", 7),
     (as3_movieclip_play, "avm2/movieclip_play", 5),
     (as3_movieclip_constr, "avm2/movieclip_constr", 1),
     (as3_lazyinit, "avm2/lazyinit", 1),
@@ -434,6 +494,26 @@ swf_tests! {
     (button_order, "avm1/button_order", 1),
     (as3_displayobjectcontainer_stopallmovieclips, "avm2/displayobjectcontainer_stopallmovieclips", 2),
     (as3_displayobjectcontainer_timelineinstance, "avm2/displayobjectcontainer_timelineinstance", 6),
+    (as3_sampledataevent, "avm2/sampledataevent", 5),
+    (as3_new_sprite, "avm2/new_sprite", 1),
+    (as3_bitmap_display, "avm2/bitmap_display", 1),
+    (as3_bitmapdata_draw, "avm2/bitmapdata_draw", 1),
+    (as3_bitmapdata_apply_filter, "avm2/bitmapdata_apply_filter", 1),
+    (as3_color_matrix_filter, "avm2/color_matrix_filter", 1),
+    (as3_localconnection, "avm2/localconnection", 3),
+    (as3_addframescript, "avm2/addframescript", 3),
+    (as3_loader_avm1, "avm2/loader_avm1", 3),
+    (as3_urlloader_text, "avm2/urlloader_text", 2),
+    (as3_urlloader_variables, "avm2/urlloader_variables", 2),
+    (as3_urlvariables, "avm2/urlvariables", 1),
+    (as3_eventdispatcher_subclass, "avm2/eventdispatcher_subclass", 1),
+    (as3_dxns, "avm2/dxns", 1),
+    (as3_xml_descendants, "avm2/xml_descendants", 1),
+    (as3_xml_normalize, "avm2/xml_normalize", 1),
+    (as3_xml_namespace_declarations, "avm2/xml_namespace_declarations", 1),
+    (as3_regexp_exec, "avm2/regexp_exec", 1),
+    (as3_filereference_user_gesture, "avm2/filereference_user_gesture", 1),
+    (as3_try_catch, "avm2/try_catch", 1),
 }
 
 // TODO: These tests have some inaccuracies currently, so we use approx_eq to test that numeric values are close enough.
@@ -525,154 +605,1999 @@ fn timeout_avm1() -> Result<(), Error> {
     )
 }
 
-/// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
-/// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
-/// test failures to show nice diffs.
-/// Courtesy of https://github.com/colin-kiegel/rust-pretty-assertions/issues/24
-#[derive(PartialEq, Eq)]
-#[doc(hidden)]
-pub struct PrettyString<'a>(pub &'a str);
+/// Snapshots taken with every frame must not change the outcome of any rewind.
+#[test]
+fn goto_rewind_every_frame_snapshots() -> Result<(), Error> {
+    let every_frame = GotoSnapshotSettings {
+        interval: 1,
+        min_total_frames: 0,
+    };
+    for &(name, num_frames) in &[
+        ("goto_rewind1", 4),
+        ("goto_rewind2", 5),
+        ("goto_rewind3", 2),
+        ("goto_execution_order", 3),
+        ("goto_execution_order2", 2),
+    ] {
+        test_swf(
+            &format!("tests/swfs/avm1/{}/test.swf", name),
+            num_frames,
+            &format!("tests/swfs/avm1/{}/output.txt", name),
+            |player| {
+                player.lock().unwrap().set_goto_snapshots(every_frame);
+                Ok(())
+            },
+            |_| Ok(()),
+        )?;
+    }
+    Ok(())
+}
 
-/// Make diff to display string as multi-line string
-impl<'a> std::fmt::Debug for PrettyString<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(self.0)
+/// Scrubs backwards through a 1000 frame timeline, with and without snapshots,
+/// and checks how many frames the rewinds had to replay.
+#[test]
+fn goto_rewind_snapshots() -> Result<(), Error> {
+    for &(interval, frames_replayed) in &[(0, 95372), (50, 3472), (1, 1100)] {
+        let settings = GotoSnapshotSettings {
+            interval,
+            ..Default::default()
+        };
+        test_swf(
+            "tests/swfs/avm1/goto_rewind_snapshots/test.swf",
+            1,
+            "tests/swfs/avm1/goto_rewind_snapshots/output.txt",
+            |player| {
+                player.lock().unwrap().set_goto_snapshots(settings);
+                Ok(())
+            },
+            |player| {
+                assert_eq!(
+                    player.lock().unwrap().rewind_frames_replayed(),
+                    frames_replayed,
+                    "frames replayed with a snapshot interval of {}",
+                    interval
+                );
+                Ok(())
+            },
+        )?;
     }
+    Ok(())
 }
 
-macro_rules! assert_eq {
-    ($left:expr, $right:expr) => {
-        pretty_assertions::assert_eq!(PrettyString($left.as_ref()), PrettyString($right.as_ref()));
-    };
-    ($left:expr, $right:expr, $message:expr) => {
-        pretty_assertions::assert_eq!(
-            PrettyString($left.as_ref()),
-            PrettyString($right.as_ref()),
-            $message
-        );
+/// Loops a clip with fifty children through its ten frames twenty times.
+#[test]
+fn timeline_loop() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/timeline_loop/test.swf",
+        200,
+        "tests/swfs/avm1/timeline_loop/output.txt",
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+/// Hit tests a star with five hundred edges at a thousand points on each of
+/// ten frames.
+#[test]
+fn hittest_shapeflag_benchmark() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/hittest_shapeflag_benchmark/test.swf",
+        15,
+        "tests/swfs/avm1/hittest_shapeflag_benchmark/output.txt",
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+/// Assigns the same text to two hundred text fields every frame, then renders
+/// them repeatedly. Each field should only be laid out once, when its text is
+/// first set.
+#[test]
+fn edittext_layout_cache_benchmark() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/edittext_layout_cache_benchmark/test.swf",
+        1,
+        "tests/swfs/avm1/edittext_layout_cache_benchmark/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            assert_eq!(player.text_relayouts(), 200);
+
+            for _ in 0..11 {
+                player.run_frame();
+            }
+            for _ in 0..3 {
+                player.render();
+            }
+            assert_eq!(player.text_relayouts(), 200);
+            Ok(())
+        },
+    )
+}
+
+/// Draws three shapes into three thousand clips, and checks that each distinct
+/// shape is only registered once.
+#[test]
+fn shape_cache() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/shape_cache/test.swf",
+        3,
+        "tests/swfs/avm1/shape_cache/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for _ in 0..2 {
+                player.render();
+                // The squares, lenses and triangles, and the changed square.
+                assert_eq!(player.shape_cache().len(), 4);
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Renders a stopped movie every frame, and checks that only the frames after
+/// a timer or an ExternalInterface call have changed it are rendered.
+#[test]
+fn render_skipping() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/render_skipping/test.swf",
+        1,
+        "tests/swfs/avm1/render_skipping/output.txt",
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .add_external_interface(Box::new(ExternalInterfaceTestProvider::new()));
+            Ok(())
+        },
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.render();
+            assert_eq!(player.skipped_frames(), 0);
+
+            // Nothing changes while the movie is stopped.
+            player.run_frame();
+            assert!(!player.needs_render());
+            player.render();
+            player.update_timers(500.0);
+            player.render();
+            assert_eq!(player.skipped_frames(), 2);
+
+            // The interval moves the box.
+            player.update_timers(1000.0);
+            assert!(player.needs_render());
+            player.render();
+            player.run_frame();
+            player.render();
+            assert_eq!(player.skipped_frames(), 3);
+
+            // Reading the box from outside doesn't change it, but moving it does.
+            player.call_internal_interface("getBox", vec![]);
+            player.render();
+            player.call_internal_interface("moveBox", vec![30.into()]);
+            assert!(player.needs_render());
+            player.render();
+            player.render();
+            assert_eq!(player.skipped_frames(), 5);
+
+            // Resizing the viewport redraws everything.
+            player.set_viewport_dimensions(100, 100);
+            player.render();
+            assert_eq!(player.skipped_frames(), 5);
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn define_bits_jpeg_3() -> Result<(), Error> {
+    let data = std::fs::read("tests/swfs/avm1/define_bits_jpeg_3/test.swf")?;
+    let swf = swf::read_swf(&data[..])?;
+    let jpeg = swf
+        .tags
+        .iter()
+        .find_map(|tag| match tag {
+            swf::Tag::DefineBitsJpeg3(jpeg) => Some(jpeg),
+            _ => None,
+        })
+        .ok_or("Missing DefineBitsJPEG3 tag")?;
+
+    let bitmap = decode_define_bits_jpeg(&jpeg.data, Some(&jpeg.alpha_data))?;
+    assert_eq!((bitmap.width, bitmap.height), (8, 8));
+    let rgba = match bitmap.data {
+        BitmapFormat::Rgba(rgba) => rgba,
+        BitmapFormat::Rgb(_) => panic!("Alpha data was not applied"),
     };
+
+    // The opaque top row keeps the JPEG color, while the color of the
+    // translucent pixels below is clamped to their alpha.
+    assert_eq!(rgba[0..4], [144, 144, 144, 255]);
+    assert_eq!(rgba[7 * 4..8 * 4], [144, 144, 144, 255]);
+    assert_eq!(rgba[8 * 4..9 * 4], [64, 64, 64, 64]);
+    assert_eq!(rgba[63 * 4..64 * 4], [64, 64, 64, 64]);
+    Ok(())
 }
 
-/// Loads an SWF and runs it through the Ruffle core for a number of frames.
-/// Tests that the trace output matches the given expected output.
-fn test_swf(
-    swf_path: &str,
-    num_frames: u32,
-    expected_output_path: &str,
-    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-) -> Result<(), Error> {
-    let mut expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
+#[test]
+fn define_bits_lossless() -> Result<(), Error> {
+    let data = std::fs::read("tests/swfs/avm1/define_bits_lossless/test.swf")?;
+    let swf = swf::read_swf(&data[..])?;
+    let mut bitmaps = swf.tags.iter().filter_map(|tag| match tag {
+        swf::Tag::DefineBitsLossless(bitmap) => Some(bitmap),
+        _ => None,
+    });
 
-    // Strip a trailing newline if it has one.
-    if expected_output.ends_with('\n') {
-        expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
-    }
+    let decode = |bitmap: Option<&swf::DefineBitsLossless>| -> Result<_, Error> {
+        let bitmap = decode_define_bits_lossless(bitmap.ok_or("Missing DefineBitsLossless tag")?)?;
+        match bitmap.data {
+            BitmapFormat::Rgba(rgba) => Ok((bitmap.width, bitmap.height, rgba)),
+            BitmapFormat::Rgb(_) => panic!("Lossless bitmaps should decode to RGBA"),
+        }
+    };
 
-    let trace_log = run_swf(swf_path, num_frames, before_start, before_end)?;
+    // 32-bit premultiplied ARGB from DefineBitsLossless2.
+    let (width, height, rgba) = decode(bitmaps.next())?;
+    assert_eq!((width, height), (2, 2));
     assert_eq!(
-        trace_log, expected_output,
-        "ruffle output != flash player output"
+        rgba,
+        [255, 0, 0, 255, 0, 128, 0, 128, 0, 0, 0, 0, 16, 32, 48, 64]
     );
 
+    // 15-bit RGB from DefineBitsLossless, with rows padded to 32 bits.
+    let (width, height, rgba) = decode(bitmaps.next())?;
+    assert_eq!((width, height), (3, 2));
+    assert_eq!(
+        rgba[0..12],
+        [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255]
+    );
+    assert_eq!(
+        rgba[12..24],
+        [255, 255, 255, 255, 0, 0, 0, 255, 132, 132, 132, 255]
+    );
     Ok(())
 }
 
-/// Loads an SWF and runs it through the Ruffle core for a number of frames.
-/// Tests that the trace output matches the given expected output.
-/// If a line has a floating point value, it will be compared approxinmately using the given epsilon.
-fn test_swf_approx(
-    swf_path: &str,
-    num_frames: u32,
-    expected_output_path: &str,
-    approx_assert_fn: impl Fn(f64, f64),
-    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-) -> Result<(), Error> {
-    let trace_log = run_swf(swf_path, num_frames, before_start, before_end)?;
-    let mut expected_data = std::fs::read_to_string(expected_output_path)?;
+#[test]
+fn define_bits_formats() -> Result<(), Error> {
+    let data = std::fs::read("tests/swfs/avm1/bitmap_formats/test.swf")?;
+    let swf = swf::read_swf(&data[..])?;
+    let mut jpegs = swf.tags.iter().filter_map(|tag| match tag {
+        swf::Tag::DefineBitsJpeg2 { jpeg_data, .. } => {
+            Some(decode_define_bits_jpeg(jpeg_data, None))
+        }
+        swf::Tag::DefineBitsJpeg3(jpeg) => {
+            Some(decode_define_bits_jpeg(&jpeg.data, Some(&jpeg.alpha_data)))
+        }
+        _ => None,
+    });
 
-    // Strip a trailing newline if it has one.
-    if expected_data.ends_with('\n') {
-        expected_data = expected_data[0..expected_data.len() - "\n".len()].to_string();
+    // Grayscale PNGs with alpha are expanded to RGBA and premultiplied.
+    let bitmap = jpegs.next().ok_or("Missing grayscale PNG")??;
+    assert_eq!((bitmap.width, bitmap.height), (2, 1));
+    match bitmap.data {
+        BitmapFormat::Rgba(rgba) => assert_eq!(rgba, [100, 100, 100, 128, 50, 50, 50, 255]),
+        BitmapFormat::Rgb(_) => panic!("Grayscale PNG alpha was not applied"),
     }
 
-    std::assert_eq!(
-        trace_log.lines().count(),
-        expected_data.lines().count(),
-        "# of lines of output didn't match"
-    );
+    // Palettized PNGs get their alpha from the tRNS chunk.
+    let bitmap = jpegs.next().ok_or("Missing palettized PNG")??;
+    assert_eq!((bitmap.width, bitmap.height), (2, 1));
+    match bitmap.data {
+        BitmapFormat::Rgba(rgba) => assert_eq!(rgba, [64, 0, 0, 64, 0, 0, 255, 255]),
+        BitmapFormat::Rgb(_) => panic!("Palettized PNG alpha was not applied"),
+    }
 
-    for (actual, expected) in trace_log.lines().zip(expected_data.lines()) {
-        // If these are numbers, compare using approx_eq.
-        if let (Ok(actual), Ok(expected)) = (actual.parse::<f64>(), expected.parse::<f64>()) {
-            // NaNs should be able to pass in an approx test.
-            if actual.is_nan() && expected.is_nan() {
-                continue;
-            }
+    // A DefineBitsJPEG3 without alpha data is opaque.
+    let bitmap = jpegs.next().ok_or("Missing DefineBitsJPEG3")??;
+    assert_eq!((bitmap.width, bitmap.height), (8, 8));
+    match bitmap.data {
+        BitmapFormat::Rgb(rgb) => assert!(rgb.iter().all(|&c| c == 144)),
+        BitmapFormat::Rgba(_) => panic!("Empty alpha data should be ignored"),
+    }
 
-            // TODO: Lower this epsilon as the accuracy of the properties improves.
-            // if let Some(relative_epsilon) = relative_epsilon {
-            //     assert_relative_eq!(
-            //         actual,
-            //         expected,
-            //         epsilon = absolute_epsilon,
-            //         max_relative = relative_epsilon
-            //     );
-            // } else {
-            //     assert_abs_diff_eq!(actual, expected, epsilon = absolute_epsilon);
-            // }
-            approx_assert_fn(actual, expected);
-        } else {
-            assert_eq!(actual, expected);
+    // Colormapped DefineBitsLossless2 skips the padding at the end of each row.
+    let lossless = swf
+        .tags
+        .iter()
+        .find_map(|tag| match tag {
+            swf::Tag::DefineBitsLossless(bitmap) => Some(bitmap),
+            _ => None,
+        })
+        .ok_or("Missing DefineBitsLossless2 tag")?;
+    let bitmap = decode_define_bits_lossless(lossless)?;
+    assert_eq!((bitmap.width, bitmap.height), (3, 2));
+    match bitmap.data {
+        BitmapFormat::Rgba(rgba) => assert_eq!(
+            rgba,
+            [
+                128, 0, 0, 128, 0, 0, 255, 255, 128, 0, 0, 128, //
+                0, 0, 255, 255, 0, 0, 255, 255, 128, 0, 0, 128,
+            ]
+        ),
+        BitmapFormat::Rgb(_) => panic!("Lossless bitmaps should decode to RGBA"),
+    }
+    Ok(())
+}
+
+#[test]
+fn loaded_image_formats() -> Result<(), Error> {
+    let png = std::fs::read("tests/swfs/avm1/loadmovie_image/image.png")?;
+    let gif = std::fs::read("tests/swfs/avm1/loadmovie_image/animated.gif")?;
+    assert_eq!(determine_jpeg_tag_format(&png), JpegTagFormat::Png);
+    assert_eq!(determine_jpeg_tag_format(&gif), JpegTagFormat::Gif);
+
+    // Interlaced, palettized PNGs are deinterlaced and get their alpha from
+    // the tRNS chunk.
+    let bitmap = decode_define_bits_jpeg(&png, None)?;
+    assert_eq!((bitmap.width, bitmap.height), (3, 2));
+    match bitmap.data {
+        BitmapFormat::Rgba(rgba) => assert_eq!(
+            rgba,
+            [
+                255, 0, 0, 255, 0, 128, 0, 128, 0, 0, 255, 255, //
+                0, 0, 255, 255, 0, 128, 0, 128, 255, 0, 0, 255,
+            ]
+        ),
+        BitmapFormat::Rgb(_) => panic!("Palettized PNG alpha was not applied"),
+    }
+
+    // Only the first frame of an animated GIF is decoded. It covers part of
+    // the image, and the rest is transparent.
+    let bitmap = decode_define_bits_jpeg(&gif, None)?;
+    assert_eq!((bitmap.width, bitmap.height), (4, 2));
+    match bitmap.data {
+        BitmapFormat::Rgba(rgba) => {
+            assert_eq!(
+                rgba[0..16],
+                [0, 0, 0, 0, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0]
+            );
+            assert!(rgba[16..].iter().all(|&c| c == 0));
         }
+        BitmapFormat::Rgb(_) => panic!("GIFs should decode to RGBA"),
     }
+
+    let broken = std::fs::read("tests/swfs/avm1/loadmovie_image/broken.png")?;
+    assert!(decode_define_bits_jpeg(&broken, None).is_err());
     Ok(())
 }
 
-/// Loads an SWF and runs it through the Ruffle core for a number of frames.
-/// Tests that the trace output matches the given expected output.
-fn run_swf(
-    swf_path: &str,
-    num_frames: u32,
-    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
-) -> Result<String, Error> {
-    let base_path = Path::new(swf_path).parent().unwrap();
-    let (mut executor, channel) = NullExecutor::new();
-    let movie = SwfMovie::from_path(swf_path)?;
-    let frame_time = 1000.0 / movie.header().frame_rate as f64;
-    let trace_output = Rc::new(RefCell::new(Vec::new()));
+#[test]
+fn edittext_device_font_fallback() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/edittext_device_font_fallback/test.swf",
+        1,
+        "tests/swfs/avm1/edittext_device_font_fallback/output.txt",
+        |player| {
+            // Only "Arial" is installed, so every other font falls back to it
+            // or to the bundled device font.
+            let font = std::fs::read("tests/swfs/avm1/edittext_device_font_fallback/font.otf")?;
+            player
+                .lock()
+                .unwrap()
+                .ui_mut()
+                .downcast_mut::<NullUiBackend>()
+                .unwrap()
+                .add_device_font("Arial", font);
+            Ok(())
+        },
+        |_| Ok(()),
+    )
+}
 
-    let player = Player::new(
-        Box::new(NullRenderer),
-        Box::new(NullAudioBackend::new()),
-        Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
-        Box::new(NullInputBackend::new()),
-        Box::new(MemoryStorageBackend::default()),
-        Box::new(NullLocaleBackend::new()),
-        Box::new(TestLogBackend::new(trace_output.clone())),
-    )?;
-    player.lock().unwrap().set_root_movie(Arc::new(movie));
-    player
-        .lock()
-        .unwrap()
-        .set_max_execution_duration(Duration::from_secs(200));
+#[test]
+fn button_conditions_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/button_conditions/test.swf",
+        1,
+        "tests/swfs/avm1/button_conditions/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let events = vec![
+                (
+                    "// keyPress a",
+                    vec![PlayerEvent::TextInput { codepoint: 'a' }],
+                ),
+                (
+                    "// keyPress <Left>",
+                    vec![PlayerEvent::KeyDown {
+                        key_code: KeyCode::Left,
+                    }],
+                ),
+                (
+                    "// keyPress b",
+                    vec![PlayerEvent::TextInput { codepoint: 'b' }],
+                ),
+                (
+                    "// over push",
+                    vec![PlayerEvent::MouseMove { x: 150.0, y: 150.0 }],
+                ),
+                (
+                    "// press push",
+                    vec![PlayerEvent::MouseDown {
+                        x: 150.0,
+                        y: 150.0,
+                        button: MouseButton::Left,
+                    }],
+                ),
+                (
+                    "// drag out of push",
+                    vec![PlayerEvent::MouseMove { x: 50.0, y: 50.0 }],
+                ),
+                (
+                    "// drag over push",
+                    vec![PlayerEvent::MouseMove { x: 150.0, y: 150.0 }],
+                ),
+                (
+                    "// drag over menu",
+                    vec![PlayerEvent::MouseMove { x: 350.0, y: 150.0 }],
+                ),
+                (
+                    "// drag out of menu",
+                    vec![PlayerEvent::MouseMove { x: 50.0, y: 50.0 }],
+                ),
+                (
+                    "// drag over menu",
+                    vec![PlayerEvent::MouseMove { x: 350.0, y: 150.0 }],
+                ),
+                (
+                    "// release over menu",
+                    vec![PlayerEvent::MouseUp {
+                        x: 350.0,
+                        y: 150.0,
+                        button: MouseButton::Left,
+                    }],
+                ),
+                (
+                    "// out of menu",
+                    vec![PlayerEvent::MouseMove { x: 50.0, y: 50.0 }],
+                ),
+                (
+                    "// click push",
+                    vec![
+                        PlayerEvent::MouseMove { x: 150.0, y: 150.0 },
+                        PlayerEvent::MouseDown {
+                            x: 150.0,
+                            y: 150.0,
+                            button: MouseButton::Left,
+                        },
+                        PlayerEvent::MouseUp {
+                            x: 150.0,
+                            y: 150.0,
+                            button: MouseButton::Left,
+                        },
+                    ],
+                ),
+            ];
 
-    before_start(player.clone())?;
+            for (label, events) in events {
+                player.log_backend().avm_trace(label);
+                for event in events {
+                    player.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
 
-    for _ in 0..num_frames {
-        player.lock().unwrap().run_frame();
-        player.lock().unwrap().update_timers(frame_time);
-        executor.poll_all().unwrap();
-    }
+#[test]
+fn set_tab_index() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/set_tab_index/test.swf",
+        1,
+        "tests/swfs/avm1/set_tab_index/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for _ in 0..5 {
+                player.log_backend().avm_trace("// Tab");
+                player.handle_event(PlayerEvent::KeyDown {
+                    key_code: KeyCode::Tab,
+                });
+                player.handle_event(PlayerEvent::KeyUp {
+                    key_code: KeyCode::Tab,
+                });
+            }
+            Ok(())
+        },
+    )
+}
 
-    before_end(player)?;
+#[test]
+fn mouse_wheel_scroll() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/mouse_wheel_scroll/test.swf",
+        1,
+        "tests/swfs/avm1/mouse_wheel_scroll/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let wheel = |delta| PlayerEvent::MouseWheel { delta };
+            let events = vec![
+                (
+                    "// wheel outside the text field",
+                    vec![wheel(MouseWheelDelta::Lines(-3.0))],
+                ),
+                (
+                    "// wheel down over the text field",
+                    vec![
+                        PlayerEvent::MouseMove { x: 50.0, y: 15.0 },
+                        wheel(MouseWheelDelta::Lines(-3.0)),
+                    ],
+                ),
+                (
+                    "// wheel down again",
+                    vec![wheel(MouseWheelDelta::Lines(-3.0))],
+                ),
+                (
+                    "// wheel down past the end",
+                    vec![wheel(MouseWheelDelta::Lines(-10.0))],
+                ),
+                ("// wheel up", vec![wheel(MouseWheelDelta::Lines(5.0))]),
+                (
+                    "// wheel up by pixels",
+                    vec![wheel(MouseWheelDelta::Pixels(300.0))],
+                ),
+                (
+                    "// wheel up past the start",
+                    vec![wheel(MouseWheelDelta::Lines(20.0))],
+                ),
+            ];
 
-    executor.block_all().unwrap();
+            for (label, events) in events {
+                player.log_backend().avm_trace(label);
+                for event in events {
+                    player.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
 
-    let trace = trace_output.borrow().join("\n");
-    Ok(trace)
+#[test]
+fn context_menu_right_click() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/context_menu_right_click/test.swf",
+        1,
+        "tests/swfs/avm1/context_menu_right_click/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let click = |x, y, button| {
+                vec![
+                    PlayerEvent::MouseMove { x, y },
+                    PlayerEvent::MouseDown { x, y, button },
+                    PlayerEvent::MouseUp { x, y, button },
+                ]
+            };
+            let events = vec![
+                (
+                    "// right click beside the box",
+                    click(300.0, 300.0, MouseButton::Right),
+                ),
+                (
+                    "// right click on the box",
+                    click(50.0, 50.0, MouseButton::Right),
+                ),
+                (
+                    "// left click on the box",
+                    click(50.0, 50.0, MouseButton::Left),
+                ),
+            ];
+
+            for (label, events) in events {
+                player.log_backend().avm_trace(label);
+                for event in events {
+                    player.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn context_menu_items() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/context_menu_items/test.swf",
+        1,
+        "tests/swfs/avm1/context_menu_items/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let player = &mut *player;
+            let right_click = |player: &mut Player, label, x, y| -> Vec<ContextMenuItem> {
+                player.log_backend().avm_trace(label);
+                player.handle_event(PlayerEvent::MouseMove { x, y });
+                player.handle_event(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                player.handle_event(PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                player
+                    .ui()
+                    .downcast_ref::<NullUiBackend>()
+                    .unwrap()
+                    .context_menu()
+                    .to_vec()
+            };
+            let item = |caption: &str, enabled, separator_before, checked| ContextMenuItem {
+                caption: caption.to_string(),
+                enabled,
+                separator_before,
+                checked,
+            };
+
+            let menu = right_click(player, "// right click the stage", 300.0, 300.0);
+            assert_eq!(
+                menu,
+                vec![
+                    item("Say hello", true, false, false),
+                    item("Disabled", false, false, false),
+                    item("After separator", true, true, false),
+                    item("Play", true, true, false),
+                    item("Rewind", false, false, false),
+                    item("Forward", true, false, false),
+                    item("Back", false, false, false),
+                ]
+            );
+            player.log_backend().avm_trace("// choose the first item");
+            player.on_context_menu_select(0);
+
+            right_click(
+                player,
+                "// right click the stage and choose the disabled item",
+                300.0,
+                300.0,
+            );
+            player.on_context_menu_select(1);
+
+            right_click(
+                player,
+                "// right click the stage and choose Forward",
+                300.0,
+                300.0,
+            );
+            player.on_context_menu_select(5);
+
+            let menu = right_click(player, "// right click the stage on frame 2", 300.0, 300.0);
+            assert_eq!(
+                menu[3..],
+                [
+                    item("Play", true, true, false),
+                    item("Rewind", true, false, false),
+                    item("Forward", false, false, false),
+                    item("Back", true, false, false),
+                ]
+            );
+
+            let menu = right_click(player, "// right click the box", 50.0, 50.0);
+            assert_eq!(menu, vec![item("Box item", true, false, false)]);
+            player.log_backend().avm_trace("// choose the box item");
+            player.on_context_menu_select(0);
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn stage_display_state() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/stage_display_state/test.swf",
+        1,
+        "tests/swfs/avm1/stage_display_state/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for label in &["// click the box", "// click the box again"] {
+                player.log_backend().avm_trace(label);
+                player.handle_event(PlayerEvent::MouseMove { x: 50.0, y: 50.0 });
+                player.handle_event(PlayerEvent::MouseDown {
+                    x: 50.0,
+                    y: 50.0,
+                    button: MouseButton::Left,
+                });
+                player.handle_event(PlayerEvent::MouseUp {
+                    x: 50.0,
+                    y: 50.0,
+                    button: MouseButton::Left,
+                });
+            }
+
+            // The request made without user input is ignored.
+            let ui = player.ui().downcast_ref::<NullUiBackend>().unwrap();
+            assert_eq!(ui.fullscreen_requests(), &[true, false]);
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn stream_sound_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/stream_sound/test.swf",
+        0,
+        "tests/swfs/avm1/stream_sound/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for _ in 0..12 {
+                player.run_frame();
+                let drift = match player.stream_drift() {
+                    Some(drift) => format!(
+                        "drift: {:.2} frames{}",
+                        drift.frames,
+                        if drift.is_hypothetical {
+                            " (hypothetical)"
+                        } else {
+                            ""
+                        }
+                    ),
+                    None => "no stream".to_string(),
+                };
+                player.log_backend().avm_trace(&drift);
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn stream_sound_stop_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/stream_sound_stop/test.swf",
+        0,
+        "tests/swfs/avm1/stream_sound_stop/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for _ in 0..4 {
+                player.run_frame();
+                let audio = player.audio().downcast_ref::<NullAudioBackend>().unwrap();
+                let message = format!(
+                    "started {} stream(s), {} playing",
+                    audio.started_streams().len(),
+                    audio.playing_stream_count()
+                );
+                player.log_backend().avm_trace(&message);
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn sound_complete_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/sound_complete/test.swf",
+        0,
+        "tests/swfs/avm1/sound_complete/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.run_frame();
+            for _ in 0..3 {
+                player
+                    .audio_mut()
+                    .downcast_mut::<NullAudioBackend>()
+                    .unwrap()
+                    .advance_sounds(250.0);
+                player.run_frame();
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn sound_envelope_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/sound_envelope/test.swf",
+        2,
+        "tests/swfs/avm1/sound_envelope/output.txt",
+        |_| Ok(()),
+        |player| {
+            let player = player.lock().unwrap();
+            let started_sounds = player
+                .audio()
+                .downcast_ref::<NullAudioBackend>()
+                .unwrap()
+                .started_sounds()
+                .to_vec();
+            for (_, settings) in started_sounds {
+                player.log_backend().avm_trace(&format!(
+                    "started sound: in {:?}, out {:?}, {} loops",
+                    settings.in_sample, settings.out_sample, settings.num_loops
+                ));
+                let points = settings.envelope.unwrap_or_default();
+                let envelope = SoundEnvelope::new(&points);
+                for sample in &[0, 22050, 44100, 88200] {
+                    player.log_backend().avm_trace(&format!(
+                        "volume at {}: {:?}",
+                        sample,
+                        envelope.volume_at(*sample)
+                    ));
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn soundtransform_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/soundtransform/test.swf",
+        1,
+        "tests/swfs/avm2/soundtransform/output.txt",
+        |_| Ok(()),
+        |player| {
+            let player = player.lock().unwrap();
+            let audio = player.audio().downcast_ref::<NullAudioBackend>().unwrap();
+            for (_, transform) in audio.sound_transforms() {
+                player.log_backend().avm_trace(&format!(
+                    "sound transform: volume {:?}, pan {:?}",
+                    transform.volume, transform.pan
+                ));
+            }
+            let master = audio.master_transform();
+            player.log_backend().avm_trace(&format!(
+                "master transform: volume {:?}, pan {:?}",
+                master.volume, master.pan
+            ));
+            player.log_backend().avm_trace(&format!(
+                "stopped all sounds {} time(s)",
+                audio.stop_all_count()
+            ));
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn sample_data_sine_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/sample_data_sine/test.swf",
+        3,
+        "tests/swfs/avm2/sample_data_sine/output.txt",
+        |_| Ok(()),
+        |player| {
+            let player = player.lock().unwrap();
+            let audio = player.audio().downcast_ref::<NullAudioBackend>().unwrap();
+            let samples = audio.generated_samples();
+            assert_eq!(samples.len(), 4096);
+            for (i, sample) in samples.iter().enumerate() {
+                let expected =
+                    ((i as f64 * 2.0 * std::f64::consts::PI * 440.0 / 44100.0).sin() * 0.5) as f32;
+                assert!(
+                    (sample[0] - expected).abs() < 1e-6 && (sample[1] - expected).abs() < 1e-6,
+                    "sample {} is {:?}, expected {}",
+                    i,
+                    sample,
+                    expected
+                );
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn mouse_events_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/mouse_events/test.swf",
+        1,
+        "tests/swfs/avm2/mouse_events/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+            player_locked.handle_event(PlayerEvent::MouseDown {
+                x: 20.0,
+                y: 30.0,
+                button: MouseButton::Left,
+            });
+            player_locked.handle_event(PlayerEvent::MouseUp {
+                x: 20.0,
+                y: 30.0,
+                button: MouseButton::Left,
+            });
+
+            // Releasing the mouse without having pressed it isn't a click.
+            player_locked.handle_event(PlayerEvent::MouseUp {
+                x: 40.0,
+                y: 50.0,
+                button: MouseButton::Left,
+            });
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn filereference_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/filereference/test.swf",
+        1,
+        "tests/swfs/avm2/filereference/output.txt",
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .ui_mut()
+                .downcast_mut::<NullUiBackend>()
+                .unwrap()
+                .set_auto_select(Some(PathBuf::from(
+                    "tests/swfs/avm2/filereference/input.txt",
+                )));
+            Ok(())
+        },
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+
+            // File dialogs can only be opened in response to user input.
+            player_locked.handle_event(PlayerEvent::MouseDown {
+                x: 10.0,
+                y: 10.0,
+                button: MouseButton::Left,
+            });
+            player_locked.handle_event(PlayerEvent::MouseUp {
+                x: 10.0,
+                y: 10.0,
+                button: MouseButton::Left,
+            });
+
+            let saved_files = player_locked
+                .ui()
+                .downcast_ref::<NullUiBackend>()
+                .unwrap()
+                .saved_files()
+                .to_vec();
+            for (name, data) in saved_files {
+                player_locked.log_backend().avm_trace(&format!(
+                    "Saved {}: {}",
+                    name,
+                    String::from_utf8_lossy(&data)
+                ));
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn scroll_rect_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/scroll_rect/test.swf",
+        1,
+        "tests/swfs/avm2/scroll_rect/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+
+            // Click inside the scroll rectangle, then where the clipped child
+            // would be without it. The second click scrolls the container.
+            for &(x, y) in &[(10.0, 10.0), (160.0, 10.0), (10.0, 10.0), (160.0, 10.0)] {
+                player_locked.handle_event(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                });
+                player_locked.handle_event(PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                });
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn mouse_buttons_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/mouse_buttons/test.swf",
+        1,
+        "tests/swfs/avm2/mouse_buttons/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let down = |button| PlayerEvent::MouseDown {
+                x: 10.0,
+                y: 10.0,
+                button,
+            };
+            let up = |button| PlayerEvent::MouseUp {
+                x: 10.0,
+                y: 10.0,
+                button,
+            };
+            let events = vec![
+                (
+                    "// left click",
+                    vec![down(MouseButton::Left), up(MouseButton::Left)],
+                ),
+                (
+                    "// right click",
+                    vec![down(MouseButton::Right), up(MouseButton::Right)],
+                ),
+                (
+                    "// middle click",
+                    vec![down(MouseButton::Middle), up(MouseButton::Middle)],
+                ),
+                (
+                    "// right button released after a left press",
+                    vec![
+                        down(MouseButton::Left),
+                        up(MouseButton::Right),
+                        up(MouseButton::Left),
+                    ],
+                ),
+            ];
+
+            for (label, events) in events {
+                player.log_backend().avm_trace(label);
+                for event in events {
+                    player.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn double_click_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/double_click/test.swf",
+        1,
+        "tests/swfs/avm2/double_click/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let click = |x, y| {
+                vec![
+                    PlayerEvent::MouseMove { x, y },
+                    PlayerEvent::MouseDown {
+                        x,
+                        y,
+                        button: MouseButton::Left,
+                    },
+                    PlayerEvent::MouseUp {
+                        x,
+                        y,
+                        button: MouseButton::Left,
+                    },
+                ]
+            };
+            let clicks = vec![
+                (
+                    "// double click with doubleClickEnabled",
+                    vec![click(50.0, 50.0), click(50.0, 50.0)],
+                ),
+                ("// third click", vec![click(50.0, 50.0)]),
+                (
+                    "// double click without doubleClickEnabled",
+                    vec![click(250.0, 50.0), click(250.0, 50.0)],
+                ),
+            ];
+
+            for (label, clicks) in clicks {
+                player.log_backend().avm_trace(label);
+                for event in clicks.into_iter().flatten() {
+                    player.handle_event(event);
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn sprite_button_mode_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/sprite_button_mode/test.swf",
+        1,
+        "tests/swfs/avm2/sprite_button_mode/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let moves = [
+                (50.0, 50.0, MouseCursor::Hand),
+                (250.0, 50.0, MouseCursor::Arrow),
+                (50.0, 50.0, MouseCursor::Hand),
+                (400.0, 300.0, MouseCursor::Arrow),
+            ];
+
+            for &(x, y, cursor) in moves.iter() {
+                player.handle_event(PlayerEvent::MouseMove { x, y });
+                let input = player.input().downcast_ref::<NullInputBackend>().unwrap();
+                assert_eq!(input.mouse_cursor(), cursor);
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn context_menu_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/context_menu/test.swf",
+        1,
+        "tests/swfs/avm2/context_menu/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let player = &mut *player;
+            let right_click = |player: &mut Player, label, x, y| -> Vec<ContextMenuItem> {
+                player.log_backend().avm_trace(label);
+                player.handle_event(PlayerEvent::MouseMove { x, y });
+                player.handle_event(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                player.handle_event(PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                player
+                    .ui()
+                    .downcast_ref::<NullUiBackend>()
+                    .unwrap()
+                    .context_menu()
+                    .to_vec()
+            };
+            let item = |caption: &str, enabled, separator_before| ContextMenuItem {
+                caption: caption.to_string(),
+                enabled,
+                separator_before,
+                checked: false,
+            };
+
+            let menu = right_click(player, "// right click the inner sprite", 50.0, 50.0);
+            assert_eq!(
+                menu,
+                vec![item("First", true, false), item("Second", false, true)]
+            );
+            player.log_backend().avm_trace("// choose the first item");
+            player.on_context_menu_select(0);
+
+            right_click(
+                player,
+                "// right click the inner sprite and choose the disabled item",
+                50.0,
+                50.0,
+            );
+            player.on_context_menu_select(1);
+
+            let menu = right_click(player, "// right click the stage", 300.0, 300.0);
+            assert!(menu.is_empty());
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn shared_object_avm2() -> Result<(), Error> {
+    let swf_path = "tests/swfs/avm2/shared_object/test.swf";
+    let mut expected_output =
+        std::fs::read_to_string("tests/swfs/avm2/shared_object/output.txt")?.replace("\r\n", "\n");
+
+    // Strip a trailing newline if it has one.
+    if expected_output.ends_with('\n') {
+        expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
+    }
+
+    // Only allow a small amount of storage, so that flushes can fail.
+    let limit_storage = |player: Arc<Mutex<Player>>| -> Result<(), Error> {
+        player
+            .lock()
+            .unwrap()
+            .storage_mut()
+            .downcast_mut::<MemoryStorageBackend>()
+            .unwrap()
+            .max_size = Some(10000);
+        Ok(())
+    };
+
+    // The first run stores the data...
+    let mut saved = HashMap::new();
+    let first_run = run_swf(swf_path, 1, limit_storage, |player| {
+        saved = player
+            .lock()
+            .unwrap()
+            .storage()
+            .downcast_ref::<MemoryStorageBackend>()
+            .unwrap()
+            .map
+            .clone();
+        Ok(())
+    })?;
+
+    // ...and the second run loads it again.
+    let second_run = run_swf(
+        swf_path,
+        1,
+        |player| {
+            limit_storage(player.clone())?;
+            player
+                .lock()
+                .unwrap()
+                .storage_mut()
+                .downcast_mut::<MemoryStorageBackend>()
+                .unwrap()
+                .map = saved;
+            Ok(())
+        },
+        |_| Ok(()),
+    )?;
+
+    assert_eq!(
+        format!("{}\n{}", first_run, second_run),
+        expected_output,
+        "ruffle output != flash player output"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn class_alias_avm2() -> Result<(), Error> {
+    let swf_path = "tests/swfs/avm2/class_alias/test.swf";
+    let mut expected_output =
+        std::fs::read_to_string("tests/swfs/avm2/class_alias/output.txt")?.replace("\r\n", "\n");
+
+    // Strip a trailing newline if it has one.
+    if expected_output.ends_with('\n') {
+        expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
+    }
+
+    // The first run stores a typed object in a shared object...
+    let mut saved = HashMap::new();
+    let first_run = run_swf(
+        swf_path,
+        1,
+        |_| Ok(()),
+        |player| {
+            saved = player
+                .lock()
+                .unwrap()
+                .storage()
+                .downcast_ref::<MemoryStorageBackend>()
+                .unwrap()
+                .map
+                .clone();
+            Ok(())
+        },
+    )?;
+
+    // ...and the second run reads it back.
+    let second_run = run_swf(
+        swf_path,
+        1,
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .storage_mut()
+                .downcast_mut::<MemoryStorageBackend>()
+                .unwrap()
+                .map = saved;
+            Ok(())
+        },
+        |_| Ok(()),
+    )?;
+
+    assert_eq!(
+        format!("{}\n{}", first_run, second_run),
+        expected_output,
+        "ruffle output != flash player output"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn movieclip_get_url_post() -> Result<(), Error> {
+    test_swf_with(
+        "tests/swfs/avm1/movieclip_get_url_post/test.swf",
+        1,
+        "tests/swfs/avm1/movieclip_get_url_post/output.txt",
+        RunOptions::default().with_navigation_trace(),
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn worker_avm2() -> Result<(), Error> {
+    let worker_swf = std::fs::read("tests/swfs/avm2/worker/worker.swf")?;
+    test_swf_with(
+        "tests/swfs/avm2/worker/test.swf",
+        2,
+        "tests/swfs/avm2/worker/output.txt",
+        RunOptions::default().before_load(|player| {
+            player.set_worker_shared_byte_array("workerSwf", worker_swf);
+        }),
+        |player| {
+            // The movie starts the worker as it loads. Wait for its message
+            // before running any frames, so that the first frame sees it.
+            assert!(
+                player
+                    .lock()
+                    .unwrap()
+                    .wait_for_worker_messages(Duration::from_secs(10)),
+                "the worker didn't send its message"
+            );
+            Ok(())
+        },
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn navigate_to_url_avm2() -> Result<(), Error> {
+    test_swf_with(
+        "tests/swfs/avm2/navigate_to_url/test.swf",
+        1,
+        "tests/swfs/avm2/navigate_to_url/output.txt",
+        RunOptions::default().with_navigation_trace(),
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn swf_movie_symbols() -> Result<(), Error> {
+    let movie = SwfMovie::from_path("tests/swfs/avm1/symbols/test.swf")?;
+
+    let symbol = |character_id, export_name: Option<&str>, kind| Symbol {
+        character_id,
+        export_name: export_name.map(str::to_string),
+        kind,
+    };
+    assert_eq!(
+        movie.symbols(),
+        vec![
+            symbol(1, Some("bitmap"), SymbolKind::Bitmap),
+            symbol(2, Some("sound"), SymbolKind::Sound),
+            symbol(3, Some("font"), SymbolKind::Font),
+            symbol(4, Some("video"), SymbolKind::Video),
+            symbol(5, Some("com.example.Clip"), SymbolKind::MovieClip),
+            symbol(6, None, SymbolKind::MovieClip),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn swf_movie_unsupported_tags() -> Result<(), Error> {
+    let movie = SwfMovie::from_path("tests/swfs/avm1/debug_tags/test.swf")?;
+
+    let tag = |tag_code, count| UnsupportedTag { tag_code, count };
+    assert_eq!(
+        movie.unsupported_tags(),
+        &[
+            tag(41, 1),
+            tag(77, 1),
+            tag(64, 1),
+            tag(63, 1),
+            tag(13, 1),
+            tag(1000, 2),
+        ][..]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn swf_movie_stream() -> Result<(), Error> {
+    test_swf_stream(
+        "tests/swfs/avm1/swf_movie_stream/test.swf",
+        "tests/swfs/avm1/swf_movie_stream/output.txt",
+    )
+}
+
+#[test]
+fn wait_for_frame_stream() -> Result<(), Error> {
+    test_swf_stream(
+        "tests/swfs/avm1/wait_for_frame/test.swf",
+        "tests/swfs/avm1/wait_for_frame/output_stream.txt",
+    )
+}
+
+/// Loads an SWF in small chunks, running a frame after each one, and ensures
+/// that the output from `trace` matches `expected_output_path`.
+fn test_swf_stream(swf_path: &str, expected_output_path: &str) -> Result<(), Error> {
+    test_swf_with(
+        swf_path,
+        2,
+        expected_output_path,
+        RunOptions::default().streamed(128),
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn loader_http_error_avm2() -> Result<(), Error> {
+    let mut requests = None;
+    test_swf_with(
+        "tests/swfs/avm2/loader_http_error/test.swf",
+        2,
+        "tests/swfs/avm2/loader_http_error/output.txt",
+        RunOptions::default().with_navigator(|navigator| {
+            let navigator = navigator
+                .with_response("http://example.com/missing.swf", Response::with_status(404));
+            requests = Some(navigator.requests());
+            navigator
+        }),
+        |_| Ok(()),
+        |_| Ok(()),
+    )?;
+
+    let requests = requests.unwrap();
+    let requests = requests.borrow();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url, "http://example.com/missing.swf");
+    assert_eq!(requests[0].method, NavigationMethod::GET);
+    assert!(requests[0].body.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn xml_send_and_load_echo() -> Result<(), Error> {
+    let mut requests = None;
+    test_swf_with(
+        "tests/swfs/avm1/xml_send_and_load_echo/test.swf",
+        2,
+        "tests/swfs/avm1/xml_send_and_load_echo/output.txt",
+        RunOptions::default().with_navigator(|navigator| {
+            let navigator = navigator.with_echo("http://example.com/echo");
+            requests = Some(navigator.requests());
+            navigator
+        }),
+        |_| Ok(()),
+        |_| Ok(()),
+    )?;
+
+    let requests = requests.unwrap();
+    let requests = requests.borrow();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url, "http://example.com/echo");
+    assert_eq!(requests[0].method, NavigationMethod::POST);
+    assert_eq!(
+        requests[0].body,
+        Some((
+            b"<message from=\"ruffle\"><text>Hello</text></message>".to_vec(),
+            "application/x-www-form-urlencoded".to_string()
+        ))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn load_progress_callback() -> Result<(), Error> {
+    let data = std::fs::read("tests/swfs/avm1/swf_movie_stream/test.swf")?;
+    let progress = Arc::new(Mutex::new(Vec::new()));
+
+    let callback_progress = progress.clone();
+    run_swf_with(
+        "tests/swfs/avm1/swf_movie_stream/test.swf",
+        0,
+        RunOptions::default()
+            .with_navigator(|navigator| navigator.with_chunk_size(128))
+            .fetched(),
+        |player| {
+            player.lock().unwrap().set_load_progress_callback(Box::new(
+                move |bytes_loaded, bytes_total| {
+                    callback_progress
+                        .lock()
+                        .unwrap()
+                        .push((bytes_loaded, bytes_total));
+                },
+            ));
+            Ok(())
+        },
+        |_| Ok(()),
+    )?;
+
+    // The callback fires once for each chunk, with the total length known up front.
+    let bytes_total = data.len() as u64;
+    let expected: Vec<_> = (1..=data.chunks(128).len() as u64)
+        .map(|chunks| ((chunks * 128).min(bytes_total), Some(bytes_total)))
+        .collect();
+    assert_eq!(*progress.lock().unwrap(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn mcl_unloadclip_in_progress() -> Result<(), Error> {
+    // The child movie arrives in small chunks, so that it can be unloaded
+    // while it is still downloading.
+    test_swf_with(
+        "tests/swfs/avm1/mcl_unloadclip_in_progress/test.swf",
+        10,
+        "tests/swfs/avm1/mcl_unloadclip_in_progress/output.txt",
+        RunOptions::default().with_navigator(|navigator| navigator.with_chunk_size(64)),
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn mcl_bytes_loaded() -> Result<(), Error> {
+    // The child movie arrives in small chunks, so that its progress can be
+    // seen while it is still downloading.
+    test_swf_with(
+        "tests/swfs/avm1/mcl_bytes_loaded/test.swf",
+        10,
+        "tests/swfs/avm1/mcl_bytes_loaded/output.txt",
+        RunOptions::default().with_navigator(|navigator| navigator.with_chunk_size(64)),
+        |_| Ok(()),
+        |_| Ok(()),
+    )
+}
+
+#[test]
+fn gc_stats() -> Result<(), Error> {
+    run_swf(
+        "tests/swfs/avm1/gc_stats/test.swf",
+        0,
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let initial = player.gc_stats();
+
+            // The first frame keeps thousands of objects alive.
+            player.run_frame();
+            let allocated = player.gc_stats();
+            assert!(allocated.total_allocated > initial.total_allocated);
+            assert_eq!(allocated.full_collects, 0);
+
+            // The second frame lets go of them, so a full collection frees them.
+            player.run_frame();
+            let before_collect = player.gc_stats();
+            player.collect_garbage();
+            let collected = player.gc_stats();
+            assert_eq!(collected.full_collects, 1);
+            assert!(collected.freed_by_last_collect > 0);
+            assert!(collected.total_allocated < before_collect.total_allocated);
+            assert_eq!(collected.allocated_since_last_collect(), 0);
+
+            // Frame pacing collects fully every other frame.
+            player.set_gc_pacing(GcPacing {
+                frame_interval: Some(2),
+                allocation_threshold: None,
+            });
+            for _ in 0..4 {
+                player.run_frame();
+            }
+            assert_eq!(player.gc_stats().full_collects, 3);
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn as3_system_gc() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/system_gc/test.swf",
+        1,
+        "tests/swfs/avm2/system_gc/output.txt",
+        |_| Ok(()),
+        |player| {
+            // The collection asked for by the movie is done once its script
+            // has finished.
+            assert_eq!(player.lock().unwrap().gc_stats().full_collects, 1);
+            Ok(())
+        },
+    )
+}
+
+/// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
+/// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
+/// test failures to show nice diffs.
+/// Courtesy of https://github.com/colin-kiegel/rust-pretty-assertions/issues/24
+#[derive(PartialEq, Eq)]
+#[doc(hidden)]
+pub struct PrettyString<'a>(pub &'a str);
+
+/// Make diff to display string as multi-line string
+impl<'a> std::fmt::Debug for PrettyString<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+macro_rules! assert_eq {
+    ($left:expr, $right:expr) => {
+        pretty_assertions::assert_eq!(PrettyString($left.as_ref()), PrettyString($right.as_ref()));
+    };
+    ($left:expr, $right:expr, $message:expr) => {
+        pretty_assertions::assert_eq!(
+            PrettyString($left.as_ref()),
+            PrettyString($right.as_ref()),
+            $message
+        );
+    };
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames.
+/// Tests that the trace output matches the given expected output.
+fn test_swf(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    test_swf_with(
+        swf_path,
+        num_frames,
+        expected_output_path,
+        RunOptions::default(),
+        before_start,
+        before_end,
+    )
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// with the given options.
+/// Tests that the trace output matches the given expected output.
+fn test_swf_with(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    options: RunOptions,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
+
+    // Strip a trailing newline if it has one.
+    if expected_output.ends_with('\n') {
+        expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
+    }
+
+    let trace_log = run_swf_with(swf_path, num_frames, options, before_start, before_end)?;
+    assert_eq!(
+        trace_log, expected_output,
+        "ruffle output != flash player output"
+    );
+
+    Ok(())
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames.
+/// Tests that the trace output matches the given expected output.
+/// If a line has a floating point value, it will be compared approxinmately using the given epsilon.
+fn test_swf_approx(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    approx_assert_fn: impl Fn(f64, f64),
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let trace_log = run_swf(swf_path, num_frames, before_start, before_end)?;
+    let mut expected_data = std::fs::read_to_string(expected_output_path)?;
+
+    // Strip a trailing newline if it has one.
+    if expected_data.ends_with('\n') {
+        expected_data = expected_data[0..expected_data.len() - "\n".len()].to_string();
+    }
+
+    std::assert_eq!(
+        trace_log.lines().count(),
+        expected_data.lines().count(),
+        "# of lines of output didn't match"
+    );
+
+    for (actual, expected) in trace_log.lines().zip(expected_data.lines()) {
+        // If these are numbers, compare using approx_eq.
+        if let (Ok(actual), Ok(expected)) = (actual.parse::<f64>(), expected.parse::<f64>()) {
+            // NaNs should be able to pass in an approx test.
+            if actual.is_nan() && expected.is_nan() {
+                continue;
+            }
+
+            // TODO: Lower this epsilon as the accuracy of the properties improves.
+            // if let Some(relative_epsilon) = relative_epsilon {
+            //     assert_relative_eq!(
+            //         actual,
+            //         expected,
+            //         epsilon = absolute_epsilon,
+            //         max_relative = relative_epsilon
+            //     );
+            // } else {
+            //     assert_abs_diff_eq!(actual, expected, epsilon = absolute_epsilon);
+            // }
+            approx_assert_fn(actual, expected);
+        } else {
+            assert_eq!(actual, expected);
+        }
+    }
+    Ok(())
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames.
+/// Tests that the trace output matches the given expected output.
+fn run_swf(
+    swf_path: &str,
+    num_frames: u32,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<String, Error> {
+    run_swf_with(
+        swf_path,
+        num_frames,
+        RunOptions::default(),
+        before_start,
+        before_end,
+    )
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// with the given options, and returns its trace output.
+fn run_swf_with(
+    swf_path: &str,
+    num_frames: u32,
+    options: RunOptions,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<String, Error> {
+    let base_path = Path::new(swf_path).parent().unwrap();
+    let (mut executor, channel) = NullExecutor::new();
+    let movie = SwfMovie::from_path(swf_path)?;
+    let frame_time = 1000.0 / movie.header().frame_rate as f64;
+    let trace_output = Rc::new(RefCell::new(Vec::new()));
+
+    let mut navigator = NullNavigatorBackend::with_base_path(base_path, channel);
+    if let Some(configure_navigator) = options.navigator {
+        navigator = configure_navigator(navigator);
+    }
+    let navigator: Box<dyn NavigatorBackend> = if options.navigation_trace {
+        Box::new(NavigationTraceBackend::new(navigator, trace_output.clone()))
+    } else {
+        Box::new(navigator)
+    };
+
+    let player = Player::new(
+        Box::new(NullRenderer),
+        Box::new(NullAudioBackend::new()),
+        navigator,
+        Box::new(NullInputBackend::new()),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(TestLogBackend::new(trace_output.clone())),
+        Box::new(NullUiBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
+    )?;
+    player
+        .lock()
+        .unwrap()
+        .set_max_execution_duration(Duration::from_secs(200));
+
+    if let Some(before_load) = options.before_load {
+        before_load(&mut player.lock().unwrap());
+    }
+
+    match options.movie_load {
+        MovieLoad::Whole => player.lock().unwrap().set_root_movie(Arc::new(movie)),
+        MovieLoad::Stream(chunk_size) => {
+            // Feed the movie in small chunks, running a frame after each one.
+            let data = std::fs::read(swf_path)?;
+            player
+                .lock()
+                .unwrap()
+                .begin_root_movie_stream(None, PropertyMap::new());
+            for chunk in data.chunks(chunk_size) {
+                player.lock().unwrap().push_root_movie_bytes(chunk)?;
+                player.lock().unwrap().run_frame();
+            }
+        }
+        MovieLoad::Fetch => {
+            let file_name = Path::new(swf_path).file_name().unwrap().to_string_lossy();
+            player
+                .lock()
+                .unwrap()
+                .fetch_root_movie(&file_name, PropertyMap::new());
+        }
+    }
+
+    before_start(player.clone())?;
+
+    for _ in 0..num_frames {
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        executor.poll_all().unwrap();
+    }
+
+    before_end(player)?;
+
+    executor.block_all().unwrap();
+
+    let trace = trace_output.borrow().join("\n");
+    Ok(trace)
+}
+
+/// How `run_swf_with` gives the movie to the player.
+enum MovieLoad {
+    /// The whole movie is set as the root movie before the first frame.
+    Whole,
+
+    /// The movie is pushed to the player in chunks of the given size, with a
+    /// frame run after each one.
+    Stream(usize),
+
+    /// The player fetches the movie through its navigator.
+    Fetch,
+}
+
+/// Options for `run_swf_with`, for tests that need more than the defaults of
+/// `run_swf`.
+struct RunOptions<'a> {
+    navigator: Option<Box<dyn FnOnce(NullNavigatorBackend) -> NullNavigatorBackend + 'a>>,
+    navigation_trace: bool,
+    movie_load: MovieLoad,
+    before_load: Option<Box<dyn FnOnce(&mut Player) + 'a>>,
+}
+
+impl<'a> Default for RunOptions<'a> {
+    fn default() -> Self {
+        Self {
+            navigator: None,
+            navigation_trace: false,
+            movie_load: MovieLoad::Whole,
+            before_load: None,
+        }
+    }
+}
+
+impl<'a> RunOptions<'a> {
+    /// Configure the navigator, which reads files relative to the SWF.
+    fn with_navigator(
+        mut self,
+        configure: impl FnOnce(NullNavigatorBackend) -> NullNavigatorBackend + 'a,
+    ) -> Self {
+        self.navigator = Some(Box::new(configure));
+        self
+    }
+
+    /// Write browser navigations and fetches made by the movie to the trace
+    /// output, alongside its own traces.
+    fn with_navigation_trace(mut self) -> Self {
+        self.navigation_trace = true;
+        self
+    }
+
+    /// Push the movie to the player in chunks of the given size.
+    fn streamed(mut self, chunk_size: usize) -> Self {
+        self.movie_load = MovieLoad::Stream(chunk_size);
+        self
+    }
+
+    /// Have the player fetch the movie through its navigator.
+    fn fetched(mut self) -> Self {
+        self.movie_load = MovieLoad::Fetch;
+        self
+    }
+
+    /// Prepare the player before the movie is loaded, which may already run
+    /// some of its scripts.
+    fn before_load(mut self, before_load: impl FnOnce(&mut Player) + 'a) -> Self {
+        self.before_load = Some(Box::new(before_load));
+        self
+    }
+}
+
+/// A navigator that writes every browser navigation and fetch to the trace
+/// output.
+struct NavigationTraceBackend {
+    inner: NullNavigatorBackend,
+    trace_output: Rc<RefCell<Vec<String>>>,
+}
+
+impl NavigationTraceBackend {
+    pub fn new(inner: NullNavigatorBackend, trace_output: Rc<RefCell<Vec<String>>>) -> Self {
+        Self {
+            inner,
+            trace_output,
+        }
+    }
+}
+
+impl NavigatorBackend for NavigationTraceBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        let window = window.unwrap_or_default();
+        let message = match vars_method {
+            Some((method, vars)) => {
+                let method = match method {
+                    NavigationMethod::GET => "GET",
+                    NavigationMethod::POST => "POST",
+                };
+                let vars: Vec<String> = vars
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect();
+                format!(
+                    "[navigate] {} {} in \"{}\": {}",
+                    method,
+                    url,
+                    window,
+                    vars.join("&")
+                )
+            }
+            None => format!("[navigate] {} in \"{}\"", url, window),
+        };
+        self.trace_output.borrow_mut().push(message);
+    }
+
+    fn fetch(
+        &self,
+        url: &str,
+        request_options: RequestOptions,
+    ) -> OwnedFuture<Vec<u8>, LoaderError> {
+        let method = match request_options.method() {
+            NavigationMethod::GET => "GET",
+            NavigationMethod::POST => "POST",
+        };
+        let mut message = format!("[fetch] {} {}", method, url);
+        if !request_options.headers().is_empty() {
+            let headers: Vec<String> = request_options
+                .headers()
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            message.push_str(&format!(" with headers {}", headers.join(", ")));
+        }
+        if let Some((body, mime)) = request_options.body() {
+            message.push_str(&format!(
+                " with body ({}): {}",
+                mime,
+                String::from_utf8_lossy(body)
+            ));
+        }
+        self.trace_output.borrow_mut().push(message);
+
+        self.inner.fetch(url, request_options)
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.inner.time_since_launch()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), LoaderError>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> std::borrow::Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
 }
 
 struct TestLogBackend {
@@ -0,0 +1,25 @@
+// Frame 1
+var parent = _root.createEmptyMovieClip("parent", 1);
+var child = parent.createEmptyMovieClip("child", 1);
+
+function traceAlphas(label) {
+	trace(label + ": parent=" + parent._alpha + " child=" + child._alpha + " effective=" + child._parent._alpha * child._alpha / 100);
+}
+
+traceAlphas("default");
+parent._alpha = 50;
+traceAlphas("parent 50");
+child._alpha = 50;
+traceAlphas("both 50");
+child._alpha = 12.5;
+traceAlphas("child 12.5");
+parent._alpha = 37.5;
+traceAlphas("parent 37.5");
+child._alpha = 150;
+traceAlphas("child 150");
+parent._alpha = -20;
+traceAlphas("parent -20");
+parent._alpha = "75";
+child._alpha = "abc";
+traceAlphas("parent \"75\", child \"abc\"");
+stop();
@@ -0,0 +1,25 @@
+// The library holds bitmaps in formats that need conversion when decoded:
+// "gray_alpha": DefineBitsJPEG2 with a 2x1 grayscale and alpha PNG.
+// "palette": DefineBitsJPEG2 with a 2x1 palettized PNG with a tRNS chunk.
+// "colormap": DefineBitsLossless2 with a 3x2 colormapped image. Its rows are
+//   padded to 4 bytes with indices that would be invalid.
+// "jpeg3": DefineBitsJPEG3 with an 8x8 gray JPEG and no alpha data.
+
+// Frame 1
+var pixels = {
+	gray_alpha: [[0, 0], [1, 0]],
+	palette: [[0, 0], [1, 0]],
+	colormap: [[0, 0], [1, 0], [2, 0], [0, 1], [2, 1]],
+	jpeg3: [[0, 0], [7, 7]]
+};
+var names = ["gray_alpha", "palette", "colormap", "jpeg3"];
+for (var i = 0; i < names.length; i++) {
+	var name = names[i];
+	var bmp = flash.display.BitmapData.loadBitmap(name);
+	trace(name + ": " + bmp.width + "x" + bmp.height + ", transparent: " + bmp.transparent);
+	for (var j = 0; j < pixels[name].length; j++) {
+		var x = pixels[name][j][0];
+		var y = pixels[name][j][1];
+		trace("  getPixel32(" + x + ", " + y + "): " + bmp.getPixel32(x, y));
+	}
+}
@@ -0,0 +1,44 @@
+// Two DefineButton2 buttons, each a 100x100 rectangle.
+// "push" is at (100, 100) and is tracked as a push button.
+// "menu" is at (300, 100) and is tracked as a menu item.
+// Each button has one condition action per state transition, with only that
+// transition's flag set, so that every transition is traced on its own.
+// The handlers below are listed in the order of the condition flags.
+// "menu" has the same handlers as "push", with "menu: " as the prefix, but no keyPress handlers.
+
+// Button "push"
+on (rollOver) {
+	trace("push: idleToOverUp");
+}
+on (rollOut) {
+	trace("push: overUpToIdle");
+}
+on (press) {
+	trace("push: overUpToOverDown");
+}
+on (release) {
+	trace("push: overDownToOverUp");
+}
+on (dragOut) {
+	trace("push: overDownToOutDown");
+}
+on (dragOver) {
+	trace("push: outDownToOverDown");
+}
+on (releaseOutside) {
+	trace("push: outDownToIdle");
+}
+// idleToOverDown, only set by "on (dragOver)" on a menu item.
+on (dragOver) {
+	trace("push: idleToOverDown");
+}
+// overDownToIdle, only set by "on (dragOut)" on a menu item.
+on (dragOut) {
+	trace("push: overDownToIdle");
+}
+on (keyPress "a") {
+	trace("push: keyPress a");
+}
+on (keyPress "<Left>") {
+	trace("push: keyPress <Left>");
+}
@@ -0,0 +1,39 @@
+// Frame 1
+stop();
+_root.createEmptyMovieClip("box", 1);
+box.beginFill(0xFF0000);
+box.moveTo(0, 0);
+box.lineTo(100, 0);
+box.lineTo(100, 100);
+box.lineTo(0, 100);
+box.lineTo(0, 0);
+box.endFill();
+
+menu = new ContextMenu(function(obj, menu) {
+	trace("menu onSelect: " + obj);
+	menu.customItems[0].caption = "Say hello";
+});
+menu.customItems.push(new ContextMenuItem("Hello", function(obj, item) {
+	trace("hello: " + obj + ", " + item.caption);
+}));
+menu.customItems.push(new ContextMenuItem("Disabled", function(obj, item) {
+	trace("disabled: " + obj + ", " + item.caption);
+}, false, false));
+menu.customItems.push(new ContextMenuItem("Invisible", function(obj, item) {
+	trace("invisible: " + obj + ", " + item.caption);
+}, false, true, false));
+menu.customItems.push(new ContextMenuItem("After separator", function(obj, item) {
+	trace("after separator: " + obj + ", " + item.caption);
+}, true));
+_root.menu = menu;
+
+boxMenu = new ContextMenu();
+boxMenu.hideBuiltInItems();
+boxMenu.customItems.push(new ContextMenuItem("Box item", function(obj, item) {
+	trace("box item: " + obj + ", " + item.caption);
+}));
+box.menu = boxMenu;
+
+// Frame 2
+trace("frame 2");
+stop();
@@ -0,0 +1,29 @@
+// Frame 1
+stop();
+_root.createEmptyMovieClip("box", 1);
+box.beginFill(0xFF0000);
+box.moveTo(0, 0);
+box.lineTo(100, 0);
+box.lineTo(100, 100);
+box.lineTo(0, 100);
+box.lineTo(0, 0);
+box.endFill();
+
+rootMenu = new ContextMenu(function(obj, menu) {
+	trace("root menu: " + obj + ", menu is rootMenu: " + (menu == rootMenu));
+});
+_root.menu = rootMenu;
+
+box.menu = new ContextMenu(function(obj, menu) {
+	trace("box menu: " + obj);
+});
+
+listener = {
+	onMouseDown: function() {
+		trace("onMouseDown");
+	},
+	onMouseUp: function() {
+		trace("onMouseUp");
+	}
+};
+Mouse.addListener(listener);
@@ -0,0 +1,29 @@
+// Frame 1
+var bmp = new flash.display.BitmapData(5, 5, false, 0x404040);
+bmp.setPixel(2, 2, 0xC0C0C0);
+var out = new flash.display.BitmapData(5, 5, false, 0);
+var rect = new flash.geom.Rectangle(0, 0, 5, 5);
+var point = new flash.geom.Point(0, 0);
+var filter = new flash.filters.ConvolutionFilter(3, 3, [0, -1, 0, -1, 4, -1, 0, -1, 0]);
+trace("matrixX: " + filter.matrixX);
+trace("matrixY: " + filter.matrixY);
+trace("matrix: " + filter.matrix);
+trace("divisor: " + filter.divisor);
+trace("bias: " + filter.bias);
+trace("preserveAlpha: " + filter.preserveAlpha);
+trace("clamp: " + filter.clamp);
+
+trace("applyFilter: " + out.applyFilter(bmp, rect, point, filter));
+trace("getPixel(2, 2): " + out.getPixel(2, 2));
+trace("getPixel(2, 1): " + out.getPixel(2, 1));
+trace("getPixel(0, 0): " + out.getPixel(0, 0));
+
+filter.bias = 128;
+trace("applyFilter: " + out.applyFilter(bmp, rect, point, filter));
+trace("getPixel(2, 2): " + out.getPixel(2, 2));
+trace("getPixel(2, 1): " + out.getPixel(2, 1));
+trace("getPixel(0, 0): " + out.getPixel(0, 0));
+
+var copy = filter.clone();
+trace("clone bias: " + copy.bias);
+trace("clone matrix: " + copy.matrix);
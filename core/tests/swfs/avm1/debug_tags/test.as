@@ -0,0 +1,7 @@
+// Before its script, the movie has ProductInfo, Metadata, EnableDebugger2, DebugID and DefineFontInfo tags.
+// The DefineFontInfo tag names font 1, which isn't defined.
+// An unknown tag (code 1000) comes before and after the script.
+// Its body is a DoAction tag that traces "Unknown tag parsed", which must not run.
+
+// Frame 1
+trace("Debug tags skipped");
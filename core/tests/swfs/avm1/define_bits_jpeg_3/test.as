@@ -0,0 +1,4 @@
+// This movie has no scripts.
+// Its library holds a DefineBitsJPEG3 bitmap: an 8x8 gray JPEG (Y = 144)
+// with a zlib alpha channel. The top row is opaque and the rest has an alpha of 64.
+// The bitmap is placed at depth 1 on frame 1.
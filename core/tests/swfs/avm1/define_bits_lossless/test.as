@@ -0,0 +1,6 @@
+// This movie has no scripts. Its library holds:
+// 1: a 2x2 DefineBitsLossless2 bitmap with premultiplied 32-bit ARGB pixels.
+//    They are opaque red, half-transparent green, fully transparent, and a quarter-alpha gray.
+// 2: a 3x2 DefineBitsLossless bitmap with 15-bit RGB pixels.
+//    The top row is red, green and blue. The bottom row is white, black and gray.
+// Bitmap 1 is placed at depth 1 on frame 1.
@@ -0,0 +1,20 @@
+// The movie embeds "TestCFF", a DefineFont4 font with CFF outlines.
+// It has 1000 units per em, an ascent of 800, a descent of 200 and a line gap of 100.
+// It has glyphs for "a" (500 units wide), "b" (600 units wide) and " " (250 units wide).
+
+// Frame 1
+this.createTextField("tf", 1, 0, 0, 400, 100);
+tf.embedFonts = true;
+var fmt = new TextFormat("TestCFF", 20);
+
+function measure(text, size) {
+	fmt.size = size;
+	tf.setNewTextFormat(fmt);
+	tf.text = text;
+	trace(text + " @ " + size + ": " + tf.textWidth + "x" + tf.textHeight);
+}
+
+measure("aab", 20);
+measure("aab", 50);
+measure("a b", 20);
+measure("a?b", 20);
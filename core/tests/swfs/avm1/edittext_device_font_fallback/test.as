@@ -0,0 +1,17 @@
+// Frame 1
+this.createTextField("tf", 1, 0, 0, 400, 100);
+tf.embedFonts = false;
+
+function measure(font) {
+	fmt = new TextFormat(font, 20);
+	tf.setNewTextFormat(fmt);
+	tf.text = "aab";
+	trace(font + ": " + tf.textWidth + "x" + tf.textHeight);
+}
+
+measure("Arial");
+measure("Verdana");
+measure("Times New Roman");
+measure("_sans");
+measure("_serif");
+measure("_typewriter");
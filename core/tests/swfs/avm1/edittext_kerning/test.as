@@ -0,0 +1,21 @@
+// The movie embeds "KernTest", a DefineFont3 font with an EM square of 20480 units.
+// It has an ascent of 16384, a descent of 4096 and a leading of 2048.
+// Its glyphs "A" and "V" are empty and 12288 units wide.
+// Its layout table has one kerning pair: "AV", with an adjustment of -4096.
+
+// Frame 1
+this.createTextField("tf", 1, 0, 0, 400, 100);
+tf.embedFonts = true;
+var fmt = new TextFormat("KernTest", 20);
+
+function measure(text, kerning) {
+	fmt.kerning = kerning;
+	tf.setNewTextFormat(fmt);
+	tf.text = text;
+	trace(text + " (kerning " + kerning + "): " + tf.textWidth + "x" + tf.textHeight);
+}
+
+measure("AV", false);
+measure("AV", true);
+measure("VA", true);
+measure("AVAV", true);
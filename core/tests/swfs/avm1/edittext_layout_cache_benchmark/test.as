@@ -0,0 +1,17 @@
+// Frame 1
+for (i = 0; i < 200; i++) {
+	_root.createTextField("tf" + i, i + 1, (i % 10) * 50, (i - i % 10) * 2, 50, 20);
+	_root["tf" + i].text = "Label " + i;
+}
+width = _root.tf0.textWidth;
+frame = 1;
+_root.onEnterFrame = function() {
+	if (frame > 10) {
+		return;
+	}
+	for (i = 0; i < 200; i++) {
+		_root["tf" + i].text = "Label " + i;
+	}
+	trace("frame " + frame + ": textWidth unchanged = " + (_root.tf0.textWidth == width) + ", maxscroll = " + _root.tf0.maxscroll);
+	frame++;
+};
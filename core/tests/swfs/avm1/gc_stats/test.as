@@ -0,0 +1,13 @@
+// Frame 1
+keep = [];
+for (i = 0; i < 5000; i++) {
+	keep.push({value: i});
+}
+trace("kept " + keep.length + " objects");
+
+// Frame 2
+keep = null;
+trace("released");
+
+// Frame 3
+stop();
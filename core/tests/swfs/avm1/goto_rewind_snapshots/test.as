@@ -0,0 +1,29 @@
+// The timeline has 1000 frames. Frame 1 places an empty clip as "clip" at (1, 1).
+// Every later frame N moves "clip" to (N, N). Every 10th frame N places another
+// empty clip at depth 2, named "mN", and frame N + 5 removes it.
+
+// Frame 1
+stop();
+gotoAndStop(999);
+total = 0;
+count = 0;
+for (i = 998; i >= 900; i--) {
+	gotoAndStop(i);
+	total = total + clip._x;
+	if (typeof eval("m" + (i - i % 10)) == "movieclip") {
+		count++;
+	}
+}
+trace("total: " + total);
+trace("count: " + count);
+
+gotoAndStop(652);
+trace("frame 652: clip._x=" + clip._x + " m650=" + typeof m650);
+gotoAndStop(457);
+trace("frame 457: clip._x=" + clip._x + " m450=" + typeof m450);
+gotoAndStop(2);
+trace("frame 2: clip._x=" + clip._x + " m0=" + typeof m0);
+gotoAndStop(999);
+trace("frame 999: clip._x=" + clip._x + " m990=" + typeof m990);
+gotoAndStop(310);
+trace("frame 310: clip._x=" + clip._x + " m310=" + typeof m310);
@@ -0,0 +1,18 @@
+// The stage holds a 400x400 star shape at depth 1.
+// The star has 256 points and mixes straight and curved edges, so that hit tests are expensive.
+
+// Frame 1
+frame = 1;
+_root.onEnterFrame = function() {
+	if (frame > 10) {
+		return;
+	}
+	hits = 0;
+	for (i = 0; i < 1000; i++) {
+		if (_root.hitTest((i * 37 + frame * 13) % 400, (i * 91 + frame * 7) % 400, true)) {
+			hits++;
+		}
+	}
+	trace("frame " + frame + ": " + hits + " hits");
+	frame++;
+};
@@ -0,0 +1,3 @@
+// This movie has no scripts. Its library holds a two-frame sprite, exported as "Box".
+// Frame 1 of the sprite has a 20x10 rectangle, and frame 2 adds a 30x10 one.
+// Its character IDs differ from the ones that test.swf imports it as.
@@ -0,0 +1,16 @@
+// Frame 1 imports "Box" from library.swf with ImportAssets2, as character 5,
+// and places it at depth 1 as "placed".
+// Frame 2 imports "Missing" from missing.swf, which doesn't exist.
+
+// Frame 1
+trace("frame 1");
+trace("placed: _width=" + placed._width + " _height=" + placed._height + " _totalframes=" + placed._totalframes);
+_root.attachMovie("Box", "attached", 1);
+trace("attached: _width=" + attached._width + " _height=" + attached._height + " _totalframes=" + attached._totalframes);
+
+// Frame 2
+trace("frame 2");
+trace("placed: _width=" + placed._width + " _height=" + placed._height + " _totalframes=" + placed._totalframes);
+trace("attached: _width=" + attached._width + " _height=" + attached._height + " _totalframes=" + attached._totalframes);
+trace("attachMovie(\"Missing\"): " + _root.attachMovie("Missing", "missing", 2));
+stop();
@@ -0,0 +1,15 @@
+// The library holds an opaque 3x2 DefineBitsLossless2 bitmap, exported as "image".
+// Its top row is 0xFF0000, 0x336699 and 0x00FF00.
+// Its bottom row is 0x0000FF, 0xFFFFFF and 0x123456.
+
+// Frame 1
+var bmp = flash.display.BitmapData.loadBitmap("image");
+trace("width: " + bmp.width);
+trace("height: " + bmp.height);
+trace("transparent: " + bmp.transparent);
+trace("getPixel(1, 0): " + bmp.getPixel(1, 0));
+trace("getPixel32(1, 0): " + bmp.getPixel32(1, 0));
+trace("getPixel(2, 1): " + bmp.getPixel(2, 1));
+trace("getPixel(3, 0): " + bmp.getPixel(3, 0));
+var none = flash.display.BitmapData.loadBitmap("missing");
+trace("loadBitmap(\"missing\"): " + none);
@@ -0,0 +1,28 @@
+// image.png is a 3x2 interlaced, palettized PNG with a tRNS chunk.
+// animated.gif is a 4x2 GIF87a with two frames. Only the first frame is shown.
+// broken.png is a PNG signature followed by garbage.
+
+// Frame 1
+_root.createEmptyMovieClip("png", 1);
+_root.createEmptyMovieClip("gif", 2);
+_root.createEmptyMovieClip("broken", 3);
+listener = new Object();
+mcl = new MovieClipLoader();
+listener.onLoadInit = function(target) {
+	trace("onLoadInit: " + target._name);
+};
+listener.onLoadError = function(target, code) {
+	trace("onLoadError: " + target._name + ", " + code);
+};
+mcl.addListener(listener);
+
+loadMovie("image.png", "_root.png");
+mcl.loadClip("animated.gif", _root.gif);
+mcl.loadClip("broken.png", _root.broken);
+
+// Frames 2-3
+
+// Frame 4
+trace("png: " + _root.png._width + "x" + _root.png._height + ", _framesloaded: " + _root.png._framesloaded + ", _totalframes: " + _root.png._totalframes + ", getBytesTotal: " + _root.png.getBytesTotal());
+trace("gif: " + _root.gif._width + "x" + _root.gif._height + ", _framesloaded: " + _root.gif._framesloaded + ", _totalframes: " + _root.gif._totalframes + ", getBytesTotal: " + _root.gif.getBytesTotal());
+stop();
@@ -0,0 +1,20 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.events.StatusEvent;
+import flash.net.LocalConnection;
+
+var lc = new LocalConnection();
+var client = {};
+client.fromAvm1 = function(text, obj) {
+	trace("AVM2 received: " + text + ", " + obj.a);
+};
+lc.client = client;
+lc.addEventListener(StatusEvent.STATUS, function(e) {
+	trace("AVM2 status: " + e.level);
+});
+lc.connect("_avm2");
+lc.send("_avm1", "fromAvm2", "hi from AVM2", 5);
+trace("AVM2 sent");
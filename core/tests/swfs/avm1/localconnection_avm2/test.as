@@ -0,0 +1,16 @@
+// Frame 1
+var lc = new LocalConnection();
+lc.fromAvm2 = function(text, n) {
+	trace("AVM1 received: " + text + ", " + n);
+};
+lc.onStatus = function(info) {
+	trace("AVM1 onStatus: " + info.level);
+};
+trace("AVM1 connect: " + lc.connect("_avm1"));
+trace("AVM1 domain: " + lc.domain());
+loadMovieNum("child.swf", 1);
+
+// Frame 2
+var sent = lc.send("_avm2", "fromAvm1", "hi from AVM1", {a: 1});
+trace("AVM1 send: " + sent);
+stop();
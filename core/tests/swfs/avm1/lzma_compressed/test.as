@@ -0,0 +1,5 @@
+// The movie is saved with LZMA compression, as a SWF 13 "ZWS" file.
+
+// Frame 1
+trace("Hello from an LZMA compressed SWF");
+trace("_root._framesloaded: " + _root._framesloaded);
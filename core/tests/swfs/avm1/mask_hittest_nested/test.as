@@ -0,0 +1,20 @@
+// Nested masks: a two-part mask on the root masks a clip that is itself
+// masked inside. All rectangles below are given as (x, y, width, height).
+// Depth 1: "outer_mask", masking up to depth 10.
+//   It holds two rectangles: (0, 0, 100, 100) and (200, 0, 100, 100).
+// Depth 2: "content".
+//   Depth 1: "inner_mask", masking up to depth 5.
+//     It holds the rectangle (50, 0, 200, 50).
+//   Depth 2: "inner", which holds the rectangle (0, 0, 300, 200).
+
+// Frame 1
+var points = [[25, 25], [75, 25], [150, 25], [225, 25], [275, 25], [75, 75], [150, 150]];
+var targets = ["_root", "content", "content.inner"];
+for (var i = 0; i < targets.length; i++) {
+	var target = eval(targets[i]);
+	for (var j = 0; j < points.length; j++) {
+		var x = points[j][0];
+		var y = points[j][1];
+		trace(targets[i] + ".hitTest(" + x + ", " + y + ", true): " + target.hitTest(x, y, true));
+	}
+}
@@ -0,0 +1,18 @@
+// Sibling masks: each mask only applies up to its clip depth, and a mask
+// nested in another can't outlast it. Masks themselves are not hit.
+// All rectangles below are given as (x, y, width, height).
+// Depth 1: a mask up to depth 6, (0, 0, 100, 100).
+// Depth 2: (0, 0, 200, 50).
+// Depth 3: a mask up to depth 10, (0, 0, 300, 200).
+// Depth 4: (0, 50, 200, 50).
+// Depth 7: (0, 100, 200, 50).
+// Depth 8: a mask up to depth 10, (0, 150, 100, 50).
+// Depth 9: (0, 150, 200, 50).
+
+// Frame 1
+var points = [[50, 25], [150, 25], [50, 75], [150, 75], [50, 125], [150, 125], [250, 125], [50, 175], [150, 175]];
+for (var i = 0; i < points.length; i++) {
+	var x = points[i][0];
+	var y = points[i][1];
+	trace("_root.hitTest(" + x + ", " + y + ", true): " + _root.hitTest(x, y, true));
+}
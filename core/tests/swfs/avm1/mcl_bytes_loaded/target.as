@@ -0,0 +1,4 @@
+// The only script pushes a 100-character string literal and discards it.
+// It pads the movie, so that it arrives over several chunks.
+
+// Frame 1
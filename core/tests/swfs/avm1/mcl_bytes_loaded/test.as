@@ -0,0 +1,19 @@
+// Frame 1
+stop();
+trace("root: " + (_root.getBytesLoaded() + " / " + _root.getBytesTotal()));
+trace("root loaded: " + (_root.getBytesLoaded() == _root.getBytesTotal()));
+
+listener = {
+	onLoadProgress: function(target, loaded, total) {
+		trace("onLoadProgress: " + loaded + " / " + total + ", clip: " + (target.getBytesLoaded() + " / " + target.getBytesTotal()));
+	},
+	onLoadInit: function(target) {
+		trace("onLoadInit: " + (target.getBytesLoaded() + " / " + target.getBytesTotal()));
+		trace("loaded == total: " + (target.getBytesLoaded() == target.getBytesTotal()));
+	}
+};
+mcl = new MovieClipLoader();
+mcl.addListener(listener);
+
+_root.createEmptyMovieClip("child", 1);
+mcl.loadClip("target.swf", child);
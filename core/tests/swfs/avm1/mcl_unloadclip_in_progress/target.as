@@ -0,0 +1,5 @@
+// The script ends with an unused 300-character string literal.
+// It pads the movie, so that it arrives over several chunks.
+
+// Frame 1
+trace("Child movie loaded!");
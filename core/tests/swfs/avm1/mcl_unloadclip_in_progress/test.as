@@ -0,0 +1,26 @@
+// Frame 1
+var mcl = new MovieClipLoader();
+_root.createEmptyMovieClip("target", 1);
+
+mcl.onLoadStart = function(target) {
+	trace("Event: onLoadStart, target is clip: " + (target == _root.target));
+};
+mcl.onLoadProgress = function(target, loaded, total) {
+	trace("Event: onLoadProgress: " + loaded + " of " + total);
+	if (loaded >= 128) {
+		var progress = mcl.getProgress(target);
+		trace("getProgress: " + progress.bytesLoaded + " of " + progress.bytesTotal);
+		trace("unloadClip: " + mcl.unloadClip(target));
+	}
+};
+mcl.onLoadError = function(target, code, description) {
+	trace("Event: onLoadError, target is clip: " + (target == _root.target) + ", " + code + ", " + description);
+};
+mcl.onLoadComplete = function(target) {
+	trace("Event: onLoadComplete");
+};
+mcl.onLoadInit = function(target) {
+	trace("Event: onLoadInit");
+};
+
+mcl.loadClip("target.swf", _root.target);
@@ -0,0 +1,15 @@
+// The movie embeds a DefineMorphShape2 with a red fill and a 10px stroke.
+// Start shape: a 100x100 square at (0, 0).
+// End shape: a 200x50 rectangle at (50, 50).
+// It is placed on frame 1, and each frame sets a new ratio:
+// 0, 16384, 49152 and 65535.
+
+// Frames 1-4
+trace("ratio N");
+trace("  _width: " + _root._width);
+trace("  _height: " + _root._height);
+trace("  hitTest(15, 15): " + _root.hitTest(15, 15, true));
+trace("  hitTest(150, 75): " + _root.hitTest(150, 75, true));
+
+// Frame 4
+stop();
@@ -0,0 +1,38 @@
+// The movie defines a morph shape.
+// It tweens from a red 100x100 square into a blue 200x100 shape with a notch cut into its lower left.
+// Each frame places or moves the morph shape at depth 1 with the ratio that the frame traces.
+
+// Frame 1
+trace("ratio 0");
+trace("  _width: " + _root._width);
+trace("  _height: " + _root._height);
+trace("  hitTest(40, 80, true): " + _root.hitTest(40, 80, true));
+trace("  hitTest(40, 80, false): " + _root.hitTest(40, 80, false));
+trace("  hitTest(140, 10, true): " + _root.hitTest(140, 10, true));
+trace("  hitTest(140, 10, false): " + _root.hitTest(140, 10, false));
+
+// Frame 2
+trace("ratio 32768");
+trace("  _width: " + _root._width);
+trace("  _height: " + _root._height);
+trace("  hitTest(40, 80, true): " + _root.hitTest(40, 80, true));
+trace("  hitTest(40, 80, false): " + _root.hitTest(40, 80, false));
+trace("  hitTest(140, 10, true): " + _root.hitTest(140, 10, true));
+trace("  hitTest(140, 10, false): " + _root.hitTest(140, 10, false));
+
+// Frame 3
+trace("ratio 65535");
+trace("  _width: " + _root._width);
+trace("  _height: " + _root._height);
+trace("  hitTest(40, 80, true): " + _root.hitTest(40, 80, true));
+trace("  hitTest(40, 80, false): " + _root.hitTest(40, 80, false));
+trace("  hitTest(140, 10, true): " + _root.hitTest(140, 10, true));
+trace("  hitTest(140, 10, false): " + _root.hitTest(140, 10, false));
+gotoAndStop(1);
+trace("after gotoAndStop(1)");
+trace("  _width: " + _root._width);
+trace("  _height: " + _root._height);
+trace("  hitTest(40, 80, true): " + _root.hitTest(40, 80, true));
+trace("  hitTest(40, 80, false): " + _root.hitTest(40, 80, false));
+trace("  hitTest(140, 10, true): " + _root.hitTest(140, 10, true));
+trace("  hitTest(140, 10, false): " + _root.hitTest(140, 10, false));
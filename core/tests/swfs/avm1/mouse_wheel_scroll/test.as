@@ -0,0 +1,20 @@
+// Frame 1
+stop();
+_root.createTextField("tf", 1, 10, 10, 200, 20);
+tf.multiline = true;
+tf.wordWrap = true;
+tf.text = "line 1\rline 2\rline 3\rline 4\rline 5\rline 6\rline 7\rline 8\rline 9\rline 10";
+trace("scroll = " + tf.scroll + ", maxscroll = " + tf.maxscroll);
+
+tf.scroll = 100;
+trace("scroll = 100: scroll = " + tf.scroll + ", maxscroll = " + tf.maxscroll);
+tf.scroll = 0;
+trace("scroll = 0: scroll = " + tf.scroll + ", maxscroll = " + tf.maxscroll);
+trace("mouseWheelEnabled: " + tf.mouseWheelEnabled);
+
+listener = {
+	onMouseWheel: function(delta) {
+		trace("onMouseWheel: delta = " + delta + ", scroll = " + tf.scroll);
+	}
+};
+Mouse.addListener(listener);
@@ -0,0 +1,11 @@
+// Frame 1
+var form = _root.createEmptyMovieClip("form", 1);
+form.name = "Ruffle";
+
+trace("sending with POST");
+form.getURL("http://example.com/submit", "_blank", "POST");
+trace("sending with GET");
+form.getURL("http://example.com/search", "_self", "GET");
+trace("sending without a method");
+form.getURL("http://example.com/page", "_self");
+trace("done");
@@ -0,0 +1,60 @@
+// Three empty clips, "a", "b" and "c", are placed with PlaceObject3 on frame 1.
+// "a": blend mode "add", cacheAsBitmap off, invisible, background color 0x336699.
+//   Its filters are a BlurFilter (5, 6, 2 passes) and a BevelFilter:
+//   black shadow, white highlight, blur 4x4, angle 0, distance 4, strength 2,
+//   3 passes, inner and on top.
+// "b": no optional fields.
+// "c": blend mode "multiply" and cacheAsBitmap on.
+// On frame 2, the placements are modified:
+// "a": blend mode "screen", with no other fields.
+// "c": an empty filter list, and invisible.
+
+// Frame 1
+function traceClip(name) {
+	var clip = eval(name);
+	trace("// " + name);
+	trace("blendMode: " + clip.blendMode);
+	trace("cacheAsBitmap: " + clip.cacheAsBitmap);
+	trace("_visible: " + clip._visible);
+	trace("opaqueBackground: " + clip.opaqueBackground);
+	trace("filters.length: " + clip.filters.length);
+}
+
+function traceFilters(name) {
+	var clip = eval(name);
+	trace("filters[0].blurX: " + clip.filters[0].blurX);
+	trace("filters[0].blurY: " + clip.filters[0].blurY);
+	trace("filters[0].quality: " + clip.filters[0].quality);
+	var props = ["distance", "angle", "highlightColor", "highlightAlpha", "shadowColor", "shadowAlpha", "blurX", "blurY", "strength", "quality", "type", "knockout"];
+	for (var i = 0; i < props.length; i++) {
+		trace("filters[1]." + props[i] + ": " + clip.filters[1][props[i]]);
+	}
+}
+
+traceClip("a");
+traceFilters("a");
+traceClip("b");
+traceClip("c");
+
+// Frame 2
+trace("// frame 2");
+traceClip("a");
+traceFilters("a");
+traceClip("c");
+trace("// script changes");
+a.blendMode = "bogus";
+trace("a.blendMode = \"bogus\": " + a.blendMode);
+a.blendMode = "darken";
+trace("a.blendMode = \"darken\": " + a.blendMode);
+a.opaqueBackground = undefined;
+trace("a.opaqueBackground = undefined: " + a.opaqueBackground);
+a.filters = [];
+trace("a.filters = []: " + a.filters.length);
+trace("cacheAsBitmap: " + a.cacheAsBitmap);
+b.filters = [new flash.filters.BlurFilter(3, 2)];
+trace("b.filters = [new BlurFilter(3, 2)]: " + b.filters.length);
+trace("filters[0].blurX: " + b.filters[0].blurX);
+trace("cacheAsBitmap: " + b.cacheAsBitmap);
+c.opaqueBackground = 0x123456;
+trace("c.opaqueBackground = 0x123456: " + c.opaqueBackground);
+stop();
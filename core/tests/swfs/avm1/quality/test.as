@@ -0,0 +1,18 @@
+// Frame 1
+function traceQuality() {
+	trace("_quality: " + _quality + ", _highquality: " + _highquality);
+}
+
+traceQuality();
+_quality = "low";
+traceQuality();
+_root._quality = "Medium";
+traceQuality();
+_quality = "bogus";
+traceQuality();
+_highquality = 2;
+traceQuality();
+_highquality = 0;
+traceQuality();
+_highquality = 1;
+traceQuality();
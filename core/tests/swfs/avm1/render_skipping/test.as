@@ -0,0 +1,25 @@
+// Frame 1
+stop();
+_root.createEmptyMovieClip("box", 1);
+box.beginFill(0xFF0000);
+box.moveTo(0, 0);
+box.lineTo(20, 0);
+box.lineTo(20, 20);
+box.lineTo(0, 20);
+box.lineTo(0, 0);
+box.endFill();
+trace("created box");
+
+id = setInterval(function() {
+	box._x = box._x + 10;
+	clearInterval(id);
+	trace("timer: box._x = " + box._x);
+}, 1000);
+
+flash.external.ExternalInterface.addCallback("moveBox", null, function(y) {
+	box._y = y;
+	trace("moveBox: box._y = " + box._y);
+});
+flash.external.ExternalInterface.addCallback("getBox", null, function() {
+	return box._x;
+});
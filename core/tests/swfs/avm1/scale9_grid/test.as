@@ -0,0 +1,53 @@
+// The movie embeds a 100x100 ring shape, 20px thick, inside a sprite.
+// A DefineScalingGrid tag gives the sprite a grid of (20, 20, 60, 60).
+// The sprite is placed at (50, 50) as "box".
+
+// Frame 1
+var points = [[60, 90], [78, 95], [320, 100], [90, 60], [200, 78]];
+
+function setScale(sx, sy) {
+	box._xscale = sx;
+	box._yscale = sy;
+	trace("_xscale = " + sx + ", _yscale = " + sy);
+}
+
+function traceBounds() {
+	var b = box.getBounds(_root);
+	trace("getBounds: " + b.xMin + ", " + b.xMax + ", " + b.yMin + ", " + b.yMax);
+}
+
+function traceHits() {
+	for (var i = 0; i < points.length; i++) {
+		var x = points[i][0];
+		var y = points[i][1];
+		trace("hitTest(" + x + ", " + y + ", true): " + box.hitTest(x, y, true));
+	}
+}
+
+trace("scale9Grid: " + box.scale9Grid);
+setScale(100, 100);
+traceBounds();
+traceHits();
+setScale(300, 100);
+traceBounds();
+traceHits();
+setScale(300, 200);
+traceBounds();
+traceHits();
+setScale(50, 50);
+traceBounds();
+traceHits();
+
+// Assigning copies the rectangle.
+var r = new flash.geom.Rectangle(10, 10, 80, 80);
+box.scale9Grid = r;
+r.x = 0;
+trace("after assigning and changing the rectangle: " + box.scale9Grid);
+trace("scale9Grid == scale9Grid: " + (box.scale9Grid == box.scale9Grid));
+
+// null removes the grid.
+box.scale9Grid = null;
+trace("after assigning null: " + box.scale9Grid);
+setScale(300, 100);
+traceBounds();
+traceHits();
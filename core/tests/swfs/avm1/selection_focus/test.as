@@ -0,0 +1,36 @@
+// Frame 1
+_root.createTextField("field", 1, 0, 30, 100, 20);
+field.onSetFocus = function(other) {
+	trace("field.onSetFocus: " + other);
+};
+field.onKillFocus = function(other) {
+	trace("field.onKillFocus: " + other);
+};
+_root.createTextField("other", 2, 0, 60, 100, 20);
+other.onSetFocus = function(other) {
+	trace("other.onSetFocus: " + other);
+};
+other.onKillFocus = function(other) {
+	trace("other.onKillFocus: " + other);
+};
+field.text = "Hello world";
+
+trace("// Selection.setFocus(\"field\")");
+trace("result: " + Selection.setFocus("field"));
+trace("getFocus: " + Selection.getFocus());
+trace("typeof getFocus: " + typeof Selection.getFocus());
+
+trace("// Selection.setSelection(2, 5)");
+Selection.setSelection(2, 5);
+trace("getBeginIndex: " + Selection.getBeginIndex());
+trace("getEndIndex: " + Selection.getEndIndex());
+trace("getCaretIndex: " + Selection.getCaretIndex());
+
+trace("// Selection.setFocus(other)");
+trace("result: " + Selection.setFocus(other));
+trace("getFocus: " + Selection.getFocus());
+
+trace("// Selection.setFocus(null)");
+trace("result: " + Selection.setFocus(null));
+trace("getFocus: " + Selection.getFocus());
+trace("getCaretIndex: " + Selection.getCaretIndex());
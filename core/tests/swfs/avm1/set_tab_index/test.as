@@ -0,0 +1,29 @@
+// The stage holds four editable text fields, named "t1" to "t4" at depths 1-4.
+// SetTabIndex tags give t1, t2 and t3 the tab indices 3, 1 and 2. t4 has no tab index.
+// The clips "onTarget" and "passedOver" at depths 5 and 6 are instances of the same 3-frame clip.
+// Frame 2 of that clip places a text field named "g", with a SetTabIndex tag giving it index 7.
+
+// Frame 1
+trace("t1.tabIndex: " + t1.tabIndex);
+trace("t2.tabIndex: " + t2.tabIndex);
+trace("t3.tabIndex: " + t3.tabIndex);
+trace("t4.tabIndex: " + t4.tabIndex);
+
+t3.tabIndex = 5;
+t4.tabIndex = 4;
+trace("t3.tabIndex: " + t3.tabIndex);
+trace("t4.tabIndex: " + t4.tabIndex);
+
+onTarget.gotoAndStop(2);
+trace("onTarget.g.tabIndex: " + onTarget.g.tabIndex);
+onTarget.gotoAndStop(1);
+passedOver.gotoAndStop(3);
+trace("passedOver.g.tabIndex: " + passedOver.g.tabIndex);
+passedOver.gotoAndStop(1);
+
+listener = new Object();
+listener.onSetFocus = function(oldFocus, newFocus) {
+	trace("focus: " + Selection.getFocus());
+};
+Selection.addListener(listener);
+stop();
@@ -0,0 +1,59 @@
+// Frame 1 is made of four DoAction tags, one per block below.
+
+// Frame 1
+trace("frame 1");
+
+for (i = 0; i < 1000; i++) {
+	mc = _root.createEmptyMovieClip("square" + i, 1 + i);
+	mc._x = i % 50 * 10;
+	mc._y = i % 40 * 10;
+	mc.beginFill(0xFF0000);
+	mc.moveTo(0, 0);
+	mc.lineTo(20, 0);
+	mc.lineTo(20, 20);
+	mc.lineTo(0, 20);
+	mc.lineTo(0, 0);
+	mc.endFill();
+}
+
+for (i = 0; i < 1000; i++) {
+	mc = _root.createEmptyMovieClip("lens" + i, 1001 + i);
+	mc._x = i % 50 * 10;
+	mc._y = i % 40 * 10;
+	mc.lineStyle(2, 0x0000FF);
+	mc.moveTo(0, 0);
+	mc.curveTo(10, -10, 20, 0);
+	mc.curveTo(10, 10, 0, 0);
+}
+
+for (i = 0; i < 1000; i++) {
+	mc = _root.createEmptyMovieClip("triangle" + i, 2001 + i);
+	mc._x = i % 50 * 10;
+	mc._y = i % 40 * 10;
+	mc.beginFill(0x00FF00);
+	mc.moveTo(10, 0);
+	mc.lineTo(20, 20);
+	mc.lineTo(0, 20);
+	mc.lineTo(10, 0);
+	mc.endFill();
+}
+
+// Frame 2
+trace("frame 2");
+for (i = 0; i < 1000; i++) {
+	mc = eval("square" + i);
+	mc.clear();
+	mc.beginFill(0xFF0000);
+	mc.moveTo(0, 0);
+	mc.lineTo(20, 0);
+	mc.lineTo(20, 20);
+	mc.lineTo(0, 20);
+	mc.lineTo(0, 0);
+	mc.endFill();
+}
+
+// Frame 3
+trace("frame 3");
+square0.lineTo(30, 30);
+trace("clips: " + square999._name + " " + lens999._name + " " + triangle999._name);
+stop();
@@ -0,0 +1,62 @@
+// Frame 1 places the clip "a", which holds "b", which holds "c".
+// "c" has three frames. Frame 1 has a stop() and frame 3 is labelled "greet":
+//   trace("greet: " add getProperty("", _target));
+
+// Frame 2
+trace("// /a/b/c:val = \"deep\"");
+set("/a/b/c:val", "deep");
+trace("// /a/b/c:val");
+trace(eval("/a/b/c:val"));
+trace("// a/b/c:val");
+trace(eval("a/b/c:val"));
+trace("// /A/B/C:VAL");
+trace(eval("/A/B/C:VAL"));
+trace("// _level0:a:b:c:val");
+trace(eval("_level0:a:b:c:val"));
+trace("// _level0/a/b/c:val");
+trace(eval("_level0/a/b/c:val"));
+trace("// /a/b/c");
+trace(eval("/a/b/c"));
+trace("// /a/x/c:val");
+trace(eval("/a/x/c:val"));
+
+trace("// /a/b/c/../:up = \"up\"");
+set("/a/b/c/../:up", "up");
+trace("// /a/b:up");
+trace(eval("/a/b:up"));
+
+trace("// tellTarget(\"/a/b/c\") { _parent:_parent:viaParent = \"parent\" }");
+tellTarget ("/a/b/c") {
+	set("_parent:_parent:viaParent", "parent");
+	trace("// _parent:up");
+	trace(eval("_parent:up"));
+	trace("// _parent:_parent:viaParent");
+	trace(eval("_parent:_parent:viaParent"));
+	trace("// getProperty(\"\", _target)");
+	trace(getProperty("", _target));
+	trace("// gotoAndStop(2)");
+	gotoAndStop(2);
+}
+trace("// /a:viaParent");
+trace(eval("/a:viaParent"));
+trace("// getProperty(\"/a/b/c\", _currentframe)");
+trace(getProperty("/a/b/c", _currentframe));
+
+trace("// setProperty(\"/a/b/c\", _x, 25)");
+setProperty("/a/b/c", _x, 25);
+trace("// getProperty(\"/a/b/c\", _x)");
+trace(getProperty("/a/b/c", _x));
+trace("// /a/b/c:_x = 40");
+set("/a/b/c:_x", 40);
+trace("// getProperty(\"a/b/c\", _x)");
+trace(getProperty("a/b/c", _x));
+trace("// getProperty(\"_level0/a/b\", _name)");
+trace(getProperty("_level0/a/b", _name));
+trace("// getProperty(\"/a/b/c\", _target)");
+trace(getProperty("/a/b/c", _target));
+
+trace("// call(\"/a/b/c:greet\")");
+call("/a/b/c:greet");
+trace("// call(\"/a/b/c:3\")");
+call("/a/b/c:3");
+stop();
@@ -0,0 +1,27 @@
+// The library holds half a second of silent, uncompressed 5512Hz 8-bit mono audio,
+// exported as "beep".
+
+// Frame 1
+var s = new Sound();
+s.attachSound("beep");
+s.onSoundComplete = function() {
+	trace("onSoundComplete: position " + this.position);
+};
+trace("duration: " + s.duration);
+trace("position before start: " + s.position);
+s.start();
+trace("position after start: " + s.position);
+
+// Frame 2
+trace("frame 2: position " + s.position);
+
+// Frame 3
+trace("frame 3: position " + s.position);
+s.start(0.25);
+trace("restarted at: " + s.position);
+s.stop();
+trace("stopped at: " + s.position);
+
+// Frame 4
+trace("frame 4");
+stop();
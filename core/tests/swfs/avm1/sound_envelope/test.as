@@ -0,0 +1,12 @@
+// The library holds one second of silent, uncompressed 5512Hz 16-bit mono audio.
+// Frame 1 starts it as an event sound with a StartSound tag:
+//   in point at sample 4410, out point at sample 22050, and 3 loops.
+//   Its envelope fades from the right channel at sample 0 to the left channel at sample 44100.
+// Frame 2 stops it with a StartSound tag that has the stop flag set.
+
+// Frame 1
+trace("frame 1");
+
+// Frame 2
+trace("frame 2");
+stop();
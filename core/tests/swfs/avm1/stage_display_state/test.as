@@ -0,0 +1,30 @@
+// Frame 1
+stop();
+_root.createEmptyMovieClip("box", 1);
+box.beginFill(0xFF0000);
+box.moveTo(0, 0);
+box.lineTo(100, 0);
+box.lineTo(100, 100);
+box.lineTo(0, 100);
+box.lineTo(0, 0);
+box.endFill();
+
+trace("displayState: " + Stage.displayState);
+Stage.displayState = "fullScreen";
+trace("after request without input: " + Stage.displayState);
+
+listener = {
+	onFullScreen: function(full) {
+		trace("onFullScreen: " + full);
+	}
+};
+Stage.addListener(listener);
+
+box.onRelease = function() {
+	if (Stage.displayState == "normal") {
+		Stage.displayState = "fullScreen";
+	} else {
+		Stage.displayState = "normal";
+	}
+	trace("displayState after click: " + Stage.displayState);
+};
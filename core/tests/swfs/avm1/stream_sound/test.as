@@ -0,0 +1,16 @@
+// The movie runs at 25 fps and has an MP3 stream sound (11025Hz, 16-bit, mono)
+// with 441 samples per block.
+// The blocks of frames 1-8 have 441, 882, 0, 441, 441, none, 441 and 576 samples.
+// Frame 6 has no block.
+
+// Frames 1-8
+trace("frame N");
+
+// Frame 5
+if (!jumped) {
+	jumped = true;
+	gotoAndPlay(2);
+}
+
+// Frame 8
+stop();
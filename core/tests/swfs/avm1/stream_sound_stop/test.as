@@ -0,0 +1,12 @@
+// The movie runs at 25 fps.
+// Frame 1 places three instances of a looping four-frame sprite: "a", "b" and "c".
+// The sprite has an MP3 stream sound (11025Hz, 16-bit, mono) with a block of
+// 441 samples on every frame.
+
+// Frame 2
+trace("stopping a, unloading b");
+a.stop();
+loadMovie("", "b");
+
+// Frame 3
+stop();
@@ -0,0 +1,39 @@
+// Published as SWF 5.
+
+// Frame 1
+function E(v) {
+	this.v = v;
+}
+E.prototype.toString = function() {
+	return this.v;
+};
+function V(v) {
+	this.v = v;
+}
+V.prototype.valueOf = function() {
+	return this.v;
+};
+
+trace("// new E(4)");
+trace(new E(4));
+trace("// new E(undefined)");
+trace(new E(undefined));
+trace("// new E(null)");
+trace(new E(null));
+trace("// new E(true)");
+trace(new E(true));
+trace("// new E(\"s\")");
+trace(new E("s"));
+trace("// \"x\" + new E(4)");
+trace("x" + new E(4));
+trace("// \"a\" < new E(\"b\")");
+trace("a" < new E("b"));
+trace("// new E(\"b\") > \"a\"");
+trace(new E("b") > "a");
+trace("// new E(\"b\") < this");
+trace(new E("b") < this);
+trace("// new V(3) < 4");
+trace(new V(3) < 4);
+trace("// \"a\" < new V(\"b\")");
+trace("a" < new V("b"));
+stop();
@@ -0,0 +1,21 @@
+// Published as SWF 7.
+// The String() calls below are ToString actions.
+
+// Frame 1
+trace("// undefined + \"foo\"");
+trace(undefined + "foo");
+trace("// \"foo\" + undefined");
+trace("foo" + undefined);
+trace("// null + \"foo\"");
+trace(null + "foo");
+trace("// \"foo\" + null");
+trace("foo" + null);
+trace("// undefined add \"foo\"");
+trace(undefined add "foo");
+trace("// null add \"foo\"");
+trace(null add "foo");
+trace("// String(undefined).length");
+trace(String(undefined).length);
+trace("// String(null).length");
+trace(String(null).length);
+stop();
@@ -0,0 +1,15 @@
+// Each frame script ends with an unused 150-character string literal.
+// It pads the movie, so that each frame arrives over several chunks.
+
+// Frame 1
+_root.onEnterFrame = function() {
+	trace("enterFrame: frame " + _root._currentframe + ", " + _root._framesloaded + " frames loaded");
+};
+trace("frame 1: " + _root._framesloaded + " of " + _root._totalframes + " frames loaded, all bytes loaded: " + (_root.getBytesLoaded() == _root.getBytesTotal()));
+
+// Frame 2
+trace("frame 2: " + _root._framesloaded + " of " + _root._totalframes + " frames loaded, all bytes loaded: " + (_root.getBytesLoaded() == _root.getBytesTotal()));
+
+// Frame 3
+trace("frame 3: " + _root._framesloaded + " of " + _root._totalframes + " frames loaded, all bytes loaded: " + (_root.getBytesLoaded() == _root.getBytesTotal()));
+stop();
@@ -0,0 +1,7 @@
+// This movie has no scripts. Its library holds:
+// 1: a 1x1 bitmap, exported as "bitmap".
+// 2: an empty sound, exported as "sound".
+// 3: a font with no glyphs, exported as "font".
+// 4: a 16x16 video, exported as "video".
+// 5: an empty clip, linked to the class "com.example.Clip" by SymbolClass.
+// 6: an empty clip with no export name.
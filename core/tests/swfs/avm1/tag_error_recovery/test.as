@@ -0,0 +1,13 @@
+// Frame 1 has two scripts, with malformed tags between them:
+// - A DefineSprite tag that only holds a character ID.
+// - A DefineBitsLossless tag with an invalid format.
+// - A DefineBitsLossless tag whose image data isn't zlib compressed.
+// - A PlaceObject2 tag that places an undefined character at depth 1.
+
+// Frame 1
+trace("before malformed tags");
+
+trace("after malformed tags");
+
+// Frame 2
+trace("second frame");
@@ -0,0 +1,33 @@
+// The movie defines a DefineFont3 font named "ZoneTest", with glyphs for "A" and "V".
+// A DefineFontAlignZones tag gives the font two alignment zones, with medium thickness.
+// A text field using the font shows "AV", and is placed at depth 1 as "tf".
+// A CSMTextSettings tag sets the field to advanced anti-aliasing with sub-pixel grid fitting,
+// thickness 50 and sharpness -100.5.
+
+// Frame 1
+trace("from tags: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness + " textWidth=" + tf.textWidth);
+
+this.createTextField("tf2", 2, 0, 0, 400, 100);
+tf2.embedFonts = true;
+fmt = new TextFormat("ZoneTest", 20);
+tf2.setNewTextFormat(fmt);
+tf2.text = "AV";
+trace("created: " + tf2.antiAliasType + " sharpness=" + tf2.sharpness + " thickness=" + tf2.thickness + " textWidth=" + tf2.textWidth);
+
+tf2.antiAliasType = "advanced";
+tf2.sharpness = 123.5;
+tf2.thickness = -42;
+trace("round trip: " + tf2.antiAliasType + " sharpness=" + tf2.sharpness + " thickness=" + tf2.thickness + " textWidth=" + tf2.textWidth);
+
+tf2.antiAliasType = "bogus";
+tf2.sharpness = 1000;
+tf2.thickness = -1000;
+trace("invalid and clamped: " + tf2.antiAliasType + " sharpness=" + tf2.sharpness + " thickness=" + tf2.thickness + " textWidth=" + tf2.textWidth);
+
+tf2.antiAliasType = "NORMAL";
+tf2.sharpness = -401;
+tf2.thickness = 201;
+trace("normal and clamped: " + tf2.antiAliasType + " sharpness=" + tf2.sharpness + " thickness=" + tf2.thickness + " textWidth=" + tf2.textWidth);
+
+tf.antiAliasType = "normal";
+trace("tags reset: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness + " textWidth=" + tf.textWidth);
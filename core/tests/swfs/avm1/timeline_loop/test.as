@@ -0,0 +1,12 @@
+// The root places one instance of a 10-frame clip, named "timeline".
+// Frame 1 of that clip places 50 children at depths 1-50, named "c1" to "c50".
+// Each child is an empty clip. Every frame up to frame 9 moves each child to _x = frame number.
+// Frame 5 removes the children at depths 26-50. Frame 10 doesn't move any child.
+// The scripts below are on the timeline clip.
+
+// Frame 4
+trace("frame 4: c1._x=" + c1._x + " c30=" + typeof c30);
+
+// Frame 10
+loops++;
+trace("loop " + loops + ": c1._x=" + c1._x + " c30=" + typeof c30);
@@ -0,0 +1,24 @@
+// The movie embeds a 20x20 Screen Video stream with 4 frames:
+// 0: keyframe, 20x20
+// 1: interframe, 20x20
+// 2: keyframe, 40x30
+// 3: interframe, 40x30
+// It is placed on frame 1 as "vid", and each frame sets its ratio to the
+// matching video frame.
+
+// Frames 1-3
+trace("frame N vid.width: " + vid.width);
+trace("frame N vid.height: " + vid.height);
+trace("frame N vid._width: " + vid._width);
+trace("frame N vid.smoothing: " + vid.smoothing);
+
+// Frame 4
+trace("frame 4 vid.width: " + vid.width);
+trace("frame 4 vid.height: " + vid.height);
+trace("frame 4 vid._width: " + vid._width);
+trace("frame 4 vid.smoothing: " + vid.smoothing);
+gotoAndStop(2);
+trace("after gotoAndStop(2) vid.width: " + vid.width);
+trace("after gotoAndStop(2) vid.height: " + vid.height);
+trace("after gotoAndStop(2) vid._width: " + vid._width);
+trace("after gotoAndStop(2) vid.smoothing: " + vid.smoothing);
@@ -0,0 +1,41 @@
+// Each frame script ends with an unused 200-character string literal.
+// It pads the movie, so that each frame arrives over several chunks.
+// The third check in check() is a WaitForFrame2 with the number 3 on the stack,
+// instead of the WaitForFrame that a constant frame number compiles to.
+// Frame 3 is labelled "end".
+
+// Frame 1
+_root.onEnterFrame = function() {
+	s = "enterFrame";
+	ifFrameLoaded (2) {
+		s = s + ", frame 2 ready";
+	}
+	ifFrameLoaded (3) {
+		s = s + ", frame 3 ready";
+	}
+	ifFrameLoaded (3) {
+		s = s + ", frame 3 ready (WaitForFrame2)";
+	}
+	ifFrameLoaded ("end") {
+		s = s + ", end ready";
+	}
+	trace(s);
+};
+// Frames 1-3, with their own frame number in the prefix.
+s = "frame 1";
+ifFrameLoaded (2) {
+	s = s + ", frame 2 ready";
+}
+ifFrameLoaded (3) {
+	s = s + ", frame 3 ready";
+}
+ifFrameLoaded (3) {
+	s = s + ", frame 3 ready (WaitForFrame2)";
+}
+ifFrameLoaded ("end") {
+	s = s + ", end ready";
+}
+trace(s);
+
+// Frame 3
+stop();
@@ -0,0 +1,44 @@
+// Frame 1
+doc = new XML();
+bad = new XML();
+missing = new XML();
+query = new XML("<query id=\"1\" />");
+reply = new XML();
+
+doc.onLoad = function(success) {
+	trace("doc.onLoad: " + success);
+	trace("loaded: " + this.loaded);
+	trace("status: " + this.status);
+	trace("nodeName: " + this.firstChild.firstChild.nodeName);
+	trace("name: " + this.firstChild.firstChild.attributes.name);
+	trace("value: " + this.firstChild.firstChild.firstChild.nodeValue);
+	trace("bad.load: " + bad.load("bad.xml"));
+};
+bad.onLoad = function(success) {
+	trace("bad.onLoad: " + success);
+	trace("loaded: " + this.loaded);
+	trace("status: " + this.status);
+	trace("missing.load: " + missing.load("missing.xml"));
+};
+missing.onLoad = function(success) {
+	trace("missing.onLoad: " + success);
+	trace("loaded: " + this.loaded);
+	trace("query.contentType: " + query.contentType);
+	trace("query.sendAndLoad: " + query.sendAndLoad("data.xml", reply));
+	trace("reply.loaded: " + reply.loaded);
+};
+reply.onData = function(src) {
+	trace("reply.onData: " + src);
+	XML.prototype.onData.call(this, src);
+};
+reply.onLoad = function(success) {
+	trace("reply.onLoad: " + success);
+	trace("loaded: " + this.loaded);
+	trace("status: " + this.status);
+	trace("nodeName: " + this.firstChild.nodeName);
+};
+
+trace("doc.loaded: " + doc.loaded);
+trace("doc.load: " + doc.load("data.xml"));
+trace("doc.loaded: " + doc.loaded);
+trace("doc.status: " + doc.status);
@@ -0,0 +1,11 @@
+// Frame 1
+var doc = new XML("<message from=\"ruffle\"><text>Hello</text></message>");
+var reply = new XML();
+reply.onLoad = function(success) {
+	trace("reply.onLoad: " + success);
+	trace("nodeName: " + this.firstChild.nodeName);
+	trace("from: " + this.firstChild.attributes.from);
+	trace("text: " + this.firstChild.firstChild.firstChild.nodeValue);
+	trace("toString: " + this);
+};
+trace("doc.sendAndLoad: " + doc.sendAndLoad("http://example.com/echo", reply));
@@ -0,0 +1,19 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var mc:MovieClip = new MovieClip();
+			trace("currentFrame: " + mc.currentFrame);
+			trace("totalFrames: " + mc.totalFrames);
+
+			mc.addFrameScript(0, function() {
+				trace("frame script ran, currentFrame: " + mc.currentFrame);
+			});
+			trace("added frame script");
+
+			addChild(mc);
+			trace("added to stage");
+		}
+	}
+}
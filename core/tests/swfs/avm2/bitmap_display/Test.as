@@ -0,0 +1,43 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public var bd:BitmapData;
+		public var opaque:BitmapData;
+		public var b:Bitmap;
+
+		public function Test() {
+			bd = new BitmapData(20, 10, true, 0x8000FF00);
+			b = new Bitmap(bd);
+			addChild(b);
+			trace("bitmapData: " + bd.width + "x" + bd.height);
+			trace("transparent: " + bd.transparent);
+			trace("bitmap: " + b.width + "x" + b.height);
+			trace("same bitmapData: " + (b.bitmapData === bd));
+			trace("smoothing: " + b.smoothing);
+			trace("numChildren: " + this.numChildren);
+			trace("getPixel32(0, 0): " + bd.getPixel32(0, 0));
+			trace("getPixel(0, 0): " + bd.getPixel(0, 0));
+			trace("getPixel32(20, 0): " + bd.getPixel32(20, 0));
+			bd.setPixel(1, 1, 0xFF0000);
+			trace("after setPixel: " + bd.getPixel32(1, 1));
+			bd.setPixel32(2, 1, 0x8000FFFF);
+			trace("after setPixel32: " + bd.getPixel32(2, 1));
+
+			opaque = new BitmapData(5, 7, false, 0x12345678);
+			trace("opaque transparent: " + opaque.transparent);
+			trace("opaque getPixel32(0, 0): " + opaque.getPixel32(0, 0));
+			opaque.setPixel32(0, 0, 0x11223344);
+			trace("opaque after setPixel32: " + opaque.getPixel32(0, 0));
+
+			b.bitmapData = opaque;
+			trace("bitmap after bitmapData set: " + b.width + "x" + b.height);
+			b.bitmapData = null;
+			trace("bitmap without bitmapData: " + b.width + "x" + b.height);
+			trace("bitmapData: " + b.bitmapData);
+			trace("smoothed: " + new Bitmap(bd, "auto", true).smoothing);
+		}
+	}
+}
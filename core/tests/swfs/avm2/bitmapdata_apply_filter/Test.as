@@ -0,0 +1,26 @@
+package {
+	public class Test {}
+}
+
+import flash.display.BitmapData;
+import flash.geom.Rectangle;
+
+// Plain objects stand in for the Point and the filters.
+var bd = new BitmapData(5, 5, false, 0);
+bd.setPixel(2, 2, 0xFFFFFF);
+var rect = new Rectangle(0, 0, 5, 5);
+bd.applyFilter(bd, rect, {x: 0, y: 0}, {blurX: 3, blurY: 3, quality: 1});
+trace("center: " + bd.getPixel(2, 2));
+trace("left: " + bd.getPixel(1, 2));
+trace("above: " + bd.getPixel(2, 1));
+trace("diagonal: " + bd.getPixel(1, 1));
+trace("corner: " + bd.getPixel(0, 0));
+
+var swapped = new BitmapData(5, 5, false, 0xFF0000);
+swapped.applyFilter(swapped, rect, {x: 0, y: 0}, {matrix: [
+	0, 0, 1, 0, 0,
+	0, 1, 0, 0, 0,
+	1, 0, 0, 0, 0,
+	0, 0, 0, 1, 0
+]});
+trace("swapped: " + swapped.getPixel(3, 3));
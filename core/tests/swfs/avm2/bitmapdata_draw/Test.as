@@ -0,0 +1,33 @@
+package {
+	public class Test {}
+}
+
+import flash.display.BitmapData;
+import flash.display.Shape;
+import flash.display.Sprite;
+
+var s = new Sprite();
+s.graphics.beginFill(0xFF0000);
+s.graphics.drawRect(0, 0, 20, 20);
+s.graphics.endFill();
+var sh = new Shape();
+sh.graphics.beginFill(0x0000FF);
+sh.graphics.drawRect(15, 0, 5, 5);
+sh.graphics.endFill();
+s.addChild(sh);
+
+var bd = new BitmapData(40, 40, false, 0xFFFFFF);
+bd.draw(s);
+trace("center: " + bd.getPixel(10, 10));
+trace("outside: " + bd.getPixel(30, 30));
+trace("child: " + bd.getPixel(17, 2));
+
+// A plain object stands in for a Matrix translating by (10, 10).
+bd.draw(s, {tx: 10, ty: 10});
+trace("translated center: " + bd.getPixel(20, 20));
+trace("translated child: " + bd.getPixel(27, 12));
+
+// A plain object stands in for a ColorTransform halving red.
+var tinted = new BitmapData(20, 20, false, 0xFFFFFF);
+tinted.draw(s, null, {redMultiplier: 0.5});
+trace("tinted center: " + tinted.getPixel(10, 10));
@@ -0,0 +1,92 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.net.SharedObject;
+import flash.net.getClassByAlias;
+import flash.net.registerClassAlias;
+import flash.utils.ByteArray;
+import flash.utils.IDataInput;
+import flash.utils.IDataOutput;
+import flash.utils.IExternalizable;
+
+class Save {
+	public var name;
+	public var level;
+	public var items;
+	public var pos;
+}
+
+class Pos implements IExternalizable {
+	public var x;
+	public var y;
+
+	public function writeExternal(output:IDataOutput):void {
+		output.writeDouble(x);
+		output.writeDouble(y);
+	}
+
+	public function readExternal(input:IDataInput):void {
+		x = input.readDouble();
+		y = input.readDouble();
+	}
+}
+
+class Unknown {
+	public var label;
+}
+
+var save:Save;
+var pos:Pos;
+var unknown:Unknown;
+var bytes:ByteArray;
+var so:SharedObject;
+var copy;
+
+function traceCopy():void {
+	trace("is Save: " + (copy is Save));
+	trace("name: " + copy.name);
+	trace("level: " + copy.level);
+	trace("pos is Pos: " + (copy.pos is Pos));
+	trace("pos: " + copy.pos.x + "," + copy.pos.y);
+	trace("items[0]: " + copy.items[0]);
+	trace("items[1] === pos: " + (copy.items[1] === copy.pos));
+	trace("items[2] is Unknown: " + (copy.items[2] is Unknown));
+	trace("items[2].label: " + copy.items[2].label);
+}
+
+registerClassAlias("com.ruffle.Save", Save);
+registerClassAlias("com.ruffle.Pos", Pos);
+trace("getClassByAlias: " + (getClassByAlias("com.ruffle.Save") === Save));
+
+// This movie is run twice: the first run stores the data, the second loads it.
+so = SharedObject.getLocal("class_alias_test");
+if (so.data.save !== undefined) {
+	trace("// From SharedObject");
+	copy = so.data.save;
+	traceCopy();
+} else {
+	save = new Save();
+	save.name = "Ruffle";
+	save.level = 5;
+	pos = new Pos();
+	pos.x = 1.5;
+	pos.y = -2;
+	unknown = new Unknown();
+	unknown.label = "unregistered";
+	save.items = ["sword", pos, unknown];
+	save.pos = pos;
+
+	bytes = new ByteArray();
+	bytes.writeObject(save);
+	bytes.position = 0;
+
+	trace("// From ByteArray");
+	copy = bytes.readObject();
+	traceCopy();
+	trace("bytesAvailable: " + bytes.bytesAvailable);
+
+	so.data.save = save;
+	trace("flush: " + so.flush());
+}
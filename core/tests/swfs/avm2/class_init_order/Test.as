@@ -0,0 +1,40 @@
+// Compiled as a single script, with A, B and C defined in that order.
+package {
+	public class A {
+		public static var seenB:* = null;
+		public static var label:* = null;
+
+		{
+			trace("A initializer: B is " + B);
+			seenB = B;
+			label = "A";
+		}
+	}
+}
+
+package {
+	public class B extends A {
+		public static var label:* = null;
+
+		{
+			trace("B initializer: A.label is " + A.label);
+			label = "B";
+		}
+	}
+}
+
+package {
+	public class C {
+		{
+			// This is hand-written bytecode that can't be expressed in
+			// ActionScript: `getlex Object; newclass C`, which defines C again
+			// from C's own class initializer.
+			trace("C initializer: newclass C gives " + /* newclass C */ null);
+		}
+	}
+}
+
+trace("C defined");
+trace("A.seenB: " + A.seenB);
+trace("A.label: " + A.label);
+trace("B.label: " + B.label);
@@ -0,0 +1,38 @@
+package {
+	public class Test {}
+}
+
+class Maker {
+	public var name;
+	public var indexed;
+	public var shared;
+	public var selfGetter;
+
+	public function Maker(name) {
+		var self = this;
+		this.name = name;
+		this.indexed = [];
+		this.shared = [];
+		for (var j = 0; j < 3; j++) {
+			indexed.push(makeIndex(j));
+			shared.push(function() { return j; });
+		}
+		selfGetter = function() { return self.name; };
+	}
+
+	public function makeIndex(i) {
+		return function() { return i; };
+	}
+}
+
+var first = new Maker("first");
+var second = new Maker("second");
+trace("first.indexed[0](): " + first.indexed[0]());
+trace("first.indexed[1](): " + first.indexed[1]());
+trace("first.indexed[2](): " + first.indexed[2]());
+trace("first.shared[0](): " + first.shared[0]());
+trace("first.shared[1](): " + first.shared[1]());
+trace("first.shared[2](): " + first.shared[2]());
+trace("first.selfGetter(): " + first.selfGetter());
+trace("second.selfGetter(): " + second.selfGetter());
+trace("second.indexed[2](): " + second.indexed[2]());
@@ -0,0 +1,27 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var mc:* = new MovieClip();
+			var nothing:* = null;
+			var anything:* = "foo";
+
+			var fromNull:MovieClip = nothing;
+			trace("null to MovieClip: " + fromNull);
+			var fromClip:MovieClip = mc;
+			trace("MovieClip to MovieClip is same object: " + (fromClip === mc));
+			var untyped:* = anything;
+			trace("String to *: " + untyped);
+
+			try {
+				var coerced:MovieClip = anything;
+				trace("String to MovieClip: " + coerced);
+			} catch (e:TypeError) {
+				trace("caught TypeError: " + e.errorID);
+			}
+
+			trace("done");
+		}
+	}
+}
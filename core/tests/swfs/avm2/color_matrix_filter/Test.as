@@ -0,0 +1,27 @@
+package {
+	public class Test {}
+}
+
+import flash.display.BitmapData;
+import flash.display.Shape;
+import flash.filters.ColorMatrixFilter;
+import flash.geom.Rectangle;
+
+var bd = new BitmapData(2, 2, false, 0xFF8040);
+var filter = new ColorMatrixFilter([
+	0.299, 0.587, 0.114, 0, 0,
+	0.299, 0.587, 0.114, 0, 0,
+	0.299, 0.587, 0.114, 0, 0,
+	0, 0, 0, 1, 0
+]);
+trace("default matrix: " + new ColorMatrixFilter().matrix);
+// A plain object stands in for the Point.
+bd.applyFilter(bd, new Rectangle(0, 0, 2, 2), {x: 0, y: 0}, filter);
+trace("gray: " + bd.getPixel(1, 1));
+
+var sh = new Shape();
+trace("no filters: " + sh.filters.length);
+sh.filters = [filter];
+trace("filters: " + sh.filters);
+trace("matrix: " + sh.filters[0].matrix);
+trace("copied: " + (sh.filters[0] === filter));
@@ -0,0 +1,59 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.ui.ContextMenu;
+	import flash.ui.ContextMenuItem;
+
+	public class Test extends MovieClip {
+		public var owner:Sprite;
+		public var inner:Sprite;
+		public var menu:ContextMenu;
+		public var first:ContextMenuItem;
+		public var second:ContextMenuItem;
+		public var hidden:ContextMenuItem;
+
+		public function Test() {
+			owner = new Sprite();
+			owner.name = "owner";
+			owner.graphics.beginFill(0);
+			owner.graphics.drawRect(0, 0, 100, 100);
+			addChild(owner);
+
+			inner = new Sprite();
+			inner.name = "inner";
+			inner.graphics.beginFill(0);
+			inner.graphics.drawRect(0, 0, 100, 100);
+			owner.addChild(inner);
+
+			trace("default contextMenu: " + owner.contextMenu);
+
+			menu = new ContextMenu();
+			trace("default customItems.length: " + menu.customItems.length);
+			first = new ContextMenuItem("First");
+			second = new ContextMenuItem("Second", true, false);
+			hidden = new ContextMenuItem("Hidden");
+			hidden.visible = false;
+			traceItem("first", first);
+			traceItem("second", second);
+			traceItem("hidden", hidden);
+
+			menu.hideBuiltInItems();
+			menu.customItems = [first, second, hidden];
+			menu.addEventListener("menuSelect", function(e) {
+				trace(e.type + " mouseTarget=" + e.mouseTarget.name + " contextMenuOwner=" + e.contextMenuOwner.name);
+			});
+			var onItemSelect = function(e) {
+				trace(e.type + " mouseTarget=" + e.mouseTarget.name + " contextMenuOwner=" + e.contextMenuOwner.name + " target=" + e.target.caption);
+			};
+			first.addEventListener("menuItemSelect", onItemSelect);
+			second.addEventListener("menuItemSelect", onItemSelect);
+			owner.contextMenu = menu;
+			trace("owner.contextMenu is menu: " + (owner.contextMenu === menu));
+			trace("inner.contextMenu: " + inner.contextMenu);
+		}
+
+		private function traceItem(name:String, item:ContextMenuItem) {
+			trace(name + ": caption=" + item.caption + " separatorBefore=" + item.separatorBefore + " enabled=" + item.enabled + " visible=" + item.visible);
+		}
+	}
+}
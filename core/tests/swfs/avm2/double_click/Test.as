@@ -0,0 +1,35 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public var enabled:Sprite;
+		public var disabled:Sprite;
+
+		public function Test() {
+			var onEvent = function(e) {
+				trace(e.currentTarget.name + " " + e.type);
+			};
+
+			enabled = new Sprite();
+			enabled.name = "enabled";
+			enabled.graphics.beginFill(0);
+			enabled.graphics.drawRect(0, 0, 100, 100);
+			addChild(enabled);
+			enabled.addEventListener("click", onEvent);
+			enabled.addEventListener("doubleClick", onEvent);
+
+			disabled = new Sprite();
+			disabled.name = "disabled";
+			disabled.graphics.beginFill(0);
+			disabled.graphics.drawRect(200, 0, 100, 100);
+			addChild(disabled);
+			disabled.addEventListener("click", onEvent);
+			disabled.addEventListener("doubleClick", onEvent);
+
+			trace("default doubleClickEnabled: " + enabled.doubleClickEnabled);
+			enabled.doubleClickEnabled = true;
+			trace("enabled.doubleClickEnabled: " + enabled.doubleClickEnabled);
+		}
+	}
+}
@@ -0,0 +1,20 @@
+// `g` sets its default XML namespace at runtime, which compiles to `dxnslate`.
+package {
+	function f():String {
+		default xml namespace = "ns1";
+		trace("f: <a/> is in " + <a/>.namespace().uri);
+		return "f returned";
+	}
+
+	function g():String {
+		var two:int = 2;
+		default xml namespace = "ns" + two;
+		trace("g: <a/> is in " + <a/>.namespace().uri);
+		return "g returned";
+	}
+
+	trace(f());
+	trace(g());
+	trace(f());
+	trace("script: <a/> is in \"" + <a/>.namespace().uri + "\"");
+}
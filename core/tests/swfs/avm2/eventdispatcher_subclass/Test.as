@@ -0,0 +1,43 @@
+package {
+	public class Test {}
+}
+
+import flash.events.Event;
+import flash.events.EventDispatcher;
+
+class MyDispatcher extends EventDispatcher {
+	public function MyDispatcher() {
+		super();
+		this.addEventListener("test", this.handler);
+	}
+
+	override public function addEventListener(type:String, listener:Function, useCapture:Boolean = false, priority:int = 0, useWeakReference:Boolean = false):void {
+		trace("addEventListener: " + type);
+		super.addEventListener(type, listener);
+	}
+
+	public function handler(e) {
+		trace("handler: " + e.type);
+		trace("hasEventListener: " + super.hasEventListener("test"));
+	}
+}
+
+class SubDispatcher extends MyDispatcher {
+	public function SubDispatcher() {
+		super();
+		super.addEventListener("test", this.subHandler);
+	}
+
+	public function subHandler(e) {
+		trace("subHandler: " + e.type);
+	}
+}
+
+trace("// new MyDispatcher()");
+var d = new MyDispatcher();
+trace("hasEventListener: " + d.hasEventListener("test"));
+trace("dispatchEvent(test): " + d.dispatchEvent(new Event("test")));
+trace("dispatchEvent(other): " + d.dispatchEvent(new Event("other")));
+trace("// new SubDispatcher()");
+var s = new SubDispatcher();
+trace("dispatchEvent(test): " + s.dispatchEvent(new Event("test")));
@@ -0,0 +1,51 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.MouseEvent;
+	import flash.net.FileFilter;
+	import flash.net.FileReference;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("constructed");
+
+			var file:FileReference = new FileReference();
+			var saveFile:FileReference = new FileReference();
+
+			file.addEventListener(Event.SELECT, function(e:Event):void {
+				trace("select: " + file.name + " (" + file.size + " bytes, type " + file.type + ")");
+				trace("data before load: " + file.data);
+				file.load();
+			});
+			file.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("open");
+			});
+			file.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: " + file.data.length + " bytes: " + file.data.readUTFBytes(file.data.length));
+			});
+			file.addEventListener(Event.CANCEL, function(e:Event):void {
+				trace("cancel");
+			});
+
+			saveFile.addEventListener(Event.SELECT, function(e:Event):void {
+				trace("save select: " + saveFile.name);
+			});
+			saveFile.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("save complete: " + saveFile.size + " bytes");
+			});
+			saveFile.addEventListener(Event.CANCEL, function(e:Event):void {
+				trace("save cancel");
+			});
+
+			// File dialogs may only be opened in response to user input.
+			this.addEventListener(MouseEvent.MOUSE_DOWN, function(e:MouseEvent):void {
+				trace("mouseDown: bubbles=" + e.bubbles + " localX=" + e.localX);
+				file.browse([new FileFilter("Text files", "*.txt")]);
+			});
+			this.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+				trace("click");
+				saveFile.save("Hello from Ruffle", "saved.txt");
+			});
+		}
+	}
+}
@@ -0,0 +1,31 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.FileReference;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var file:FileReference = new FileReference();
+
+			// Dialogs may only be opened in response to user input, which
+			// constructing the document class isn't.
+			try {
+				file.browse();
+				trace("browse() succeeded");
+			} catch (e:SecurityError) {
+				trace("browse(): " + e);
+				trace("name: " + e.name);
+				trace("errorID: " + e.errorID);
+				trace("message: " + e.message);
+			}
+
+			try {
+				file.save("Hello", "test.txt");
+				trace("save() succeeded");
+			} catch (e:Error) {
+				trace("save(): " + e);
+			}
+
+			trace("done");
+		}
+	}
+}
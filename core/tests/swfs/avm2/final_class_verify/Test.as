@@ -0,0 +1,57 @@
+// Each block below is compiled into its own DoABC tag, in this order. The
+// `try` wraps the class definition, i.e. the `newclass` opcode, so that the
+// VerifyError it throws can be caught.
+package {
+	// ExtendFinal
+	trace("// final class FinalClass");
+	final class FinalClass {
+	}
+	trace("// class SubFinal extends FinalClass");
+	try {
+		class SubFinal extends FinalClass {
+		}
+		trace("SubFinal defined");
+	} catch (e:VerifyError) {
+		trace("caught VerifyError: " + e.errorID);
+	}
+
+	// OverrideFinal
+	trace("// class FinalBase with final greet()");
+	class FinalBase {
+		public final function greet():void {
+			trace("FinalBase.greet");
+		}
+	}
+	trace("// class FinalSub extends FinalBase overriding greet()");
+	try {
+		class FinalSub extends FinalBase {
+			override public function greet():void {
+				trace("FinalSub.greet");
+			}
+		}
+		trace("FinalSub defined");
+		new FinalSub().greet();
+	} catch (e:VerifyError) {
+		trace("caught VerifyError: " + e.errorID);
+	}
+
+	// OverrideVirtual
+	trace("// class VirtualBase with greet()");
+	class VirtualBase {
+		public function greet():void {
+			trace("VirtualBase.greet");
+		}
+	}
+	trace("// class VirtualSub extends VirtualBase overriding greet()");
+	try {
+		class VirtualSub extends VirtualBase {
+			override public function greet():void {
+				trace("VirtualSub.greet");
+			}
+		}
+		trace("VirtualSub defined");
+		new VirtualSub().greet();
+	} catch (e:VerifyError) {
+		trace("caught VerifyError: " + e.errorID);
+	}
+}
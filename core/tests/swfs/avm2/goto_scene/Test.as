@@ -0,0 +1,51 @@
+package {
+	import flash.display.MovieClip;
+
+	// The timeline has two scenes of three frames each:
+	// "Scene 1" (frames 1-3, label "start" on frame 1) and
+	// "Scene 2" (frames 4-6, labels "middle" on frame 5 and "Finale" on frame 6).
+	public class Test extends MovieClip {
+		public function Test() {
+			addFrameScript(0, function() {
+				trace("frame 1");
+				state();
+
+				trace("gotoAndStop(2, \"Scene 2\")");
+				gotoAndStop(2, "Scene 2");
+				state();
+
+				trace("gotoAndStop(\"middle\")");
+				gotoAndStop("middle");
+				state();
+
+				trace("gotoAndStop(1)");
+				gotoAndStop(1);
+				state();
+
+				trace("gotoAndStop(\"finale\", \"Scene 2\")");
+				gotoAndStop("finale", "Scene 2");
+				state();
+
+				trace("gotoAndStop(\"FINALE\")");
+				gotoAndStop("FINALE");
+				state();
+
+				trace("gotoAndStop(3, \"Scene 1\")");
+				gotoAndStop(3, "Scene 1");
+				state();
+
+				trace("gotoAndStop(\"middle\")");
+				gotoAndStop("middle");
+				state();
+
+				trace("gotoAndStop(3.0)");
+				gotoAndStop(3.0);
+				state();
+			});
+		}
+
+		function state() {
+			trace("  " + currentScene.name + " currentFrame: " + currentFrame);
+		}
+	}
+}
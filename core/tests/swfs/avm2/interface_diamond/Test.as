@@ -0,0 +1,31 @@
+package {
+	public class Test {}
+}
+
+interface IBase {}
+interface ILeft extends IBase {}
+interface IRight extends IBase {}
+
+class D implements ILeft, IRight {}
+class L implements ILeft {}
+
+var d = new D();
+var l = new L();
+
+trace("d is D: " + (d is D));
+trace("d is L: " + (d is L));
+trace("d is ILeft: " + (d is ILeft));
+trace("d is IRight: " + (d is IRight));
+trace("d is IBase: " + (d is IBase));
+trace("d is Object: " + (d is Object));
+trace("d instanceof D: " + (d instanceof D));
+trace("d instanceof IBase: " + (d instanceof IBase));
+
+trace("l is D: " + (l is D));
+trace("l is L: " + (l is L));
+trace("l is ILeft: " + (l is ILeft));
+trace("l is IRight: " + (l is IRight));
+trace("l is IBase: " + (l is IBase));
+trace("l is Object: " + (l is Object));
+trace("l instanceof D: " + (l instanceof D));
+trace("l instanceof IBase: " + (l instanceof IBase));
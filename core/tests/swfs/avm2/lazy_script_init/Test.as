@@ -0,0 +1,26 @@
+// Each block below is compiled into its own DoABC tag, in this order.
+// "lib" and "unused" have the lazy initialization flag set; "main" doesn't.
+// "main" holds two scripts, and only its last one is the entry point.
+
+// lib
+var lazyValue;
+trace("lib script initialized");
+lazyValue = "lazy value";
+
+// main, first script
+var helperValue;
+trace("helper script initialized");
+helperValue = "helper value";
+
+// main, second script
+trace("main script initialized");
+trace("reading lazyValue");
+trace(lazyValue);
+trace("reading lazyValue again");
+trace(lazyValue);
+trace("reading helperValue");
+trace(helperValue);
+
+// unused
+var unusedValue;
+trace("unused script initialized");
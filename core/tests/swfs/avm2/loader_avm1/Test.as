@@ -0,0 +1,37 @@
+package {
+	import flash.display.ActionScriptVersion;
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		var loader:Loader;
+
+		public function Test() {
+			loader = new Loader();
+			addChild(loader);
+
+			loader.contentLoaderInfo.addEventListener("init", function(e:Event) {
+				trace("event: " + e.type);
+			});
+			loader.contentLoaderInfo.addEventListener("complete", function(e:Event) {
+				trace("event: " + e.type);
+				trace("swfVersion: " + loader.contentLoaderInfo.swfVersion);
+				trace("actionScriptVersion: " + loader.contentLoaderInfo.actionScriptVersion);
+				trace("is ACTIONSCRIPT2: " + (loader.contentLoaderInfo.actionScriptVersion === ActionScriptVersion.ACTIONSCRIPT2));
+				trace("content: " + loader.content);
+				trace("content is contentLoaderInfo.content: " + (loader.content === loader.contentLoaderInfo.content));
+				trace("contentLoaderInfo.loader is loader: " + (loader.contentLoaderInfo.loader === loader));
+				trace("numChildren: " + loader.numChildren);
+			});
+
+			loader.load(new URLRequest("child.swf"));
+			trace("load started, content: " + loader.content);
+
+			addFrameScript(2, function() {
+				trace("parent frame 3, content: " + loader.content);
+			});
+		}
+	}
+}
@@ -0,0 +1,9 @@
+// child.swf: an AVM1 movie (SWF 8) with two frames.
+
+// Frame 1
+trace("child frame 1, _root == this: " + (_root == this));
+trace("child frame 1, _root._currentframe: " + _root._currentframe);
+
+// Frame 2
+trace("child frame 2");
+stop();
@@ -0,0 +1,27 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.HTTPStatusEvent;
+	import flash.events.IOErrorEvent;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		public var loader:Loader;
+
+		public function Test() {
+			loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e) {
+				trace("event: " + e.type + ", status: " + e.status);
+			});
+			loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e) {
+				trace("event: " + e.type + ", errorID: " + e.errorID);
+				trace("text: " + e.text);
+			});
+			loader.contentLoaderInfo.addEventListener("complete", function(e) {
+				trace("event: " + e.type);
+			});
+			loader.load(new URLRequest("http://example.com/missing.swf"));
+			trace("load started");
+		}
+	}
+}
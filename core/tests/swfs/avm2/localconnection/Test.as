@@ -0,0 +1,41 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.events.AsyncErrorEvent;
+import flash.events.StatusEvent;
+import flash.net.LocalConnection;
+
+var statuses = 0;
+
+var receiver = new LocalConnection();
+trace("domain: " + receiver.domain);
+trace("isPerUser: " + receiver.isPerUser);
+trace("client is receiver: " + (receiver.client === receiver));
+
+var client = {};
+client.greet = function(text, number, list) {
+	trace("greet: " + text + ", " + number + ", " + list.length + " " + list[1]);
+};
+receiver.client = client;
+receiver.addEventListener(AsyncErrorEvent.ASYNC_ERROR, function(e) {
+	trace("asyncError: " + e.text);
+});
+receiver.connect("test_connection");
+
+var sender = new LocalConnection();
+sender.addEventListener(StatusEvent.STATUS, function(e) {
+	trace("status: " + e.level);
+	statuses++;
+	if (statuses == 3) {
+		receiver.close();
+		trace("closed");
+		sender.send("test_connection", "greet");
+	}
+});
+
+sender.send("test_connection", "greet", "hello", 42, [1, 2]);
+sender.send("test_connection", "missing");
+sender.send("other_connection", "greet");
+trace("sent");
@@ -0,0 +1,20 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var onEvent = function(e) {
+				trace(e.type);
+			};
+			addEventListener("mouseDown", onEvent);
+			addEventListener("mouseUp", onEvent);
+			addEventListener("click", onEvent);
+			addEventListener("rightMouseDown", onEvent);
+			addEventListener("rightMouseUp", onEvent);
+			addEventListener("rightClick", onEvent);
+			addEventListener("middleMouseDown", onEvent);
+			addEventListener("middleMouseUp", onEvent);
+			addEventListener("middleClick", onEvent);
+		}
+	}
+}
@@ -0,0 +1,16 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			this.addEventListener(MouseEvent.MOUSE_DOWN, onMouse);
+			this.addEventListener(MouseEvent.MOUSE_UP, onMouse);
+			this.addEventListener(MouseEvent.CLICK, onMouse);
+		}
+
+		private function onMouse(e:MouseEvent):void {
+			trace(e.type + ": bubbles=" + e.bubbles + " localX=" + e.localX + " localY=" + e.localY);
+		}
+	}
+}
@@ -0,0 +1,60 @@
+// Each block below is compiled into its own DoABC tag, in this order. The
+// first runs inside package com.other, the others outside of it.
+//
+// `C.readGuarded` accesses `guarded` by slot (`setslot`/`getslot` 2), and the
+// last block reads slot 2 of a `B` directly, which can't be written in AS3.
+package {
+	import com.example.A;
+	import com.example.C;
+	import com.other.B;
+
+	// Allowed
+	trace("// com.other reads B.secret");
+	try {
+		trace(new B().secret);
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+	trace("// B.reveal()");
+	try {
+		trace(new B().reveal());
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+	trace("// C reads its inherited protected slot");
+	try {
+		trace(new C().readGuarded());
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+
+	// InternalGet
+	trace("// com.example.A reads B.secret");
+	try {
+		trace(new A().peek(new B()));
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+
+	// InternalSet
+	trace("// com.example.A writes B.secret");
+	try {
+		new A().poke(new B());
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+
+	// ProtectedGet
+	trace("// script code reads B's protected slot");
+	try {
+		trace(/* getslot 2 */ new B());
+		trace("no error");
+	} catch (e:ReferenceError) {
+		trace("caught ReferenceError: " + e.errorID);
+	}
+}
@@ -0,0 +1,13 @@
+package com.example {
+	import com.other.B;
+
+	public class A {
+		public function peek(b:*):* {
+			return b.secret;
+		}
+
+		public function poke(b:*):void {
+			b.secret = "A secret";
+		}
+	}
+}
@@ -0,0 +1,10 @@
+package com.example {
+	import com.other.B;
+
+	public class C extends B {
+		public function readGuarded():* {
+			this.guarded = "C guarded";
+			return this.guarded;
+		}
+	}
+}
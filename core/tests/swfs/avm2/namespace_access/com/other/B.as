@@ -0,0 +1,10 @@
+package com.other {
+	public class B {
+		internal var secret:* = "B secret";
+		protected var guarded:* = "B guarded";
+
+		public function reveal():* {
+			return this.secret;
+		}
+	}
+}
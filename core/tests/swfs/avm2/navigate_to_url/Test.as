@@ -0,0 +1,45 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.net.URLRequest;
+import flash.net.navigateToURL;
+import flash.net.sendToURL;
+
+var req = new URLRequest("http://example.com/page");
+var vars;
+
+trace("// navigateToURL(req)");
+navigateToURL(req);
+
+trace("// navigateToURL(req, \"_blank\")");
+navigateToURL(req, "_blank");
+
+req.method = "POST";
+req.data = "name=Ruffle&level=5";
+trace("// navigateToURL with POST and string data");
+navigateToURL(req, "_self");
+
+vars = new Object();
+vars.q = "flash player";
+req.method = "get";
+req.data = vars;
+trace("// navigateToURL with GET and object data");
+navigateToURL(req, "_self");
+
+req.method = "POST";
+req.data = "a=1&b=2";
+req.requestHeaders = [{name: "X-Test", value: "yes"}];
+trace("// sendToURL with POST, headers and string data");
+sendToURL(req);
+
+req.contentType = "text/plain";
+trace("// sendToURL with POST and a content type");
+sendToURL(req);
+
+req.method = "GET";
+req.data = "x=1";
+req.requestHeaders = [];
+trace("// sendToURL with GET and string data");
+sendToURL(req);
@@ -0,0 +1,28 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var s:Sprite = new Sprite();
+			s.graphics.beginFill(0xFF0000);
+			s.graphics.drawRect(0, 0, 100, 50);
+			s.graphics.endFill();
+			trace("sprite.numChildren: " + s.numChildren);
+			trace("same graphics: " + (s.graphics === s.graphics));
+
+			var sh:Shape = new Shape();
+			sh.graphics.lineStyle(2, 0x0000FF);
+			sh.graphics.drawCircle(25, 25, 10);
+			s.addChild(sh);
+			trace("sprite.numChildren: " + s.numChildren);
+			trace("getChildAt(0) === shape: " + (s.getChildAt(0) === sh));
+
+			trace("root.numChildren: " + this.numChildren);
+			this.addChild(s);
+			trace("root.numChildren: " + this.numChildren);
+			trace("getChildAt(0) === sprite: " + (this.getChildAt(0) === s));
+		}
+	}
+}
@@ -0,0 +1,46 @@
+package {
+	public class Test {}
+}
+
+var re;
+var m;
+
+re = /e/;
+m = re.exec("hello");
+trace("/e/.exec(\"hello\"): " + m);
+trace("index: " + m.index);
+trace("input: " + m.input);
+trace("length: " + m.length);
+trace("index == 1: " + (m.index === 1));
+trace("input == \"hello\": " + (m.input === "hello"));
+
+re = /(x)?y/;
+m = re.exec("zy");
+trace("/(x)?y/.exec(\"zy\"): length " + m.length);
+trace("[0]: " + m[0]);
+trace("[1] === undefined: " + (m[1] === undefined));
+trace("index: " + m.index);
+
+re = /(a)|(b)/;
+m = re.exec("xb");
+trace("/(a)|(b)/.exec(\"xb\"): [1] " + m[1] + " [2] " + m[2] + " index " + m.index);
+
+re = /l/;
+m = re.exec("héllo");
+trace("/l/.exec(\"héllo\").index: " + m.index);
+
+re = /q/;
+m = re.exec("hello");
+trace("no match: " + (m === null));
+
+re = /o/g;
+trace("/o/g: source " + re.source + " global " + re.global);
+m = re.exec("foo boo");
+trace("exec: " + m + " index " + m.index + " lastIndex " + re.lastIndex);
+m = re.exec("foo boo");
+trace("exec: " + m + " index " + m.index + " lastIndex " + re.lastIndex);
+m = re.exec("foo boo");
+trace("exec: " + m + " index " + m.index + " lastIndex " + re.lastIndex);
+
+re = /E/i;
+trace("/E/i.test(\"hello\"): " + re.test("hello"));
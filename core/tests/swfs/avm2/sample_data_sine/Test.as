@@ -0,0 +1,25 @@
+package {
+	public class Test {}
+}
+
+import flash.events.SampleDataEvent;
+import flash.media.Sound;
+
+var calls = 0;
+var sound = new Sound();
+sound.addEventListener(SampleDataEvent.SAMPLE_DATA, function(e) {
+	trace("sampleData: position=" + e.position);
+	if (calls < 2) {
+		for (var i = 0; i < 2048; i++) {
+			var sample = Math.sin((e.position + i) * 2 * Math.PI * 440 / 44100) * 0.5;
+			e.data.writeFloat(sample);
+			e.data.writeFloat(sample);
+		}
+		calls++;
+	}
+	trace("wrote: " + e.data.length);
+});
+var channel = sound.play();
+channel.addEventListener("soundComplete", function(e) {
+	trace("soundComplete");
+});
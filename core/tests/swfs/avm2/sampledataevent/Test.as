@@ -0,0 +1,31 @@
+package {
+	public class Test {}
+}
+
+import flash.events.SampleDataEvent;
+import flash.media.Sound;
+import flash.media.SoundChannel;
+
+var calls = 0;
+
+var sound = new Sound();
+trace("sound.length: " + sound.length);
+
+sound.addEventListener(SampleDataEvent.SAMPLE_DATA, function(event) {
+	trace("sampleData: position=" + event.position + " data.length=" + event.data.length);
+	if (calls < 2) {
+		for (var i = 0; i < 2048; i++) {
+			event.data.writeFloat(0.0);
+			event.data.writeFloat(0.0);
+		}
+	}
+	trace("wrote: " + event.data.length);
+	calls++;
+});
+
+var channel = sound.play();
+trace("channel is SoundChannel: " + (channel is SoundChannel));
+
+channel.addEventListener("soundComplete", function(event) {
+	trace("soundComplete: " + event.type + " calls=" + calls);
+});
@@ -0,0 +1,23 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("scale9Grid: " + scale9Grid);
+
+			var rect:Rectangle = new Rectangle(10, 20, 30, 40);
+			trace("rect: " + rect);
+
+			scale9Grid = rect;
+			rect.x = 0;
+			trace("after assigning and changing rect: " + scale9Grid);
+			trace("scale9Grid == rect: " + (scale9Grid == rect));
+			trace("scale9Grid == scale9Grid: " + (scale9Grid == scale9Grid));
+			trace("scale9Grid is Rectangle: " + (scale9Grid is Rectangle));
+
+			scale9Grid = null;
+			trace("after assigning null: " + scale9Grid);
+		}
+	}
+}
@@ -0,0 +1,39 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Scene;
+
+	// The timeline has three scenes: "Intro" (frames 1-2), "Main" (frames 3-5)
+	// and "Outro" (frames 6-7), with the frame labels "start", "loop",
+	// "loop end" and "end" on frames 1, 4, 5 and 7.
+	//
+	// `child` is a three-frame clip without any scenes, with the frame label
+	// "last" on its last frame.
+	public class Test extends MovieClip {
+		public function Test() {
+			addFrameScript(0, function() {
+				trace("scenes: " + scenes.length);
+				for (var i = 0; i < scenes.length; i++) {
+					traceScene(scenes[i].name + ": ", scenes[i]);
+				}
+
+				var child:MovieClip = getChildByName("child") as MovieClip;
+				trace("child scenes: " + child.scenes.length);
+				traceScene("child scene: ", child.scenes[0]);
+				trace("child currentLabels: " + child.currentLabels.length);
+
+				traceState();
+			}, 2, traceState, 5, traceState);
+		}
+
+		function traceScene(prefix:String, scene:Scene) {
+			trace(prefix + "numFrames " + scene.numFrames + ", labels " + scene.labels.length);
+			for (var i = 0; i < scene.labels.length; i++) {
+				trace("  " + scene.labels[i].name + " @ " + scene.labels[i].frame);
+			}
+		}
+
+		function traceState() {
+			trace("frame " + currentFrame + " of " + currentScene.name + ", currentLabels " + currentLabels.length);
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public var main:MovieClip;
+		public var container:Sprite;
+		public var inside:Sprite;
+		public var outside:Sprite;
+		public var clicks:int;
+
+		public function Test() {
+			main = this;
+			clicks = 0;
+			container = new Sprite();
+			addChild(container);
+			inside = new Sprite();
+			inside.graphics.beginFill(0);
+			inside.graphics.drawRect(0, 0, 50, 50);
+			inside.graphics.endFill();
+			container.addChild(inside);
+			outside = new Sprite();
+			outside.graphics.beginFill(0);
+			outside.graphics.drawRect(150, 0, 50, 50);
+			outside.graphics.endFill();
+			container.addChild(outside);
+
+			trace("scrollRect: " + container.scrollRect);
+			trace("bounds without scrollRect: " + container.getBounds(container));
+			container.scrollRect = new Rectangle(0, 0, 100, 100);
+			trace("scrollRect: " + container.scrollRect);
+			trace("bounds: " + container.getBounds(container));
+			trace("bounds in parent: " + container.getBounds(this));
+
+			inside.addEventListener("click", function(e) {
+				trace("inside clicked");
+			});
+			outside.addEventListener("click", function(e) {
+				trace("outside clicked");
+			});
+			this.addEventListener("click", function(e) {
+				trace("root saw click, target is root: " + (e.target == e.currentTarget));
+				clicks++;
+				if (clicks == 2) {
+					container.scrollRect = new Rectangle(150, 0, 100, 100);
+					trace("scrolled to " + container.scrollRect);
+					trace("bounds: " + container.getBounds(container));
+					trace("bounds in parent: " + container.getBounds(main));
+				}
+			});
+		}
+	}
+}
@@ -0,0 +1,49 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.events.NetStatusEvent;
+import flash.net.SharedObject;
+import flash.utils.ByteArray;
+
+var so:SharedObject = SharedObject.getLocal("ruffle_test");
+so.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+	trace("netStatus: " + e.info.level + " " + e.info.code);
+});
+
+if (so.data.nested !== undefined) {
+	trace("Found saved data");
+	trace("name: " + so.data.name);
+	trace("count: " + so.data.count + " (int: " + (so.data.count is int) + ")");
+	trace("ratio: " + so.data.ratio);
+	trace("flag: " + so.data.flag);
+	trace("nothing: " + so.data.nothing);
+	trace("list.length: " + so.data.nested.list.length);
+	trace("list[0]: " + so.data.nested.list[0]);
+	trace("list[1]: " + so.data.nested.list[1]);
+	trace("list[2].three: " + so.data.nested.list[2].three);
+	trace("list.label: " + so.data.nested.list.label);
+	trace("inner.deep: " + so.data.nested.inner.deep);
+	trace("again === inner: " + (so.data.nested.again === so.data.nested.inner));
+	trace("bytes: " + so.data.bytes.length + " " + so.data.bytes.readUTFBytes(so.data.bytes.length));
+} else {
+	trace("No saved data");
+}
+
+so.data.name = "Ruffle";
+so.data.count = 123456789;
+so.data.ratio = 0.5;
+so.data.flag = true;
+so.data.nothing = null;
+so.data.nested = {list: [1, "two", {three: 3}], inner: {deep: "value"}};
+so.data.nested.list.label = "numbers";
+so.data.nested.again = so.data.nested.inner;
+
+var bytes:ByteArray = new ByteArray();
+bytes.writeUTFBytes("bytes!");
+so.data.bytes = bytes;
+
+// The test harness limits storage to 10000 bytes, so the second flush fails.
+trace("flush(): " + so.flush());
+trace("flush(1000000): " + so.flush(1000000));
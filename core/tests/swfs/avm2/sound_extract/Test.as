@@ -0,0 +1,24 @@
+package {
+	import flash.display.MovieClip;
+	import flash.utils.ByteArray;
+
+	// The library holds 100 samples of uncompressed 22050Hz 16-bit mono audio,
+	// linked to the TestSound class. Sample i has the value i * 300 - 15000.
+	public class Test extends MovieClip {
+		public var sound:TestSound;
+		public var bytes:ByteArray;
+
+		public function Test() {
+			sound = new TestSound();
+			bytes = new ByteArray();
+			trace("extract(bytes, 150, 0): " + sound.extract(bytes, 150, 0) + " floats=" + bytes.length / 4);
+			trace("extract(bytes, 150): " + sound.extract(bytes, 150) + " floats=" + bytes.length / 4);
+			trace("extract(bytes, 150): " + sound.extract(bytes, 150) + " floats=" + bytes.length / 4);
+		}
+	}
+}
+
+import flash.media.Sound;
+
+class TestSound extends Sound {
+}
@@ -0,0 +1,37 @@
+package {
+	public class Test {}
+}
+
+import flash.media.Sound;
+import flash.media.SoundMixer;
+import flash.media.SoundTransform;
+
+var sound = new Sound();
+sound.addEventListener("sampleData", function(e) {
+	for (var i = 0; i < 4096; i++) {
+		e.data.writeFloat(0.0);
+	}
+});
+var channel = sound.play(0, 0, new SoundTransform(0.25, 1.0));
+channel.addEventListener("soundComplete", function(e) {
+	trace("soundComplete");
+});
+trace("play:" + " volume=" + channel.soundTransform.volume + " pan=" + channel.soundTransform.pan);
+
+// Assigning a transform copies it.
+var transform = new SoundTransform(0.5, -0.25);
+channel.soundTransform = transform;
+transform.volume = 0.1;
+trace("channel:" + " volume=" + channel.soundTransform.volume + " pan=" + channel.soundTransform.pan);
+channel.soundTransform.volume = 0.2;
+trace("channel after editing copy:" + " volume=" + channel.soundTransform.volume + " pan=" + channel.soundTransform.pan);
+trace("mixer:" + " volume=" + SoundMixer.soundTransform.volume + " pan=" + SoundMixer.soundTransform.pan);
+SoundMixer.soundTransform = new SoundTransform(0.8, 0.5);
+trace("mixer after set:" + " volume=" + SoundMixer.soundTransform.volume + " pan=" + SoundMixer.soundTransform.pan);
+SoundMixer.stopAll();
+trace("stopped, position=" + channel.position);
+
+// Channels that are no longer playing keep their transform, but nothing
+// is sent to the audio backend.
+channel.soundTransform = new SoundTransform(0.75, 0.0);
+trace("channel after stop:" + " volume=" + channel.soundTransform.volume + " pan=" + channel.soundTransform.pan);
@@ -0,0 +1,28 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public var button:Sprite;
+		public var noHand:Sprite;
+
+		public function Test() {
+			button = new Sprite();
+			button.graphics.beginFill(0);
+			button.graphics.drawRect(0, 0, 100, 100);
+			addChild(button);
+
+			noHand = new Sprite();
+			noHand.graphics.beginFill(0);
+			noHand.graphics.drawRect(200, 0, 100, 100);
+			addChild(noHand);
+
+			trace("button.buttonMode: " + button.buttonMode + ", useHandCursor: " + button.useHandCursor);
+			button.buttonMode = true;
+			noHand.buttonMode = true;
+			noHand.useHandCursor = false;
+			trace("button.buttonMode: " + button.buttonMode + ", useHandCursor: " + button.useHandCursor);
+			trace("noHand.buttonMode: " + noHand.buttonMode + ", useHandCursor: " + noHand.useHandCursor);
+		}
+	}
+}
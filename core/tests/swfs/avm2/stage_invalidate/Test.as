@@ -0,0 +1,25 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.display.Stage;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		public var child:Sprite;
+
+		public function Test() {
+			child = new Sprite();
+			trace("child.stage before addChild: " + child.stage);
+			addChild(child);
+			trace("child.stage is Stage: " + (child.stage is Stage));
+			trace("same stage: " + (child.stage === this.stage));
+			this.stage.addEventListener(Event.RENDER, function(e) {
+				trace("render: type=" + e.type + " child.x=" + child.x);
+			});
+			child.x = 25;
+			trace("invalidating, child.x=" + child.x);
+			this.stage.invalidate();
+			trace("constructor done");
+		}
+	}
+}
@@ -0,0 +1,10 @@
+package {
+	public class Test {}
+}
+
+import flash.system.System;
+
+trace("totalMemory > 0: " + (System.totalMemory > 0));
+trace("totalMemoryNumber == totalMemory: " + (System.totalMemoryNumber == System.totalMemory));
+System.gc();
+trace("gc requested");
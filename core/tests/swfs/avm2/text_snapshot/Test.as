@@ -0,0 +1,47 @@
+package {
+	import flash.display.MovieClip;
+	import flash.text.TextSnapshot;
+
+	// Frame 1 places two static texts, using "TestFont" at 12px.
+	// Every glyph is 10px wide, and the font's ascent and descent are 800 and 224
+	// of its 1024 EM square.
+	// The first text has two lines, "Hello" and "World", and is placed at (10, 20).
+	// The second text is "Text", and is placed at (10, 60).
+	public class Test extends MovieClip {
+		public var root:MovieClip;
+		public var snapshot:TextSnapshot;
+
+		public function Test() {
+			root = this;
+			addFrameScript(1, frame2);
+		}
+
+		public function frame2() {
+			snapshot = root.textSnapshot;
+			trace("charCount: " + snapshot.charCount);
+			trace("getText(0, charCount, true): " + snapshot.getText(0, 14, true));
+			trace("getText(0, charCount): " + snapshot.getText(0, 14));
+			trace("getText(3, 8): " + snapshot.getText(3, 8));
+			trace("getText(10, 100): " + snapshot.getText(10, 100));
+			trace("findText(0, \"world\", false): " + snapshot.findText(0, "world", false));
+			trace("findText(0, \"world\", true): " + snapshot.findText(0, "world", true));
+			trace("findText(5, \"o\", true): " + snapshot.findText(5, "o", true));
+			snapshot.setSelected(5, 10, true);
+			trace("getSelected(0, 5): " + snapshot.getSelected(0, 5));
+			trace("getSelected(9, 12): " + snapshot.getSelected(9, 12));
+			trace("getSelectedText(): " + snapshot.getSelectedText());
+			snapshot.setSelectColor(0x00FF);
+			snapshot.setSelected(0, 14, false);
+			trace("getSelectedText() after deselecting: " + snapshot.getSelectedText());
+			trace("hitTestTextNearPos(15, 25): " + snapshot.hitTestTextNearPos(15, 25));
+			trace("hitTestTextNearPos(35, 25): " + snapshot.hitTestTextNearPos(35, 25));
+			trace("hitTestTextNearPos(62, 25): " + snapshot.hitTestTextNearPos(62, 25));
+			trace("hitTestTextNearPos(62, 25, 5): " + snapshot.hitTestTextNearPos(62, 25, 5));
+			trace("getTextRunInfo(0, 2).length: " + snapshot.getTextRunInfo(0, 2).length);
+			var names = ["indexInRun", "font", "height", "selected", "matrix_tx", "matrix_ty", "corner0x", "corner0y", "corner2x"];
+			for each (var name in names) {
+				trace("getTextRunInfo(6, 7)[0]." + name + ": " + snapshot.getTextRunInfo(6, 7)[0][name]);
+			}
+		}
+	}
+}
@@ -0,0 +1,18 @@
+package {
+	public class Test {}
+}
+
+import flash.text.TextField;
+
+var tf = new TextField();
+trace("default: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness);
+tf.antiAliasType = "advanced";
+tf.sharpness = 123.5;
+tf.thickness = -42;
+trace("round trip: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness);
+tf.sharpness = 1000;
+tf.thickness = -1000;
+trace("clamped: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness);
+tf.antiAliasType = "normal";
+tf.sharpness = NaN;
+trace("normal: " + tf.antiAliasType + " sharpness=" + tf.sharpness + " thickness=" + tf.thickness);
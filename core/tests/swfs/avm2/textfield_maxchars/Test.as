@@ -0,0 +1,43 @@
+package {
+	public class Test {}
+}
+
+import flash.text.TextField;
+
+var tf = new TextField();
+trace("maxChars: " + tf.maxChars);
+tf.type = "input";
+tf.maxChars = 5;
+trace("maxChars: " + tf.maxChars);
+tf.appendText("Hello World");
+trace("appendText: " + tf.text + " length=" + tf.length);
+tf.appendText("!");
+trace("appendText when full: " + tf.text + " length=" + tf.length);
+tf.text = "";
+tf.replaceText(0, 0, "Hello World");
+trace("replaceText: " + tf.text + " length=" + tf.length);
+tf.replaceText(0, 5, "Hi there");
+trace("replaceText over all: " + tf.text + " length=" + tf.length);
+tf.replaceText(2, 4, "ppy days");
+trace("replaceText in the middle: " + tf.text + " length=" + tf.length);
+
+tf.text = "";
+tf.restrict = "a-z";
+tf.appendText("H e l l o world");
+trace("restricted: " + tf.text + " length=" + tf.length);
+tf.restrict = null;
+
+tf.text = "";
+tf.maxChars = 3;
+tf.appendText("héllo");
+trace("multibyte: " + tf.text);
+
+tf.maxChars = 0;
+tf.text = "";
+tf.appendText("Hello World");
+trace("unlimited: " + tf.text + " length=" + tf.length);
+
+tf.type = "dynamic";
+tf.maxChars = 5;
+tf.appendText("!!!");
+trace("dynamic: " + tf.text + " length=" + tf.length);
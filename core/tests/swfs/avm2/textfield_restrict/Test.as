@@ -0,0 +1,32 @@
+package {
+	public class Test {}
+}
+
+import flash.text.TextField;
+
+var tf = new TextField();
+trace("restrict: " + tf.restrict);
+trace("type: " + tf.type);
+tf.type = "input";
+trace("type: " + tf.type);
+tf.restrict = "0-9";
+trace("restrict: " + tf.restrict);
+tf.appendText("a1b2");
+trace("appendText: " + tf.text);
+tf.replaceText(0, 1, "x9");
+trace("replaceText: " + tf.text);
+tf.restrict = "^aeiou";
+tf.appendText("hello");
+trace("^aeiou: " + tf.text);
+tf.restrict = "A-Z\\-";
+tf.appendText("Ab-C");
+trace("escape: " + tf.text + " length=" + tf.length);
+trace("restrict: " + tf.restrict);
+tf.restrict = null;
+trace("restrict is null: " + (tf.restrict === null));
+tf.appendText("any text");
+trace("unrestricted: " + tf.text);
+tf.restrict = "";
+tf.appendText("nothing");
+trace("empty: " + tf.text);
+trace("restrict is empty: " + (tf.restrict === ""));
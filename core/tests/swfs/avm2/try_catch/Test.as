@@ -0,0 +1,41 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var thrower:Function = function():void {
+				throw new Error("boom");
+			};
+
+			try {
+				throw "thrown string";
+			} catch (e) {
+				trace("caught: " + e);
+			}
+
+			try {
+				thrower();
+			} catch (e:Error) {
+				trace("caught from function: " + e.message);
+			}
+
+			try {
+				try {
+					throw new Error("mismatch");
+				} catch (e:SecurityError) {
+					trace("inner caught: " + e);
+				}
+			} catch (e:Error) {
+				trace("outer caught: " + e);
+			}
+
+			try {
+				throw "typed string";
+			} catch (e:String) {
+				trace("caught String: " + e);
+			}
+
+			trace("done");
+		}
+	}
+}
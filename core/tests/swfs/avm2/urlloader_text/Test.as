@@ -0,0 +1,37 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		public var loader:URLLoader;
+
+		public function Test() {
+			loader = new URLLoader();
+			trace("dataFormat: " + loader.dataFormat);
+			trace("data: " + loader.data);
+			loader.addEventListener("open", function(e) {
+				trace("event: " + e.type);
+			});
+			loader.addEventListener("progress", function(e) {
+				trace("event: " + e.type + ", bytesLoaded: " + e.bytesLoaded + ", bytesTotal: " + e.bytesTotal);
+			});
+			loader.addEventListener("httpStatus", function(e) {
+				trace("event: " + e.type + ", status: " + e.status);
+			});
+			loader.addEventListener("ioError", function(e) {
+				trace("event: " + e.type + ", errorID: " + e.errorID);
+				trace("text: " + e.text);
+			});
+			loader.addEventListener("complete", function(e) {
+				trace("event: " + e.type);
+				trace("bytesLoaded: " + loader.bytesLoaded + ", bytesTotal: " + loader.bytesTotal);
+				trace("data: " + loader.data);
+				trace("data is String: " + (loader.data is String));
+			});
+			loader.dataFormat = "text";
+			loader.load(new URLRequest("data.txt"));
+			trace("load started");
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.net.URLVariables;
+
+	public class Test extends MovieClip {
+		public var loader:URLLoader;
+
+		public function Test() {
+			loader = new URLLoader();
+			trace("dataFormat: " + loader.dataFormat);
+			trace("data: " + loader.data);
+			loader.addEventListener("open", function(e) {
+				trace("event: " + e.type);
+			});
+			loader.addEventListener("progress", function(e) {
+				trace("event: " + e.type + ", bytesLoaded: " + e.bytesLoaded + ", bytesTotal: " + e.bytesTotal);
+			});
+			loader.addEventListener("httpStatus", function(e) {
+				trace("event: " + e.type + ", status: " + e.status);
+			});
+			loader.addEventListener("ioError", function(e) {
+				trace("event: " + e.type + ", errorID: " + e.errorID);
+				trace("text: " + e.text);
+			});
+			loader.addEventListener("complete", function(e) {
+				trace("event: " + e.type);
+				trace("bytesLoaded: " + loader.bytesLoaded + ", bytesTotal: " + loader.bytesTotal);
+				trace("data.name: " + loader.data.name);
+				trace("data.greeting: " + loader.data.greeting);
+				trace("data.empty: " + loader.data.empty);
+				trace("data is URLVariables: " + (loader.data is URLVariables));
+			});
+			loader.dataFormat = "variables";
+			loader.load(new URLRequest("data.txt"));
+			trace("load started");
+		}
+	}
+}
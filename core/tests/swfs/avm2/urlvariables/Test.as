@@ -0,0 +1,42 @@
+package {
+	public class Test {}
+}
+
+import flash.net.URLRequest;
+import flash.net.URLVariables;
+
+var v = new URLVariables("name=ruffle&greeting=hello%20world%21&plus=a+b&empty=&list=1&list=2");
+trace("v.name: " + v.name);
+trace("v.greeting: " + v.greeting);
+trace("v.plus: " + v.plus);
+trace("v.empty: [" + v.empty + "]");
+trace("v.list is Array: " + (v.list is Array));
+trace("v.list: " + v.list);
+trace("v.toString(): " + v.toString());
+
+var w = new URLVariables();
+trace("empty toString(): [" + w.toString() + "]");
+w.a = "x y&z=é";
+w["b c"] = "-_.!~*'()@/+";
+trace("w.toString(): " + w.toString());
+var round = new URLVariables(w.toString());
+trace("round.a: " + round.a);
+trace("round['b c']: " + round["b c"]);
+round.decode("extra=1&a=again");
+trace("after decode, round.extra: " + round.extra);
+trace("after decode, round.a: " + round.a);
+
+var r = new URLRequest("http://example.com/data");
+trace("r.url: " + r.url);
+trace("r.method: " + r.method);
+trace("r.data: " + r.data);
+trace("r.contentType: " + r.contentType);
+trace("r.requestHeaders.length: " + r.requestHeaders.length);
+r.method = "post";
+trace("r.method: " + r.method);
+r.data = v;
+trace("r.data == v: " + (r.data === v));
+r.contentType = "text/plain";
+trace("r.contentType: " + r.contentType);
+r.url = "other.txt";
+trace("r.url: " + r.url);
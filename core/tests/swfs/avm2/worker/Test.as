@@ -0,0 +1,35 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.system.MessageChannel;
+	import flash.system.Worker;
+	import flash.system.WorkerDomain;
+
+	var worker:Worker;
+	var channel:MessageChannel;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// The test harness shares the bytes of `worker.swf` under this name.
+			worker = WorkerDomain.current.createWorker(Worker.current.getSharedProperty("workerSwf"));
+			trace("Worker.current.isPrimordial: " + Worker.current.isPrimordial);
+			trace("worker.isPrimordial: " + worker.isPrimordial);
+			trace("worker.state: " + worker.state);
+
+			channel = worker.createMessageChannel(Worker.current);
+			channel.addEventListener("channelMessage", function(e:Event) {
+				trace("message: " + channel.receive());
+				trace("reply: " + worker.getSharedProperty("reply"));
+				worker.terminate();
+			});
+			worker.addEventListener(Event.WORKER_STATE, function(e:Event) {
+				trace("workerState: " + worker.state);
+			});
+
+			worker.setSharedProperty("greeting", "hello from the main movie");
+			worker.setSharedProperty("channel", channel);
+			worker.start();
+			trace("started");
+		}
+	}
+}
@@ -0,0 +1,10 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.system.Worker;
+
+Worker.current.setSharedProperty("reply",
+	Worker.current.getSharedProperty("greeting") + ", and hello from the worker (primordial: " + Worker.current.isPrimordial + ")");
+Worker.current.getSharedProperty("channel").send("hello from the worker");
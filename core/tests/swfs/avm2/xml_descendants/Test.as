@@ -0,0 +1,32 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var xml = new XML("<root><item id=\"1\">a</item><group id=\"2\"><item id=\"3\">b</item><sub><item id=\"4\">c</item></sub></group></root>");
+
+			trace("// xml..item.length()");
+			trace(xml..item.length());
+			trace("// xml..item.toXMLString()");
+			trace(xml..item.toXMLString());
+			trace("// xml..item");
+			trace(xml..item);
+			trace("// xml..item[2]");
+			trace(xml..item[2]);
+
+			trace("// xml..@id.length()");
+			trace(xml..@id.length());
+			trace("// xml..@id");
+			trace(xml..@id);
+
+			trace("// xml..sub..item");
+			trace(xml..sub..item.toXMLString());
+
+			trace("// xml..*.length()");
+			trace(xml..*.length());
+
+			trace("// xml..missing.length()");
+			trace(xml..missing.length());
+		}
+	}
+}
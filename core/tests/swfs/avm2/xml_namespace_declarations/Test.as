@@ -0,0 +1,36 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var x = new XML("<a xmlns:foo='http://x'><foo:b/></a>");
+			trace("// x");
+			trace(x.toXMLString());
+			var child = x.children()[0];
+			trace("// x.children()[0]");
+			trace(child.toXMLString());
+
+			delete x.children()[0];
+			trace("// x after delete x.children()[0]");
+			trace(x.toXMLString());
+			trace("// deleted child");
+			trace(child.toXMLString());
+
+			x.appendChild(child);
+			trace("// x after x.appendChild(child)");
+			trace(x.toXMLString());
+
+			var c = new XML("<c/>");
+			c.appendChild(child);
+			trace("// c after c.appendChild(child)");
+			trace(c.toXMLString());
+			trace("// x after c.appendChild(child)");
+			trace(x.toXMLString());
+
+			trace("// redundant declaration");
+			trace(new XML("<a xmlns:foo='http://x'><foo:b xmlns:foo='http://x'/></a>").toXMLString());
+			trace("// redeclared prefix");
+			trace(new XML("<a xmlns:foo='http://x'><foo:b xmlns:foo='http://y'/></a>").toXMLString());
+		}
+	}
+}
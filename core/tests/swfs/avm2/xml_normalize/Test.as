@@ -0,0 +1,51 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var text = new XML("<root/>");
+			text.appendChild("b");
+			text.appendChild("c");
+			text.insertChildBefore(text.children()[0], "a");
+			trace("// text.children().length()");
+			trace(text.children().length());
+			trace("// text.toXMLString()");
+			trace(text.toXMLString());
+			text.normalize();
+			trace("// text.children().length()");
+			trace(text.children().length());
+			trace("// text.toXMLString()");
+			trace(text.toXMLString());
+
+			var mixed = new XML("<root/>");
+			mixed.appendChild("a");
+			mixed.appendChild(new XML("<b>x</b>"));
+			mixed.appendChild("c");
+			mixed.appendChild("d");
+			trace("// mixed.children().length()");
+			trace(mixed.children().length());
+			trace("// mixed.toXMLString()");
+			trace(mixed.toXMLString());
+			mixed.normalize();
+			trace("// mixed.children().length()");
+			trace(mixed.children().length());
+			trace("// mixed.toXMLString()");
+			trace(mixed.toXMLString());
+
+			var spaced = new XML("<root/>");
+			spaced.appendChild("   ");
+			spaced.appendChild(new XML("<b>x</b>"));
+			spaced.appendChild(" ");
+			spaced.appendChild(" ");
+			trace("// spaced.children().length()");
+			trace(spaced.children().length());
+			trace("// spaced.toXMLString()");
+			trace(spaced.toXMLString());
+			spaced.normalize();
+			trace("// spaced.children().length()");
+			trace(spaced.children().length());
+			trace("// spaced.toXMLString()");
+			trace(spaced.toXMLString());
+		}
+	}
+}
@@ -1,12 +1,14 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use generational_arena::Arena;
 use ruffle_core::backend::audio::decoders::{
-    self, AdpcmDecoder, Mp3Decoder, PcmDecoder, SeekableDecoder,
+    self, AdpcmDecoder, Decoder, Mp3Decoder, NellymoserDecoder, PcmDecoder, SeekableDecoder,
 };
+use ruffle_core::backend::audio::sound_info::{SoundEnvelope, SoundRange};
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
+    self, swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::tag_utils::SwfSlice;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 use swf::AudioCompression;
@@ -18,6 +20,8 @@ pub struct CpalAudioBackend {
     stream: Stream,
     sounds: Arena<Sound>,
     sound_instances: Arc<Mutex<Arena<SoundInstance>>>,
+    master_transform: Arc<Mutex<SoundTransform>>,
+    generated_sounds: HashMap<SoundInstanceHandle, Arc<Mutex<GeneratedSoundBuffer>>>,
 }
 
 // Because of https://github.com/RustAudio/cpal/pull/348, we have to initialize cpal on a
@@ -66,6 +70,13 @@ struct SoundInstance {
     /// If this flag is false, the sound will be cleaned up during the
     /// next loop of the sound thread.
     active: bool,
+
+    /// How many sample frames of this sound have been mixed into the output.
+    sample_frames_played: u64,
+
+    /// The volume of the left and right channels, as set by the sound's
+    /// transform.
+    volumes: [f32; 2],
 }
 
 impl CpalAudioBackend {
@@ -97,10 +108,12 @@ impl CpalAudioBackend {
         let config = cpal::StreamConfig::from(config);
 
         let sound_instances: Arc<Mutex<Arena<SoundInstance>>> = Arc::new(Mutex::new(Arena::new()));
+        let master_transform = Arc::new(Mutex::new(SoundTransform::default()));
 
         // Start the audio stream.
         let stream = {
             let sound_instances = Arc::clone(&sound_instances);
+            let master_transform = Arc::clone(&master_transform);
             let error_handler = move |err| log::error!("Audio stream error: {}", err);
             let output_config = config.clone();
 
@@ -109,24 +122,42 @@ impl CpalAudioBackend {
                 SampleFormat::F32 => device.build_output_stream(
                     &config,
                     move |buffer, _| {
+                        let master_volumes = master_transform.lock().unwrap().channel_volumes();
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<f32>(&mut sound_instances, &output_config, buffer)
+                        Self::mix_audio::<f32>(
+                            &mut sound_instances,
+                            master_volumes,
+                            &output_config,
+                            buffer,
+                        )
                     },
                     error_handler,
                 ),
                 SampleFormat::I16 => device.build_output_stream(
                     &config,
                     move |buffer, _| {
+                        let master_volumes = master_transform.lock().unwrap().channel_volumes();
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<i16>(&mut sound_instances, &output_config, buffer)
+                        Self::mix_audio::<i16>(
+                            &mut sound_instances,
+                            master_volumes,
+                            &output_config,
+                            buffer,
+                        )
                     },
                     error_handler,
                 ),
                 SampleFormat::U16 => device.build_output_stream(
                     &config,
                     move |buffer, _| {
+                        let master_volumes = master_transform.lock().unwrap().channel_volumes();
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<u16>(&mut sound_instances, &output_config, buffer)
+                        Self::mix_audio::<u16>(
+                            &mut sound_instances,
+                            master_volumes,
+                            &output_config,
+                            buffer,
+                        )
                     },
                     error_handler,
                 ),
@@ -141,6 +172,8 @@ impl CpalAudioBackend {
             stream: Stream(stream),
             sounds: Arena::new(),
             sound_instances,
+            master_transform,
+            generated_sounds: HashMap::new(),
         })
     }
 
@@ -166,6 +199,15 @@ impl CpalAudioBackend {
                 format.sample_rate.into(),
                 data,
             )),
+            AudioCompression::Nellymoser16Khz => Box::new(NellymoserDecoder::new(data, 16000)),
+            AudioCompression::Nellymoser8Khz => Box::new(NellymoserDecoder::new(data, 8000)),
+            AudioCompression::Nellymoser => {
+                log::warn!(
+                    "start_stream: Nellymoser sound at {} Hz is unsupported; playing silence",
+                    format.sample_rate
+                );
+                Box::new(NellymoserDecoder::silent(data, format.sample_rate))
+            }
             _ => {
                 let msg = format!(
                     "start_stream: Unhandled audio compression {:?}",
@@ -182,7 +224,7 @@ impl CpalAudioBackend {
     /// TODO: Allow interpolator to be user-configurable?
     fn make_resampler<S: Send + dasp::signal::Signal<Frame = [i16; 2]>>(
         &self,
-        format: &swf::SoundFormat,
+        sample_rate: u16,
         mut signal: S,
     ) -> dasp::signal::interpolate::Converter<
         S,
@@ -194,7 +236,7 @@ impl CpalAudioBackend {
         dasp::signal::interpolate::Converter::from_hz_to_hz(
             signal,
             interpolator,
-            format.sample_rate.into(),
+            sample_rate.into(),
             self.output_config.sample_rate.0.into(),
         )
     }
@@ -209,6 +251,7 @@ impl CpalAudioBackend {
     ) -> Result<Box<dyn Send + dasp::signal::Signal<Frame = [i16; 2]>>, Error> {
         // Instantiate a decoder for the compression that the sound data uses.
         let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let sample_rate = decoder.sample_rate();
 
         // Wrap the decoder in the event sound signal (controls looping/envelope)
         let signal = EventSoundSignal::new_with_settings(
@@ -219,7 +262,7 @@ impl CpalAudioBackend {
         );
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
-        let signal = self.make_resampler(&sound.format, signal);
+        let signal = self.make_resampler(sample_rate, signal);
         Ok(Box::new(signal))
     }
 
@@ -234,8 +277,9 @@ impl CpalAudioBackend {
 
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
+        let sample_rate = clip_stream_decoder.sample_rate();
         let signal = dasp::signal::from_iter(clip_stream_decoder);
-        let signal = Box::new(self.make_resampler(format, signal));
+        let signal = Box::new(self.make_resampler(sample_rate, signal));
         Ok(signal)
     }
 
@@ -251,16 +295,19 @@ impl CpalAudioBackend {
 
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
+        let sample_rate = decoder.sample_rate();
         let signal = dasp::signal::from_iter(decoder);
-        let signal = self.make_resampler(format, signal);
+        let signal = self.make_resampler(sample_rate, signal);
         Ok(Box::new(signal))
     }
 
     /// Callback to the audio thread.
     /// Refill the output buffer by stepping through all active sounds
-    /// and mixing in their output.
+    /// and mixing in their output, scaled by their volume and the master
+    /// volume.
     fn mix_audio<'a, T>(
         sound_instances: &mut Arena<SoundInstance>,
+        master_volumes: [f32; 2],
         output_format: &cpal::StreamConfig,
         mut output_buffer: &mut [T],
     ) where
@@ -281,7 +328,12 @@ impl CpalAudioBackend {
             let mut output_frame = Stereo::<T::Signed>::EQUILIBRIUM;
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.signal.is_exhausted() {
-                    let sound_frame = sound.signal.next();
+                    let [left, right] = sound.signal.next();
+                    sound.sample_frames_played += 1;
+                    let sound_frame = [
+                        (f32::from(left) * sound.volumes[0] * master_volumes[0]) as i16,
+                        (f32::from(right) * sound.volumes[1] * master_volumes[1]) as i16,
+                    ];
                     let sound_frame: Stereo<T::Signed> = Frame::map(sound_frame, Sample::to_sample);
                     output_frame = output_frame.add_amp(sound_frame);
                 } else {
@@ -347,6 +399,8 @@ impl AudioBackend for CpalAudioBackend {
             clip_id: Some(clip_id),
             signal,
             active: true,
+            sample_frames_played: 0,
+            volumes: [1.0, 1.0],
         });
         Ok(handle)
     }
@@ -356,6 +410,31 @@ impl AudioBackend for CpalAudioBackend {
         sound_instances.remove(stream);
     }
 
+    fn get_stream_position(&self, stream: AudioStreamHandle) -> Option<f64> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        let sample_rate = f64::from(self.output_config.sample_rate.0);
+        sound_instances
+            .get(stream)
+            .map(|sound| sound.sample_frames_played as f64 * 1000.0 / sample_rate)
+    }
+
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        sound_instances
+            .get(instance)
+            .map(|sound| sound.active)
+            .unwrap_or(false)
+    }
+
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<f64> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        let sample_rate = f64::from(self.output_config.sample_rate.0);
+        sound_instances
+            .get(instance)
+            .filter(|sound| sound.active)
+            .map(|sound| sound.sample_frames_played as f64 * 1000.0 / sample_rate)
+    }
+
     fn start_sound(
         &mut self,
         sound_handle: SoundHandle,
@@ -384,16 +463,83 @@ impl AudioBackend for CpalAudioBackend {
             clip_id: None,
             signal,
             active: true,
+            sample_frames_played: 0,
+            volumes: [1.0, 1.0],
         });
         Ok(handle)
     }
 
+    fn start_generated_sound(&mut self) -> Result<SoundInstanceHandle, Error> {
+        let buffer = Arc::new(Mutex::new(GeneratedSoundBuffer::default()));
+        let signal = self.make_resampler(
+            GENERATED_SOUND_SAMPLE_RATE,
+            GeneratedSoundSignal::new(Arc::clone(&buffer)),
+        );
+
+        let mut sound_instances = self.sound_instances.lock().unwrap();
+        let handle = sound_instances.insert(SoundInstance {
+            handle: None,
+            clip_id: None,
+            signal: Box::new(signal),
+            active: true,
+            sample_frames_played: 0,
+            volumes: [1.0, 1.0],
+        });
+        self.generated_sounds.insert(handle, buffer);
+        Ok(handle)
+    }
+
+    fn generated_sound_samples_needed(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let buffer = self.generated_sounds.get(&instance)?;
+        if !self.sound_instances.lock().unwrap().contains(instance) {
+            return None;
+        }
+
+        let buffer = buffer.lock().unwrap();
+        if buffer.end_of_stream {
+            Some(0)
+        } else {
+            Some(GENERATED_SOUND_BUFFER_FRAMES.saturating_sub(buffer.frames.len()))
+        }
+    }
+
+    fn append_generated_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        end_of_stream: bool,
+    ) {
+        if let Some(buffer) = self.generated_sounds.get(&instance) {
+            let mut buffer = buffer.lock().unwrap();
+            buffer.frames.extend(samples.iter().map(|[left, right]| {
+                [
+                    (left.max(-1.0).min(1.0) * 32767.0) as i16,
+                    (right.max(-1.0).min(1.0) * 32767.0) as i16,
+                ]
+            }));
+            buffer.end_of_stream |= end_of_stream;
+        }
+    }
+
     fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self.sound_instances.lock().unwrap();
         sound_instances.remove(sound);
+        self.generated_sounds.remove(&sound);
+    }
+
+    fn set_sound_transform(&mut self, sound: SoundInstanceHandle, transform: SoundTransform) {
+        let mut sound_instances = self.sound_instances.lock().unwrap();
+        if let Some(instance) = sound_instances.get_mut(sound) {
+            instance.volumes = transform.channel_volumes();
+        }
+    }
+
+    fn set_master_transform(&mut self, transform: SoundTransform) {
+        *self.master_transform.lock().unwrap() = transform;
     }
 
     fn stop_all_sounds(&mut self) {
+        self.generated_sounds.clear();
         let mut sound_instances = self.sound_instances.lock().unwrap();
         // This is a workaround for a bug in generational-arena:
         // Arena::clear does not properly bump the generational index, allowing for stale references
@@ -422,6 +568,23 @@ impl AudioBackend for CpalAudioBackend {
         }
     }
 
+    fn extract_sound_samples(
+        &self,
+        sound: SoundHandle,
+        start: usize,
+        length: usize,
+    ) -> Option<Vec<[f32; 2]>> {
+        let sound = self.sounds.get(sound)?;
+        audio::decode_sound_samples(
+            &sound.format,
+            &sound.data,
+            sound.skip_sample_frames,
+            start,
+            length,
+        )
+        .ok()
+    }
+
     fn is_sound_playing_with_handle(&mut self, handle: SoundHandle) -> bool {
         let sound_instances = self.sound_instances.lock().unwrap();
         let handle = Some(handle);
@@ -433,6 +596,54 @@ impl AudioBackend for CpalAudioBackend {
     fn tick(&mut self) {}
 }
 
+/// The sample rate of sounds generated by `sampleData` event handlers.
+const GENERATED_SOUND_SAMPLE_RATE: u16 = 44100;
+
+/// How many sample frames of a generated sound to keep buffered ahead of the
+/// audio thread.
+const GENERATED_SOUND_BUFFER_FRAMES: usize = 8192;
+
+/// Samples of a generated sound that have yet to be played, shared between
+/// the player and the audio thread.
+#[derive(Default)]
+struct GeneratedSoundBuffer {
+    frames: VecDeque<[i16; 2]>,
+
+    /// Whether the sound ends once `frames` has been drained.
+    end_of_stream: bool,
+}
+
+/// A signal that plays back the samples of a generated sound.
+///
+/// Plays silence if the buffer runs dry before the end of the stream.
+struct GeneratedSoundSignal {
+    buffer: Arc<Mutex<GeneratedSoundBuffer>>,
+}
+
+impl GeneratedSoundSignal {
+    fn new(buffer: Arc<Mutex<GeneratedSoundBuffer>>) -> Self {
+        Self { buffer }
+    }
+}
+
+impl dasp::signal::Signal for GeneratedSoundSignal {
+    type Frame = [i16; 2];
+
+    fn next(&mut self) -> Self::Frame {
+        self.buffer
+            .lock()
+            .unwrap()
+            .frames
+            .pop_front()
+            .unwrap_or([0, 0])
+    }
+
+    fn is_exhausted(&self) -> bool {
+        let buffer = self.buffer.lock().unwrap();
+        buffer.end_of_stream && buffer.frames.is_empty()
+    }
+}
+
 /// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>`.
 /// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
 struct ArcAsRef(Arc<[u8]>);
@@ -453,11 +664,15 @@ impl Default for ArcAsRef {
 /// A signal for event sound instances using sound settings (looping, start/end point, envelope).
 struct EventSoundSignal {
     decoder: Box<dyn SeekableDecoder + Send>,
-    num_loops: u16,
-    envelope_signal: Option<EnvelopeSignal>,
-    start_sample_frame: u32,
-    end_sample_frame: Option<u32>,
+    sample_rate: u16,
+    range: SoundRange,
+    loops_remaining: u16,
+    envelope: Option<SoundEnvelope>,
     cur_sample_frame: u32,
+
+    /// How many sample frames have been played over all loops.
+    /// The envelope is positioned by this.
+    sample_frames_played: u64,
     is_exhausted: bool,
 }
 
@@ -468,29 +683,21 @@ impl EventSoundSignal {
         num_sample_frames: u32,
         skip_sample_frames: u16,
     ) -> Self {
-        let skip_sample_frames = u32::from(skip_sample_frames);
-        let sample_divisor = 44100 / u32::from(decoder.sample_rate());
-        let start_sample_frame =
-            settings.in_sample.unwrap_or(0) / sample_divisor + skip_sample_frames;
-        let end_sample_frame = settings
-            .out_sample
-            .map(|n| n / sample_divisor)
-            .unwrap_or(num_sample_frames)
-            + skip_sample_frames;
-
-        let envelope_signal = if let Some(envelope) = &settings.envelope {
-            Some(EnvelopeSignal::new(envelope.clone()))
-        } else {
-            None
-        };
+        let sample_rate = decoder.sample_rate();
+        let range = SoundRange::new(settings, sample_rate, num_sample_frames, skip_sample_frames);
+        let envelope = settings
+            .envelope
+            .as_ref()
+            .map(|envelope| SoundEnvelope::new(envelope));
 
         let mut signal = Self {
             decoder,
-            num_loops: settings.num_loops,
-            envelope_signal,
-            start_sample_frame,
-            end_sample_frame: Some(end_sample_frame),
-            cur_sample_frame: start_sample_frame,
+            sample_rate,
+            loops_remaining: range.num_loops,
+            cur_sample_frame: range.start_sample_frame,
+            range,
+            envelope,
+            sample_frames_played: 0,
             is_exhausted: false,
         };
         signal.next_loop();
@@ -501,10 +708,11 @@ impl EventSoundSignal {
 impl EventSoundSignal {
     /// Resets the decoder to the start point of the loop.
     fn next_loop(&mut self) {
-        if self.num_loops > 0 {
-            self.num_loops -= 1;
-            self.decoder.seek_to_sample_frame(self.start_sample_frame);
-            self.cur_sample_frame = self.start_sample_frame;
+        if self.loops_remaining > 0 && self.range.loop_sample_frames() > 0 {
+            self.loops_remaining -= 1;
+            self.decoder
+                .seek_to_sample_frame(self.range.start_sample_frame);
+            self.cur_sample_frame = self.range.start_sample_frame;
         } else {
             self.is_exhausted = true;
         }
@@ -515,117 +723,35 @@ impl dasp::signal::Signal for EventSoundSignal {
     type Frame = [i16; 2];
 
     fn next(&mut self) -> Self::Frame {
-        // Loop the sound if necessary, and get the next frame.
-        if !self.is_exhausted {
-            let frame = if let Some(frame) = self.decoder.next() {
-                self.cur_sample_frame += 1;
-                if let Some(end) = self.end_sample_frame {
-                    if self.cur_sample_frame > end {
-                        self.next_loop();
-                    }
-                }
-                frame
-            } else {
+        let frame = loop {
+            // Loop the sound if necessary, and get the next frame.
+            if self.cur_sample_frame >= self.range.end_sample_frame {
                 self.next_loop();
-                self.next()
-            };
-            if let Some(envelope) = &mut self.envelope_signal {
-                use dasp::frame::Frame;
-                frame.mul_amp(envelope.next())
-            } else {
-                frame
             }
-        } else {
-            [0, 0]
-        }
-    }
-
-    fn is_exhausted(&self) -> bool {
-        self.is_exhausted
-    }
-}
-
-/// A signal that represents the sound envelope for an event sound.
-/// The sound signal gets multiplied by the envelope for volume/panning effects.
-struct EnvelopeSignal {
-    /// Iterator through the envelope points specified in the SWWF file.
-    envelope: std::vec::IntoIter<swf::SoundEnvelopePoint>,
-
-    /// The starting envelope point.
-    prev_point: swf::SoundEnvelopePoint,
-
-    /// The ending envelope point.
-    next_point: swf::SoundEnvelopePoint,
+            if self.is_exhausted {
+                return [0, 0];
+            }
 
-    /// The current sample index.
-    cur_sample: u32,
-}
+            if let Some(frame) = self.decoder.next() {
+                self.cur_sample_frame += 1;
+                break frame;
+            }
 
-impl EnvelopeSignal {
-    fn new(envelope: swf::SoundEnvelope) -> Self {
-        // TODO: This maybe can be done more clever using the `sample` crate.
-        let mut envelope = envelope.into_iter();
-        let first_point = envelope.next().unwrap_or(swf::SoundEnvelopePoint {
-            sample: 0,
-            left_volume: 1.0,
-            right_volume: 1.0,
-        });
-        Self {
-            // The initial volume is the first point's volume.
-            prev_point: swf::SoundEnvelopePoint {
-                sample: 0,
-                left_volume: first_point.left_volume,
-                right_volume: first_point.right_volume,
-            },
-            next_point: first_point,
-            cur_sample: 0,
-            envelope,
-        }
-    }
-}
-impl dasp::signal::Signal for EnvelopeSignal {
-    type Frame = [f32; 2];
+            // The sound data ended before the end of the loop.
+            self.cur_sample_frame = self.range.end_sample_frame;
+        };
 
-    fn next(&mut self) -> Self::Frame {
-        // Calculate interpolated volume.
-        let out = if self.prev_point.sample < self.next_point.sample {
-            let a = f64::from(self.cur_sample - self.prev_point.sample);
-            let b = f64::from(self.next_point.sample - self.prev_point.sample);
-            let lerp = a / b;
-            let interpolator = dasp::interpolate::linear::Linear::new(
-                [self.prev_point.left_volume, self.prev_point.right_volume],
-                [self.next_point.left_volume, self.next_point.right_volume],
-            );
-            use dasp::interpolate::Interpolator;
-            interpolator.interpolate(lerp)
+        let frame = if let Some(envelope) = &self.envelope {
+            use dasp::frame::Frame;
+            frame.mul_amp(envelope.volume_after(self.sample_frames_played, self.sample_rate))
         } else {
-            [self.next_point.left_volume, self.next_point.right_volume]
+            frame
         };
-
-        // Update envelope endpoints.
-        self.cur_sample = self.cur_sample.saturating_add(1);
-        while self.cur_sample > self.next_point.sample {
-            self.prev_point = self.next_point.clone();
-            self.next_point = self
-                .envelope
-                .next()
-                .clone()
-                .unwrap_or(swf::SoundEnvelopePoint {
-                    sample: std::u32::MAX,
-                    left_volume: self.prev_point.left_volume,
-                    right_volume: self.prev_point.right_volume,
-                });
-
-            if self.prev_point.sample > self.next_point.sample {
-                self.next_point.sample = self.prev_point.sample;
-                log::error!("Invalid sound envelope; sample indices are out of order");
-            }
-        }
-
-        out
+        self.sample_frames_played += 1;
+        frame
     }
 
     fn is_exhausted(&self) -> bool {
-        false
+        self.is_exhausted
     }
 }
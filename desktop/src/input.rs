@@ -1,9 +1,11 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
-use ruffle_core::backend::input::{InputBackend, MouseCursor};
+use ruffle_core::backend::input::{InputBackend, MouseButton, MouseCursor};
 use ruffle_core::events::{KeyCode, PlayerEvent};
 use std::collections::HashSet;
 use std::rc::Rc;
-use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
+use winit::event::{
+    ElementState, ModifiersState, MouseButton as WinitMouseButton, VirtualKeyCode, WindowEvent,
+};
 use winit::window::Window;
 
 pub struct WinitInputBackend {
@@ -74,6 +76,7 @@ impl InputBackend for WinitInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains(&VirtualKeyCode::Back),
+            KeyCode::Tab => self.keys_down.contains(&VirtualKeyCode::Tab),
             KeyCode::Return => self.keys_down.contains(&VirtualKeyCode::Return),
             KeyCode::Shift => {
                 self.keys_down.contains(&VirtualKeyCode::LShift)
@@ -217,11 +220,23 @@ impl InputBackend for WinitInputBackend {
     }
 }
 
+/// Converts a winit `MouseButton` into a Ruffle `MouseButton`.
+/// Returns `None` if there is no matching Flash mouse button.
+pub fn winit_to_ruffle_mouse_button(button: WinitMouseButton) -> Option<MouseButton> {
+    match button {
+        WinitMouseButton::Left => Some(MouseButton::Left),
+        WinitMouseButton::Right => Some(MouseButton::Right),
+        WinitMouseButton::Middle => Some(MouseButton::Middle),
+        WinitMouseButton::Other(_) => None,
+    }
+}
+
 /// Converts a winit `VirtualKeyCode` into a Ruffle `KeyCode`.
 /// Returns `None` if there is no matching Flash key code.
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
     let out = match key_code {
         VirtualKeyCode::Back => KeyCode::Backspace,
+        VirtualKeyCode::Tab => KeyCode::Tab,
         VirtualKeyCode::Return => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
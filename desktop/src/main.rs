@@ -8,6 +8,7 @@ mod locale;
 mod navigator;
 mod storage;
 mod task;
+mod ui;
 
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
@@ -26,12 +27,13 @@ use url::Url;
 
 use crate::storage::DiskStorageBackend;
 use ruffle_core::backend::log::NullLogBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::io::Read;
 use std::rc::Rc;
 use winit::dpi::{LogicalSize, PhysicalPosition};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Icon, WindowBuilder};
 
@@ -196,6 +198,8 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
         opt.input_path.file_name().unwrap_or_default().as_ref(),
     ));
     let locale = Box::new(locale::DesktopLocaleBackend::new());
+    let ui = Box::new(ui::DesktopUiBackend::new(window.clone()));
+    let video = Box::new(SoftwareVideoBackend::new());
     let player = Player::new(
         renderer,
         audio,
@@ -204,6 +208,8 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
         storage,
         locale,
         Box::new(NullLogBackend::new()),
+        ui,
+        video,
     )?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player.lock().unwrap().set_is_playing(true); // Desktop player will auto-play.
@@ -265,25 +271,29 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                     WindowEvent::MouseInput {
-                        button: MouseButton::Left,
+                        button,
                         state: pressed,
                         ..
                     } => {
-                        let mut player_lock = player.lock().unwrap();
-                        let event = if pressed == ElementState::Pressed {
-                            ruffle_core::PlayerEvent::MouseDown {
-                                x: mouse_pos.x,
-                                y: mouse_pos.y,
-                            }
-                        } else {
-                            ruffle_core::PlayerEvent::MouseUp {
-                                x: mouse_pos.x,
-                                y: mouse_pos.y,
+                        if let Some(button) = input::winit_to_ruffle_mouse_button(button) {
+                            let mut player_lock = player.lock().unwrap();
+                            let event = if pressed == ElementState::Pressed {
+                                ruffle_core::PlayerEvent::MouseDown {
+                                    x: mouse_pos.x,
+                                    y: mouse_pos.y,
+                                    button,
+                                }
+                            } else {
+                                ruffle_core::PlayerEvent::MouseUp {
+                                    x: mouse_pos.x,
+                                    y: mouse_pos.y,
+                                    button,
+                                }
+                            };
+                            player_lock.handle_event(event);
+                            if player_lock.needs_render() {
+                                window.request_redraw();
                             }
-                        };
-                        player_lock.handle_event(event);
-                        if player_lock.needs_render() {
-                            window.request_redraw();
                         }
                     }
                     WindowEvent::MouseWheel { delta, .. } => {
@@ -123,10 +123,13 @@ impl NavigatorBackend for ExternalNavigatorBackend {
             _ => Box::pin(async move {
                 let client = client.ok_or(Error::NetworkUnavailable)?;
 
-                let request = match options.method() {
+                let mut request = match options.method() {
                     NavigationMethod::GET => Request::get(full_url.to_string()),
                     NavigationMethod::POST => Request::post(full_url.to_string()),
                 };
+                for (name, value) in options.headers() {
+                    request = request.header(name.as_str(), value.as_str());
+                }
 
                 let (body_data, _) = options.body().clone().unwrap_or_default();
                 let body = request
@@ -67,6 +67,30 @@ impl StorageBackend for DiskStorageBackend {
         }
     }
 
+    fn get_bytes(&self, name: &str) -> Option<Vec<u8>> {
+        let full_path = self.base_path.join(Path::new(name));
+
+        match fs::read(full_path) {
+            Ok(bytes) => Some(bytes),
+            Err(r) => {
+                log::warn!("Unable to read file {:?}", r);
+                None
+            }
+        }
+    }
+
+    fn put_bytes(&mut self, name: &str, value: &[u8]) -> bool {
+        let full_path = self.base_path.join(Path::new(name));
+
+        match fs::write(full_path, value) {
+            Ok(_) => true,
+            Err(r) => {
+                log::warn!("Unable to save file {:?}", r);
+                false
+            }
+        }
+    }
+
     fn remove_key(&mut self, name: &str) {
         let full_path = self.base_path.join(Path::new(name));
         let _ = fs::remove_file(full_path);
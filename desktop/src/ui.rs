@@ -0,0 +1,86 @@
+use ruffle_core::backend::ui::{ContextMenuItem, FileFilter, SelectedFile, UiBackend};
+use std::error::Error;
+use std::rc::Rc;
+use winit::window::{Fullscreen, Window};
+
+/// UI backend that displays the operating system's native dialogs.
+pub struct DesktopUiBackend {
+    window: Rc<Window>,
+}
+
+impl DesktopUiBackend {
+    pub fn new(window: Rc<Window>) -> Self {
+        Self { window }
+    }
+}
+
+impl UiBackend for DesktopUiBackend {
+    fn display_file_open_dialog(&mut self, filters: &[FileFilter]) -> Option<SelectedFile> {
+        // Native dialogs only take a single filter, so all of them are merged.
+        let patterns: Vec<&str> = filters.iter().flat_map(|f| f.patterns()).collect();
+        let description = filters
+            .iter()
+            .map(|f| f.description.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let filter = if patterns.is_empty() {
+            None
+        } else {
+            Some((&patterns[..], description.as_str()))
+        };
+
+        let path = tinyfiledialogs::open_file_dialog("Open", "", filter)?;
+        match SelectedFile::from_path(path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                log::error!("Could not open selected file: {}", e);
+                None
+            }
+        }
+    }
+
+    fn load_file(&mut self, file: &SelectedFile) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(std::fs::read(&file.path)?)
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        default_name: &str,
+        data: &[u8],
+    ) -> Result<Option<SelectedFile>, Box<dyn Error>> {
+        let path = match tinyfiledialogs::save_file_dialog("Save", default_name) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        std::fs::write(&path, data)?;
+        Ok(Some(SelectedFile::from_path(path)?))
+    }
+
+    fn load_device_font(
+        &mut self,
+        _name: &str,
+        _is_bold: bool,
+        _is_italic: bool,
+    ) -> Option<Vec<u8>> {
+        // TODO: Look up the fonts installed on the system.
+        None
+    }
+
+    fn build_context_menu(&mut self, _items: &[ContextMenuItem]) {
+        // TODO: Show a native context menu.
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.window.fullscreen().is_some()
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Box<dyn Error>> {
+        self.window.set_fullscreen(if is_full {
+            Some(Fullscreen::Borderless(self.window.current_monitor()))
+        } else {
+            None
+        });
+        Ok(())
+    }
+}
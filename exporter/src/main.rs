@@ -8,6 +8,8 @@ use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::NullLogBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::NullUiBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
@@ -111,6 +113,8 @@ fn take_screenshot(
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
         Box::new(NullLogBackend::new()),
+        Box::new(NullUiBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
     )?;
 
     player
@@ -1,7 +1,8 @@
 use ruffle_core::backend::render::{
-    swf::{self, CharacterId, GradientInterpolation, GradientSpread},
+    blur_radius,
+    swf::{self, BlendMode, CharacterId, GradientInterpolation, GradientSpread},
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, Letterbox, RenderBackend,
-    ShapeHandle, Transform,
+    ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
@@ -32,6 +33,10 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+    drawing_mask: bool,
+    quality: StageQuality,
+    blend_modes: Vec<BlendMode>,
+    filters: Vec<Vec<swf::Filter>>,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -93,7 +98,8 @@ enum CanvasFillStyle {
     Color(CanvasColor),
     #[allow(dead_code)]
     Gradient(CanvasGradient),
-    Pattern(CanvasPattern),
+    /// A bitmap pattern, and whether the bitmap is smoothed.
+    Pattern(CanvasPattern, bool),
 }
 
 impl CanvasFillStyle {
@@ -215,6 +221,10 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            drawing_mask: false,
+            quality: StageQuality::default(),
+            blend_modes: vec![],
+            filters: vec![],
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -306,6 +316,153 @@ impl WebCanvasRenderBackend {
         }
     }
 
+    /// Applies a filter to the current render target.
+    ///
+    /// Canvas blurs and shadows are gaussian and the same in both directions,
+    /// so they only approximate the filters of Flash.
+    fn apply_filter(&mut self, filter: &swf::Filter) {
+        // TODO: Glow, bevel and convolution filters.
+        if !matches!(
+            filter,
+            swf::Filter::BlurFilter(_)
+                | swf::Filter::DropShadowFilter(_)
+                | swf::Filter::ColorMatrixFilter(_)
+        ) {
+            return;
+        }
+
+        // The filtered group is drawn to a new render target, and then copied
+        // back to the render target of the group.
+        let (group_canvas, group_context) = (self.canvas.clone(), self.context.clone());
+        self.push_render_target();
+        let (canvas, context) = (self.canvas.clone(), self.context.clone());
+        let (width, height) = (
+            f64::from(group_canvas.width()),
+            f64::from(group_canvas.height()),
+        );
+
+        match filter {
+            swf::Filter::BlurFilter(blur) => {
+                let sigma = blur_sigma(blur.blur_x, blur.blur_y, blur.num_passes);
+                context.set_filter(&format!("blur({}px)", sigma));
+                context
+                    .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                    .warn_on_error();
+                context.set_filter("none");
+            }
+            swf::Filter::DropShadowFilter(shadow) => {
+                let offset_x = shadow.distance * shadow.angle.cos();
+                let offset_y = shadow.distance * shadow.angle.sin();
+                // TODO: Strengths above 1 can't be done with canvas shadows.
+                let alpha = f32::from(shadow.color.a) / 255.0 * shadow.strength.min(1.0);
+                let color = format!(
+                    "rgba({}, {}, {}, {})",
+                    shadow.color.r, shadow.color.g, shadow.color.b, alpha
+                );
+                // The shadow blur is twice the standard deviation of the blur.
+                let blur = 2.0 * blur_sigma(shadow.blur_x, shadow.blur_y, shadow.num_passes);
+
+                if shadow.is_inner {
+                    // An inner shadow is cast by the area around the object.
+                    context.set_fill_style(&"black".into());
+                    context.fill_rect(0.0, 0.0, width, height);
+                    context
+                        .set_global_composite_operation("destination-out")
+                        .warn_on_error();
+                    context
+                        .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                        .warn_on_error();
+
+                    self.push_render_target();
+                    let shadow_context = self.context.clone();
+                    set_shadow(&shadow_context, &color, blur);
+                    draw_shadow_only(&shadow_context, &canvas, offset_x, offset_y);
+                    set_shadow(&shadow_context, "transparent", 0.0);
+                    let (shadow_canvas, _) = self.pop_render_target();
+
+                    // The shadow is only drawn over the object.
+                    context
+                        .set_global_composite_operation("copy")
+                        .warn_on_error();
+                    context
+                        .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                        .warn_on_error();
+                    let operation = if shadow.is_knockout || shadow.hide_object {
+                        "source-in"
+                    } else {
+                        "source-atop"
+                    };
+                    context
+                        .set_global_composite_operation(operation)
+                        .warn_on_error();
+                    context
+                        .draw_image_with_html_canvas_element(&shadow_canvas, 0.0, 0.0)
+                        .warn_on_error();
+                } else {
+                    set_shadow(&context, &color, blur);
+                    if shadow.is_knockout || shadow.hide_object {
+                        draw_shadow_only(&context, &group_canvas, offset_x, offset_y);
+                    } else {
+                        context.set_shadow_offset_x(offset_x);
+                        context.set_shadow_offset_y(offset_y);
+                        context
+                            .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                            .warn_on_error();
+                    }
+                    set_shadow(&context, "transparent", 0.0);
+
+                    if shadow.is_knockout {
+                        context
+                            .set_global_composite_operation("destination-out")
+                            .warn_on_error();
+                        context
+                            .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                            .warn_on_error();
+                    }
+                }
+                context
+                    .set_global_composite_operation("source-over")
+                    .warn_on_error();
+            }
+            swf::Filter::ColorMatrixFilter(color_matrix) => {
+                // SVG color matrices have offsets in the range of 0 to 1.
+                let values: Vec<String> = color_matrix
+                    .matrix
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        if i % 5 == 4 {
+                            (value / 255.0).to_string()
+                        } else {
+                            value.to_string()
+                        }
+                    })
+                    .collect();
+                self.color_matrix
+                    .set_attribute("values", &values.join(" "))
+                    .warn_on_error();
+                context.set_filter("url('#_cm')");
+                context
+                    .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+                    .warn_on_error();
+                context.set_filter("none");
+            }
+            _ => (),
+        }
+
+        let (filtered_canvas, _) = self.pop_render_target();
+        group_context.reset_transform().warn_on_error();
+        group_context
+            .set_global_composite_operation("copy")
+            .warn_on_error();
+        group_context
+            .draw_image_with_html_canvas_element(&filtered_canvas, 0.0, 0.0)
+            .warn_on_error();
+        group_context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
+    }
+
     #[allow(clippy::float_cmp)]
     #[inline]
     fn set_transform(&mut self, matrix: &Matrix) {
@@ -369,6 +526,14 @@ impl WebCanvasRenderBackend {
         self.context.set_global_alpha(1.0);
     }
 
+    /// Enables or disables image smoothing for the next draw.
+    /// Images are never smoothed at low quality.
+    #[inline]
+    fn set_image_smoothing(&self, smoothing: bool) {
+        self.context
+            .set_image_smoothing_enabled(smoothing && self.quality.allows_bitmap_smoothing());
+    }
+
     fn register_bitmap_pure_jpeg(
         &mut self,
         id: CharacterId,
@@ -398,7 +563,26 @@ impl WebCanvasRenderBackend {
         })
     }
 
-    fn register_bitmap_raw(
+    /// Convert raw RGBA pixels into an image that can be drawn to the canvas.
+    fn raw_bitmap_data(width: u32, height: u32, rgba: Vec<u8>) -> Result<BitmapData, Error> {
+        let png = Self::bitmap_to_png_data_uri(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        })?;
+
+        let image = HtmlImageElement::new().into_js_result()?;
+        image.set_src(&png);
+
+        Ok(BitmapData {
+            image,
+            width,
+            height,
+            data: png,
+        })
+    }
+
+    fn register_bitmap_decoded(
         &mut self,
         id: CharacterId,
         bitmap: Bitmap,
@@ -501,7 +685,7 @@ impl RenderBackend for WebCanvasRenderBackend {
             self.register_bitmap_pure_jpeg(id, data)
         } else {
             let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
-            self.register_bitmap_raw(id, bitmap)
+            self.register_bitmap_decoded(id, bitmap)
         }
     }
 
@@ -513,7 +697,7 @@ impl RenderBackend for WebCanvasRenderBackend {
     ) -> Result<BitmapInfo, Error> {
         let bitmap =
             ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
-        self.register_bitmap_raw(id, bitmap)
+        self.register_bitmap_decoded(id, bitmap)
     }
 
     fn register_bitmap_png(
@@ -542,6 +726,34 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let handle = BitmapHandle(self.bitmaps.len());
+        self.bitmaps
+            .push(Self::raw_bitmap_data(width, height, rgba)?);
+        Ok(handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let bitmap_data = Self::raw_bitmap_data(width, height, rgba)?;
+        let bitmap = self
+            .bitmaps
+            .get_mut(handle.0)
+            .ok_or("Unknown bitmap handle")?;
+        *bitmap = bitmap_data;
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().unwrap();
@@ -555,19 +767,21 @@ impl RenderBackend for WebCanvasRenderBackend {
             .fill_rect(0.0, 0.0, width.into(), height.into());
 
         self.deactivating_mask = false;
+        self.drawing_mask = false;
     }
 
     fn end_frame(&mut self) {
         // Noop
     }
 
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         if self.deactivating_mask {
             return;
         }
 
         self.set_transform(&transform.matrix);
         self.set_color_filter(transform);
+        self.set_image_smoothing(smoothing);
         if let Some(bitmap) = self.bitmaps.get(bitmap.0) {
             let _ = self
                 .context
@@ -597,7 +811,10 @@ impl RenderBackend for WebCanvasRenderBackend {
                                 self.context.set_fill_style(&JsValue::from_str(&color))
                             }
                             CanvasFillStyle::Gradient(grad) => self.context.set_fill_style(grad),
-                            CanvasFillStyle::Pattern(patt) => self.context.set_fill_style(patt),
+                            CanvasFillStyle::Pattern(patt, smoothing) => {
+                                self.set_image_smoothing(*smoothing);
+                                self.context.set_fill_style(patt)
+                            }
                         };
 
                         self.context.fill_with_path_2d(&path);
@@ -614,6 +831,11 @@ impl RenderBackend for WebCanvasRenderBackend {
                         line_join,
                         miter_limit,
                     } => {
+                        // Only the fills of a shape contribute to a mask.
+                        if self.drawing_mask {
+                            continue;
+                        }
+
                         let xformed_stroke_style =
                             stroke_style.color_transform(&transform.color_transform);
                         self.context.set_line_width(*line_width);
@@ -630,6 +852,7 @@ impl RenderBackend for WebCanvasRenderBackend {
                         y_min,
                     } => {
                         self.set_color_filter(transform);
+                        self.set_image_smoothing(true);
                         let _ = self
                             .context
                             .draw_image_with_html_image_element(&image, *x_min, *y_min);
@@ -696,10 +919,12 @@ impl RenderBackend for WebCanvasRenderBackend {
         // In the canvas backend, masks are implemented using two render targets.
         // We render the masker clips to the first render target.
         self.push_render_target();
+        self.drawing_mask = true;
     }
     fn activate_mask(&mut self) {
         // We render the maskee clips to the second render target.
         self.push_render_target();
+        self.drawing_mask = false;
     }
     fn deactivate_mask(&mut self) {
         self.deactivating_mask = true;
@@ -746,6 +971,132 @@ impl RenderBackend for WebCanvasRenderBackend {
             .draw_image_with_html_canvas_element(&maskee_canvas, 0.0, 0.0)
             .unwrap();
     }
+
+    fn push_blend_mode(&mut self, blend: BlendMode) {
+        // Each blend group is drawn to its own render target, which is
+        // composited onto the parent target when the group ends.
+        self.blend_modes.push(blend);
+        self.push_render_target();
+    }
+
+    fn pop_blend_mode(&mut self) {
+        let blend = self.blend_modes.pop().unwrap_or(BlendMode::Normal);
+        let (group_canvas, group_context) = self.pop_render_target();
+        if self.deactivating_mask {
+            return;
+        }
+
+        let in_layer = self.blend_modes.last() == Some(&BlendMode::Layer);
+        let operation = match blend {
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Lighten => "lighten",
+            BlendMode::Darken => "darken",
+            BlendMode::Difference | BlendMode::Invert => "difference",
+            BlendMode::Add => "lighter",
+            BlendMode::Overlay => "overlay",
+            BlendMode::HardLight => "hard-light",
+            // Unlike Flash, these also clear the layer outside of the group.
+            BlendMode::Alpha if in_layer => "destination-in",
+            BlendMode::Erase if in_layer => "destination-out",
+            // Subtract has no canvas equivalent.
+            _ => "source-over",
+        };
+
+        if blend == BlendMode::Invert {
+            // Invert ignores the colors of the group, so fill it with white;
+            // the difference with white inverts the content below it.
+            group_context.reset_transform().warn_on_error();
+            group_context
+                .set_global_composite_operation("source-in")
+                .warn_on_error();
+            group_context.set_fill_style(&"white".into());
+            group_context.fill_rect(
+                0.0,
+                0.0,
+                group_canvas.width().into(),
+                group_canvas.height().into(),
+            );
+            group_context
+                .set_global_composite_operation("source-over")
+                .warn_on_error();
+        }
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .set_global_composite_operation(operation)
+            .warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+            .warn_on_error();
+        self.context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
+    }
+
+    fn push_filters(&mut self, filters: &[swf::Filter], _bounds: &swf::Rectangle) {
+        // Like blend groups, each filter group is drawn to its own render target.
+        self.filters.push(filters.to_vec());
+        self.push_render_target();
+    }
+
+    fn pop_filters(&mut self) {
+        let filters = self.filters.pop().unwrap_or_default();
+        if !self.deactivating_mask {
+            for filter in &filters {
+                self.apply_filter(filter);
+            }
+        }
+
+        let (group_canvas, _group_context) = self.pop_render_target();
+        if self.deactivating_mask {
+            return;
+        }
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&group_canvas, 0.0, 0.0)
+            .warn_on_error();
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+}
+
+/// Returns the standard deviation in pixels of the gaussian blur closest to
+/// a box blur with the given sizes and number of passes.
+fn blur_sigma(blur_x: f64, blur_y: f64, num_passes: u8) -> f64 {
+    // Each pass of a box blur with a radius of `r` has a variance of `r * (r + 1) / 3`.
+    let variance = |blur| {
+        let radius = f64::from(blur_radius(blur));
+        f64::from(num_passes) * radius * (radius + 1.0) / 3.0
+    };
+    ((variance(blur_x) + variance(blur_y)) / 2.0).sqrt()
+}
+
+/// Sets the shadow drawn along with everything drawn to a context.
+fn set_shadow(context: &CanvasRenderingContext2d, color: &str, blur: f64) {
+    context.set_shadow_color(color);
+    context.set_shadow_blur(blur);
+    context.set_shadow_offset_x(0.0);
+    context.set_shadow_offset_y(0.0);
+}
+
+/// Draws only the shadow of a canvas, using the shadow color and blur of the context.
+fn draw_shadow_only(
+    context: &CanvasRenderingContext2d,
+    canvas: &HtmlCanvasElement,
+    offset_x: f64,
+    offset_y: f64,
+) {
+    // The canvas is drawn outside of the context, and its shadow is moved back into it.
+    let width = f64::from(canvas.width());
+    context.set_shadow_offset_x(offset_x + width);
+    context.set_shadow_offset_y(offset_y);
+    context
+        .draw_image_with_html_canvas_element(canvas, -width, 0.0)
+        .warn_on_error();
 }
 
 #[allow(clippy::cognitive_complexity)]
@@ -1292,10 +1643,6 @@ fn swf_shape_to_canvas_commands(
                         )
                         .expect("html image element");
 
-                        if !*is_smoothed {
-                            //image = image.set("image-rendering", pixelated_property_value);
-                        }
-
                         let repeat = if !*is_repeating {
                             "no-repeat"
                         } else {
@@ -1323,7 +1670,7 @@ fn swf_shape_to_canvas_commands(
 
                         bitmap_pattern.set_transform(&matrix);
 
-                        CanvasFillStyle::Pattern(bitmap_pattern)
+                        CanvasFillStyle::Pattern(bitmap_pattern, *is_smoothed)
                     }
                 };
 
@@ -42,10 +42,26 @@ impl ShapeTessellator {
             });
         }
 
+        // Solid fills and strokes are batched into separate draws, because
+        // strokes are left out when a shape is drawn as a mask.
+        let mut strokes_pending = false;
+        fn pending_draw_type(strokes_pending: &mut bool) -> DrawType {
+            if std::mem::take(strokes_pending) {
+                DrawType::Stroke
+            } else {
+                DrawType::Color
+            }
+        }
+
         for path in shape.paths {
             match path {
                 DrawPath::Fill { style, commands } => match style {
                     FillStyle::Color(color) => {
+                        if strokes_pending {
+                            flush_draw(DrawType::Stroke, &mut mesh, &mut lyon_mesh);
+                            strokes_pending = false;
+                        }
+
                         let color = ((color.a as u32) << 24)
                             | ((color.b as u32) << 16)
                             | ((color.g as u32) << 8)
@@ -65,7 +81,11 @@ impl ShapeTessellator {
                         }
                     }
                     FillStyle::LinearGradient(gradient) => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        flush_draw(
+                            pending_draw_type(&mut strokes_pending),
+                            &mut mesh,
+                            &mut lyon_mesh,
+                        );
 
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -108,7 +128,11 @@ impl ShapeTessellator {
                         flush_draw(DrawType::Gradient(gradient), &mut mesh, &mut lyon_mesh);
                     }
                     FillStyle::RadialGradient(gradient) => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        flush_draw(
+                            pending_draw_type(&mut strokes_pending),
+                            &mut mesh,
+                            &mut lyon_mesh,
+                        );
 
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -154,7 +178,11 @@ impl ShapeTessellator {
                         gradient,
                         focal_point,
                     } => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        flush_draw(
+                            pending_draw_type(&mut strokes_pending),
+                            &mut mesh,
+                            &mut lyon_mesh,
+                        );
 
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -202,7 +230,11 @@ impl ShapeTessellator {
                         is_smoothed,
                         is_repeating,
                     } => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        flush_draw(
+                            pending_draw_type(&mut strokes_pending),
+                            &mut mesh,
+                            &mut lyon_mesh,
+                        );
 
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -237,6 +269,11 @@ impl ShapeTessellator {
                     commands,
                     is_closed,
                 } => {
+                    if !strokes_pending {
+                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        strokes_pending = true;
+                    }
+
                     let color = ((style.color.a as u32) << 24)
                         | ((style.color.b as u32) << 16)
                         | ((style.color.g as u32) << 8)
@@ -293,7 +330,11 @@ impl ShapeTessellator {
             }
         }
 
-        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+        flush_draw(
+            pending_draw_type(&mut strokes_pending),
+            &mut mesh,
+            &mut lyon_mesh,
+        );
 
         mesh
     }
@@ -315,6 +356,7 @@ pub struct Draw {
 
 pub enum DrawType {
     Color,
+    Stroke,
     Gradient(Gradient),
     Bitmap(Bitmap),
 }
@@ -464,3 +506,112 @@ pub enum GradientType {
     Radial,
     Focal,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{Rectangle, Shape, ShapeRecord, ShapeStyles, StyleChangeData};
+
+    /// A 20x20 square filled with the given fill style.
+    fn square_shape(fill_style: FillStyle) -> Shape {
+        let bounds = Rectangle {
+            x_min: Twips::new(0),
+            x_max: Twips::new(400),
+            y_min: Twips::new(0),
+            y_max: Twips::new(400),
+        };
+        let edge = |delta_x, delta_y| ShapeRecord::StraightEdge {
+            delta_x: Twips::new(delta_x),
+            delta_y: Twips::new(delta_y),
+        };
+
+        Shape {
+            version: 4,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: false,
+            has_scaling_strokes: false,
+            styles: ShapeStyles {
+                fill_styles: vec![fill_style],
+                line_styles: vec![],
+            },
+            shape: vec![
+                ShapeRecord::StyleChange(StyleChangeData {
+                    move_to: Some((Twips::new(0), Twips::new(0))),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                }),
+                edge(400, 0),
+                edge(0, 400),
+                edge(-400, 0),
+                edge(0, -400),
+            ],
+        }
+    }
+
+    /// Tessellates a square with a bitmap fill, returning its only bitmap draw.
+    fn tessellate_bitmap_fill(is_smoothed: bool, is_repeating: bool) -> Bitmap {
+        let shape = square_shape(FillStyle::Bitmap {
+            id: 2,
+            matrix: swf::Matrix::identity(),
+            is_smoothed,
+            is_repeating,
+        });
+        let mesh = ShapeTessellator::new().tessellate_shape((&shape).into(), |_| Some((10, 10)));
+
+        let mut bitmaps = mesh.into_iter().filter_map(|draw| match draw.draw_type {
+            DrawType::Bitmap(bitmap) => Some(bitmap),
+            _ => None,
+        });
+        let bitmap = bitmaps.next().expect("Shape should have a bitmap draw");
+        assert!(
+            bitmaps.next().is_none(),
+            "Shape should have one bitmap draw"
+        );
+        bitmap
+    }
+
+    #[test]
+    fn bitmap_fill_flags() {
+        for &(is_smoothed, is_repeating) in
+            &[(true, true), (true, false), (false, true), (false, false)]
+        {
+            let bitmap = tessellate_bitmap_fill(is_smoothed, is_repeating);
+            assert_eq!(bitmap.id, 2);
+            assert_eq!(bitmap.is_smoothed, is_smoothed);
+            assert_eq!(bitmap.is_repeating, is_repeating);
+        }
+    }
+
+    #[test]
+    fn strokes_are_separate_draws() {
+        let red = swf::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let mut shape = square_shape(FillStyle::Color(red.clone()));
+        shape.styles.line_styles = vec![swf::LineStyle::new_v1(Twips::new(40), red)];
+        if let ShapeRecord::StyleChange(style_change) = &mut shape.shape[0] {
+            style_change.line_style = Some(1);
+        }
+
+        let mesh = ShapeTessellator::new().tessellate_shape((&shape).into(), |_| None);
+
+        let draw_types: Vec<_> = mesh
+            .iter()
+            .map(|draw| match draw.draw_type {
+                DrawType::Color => "color",
+                DrawType::Stroke => "stroke",
+                DrawType::Gradient(_) => "gradient",
+                DrawType::Bitmap(_) => "bitmap",
+            })
+            .collect();
+        assert_eq!(draw_types, vec!["color", "stroke"]);
+    }
+}
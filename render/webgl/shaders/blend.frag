@@ -0,0 +1,49 @@
+#version 100
+precision mediump float;
+
+uniform mat4 view_matrix;
+uniform mat4 world_matrix;
+uniform mat3 u_matrix;
+uniform int u_blend_mode;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_backdrop;
+
+varying vec2 frag_uv;
+
+// These should match the indices in `BlendGroup::shader_blend_mode`.
+const int BLEND_DIFFERENCE = 1;
+const int BLEND_OVERLAY = 2;
+const int BLEND_HARDLIGHT = 3;
+
+vec3 hard_light(vec3 src, vec3 dst) {
+    vec3 multiply = 2.0 * src * dst;
+    vec3 screen = 1.0 - 2.0 * (1.0 - src) * (1.0 - dst);
+    return mix(multiply, screen, step(0.5, src));
+}
+
+vec3 blend(vec3 src, vec3 dst) {
+    if( u_blend_mode == BLEND_DIFFERENCE ) {
+        return abs(dst - src);
+    } else if( u_blend_mode == BLEND_OVERLAY ) {
+        // Overlay is hard light with the source and backdrop swapped.
+        return hard_light(dst, src);
+    } else {
+        return hard_light(src, dst);
+    }
+}
+
+void main() {
+    // Both textures use pre-multiplied alpha.
+    vec4 src = texture2D(u_texture, frag_uv);
+    vec4 dst = texture2D(u_backdrop, frag_uv);
+
+    vec3 src_color = src.a > 0.0 ? src.rgb / src.a : vec3(0.0);
+    vec3 dst_color = dst.a > 0.0 ? dst.rgb / dst.a : vec3(0.0);
+    vec3 color = blend(src_color, dst_color);
+
+    gl_FragColor = vec4(
+        src.rgb * (1.0 - dst.a) + dst.rgb * (1.0 - src.a) + src.a * dst.a * color,
+        src.a + dst.a * (1.0 - src.a)
+    );
+}
@@ -0,0 +1,23 @@
+#version 100
+precision mediump float;
+
+uniform sampler2D u_texture;
+
+// The multipliers of a color matrix filter, and its offsets in the range of
+// 0 to 1. They are applied to colors without premultiplied alpha.
+uniform mat4 u_color_matrix;
+uniform vec4 u_color_offset;
+
+varying vec2 frag_uv;
+
+void main() {
+    vec4 color = texture2D(u_texture, frag_uv);
+    if( color.a > 0.0 ) {
+        color.rgb /= color.a;
+    }
+
+    color = clamp(u_color_matrix * color + u_color_offset, 0.0, 1.0);
+    color.rgb *= color.a;
+
+    gl_FragColor = color;
+}
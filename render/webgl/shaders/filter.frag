@@ -0,0 +1,27 @@
+#version 100
+precision mediump float;
+
+uniform sampler2D u_texture;
+
+// The distance between samples of the blur in texture coordinates, and the
+// number of samples on either side of each pixel.
+uniform vec2 u_blur_direction;
+uniform float u_blur_radius;
+
+varying vec2 frag_uv;
+
+// This should match `MAX_BLUR_RADIUS` in the renderer.
+const int MAX_BLUR_RADIUS = 64;
+
+void main() {
+    // A box blur averages the pixels within the radius of each pixel.
+    // Loops need a constant bound, so samples beyond the radius are skipped.
+    vec4 color = vec4(0.0);
+    for( int i = -MAX_BLUR_RADIUS; i <= MAX_BLUR_RADIUS; i++ ) {
+        if( abs(float(i)) <= u_blur_radius ) {
+            color += texture2D(u_texture, frag_uv + float(i) * u_blur_direction);
+        }
+    }
+
+    gl_FragColor = color / (2.0 * u_blur_radius + 1.0);
+}
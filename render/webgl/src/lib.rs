@@ -1,7 +1,7 @@
-use ruffle_core::backend::render::swf;
+use ruffle_core::backend::render::swf::{self, BlendMode};
 use ruffle_core::backend::render::{
-    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
-    RenderBackend, ShapeHandle, Transform,
+    blur_radius, srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
+    RenderBackend, ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf::Matrix;
@@ -21,6 +21,9 @@ const COLOR_FRAGMENT_GLSL: &str = include_str!("../shaders/color.frag");
 const TEXTURE_VERTEX_GLSL: &str = include_str!("../shaders/texture.vert");
 const GRADIENT_FRAGMENT_GLSL: &str = include_str!("../shaders/gradient.frag");
 const BITMAP_FRAGMENT_GLSL: &str = include_str!("../shaders/bitmap.frag");
+const BLEND_FRAGMENT_GLSL: &str = include_str!("../shaders/blend.frag");
+const FILTER_FRAGMENT_GLSL: &str = include_str!("../shaders/filter.frag");
+const COLOR_MATRIX_FRAGMENT_GLSL: &str = include_str!("../shaders/color_matrix.frag");
 const NUM_VERTEX_ATTRIBUTES: u32 = 2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +51,9 @@ pub struct WebGlRenderBackend {
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
+    blend_program: ShaderProgram,
+    filter_program: ShaderProgram,
+    color_matrix_program: ShaderProgram,
 
     shape_tessellator: ShapeTessellator,
 
@@ -61,7 +67,7 @@ pub struct WebGlRenderBackend {
     mask_state_dirty: bool,
 
     active_program: *const ShaderProgram,
-    blend_func: (u32, u32),
+    blend_state: Option<BlendState>,
     mult_color: Option<[f32; 4]>,
     add_color: Option<[f32; 4]>,
 
@@ -70,10 +76,40 @@ pub struct WebGlRenderBackend {
     view_width: i32,
     view_height: i32,
     view_matrix: [[f32; 4]; 4],
+
+    quality: StageQuality,
+
+    /// Whether the `MIN` and `MAX` blend equations are available.
+    has_min_max_blending: bool,
+
+    blend_groups: Vec<BlendGroup>,
+
+    /// Offscreen targets for blend groups, reused between frames.
+    blend_targets: Vec<BlendTarget>,
+
+    /// Textures holding a copy of the content below a blend group.
+    /// The RGB texture is used for the main framebuffer, which has no alpha.
+    backdrop_rgb: Option<WebGlTexture>,
+    backdrop_rgba: Option<WebGlTexture>,
+
+    /// Offscreen targets that filters are applied in, reused between frames.
+    /// See `BLUR_TARGETS` and `RESULT_TARGETS`.
+    filter_targets: Vec<BlendTarget>,
 }
 
 const MAX_GRADIENT_COLORS: usize = 15;
 
+/// The largest radius of a pass of a blur. This should match `MAX_BLUR_RADIUS`
+/// in the filter shader.
+const MAX_BLUR_RADIUS: u32 = 64;
+
+/// The filter targets that blurs are applied back and forth between, ending in the first one.
+const BLUR_TARGETS: [usize; 2] = [0, 1];
+
+/// The filter targets holding the result of each filter. Each filter draws
+/// to the target that the previous filter didn't use.
+const RESULT_TARGETS: [usize; 2] = [2, 3];
+
 impl WebGlRenderBackend {
     pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, Error> {
         // Create WebGL context.
@@ -171,8 +207,22 @@ impl WebGlRenderBackend {
         let bitmap_program = ShaderProgram::new(&gl, &texture_vertex, &bitmap_fragment)?;
         let gradient_program = ShaderProgram::new(&gl, &texture_vertex, &gradient_fragment)?;
 
+        let blend_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, BLEND_FRAGMENT_GLSL)?;
+        let blend_program = ShaderProgram::new(&gl, &texture_vertex, &blend_fragment)?;
+
+        let filter_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, FILTER_FRAGMENT_GLSL)?;
+        let filter_program = ShaderProgram::new(&gl, &texture_vertex, &filter_fragment)?;
+
+        let color_matrix_fragment =
+            Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, COLOR_MATRIX_FRAGMENT_GLSL)?;
+        let color_matrix_program =
+            ShaderProgram::new(&gl, &texture_vertex, &color_matrix_fragment)?;
+
         gl.enable(Gl::BLEND);
-        gl.blend_func(Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA);
+
+        // WebGL1 requires an extension for the `MIN` and `MAX` blend equations.
+        let has_min_max_blending =
+            gl2.is_some() || matches!(gl.get_extension("EXT_blend_minmax"), Ok(Some(_)));
 
         // Necessary to load RGB textures (alignment defaults to 4).
         gl.pixel_storei(Gl::UNPACK_ALIGNMENT, 1);
@@ -188,6 +238,9 @@ impl WebGlRenderBackend {
             color_program,
             gradient_program,
             bitmap_program,
+            blend_program,
+            filter_program,
+            color_matrix_program,
 
             shape_tessellator: ShapeTessellator::new(),
 
@@ -199,13 +252,21 @@ impl WebGlRenderBackend {
             view_width: 1,
             view_height: 1,
             view_matrix: [[0.0; 4]; 4],
+            quality: StageQuality::default(),
+
+            has_min_max_blending,
+            blend_groups: vec![],
+            blend_targets: vec![],
+            backdrop_rgb: None,
+            backdrop_rgba: None,
+            filter_targets: vec![],
 
             mask_state: MaskState::NoMask,
             num_masks: 0,
             mask_state_dirty: true,
 
             active_program: std::ptr::null(),
-            blend_func: (Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_state: None,
             mult_color: None,
             add_color: None,
         };
@@ -501,6 +562,16 @@ impl WebGlRenderBackend {
                         num_indices,
                     },
                 ),
+                TessDrawType::Stroke => (
+                    &self.color_program,
+                    Draw {
+                        draw_type: DrawType::Stroke,
+                        vao,
+                        vertex_buffer,
+                        index_buffer,
+                        num_indices,
+                    },
+                ),
                 TessDrawType::Gradient(gradient) => {
                     let mut ratios = [0.0; MAX_GRADIENT_COLORS];
                     let mut colors = [[0.0; 4]; MAX_GRADIENT_COLORS];
@@ -670,6 +741,595 @@ impl WebGlRenderBackend {
         }
     }
 
+    fn set_blend_state(&mut self, blend_state: BlendState) {
+        if Some(blend_state) != self.blend_state {
+            blend_state.apply(&self.gl);
+            self.blend_state = Some(blend_state);
+        }
+    }
+
+    /// The blend mode that draws use, given the current blend groups.
+    ///
+    /// Groups that are drawn to an offscreen target start over with normal
+    /// blending; the group's blend mode is applied when compositing it.
+    fn draw_blend_mode(&self) -> BlendMode {
+        for (i, group) in self.blend_groups.iter().enumerate().rev() {
+            if group.target.is_some() {
+                return BlendMode::Normal;
+            }
+            match group.blend {
+                BlendMode::Normal => (),
+                BlendMode::Alpha | BlendMode::Erase => {
+                    if self.blend_groups[..i]
+                        .iter()
+                        .any(|parent| parent.blend == BlendMode::Layer)
+                    {
+                        return group.blend;
+                    }
+                }
+                blend => return blend,
+            }
+        }
+        BlendMode::Normal
+    }
+
+    /// The index of the offscreen target that draws currently go to.
+    fn current_blend_target(&self) -> Option<usize> {
+        self.blend_groups
+            .iter()
+            .rev()
+            .find_map(|group| group.target)
+    }
+
+    /// Binds the framebuffer that draws currently go to.
+    fn bind_render_target(&self) {
+        let framebuffer = if let Some(target) = self.current_blend_target() {
+            Some(&self.blend_targets[target].framebuffer)
+        } else {
+            self.msaa_buffers
+                .as_ref()
+                .map(|msaa_buffers| &msaa_buffers.render_framebuffer)
+        };
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, framebuffer);
+    }
+
+    /// Returns the offscreen target for a new blend group, creating it if necessary.
+    fn push_blend_target(&mut self) -> Result<usize, Error> {
+        let target = self
+            .blend_groups
+            .iter()
+            .filter(|group| group.target.is_some())
+            .count();
+        if target >= self.blend_targets.len() {
+            let blend_target = self.create_blend_target()?;
+            self.blend_targets.push(blend_target);
+        }
+        Ok(target)
+    }
+
+    fn create_blend_target(&self) -> Result<BlendTarget, Error> {
+        let gl = &self.gl;
+        let (width, height) = (self.renderbuffer_width, self.renderbuffer_height);
+
+        let texture = Self::create_target_texture(gl, width, height, Gl::RGBA)?;
+
+        let stencil_renderbuffer = gl
+            .create_renderbuffer()
+            .ok_or("Unable to create renderbuffer")?;
+        gl.bind_renderbuffer(Gl::RENDERBUFFER, Some(&stencil_renderbuffer));
+        gl.renderbuffer_storage(Gl::RENDERBUFFER, Gl::STENCIL_INDEX8, width, height);
+        gl.check_error("renderbuffer_storage (blend target stencil)")?;
+
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or("Unable to create framebuffer")?;
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+        gl.framebuffer_renderbuffer(
+            Gl::FRAMEBUFFER,
+            Gl::STENCIL_ATTACHMENT,
+            Gl::RENDERBUFFER,
+            Some(&stencil_renderbuffer),
+        );
+        self.bind_render_target();
+
+        Ok(BlendTarget {
+            framebuffer,
+            texture,
+            stencil_renderbuffer,
+        })
+    }
+
+    fn create_target_texture(
+        gl: &Gl,
+        width: i32,
+        height: i32,
+        format: u32,
+    ) -> Result<WebGlTexture, Error> {
+        let texture = gl.create_texture().ok_or("Unable to create texture")?;
+        gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::NEAREST as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            Gl::TEXTURE_2D,
+            0,
+            format as i32,
+            width,
+            height,
+            0,
+            format,
+            Gl::UNSIGNED_BYTE,
+            None,
+        )
+        .into_js_result()?;
+        Ok(texture)
+    }
+
+    fn delete_blend_targets(&mut self) {
+        for target in self
+            .blend_targets
+            .drain(..)
+            .chain(self.filter_targets.drain(..))
+        {
+            self.gl.delete_framebuffer(Some(&target.framebuffer));
+            self.gl
+                .delete_renderbuffer(Some(&target.stencil_renderbuffer));
+            self.gl.delete_texture(Some(&target.texture));
+        }
+        for texture in self
+            .backdrop_rgb
+            .take()
+            .into_iter()
+            .chain(self.backdrop_rgba.take())
+        {
+            self.gl.delete_texture(Some(&texture));
+        }
+    }
+
+    /// Copies the content of the current render target, which a blend group
+    /// is about to be composited onto, to a texture.
+    fn copy_backdrop(&mut self) -> Result<WebGlTexture, Error> {
+        let (width, height) = (self.renderbuffer_width, self.renderbuffer_height);
+        let is_offscreen = self.current_blend_target().is_some();
+
+        if let (false, Some(gl), Some(msaa_buffers)) = (is_offscreen, &self.gl2, &self.msaa_buffers)
+        {
+            // The MSAA render buffer can't be read from directly, so resolve
+            // it into the color framebuffer, which is otherwise only used at
+            // the end of the frame.
+            gl.bind_framebuffer(
+                Gl2::READ_FRAMEBUFFER,
+                Some(&msaa_buffers.render_framebuffer),
+            );
+            gl.bind_framebuffer(Gl2::DRAW_FRAMEBUFFER, Some(&msaa_buffers.color_framebuffer));
+            gl.blit_framebuffer(
+                0,
+                0,
+                width,
+                height,
+                0,
+                0,
+                width,
+                height,
+                Gl2::COLOR_BUFFER_BIT,
+                Gl2::NEAREST,
+            );
+            gl.bind_framebuffer(Gl2::FRAMEBUFFER, Some(&msaa_buffers.render_framebuffer));
+            return Ok(msaa_buffers.framebuffer_texture.clone());
+        }
+
+        // The main framebuffer has no alpha channel, so it can only be
+        // copied to an RGB texture.
+        let (backdrop, format) = if is_offscreen {
+            (&mut self.backdrop_rgba, Gl::RGBA)
+        } else {
+            (&mut self.backdrop_rgb, Gl::RGB)
+        };
+        let texture = if let Some(texture) = backdrop {
+            texture.clone()
+        } else {
+            let texture = Self::create_target_texture(&self.gl, width, height, format)?;
+            *backdrop = Some(texture.clone());
+            texture
+        };
+
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        self.gl
+            .copy_tex_sub_image_2d(Gl::TEXTURE_2D, 0, 0, 0, 0, 0, width, height);
+        self.gl.check_error("copy_tex_sub_image_2d (backdrop)")?;
+        Ok(texture)
+    }
+
+    /// Draws the contents of a blend group onto the current render target.
+    fn composite_blend_group(
+        &mut self,
+        blend: BlendMode,
+        texture: WebGlTexture,
+    ) -> Result<(), Error> {
+        self.set_stencil_state();
+
+        if let Some(shader_blend_mode) = BlendGroup::shader_blend_mode(blend) {
+            let backdrop = self.copy_backdrop()?;
+
+            // The shader composites the group over the backdrop itself.
+            self.gl.disable(Gl::BLEND);
+
+            let program = &self.blend_program;
+            self.gl.use_program(Some(&program.program));
+
+            self.gl.active_texture(Gl::TEXTURE1);
+            self.gl.bind_texture(Gl::TEXTURE_2D, Some(&backdrop));
+            program.uniform1i(&self.gl, ShaderUniform::BackdropTexture, 1);
+            self.gl.active_texture(Gl::TEXTURE0);
+            self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+            program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+            program.uniform1i(&self.gl, ShaderUniform::BlendMode, shader_blend_mode);
+
+            self.draw_target_quad(program, (0.0, 0.0));
+            self.gl.enable(Gl::BLEND);
+        } else {
+            // Layers are drawn like any other premultiplied bitmap.
+            let blend = self.draw_blend_mode();
+            let (mult_color, add_color) = blend_colors(blend, [1.0; 4], [0.0; 4]);
+            self.set_blend_state(BlendState::new(blend, true, self.has_min_max_blending));
+
+            let program = &self.bitmap_program;
+            self.gl.use_program(Some(&program.program));
+
+            self.gl.active_texture(Gl::TEXTURE0);
+            self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+            program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
+            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
+
+            self.draw_target_quad(program, (0.0, 0.0));
+        }
+
+        // The quad was drawn with different uniforms than regular draws.
+        self.active_program = std::ptr::null();
+        self.mult_color = None;
+        self.add_color = None;
+        Ok(())
+    }
+
+    /// Draws a quad covering the whole render target, textured with a render target.
+    ///
+    /// The quad is moved by `offset`, in pixels.
+    fn draw_target_quad(&self, program: &ShaderProgram, offset: (f32, f32)) {
+        // The view matrix flips the Y axis, so moving down is moving towards -1.
+        let offset_x = 2.0 * offset.0 / self.view_width as f32;
+        let offset_y = -2.0 * offset.1 / self.view_height as f32;
+        program.uniform_matrix4fv(
+            &self.gl,
+            ShaderUniform::WorldMatrix,
+            &[
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0 + offset_x, -1.0 + offset_y, 0.0, 1.0],
+            ],
+        );
+        program.uniform_matrix4fv(
+            &self.gl,
+            ShaderUniform::ViewMatrix,
+            &[
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        );
+        program.uniform_matrix3fv(
+            &self.gl,
+            ShaderUniform::TextureMatrix,
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+
+        let quad = &self.meshes[self.quad_shape.0];
+        self.bind_vertex_array(Some(&quad.draws[0].vao));
+        self.gl.draw_elements_with_i32(
+            Gl::TRIANGLES,
+            quad.draws[0].num_indices,
+            Gl::UNSIGNED_SHORT,
+            0,
+        );
+    }
+
+    /// Clears the offscreen target of a group that was just pushed.
+    fn begin_offscreen_group(&mut self) {
+        // The offscreen target has a stencil buffer of its own.
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.mask_state_dirty = true;
+        self.set_stencil_state();
+
+        self.bind_render_target();
+        self.gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        self.gl.stencil_mask(0xff);
+        self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+    }
+
+    /// Applies the filters of a group to its contents, returning the texture
+    /// holding the filtered group.
+    fn apply_filters(
+        &mut self,
+        group: &FilterGroup,
+        source: &WebGlTexture,
+    ) -> Result<Option<WebGlTexture>, Error> {
+        while self.filter_targets.len() < BLUR_TARGETS.len() + RESULT_TARGETS.len() {
+            let target = self.create_blend_target()?;
+            self.filter_targets.push(target);
+        }
+        let texture = |target: usize| self.filter_targets[target].texture.clone();
+        let blur = [texture(BLUR_TARGETS[0]), texture(BLUR_TARGETS[1])];
+        let results = [texture(RESULT_TARGETS[0]), texture(RESULT_TARGETS[1])];
+
+        // Filter targets don't use masks.
+        self.gl.disable(Gl::STENCIL_TEST);
+        self.mask_state_dirty = true;
+
+        let mut result = None;
+        for filter in &group.filters {
+            let output = if result == Some(0) { 1 } else { 0 };
+            let input = result.map_or(source, |result: usize| &results[result]);
+            match filter {
+                swf::Filter::BlurFilter(blur_filter) => {
+                    self.draw_filter_quads(
+                        BLUR_TARGETS[0],
+                        Some([0.0; 4]),
+                        group.scissor,
+                        &[FilterDraw::new(input, BlendState::COPY)],
+                    );
+                    self.blur(
+                        &blur,
+                        blur_filter.blur_x,
+                        blur_filter.blur_y,
+                        blur_filter.num_passes,
+                        group.scissor,
+                    );
+                    self.draw_filter_quads(
+                        RESULT_TARGETS[output],
+                        Some([0.0; 4]),
+                        group.scissor,
+                        &[FilterDraw::new(&blur[0], BlendState::COPY)],
+                    );
+                }
+                swf::Filter::DropShadowFilter(shadow) => {
+                    self.drop_shadow(shadow, input, RESULT_TARGETS[output], &blur, group.scissor)
+                }
+                swf::Filter::ColorMatrixFilter(color_matrix) => self.draw_filter_quads(
+                    RESULT_TARGETS[output],
+                    Some([0.0; 4]),
+                    group.scissor,
+                    &[FilterDraw {
+                        color_matrix: Some(color_matrix_uniforms(color_matrix)),
+                        ..FilterDraw::new(input, BlendState::COPY)
+                    }],
+                ),
+                _ => continue,
+            }
+            result = Some(output);
+        }
+
+        // The quads were drawn with different uniforms than regular draws.
+        self.active_program = std::ptr::null();
+        self.mult_color = None;
+        self.add_color = None;
+        self.gl.check_error("apply_filters")?;
+        Ok(result.map(|result| results[result].clone()))
+    }
+
+    /// Blurs the first blur target in place, with the given number of passes
+    /// of a box blur.
+    fn blur(
+        &mut self,
+        blur: &[WebGlTexture; 2],
+        blur_x: f64,
+        blur_y: f64,
+        num_passes: u8,
+        scissor: [i32; 4],
+    ) {
+        let radius_x = blur_radius(blur_x).min(MAX_BLUR_RADIUS);
+        let radius_y = blur_radius(blur_y).min(MAX_BLUR_RADIUS);
+        if radius_x == 0 && radius_y == 0 {
+            return;
+        }
+
+        for _ in 0..num_passes {
+            self.draw_filter_quads(
+                BLUR_TARGETS[1],
+                Some([0.0; 4]),
+                scissor,
+                &[FilterDraw {
+                    blur: Some(((1.0, 0.0), radius_x)),
+                    ..FilterDraw::new(&blur[0], BlendState::COPY)
+                }],
+            );
+            self.draw_filter_quads(
+                BLUR_TARGETS[0],
+                Some([0.0; 4]),
+                scissor,
+                &[FilterDraw {
+                    blur: Some(((0.0, 1.0), radius_y)),
+                    ..FilterDraw::new(&blur[1], BlendState::COPY)
+                }],
+            );
+        }
+    }
+
+    /// Draws a drop shadow of `source`, along with `source` itself unless the
+    /// filter hides it, to the given filter target.
+    fn drop_shadow(
+        &mut self,
+        shadow: &swf::DropShadowFilter,
+        source: &WebGlTexture,
+        output: usize,
+        blur: &[WebGlTexture; 2],
+        scissor: [i32; 4],
+    ) {
+        let offset = (
+            (shadow.distance * shadow.angle.cos()) as f32,
+            (shadow.distance * shadow.angle.sin()) as f32,
+        );
+        let color = [
+            f32::from(shadow.color.r) / 255.0,
+            f32::from(shadow.color.g) / 255.0,
+            f32::from(shadow.color.b) / 255.0,
+            f32::from(shadow.color.a) / 255.0,
+        ];
+        let erase = BlendState::new(BlendMode::Erase, true, self.has_min_max_blending);
+
+        if shadow.is_inner {
+            // An inner shadow is cast by the area around the object, so the
+            // shadow color is drawn everywhere except for the object.
+            self.draw_filter_quads(
+                BLUR_TARGETS[0],
+                Some([
+                    color[0] * color[3],
+                    color[1] * color[3],
+                    color[2] * color[3],
+                    color[3],
+                ]),
+                scissor,
+                &[FilterDraw {
+                    offset,
+                    ..FilterDraw::new(source, erase)
+                }],
+            );
+        } else {
+            // The silhouette of the object, in the shadow color.
+            self.draw_filter_quads(
+                BLUR_TARGETS[0],
+                Some([0.0; 4]),
+                scissor,
+                &[FilterDraw {
+                    offset,
+                    mult_color: [0.0, 0.0, 0.0, color[3]],
+                    add_color: [color[0], color[1], color[2], 0.0],
+                    ..FilterDraw::new(source, BlendState::COPY)
+                }],
+            );
+        }
+
+        self.blur(
+            blur,
+            shadow.blur_x,
+            shadow.blur_y,
+            shadow.num_passes,
+            scissor,
+        );
+
+        if shadow.is_inner {
+            let mask = BlendState::new(BlendMode::Alpha, true, self.has_min_max_blending);
+            self.draw_filter_quads(
+                BLUR_TARGETS[0],
+                None,
+                scissor,
+                &[FilterDraw::new(source, mask)],
+            );
+        }
+
+        // The strength multiplies the alpha of the shadow, which is clamped
+        // when drawn to the result.
+        // TODO: Colors are multiplied along with the alpha, so shadows with a
+        // strength above 1 are brighter than in Flash.
+        let normal = BlendState::new(BlendMode::Normal, true, self.has_min_max_blending);
+        let shadow_draw = |blend| FilterDraw {
+            mult_color: [1.0, 1.0, 1.0, shadow.strength],
+            ..FilterDraw::new(&blur[0], blend)
+        };
+        let draws = if shadow.is_inner {
+            if shadow.is_knockout || shadow.hide_object {
+                vec![shadow_draw(BlendState::COPY)]
+            } else {
+                vec![
+                    FilterDraw::new(source, BlendState::COPY),
+                    shadow_draw(normal),
+                ]
+            }
+        } else if shadow.is_knockout {
+            vec![
+                shadow_draw(BlendState::COPY),
+                FilterDraw::new(source, erase),
+            ]
+        } else if shadow.hide_object {
+            vec![shadow_draw(BlendState::COPY)]
+        } else {
+            vec![
+                shadow_draw(BlendState::COPY),
+                FilterDraw::new(source, normal),
+            ]
+        };
+        self.draw_filter_quads(output, Some([0.0; 4]), scissor, &draws);
+    }
+
+    /// Draws textures covering the whole render target to a filter target,
+    /// after clearing it to the given color.
+    fn draw_filter_quads(
+        &mut self,
+        target: usize,
+        clear: Option<[f32; 4]>,
+        scissor: [i32; 4],
+        draws: &[FilterDraw<'_>],
+    ) {
+        let gl = &self.gl;
+        gl.bind_framebuffer(
+            Gl::FRAMEBUFFER,
+            Some(&self.filter_targets[target].framebuffer),
+        );
+
+        // The whole target is cleared, as the whole target is composited.
+        if let Some([r, g, b, a]) = clear {
+            gl.clear_color(r, g, b, a);
+            gl.clear(Gl::COLOR_BUFFER_BIT);
+        }
+
+        gl.enable(Gl::SCISSOR_TEST);
+        gl.scissor(scissor[0], scissor[1], scissor[2], scissor[3]);
+        for draw in draws {
+            self.set_blend_state(draw.blend);
+            let gl = &self.gl;
+            let program = if let Some(((direction_x, direction_y), radius)) = draw.blur {
+                let program = &self.filter_program;
+                gl.use_program(Some(&program.program));
+                program.uniform2f(
+                    gl,
+                    ShaderUniform::BlurDirection,
+                    direction_x / self.view_width as f32,
+                    direction_y / self.view_height as f32,
+                );
+                program.uniform1f(gl, ShaderUniform::BlurRadius, radius as f32);
+                program
+            } else if let Some((matrix, offset)) = &draw.color_matrix {
+                let program = &self.color_matrix_program;
+                gl.use_program(Some(&program.program));
+                program.uniform_matrix4fv(gl, ShaderUniform::ColorMatrix, matrix);
+                program.uniform4fv(gl, ShaderUniform::ColorOffset, offset);
+                program
+            } else {
+                let program = &self.bitmap_program;
+                gl.use_program(Some(&program.program));
+                program.uniform4fv(gl, ShaderUniform::MultColor, &draw.mult_color);
+                program.uniform4fv(gl, ShaderUniform::AddColor, &draw.add_color);
+                program
+            };
+
+            gl.active_texture(Gl::TEXTURE0);
+            gl.bind_texture(Gl::TEXTURE_2D, Some(draw.texture));
+            program.uniform1i(gl, ShaderUniform::BitmapTexture, 0);
+            self.draw_target_quad(program, draw.offset);
+        }
+        self.gl.disable(Gl::SCISSOR_TEST);
+    }
+
     fn register_bitmap(
         &mut self,
         id: swf::CharacterId,
@@ -749,6 +1409,7 @@ impl RenderBackend for WebGlRenderBackend {
         self.renderbuffer_height = self.view_height.max(1).min(self.gl.drawing_buffer_height());
 
         // Recreate framebuffers with the new size.
+        self.delete_blend_targets();
         let _ = self.build_msaa_buffers(self.renderbuffer_width, self.renderbuffer_height);
         self.gl
             .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
@@ -812,11 +1473,61 @@ impl RenderBackend for WebGlRenderBackend {
         self.register_bitmap(swf_tag.id, bitmap)
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let bitmap = Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        };
+        Ok(self.register_bitmap(0, bitmap)?.handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some((_id, texture)) = self.textures.get_mut(handle.0) {
+            texture
+        } else {
+            return Err("Unknown bitmap handle".into());
+        };
+
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture.texture));
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&rgba),
+            )
+            .into_js_result()?;
+
+        texture.width = width;
+        texture.height = height;
+
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         self.active_program = std::ptr::null();
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
         self.mask_state_dirty = true;
+        self.blend_groups.clear();
+        self.blend_state = None;
 
         self.mult_color = None;
         self.add_color = None;
@@ -875,6 +1586,7 @@ impl RenderBackend for WebGlRenderBackend {
                 self.gl.drawing_buffer_height(),
             );
 
+            self.set_blend_state(BlendState::new(BlendMode::Normal, true, false));
             let program = &self.bitmap_program;
             self.gl.use_program(Some(&program.program));
 
@@ -926,7 +1638,7 @@ impl RenderBackend for WebGlRenderBackend {
         }
     }
 
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         // TODO: Might be better to make this separate code to render the bitmap
         // instead of going through render_shape. But render_shape already handles
         // masking etc.
@@ -936,8 +1648,14 @@ impl RenderBackend for WebGlRenderBackend {
             let draw = &mut mesh.draws[0];
             let width = bitmap.width as f32;
             let height = bitmap.height as f32;
-            if let DrawType::Bitmap(BitmapDraw { id: draw_id, .. }) = &mut draw.draw_type {
+            if let DrawType::Bitmap(BitmapDraw {
+                id: draw_id,
+                is_smoothed,
+                ..
+            }) = &mut draw.draw_type
+            {
                 *draw_id = *id;
+                *is_smoothed = smoothing;
             }
 
             // Scale the quad to the bitmap's dimensions.
@@ -983,21 +1701,29 @@ impl RenderBackend for WebGlRenderBackend {
             transform.color_transform.a_add,
         ];
 
+        let blend = self.draw_blend_mode();
+        let (mult_color, add_color) = blend_colors(blend, mult_color, add_color);
+
         self.set_stencil_state();
 
         let mesh = &self.meshes[shape.0];
+        let drawing_mask = matches!(
+            self.mask_state,
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        );
         for draw in &mesh.draws {
+            // Only the fills of a shape contribute to a mask.
+            if drawing_mask && matches!(draw.draw_type, DrawType::Stroke) {
+                continue;
+            }
+
             self.bind_vertex_array(Some(&draw.vao));
 
-            let (program, src_blend, dst_blend) = match &draw.draw_type {
-                DrawType::Color => (&self.color_program, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
-                DrawType::Gradient(_) => (
-                    &self.gradient_program,
-                    Gl::SRC_ALPHA,
-                    Gl::ONE_MINUS_SRC_ALPHA,
-                ),
+            let (program, premultiplied) = match &draw.draw_type {
+                DrawType::Color | DrawType::Stroke => (&self.color_program, false),
+                DrawType::Gradient(_) => (&self.gradient_program, false),
                 // Bitmaps use pre-multiplied alpha.
-                DrawType::Bitmap { .. } => (&self.bitmap_program, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA),
+                DrawType::Bitmap { .. } => (&self.bitmap_program, true),
             };
 
             // Set common render state, while minimizing unnecessary state changes.
@@ -1011,11 +1737,12 @@ impl RenderBackend for WebGlRenderBackend {
 
                 self.mult_color = None;
                 self.add_color = None;
+            }
 
-                if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
-                    self.blend_func = (src_blend, dst_blend);
-                }
+            let blend_state = BlendState::new(blend, premultiplied, self.has_min_max_blending);
+            if Some(blend_state) != self.blend_state {
+                blend_state.apply(&self.gl);
+                self.blend_state = Some(blend_state);
             }
 
             program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
@@ -1030,7 +1757,7 @@ impl RenderBackend for WebGlRenderBackend {
 
             // Set shader specific uniforms.
             match &draw.draw_type {
-                DrawType::Color => (),
+                DrawType::Color | DrawType::Stroke => (),
                 DrawType::Gradient(gradient) => {
                     program.uniform_matrix3fv(
                         &self.gl,
@@ -1093,7 +1820,7 @@ impl RenderBackend for WebGlRenderBackend {
                     program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
 
                     // Set texture parameters.
-                    let filter = if bitmap.is_smoothed {
+                    let filter = if bitmap.is_smoothed && self.quality.allows_bitmap_smoothing() {
                         Gl::LINEAR as i32
                     } else {
                         Gl::NEAREST as i32
@@ -1104,7 +1831,7 @@ impl RenderBackend for WebGlRenderBackend {
                         .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
                     // On WebGL1, you are unable to change the wrapping parameter causes non-power-of-2 textures.
                     let wrap = if self.gl2.is_some() && bitmap.is_repeating {
-                        Gl::MIRRORED_REPEAT as i32
+                        Gl::REPEAT as i32
                     } else {
                         Gl::CLAMP_TO_EDGE as i32
                     };
@@ -1143,11 +1870,12 @@ impl RenderBackend for WebGlRenderBackend {
             color.a as f32 * 255.0,
         ];
 
+        let blend = self.draw_blend_mode();
+        let (mult_color, add_color) = blend_colors(blend, mult_color, add_color);
+
         self.set_stencil_state();
 
         let program = &self.color_program;
-        let src_blend = Gl::SRC_ALPHA;
-        let dst_blend = Gl::ONE_MINUS_SRC_ALPHA;
 
         // Set common render state, while minimizing unnecessary state changes.
         // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
@@ -1160,13 +1888,14 @@ impl RenderBackend for WebGlRenderBackend {
 
             self.mult_color = None;
             self.add_color = None;
-
-            if (src_blend, dst_blend) != self.blend_func {
-                self.gl.blend_func(src_blend, dst_blend);
-                self.blend_func = (src_blend, dst_blend);
-            }
         };
 
+        let blend_state = BlendState::new(blend, false, self.has_min_max_blending);
+        if Some(blend_state) != self.blend_state {
+            blend_state.apply(&self.gl);
+            self.blend_state = Some(blend_state);
+        }
+
         self.color_program
             .uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
         if Some(mult_color) != self.mult_color {
@@ -1266,6 +1995,129 @@ impl RenderBackend for WebGlRenderBackend {
         };
         self.mask_state_dirty = true;
     }
+
+    fn push_blend_mode(&mut self, blend: BlendMode) {
+        // Blend modes are ignored while drawing masks.
+        let blend = match self.mask_state {
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil => BlendMode::Normal,
+            _ => blend,
+        };
+
+        let target = if BlendGroup::needs_target(blend) {
+            match self.push_blend_target() {
+                Ok(target) => Some(target),
+                Err(e) => {
+                    log::error!("Unable to create blend target: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        self.blend_groups.push(BlendGroup {
+            blend,
+            target,
+            filters: None,
+            mask_state: self.mask_state,
+            num_masks: self.num_masks,
+        });
+
+        if target.is_some() {
+            self.begin_offscreen_group();
+        }
+    }
+
+    fn pop_blend_mode(&mut self) {
+        let group = if let Some(group) = self.blend_groups.pop() {
+            group
+        } else {
+            log::error!("Blend group stack underflow");
+            return;
+        };
+
+        if let Some(target) = group.target {
+            self.mask_state = group.mask_state;
+            self.num_masks = group.num_masks;
+            self.mask_state_dirty = true;
+
+            self.bind_render_target();
+            let texture = self.blend_targets[target].texture.clone();
+            if let Err(e) = self.composite_blend_group(group.blend, texture) {
+                log::error!("Unable to composite blend group: {}", e);
+            }
+        }
+    }
+
+    fn push_filters(&mut self, filters: &[swf::Filter], bounds: &swf::Rectangle) {
+        // Filters are ignored while drawing masks.
+        let filters = match self.mask_state {
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil => None,
+            _ => FilterGroup::new(
+                filters,
+                bounds,
+                (self.view_width, self.view_height),
+                (self.renderbuffer_width, self.renderbuffer_height),
+            ),
+        };
+
+        let target = if filters.is_some() {
+            match self.push_blend_target() {
+                Ok(target) => Some(target),
+                Err(e) => {
+                    log::error!("Unable to create filter target: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        self.blend_groups.push(BlendGroup {
+            blend: BlendMode::Normal,
+            target,
+            filters: filters.filter(|_| target.is_some()),
+            mask_state: self.mask_state,
+            num_masks: self.num_masks,
+        });
+
+        if target.is_some() {
+            self.begin_offscreen_group();
+        }
+    }
+
+    fn pop_filters(&mut self) {
+        let group = if let Some(group) = self.blend_groups.pop() {
+            group
+        } else {
+            log::error!("Filter group stack underflow");
+            return;
+        };
+
+        if let Some(target) = group.target {
+            self.mask_state = group.mask_state;
+            self.num_masks = group.num_masks;
+            self.mask_state_dirty = true;
+
+            let mut texture = self.blend_targets[target].texture.clone();
+            if let Some(filters) = &group.filters {
+                match self.apply_filters(filters, &texture) {
+                    Ok(Some(filtered)) => texture = filtered,
+                    Ok(None) => (),
+                    Err(e) => log::error!("Unable to apply filters: {}", e),
+                }
+            }
+
+            self.bind_render_target();
+            if let Err(e) = self.composite_blend_group(BlendMode::Normal, texture) {
+                log::error!("Unable to composite filter group: {}", e);
+            }
+        }
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
 }
 
 struct Texture {
@@ -1309,10 +2161,267 @@ struct Draw {
 
 enum DrawType {
     Color,
+    Stroke,
     Gradient(Box<Gradient>),
     Bitmap(BitmapDraw),
 }
 
+/// A group of draws that is composited with a blend mode.
+struct BlendGroup {
+    blend: BlendMode,
+
+    /// The offscreen target that the group is drawn to, for blend modes that
+    /// can't be done with fixed-function blending.
+    target: Option<usize>,
+
+    /// The filters applied to the group when compositing it, if any.
+    /// Filtered groups are always drawn to an offscreen target.
+    filters: Option<FilterGroup>,
+
+    /// The mask state of the parent target, restored when the group ends.
+    mask_state: MaskState,
+    num_masks: u32,
+}
+
+impl BlendGroup {
+    fn needs_target(blend: BlendMode) -> bool {
+        blend == BlendMode::Layer || Self::shader_blend_mode(blend).is_some()
+    }
+
+    /// The blend mode index used by the blend shader, for blend modes that
+    /// need to read the content below the group.
+    fn shader_blend_mode(blend: BlendMode) -> Option<i32> {
+        match blend {
+            BlendMode::Difference => Some(1),
+            BlendMode::Overlay => Some(2),
+            BlendMode::HardLight => Some(3),
+            _ => None,
+        }
+    }
+}
+
+/// The filters applied to a blend group, and the area of the render target
+/// that the filtered group covers.
+struct FilterGroup {
+    filters: Vec<swf::Filter>,
+
+    /// The area covered by the group, in render target pixels, as passed to `scissor`.
+    scissor: [i32; 4],
+}
+
+impl FilterGroup {
+    /// Creates a group from the filters that can be applied by this backend.
+    ///
+    /// Returns `None` if none of the filters can be applied, or if the group
+    /// is outside of the render target.
+    fn new(
+        filters: &[swf::Filter],
+        bounds: &swf::Rectangle,
+        (view_width, view_height): (i32, i32),
+        (target_width, target_height): (i32, i32),
+    ) -> Option<Self> {
+        // TODO: Glow, bevel and convolution filters.
+        let filters: Vec<swf::Filter> = filters
+            .iter()
+            .filter(|filter| {
+                matches!(
+                    filter,
+                    swf::Filter::BlurFilter(_)
+                        | swf::Filter::DropShadowFilter(_)
+                        | swf::Filter::ColorMatrixFilter(_)
+                )
+            })
+            .cloned()
+            .collect();
+        if filters.is_empty() {
+            return None;
+        }
+
+        // The render target may be smaller than the view.
+        let scale_x = f64::from(target_width) / f64::from(view_width.max(1));
+        let scale_y = f64::from(target_height) / f64::from(view_height.max(1));
+        let x_min = ((bounds.x_min.to_pixels() * scale_x).floor() as i32).max(0);
+        let y_min = ((bounds.y_min.to_pixels() * scale_y).floor() as i32).max(0);
+        let x_max = ((bounds.x_max.to_pixels() * scale_x).ceil() as i32).min(target_width);
+        let y_max = ((bounds.y_max.to_pixels() * scale_y).ceil() as i32).min(target_height);
+        if x_min >= x_max || y_min >= y_max {
+            return None;
+        }
+
+        Some(Self {
+            filters,
+            // The Y axis of the render target points up.
+            scissor: [x_min, target_height - y_max, x_max - x_min, y_max - y_min],
+        })
+    }
+}
+
+/// Returns the uniforms of a color matrix filter: its multipliers as a
+/// column-major matrix, and its offsets scaled to the range of 0 to 1.
+fn color_matrix_uniforms(filter: &swf::ColorMatrixFilter) -> ([[f32; 4]; 4], [f32; 4]) {
+    let m = &filter.matrix;
+    let mut matrix = [[0.0; 4]; 4];
+    let mut offset = [0.0; 4];
+    for (row, row_offset) in offset.iter_mut().enumerate() {
+        for (column, out) in matrix.iter_mut().enumerate() {
+            out[row] = m[row * 5 + column] as f32;
+        }
+        *row_offset = m[row * 5 + 4] as f32 / 255.0;
+    }
+    (matrix, offset)
+}
+
+/// A texture covering the whole render target, drawn to a filter target.
+struct FilterDraw<'a> {
+    texture: &'a WebGlTexture,
+
+    /// How far the texture is moved, in pixels.
+    offset: (f32, f32),
+    mult_color: [f32; 4],
+    add_color: [f32; 4],
+    blend: BlendState,
+
+    /// The direction and radius in pixels of a pass of a box blur, if the
+    /// texture is blurred.
+    blur: Option<((f32, f32), u32)>,
+
+    /// The matrix and offsets of a color matrix filter, if one is applied.
+    color_matrix: Option<([[f32; 4]; 4], [f32; 4])>,
+}
+
+impl<'a> FilterDraw<'a> {
+    fn new(texture: &'a WebGlTexture, blend: BlendState) -> Self {
+        Self {
+            texture,
+            offset: (0.0, 0.0),
+            mult_color: [1.0; 4],
+            add_color: [0.0; 4],
+            blend,
+            blur: None,
+            color_matrix: None,
+        }
+    }
+}
+
+struct BlendTarget {
+    framebuffer: WebGlFramebuffer,
+    texture: WebGlTexture,
+    stencil_renderbuffer: WebGlRenderbuffer,
+}
+
+/// The fixed-function blending used to draw with a blend mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BlendState {
+    equation: u32,
+    src_rgb: u32,
+    dst_rgb: u32,
+    src_alpha: u32,
+    dst_alpha: u32,
+}
+
+impl BlendState {
+    /// Replaces the content below, used when drawing to filter targets.
+    const COPY: Self = Self {
+        equation: Gl::FUNC_ADD,
+        src_rgb: Gl::ONE,
+        dst_rgb: Gl::ZERO,
+        src_alpha: Gl::ONE,
+        dst_alpha: Gl::ZERO,
+    };
+
+    /// `premultiplied` is whether the shader outputs pre-multiplied alpha.
+    /// Blend modes that can't be done with fixed-function blending are drawn
+    /// as `BlendMode::Normal`.
+    fn new(blend: BlendMode, premultiplied: bool, has_min_max_blending: bool) -> Self {
+        let normal = Self {
+            equation: Gl::FUNC_ADD,
+            src_rgb: if premultiplied {
+                Gl::ONE
+            } else {
+                Gl::SRC_ALPHA
+            },
+            dst_rgb: Gl::ONE_MINUS_SRC_ALPHA,
+            src_alpha: Gl::ONE,
+            dst_alpha: Gl::ONE_MINUS_SRC_ALPHA,
+        };
+        match blend {
+            BlendMode::Multiply => Self {
+                src_rgb: Gl::DST_COLOR,
+                ..normal
+            },
+            BlendMode::Screen => Self {
+                dst_rgb: Gl::ONE_MINUS_SRC_COLOR,
+                ..normal
+            },
+            BlendMode::Lighten if has_min_max_blending => Self {
+                equation: Gl2::MAX,
+                src_rgb: Gl::ONE,
+                dst_rgb: Gl::ONE,
+                ..normal
+            },
+            BlendMode::Darken if has_min_max_blending => Self {
+                equation: Gl2::MIN,
+                src_rgb: Gl::ONE,
+                dst_rgb: Gl::ONE,
+                ..normal
+            },
+            BlendMode::Add => Self {
+                dst_rgb: Gl::ONE,
+                ..normal
+            },
+            BlendMode::Subtract => Self {
+                equation: Gl::FUNC_REVERSE_SUBTRACT,
+                dst_rgb: Gl::ONE,
+                ..normal
+            },
+            // The object is drawn in white; see `blend_colors`.
+            BlendMode::Invert => Self {
+                src_rgb: Gl::ONE_MINUS_DST_COLOR,
+                ..normal
+            },
+            BlendMode::Alpha => Self {
+                src_rgb: Gl::ZERO,
+                dst_rgb: Gl::SRC_ALPHA,
+                src_alpha: Gl::ZERO,
+                dst_alpha: Gl::SRC_ALPHA,
+                ..normal
+            },
+            BlendMode::Erase => Self {
+                src_rgb: Gl::ZERO,
+                dst_rgb: Gl::ONE_MINUS_SRC_ALPHA,
+                src_alpha: Gl::ZERO,
+                dst_alpha: Gl::ONE_MINUS_SRC_ALPHA,
+                ..normal
+            },
+            _ => normal,
+        }
+    }
+
+    fn apply(&self, gl: &Gl) {
+        gl.blend_equation_separate(self.equation, Gl::FUNC_ADD);
+        gl.blend_func_separate(self.src_rgb, self.dst_rgb, self.src_alpha, self.dst_alpha);
+    }
+}
+
+/// Returns the color transform used to draw an object with a blend mode.
+///
+/// Inverting ignores the colors of the object, so it is drawn in white,
+/// keeping its alpha.
+fn blend_colors(
+    blend: BlendMode,
+    mult_color: [f32; 4],
+    add_color: [f32; 4],
+) -> ([f32; 4], [f32; 4]) {
+    if blend == BlendMode::Invert {
+        (
+            [0.0, 0.0, 0.0, mult_color[3]],
+            [1.0, 1.0, 1.0, add_color[3]],
+        )
+    } else {
+        (mult_color, add_color)
+    }
+}
+
 struct MsaaBuffers {
     color_renderbuffer: WebGlRenderbuffer,
     stencil_renderbuffer: WebGlRenderbuffer,
@@ -1333,7 +2442,7 @@ struct ShaderProgram {
 }
 
 // These should match the uniform names in the shaders.
-const NUM_UNIFORMS: usize = 13;
+const NUM_UNIFORMS: usize = 19;
 const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "world_matrix",
     "view_matrix",
@@ -1348,6 +2457,12 @@ const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "u_focal_point",
     "u_interpolation",
     "u_texture",
+    "u_backdrop",
+    "u_blend_mode",
+    "u_blur_direction",
+    "u_blur_radius",
+    "u_color_matrix",
+    "u_color_offset",
 ];
 
 enum ShaderUniform {
@@ -1364,6 +2479,12 @@ enum ShaderUniform {
     GradientFocalPoint,
     GradientInterpolation,
     BitmapTexture,
+    BackdropTexture,
+    BlendMode,
+    BlurDirection,
+    BlurRadius,
+    ColorMatrix,
+    ColorOffset,
 }
 
 impl ShaderProgram {
@@ -1429,6 +2550,10 @@ impl ShaderProgram {
         gl.uniform1i(self.uniforms[uniform as usize].as_ref(), value);
     }
 
+    fn uniform2f(&self, gl: &Gl, uniform: ShaderUniform, x: f32, y: f32) {
+        gl.uniform2f(self.uniforms[uniform as usize].as_ref(), x, y);
+    }
+
     fn uniform4fv(&self, gl: &Gl, uniform: ShaderUniform, values: &[f32]) {
         gl.uniform4fv_with_f32_array(self.uniforms[uniform as usize].as_ref(), values);
     }
@@ -0,0 +1,433 @@
+use crate::filters::FilterGroup;
+use crate::MaskState;
+use enum_map::Enum;
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::swf::BlendMode;
+
+/// The blend modes that can be drawn with fixed-function blending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum TrivialBlend {
+    Normal,
+    Multiply,
+    Screen,
+    Lighten,
+    Darken,
+    Add,
+    Subtract,
+    Invert,
+    Alpha,
+    Erase,
+}
+
+impl TrivialBlend {
+    /// The color state used to draw with this blend mode.
+    ///
+    /// `premultiplied` is whether the shader outputs pre-multiplied alpha.
+    pub fn color_state(
+        self,
+        premultiplied: bool,
+        write_mask: wgpu::ColorWrite,
+    ) -> wgpu::ColorStateDescriptor {
+        let src_factor = if premultiplied {
+            wgpu::BlendFactor::One
+        } else {
+            wgpu::BlendFactor::SrcAlpha
+        };
+        let blend = |src_factor, dst_factor, operation| wgpu::BlendDescriptor {
+            src_factor,
+            dst_factor,
+            operation,
+        };
+        let normal_alpha = blend(
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::OneMinusSrcAlpha,
+            wgpu::BlendOperation::Add,
+        );
+
+        let (color_blend, alpha_blend) = match self {
+            TrivialBlend::Normal => (
+                blend(
+                    src_factor,
+                    wgpu::BlendFactor::OneMinusSrcAlpha,
+                    wgpu::BlendOperation::Add,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Multiply => (
+                blend(
+                    wgpu::BlendFactor::DstColor,
+                    wgpu::BlendFactor::OneMinusSrcAlpha,
+                    wgpu::BlendOperation::Add,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Screen => (
+                blend(
+                    src_factor,
+                    wgpu::BlendFactor::OneMinusSrcColor,
+                    wgpu::BlendOperation::Add,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Lighten => (
+                blend(
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendOperation::Max,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Darken => (
+                blend(
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendOperation::Min,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Add => (
+                blend(
+                    src_factor,
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendOperation::Add,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Subtract => (
+                blend(
+                    src_factor,
+                    wgpu::BlendFactor::One,
+                    wgpu::BlendOperation::ReverseSubtract,
+                ),
+                normal_alpha,
+            ),
+            // The object is drawn in white; see `TrivialBlend::color_transform`.
+            TrivialBlend::Invert => (
+                blend(
+                    wgpu::BlendFactor::OneMinusDstColor,
+                    wgpu::BlendFactor::OneMinusSrcAlpha,
+                    wgpu::BlendOperation::Add,
+                ),
+                normal_alpha,
+            ),
+            TrivialBlend::Alpha => {
+                let alpha = blend(
+                    wgpu::BlendFactor::Zero,
+                    wgpu::BlendFactor::SrcAlpha,
+                    wgpu::BlendOperation::Add,
+                );
+                (alpha.clone(), alpha)
+            }
+            TrivialBlend::Erase => {
+                let erase = blend(
+                    wgpu::BlendFactor::Zero,
+                    wgpu::BlendFactor::OneMinusSrcAlpha,
+                    wgpu::BlendOperation::Add,
+                );
+                (erase.clone(), erase)
+            }
+        };
+
+        wgpu::ColorStateDescriptor {
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            color_blend,
+            alpha_blend,
+            write_mask,
+        }
+    }
+
+    /// Returns the color transform used to draw an object with this blend mode.
+    ///
+    /// Inverting ignores the colors of the object, so it is drawn in white,
+    /// keeping its alpha.
+    pub fn color_transform(self, color_transform: ColorTransform) -> ColorTransform {
+        if self == TrivialBlend::Invert {
+            ColorTransform {
+                r_mult: 0.0,
+                g_mult: 0.0,
+                b_mult: 0.0,
+                r_add: 1.0,
+                g_add: 1.0,
+                b_add: 1.0,
+                ..color_transform
+            }
+        } else {
+            color_transform
+        }
+    }
+}
+
+/// A group of draws that is composited with a blend mode.
+pub struct BlendGroup {
+    pub blend: BlendMode,
+
+    /// The offscreen target that the group is drawn to, for blend modes that
+    /// can't be done with fixed-function blending.
+    pub target: Option<usize>,
+
+    /// The filters applied to the group when compositing it, if any.
+    /// Filtered groups are always drawn to an offscreen target.
+    pub filters: Option<FilterGroup>,
+
+    /// The mask state of the parent target, restored when the group ends.
+    pub mask_state: MaskState,
+    pub num_masks: u32,
+}
+
+impl BlendGroup {
+    fn needs_target(blend: BlendMode) -> bool {
+        // TODO: Difference, overlay and hard light need a shader that reads
+        // the content below the group. For now, they are composited as layers.
+        matches!(
+            blend,
+            BlendMode::Layer | BlendMode::Difference | BlendMode::Overlay | BlendMode::HardLight
+        )
+    }
+}
+
+/// An offscreen target that a blend group is drawn to.
+pub struct BlendTarget {
+    color_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+
+    /// The texture that the group is sampled from when compositing it.
+    /// With MSAA, this is the texture that the color attachment is resolved to.
+    sample_texture: wgpu::Texture,
+    resolve_view: Option<wgpu::TextureView>,
+}
+
+impl BlendTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32, msaa_sample_count: u32) -> Self {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        let create_texture = |label: Option<String>, sample_count, format, usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size,
+                mip_level_count: 1,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+            })
+        };
+
+        let depth_texture = create_texture(
+            create_debug_label!("Blend target depth texture"),
+            msaa_sample_count,
+            wgpu::TextureFormat::Depth24PlusStencil8,
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        );
+        let depth_view = depth_texture.create_view(&Default::default());
+
+        if msaa_sample_count >= 2 {
+            let color_texture = create_texture(
+                create_debug_label!("Blend target MSAA texture"),
+                msaa_sample_count,
+                wgpu::TextureFormat::Bgra8Unorm,
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            );
+            let sample_texture = create_texture(
+                create_debug_label!("Blend target texture"),
+                1,
+                wgpu::TextureFormat::Bgra8Unorm,
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+            );
+            Self {
+                color_view: color_texture.create_view(&Default::default()),
+                depth_view,
+                resolve_view: Some(sample_texture.create_view(&Default::default())),
+                sample_texture,
+            }
+        } else {
+            let sample_texture = create_texture(
+                create_debug_label!("Blend target texture"),
+                1,
+                wgpu::TextureFormat::Bgra8Unorm,
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+            );
+            Self {
+                color_view: sample_texture.create_view(&Default::default()),
+                depth_view,
+                resolve_view: None,
+                sample_texture,
+            }
+        }
+    }
+
+    pub fn color_view(&self) -> &wgpu::TextureView {
+        &self.color_view
+    }
+
+    pub fn depth_view(&self) -> &wgpu::TextureView {
+        &self.depth_view
+    }
+
+    pub fn resolve_view(&self) -> Option<&wgpu::TextureView> {
+        self.resolve_view.as_ref()
+    }
+
+    pub fn sample_texture(&self) -> &wgpu::Texture {
+        &self.sample_texture
+    }
+}
+
+/// The stack of blend groups being drawn, and the offscreen targets they use.
+#[derive(Default)]
+pub struct BlendStack {
+    groups: Vec<BlendGroup>,
+
+    /// Offscreen targets, reused between frames.
+    targets: Vec<BlendTarget>,
+}
+
+impl BlendStack {
+    /// Drops all groups, at the start of a frame.
+    pub fn clear(&mut self) {
+        self.groups.clear();
+    }
+
+    /// Drops all offscreen targets, after the viewport has been resized.
+    pub fn clear_targets(&mut self) {
+        self.targets.clear();
+    }
+
+    /// Begins a new blend group, returning whether it is drawn to an offscreen target.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push(
+        &mut self,
+        device: &wgpu::Device,
+        blend: BlendMode,
+        mask_state: MaskState,
+        num_masks: u32,
+        width: u32,
+        height: u32,
+        msaa_sample_count: u32,
+    ) -> bool {
+        let target = if BlendGroup::needs_target(blend) {
+            Some(self.next_target(device, width, height, msaa_sample_count))
+        } else {
+            None
+        };
+
+        self.groups.push(BlendGroup {
+            blend,
+            target,
+            filters: None,
+            mask_state,
+            num_masks,
+        });
+        target.is_some()
+    }
+
+    /// Begins a new group that is drawn to an offscreen target and filtered
+    /// when it ends.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push_filters(
+        &mut self,
+        device: &wgpu::Device,
+        filters: FilterGroup,
+        mask_state: MaskState,
+        num_masks: u32,
+        width: u32,
+        height: u32,
+        msaa_sample_count: u32,
+    ) {
+        let target = self.next_target(device, width, height, msaa_sample_count);
+        self.groups.push(BlendGroup {
+            blend: BlendMode::Normal,
+            target: Some(target),
+            filters: Some(filters),
+            mask_state,
+            num_masks,
+        });
+    }
+
+    /// Returns the index of the offscreen target for a new group, creating
+    /// the target if needed.
+    fn next_target(
+        &mut self,
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        msaa_sample_count: u32,
+    ) -> usize {
+        let target = self
+            .groups
+            .iter()
+            .filter(|group| group.target.is_some())
+            .count();
+        if target >= self.targets.len() {
+            self.targets
+                .push(BlendTarget::new(device, width, height, msaa_sample_count));
+        }
+        target
+    }
+
+    pub fn pop(&mut self) -> Option<BlendGroup> {
+        self.groups.pop()
+    }
+
+    pub fn target(&self, index: usize) -> &BlendTarget {
+        &self.targets[index]
+    }
+
+    /// The offscreen target that draws currently go to.
+    pub fn current_target(&self) -> Option<&BlendTarget> {
+        self.groups
+            .iter()
+            .rev()
+            .find_map(|group| group.target)
+            .map(|target| &self.targets[target])
+    }
+
+    /// Returns the color and depth-stencil attachments that draws currently
+    /// go to, given the attachments of the frame.
+    pub fn attachments<'a>(
+        &'a self,
+        frame_color: &'a wgpu::TextureView,
+        frame_depth: &'a wgpu::TextureView,
+    ) -> (&'a wgpu::TextureView, &'a wgpu::TextureView) {
+        if let Some(target) = self.current_target() {
+            (target.color_view(), target.depth_view())
+        } else {
+            (frame_color, frame_depth)
+        }
+    }
+
+    /// The blend mode that draws use, given the current blend groups.
+    ///
+    /// Groups that are drawn to an offscreen target start over with normal
+    /// blending; the group's blend mode is applied when compositing it.
+    pub fn draw_blend(&self) -> TrivialBlend {
+        for (i, group) in self.groups.iter().enumerate().rev() {
+            if group.target.is_some() {
+                return TrivialBlend::Normal;
+            }
+            let blend = match group.blend {
+                BlendMode::Multiply => TrivialBlend::Multiply,
+                BlendMode::Screen => TrivialBlend::Screen,
+                BlendMode::Lighten => TrivialBlend::Lighten,
+                BlendMode::Darken => TrivialBlend::Darken,
+                BlendMode::Add => TrivialBlend::Add,
+                BlendMode::Subtract => TrivialBlend::Subtract,
+                BlendMode::Invert => TrivialBlend::Invert,
+                BlendMode::Alpha if self.in_layer(i) => TrivialBlend::Alpha,
+                BlendMode::Erase if self.in_layer(i) => TrivialBlend::Erase,
+                _ => continue,
+            };
+            return blend;
+        }
+        TrivialBlend::Normal
+    }
+
+    /// Whether the group at the given index is inside of a layer group.
+    fn in_layer(&self, index: usize) -> bool {
+        self.groups[..index]
+            .iter()
+            .any(|parent| parent.blend == BlendMode::Layer)
+    }
+}
@@ -0,0 +1,554 @@
+use crate::target::RenderTarget;
+use crate::utils::create_buffer_with_data;
+use crate::{ColorAdjustments, TextureTransforms, Transforms, WgpuRenderBackend};
+use enum_map::Enum;
+use ruffle_core::backend::render::blur_radius;
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::swf::{self, Filter};
+
+/// The format of the textures that blurs are accumulated in.
+///
+/// Each sample of a blur adds a small fraction of a pixel, which would lose
+/// too much precision with 8 bits per channel.
+pub const BLUR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// The format of the textures holding the result of each filter.
+///
+/// Colors are clamped when they are drawn to these textures.
+pub const RESULT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Bgra8Unorm;
+
+/// How a texture is blended with the filter target it is drawn to.
+///
+/// All textures use pre-multiplied alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum FilterBlend {
+    /// Replaces the target.
+    Copy,
+
+    /// Adds the texture to the target.
+    Accumulate,
+
+    /// Draws the texture over the target.
+    Normal,
+
+    /// Keeps the target where the texture is opaque.
+    Mask,
+
+    /// Erases the target where the texture is opaque.
+    Erase,
+}
+
+impl FilterBlend {
+    pub fn color_state(self, format: wgpu::TextureFormat) -> wgpu::ColorStateDescriptor {
+        let (src_factor, dst_factor) = match self {
+            FilterBlend::Copy => (wgpu::BlendFactor::One, wgpu::BlendFactor::Zero),
+            FilterBlend::Accumulate => (wgpu::BlendFactor::One, wgpu::BlendFactor::One),
+            FilterBlend::Normal => (wgpu::BlendFactor::One, wgpu::BlendFactor::OneMinusSrcAlpha),
+            FilterBlend::Mask => (wgpu::BlendFactor::Zero, wgpu::BlendFactor::SrcAlpha),
+            FilterBlend::Erase => (wgpu::BlendFactor::Zero, wgpu::BlendFactor::OneMinusSrcAlpha),
+        };
+        let blend = wgpu::BlendDescriptor {
+            src_factor,
+            dst_factor,
+            operation: wgpu::BlendOperation::Add,
+        };
+
+        wgpu::ColorStateDescriptor {
+            format,
+            color_blend: blend.clone(),
+            alpha_blend: blend,
+            write_mask: wgpu::ColorWrite::ALL,
+        }
+    }
+}
+
+/// An area of the viewport, in pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct Scissor {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The filters applied to a blend group, and the area of the viewport that
+/// the filtered group covers.
+pub struct FilterGroup {
+    pub filters: Vec<Filter>,
+    pub scissor: Scissor,
+}
+
+impl FilterGroup {
+    /// Creates a group from the filters that can be applied by this backend.
+    ///
+    /// Returns `None` if none of the filters can be applied, or if the group
+    /// is outside of the viewport.
+    pub fn new(
+        filters: &[Filter],
+        bounds: &swf::Rectangle,
+        viewport_width: u32,
+        viewport_height: u32,
+    ) -> Option<Self> {
+        // TODO: Glow, bevel, convolution and color matrix filters.
+        let filters: Vec<Filter> = filters
+            .iter()
+            .filter(|filter| matches!(filter, Filter::BlurFilter(_) | Filter::DropShadowFilter(_)))
+            .cloned()
+            .collect();
+        if filters.is_empty() {
+            return None;
+        }
+
+        let x_min = bounds.x_min.to_pixels().floor().max(0.0) as u32;
+        let y_min = bounds.y_min.to_pixels().floor().max(0.0) as u32;
+        let x_max = (bounds.x_max.to_pixels().ceil().max(0.0) as u32).min(viewport_width);
+        let y_max = (bounds.y_max.to_pixels().ceil().max(0.0) as u32).min(viewport_height);
+        if x_min >= x_max || y_min >= y_max {
+            return None;
+        }
+
+        Some(Self {
+            filters,
+            scissor: Scissor {
+                x: x_min,
+                y: y_min,
+                width: x_max - x_min,
+                height: y_max - y_min,
+            },
+        })
+    }
+}
+
+/// The viewport-sized textures that filters are applied in, reused between frames.
+pub struct FilterTargets {
+    /// Blurs are applied back and forth between these textures, ending in the first one.
+    blur: [wgpu::Texture; 2],
+
+    /// Each filter draws its result to the texture that the previous filter didn't use.
+    results: [wgpu::Texture; 2],
+}
+
+impl FilterTargets {
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let create_texture = |label: Option<String>, format| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED,
+            })
+        };
+
+        Self {
+            blur: [
+                create_texture(create_debug_label!("Filter blur texture 0"), BLUR_FORMAT),
+                create_texture(create_debug_label!("Filter blur texture 1"), BLUR_FORMAT),
+            ],
+            results: [
+                create_texture(
+                    create_debug_label!("Filter result texture 0"),
+                    RESULT_FORMAT,
+                ),
+                create_texture(
+                    create_debug_label!("Filter result texture 1"),
+                    RESULT_FORMAT,
+                ),
+            ],
+        }
+    }
+}
+
+/// Returns the draws of one direction of a box blur.
+///
+/// Each pixel is the average of the pixels within the radius of it, so a
+/// draw is made for each of them, offset by its distance.
+fn box_blur(texture: &wgpu::TextureView, radius: u32, horizontal: bool) -> Vec<FilterDraw<'_>> {
+    let radius = radius as i32;
+    let weight = 1.0 / (2 * radius + 1) as f32;
+    (-radius..=radius)
+        .map(|offset| {
+            let offset = offset as f32;
+            FilterDraw {
+                offset: if horizontal {
+                    (offset, 0.0)
+                } else {
+                    (0.0, offset)
+                },
+                color_transform: ColorTransform {
+                    a_mult: weight,
+                    ..Default::default()
+                },
+                ..FilterDraw::new(texture, FilterBlend::Accumulate)
+            }
+        })
+        .collect()
+}
+
+/// A viewport-sized texture drawn to a filter target.
+struct FilterDraw<'a> {
+    texture: &'a wgpu::TextureView,
+    offset: (f32, f32),
+    color_transform: ColorTransform,
+    blend: FilterBlend,
+}
+
+impl<'a> FilterDraw<'a> {
+    fn new(texture: &'a wgpu::TextureView, blend: FilterBlend) -> Self {
+        Self {
+            texture,
+            offset: (0.0, 0.0),
+            color_transform: Default::default(),
+            blend,
+        }
+    }
+}
+
+impl<T: RenderTarget> WgpuRenderBackend<T> {
+    /// Applies the filters of a group to its contents, returning the view of
+    /// the texture holding the filtered group.
+    pub(crate) fn apply_filters(
+        &mut self,
+        group: &FilterGroup,
+        source: &wgpu::TextureView,
+    ) -> Option<wgpu::TextureView> {
+        let (width, height) = (self.viewport_width as u32, self.viewport_height as u32);
+        let device = &self.descriptors.device;
+        let targets = self
+            .filter_targets
+            .get_or_insert_with(|| FilterTargets::new(device, width, height));
+        let blur = [
+            targets.blur[0].create_view(&Default::default()),
+            targets.blur[1].create_view(&Default::default()),
+        ];
+        let results = [
+            targets.results[0].create_view(&Default::default()),
+            targets.results[1].create_view(&Default::default()),
+        ];
+        let clear = wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT);
+
+        let mut result = None;
+        for filter in &group.filters {
+            let output = if result == Some(0) { 1 } else { 0 };
+            let input = result.map_or(source, |result: usize| &results[result]);
+            match filter {
+                Filter::BlurFilter(blur_filter) => {
+                    self.draw_filter_quads(
+                        &blur[0],
+                        BLUR_FORMAT,
+                        clear,
+                        group.scissor,
+                        &[FilterDraw::new(input, FilterBlend::Copy)],
+                    );
+                    self.blur(
+                        &blur,
+                        blur_filter.blur_x,
+                        blur_filter.blur_y,
+                        blur_filter.num_passes,
+                        group.scissor,
+                    );
+                    self.draw_filter_quads(
+                        &results[output],
+                        RESULT_FORMAT,
+                        clear,
+                        group.scissor,
+                        &[FilterDraw::new(&blur[0], FilterBlend::Copy)],
+                    );
+                }
+                Filter::DropShadowFilter(shadow) => {
+                    self.drop_shadow(shadow, input, &results[output], &blur, group.scissor)
+                }
+                _ => continue,
+            }
+            result = Some(output);
+        }
+
+        let targets = self.filter_targets.as_ref()?;
+        result.map(|result| targets.results[result].create_view(&Default::default()))
+    }
+
+    /// Blurs the first blur texture in place, with the given number of passes
+    /// of a box blur.
+    fn blur(
+        &mut self,
+        blur: &[wgpu::TextureView; 2],
+        blur_x: f64,
+        blur_y: f64,
+        num_passes: u8,
+        scissor: Scissor,
+    ) {
+        let (radius_x, radius_y) = (blur_radius(blur_x), blur_radius(blur_y));
+        if radius_x == 0 && radius_y == 0 {
+            return;
+        }
+
+        let clear = wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT);
+        for _ in 0..num_passes {
+            self.draw_filter_quads(
+                &blur[1],
+                BLUR_FORMAT,
+                clear,
+                scissor,
+                &box_blur(&blur[0], radius_x, true),
+            );
+            self.draw_filter_quads(
+                &blur[0],
+                BLUR_FORMAT,
+                clear,
+                scissor,
+                &box_blur(&blur[1], radius_y, false),
+            );
+        }
+    }
+
+    /// Draws a drop shadow of `source`, along with `source` itself unless the
+    /// filter hides it, to `output`.
+    fn drop_shadow(
+        &mut self,
+        shadow: &swf::DropShadowFilter,
+        source: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+        blur: &[wgpu::TextureView; 2],
+        scissor: Scissor,
+    ) {
+        let offset = (
+            (shadow.distance * shadow.angle.cos()) as f32,
+            (shadow.distance * shadow.angle.sin()) as f32,
+        );
+        let color = &shadow.color;
+        let alpha = f32::from(color.a) / 255.0;
+
+        if shadow.is_inner {
+            // An inner shadow is cast by the area around the object, so the
+            // shadow color is drawn everywhere except for the object.
+            let alpha = f64::from(alpha);
+            let shadow_color = wgpu::Color {
+                r: f64::from(color.r) / 255.0 * alpha,
+                g: f64::from(color.g) / 255.0 * alpha,
+                b: f64::from(color.b) / 255.0 * alpha,
+                a: alpha,
+            };
+            self.draw_filter_quads(
+                &blur[0],
+                BLUR_FORMAT,
+                wgpu::LoadOp::Clear(shadow_color),
+                scissor,
+                &[FilterDraw {
+                    offset,
+                    ..FilterDraw::new(source, FilterBlend::Erase)
+                }],
+            );
+        } else {
+            // The silhouette of the object, in the shadow color.
+            let tint = ColorTransform {
+                r_mult: 0.0,
+                g_mult: 0.0,
+                b_mult: 0.0,
+                a_mult: alpha,
+                r_add: f32::from(color.r) / 255.0,
+                g_add: f32::from(color.g) / 255.0,
+                b_add: f32::from(color.b) / 255.0,
+                a_add: 0.0,
+            };
+            self.draw_filter_quads(
+                &blur[0],
+                BLUR_FORMAT,
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                scissor,
+                &[FilterDraw {
+                    offset,
+                    color_transform: tint,
+                    ..FilterDraw::new(source, FilterBlend::Copy)
+                }],
+            );
+        }
+
+        self.blur(
+            blur,
+            shadow.blur_x,
+            shadow.blur_y,
+            shadow.num_passes,
+            scissor,
+        );
+
+        if shadow.is_inner {
+            self.draw_filter_quads(
+                &blur[0],
+                BLUR_FORMAT,
+                wgpu::LoadOp::Load,
+                scissor,
+                &[FilterDraw::new(source, FilterBlend::Mask)],
+            );
+        }
+
+        // The strength multiplies the alpha of the shadow, which is clamped
+        // when drawn to the result.
+        // TODO: Colors are multiplied along with the alpha, so shadows with a
+        // strength above 1 are brighter than in Flash.
+        let shadow_draw = |blend| FilterDraw {
+            color_transform: ColorTransform {
+                a_mult: shadow.strength,
+                ..Default::default()
+            },
+            ..FilterDraw::new(&blur[0], blend)
+        };
+        let draws = if shadow.is_inner {
+            if shadow.is_knockout || shadow.hide_object {
+                vec![shadow_draw(FilterBlend::Copy)]
+            } else {
+                vec![
+                    FilterDraw::new(source, FilterBlend::Copy),
+                    shadow_draw(FilterBlend::Normal),
+                ]
+            }
+        } else if shadow.is_knockout {
+            vec![
+                shadow_draw(FilterBlend::Copy),
+                FilterDraw::new(source, FilterBlend::Erase),
+            ]
+        } else if shadow.hide_object {
+            vec![shadow_draw(FilterBlend::Copy)]
+        } else {
+            vec![
+                shadow_draw(FilterBlend::Copy),
+                FilterDraw::new(source, FilterBlend::Normal),
+            ]
+        };
+        self.draw_filter_quads(
+            output,
+            RESULT_FORMAT,
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+            scissor,
+            &draws,
+        );
+    }
+
+    /// Draws viewport-sized textures to a single-sampled filter target.
+    fn draw_filter_quads(
+        &mut self,
+        target: &wgpu::TextureView,
+        format: wgpu::TextureFormat,
+        load: wgpu::LoadOp<wgpu::Color>,
+        scissor: Scissor,
+        draws: &[FilterDraw<'_>],
+    ) {
+        let device = &self.descriptors.device;
+        let pipelines = &self.descriptors.pipelines;
+        let quad_tex_transforms = &self.quad_tex_transforms;
+        let (viewport_width, viewport_height) = (self.viewport_width, self.viewport_height);
+
+        let bind_groups: Vec<_> = draws
+            .iter()
+            .map(|draw| {
+                let world_matrix = [
+                    [viewport_width, 0.0, 0.0, 0.0],
+                    [0.0, viewport_height, 0.0, 0.0],
+                    [0.0, 0.0, 1.0, 0.0],
+                    [draw.offset.0, draw.offset.1, 0.0, 1.0],
+                ];
+                let transforms_ubo = create_buffer_with_data(
+                    device,
+                    bytemuck::cast_slice(&[Transforms { world_matrix }]),
+                    wgpu::BufferUsage::UNIFORM,
+                    create_debug_label!("Filter transforms transfer buffer"),
+                );
+                let colors_ubo = create_buffer_with_data(
+                    device,
+                    bytemuck::cast_slice(&[ColorAdjustments::from(draw.color_transform)]),
+                    wgpu::BufferUsage::UNIFORM,
+                    create_debug_label!("Filter colors transfer buffer"),
+                );
+
+                let mesh_bind_group_label = create_debug_label!("Filter mesh bind group");
+                let mesh_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &pipelines.mesh_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &transforms_ubo,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<Transforms>() as u64
+                                ),
+                            },
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &colors_ubo,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<ColorAdjustments>() as u64,
+                                ),
+                            },
+                        },
+                    ],
+                    label: mesh_bind_group_label.as_deref(),
+                });
+
+                let bitmap_bind_group_label = create_debug_label!("Filter bitmap bind group");
+                let bitmap_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &pipelines.bitmap_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: quad_tex_transforms,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<TextureTransforms>() as u64,
+                                ),
+                            },
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(draw.texture),
+                        },
+                    ],
+                    label: bitmap_bind_group_label.as_deref(),
+                });
+
+                (mesh_bind_group, bitmap_bind_group)
+            })
+            .collect();
+
+        let encoder = if let Some((_frame_output, encoder)) = &mut self.current_frame {
+            encoder
+        } else {
+            return;
+        };
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: target,
+                resolve_target: None,
+                ops: wgpu::Operations { load, store: true },
+            }],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_scissor_rect(scissor.x, scissor.y, scissor.width, scissor.height);
+        render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
+        render_pass.set_bind_group(
+            3,
+            self.descriptors
+                .bitmap_samplers
+                .get_bind_group(false, false),
+            &[],
+        );
+        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+
+        for (draw, (mesh_bind_group, bitmap_bind_group)) in draws.iter().zip(&bind_groups) {
+            render_pass.set_pipeline(pipelines.filter_pipelines.pipeline_for(draw.blend, format));
+            render_pass.set_bind_group(1, mesh_bind_group, &[]);
+            render_pass.set_bind_group(2, bitmap_bind_group, &[]);
+            render_pass.draw_indexed(0..6, 0, 0..1);
+        }
+    }
+}
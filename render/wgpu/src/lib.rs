@@ -3,10 +3,10 @@ use lyon::tessellation::{
     geometry_builder::{BuffersBuilder, FillVertexConstructor, VertexBuffers},
     FillAttributes, FillTessellator, StrokeAttributes, StrokeTessellator, StrokeVertexConstructor,
 };
-use ruffle_core::backend::render::swf::{self, FillStyle};
+use ruffle_core::backend::render::swf::{self, BlendMode, FillStyle};
 use ruffle_core::backend::render::{
     srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
-    RenderBackend, ShapeHandle, Transform,
+    RenderBackend, ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::shape_utils::{DistilledShape, DrawPath};
 use std::convert::TryInto;
@@ -16,6 +16,8 @@ use bytemuck::{Pod, Zeroable};
 use futures::executor::block_on;
 use raw_window_handle::HasRawWindowHandle;
 
+use crate::blend::BlendStack;
+use crate::filters::{FilterGroup, FilterTargets};
 use crate::pipelines::Pipelines;
 use crate::shapes::{Draw, DrawType, GradientUniforms, IncompleteDrawType, Mesh};
 use crate::target::{RenderTarget, RenderTargetFrame, SwapChainTarget};
@@ -32,6 +34,8 @@ type Error = Box<dyn std::error::Error>;
 mod utils;
 
 mod bitmaps;
+mod blend;
+mod filters;
 mod globals;
 mod pipelines;
 mod shapes;
@@ -92,9 +96,12 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     textures: Vec<(swf::CharacterId, Texture)>,
     mask_state: MaskState,
     num_masks: u32,
+    blend_stack: BlendStack,
+    filter_targets: Option<FilterTargets>,
     quad_vbo: wgpu::Buffer,
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
+    quality: StageQuality,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
@@ -264,10 +271,13 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
             num_masks: 0,
             mask_state: MaskState::NoMask,
+            blend_stack: Default::default(),
+            filter_targets: None,
 
             quad_vbo,
             quad_ibo,
             quad_tex_transforms,
+            quality: StageQuality::default(),
         })
     }
 
@@ -348,10 +358,32 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             *lyon_mesh = VertexBuffers::new();
         }
 
+        // Solid fills and strokes are batched into separate draws, because
+        // strokes are left out when a shape is drawn as a mask.
+        let mut strokes_pending = false;
+        fn pending_draw_type(strokes_pending: &mut bool) -> IncompleteDrawType {
+            if std::mem::take(strokes_pending) {
+                IncompleteDrawType::Stroke
+            } else {
+                IncompleteDrawType::Color
+            }
+        }
+
         for path in shape.paths {
             match path {
                 DrawPath::Fill { style, commands } => match style {
                     FillStyle::Color(color) => {
+                        if strokes_pending {
+                            flush_draw(
+                                shape.id,
+                                pending_draw_type(&mut strokes_pending),
+                                &mut draws,
+                                &mut lyon_mesh,
+                                &self.descriptors.device,
+                                &self.descriptors.pipelines,
+                            );
+                        }
+
                         let color = [
                             f32::from(color.r) / 255.0,
                             f32::from(color.g) / 255.0,
@@ -375,7 +407,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     FillStyle::LinearGradient(gradient) => {
                         flush_draw(
                             shape.id,
-                            IncompleteDrawType::Color,
+                            pending_draw_type(&mut strokes_pending),
                             &mut draws,
                             &mut lyon_mesh,
                             &self.descriptors.device,
@@ -417,7 +449,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     FillStyle::RadialGradient(gradient) => {
                         flush_draw(
                             shape.id,
-                            IncompleteDrawType::Color,
+                            pending_draw_type(&mut strokes_pending),
                             &mut draws,
                             &mut lyon_mesh,
                             &self.descriptors.device,
@@ -462,7 +494,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     } => {
                         flush_draw(
                             shape.id,
-                            IncompleteDrawType::Color,
+                            pending_draw_type(&mut strokes_pending),
                             &mut draws,
                             &mut lyon_mesh,
                             &self.descriptors.device,
@@ -509,7 +541,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     } => {
                         flush_draw(
                             shape.id,
-                            IncompleteDrawType::Color,
+                            pending_draw_type(&mut strokes_pending),
                             &mut draws,
                             &mut lyon_mesh,
                             &self.descriptors.device,
@@ -571,6 +603,18 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                     commands,
                     is_closed,
                 } => {
+                    if !strokes_pending {
+                        flush_draw(
+                            shape.id,
+                            IncompleteDrawType::Color,
+                            &mut draws,
+                            &mut lyon_mesh,
+                            &self.descriptors.device,
+                            &self.descriptors.pipelines,
+                        );
+                        strokes_pending = true;
+                    }
+
                     let color = [
                         f32::from(style.color.r) / 255.0,
                         f32::from(style.color.g) / 255.0,
@@ -631,7 +675,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         flush_draw(
             shape.id,
-            IncompleteDrawType::Color,
+            pending_draw_type(&mut strokes_pending),
             &mut draws,
             &mut lyon_mesh,
             &self.descriptors.device,
@@ -758,6 +802,242 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
+
+    /// Draws the unit quad, textured with a texture using pre-multiplied alpha.
+    fn draw_texture_quad(
+        &mut self,
+        texture_view: &wgpu::TextureView,
+        transform: &Transform,
+        smoothing: bool,
+    ) {
+        let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
+        {
+            (frame_output, encoder)
+        } else {
+            return;
+        };
+
+        let blend = self.blend_stack.draw_blend();
+
+        let world_matrix = [
+            [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
+            [transform.matrix.c, transform.matrix.d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                transform.matrix.tx.to_pixels() as f32,
+                transform.matrix.ty.to_pixels() as f32,
+                0.0,
+                1.0,
+            ],
+        ];
+
+        let transforms_ubo = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[Transforms { world_matrix }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Bitmap transforms transfer buffer"),
+        );
+
+        let colors_ubo = create_buffer_with_data(
+            &self.descriptors.device,
+            bytemuck::cast_slice(&[ColorAdjustments::from(
+                blend.color_transform(transform.color_transform),
+            )]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("Bitmap colors transfer buffer"),
+        );
+
+        let mesh_bind_group_label = create_debug_label!("Bitmap mesh bind group");
+        let mesh_bind_group =
+            self.descriptors
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &self.descriptors.pipelines.mesh_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &transforms_ubo,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<Transforms>() as u64
+                                ),
+                            },
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &colors_ubo,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<ColorAdjustments>() as u64,
+                                ),
+                            },
+                        },
+                    ],
+                    label: mesh_bind_group_label.as_deref(),
+                });
+
+        let bitmap_bind_group_label = create_debug_label!("Bitmap bind group");
+        let bitmap_bind_group =
+            self.descriptors
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &self.descriptors.pipelines.bitmap_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer {
+                                buffer: &self.quad_tex_transforms,
+                                offset: 0,
+                                size: wgpu::BufferSize::new(
+                                    std::mem::size_of::<TextureTransforms>() as u64,
+                                ),
+                            },
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(texture_view),
+                        },
+                    ],
+                    label: bitmap_bind_group_label.as_deref(),
+                });
+
+        let (color_attachment, depth_attachment) = self.blend_stack.attachments(
+            if self.descriptors.msaa_sample_count >= 2 {
+                &self.frame_buffer_view
+            } else {
+                frame_output.view()
+            },
+            &self.depth_texture_view,
+        );
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: color_attachment,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: depth_attachment,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                }),
+                stencil_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                }),
+            }),
+        });
+
+        render_pass.set_pipeline(
+            &self
+                .descriptors
+                .pipelines
+                .bitmap_pipelines
+                .pipeline_for(blend, self.mask_state),
+        );
+        render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
+        render_pass.set_bind_group(1, &mesh_bind_group, &[]);
+        render_pass.set_bind_group(2, &bitmap_bind_group, &[]);
+        render_pass.set_bind_group(
+            3,
+            self.descriptors
+                .bitmap_samplers
+                .get_bind_group(false, smoothing && self.quality.allows_bitmap_smoothing()),
+            &[],
+        );
+        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+
+        match self.mask_state {
+            MaskState::NoMask => (),
+            MaskState::DrawMaskStencil => {
+                debug_assert!(self.num_masks > 0);
+                render_pass.set_stencil_reference(self.num_masks - 1);
+            }
+            MaskState::DrawMaskedContent | MaskState::ClearMaskStencil => {
+                debug_assert!(self.num_masks > 0);
+                render_pass.set_stencil_reference(self.num_masks);
+            }
+        };
+
+        render_pass.draw_indexed(0..6, 0, 0..1);
+    }
+
+    /// Clears the offscreen target of a group that was just pushed.
+    fn begin_offscreen_group(&mut self) {
+        // The offscreen target has a stencil buffer of its own.
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+
+        if let (Some((_frame_output, encoder)), Some(target)) =
+            (&mut self.current_frame, self.blend_stack.current_target())
+        {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: target.color_view(),
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: target.depth_view(),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.0),
+                        store: true,
+                    }),
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0),
+                        store: true,
+                    }),
+                }),
+            });
+        }
+    }
+
+    /// Resolves the offscreen target of a group that was just popped,
+    /// returning the view that it can be sampled from.
+    fn end_offscreen_group(&mut self, target: usize) -> wgpu::TextureView {
+        let target = self.blend_stack.target(target);
+
+        // Resolve MSAA, so that the group can be sampled.
+        if let (Some((_frame_output, encoder)), Some(resolve_view)) =
+            (&mut self.current_frame, target.resolve_view())
+        {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: target.color_view(),
+                    resolve_target: Some(resolve_view),
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+        }
+
+        target.sample_texture().create_view(&Default::default())
+    }
+
+    /// Composites a group onto the enclosing target, covering the whole viewport.
+    fn composite_offscreen_group(&mut self, texture_view: &wgpu::TextureView) {
+        let transform = Transform {
+            matrix: Matrix {
+                a: self.viewport_width,
+                d: self.viewport_height,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        self.draw_texture_quad(texture_view, &transform, false);
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
@@ -805,6 +1085,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
             });
         self.depth_texture_view = depth_texture.create_view(&Default::default());
+        self.blend_stack.clear_targets();
+        self.filter_targets = None;
 
         self.viewport_width = width as f32;
         self.viewport_height = height as f32;
@@ -862,6 +1144,69 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         Ok(self.register_bitmap(swf_tag.id, bitmap, "PNG"))
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let bitmap = Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        };
+        Ok(self.register_bitmap(0, bitmap, "Raw").handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some((_id, texture)) = self.textures.get(handle.0) {
+            texture
+        } else {
+            return Err("Unknown bitmap handle".into());
+        };
+
+        if (texture.width, texture.height) != (width, height) {
+            // Textures can't be resized, so replace it with a new one.
+            let bitmap = Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            };
+            self.register_bitmap(0, bitmap, "Raw");
+            self.textures.swap_remove(handle.0);
+            return Ok(handle);
+        }
+
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+
+        self.descriptors.queue.write_texture(
+            wgpu::TextureCopyView {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: Default::default(),
+            },
+            &rgba,
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * extent.width,
+                rows_per_image: 0,
+            },
+            extent,
+        );
+
+        Ok(handle)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         assert!(self.current_frame.is_none());
         self.current_frame = match self.target.get_next_texture() {
@@ -884,6 +1229,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
+        self.blend_stack.clear();
 
         if let Some((frame_output, encoder)) = &mut self.current_frame {
             let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
@@ -923,15 +1269,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         }
     }
 
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         if let Some((_id, texture)) = self.textures.get(bitmap.0) {
-            let (frame_output, encoder) =
-                if let Some((frame_output, encoder)) = &mut self.current_frame {
-                    (frame_output, encoder)
-                } else {
-                    return;
-                };
-
+            let texture_view = texture.texture.create_view(&Default::default());
             let transform = Transform {
                 matrix: transform.matrix
                     * Matrix {
@@ -941,149 +1281,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     },
                 ..*transform
             };
-
-            let world_matrix = [
-                [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
-                [transform.matrix.c, transform.matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    transform.matrix.tx.to_pixels() as f32,
-                    transform.matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ];
-
-            let transforms_ubo = create_buffer_with_data(
-                &self.descriptors.device,
-                bytemuck::cast_slice(&[Transforms { world_matrix }]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} transforms transfer buffer", bitmap.0),
-            );
-
-            let colors_ubo = create_buffer_with_data(
-                &self.descriptors.device,
-                bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} colors transfer buffer", bitmap.0),
-            );
-
-            let texture_view = texture.texture.create_view(&Default::default());
-
-            let mesh_bind_group_label = create_debug_label!("Bitmap {} mesh bind group", bitmap.0);
-            let mesh_bind_group =
-                self.descriptors
-                    .device
-                    .create_bind_group(&wgpu::BindGroupDescriptor {
-                        layout: &self.descriptors.pipelines.mesh_layout,
-                        entries: &[
-                            wgpu::BindGroupEntry {
-                                binding: 0,
-                                resource: wgpu::BindingResource::Buffer {
-                                    buffer: &transforms_ubo,
-                                    offset: 0,
-                                    size: wgpu::BufferSize::new(
-                                        std::mem::size_of::<Transforms>() as u64
-                                    ),
-                                },
-                            },
-                            wgpu::BindGroupEntry {
-                                binding: 1,
-                                resource: wgpu::BindingResource::Buffer {
-                                    buffer: &colors_ubo,
-                                    offset: 0,
-                                    size: wgpu::BufferSize::new(
-                                        std::mem::size_of::<ColorAdjustments>() as u64,
-                                    ),
-                                },
-                            },
-                        ],
-                        label: mesh_bind_group_label.as_deref(),
-                    });
-
-            let bitmap_bind_group_label = create_debug_label!("Bitmap {} bind group", bitmap.0);
-            let bitmap_bind_group =
-                self.descriptors
-                    .device
-                    .create_bind_group(&wgpu::BindGroupDescriptor {
-                        layout: &self.descriptors.pipelines.bitmap_layout,
-                        entries: &[
-                            wgpu::BindGroupEntry {
-                                binding: 0,
-                                resource: wgpu::BindingResource::Buffer {
-                                    buffer: &self.quad_tex_transforms,
-                                    offset: 0,
-                                    size: wgpu::BufferSize::new(
-                                        std::mem::size_of::<TextureTransforms>() as u64,
-                                    ),
-                                },
-                            },
-                            wgpu::BindGroupEntry {
-                                binding: 1,
-                                resource: wgpu::BindingResource::TextureView(&texture_view),
-                            },
-                        ],
-                        label: bitmap_bind_group_label.as_deref(),
-                    });
-
-            let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-                &self.frame_buffer_view
-            } else {
-                frame_output.view()
-            };
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                    attachment: color_attachment,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    },
-                }],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                    attachment: &self.depth_texture_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    }),
-                    stencil_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    }),
-                }),
-            });
-
-            render_pass.set_pipeline(
-                &self
-                    .descriptors
-                    .pipelines
-                    .bitmap_pipelines
-                    .pipeline_for(self.mask_state),
-            );
-            render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
-            render_pass.set_bind_group(1, &mesh_bind_group, &[]);
-            render_pass.set_bind_group(2, &bitmap_bind_group, &[]);
-            render_pass.set_bind_group(
-                3,
-                self.descriptors.bitmap_samplers.get_bind_group(false, true),
-                &[],
-            );
-            render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
-            render_pass.set_index_buffer(self.quad_ibo.slice(..));
-
-            match self.mask_state {
-                MaskState::NoMask => (),
-                MaskState::DrawMaskStencil => {
-                    debug_assert!(self.num_masks > 0);
-                    render_pass.set_stencil_reference(self.num_masks - 1);
-                }
-                MaskState::DrawMaskedContent | MaskState::ClearMaskStencil => {
-                    debug_assert!(self.num_masks > 0);
-                    render_pass.set_stencil_reference(self.num_masks);
-                }
-            };
-
-            render_pass.draw_indexed(0..6, 0, 0..1);
+            self.draw_texture_quad(&texture_view, &transform, smoothing);
         }
     }
 
@@ -1096,6 +1294,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
 
         let mesh = &mut self.meshes[shape.0];
+        let blend = self.blend_stack.draw_blend();
+        let color_transform = blend.color_transform(transform.color_transform);
 
         let world_matrix = [
             [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
@@ -1109,10 +1309,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             ],
         ];
 
-        if transform.color_transform != mesh.colors_last {
+        if color_transform != mesh.colors_last {
             let colors_temp = create_buffer_with_data(
                 &self.descriptors.device,
-                bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
+                bytemuck::cast_slice(&[ColorAdjustments::from(color_transform)]),
                 wgpu::BufferUsage::COPY_SRC,
                 create_debug_label!("Shape {} colors transfer buffer", mesh.shape_id),
             );
@@ -1125,7 +1325,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 std::mem::size_of::<ColorAdjustments>() as u64,
             );
 
-            mesh.colors_last = transform.color_transform;
+            mesh.colors_last = color_transform;
         }
 
         let transforms_temp = create_buffer_with_data(
@@ -1143,11 +1343,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             std::mem::size_of::<Transforms>() as u64,
         );
 
-        let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-            &self.frame_buffer_view
-        } else {
-            frame_output.view()
-        };
+        let (color_attachment, depth_attachment) = self.blend_stack.attachments(
+            if self.descriptors.msaa_sample_count >= 2 {
+                &self.frame_buffer_view
+            } else {
+                frame_output.view()
+            },
+            &self.depth_texture_view,
+        );
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
                 attachment: color_attachment,
@@ -1158,7 +1361,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 },
             }],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
+                attachment: depth_attachment,
                 depth_ops: Some(wgpu::Operations {
                     load: wgpu::LoadOp::Load,
                     store: true,
@@ -1175,13 +1378,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         for draw in &mesh.draws {
             match &draw.draw_type {
-                DrawType::Color => {
+                // Only the fills of a shape contribute to a mask.
+                DrawType::Stroke
+                    if matches!(
+                        self.mask_state,
+                        MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+                    ) =>
+                {
+                    continue;
+                }
+                DrawType::Color | DrawType::Stroke => {
                     render_pass.set_pipeline(
                         &self
                             .descriptors
                             .pipelines
                             .color_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(blend, self.mask_state),
                     );
                 }
                 DrawType::Gradient { bind_group, .. } => {
@@ -1190,7 +1402,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .gradient_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(blend, self.mask_state),
                     );
                     render_pass.set_bind_group(2, bind_group, &[]);
                 }
@@ -1205,14 +1417,15 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .bitmap_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(blend, self.mask_state),
                     );
                     render_pass.set_bind_group(2, bind_group, &[]);
                     render_pass.set_bind_group(
                         3,
-                        self.descriptors
-                            .bitmap_samplers
-                            .get_bind_group(*is_repeating, *is_smoothed),
+                        self.descriptors.bitmap_samplers.get_bind_group(
+                            *is_repeating,
+                            *is_smoothed && self.quality.allows_bitmap_smoothing(),
+                        ),
                         &[],
                     );
                 }
@@ -1257,14 +1470,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             ],
         ];
 
-        let mult_color = [
-            f32::from(color.r) / 255.0,
-            f32::from(color.g) / 255.0,
-            f32::from(color.b) / 255.0,
-            f32::from(color.a) / 255.0,
-        ];
-
-        let add_color = [0.0, 0.0, 0.0, 0.0];
+        let blend = self.blend_stack.draw_blend();
+        let color_transform = blend.color_transform(ColorTransform {
+            r_mult: f32::from(color.r) / 255.0,
+            g_mult: f32::from(color.g) / 255.0,
+            b_mult: f32::from(color.b) / 255.0,
+            a_mult: f32::from(color.a) / 255.0,
+            ..Default::default()
+        });
 
         let transforms_ubo = create_buffer_with_data(
             &self.descriptors.device,
@@ -1275,10 +1488,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         let colors_ubo = create_buffer_with_data(
             &self.descriptors.device,
-            bytemuck::cast_slice(&[ColorAdjustments {
-                mult_color,
-                add_color,
-            }]),
+            bytemuck::cast_slice(&[ColorAdjustments::from(color_transform)]),
             wgpu::BufferUsage::UNIFORM,
             create_debug_label!("Rectangle colors transfer buffer"),
         );
@@ -1312,11 +1522,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 label: bind_group_label.as_deref(),
             });
 
-        let color_attachment = if self.descriptors.msaa_sample_count >= 2 {
-            &self.frame_buffer_view
-        } else {
-            frame_output.view()
-        };
+        let (color_attachment, depth_attachment) = self.blend_stack.attachments(
+            if self.descriptors.msaa_sample_count >= 2 {
+                &self.frame_buffer_view
+            } else {
+                frame_output.view()
+            },
+            &self.depth_texture_view,
+        );
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
                 attachment: color_attachment,
@@ -1327,7 +1540,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 },
             }],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
+                attachment: depth_attachment,
                 depth_ops: Some(wgpu::Operations {
                     load: wgpu::LoadOp::Load,
                     store: true,
@@ -1344,7 +1557,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 .descriptors
                 .pipelines
                 .color_pipelines
-                .pipeline_for(self.mask_state),
+                .pipeline_for(blend, self.mask_state),
         );
         render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
         render_pass.set_bind_group(1, &bind_group, &[]);
@@ -1488,6 +1701,97 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             MaskState::DrawMaskedContent
         };
     }
+
+    fn push_blend_mode(&mut self, blend: BlendMode) {
+        // Masks are drawn to the stencil buffer, so blend modes don't apply.
+        let blend = match self.mask_state {
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil => BlendMode::Normal,
+            _ => blend,
+        };
+
+        let is_offscreen = self.blend_stack.push(
+            &self.descriptors.device,
+            blend,
+            self.mask_state,
+            self.num_masks,
+            self.viewport_width as u32,
+            self.viewport_height as u32,
+            self.descriptors.msaa_sample_count,
+        );
+        if is_offscreen {
+            self.begin_offscreen_group();
+        }
+    }
+
+    fn pop_blend_mode(&mut self) {
+        let target = match self.blend_stack.pop() {
+            Some(group) if group.target.is_some() => {
+                self.mask_state = group.mask_state;
+                self.num_masks = group.num_masks;
+                group.target.unwrap()
+            }
+            _ => return,
+        };
+
+        let texture_view = self.end_offscreen_group(target);
+        self.composite_offscreen_group(&texture_view);
+    }
+
+    fn push_filters(&mut self, filters: &[swf::Filter], bounds: &swf::Rectangle) {
+        let group = FilterGroup::new(
+            filters,
+            bounds,
+            self.viewport_width as u32,
+            self.viewport_height as u32,
+        );
+        let group = match (group, self.mask_state) {
+            // Masks are drawn to the stencil buffer, so filters don't apply.
+            (Some(group), MaskState::NoMask) | (Some(group), MaskState::DrawMaskedContent) => group,
+            _ => {
+                // Push an empty group to be matched by `pop_filters`.
+                self.blend_stack.push(
+                    &self.descriptors.device,
+                    BlendMode::Normal,
+                    self.mask_state,
+                    self.num_masks,
+                    self.viewport_width as u32,
+                    self.viewport_height as u32,
+                    self.descriptors.msaa_sample_count,
+                );
+                return;
+            }
+        };
+
+        self.blend_stack.push_filters(
+            &self.descriptors.device,
+            group,
+            self.mask_state,
+            self.num_masks,
+            self.viewport_width as u32,
+            self.viewport_height as u32,
+            self.descriptors.msaa_sample_count,
+        );
+        self.begin_offscreen_group();
+    }
+
+    fn pop_filters(&mut self) {
+        let (target, filters) = match self.blend_stack.pop() {
+            Some(group) if group.target.is_some() => {
+                self.mask_state = group.mask_state;
+                self.num_masks = group.num_masks;
+                (group.target.unwrap(), group.filters)
+            }
+            _ => return,
+        };
+
+        let texture_view = self.end_offscreen_group(target);
+        let filtered = filters.and_then(|filters| self.apply_filters(&filters, &texture_view));
+        self.composite_offscreen_group(filtered.as_ref().unwrap_or(&texture_view));
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
 }
 
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
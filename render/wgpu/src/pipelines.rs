@@ -1,10 +1,20 @@
+use crate::blend::TrivialBlend;
+use crate::filters::{FilterBlend, BLUR_FORMAT, RESULT_FORMAT};
 use crate::{Error, GPUVertex, MaskState};
 use enum_map::{enum_map, EnumMap};
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
 pub struct ShapePipeline {
-    pub mask_pipelines: EnumMap<MaskState, wgpu::RenderPipeline>,
+    pub pipelines: EnumMap<TrivialBlend, EnumMap<MaskState, wgpu::RenderPipeline>>,
+}
+
+/// Pipelines that draw textures to the single-sampled targets used to apply
+/// filters, for each format of target.
+#[derive(Debug)]
+pub struct FilterPipelines {
+    pub blur_pipelines: EnumMap<FilterBlend, wgpu::RenderPipeline>,
+    pub result_pipelines: EnumMap<FilterBlend, wgpu::RenderPipeline>,
 }
 
 #[derive(Debug)]
@@ -18,11 +28,31 @@ pub struct Pipelines {
 
     pub gradient_pipelines: ShapePipeline,
     pub gradient_layout: wgpu::BindGroupLayout,
+
+    pub filter_pipelines: FilterPipelines,
 }
 
 impl ShapePipeline {
-    pub fn pipeline_for(&self, mask_state: MaskState) -> &wgpu::RenderPipeline {
-        &self.mask_pipelines[mask_state]
+    pub fn pipeline_for(
+        &self,
+        blend: TrivialBlend,
+        mask_state: MaskState,
+    ) -> &wgpu::RenderPipeline {
+        &self.pipelines[blend][mask_state]
+    }
+}
+
+impl FilterPipelines {
+    pub fn pipeline_for(
+        &self,
+        blend: FilterBlend,
+        format: wgpu::TextureFormat,
+    ) -> &wgpu::RenderPipeline {
+        if format == BLUR_FORMAT {
+            &self.blur_pipelines[blend]
+        } else {
+            &self.result_pipelines[blend]
+        }
     }
 }
 
@@ -78,14 +108,15 @@ impl Pipelines {
             label: mesh_bind_layout_label.as_deref(),
         });
 
-        let color_pipelines = create_color_pipelines(
+        let color_pipelines = create_shape_pipeline(
             &device,
+            "Color",
             &color_vs,
             &color_fs,
             msaa_sample_count,
             &vertex_buffers_description,
-            globals_layout,
-            &mesh_bind_layout,
+            &[globals_layout, &mesh_bind_layout],
+            false,
         );
 
         let bitmap_bind_layout_label = create_debug_label!("Bitmap shape bind group layout");
@@ -115,16 +146,34 @@ impl Pipelines {
                 label: bitmap_bind_layout_label.as_deref(),
             });
 
-        let bitmap_pipelines = create_bitmap_pipeline(
+        // Bitmaps use pre-multiplied alpha.
+        let bitmap_pipelines = create_shape_pipeline(
             &device,
+            "Bitmap",
             &texture_vs,
             &bitmap_fs,
             msaa_sample_count,
             &vertex_buffers_description,
-            sampler_layout,
-            globals_layout,
-            &mesh_bind_layout,
-            &bitmap_bind_layout,
+            &[
+                globals_layout,
+                &mesh_bind_layout,
+                &bitmap_bind_layout,
+                sampler_layout,
+            ],
+            true,
+        );
+
+        let filter_pipelines = create_filter_pipelines(
+            &device,
+            &texture_vs,
+            &bitmap_fs,
+            &vertex_buffers_description,
+            &[
+                globals_layout,
+                &mesh_bind_layout,
+                &bitmap_bind_layout,
+                sampler_layout,
+            ],
         );
 
         let gradient_bind_layout_label = create_debug_label!("Gradient shape bind group");
@@ -154,15 +203,15 @@ impl Pipelines {
                 label: gradient_bind_layout_label.as_deref(),
             });
 
-        let gradient_pipelines = create_gradient_pipeline(
+        let gradient_pipelines = create_shape_pipeline(
             &device,
+            "Gradient",
             &texture_vs,
             &gradient_fs,
             msaa_sample_count,
             &vertex_buffers_description,
-            globals_layout,
-            &mesh_bind_layout,
-            &gradient_bind_layout,
+            &[globals_layout, &mesh_bind_layout, &gradient_bind_layout],
+            false,
         );
 
         Ok(Self {
@@ -172,6 +221,7 @@ impl Pipelines {
             bitmap_layout: bitmap_bind_layout,
             gradient_pipelines,
             gradient_layout: gradient_bind_layout,
+            filter_pipelines,
         })
     }
 }
@@ -220,462 +270,84 @@ fn create_pipeline_descriptor<'a>(
     }
 }
 
-fn create_color_pipelines(
-    device: &wgpu::Device,
-    vertex_shader: &wgpu::ShaderModule,
-    fragment_shader: &wgpu::ShaderModule,
-    msaa_sample_count: u32,
-    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
-    globals_layout: &wgpu::BindGroupLayout,
-    mesh_bind_layout: &wgpu::BindGroupLayout,
-) -> ShapePipeline {
-    let pipeline_layout_label = create_debug_label!("Color shape pipeline layout");
-    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: pipeline_layout_label.as_deref(),
-        bind_group_layouts: &[globals_layout, &mesh_bind_layout],
-        push_constant_ranges: &[],
-    });
-
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline no mask").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-    };
-
-    ShapePipeline { mask_pipelines }
-}
-
 #[allow(clippy::too_many_arguments)]
-fn create_bitmap_pipeline(
+fn create_shape_pipeline(
     device: &wgpu::Device,
+    name: &str,
     vertex_shader: &wgpu::ShaderModule,
     fragment_shader: &wgpu::ShaderModule,
     msaa_sample_count: u32,
     vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
-    sampler_layout: &wgpu::BindGroupLayout,
-    globals_layout: &wgpu::BindGroupLayout,
-    mesh_bind_layout: &wgpu::BindGroupLayout,
-    bitmap_bind_layout: &wgpu::BindGroupLayout,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    premultiplied: bool,
 ) -> ShapePipeline {
-    let pipeline_layout_label = create_debug_label!("Bitmap shape pipeline layout");
+    let pipeline_layout_label = create_debug_label!("{} shape pipeline layout", name);
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: pipeline_layout_label.as_deref(),
-        bind_group_layouts: &[
-            globals_layout,
-            mesh_bind_layout,
-            bitmap_bind_layout,
-            sampler_layout,
-        ],
+        bind_group_layouts,
         push_constant_ranges: &[],
     });
 
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline no mask").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::One,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Equal,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::One,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
+    let pipelines: EnumMap<TrivialBlend, EnumMap<MaskState, wgpu::RenderPipeline>> = enum_map! {
+        blend => enum_map! {
+            mask_state => {
+                let (stencil, write_mask) = mask_render_state(mask_state);
+                device.create_render_pipeline(&create_pipeline_descriptor(
+                    create_debug_label!("{} pipeline {:?} {:?}", name, blend, mask_state)
+                        .as_deref(),
+                    vertex_shader,
+                    fragment_shader,
+                    &pipeline_layout,
+                    Some(wgpu::DepthStencilStateDescriptor {
+                        format: wgpu::TextureFormat::Depth24PlusStencil8,
+                        depth_write_enabled: true,
+                        depth_compare: wgpu::CompareFunction::Always,
+                        stencil,
+                    }),
+                    &[blend.color_state(premultiplied, write_mask)],
+                    vertex_buffers_description,
+                    msaa_sample_count,
+                ))
+            }
         }
     };
 
-    ShapePipeline { mask_pipelines }
+    ShapePipeline { pipelines }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn create_gradient_pipeline(
+fn create_filter_pipelines(
     device: &wgpu::Device,
     vertex_shader: &wgpu::ShaderModule,
     fragment_shader: &wgpu::ShaderModule,
-    msaa_sample_count: u32,
     vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
-    globals_layout: &wgpu::BindGroupLayout,
-    mesh_bind_layout: &wgpu::BindGroupLayout,
-    gradient_bind_layout: &wgpu::BindGroupLayout,
-) -> ShapePipeline {
-    let pipeline_layout_label = create_debug_label!("Gradient shape pipeline layout");
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+) -> FilterPipelines {
+    let pipeline_layout_label = create_debug_label!("Filter pipeline layout");
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: pipeline_layout_label.as_deref(),
-        bind_group_layouts: &[globals_layout, mesh_bind_layout, gradient_bind_layout],
+        bind_group_layouts,
         push_constant_ranges: &[],
     });
 
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline no mask").as_deref(),
+    let create_pipelines = |format: wgpu::TextureFormat| {
+        enum_map! {
+            blend => device.create_render_pipeline(&create_pipeline_descriptor(
+                create_debug_label!("Filter pipeline {:?} {:?}", blend, format).as_deref(),
                 vertex_shader,
                 fragment_shader,
                 &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
+                None,
+                &[FilterBlend::color_state(blend, format)],
                 vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Equal,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilStateDescriptor {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                }),
-                &[wgpu::ColorStateDescriptor {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
+                1,
             ))
         }
     };
 
-    ShapePipeline { mask_pipelines }
+    FilterPipelines {
+        blur_pipelines: create_pipelines(BLUR_FORMAT),
+        result_pipelines: create_pipelines(RESULT_FORMAT),
+    }
 }
 
 fn mask_render_state(state: MaskState) -> (wgpu::StencilStateDescriptor, wgpu::ColorWrite) {
@@ -42,6 +42,7 @@ pub struct Draw {
 #[derive(Debug)]
 pub enum DrawType {
     Color,
+    Stroke,
     Gradient {
         texture_transforms: wgpu::Buffer,
         gradient: wgpu::Buffer,
@@ -61,6 +62,7 @@ pub enum DrawType {
 #[allow(clippy::large_enum_variant)]
 pub enum IncompleteDrawType {
     Color,
+    Stroke,
     Gradient {
         texture_transform: [[f32; 4]; 4],
         gradient: GradientUniforms,
@@ -78,6 +80,7 @@ impl IncompleteDrawType {
     pub fn name(&self) -> &'static str {
         match self {
             IncompleteDrawType::Color => "Color",
+            IncompleteDrawType::Stroke => "Stroke",
             IncompleteDrawType::Gradient { .. } => "Gradient",
             IncompleteDrawType::Bitmap { .. } => "Bitmap",
         }
@@ -101,6 +104,12 @@ impl IncompleteDrawType {
                 index_buffer,
                 index_count,
             },
+            IncompleteDrawType::Stroke => Draw {
+                draw_type: DrawType::Stroke,
+                vertex_buffer,
+                index_buffer,
+                index_count,
+            },
             IncompleteDrawType::Gradient {
                 texture_transform,
                 gradient,
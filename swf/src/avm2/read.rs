@@ -101,8 +101,8 @@ impl<R: Read> Reader<R> {
     }
 
     fn read_i24(&mut self) -> Result<i32> {
-        Ok(i32::from(self.read_u8()? as i8)
-            | (i32::from(self.read_u8()? as i8) << 8)
+        Ok(i32::from(self.read_u8()?)
+            | (i32::from(self.read_u8()?) << 8)
             | (i32::from(self.read_u8()? as i8) << 16))
     }
     fn read_i32(&mut self) -> Result<i32> {
@@ -901,4 +901,18 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn read_branch_offsets() {
+        let ops = [
+            (vec![0x10, 0xa0, 0x00, 0x00], Op::Jump { offset: 160 }),
+            (vec![0x11, 0x00, 0x01, 0x00], Op::IfTrue { offset: 256 }),
+            (vec![0x12, 0xfe, 0xff, 0xff], Op::IfFalse { offset: -2 }),
+            (vec![0x14, 0x60, 0xff, 0xff], Op::IfNe { offset: -160 }),
+        ];
+        for (bytes, op) in ops.iter() {
+            let mut reader = Reader::new(&bytes[..]);
+            assert_eq!(reader.read_op().unwrap(), Some(op.clone()));
+        }
+    }
 }
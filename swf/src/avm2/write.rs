@@ -78,9 +78,9 @@ impl<W: Write> Writer<W> {
     #[allow(dead_code)]
     fn write_i24(&mut self, n: i32) -> Result<()> {
         // TODO: Verify n fits in 24-bits.
-        self.write_u8(((n >> 16) & 0xff) as u8)?;
-        self.write_u8(((n >> 8) & 0xff) as u8)?;
         self.write_u8((n & 0xff) as u8)?;
+        self.write_u8(((n >> 8) & 0xff) as u8)?;
+        self.write_u8(((n >> 16) & 0xff) as u8)?;
         Ok(())
     }
 
@@ -988,4 +988,17 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn write_branch_offsets() {
+        let ops = [
+            (Op::Jump { offset: 160 }, vec![0x10, 0xa0, 0x00, 0x00]),
+            (Op::IfFalse { offset: -2 }, vec![0x12, 0xfe, 0xff, 0xff]),
+        ];
+        for (op, bytes) in ops.iter() {
+            let mut out = vec![];
+            Writer::new(&mut out).write_op(op).unwrap();
+            assert_eq!(&out, bytes);
+        }
+    }
 }
@@ -32,12 +32,11 @@ pub fn read_swf<R: Read>(input: R) -> Result<Swf> {
 
     // Decompress all of SWF into memory at once.
     let mut data = if header.compression == Compression::Lzma {
-        // TODO: The LZMA decoder is still funky.
-        // It always errors, and doesn't return all the data if you use read_to_end,
-        // but read_exact at least returns the data... why?
-        // Does the decoder need to be flushed somehow?
+        // The LZMA stream may or may not have an end marker, and the decoder
+        // can error after returning all of the data. Read exactly the
+        // uncompressed length instead, which a truncated stream fails to fill.
         let mut data = vec![0u8; swf_stream.uncompressed_length];
-        let _ = reader.get_mut().read_exact(&mut data);
+        reader.get_mut().read_exact(&mut data)?;
         data
     } else {
         let mut data = Vec::with_capacity(swf_stream.uncompressed_length);
@@ -85,7 +84,10 @@ pub fn read_swf_header<'a, R: Read + 'a>(mut input: R) -> Result<SwfStream<'a>>
 
     // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
     // subtract it here.
-    let uncompressed_length = input.read_u32::<LittleEndian>()? - 8;
+    let uncompressed_length = input
+        .read_u32::<LittleEndian>()?
+        .checked_sub(8)
+        .ok_or_else(|| Error::invalid_data("Invalid uncompressed length in SWF header"))?;
 
     // Now the SWF switches to a compressed stream.
     let decompressed_input: Box<dyn Read> = match compression {
@@ -186,10 +188,11 @@ fn make_lzma_reader<'a, R: Read + 'a>(
     lzma_header.write_u64::<LittleEndian>(uncompressed_length.into())?;
 
     // Create LZMA decoder stream and write header
-    let mut lzma_stream = Stream::new_lzma_decoder(u64::max_value()).unwrap();
+    let mut lzma_stream = Stream::new_lzma_decoder(u64::max_value())
+        .map_err(|_| Error::unsupported("Unable to create LZMA decoder"))?;
     lzma_stream
         .process(&lzma_header.into_inner(), &mut [0u8; 1], Action::Run)
-        .unwrap();
+        .map_err(|_| Error::invalid_data("Invalid LZMA properties in SWF header"))?;
 
     // Decoder is ready
     Ok(Box::new(XzDecoder::new_stream(input, lzma_stream)))
@@ -390,7 +393,9 @@ impl<R: Read> Reader<R> {
         let tag = match TagCode::from_u16(tag_code) {
             Some(TagCode::End) => Tag::End,
             Some(TagCode::ShowFrame) => Tag::ShowFrame,
-            Some(TagCode::CsmTextSettings) => tag_reader.read_csm_text_settings()?,
+            Some(TagCode::CsmTextSettings) => {
+                Tag::CsmTextSettings(tag_reader.read_csm_text_settings()?)
+            }
             Some(TagCode::DefineBinaryData) => {
                 let id = tag_reader.read_u16()?;
                 tag_reader.read_u32()?; // Reserved
@@ -458,7 +463,9 @@ impl<R: Read> Reader<R> {
             Some(TagCode::DefineText2) => {
                 Tag::DefineText(Box::new(tag_reader.read_define_text(2)?))
             }
-            Some(TagCode::DefineVideoStream) => tag_reader.read_define_video_stream()?,
+            Some(TagCode::DefineVideoStream) => {
+                Tag::DefineVideoStream(tag_reader.read_define_video_stream()?)
+            }
             Some(TagCode::EnableTelemetry) => {
                 tag_reader.read_u16()?; // Reserved
                 let password_hash = if length > 2 {
@@ -649,7 +656,7 @@ impl<R: Read> Reader<R> {
 
             Some(TagCode::RemoveObject2) => Tag::RemoveObject(tag_reader.read_remove_object_2()?),
 
-            Some(TagCode::VideoFrame) => tag_reader.read_video_frame()?,
+            Some(TagCode::VideoFrame) => Tag::VideoFrame(tag_reader.read_video_frame()?),
             Some(TagCode::ProductInfo) => Tag::ProductInfo(tag_reader.read_product_info()?),
             _ => {
                 let size = length as usize;
@@ -1108,13 +1115,13 @@ impl<R: Read> Reader<R> {
         ))
     }
 
-    fn read_csm_text_settings(&mut self) -> Result<Tag> {
+    pub fn read_csm_text_settings(&mut self) -> Result<CsmTextSettings> {
         let id = self.read_character_id()?;
         let flags = self.read_u8()?;
         let thickness = self.read_f32()?;
         let sharpness = self.read_f32()?;
         self.read_u8()?; // Reserved (0).
-        Ok(Tag::CsmTextSettings(CsmTextSettings {
+        Ok(CsmTextSettings {
             id,
             use_advanced_rendering: flags & 0b01000000 != 0,
             grid_fit: match flags & 0b11_000 {
@@ -1125,7 +1132,7 @@ impl<R: Read> Reader<R> {
             },
             thickness,
             sharpness,
-        }))
+        })
     }
 
     pub fn read_frame_label(&mut self, length: usize) -> Result<FrameLabel> {
@@ -1287,7 +1294,17 @@ impl<R: Read> Reader<R> {
                 glyph.bounds = Some(self.read_rectangle()?);
             }
 
-            let num_kerning_records = self.read_u16()? as usize;
+            // Some SWFs end the tag before the kerning table, so a missing
+            // table is treated as having no kerning records. A table cut off
+            // partway through is still an error.
+            self.byte_align();
+            let mut num_kerning_records = [0; 2];
+            let num_kerning_records = if self.get_mut().read(&mut num_kerning_records[..1])? == 0 {
+                0
+            } else {
+                self.get_mut().read_exact(&mut num_kerning_records[1..])?;
+                u16::from_le_bytes(num_kerning_records) as usize
+            };
             let mut kerning_records = Vec::with_capacity(num_kerning_records);
             for _ in 0..num_kerning_records {
                 kerning_records.push(self.read_kerning_record(has_wide_codes)?);
@@ -1355,7 +1372,11 @@ impl<R: Read> Reader<R> {
         })
     }
 
-    fn read_define_font_align_zones(&mut self) -> Result<Tag> {
+    /// Reads the body of a `DefineFontAlignZones` tag.
+    ///
+    /// Zones are read until the end of the input, so the reader must be limited
+    /// to the length of the tag.
+    pub fn read_define_font_align_zones(&mut self) -> Result<Tag> {
         let id = self.read_character_id()?;
         let thickness = match self.read_u8()? {
             0b00_000000 => FontThickness::Thin,
@@ -2387,7 +2408,9 @@ impl<R: Read> Reader<R> {
                 strength: self.read_fixed8()?,
                 is_inner: self.read_bit()?,
                 is_knockout: self.read_bit()?,
-                num_passes: self.read_ubits(6)? as u8 & 0b011111,
+                // The composite source flag is cleared when the object is hidden.
+                hide_object: !self.read_bit()?,
+                num_passes: self.read_ubits(5)? as u8,
             })),
             1 => Filter::BlurFilter(Box::new(BlurFilter {
                 blur_x: self.read_fixed16()?,
@@ -2746,7 +2769,7 @@ impl<R: Read> Reader<R> {
         })
     }
 
-    fn read_define_video_stream(&mut self) -> Result<Tag> {
+    pub fn read_define_video_stream(&mut self) -> Result<DefineVideoStream> {
         let id = self.read_character_id()?;
         let num_frames = self.read_u16()?;
         let width = self.read_u16()?;
@@ -2760,7 +2783,7 @@ impl<R: Read> Reader<R> {
             5 => VideoCodec::VP6WithAlpha,
             _ => return Err(Error::invalid_data("Invalid video codec.")),
         };
-        Ok(Tag::DefineVideoStream(DefineVideoStream {
+        Ok(DefineVideoStream {
             id,
             num_frames,
             width,
@@ -2776,19 +2799,19 @@ impl<R: Read> Reader<R> {
                 0b101_0 => VideoDeblocking::Level4,
                 _ => return Err(Error::invalid_data("Invalid video deblocking value.")),
             },
-        }))
+        })
     }
 
-    fn read_video_frame(&mut self) -> Result<Tag> {
+    pub fn read_video_frame(&mut self) -> Result<VideoFrame> {
         let stream_id = self.read_character_id()?;
         let frame_num = self.read_u16()?;
         let mut data = vec![];
         self.input.read_to_end(&mut data)?;
-        Ok(Tag::VideoFrame(VideoFrame {
+        Ok(VideoFrame {
             stream_id,
             frame_num,
             data,
-        }))
+        })
     }
 
     fn read_define_bits_jpeg_3(&mut self, version: u8) -> Result<Tag> {
@@ -2964,6 +2987,39 @@ pub mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn read_lzma_swf() {
+        let uncompressed = read_from_file("tests/swfs/uncompressed.swf");
+        let lzma = read_from_file("tests/swfs/lzma.swf");
+        assert_eq!(lzma.header.compression, Compression::Lzma);
+        assert_eq!(lzma.header.num_frames, uncompressed.header.num_frames);
+        assert_eq!(lzma.tags, uncompressed.tags);
+    }
+
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn read_truncated_lzma_swf() {
+        let data = std::fs::read("tests/swfs/lzma.swf").unwrap();
+        assert!(read_swf(&data[..data.len() / 2]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn read_lzma_swf_with_invalid_properties() {
+        // ZWS header followed by the compressed length and LZMA properties
+        // with an out-of-range lc/lp/pb byte.
+        let data = b"ZWS\x0d\x20\x00\x00\x00\x10\x00\x00\x00\xff\x00\x00\x01\x00\x00\x00\x00\x00";
+        assert!(read_swf_header(&data[..]).is_err());
+    }
+
+    #[test]
+    fn read_swf_with_invalid_length() {
+        // The uncompressed length must at least cover the 8-byte file header.
+        assert!(read_swf_header(&b"FWS\x0a\x04\x00\x00\x00"[..]).is_err());
+        assert!(read_swf_header(&b"ZWS\x0d\x00\x00\x00\x00"[..]).is_err());
+    }
+
     #[test]
     fn read_invalid_swf() {
         let junk = [0u8; 128];
@@ -3315,6 +3371,61 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn read_define_font_2_without_kerning_table() {
+        // A DefineFont2 with layout info for a single empty glyph, where the
+        // tag ends right after the bounds table.
+        let font = reader(
+            b"\x01\x00\x80\x00\x04Test\x01\x00\x04\x00\x06\x00\x10\x00A\x00\x04\x00\x01\x00\x00\x00\x02\x00",
+        )
+        .read_define_font_2(2)
+        .unwrap();
+
+        assert_eq!(font.glyphs[0].code, u16::from(b'A'));
+        assert_eq!(font.glyphs[0].advance, Some(512));
+        assert_eq!(
+            font.layout,
+            Some(FontLayout {
+                ascent: 1024,
+                descent: 256,
+                leading: 0,
+                kerning: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn read_define_font_2_with_truncated_kerning_table() {
+        // As above, but with a single byte of the kerning record count.
+        let result = reader(
+            b"\x01\x00\x80\x00\x04Test\x01\x00\x04\x00\x06\x00\x10\x00A\x00\x04\x00\x01\x00\x00\x00\x02\x00\x01",
+        )
+        .read_define_font_2(2);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn read_define_font_align_zones_body() {
+        let tag_bytes = [1, 0, 0b01_000000, 2, 0x2A, 0x33, 0, 0, 0, 0, 0xCE, 0x42, 3];
+        let tag = Reader::new(&tag_bytes[..], 8)
+            .read_define_font_align_zones()
+            .unwrap();
+        assert_eq!(
+            tag,
+            Tag::DefineFontAlignZones {
+                id: 1,
+                thickness: FontThickness::Medium,
+                zones: vec![FontAlignZone {
+                    left: 13098,
+                    width: 0,
+                    bottom: 0,
+                    height: 17102,
+                }],
+            }
+        );
+    }
+
     /// Ensure that we return an error on invalid data.
     #[test]
     fn read_invalid_tag() {
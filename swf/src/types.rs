@@ -44,7 +44,7 @@ pub struct Header {
 ///
 /// The vast majority of SWFs will use zlib compression.
 /// [SWF19 p.27](https://www.adobe.com/content/dam/acom/en/devnet/pdf/swf-file-format-spec.pdf#page=27)
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Compression {
     None,
     Zlib,
@@ -255,7 +255,7 @@ pub struct FrameLabelData {
 pub type Depth = u16;
 pub type CharacterId = u16;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct PlaceObject {
     pub version: u8,
     pub action: PlaceObjectAction,
@@ -305,6 +305,7 @@ pub struct DropShadowFilter {
     pub strength: f32,
     pub is_inner: bool,
     pub is_knockout: bool,
+    pub hide_object: bool,
     pub num_passes: u8,
 }
 
@@ -2082,7 +2082,7 @@ impl<W: Write> Writer<W> {
                 self.write_fixed8(drop_shadow.strength)?;
                 self.write_bit(drop_shadow.is_inner)?;
                 self.write_bit(drop_shadow.is_knockout)?;
-                self.write_bit(true)?;
+                self.write_bit(!drop_shadow.hide_object)?;
                 self.write_ubits(5, drop_shadow.num_passes.into())?;
             }
 
@@ -1,6 +1,7 @@
 use fnv::FnvHashMap;
 use generational_arena::Arena;
-use ruffle_core::backend::audio::decoders::{AdpcmDecoder, Mp3Decoder};
+use ruffle_core::backend::audio::decoders::{self, AdpcmDecoder, Decoder as _, Mp3Decoder};
+use ruffle_core::backend::audio::sound_info::SoundRange;
 use ruffle_core::backend::audio::swf::{self, AudioCompression};
 use ruffle_core::backend::audio::{
     AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
@@ -86,6 +87,10 @@ struct SoundInstance {
     /// either decoded on the fly with Decoder, or pre-decoded
     /// and played with and AudioBufferSourceNode.
     instance_type: SoundInstanceType,
+
+    /// The time of the audio context, in seconds, when this sound started
+    /// playing.
+    start_time: f64,
 }
 
 /// The Drop impl ensures that the sound is stopped and remove from the audio context,
@@ -175,31 +180,30 @@ impl WebAudioBackend {
                             || settings.envelope.is_some() =>
                     {
                         // Event sound with non-default parameters.
-                        // Note that start/end values are in 44.1kHZ samples regardless of the sound's sample rate.
-                        let start_sample_frame = f64::from(settings.in_sample.unwrap_or(0))
-                            / 44100.0
-                            + f64::from(sound.skip_sample_frames) / sound_sample_rate;
-                        node.set_loop(settings.num_loops > 1);
+                        let range = SoundRange::new(
+                            settings,
+                            sound.format.sample_rate,
+                            sound.num_sample_frames,
+                            sound.skip_sample_frames,
+                        );
+                        let start_sample_frame =
+                            f64::from(range.start_sample_frame) / sound_sample_rate;
+                        let end_sample_frame =
+                            f64::from(range.end_sample_frame) / sound_sample_rate;
+                        node.set_loop(range.num_loops > 1);
                         node.set_loop_start(start_sample_frame);
                         node.start_with_when_and_grain_offset(0.0, start_sample_frame)
                             .warn_on_error();
 
                         let current_time = self.context.current_time();
 
-                        // The length of the sound in the swf, or by the script playing it, doesn't
-                        // always line up with the actual length of the sound.
-                        // Always set a custom end point to make sure we're correct.
-                        let end_sample_frame = if let Some(out_sample) = settings.out_sample {
-                            f64::from(out_sample) / 44100.0
-                        } else {
-                            f64::from(sound.num_sample_frames + u32::from(sound.skip_sample_frames))
-                                / sound_sample_rate
-                        };
                         // `AudioSourceBufferNode.loop` is a bool, so we have to stop the loop at the proper time.
                         // `start_with_when_and_grain_offset_and_grain_duration` unfortunately doesn't work
                         // as you might expect with loops, so we use `stop_with_when` to stop the loop.
-                        let total_len =
-                            (end_sample_frame - start_sample_frame) * f64::from(settings.num_loops);
+                        let total_len = range.total_sample_frames() as f64 / sound_sample_rate;
+                        // The length of the sound in the swf, or by the script playing it, doesn't
+                        // always line up with the actual length of the sound.
+                        // Always set a custom end point to make sure we're correct.
                         node.set_loop_end(end_sample_frame);
                         node.stop_with_when(current_time + total_len)
                             .warn_on_error();
@@ -235,6 +239,7 @@ impl WebAudioBackend {
                         node,
                         buffer_source_node: buffer_source_node.clone(),
                     },
+                    start_time: self.context.current_time(),
                 };
                 let instance_handle = SOUND_INSTANCES.with(|instances| {
                     let mut instances = instances.borrow_mut();
@@ -287,6 +292,7 @@ impl WebAudioBackend {
                     handle: Some(handle),
                     format: sound.format.clone(),
                     instance_type: SoundInstanceType::Decoder(decoder),
+                    start_time: self.context.current_time(),
                 };
                 SOUND_INSTANCES.with(|instances| {
                     let mut instances = instances.borrow_mut();
@@ -403,6 +409,7 @@ impl WebAudioBackend {
         self.left_samples.clear();
         self.right_samples.clear();
 
+        let mut sample_rate = format.sample_rate;
         match format.compression {
             AudioCompression::Uncompressed | AudioCompression::UncompressedUnknownEndian => {
                 use byteorder::{LittleEndian, ReadBytesExt};
@@ -448,13 +455,23 @@ impl WebAudioBackend {
                     }
                 }
             }
+            AudioCompression::Nellymoser16Khz
+            | AudioCompression::Nellymoser8Khz
+            | AudioCompression::Nellymoser => {
+                // Nellymoser is always mono, and may decode at a rate that
+                // isn't in the sound format.
+                let decoder = decoders::make_decoder(format, audio_data)?;
+                sample_rate = decoder.sample_rate();
+                self.left_samples
+                    .extend(decoder.map(|n| f32::from(n[0]) / 32767.0));
+            }
             compression => return Err(format!("Unimplemented codec: {:?}", compression).into()),
         }
 
         // This sucks. Firefox and Safari don't like low sample rates,
         // so manually multiply the samples.
-        let sample_rate = if format.sample_rate < self.min_sample_rate {
-            let sample_multiplier = self.min_sample_rate / format.sample_rate;
+        let sample_rate = if sample_rate < self.min_sample_rate {
+            let sample_multiplier = self.min_sample_rate / sample_rate;
             let mut samples = Vec::with_capacity(self.left_samples.len() * 2);
             for sample in &self.left_samples {
                 for _ in 0..sample_multiplier {
@@ -475,7 +492,7 @@ impl WebAudioBackend {
 
             self.min_sample_rate
         } else {
-            format.sample_rate
+            sample_rate
         };
 
         let num_sample_frames = self.left_samples.len() as u32;
@@ -679,6 +696,12 @@ impl AudioBackend for WebAudioBackend {
                     stream.adpcm_block_offsets.push(stream.audio_data.len());
                     stream.audio_data.extend_from_slice(audio_data);
                 }
+                AudioCompression::Nellymoser16Khz
+                | AudioCompression::Nellymoser8Khz
+                | AudioCompression::Nellymoser => {
+                    stream.num_sample_frames += stream.samples_per_block;
+                    stream.audio_data.extend_from_slice(audio_data);
+                }
                 _ => {
                     // TODO: This is a guess and will vary slightly from block to block!
                     stream.num_sample_frames += stream.samples_per_block;
@@ -788,6 +811,23 @@ impl AudioBackend for WebAudioBackend {
         })
     }
 
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+        SOUND_INSTANCES.with(|instances| {
+            let instances = instances.borrow();
+            instances.contains(instance)
+        })
+    }
+
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<f64> {
+        let current_time = self.context.current_time();
+        SOUND_INSTANCES.with(|instances| {
+            let instances = instances.borrow();
+            instances
+                .get(instance)
+                .map(|instance| (current_time - instance.start_time) * 1000.0)
+        })
+    }
+
     fn is_loading_complete(&self) -> bool {
         NUM_SOUNDS_LOADING.with(|n| n.get() == 0)
     }
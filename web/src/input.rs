@@ -1,4 +1,4 @@
-use ruffle_core::backend::input::{InputBackend, MouseCursor};
+use ruffle_core::backend::input::{InputBackend, MouseButton, MouseCursor};
 use ruffle_core::events::KeyCode;
 use ruffle_web_common::JsResult;
 use std::collections::HashSet;
@@ -66,6 +66,7 @@ impl InputBackend for WebInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains("Backspace"),
+            KeyCode::Tab => self.keys_down.contains("Tab"),
             KeyCode::Return => self.keys_down.contains("Enter"),
             KeyCode::Shift => {
                 self.keys_down.contains("ShiftLeft") || self.keys_down.contains("ShiftRight")
@@ -200,11 +201,23 @@ impl InputBackend for WebInputBackend {
     }
 }
 
+/// Converts a Web `MouseEvent.button` value into a Ruffle `MouseButton`.
+/// Returns `None` if there is no matching Flash mouse button.
+pub fn web_to_ruffle_mouse_button(button: i16) -> Option<MouseButton> {
+    match button {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    }
+}
+
 /// Converts a Web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
 /// Returns `None` if there is no matching Flash key key.
 pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
     let out = match key_code {
         "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
         "Enter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,
@@ -7,12 +7,13 @@ mod locale;
 mod log_adapter;
 mod navigator;
 mod storage;
+mod ui;
 
 use crate::log_adapter::WebLogBackend;
 use crate::storage::LocalStorageBackend;
 use crate::{
     audio::WebAudioBackend, input::WebInputBackend, locale::WebLocaleBackend,
-    navigator::WebNavigatorBackend,
+    navigator::WebNavigatorBackend, ui::WebUiBackend,
 };
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Uint8Array};
@@ -20,6 +21,7 @@ use ruffle_core::backend::input::InputBackend;
 use ruffle_core::backend::render::RenderBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::context::UpdateContext;
 use ruffle_core::events::{KeyCode, MouseWheelDelta};
 use ruffle_core::external::{
@@ -95,6 +97,18 @@ extern "C" {
 
     #[wasm_bindgen(method)]
     fn panic(this: &JavascriptPlayer, error: &JsError);
+
+    #[wasm_bindgen(method, getter, js_name = "fullscreenEnabled")]
+    fn fullscreen_enabled(this: &JavascriptPlayer) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = "isFullscreen")]
+    fn is_fullscreen(this: &JavascriptPlayer) -> bool;
+
+    #[wasm_bindgen(method, js_name = "enterFullscreen")]
+    fn enter_fullscreen(this: &JavascriptPlayer);
+
+    #[wasm_bindgen(method, js_name = "exitFullscreen")]
+    fn exit_fullscreen(this: &JavascriptPlayer);
 }
 
 struct JavascriptInterface {
@@ -314,6 +328,8 @@ impl Ruffle {
 
         let trace_observer = Arc::new(RefCell::new(JsValue::UNDEFINED));
         let log = Box::new(WebLogBackend::new(trace_observer.clone()));
+        let ui = Box::new(WebUiBackend::new(js_player.clone()));
+        let video = Box::new(SoftwareVideoBackend::new());
 
         let core = ruffle_core::Player::new(
             renderer,
@@ -323,6 +339,8 @@ impl Ruffle {
             local_storage,
             locale,
             log,
+            ui,
+            video,
         )?;
 
         // Create instance.
@@ -416,8 +434,9 @@ impl Ruffle {
                     INSTANCES.with(move |instances| {
                         let instances = instances.borrow();
                         if let Some(instance) = instances.get(index) {
-                            // Only fire player mouse event for left clicks.
-                            if js_event.button() == 0 {
+                            if let Some(button) =
+                                input::web_to_ruffle_mouse_button(js_event.button())
+                            {
                                 if let Some(target) = js_event.current_target() {
                                     let _ = target
                                         .unchecked_ref::<Element>()
@@ -427,6 +446,7 @@ impl Ruffle {
                                 let event = PlayerEvent::MouseDown {
                                     x: f64::from(js_event.offset_x()) * device_pixel_ratio,
                                     y: f64::from(js_event.offset_y()) * device_pixel_ratio,
+                                    button,
                                 };
                                 instance.borrow().core.lock().unwrap().handle_event(event);
                             }
@@ -504,8 +524,9 @@ impl Ruffle {
                         if let Some(instance) = instances.get(index) {
                             let instance = instance.borrow();
 
-                            // Only fire player mouse event for left clicks.
-                            if js_event.button() == 0 {
+                            if let Some(button) =
+                                input::web_to_ruffle_mouse_button(js_event.button())
+                            {
                                 if let Some(target) = js_event.current_target() {
                                     let _ = target
                                         .unchecked_ref::<Element>()
@@ -514,6 +535,7 @@ impl Ruffle {
                                 let event = PlayerEvent::MouseUp {
                                     x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
                                     y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                                    button,
                                 };
                                 instance.core.lock().unwrap().handle_event(event);
                             }
@@ -10,7 +10,9 @@ use std::borrow::Cow;
 use std::time::Duration;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
-use web_sys::{window, Blob, BlobPropertyBag, Performance, Request, RequestInit, Response};
+use web_sys::{
+    window, Blob, BlobPropertyBag, Headers, Performance, Request, RequestInit, Response,
+};
 
 pub struct WebNavigatorBackend {
     performance: Performance,
@@ -127,6 +129,16 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !options.headers().is_empty() {
+                let headers = Headers::new().unwrap();
+                for (name, value) in options.headers() {
+                    if headers.append(name, value).is_err() {
+                        log::warn!("Invalid request header {}", name);
+                    }
+                }
+                init.headers(&headers);
+            }
+
             let request = Request::new_with_str_and_init(&url, &init).unwrap();
 
             let window = web_sys::window().unwrap();
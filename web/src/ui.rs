@@ -0,0 +1,67 @@
+use crate::JavascriptPlayer;
+use ruffle_core::backend::ui::{ContextMenuItem, FileFilter, SelectedFile, UiBackend};
+use std::error::Error;
+
+/// UI backend for the web.
+///
+/// Browsers only offer asynchronous file pickers, which cannot yet be driven
+/// from the player, so all dialogs are currently cancelled.
+pub struct WebUiBackend {
+    js_player: JavascriptPlayer,
+}
+
+impl WebUiBackend {
+    pub fn new(js_player: JavascriptPlayer) -> Self {
+        Self { js_player }
+    }
+}
+
+impl UiBackend for WebUiBackend {
+    fn display_file_open_dialog(&mut self, _filters: &[FileFilter]) -> Option<SelectedFile> {
+        log::warn!("File dialogs are not yet supported on web");
+        None
+    }
+
+    fn load_file(&mut self, _file: &SelectedFile) -> Result<Vec<u8>, Box<dyn Error>> {
+        Err("Loading local files is not yet supported on web".into())
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _default_name: &str,
+        _data: &[u8],
+    ) -> Result<Option<SelectedFile>, Box<dyn Error>> {
+        log::warn!("File dialogs are not yet supported on web");
+        Ok(None)
+    }
+
+    fn load_device_font(
+        &mut self,
+        _name: &str,
+        _is_bold: bool,
+        _is_italic: bool,
+    ) -> Option<Vec<u8>> {
+        // Browsers do not expose the data of installed fonts.
+        None
+    }
+
+    fn build_context_menu(&mut self, _items: &[ContextMenuItem]) {
+        // TODO: Add these items to the context menu of the web player.
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.js_player.is_fullscreen()
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Box<dyn Error>> {
+        if is_full {
+            if !self.js_player.fullscreen_enabled() {
+                return Err("Fullscreen is not allowed by the browser".into());
+            }
+            self.js_player.enter_fullscreen();
+        } else {
+            self.js_player.exit_fullscreen();
+        }
+        Ok(())
+    }
+}